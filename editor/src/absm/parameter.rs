@@ -147,10 +147,13 @@ impl ParameterPanel {
                             Log::verify(result);
                         },
                     );
-                } else {
-                    sender.send(Message::DoSceneCommand(
-                        make_set_parameters_property_command((), args, absm_node_handle).unwrap(),
-                    ));
+                } else if let Some(command) = make_set_parameters_property_command(
+                    (),
+                    absm_node.machine().parameters(),
+                    args,
+                    absm_node_handle,
+                ) {
+                    sender.send(Message::DoSceneCommand(command));
                 }
             }
         }
@@ -170,8 +170,13 @@ impl CameraController {
             aabb = AxisAlignedBoundingBox::from_point(scene.graph[handle].global_position());
         }
 
+        self.fit_aabb(scene, &aabb);
+    }
+
+    /// Moves the camera so that the given world-space bounds are fully visible.
+    pub fn fit_aabb(&self, scene: &mut Scene, aabb: &AxisAlignedBoundingBox) {
         let fit_parameters = scene.graph[self.camera].as_camera().fit(
-            &aabb,
+            aabb,
             scene
                 .render_target
                 .as_ref()
@@ -574,6 +579,59 @@ impl CameraController {
     }
 }
 
+/// Casts the given ray against every visible mesh of the scene (editor objects are ignored) and
+/// returns the closest intersection, if any. Unlike [`CameraController::pick`] this does not need
+/// a camera, so it could be used to probe the geometry from arbitrary points.
+pub fn ray_cast_scene_geometry<F>(
+    graph: &Graph,
+    ray: &Ray,
+    scene_content_root: Handle<Node>,
+    editor_objects_root: Handle<Node>,
+    ignore_back_faces: bool,
+    mut filter: F,
+) -> Option<CameraPickResult>
+where
+    F: FnMut(Handle<Node>, &Node) -> bool,
+{
+    let mut closest: Option<CameraPickResult> = None;
+
+    let mut stack = vec![scene_content_root];
+    while let Some(handle) = stack.pop() {
+        if handle == editor_objects_root {
+            continue;
+        }
+
+        let node = &graph[handle];
+
+        stack.extend_from_slice(node.children());
+
+        if !node.global_visibility() || !has_hull(node) || !filter(handle, node) {
+            continue;
+        }
+
+        let object_space_ray =
+            ray.transform(node.global_transform().try_inverse().unwrap_or_default());
+        if object_space_ray
+            .aabb_intersection_points(&node.local_bounding_box())
+            .is_none()
+        {
+            continue;
+        }
+
+        if let Some((toi, position)) = precise_ray_test(node, ray, ignore_back_faces) {
+            if closest.as_ref().map_or(true, |c| toi < c.toi) {
+                closest = Some(CameraPickResult {
+                    position,
+                    node: handle,
+                    toi,
+                });
+            }
+        }
+    }
+
+    closest
+}
+
 fn read_vertex_position(data: &SurfaceData, i: u32) -> Option<Vector3<f32>> {
     data.vertex_buffer
         .get(i as usize)
@@ -1,23 +1,172 @@
 use crate::scene::commands::SceneContext;
-use std::fmt::Debug;
+use std::{
+    any::Any,
+    fmt::{Debug, Display, Formatter},
+    time::Duration,
+};
 
 pub mod panel;
 pub mod universal;
 
+/// Commands, that are executed within this time after the previous command, could be merged with
+/// it. See `try_merge` of a command.
+pub const COMMAND_MERGE_WINDOW: Duration = Duration::from_secs(2);
+
+/// Amount of the largest commands listed in [`CommandStackStatistics`].
+pub const LARGEST_COMMANDS_COUNT: usize = 5;
+
+/// Limits of a command stack, the oldest commands are dropped from the stack when any of the
+/// limits is exceeded. The command on top of the stack is never dropped, so the last change could
+/// always be undone.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct CommandStackLimits {
+    /// Max amount of commands on the stack.
+    pub max_entries: usize,
+    /// Max total estimated size (in bytes) of the commands on the stack.
+    pub max_size: usize,
+}
+
+impl Default for CommandStackLimits {
+    fn default() -> Self {
+        Self {
+            max_entries: usize::MAX,
+            max_size: usize::MAX,
+        }
+    }
+}
+
+/// Memory usage of a command stack.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandStackStatistics {
+    /// Amount of commands on the stack.
+    pub count: usize,
+    /// Total estimated size (in bytes) of the commands on the stack.
+    pub size: usize,
+    /// Names and sizes of the largest commands on the stack, sorted by size in descending order.
+    pub largest: Vec<(String, usize)>,
+}
+
+impl Display for CommandStackStatistics {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Commands: {}, estimated size: {} bytes",
+            self.count, self.size
+        )?;
+        for (name, size) in self.largest.iter() {
+            write!(f, "\n\t{} - {} bytes", name, size)?;
+        }
+        Ok(())
+    }
+}
+
+/// Gives access to a command as [`Any`], so a command could check the concrete type of another
+/// command when they're merged.
+pub trait CommandAsAny {
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T: Any> CommandAsAny for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Resolves handles of entities of a context to their names, universal commands (see
+/// `define_universal_commands`) use the names to tell which entity they change.
+pub trait EntityNameProvider<C, H> {
+    /// Returns `None` if there's no such entity (for example, it was deleted).
+    fn entity_name(context: &C, handle: H) -> Option<String>;
+}
+
+/// Provides no names, commands that use it are named after changed properties only.
+pub struct NoEntityNames;
+
+impl<C, H> EntityNameProvider<C, H> for NoEntityNames {
+    fn entity_name(_: &C, _: H) -> Option<String> {
+        None
+    }
+}
+
 #[macro_export]
 macro_rules! define_command_stack {
     ($command_trait:ident, $command_stack:ident, $context:ty) => {
-        pub trait $command_trait: Debug + 'static {
+        pub trait $command_trait: Debug + $crate::command::CommandAsAny + 'static {
             fn name(&mut self, context: &$context) -> String;
             fn execute(&mut self, context: &mut $context);
             fn revert(&mut self, context: &mut $context);
             fn finalize(&mut self, _: &mut $context) {}
+
+            /// Checks whether the command, that was just executed, changed nothing (for example,
+            /// because it failed). Such commands are discarded instead of being put on the stack,
+            /// so there's nothing to undo.
+            fn is_noop(&self) -> bool {
+                false
+            }
+
+            /// Estimated amount of memory (in bytes) occupied by the command, it is used to limit
+            /// the size of the stack. Commands that own large data (for example, sub-graphs of
+            /// deleted nodes) should include the size of the data.
+            fn estimated_size(&self) -> usize {
+                std::mem::size_of_val(self)
+            }
+
+            /// Amount of steps of the command, commands that consist of other commands (groups)
+            /// could be executed step-by-step for debugging purposes.
+            fn step_count(&self) -> usize {
+                1
+            }
+
+            /// Executes a single step of the command. Steps are always executed in order, starting
+            /// from the first one.
+            fn execute_step(&mut self, _step: usize, context: &mut $context) {
+                self.execute(context)
+            }
+
+            /// Reverts a single step of the command. Steps are always reverted in reverse order,
+            /// starting from the last executed one.
+            fn revert_step(&mut self, _step: usize, context: &mut $context) {
+                self.revert(context)
+            }
+
+            /// Checks whether the other command, that was executed right after this one, could be
+            /// merged into this one by [`Self::merge`].
+            fn can_merge(&self, _other: &dyn $command_trait) -> bool {
+                false
+            }
+
+            /// Merges the other command, that was executed right after this one, into this one, so
+            /// reverting this command reverts both of them. Called only if [`Self::can_merge`]
+            /// returned `true`, the other command is dropped afterwards.
+            fn merge(&mut self, _other: &dyn $command_trait) {}
+
+            /// Merges the other command into this one, if possible. Returns `true` if the commands
+            /// were merged.
+            fn try_merge(&mut self, other: &dyn $command_trait) -> bool {
+                if self.can_merge(other) {
+                    self.merge(other);
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+
+        /// A command, that is being executed step-by-step.
+        struct PendingCommand {
+            command: Box<dyn $command_trait>,
+            executed_steps: usize,
         }
 
         pub struct $command_stack {
             commands: Vec<Box<dyn $command_trait>>,
             top: Option<usize>,
             debug: bool,
+            pending: Option<PendingCommand>,
+            // Time of the execution of the last command, used to merge consecutive commands.
+            last_executed: Option<std::time::Instant>,
+            merge_window: std::time::Duration,
+            limits: $crate::command::CommandStackLimits,
         }
 
         impl $command_stack {
@@ -26,44 +175,224 @@ macro_rules! define_command_stack {
                     commands: Default::default(),
                     top: None,
                     debug,
+                    pending: None,
+                    last_executed: None,
+                    merge_window: $crate::command::COMMAND_MERGE_WINDOW,
+                    limits: Default::default(),
                 }
             }
 
+            /// Sets the limits of the stack. The limits are applied when the next command is put
+            /// on the stack.
+            pub fn set_limits(&mut self, limits: $crate::command::CommandStackLimits) {
+                self.limits = limits;
+            }
+
+            /// Sets the time window, within which a command could be merged with the previous one.
+            /// Zero window disables merging.
+            pub fn set_merge_window(&mut self, merge_window: std::time::Duration) {
+                self.merge_window = merge_window;
+            }
+
             pub fn do_command(
                 &mut self,
                 mut command: Box<dyn $command_trait>,
                 mut context: $context,
             ) {
-                if self.commands.is_empty() {
-                    self.top = Some(0);
-                } else {
-                    // Advance top
-                    match self.top.as_mut() {
-                        None => self.top = Some(0),
-                        Some(top) => *top += 1,
+                self.abort_pending(&mut context);
+
+                if self.debug {
+                    println!("Executing command {:?}", command);
+                }
+
+                self.drop_redo_tail(&mut context);
+
+                command.execute(&mut context);
+
+                if command.is_noop() {
+                    if self.debug {
+                        println!("Discarding command {:?}", command);
                     }
-                    // Drop everything after top.
-                    let top = self.top.unwrap_or(0);
-                    if top < self.commands.len() {
-                        for mut dropped_command in self.commands.drain(top..) {
-                            if self.debug {
-                                println!("Finalizing command {:?}", dropped_command);
-                            }
-                            dropped_command.finalize(&mut context);
+                    command.finalize(&mut context);
+                    return;
+                }
+
+                let now = std::time::Instant::now();
+                let recent = matches!(self.last_executed.replace(now),
+                    Some(time) if now.duration_since(time) < self.merge_window);
+                if recent && self.try_merge_into_top(&*command) {
+                    if self.debug {
+                        println!("Merged command {:?}", command);
+                    }
+                    command.finalize(&mut context);
+                    self.trim(&mut context);
+                    return;
+                }
+
+                self.push_executed(command, &mut context);
+            }
+
+            /// Drops and finalizes every command after the top (the commands to redo), it must
+            /// be done before a new command is executed.
+            fn drop_redo_tail(&mut self, context: &mut $context) {
+                let first = self.top.map_or(0, |top| top + 1);
+                if first < self.commands.len() {
+                    for mut dropped_command in self.commands.drain(first..) {
+                        if self.debug {
+                            println!("Finalizing command {:?}", dropped_command);
                         }
+                        dropped_command.finalize(context);
+                    }
+                }
+            }
+
+            /// Merges already executed command into the command on top of the stack, if the top
+            /// is the last command (nothing to redo).
+            fn try_merge_into_top(&mut self, command: &dyn $command_trait) -> bool {
+                match self.top {
+                    Some(top) if top + 1 == self.commands.len() => {
+                        self.commands[top].try_merge(command)
+                    }
+                    _ => false,
+                }
+            }
+
+            /// Adds already executed command on top of the stack, the commands to redo must be
+            /// dropped before the command was executed (see [`Self::drop_redo_tail`]).
+            fn push_executed(&mut self, command: Box<dyn $command_trait>, context: &mut $context) {
+                self.commands.push(command);
+                self.top = Some(self.commands.len() - 1);
+
+                self.trim(context);
+            }
+
+            /// Drops the oldest executed commands, until the stack fits its limits. Only the
+            /// commands below the top are dropped, so the commands to redo stay intact.
+            fn trim(&mut self, context: &mut $context) {
+                let mut size = self
+                    .commands
+                    .iter()
+                    .map(|command| command.estimated_size())
+                    .sum::<usize>();
+
+                while let Some(top) = self.top {
+                    if top == 0
+                        || (self.commands.len() <= self.limits.max_entries
+                            && size <= self.limits.max_size)
+                    {
+                        break;
                     }
+
+                    let mut dropped_command = self.commands.remove(0);
+                    self.top = Some(top - 1);
+                    size -= dropped_command.estimated_size();
+
+                    if self.debug {
+                        println!("Evicting command {:?}", dropped_command);
+                    }
+                    dropped_command.finalize(context);
+                }
+            }
+
+            /// Collects memory usage of the stack, including the names of the largest commands.
+            pub fn statistics(
+                &mut self,
+                context: &$context,
+            ) -> $crate::command::CommandStackStatistics {
+                let mut sizes = self
+                    .commands
+                    .iter()
+                    .map(|command| command.estimated_size())
+                    .enumerate()
+                    .collect::<Vec<_>>();
+                sizes.sort_by(|(_, a), (_, b)| b.cmp(a));
+
+                $crate::command::CommandStackStatistics {
+                    count: self.commands.len(),
+                    size: sizes.iter().map(|(_, size)| size).sum(),
+                    largest: sizes
+                        .into_iter()
+                        .take($crate::command::LARGEST_COMMANDS_COUNT)
+                        .map(|(i, size)| (self.commands[i].name(context), size))
+                        .collect(),
                 }
+            }
+
+            /// Starts step-by-step execution of the command, no steps are executed immediately.
+            /// Any other operation on the stack aborts step-by-step execution first, so the stack
+            /// is never left with a partially executed command.
+            pub fn begin_stepping(
+                &mut self,
+                command: Box<dyn $command_trait>,
+                mut context: $context,
+            ) {
+                self.abort_pending(&mut context);
 
                 if self.debug {
-                    println!("Executing command {:?}", command);
+                    println!("Stepping command {:?}", command);
                 }
 
-                command.execute(&mut context);
+                self.pending = Some(PendingCommand {
+                    command,
+                    executed_steps: 0,
+                });
+            }
 
-                self.commands.push(command);
+            /// Executes next step of the pending command. When the last step is executed, the
+            /// command is put on the stack as if it was executed at once.
+            pub fn step(&mut self, mut context: $context) {
+                if let Some(mut pending) = self.pending.take() {
+                    if pending.executed_steps == 0 {
+                        self.drop_redo_tail(&mut context);
+                    }
+
+                    if pending.executed_steps < pending.command.step_count() {
+                        pending
+                            .command
+                            .execute_step(pending.executed_steps, &mut context);
+                        pending.executed_steps += 1;
+                    }
+
+                    if pending.executed_steps < pending.command.step_count() {
+                        self.pending = Some(pending);
+                    } else if pending.command.is_noop() {
+                        pending.command.finalize(&mut context);
+                    } else {
+                        self.last_executed = None;
+                        self.push_executed(pending.command, &mut context);
+                    }
+                }
+            }
+
+            /// Reverts every executed step of the pending command and discards it.
+            pub fn abort(&mut self, mut context: $context) {
+                self.abort_pending(&mut context);
+            }
+
+            fn abort_pending(&mut self, context: &mut $context) {
+                if let Some(mut pending) = self.pending.take() {
+                    if self.debug {
+                        println!("Aborting command {:?}", pending.command);
+                    }
+
+                    for step in (0..pending.executed_steps).rev() {
+                        pending.command.revert_step(step, context);
+                    }
+                    pending.command.finalize(context);
+                }
+            }
+
+            /// Returns amount of executed steps and total amount of steps of the pending command.
+            pub fn pending_progress(&self) -> Option<(usize, usize)> {
+                self.pending
+                    .as_ref()
+                    .map(|p| (p.executed_steps, p.command.step_count()))
             }
 
             pub fn undo(&mut self, mut context: $context) {
+                self.abort_pending(&mut context);
+                self.last_executed = None;
+
                 if !self.commands.is_empty() {
                     if let Some(top) = self.top.as_mut() {
                         if let Some(command) = self.commands.get_mut(*top) {
@@ -82,6 +411,9 @@ macro_rules! define_command_stack {
             }
 
             pub fn redo(&mut self, mut context: $context) {
+                self.abort_pending(&mut context);
+                self.last_executed = None;
+
                 if !self.commands.is_empty() {
                     let command = match self.top.as_mut() {
                         None => {
@@ -109,6 +441,9 @@ macro_rules! define_command_stack {
             }
 
             pub fn clear(&mut self, mut context: $context) {
+                self.abort_pending(&mut context);
+                self.last_executed = None;
+
                 for mut dropped_command in self.commands.drain(..) {
                     if self.debug {
                         println!("Finalizing command {:?}", dropped_command);
@@ -121,3 +456,448 @@ macro_rules! define_command_stack {
 }
 
 define_command_stack!(Command, CommandStack, SceneContext);
+
+#[cfg(test)]
+mod test {
+    use crate::command::{CommandStackLimits, COMMAND_MERGE_WINDOW};
+    use std::{cell::RefCell, fmt::Debug, rc::Rc, time::Duration};
+
+    type Log = Rc<RefCell<Vec<i32>>>;
+
+    define_command_stack!(TestCommand, TestCommandStack, Log);
+
+    /// Pushes its value to the log on execution and removes it on revert.
+    #[derive(Debug)]
+    struct Push(i32);
+
+    impl TestCommand for Push {
+        fn name(&mut self, _: &Log) -> String {
+            "Push".to_owned()
+        }
+
+        fn execute(&mut self, context: &mut Log) {
+            context.borrow_mut().push(self.0);
+        }
+
+        fn revert(&mut self, context: &mut Log) {
+            assert_eq!(context.borrow_mut().pop(), Some(self.0));
+        }
+    }
+
+    #[derive(Debug)]
+    struct Group(Vec<Push>);
+
+    impl TestCommand for Group {
+        fn name(&mut self, _: &Log) -> String {
+            "Group".to_owned()
+        }
+
+        fn execute(&mut self, context: &mut Log) {
+            for cmd in self.0.iter_mut() {
+                cmd.execute(context);
+            }
+        }
+
+        fn revert(&mut self, context: &mut Log) {
+            for cmd in self.0.iter_mut().rev() {
+                cmd.revert(context);
+            }
+        }
+
+        fn step_count(&self) -> usize {
+            self.0.len()
+        }
+
+        fn execute_step(&mut self, step: usize, context: &mut Log) {
+            self.0[step].execute(context);
+        }
+
+        fn revert_step(&mut self, step: usize, context: &mut Log) {
+            self.0[step].revert(context);
+        }
+    }
+
+    /// Replaces the last value of the log, consecutive replacements are merged.
+    #[derive(Debug)]
+    struct Replace {
+        old: i32,
+        new: i32,
+    }
+
+    impl TestCommand for Replace {
+        fn name(&mut self, _: &Log) -> String {
+            "Replace".to_owned()
+        }
+
+        fn execute(&mut self, context: &mut Log) {
+            let mut log = context.borrow_mut();
+            let last = log.last_mut().unwrap();
+            assert_eq!(*last, self.old);
+            *last = self.new;
+        }
+
+        fn revert(&mut self, context: &mut Log) {
+            let mut log = context.borrow_mut();
+            let last = log.last_mut().unwrap();
+            assert_eq!(*last, self.new);
+            *last = self.old;
+        }
+
+        fn can_merge(&self, other: &dyn TestCommand) -> bool {
+            matches!(other.as_any().downcast_ref::<Replace>(), Some(other) if other.old == self.new)
+        }
+
+        fn merge(&mut self, other: &dyn TestCommand) {
+            if let Some(other) = other.as_any().downcast_ref::<Replace>() {
+                self.new = other.new;
+            }
+        }
+    }
+
+    /// Changes nothing, like a command that failed to execute.
+    #[derive(Debug)]
+    struct Fail;
+
+    impl TestCommand for Fail {
+        fn name(&mut self, _: &Log) -> String {
+            "Fail".to_owned()
+        }
+
+        fn execute(&mut self, _: &mut Log) {}
+
+        fn revert(&mut self, _: &mut Log) {
+            panic!("Failed command must not be reverted!");
+        }
+
+        fn is_noop(&self) -> bool {
+            true
+        }
+    }
+
+    /// Pushes its value to the log like [`Push`], has the given size and records its finalization.
+    #[derive(Debug)]
+    struct Tracked {
+        value: i32,
+        size: usize,
+        finalized: Log,
+    }
+
+    impl TestCommand for Tracked {
+        fn name(&mut self, _: &Log) -> String {
+            format!("Tracked{}", self.value)
+        }
+
+        fn execute(&mut self, context: &mut Log) {
+            context.borrow_mut().push(self.value);
+        }
+
+        fn revert(&mut self, context: &mut Log) {
+            assert_eq!(context.borrow_mut().pop(), Some(self.value));
+        }
+
+        fn finalize(&mut self, _: &mut Log) {
+            self.finalized.borrow_mut().push(self.value);
+        }
+
+        fn estimated_size(&self) -> usize {
+            self.size
+        }
+    }
+
+    fn tracked(value: i32, size: usize, finalized: &Log) -> Box<Tracked> {
+        Box::new(Tracked {
+            value,
+            size,
+            finalized: finalized.clone(),
+        })
+    }
+
+    fn replace(old: i32, new: i32) -> Box<Replace> {
+        Box::new(Replace { old, new })
+    }
+
+    fn group() -> Box<Group> {
+        Box::new(Group(vec![Push(1), Push(2), Push(3)]))
+    }
+
+    #[test]
+    fn test_step_through_group() {
+        let log = Log::default();
+        let mut stack = TestCommandStack::new(false);
+
+        stack.begin_stepping(group(), log.clone());
+        assert_eq!(stack.pending_progress(), Some((0, 3)));
+        assert!(log.borrow().is_empty());
+
+        stack.step(log.clone());
+        assert_eq!(*log.borrow(), [1]);
+        stack.step(log.clone());
+        stack.step(log.clone());
+        assert_eq!(*log.borrow(), [1, 2, 3]);
+
+        // Fully executed group is a regular command on the stack.
+        assert_eq!(stack.pending_progress(), None);
+        stack.undo(log.clone());
+        assert!(log.borrow().is_empty());
+        stack.redo(log.clone());
+        assert_eq!(*log.borrow(), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_abort_midway() {
+        let log = Log::default();
+        let mut stack = TestCommandStack::new(false);
+
+        stack.do_command(Box::new(Push(0)), log.clone());
+
+        stack.begin_stepping(group(), log.clone());
+        stack.step(log.clone());
+        stack.step(log.clone());
+        assert_eq!(*log.borrow(), [0, 1, 2]);
+        assert_eq!(stack.pending_progress(), Some((2, 3)));
+
+        stack.abort(log.clone());
+        assert_eq!(*log.borrow(), [0]);
+        assert_eq!(stack.pending_progress(), None);
+
+        // The group must not get to the stack, so undo reverts the previous command.
+        stack.undo(log.clone());
+        assert!(log.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_other_operations_abort_pending_group() {
+        let log = Log::default();
+        let mut stack = TestCommandStack::new(false);
+
+        stack.do_command(Box::new(Push(0)), log.clone());
+        stack.begin_stepping(group(), log.clone());
+        stack.step(log.clone());
+
+        stack.undo(log.clone());
+        assert!(log.borrow().is_empty());
+        assert_eq!(stack.pending_progress(), None);
+
+        stack.begin_stepping(group(), log.clone());
+        stack.step(log.clone());
+        stack.do_command(Box::new(Push(4)), log.clone());
+        assert_eq!(*log.borrow(), [4]);
+
+        stack.begin_stepping(group(), log.clone());
+        stack.step(log.clone());
+        stack.clear(log.clone());
+        assert_eq!(*log.borrow(), [4]);
+        assert_eq!(stack.pending_progress(), None);
+    }
+
+    #[test]
+    fn test_consecutive_commands_are_merged() {
+        let log = Log::default();
+        let mut stack = TestCommandStack::new(false);
+
+        stack.do_command(Box::new(Push(0)), log.clone());
+        for value in 1..4 {
+            stack.do_command(replace(value - 1, value), log.clone());
+        }
+        assert_eq!(*log.borrow(), [3]);
+
+        // The whole chain of replacements is reverted at once.
+        stack.undo(log.clone());
+        assert_eq!(*log.borrow(), [0]);
+        stack.redo(log.clone());
+        assert_eq!(*log.borrow(), [3]);
+        stack.undo(log.clone());
+        stack.undo(log.clone());
+        assert!(log.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_merge_rules() {
+        let log = Log::default();
+        let mut stack = TestCommandStack::new(false);
+
+        stack.do_command(Box::new(Push(0)), log.clone());
+        stack.do_command(replace(0, 1), log.clone());
+
+        // Different kind of command is not merged.
+        stack.do_command(Box::new(Push(2)), log.clone());
+        stack.do_command(replace(2, 3), log.clone());
+        stack.do_command(replace(3, 4), log.clone());
+        assert_eq!(*log.borrow(), [1, 4]);
+
+        stack.undo(log.clone());
+        assert_eq!(*log.borrow(), [1, 2]);
+
+        // Nothing is merged right after undo.
+        stack.do_command(replace(2, 6), log.clone());
+        stack.undo(log.clone());
+        assert_eq!(*log.borrow(), [1, 2]);
+
+        // Commands are not merged, if there's a pause between them.
+        stack.do_command(replace(2, 7), log.clone());
+        stack.last_executed = std::time::Instant::now().checked_sub(2 * stack.merge_window);
+        stack.do_command(replace(7, 8), log.clone());
+        stack.undo(log.clone());
+        assert_eq!(*log.borrow(), [1, 7]);
+    }
+
+    #[test]
+    fn test_merge_window() {
+        let log = Log::default();
+        let mut stack = TestCommandStack::new(false);
+
+        stack.do_command(Box::new(Push(0)), log.clone());
+
+        // Wider window allows longer pauses between merged commands.
+        stack.set_merge_window(10 * COMMAND_MERGE_WINDOW);
+        stack.do_command(replace(0, 1), log.clone());
+        stack.last_executed = std::time::Instant::now().checked_sub(2 * COMMAND_MERGE_WINDOW);
+        stack.do_command(replace(1, 2), log.clone());
+        stack.undo(log.clone());
+        assert_eq!(*log.borrow(), [0]);
+
+        // Zero window disables merging.
+        stack.set_merge_window(Duration::default());
+        stack.do_command(replace(0, 1), log.clone());
+        stack.do_command(replace(1, 2), log.clone());
+        stack.undo(log.clone());
+        assert_eq!(*log.borrow(), [1]);
+    }
+
+    #[test]
+    fn test_failed_commands_are_discarded() {
+        let log = Log::default();
+        let mut stack = TestCommandStack::new(false);
+
+        stack.do_command(Box::new(Push(0)), log.clone());
+        stack.do_command(Box::new(Push(1)), log.clone());
+        stack.undo(log.clone());
+
+        // Failed command does not get to the stack, but the commands to redo are dropped
+        // before any command is executed.
+        stack.do_command(Box::new(Fail), log.clone());
+        stack.do_command(Box::new(Fail), log.clone());
+        stack.redo(log.clone());
+        assert_eq!(*log.borrow(), [0]);
+
+        stack.undo(log.clone());
+        assert!(log.borrow().is_empty());
+        stack.redo(log.clone());
+        assert_eq!(*log.borrow(), [0]);
+    }
+
+    #[test]
+    fn test_oldest_commands_are_evicted() {
+        let log = Log::default();
+        let finalized = Log::default();
+        let mut stack = TestCommandStack::new(false);
+        stack.set_limits(CommandStackLimits {
+            max_entries: 3,
+            max_size: 100,
+        });
+
+        for value in 0..5 {
+            stack.do_command(tracked(value, 10, &finalized), log.clone());
+        }
+        assert_eq!(*finalized.borrow(), [0, 1]);
+
+        // Exceeded size evicts commands as well, but the top command is always kept.
+        stack.do_command(tracked(5, 60, &finalized), log.clone());
+        assert_eq!(*finalized.borrow(), [0, 1, 2]);
+        stack.do_command(tracked(6, 200, &finalized), log.clone());
+        assert_eq!(*finalized.borrow(), [0, 1, 2, 3, 4, 5]);
+
+        // Evicted commands could not be undone.
+        stack.undo(log.clone());
+        stack.undo(log.clone());
+        assert_eq!(*log.borrow(), [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_trimming_keeps_redo_history_consistent() {
+        let log = Log::default();
+        let finalized = Log::default();
+        let mut stack = TestCommandStack::new(false);
+        stack.set_limits(CommandStackLimits {
+            max_entries: 3,
+            max_size: usize::MAX,
+        });
+
+        for value in 0..3 {
+            stack.do_command(tracked(value, 10, &finalized), log.clone());
+        }
+        stack.undo(log.clone());
+        stack.undo(log.clone());
+
+        // Lower limits are applied on the next command, commands to redo are not evicted.
+        stack.set_limits(CommandStackLimits {
+            max_entries: 1,
+            max_size: usize::MAX,
+        });
+        stack.redo(log.clone());
+        assert_eq!(*log.borrow(), [0, 1]);
+        assert!(finalized.borrow().is_empty());
+
+        // New command drops the commands to redo first, and only then the oldest ones.
+        stack.do_command(tracked(3, 10, &finalized), log.clone());
+        assert_eq!(*finalized.borrow(), [2, 0, 1]);
+        stack.redo(log.clone());
+        assert_eq!(*log.borrow(), [0, 1, 3]);
+        stack.undo(log.clone());
+        stack.undo(log.clone());
+        assert_eq!(*log.borrow(), [0, 1]);
+    }
+
+    #[test]
+    fn test_redo_tail_is_finalized_before_execution() {
+        // Finalization and execution are written to the same log to check their order.
+        let log = Log::default();
+        let mut stack = TestCommandStack::new(false);
+
+        stack.do_command(tracked(1, 10, &log), log.clone());
+        stack.do_command(tracked(2, 10, &log), log.clone());
+        stack.undo(log.clone());
+        stack.undo(log.clone());
+        assert!(log.borrow().is_empty());
+
+        stack.do_command(tracked(3, 10, &log), log.clone());
+        assert_eq!(*log.borrow(), [1, 2, 3]);
+
+        // The same is true for step-by-step execution.
+        stack.undo(log.clone());
+        assert_eq!(*log.borrow(), [1, 2]);
+        stack.begin_stepping(tracked(4, 10, &log), log.clone());
+        stack.step(log.clone());
+        assert_eq!(*log.borrow(), [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_statistics() {
+        let log = Log::default();
+        let finalized = Log::default();
+        let mut stack = TestCommandStack::new(false);
+
+        for (value, size) in [10, 70, 20, 50, 30, 60].iter().enumerate() {
+            stack.do_command(tracked(value as i32, *size, &finalized), log.clone());
+        }
+
+        let statistics = stack.statistics(&log);
+        assert_eq!(statistics.count, 6);
+        assert_eq!(statistics.size, 240);
+        let largest = statistics
+            .largest
+            .iter()
+            .map(|(name, size)| (name.as_str(), *size))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            largest,
+            [
+                ("Tracked1", 70),
+                ("Tracked5", 60),
+                ("Tracked3", 50),
+                ("Tracked4", 30),
+                ("Tracked2", 20)
+            ]
+        );
+    }
+}
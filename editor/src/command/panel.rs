@@ -7,16 +7,18 @@ use fyrox::{
     core::{color::Color, pool::Handle, scope_profile},
     gui::{
         brush::Brush,
-        button::ButtonMessage,
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
         grid::{Column, GridBuilder, Row},
         list_view::{ListViewBuilder, ListViewMessage},
         message::{MessageDirection, UiMessage},
         scroll_viewer::ScrollViewerBuilder,
         stack_panel::StackPanelBuilder,
         text::TextBuilder,
+        utils::make_simple_tooltip,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowTitle},
-        BuildContext, Orientation, Thickness, UiNode, UserInterface,
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
     },
 };
 
@@ -27,6 +29,10 @@ pub struct CommandStackViewer {
     undo: Handle<UiNode>,
     redo: Handle<UiNode>,
     clear: Handle<UiNode>,
+    step_through: Handle<UiNode>,
+    step: Handle<UiNode>,
+    abort: Handle<UiNode>,
+    step_through_enabled: bool,
 }
 
 impl CommandStackViewer {
@@ -35,6 +41,9 @@ impl CommandStackViewer {
         let undo;
         let redo;
         let clear;
+        let step_through;
+        let step;
+        let abort;
         let window = WindowBuilder::new(WidgetBuilder::new().with_name("CommandStackPanel"))
             .with_title(WindowTitle::Text("Command Stack".to_owned()))
             .with_content(
@@ -78,6 +87,50 @@ impl CommandStackViewer {
                                             "Clear Command Stack\nChanges history will be erased.",
                                         );
                                         clear
+                                    })
+                                    .with_child({
+                                        step_through = CheckBoxBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_margin(Thickness::uniform(1.0))
+                                                .with_tooltip(make_simple_tooltip(
+                                                    ctx,
+                                                    "Execute command groups step-by-step. Use \
+                                                    Step (F10) to execute next command of a group \
+                                                    and Abort (Shift+F10) to revert every executed \
+                                                    command of the group.",
+                                                )),
+                                        )
+                                        .with_content(
+                                            TextBuilder::new(WidgetBuilder::new())
+                                                .with_vertical_text_alignment(
+                                                    VerticalAlignment::Center,
+                                                )
+                                                .with_text("Step Through")
+                                                .build(ctx),
+                                        )
+                                        .checked(Some(false))
+                                        .build(ctx);
+                                        step_through
+                                    })
+                                    .with_child({
+                                        step = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_enabled(false)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Step")
+                                        .build(ctx);
+                                        step
+                                    })
+                                    .with_child({
+                                        abort = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_enabled(false)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Abort")
+                                        .build(ctx);
+                                        abort
                                     }),
                             )
                             .with_orientation(Orientation::Horizontal)
@@ -110,10 +163,19 @@ impl CommandStackViewer {
             undo,
             redo,
             clear,
+            step_through,
+            step,
+            abort,
+            step_through_enabled: false,
         }
     }
 
-    pub fn handle_ui_message(&self, message: &UiMessage) {
+    /// Whether command groups should be executed step-by-step or not.
+    pub fn is_step_through_enabled(&self) -> bool {
+        self.step_through_enabled
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage) {
         scope_profile!();
 
         if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
@@ -123,6 +185,16 @@ impl CommandStackViewer {
                 self.sender.send(Message::RedoSceneCommand);
             } else if message.destination() == self.clear {
                 self.sender.send(Message::ClearSceneCommandStack);
+            } else if message.destination() == self.step {
+                self.sender.send(Message::StepSceneCommand);
+            } else if message.destination() == self.abort {
+                self.sender.send(Message::AbortSceneCommand);
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.step_through
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.step_through_enabled = *value;
             }
         }
     }
@@ -136,35 +208,66 @@ impl CommandStackViewer {
         scope_profile!();
 
         let top = command_stack.top;
-        let items = command_stack
-            .commands
-            .iter_mut()
-            .enumerate()
-            .rev() // First command in list is last on stack.
-            .map(|(i, cmd)| {
-                let brush = if let Some(top) = top {
-                    if (0..=top).contains(&i) {
-                        Brush::Solid(Color::opaque(255, 255, 255))
-                    } else {
-                        Brush::Solid(Color::opaque(100, 100, 100))
-                    }
-                } else {
-                    Brush::Solid(Color::opaque(100, 100, 100))
-                };
+        let pending = command_stack.pending.as_mut().map(|pending| {
+            TextBuilder::new(
+                WidgetBuilder::new()
+                    .with_margin(Thickness {
+                        left: 2.0,
+                        top: 1.0,
+                        right: 2.0,
+                        bottom: 0.0,
+                    })
+                    .with_foreground(Brush::Solid(Color::opaque(255, 200, 0))),
+            )
+            .with_text(format!(
+                "{} [{}/{}]",
+                pending.command.name(ctx),
+                pending.executed_steps,
+                pending.command.step_count()
+            ))
+            .build(&mut ui.build_ctx())
+        });
 
-                TextBuilder::new(
-                    WidgetBuilder::new()
-                        .with_margin(Thickness {
-                            left: 2.0,
-                            top: 1.0,
-                            right: 2.0,
-                            bottom: 0.0,
-                        })
-                        .with_foreground(brush),
-                )
-                .with_text(cmd.name(ctx))
-                .build(&mut ui.build_ctx())
-            })
+        for widget in [self.step, self.abort] {
+            send_sync_message(
+                ui,
+                WidgetMessage::enabled(widget, MessageDirection::ToWidget, pending.is_some()),
+            );
+        }
+
+        let items = pending
+            .into_iter()
+            .chain(
+                command_stack
+                    .commands
+                    .iter_mut()
+                    .enumerate()
+                    .rev() // First command in list is last on stack.
+                    .map(|(i, cmd)| {
+                        let brush = if let Some(top) = top {
+                            if (0..=top).contains(&i) {
+                                Brush::Solid(Color::opaque(255, 255, 255))
+                            } else {
+                                Brush::Solid(Color::opaque(100, 100, 100))
+                            }
+                        } else {
+                            Brush::Solid(Color::opaque(100, 100, 100))
+                        };
+
+                        TextBuilder::new(
+                            WidgetBuilder::new()
+                                .with_margin(Thickness {
+                                    left: 2.0,
+                                    top: 1.0,
+                                    right: 2.0,
+                                    bottom: 0.0,
+                                })
+                                .with_foreground(brush),
+                        )
+                        .with_text(cmd.name(ctx))
+                        .build(&mut ui.build_ctx())
+                    }),
+            )
             .collect();
 
         send_sync_message(
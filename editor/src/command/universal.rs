@@ -1,8 +1,21 @@
+/// Defines universal commands, that modify entities via reflection. Entities, that could be
+/// instances of some parent (e.g. of a prefab), could also provide `parent_value` block after the
+/// entity getter, it must return a clone of the value of the inheritable property of the parent at
+/// `self.path` (see `clone_inheritable_value`). The value is used to revert the property. Optional
+/// `entity_name` type (after `parent_value`, if any) must implement `EntityNameProvider` for the
+/// context and the handle, the names of the commands then tell which entity they change.
 #[macro_export]
 macro_rules! define_universal_commands {
-    ($name:ident, $command:ident, $command_wrapper:ty, $ctx:ty, $handle:ty, $ctx_ident:ident, $handle_ident:ident, $self:ident, $entity_getter:block, $($field_name:ident: $field_type:ty),*) => {
-        pub fn $name($handle_ident: $handle, property_changed: &fyrox::gui::inspector::PropertyChanged, $($field_name: $field_type),*) -> Option<$command_wrapper> {
-            match fyrox::gui::inspector::PropertyAction::from_field_kind(&property_changed.value) {
+    ($name:ident, $command:ident, $command_wrapper:ty, $ctx:ty, $handle:ty, $ctx_ident:ident, $handle_ident:ident, $self:ident, $entity_getter:block, parent_value: $parent_value_getter:block, entity_name: $name_provider:ty, $($field_name:ident: $field_type:ty),*) => {
+        /// Makes a command for the change of the property of the given entity. The change is
+        /// checked against the entity first, nothing is made if the entity has no such property
+        /// (e.g. the path is stale) or the new value has another type.
+        pub fn $name($handle_ident: $handle, entity: &dyn fyrox::core::reflect::Reflect, property_changed: &fyrox::gui::inspector::PropertyChanged, $($field_name: $field_type),*) -> Option<$command_wrapper> {
+            let action = fyrox::gui::inspector::PropertyAction::from_field_kind(&property_changed.value);
+            if !is_applicable(entity, &property_changed.path(), &action) {
+                return None;
+            }
+            match action {
                 fyrox::gui::inspector::PropertyAction::Modify { value } => Some(<$command_wrapper>::new(SetPropertyCommand::new(
                     $handle_ident,
                     property_changed.path(),
@@ -15,9 +28,52 @@ macro_rules! define_universal_commands {
                 fyrox::gui::inspector::PropertyAction::RemoveItem { index } => Some(<$command_wrapper>::new(
                     RemoveCollectionItemCommand::new($handle_ident, property_changed.path(), index, $($field_name),*)
                 )),
-                // Must be handled outside, there is not enough context and it near to impossible to create universal reversion
-                // for InheritableVariable<T>.
-                fyrox::gui::inspector::PropertyAction::Revert => None
+                fyrox::gui::inspector::PropertyAction::InsertItem { index, value } => Some(<$command_wrapper>::new(
+                    InsertCollectionItemCommand::new($handle_ident, property_changed.path(), index, value, $($field_name),*)
+                )),
+                // Moving to the same index changes nothing, so there's nothing to undo.
+                fyrox::gui::inspector::PropertyAction::MoveItem { from, to } if from == to => None,
+                fyrox::gui::inspector::PropertyAction::MoveItem { from, to } => Some(<$command_wrapper>::new(
+                    MoveCollectionItemCommand::new($handle_ident, property_changed.path(), from, to, $($field_name),*)
+                )),
+                fyrox::gui::inspector::PropertyAction::Revert => Some(<$command_wrapper>::new(
+                    RevertPropertyCommand::new($handle_ident, property_changed.path(), $($field_name),*)
+                )),
+                fyrox::gui::inspector::PropertyAction::ResetToDefault => {
+                    let mut value = None;
+                    resolve_path(entity, &property_changed.path(), |property| {
+                        value = fyrox::gui::inspector::defaults::make_default_value(
+                            fyrox::gui::inspector::defaults::property_value_type_id(property)
+                        );
+                    });
+                    value.map(|value| <$command_wrapper>::new(
+                        ResetPropertyCommand::new($handle_ident, property_changed.path(), value, $($field_name),*)
+                    ))
+                }
+            }
+        }
+
+        /// Makes a command, that sets the same value of the property to every given entity. Only
+        /// modifications of the values are supported, other changes must be made for every entity
+        /// separately.
+        pub fn make_set_property_multi_command(handles: &[$handle], property_changed: &fyrox::gui::inspector::PropertyChanged, $($field_name: $field_type),*) -> Option<$command_wrapper> {
+            // Every entity gets its own copy of the value.
+            let targets = handles
+                .iter()
+                .filter_map(|&$handle_ident| match fyrox::gui::inspector::PropertyAction::from_field_kind(&property_changed.value) {
+                    fyrox::gui::inspector::PropertyAction::Modify { value } => Some(SetPropertyCommand::new(
+                        $handle_ident,
+                        property_changed.path(),
+                        value,
+                        $($field_name),*
+                    )),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if targets.is_empty() {
+                None
+            } else {
+                Some(<$command_wrapper>::new(SetPropertyMultiCommand::new(targets)))
             }
         }
 
@@ -36,12 +92,96 @@ macro_rules! define_universal_commands {
             })
         }
 
+        fn resolve_path<F: FnOnce(&dyn fyrox::core::reflect::Reflect)>(
+            entity: &dyn fyrox::core::reflect::Reflect,
+            path: &str,
+            func: F,
+        ) {
+            let mut func = Some(func);
+            entity.resolve_path(path, &mut |result| match result {
+                Ok(field) => func.take().unwrap()(field),
+                Err(e) => fyrox::core::log::Log::err(format!(
+                    "There is no such property {}! Reason: {:?}",
+                    path, e
+                )),
+            })
+        }
+
+        /// Checks, without modifying the entity, whether the action could be applied to the
+        /// property at the path. New value of the property must have the same type as the
+        /// property, reset property must have a default value of its type.
+        fn is_applicable(
+            entity: &dyn fyrox::core::reflect::Reflect,
+            path: &str,
+            action: &fyrox::gui::inspector::PropertyAction,
+        ) -> bool {
+            use fyrox::gui::inspector::defaults::{has_default_value, property_value_type_id};
+
+            let mut applicable = false;
+            resolve_path(entity, path, |property| {
+                applicable = match action {
+                    fyrox::gui::inspector::PropertyAction::Modify { value } => {
+                        let same_type = property_value_type_id(property) == property_value_type_id(&**value);
+                        if !same_type {
+                            fyrox::core::log::Log::err(format!(
+                                "Failed to set property {}! Incompatible types {} and {}!",
+                                path,
+                                value.type_name(),
+                                property.type_name()
+                            ));
+                        }
+                        same_type
+                    }
+                    fyrox::gui::inspector::PropertyAction::ResetToDefault => {
+                        let has_default = has_default_value(property_value_type_id(property));
+                        if !has_default {
+                            fyrox::core::log::Log::err(format!(
+                                "Failed to reset property {}! There is no default value of type {}!",
+                                path,
+                                property.type_name()
+                            ));
+                        }
+                        has_default
+                    }
+                    _ => true,
+                }
+            });
+            applicable
+        }
+
+        /// Clones the value of the inheritable property of the parent entity, returns `None` if the
+        /// parent has no such property or it is not inheritable.
+        // Only the entities with `parent_value` block use it.
+        #[allow(dead_code)]
+        fn clone_inheritable_value(
+            parent: &dyn fyrox::core::reflect::Reflect,
+            path: &str,
+        ) -> Option<Box<dyn fyrox::core::reflect::Reflect>> {
+            let mut value = None;
+            parent.resolve_path(path, &mut |result| match result {
+                Ok(field) => field.as_inheritable_variable(&mut |variable| {
+                    value = variable.map(|variable| variable.clone_value_box());
+                }),
+                Err(e) => fyrox::core::log::Log::err(format!(
+                    "Failed to resolve parent path {}. Reason: {:?}",
+                    path, e
+                )),
+            });
+            value
+        }
+
+        /// Returns the name of the entity, that is shown in the names of the commands.
+        fn entity_name($ctx_ident: &$ctx, $handle_ident: $handle) -> Option<String> {
+            <$name_provider as $crate::command::EntityNameProvider<$ctx, $handle>>::entity_name($ctx_ident, $handle_ident)
+        }
+
         #[derive(Debug)]
         pub struct SetPropertyCommand {
-            #[allow(dead_code)]
             $handle_ident: $handle,
             value: Option<Box<dyn fyrox::core::reflect::Reflect>>,
             path: String,
+            // Whether the entity took the value on execution.
+            applied: bool,
             $($field_name: $field_type),*
         }
 
@@ -51,11 +191,15 @@ macro_rules! define_universal_commands {
                     $handle_ident,
                     value: Some(value),
                     path,
+                    applied: false,
                     $($field_name),*
                 }
             }
 
-            fn swap(&mut $self, $ctx_ident: &mut $ctx) {
+            /// Swaps the value of the property with the stored one, returns `false` if the entity
+            /// has no such property or it has another type, the value is kept then.
+            fn swap(&mut $self, $ctx_ident: &mut $ctx) -> bool {
+                let mut swapped = false;
                 if fyrox::core::reflect::is_path_to_array_element(&$self.path) {
                     (($entity_getter) as &mut dyn Reflect).resolve_path_mut(&$self.path, &mut |result| match result {
                         Err(reason) => {
@@ -68,6 +212,7 @@ macro_rules! define_universal_commands {
                             match property.set($self.value.take().unwrap()) {
                                 Ok(old_value) => {
                                     $self.value = Some(old_value);
+                                    swapped = true;
                                 }
                                 Err(current_value) => {
                                     fyrox::core::log::Log::err(format!(
@@ -83,6 +228,7 @@ macro_rules! define_universal_commands {
                     (($entity_getter) as &mut dyn Reflect).set_field_by_path(&$self.path, $self.value.take().unwrap(), &mut |result| match result {
                         Ok(old_value) => {
                             $self.value = Some(old_value);
+                            swapped = true;
                         }
                         Err(result) => {
                             let value = match result {
@@ -108,26 +254,256 @@ macro_rules! define_universal_commands {
                         }
                     });
                     }
+                swapped
+            }
+
+            /// Returns whether the property is modified, `None` if the property is not an
+            /// inheritable variable.
+            fn is_inheritable_modified(&mut $self, $ctx_ident: &mut $ctx) -> Option<bool> {
+                let mut is_modified = None;
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    field.as_inheritable_variable(&mut |variable| {
+                        is_modified = variable.map(|variable| variable.is_modified());
+                    })
+                });
+                is_modified
+            }
+
+            fn mark_inheritable_non_modified(&mut $self, $ctx_ident: &mut $ctx) {
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    fyrox::core::variable::mark_inheritable_properties_non_modified(field)
+                });
+            }
+
+            /// Checks whether the other command changes the same property of the same entity.
+            fn is_same_property(&$self, other: &SetPropertyCommand) -> bool {
+                $self.$handle_ident == other.$handle_ident
+                    && $self.path == other.path
+                    $(&& $self.$field_name == other.$field_name)*
             }
         }
 
         impl $command for SetPropertyCommand {
+            fn name(&mut $self, $ctx_ident: &$ctx) -> String {
+                match entity_name($ctx_ident, $self.$handle_ident) {
+                    Some(name) => format!("Set {} of '{}'", $self.path, name),
+                    None => format!("Set {} property", $self.path),
+                }
+            }
+
+            fn execute(&mut $self, $ctx_ident: &mut $ctx) {
+                $self.applied = $self.swap($ctx_ident);
+            }
+
+            fn revert(&mut $self, $ctx_ident: &mut $ctx) {
+                if $self.applied {
+                    $self.swap($ctx_ident);
+                }
+            }
+
+            fn is_noop(&$self) -> bool {
+                !$self.applied
+            }
+
+            // Values are swapped on execution, so the value of an executed command is the value
+            // the property will be reverted to. The merged command keeps its value, so the whole
+            // chain of changes (e.g. made by dragging a slider) is reverted at once.
+
+            fn can_merge(&$self, other: &dyn $command) -> bool {
+                matches!(other.as_any().downcast_ref::<SetPropertyCommand>(),
+                    Some(other) if $self.applied && other.applied && $self.is_same_property(other))
+            }
+
+            fn merge(&mut $self, _other: &dyn $command) {}
+        }
+
+        /// Sets the default value of the type of the property (see
+        /// `fyrox::gui::inspector::defaults::make_default_value`), the previous value of the
+        /// property is restored on revert. Inheritable property becomes modified on reset, revert
+        /// makes it non-modified again if it was inherited from the parent before.
+        #[derive(Debug)]
+        pub struct ResetPropertyCommand {
+            inner: SetPropertyCommand,
+            // Whether the property was a non-modified inheritable variable before the reset.
+            was_inherited: bool,
+        }
+
+        impl ResetPropertyCommand {
+            pub fn new($handle_ident: $handle, path: String, default_value: Box<dyn fyrox::core::reflect::Reflect>, $($field_name: $field_type),*) -> Self {
+                Self {
+                    inner: SetPropertyCommand::new($handle_ident, path, default_value, $($field_name),*),
+                    was_inherited: false,
+                }
+            }
+        }
+
+        impl $command for ResetPropertyCommand {
+            fn name(&mut $self, $ctx_ident: &$ctx) -> String {
+                match entity_name($ctx_ident, $self.inner.$handle_ident) {
+                    Some(name) => format!("Reset {} of '{}' to default", $self.inner.path, name),
+                    None => format!("Reset {} property to default", $self.inner.path),
+                }
+            }
+
+            fn execute(&mut $self, $ctx_ident: &mut $ctx) {
+                $self.was_inherited = $self.inner.is_inheritable_modified($ctx_ident) == Some(false);
+                $self.inner.execute($ctx_ident);
+            }
+
+            fn revert(&mut $self, $ctx_ident: &mut $ctx) {
+                let applied = $self.inner.applied;
+                $self.inner.revert($ctx_ident);
+                if applied && $self.was_inherited {
+                    $self.inner.mark_inheritable_non_modified($ctx_ident);
+                }
+            }
+
+            fn is_noop(&$self) -> bool {
+                $self.inner.is_noop()
+            }
+        }
+
+        /// Sets the same value of the property to several entities, every entity keeps its own
+        /// previous value. Entities, that have no such property or have it of another type, are
+        /// skipped, the rest take the value anyway.
+        #[derive(Debug)]
+        pub struct SetPropertyMultiCommand {
+            // Commands of the entities with the flags, whether the entities took the values.
+            targets: Vec<(SetPropertyCommand, bool)>,
+        }
+
+        impl SetPropertyMultiCommand {
+            pub fn new(targets: Vec<SetPropertyCommand>) -> Self {
+                Self {
+                    targets: targets.into_iter().map(|target| (target, false)).collect(),
+                }
+            }
+        }
+
+        impl $command for SetPropertyMultiCommand {
             fn name(&mut $self, _: &$ctx) -> String {
-                format!("Set {} property", $self.path)
+                match $self.targets.first() {
+                    Some((target, _)) => format!("Set {} property of {} objects", target.path, $self.targets.len()),
+                    None => "Set property".to_owned(),
+                }
+            }
+
+            fn execute(&mut $self, $ctx_ident: &mut $ctx) {
+                for (target, swapped) in $self.targets.iter_mut() {
+                    *swapped = target.swap($ctx_ident);
+                }
+            }
+
+            fn revert(&mut $self, $ctx_ident: &mut $ctx) {
+                for (target, swapped) in $self.targets.iter_mut().rev() {
+                    if *swapped {
+                        target.swap($ctx_ident);
+                    }
+                }
+            }
+
+            fn is_noop(&$self) -> bool {
+                !$self.targets.iter().any(|(_, swapped)| *swapped)
+            }
+
+            fn can_merge(&$self, other: &dyn $command) -> bool {
+                matches!(other.as_any().downcast_ref::<SetPropertyMultiCommand>(),
+                    Some(other) if $self.targets.len() == other.targets.len()
+                        && $self.targets.iter().zip(other.targets.iter()).all(
+                            |((target, swapped), (other, other_swapped))| {
+                                swapped == other_swapped && target.is_same_property(other)
+                            }
+                        ))
+            }
+
+            fn merge(&mut $self, _other: &dyn $command) {}
+        }
+
+        /// Reverts the inheritable property to the value of the parent entity and marks it as
+        /// non-modified, so it is inherited from the parent again. Reverting the command restores
+        /// the previous value and marks the property as modified.
+        #[derive(Debug)]
+        pub struct RevertPropertyCommand {
+            $handle_ident: $handle,
+            path: String,
+            // Value of the property before the revert, `None` if the property wasn't reverted.
+            value: Option<Box<dyn fyrox::core::reflect::Reflect>>,
+            $($field_name: $field_type),*
+        }
+
+        impl RevertPropertyCommand {
+            pub fn new($handle_ident: $handle, path: String, $($field_name: $field_type),*) -> Self {
+                Self {
+                    $handle_ident,
+                    path,
+                    value: None,
+                    $($field_name),*
+                }
+            }
+        }
+
+        impl $command for RevertPropertyCommand {
+            fn name(&mut $self, $ctx_ident: &$ctx) -> String {
+                match entity_name($ctx_ident, $self.$handle_ident) {
+                    Some(name) => format!("Revert {} of '{}'", $self.path, name),
+                    None => format!("Revert {} property", $self.path),
+                }
             }
 
             fn execute(&mut $self, $ctx_ident: &mut $ctx) {
-                $self.swap($ctx_ident);
+                let mut parent_value: Option<Box<dyn fyrox::core::reflect::Reflect>> = $parent_value_getter;
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    let mut is_modified = None;
+                    field.as_inheritable_variable(&mut |variable| {
+                        is_modified = variable.map(|variable| variable.is_modified());
+                    });
+                    match (is_modified, parent_value.take()) {
+                        (None, _) => fyrox::core::log::Log::err(format!(
+                            "Failed to revert property {}! It is not an inheritable variable.",
+                            $self.path
+                        )),
+                        // The property is inherited from the parent already.
+                        (Some(false), _) => (),
+                        (Some(true), None) => fyrox::core::log::Log::err(format!(
+                            "Failed to revert property {}! There is no parent value.",
+                            $self.path
+                        )),
+                        (Some(true), Some(parent_value)) => match field.set(parent_value) {
+                            Ok(old_value) => {
+                                $self.value = Some(old_value);
+                                fyrox::core::variable::mark_inheritable_properties_non_modified(field);
+                            }
+                            Err(parent_value) => fyrox::core::log::Log::err(format!(
+                                "Failed to revert property {}! Incompatible types {}!",
+                                $self.path, parent_value.type_name()
+                            )),
+                        },
+                    }
+                })
             }
 
             fn revert(&mut $self, $ctx_ident: &mut $ctx) {
-                $self.swap($ctx_ident);
+                if let Some(old_value) = $self.value.take() {
+                    let mut old_value = Some(old_value);
+                    try_modify_property($entity_getter, &$self.path, |field| {
+                        // Setting the value marks the property as modified again.
+                        if let Err(old_value) = field.set(old_value.take().unwrap()) {
+                            fyrox::core::log::Log::err(format!(
+                                "Failed to restore property {}! Incompatible types {}!",
+                                $self.path, old_value.type_name()
+                            ))
+                        }
+                    })
+                }
+            }
+
+            fn is_noop(&$self) -> bool {
+                $self.value.is_none()
             }
         }
 
         #[derive(Debug)]
         pub struct AddCollectionItemCommand {
-            #[allow(dead_code)]
             $handle_ident: $handle,
             path: String,
             item: Option<Box<dyn fyrox::core::reflect::Reflect>>,
@@ -146,8 +522,11 @@ macro_rules! define_universal_commands {
         }
 
         impl $command for AddCollectionItemCommand {
-            fn name(&mut $self, _: &$ctx) -> String {
-                format!("Add item to {} collection", $self.path)
+            fn name(&mut $self, $ctx_ident: &$ctx) -> String {
+                match entity_name($ctx_ident, $self.$handle_ident) {
+                    Some(name) => format!("Add item to {} collection of '{}'", $self.path, name),
+                    None => format!("Add item to {} collection", $self.path),
+                }
             }
 
             fn execute(&mut $self, $ctx_ident: &mut $ctx) {
@@ -169,6 +548,11 @@ macro_rules! define_universal_commands {
             }
 
             fn revert(&mut $self, $ctx_ident: &mut $ctx) {
+                // The item is kept, if it wasn't added.
+                if $self.item.is_some() {
+                    return;
+                }
+
                 try_modify_property($entity_getter, &$self.path, |field| {
                     field.as_list_mut(&mut |result| {
                         if let Some(list) = result {
@@ -183,11 +567,182 @@ macro_rules! define_universal_commands {
                     });
                 })
             }
+
+            fn is_noop(&$self) -> bool {
+                $self.item.is_some()
+            }
+        }
+
+        /// Inserts the item to the collection at the given index, out-of-range index is clamped to
+        /// the length of the collection, so the item is added to the end of it.
+        #[derive(Debug)]
+        pub struct InsertCollectionItemCommand {
+            $handle_ident: $handle,
+            path: String,
+            index: usize,
+            item: Option<Box<dyn fyrox::core::reflect::Reflect>>,
+            $($field_name: $field_type),*
+        }
+
+        impl InsertCollectionItemCommand {
+            pub fn new($handle_ident: $handle, path: String, index: usize, item: Box<dyn fyrox::core::reflect::Reflect>, $($field_name: $field_type),*) -> Self {
+                Self {
+                    $handle_ident,
+                    path,
+                    index,
+                    item: Some(item),
+                    $($field_name),*
+                }
+            }
+        }
+
+        impl $command for InsertCollectionItemCommand {
+            fn name(&mut $self, $ctx_ident: &$ctx) -> String {
+                match entity_name($ctx_ident, $self.$handle_ident) {
+                    Some(name) => format!("Insert item {} to {} collection of '{}'", $self.index, $self.path, name),
+                    None => format!("Insert item {} to {} collection", $self.index, $self.path),
+                }
+            }
+
+            fn execute(&mut $self, $ctx_ident: &mut $ctx) {
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    field.as_list_mut(&mut |result| {
+                        if let Some(list) = result {
+                            $self.index = $self.index.min(list.reflect_len());
+                            if let Err(item) = list.reflect_insert($self.index, $self.item.take().unwrap()) {
+                                fyrox::core::log::Log::err(format!(
+                                    "Failed to insert item to {} collection. Type mismatch {} and {}!",
+                                    $self.path, item.type_name(), list.type_name()
+                                ));
+                                $self.item = Some(item);
+                            }
+                        } else {
+                            fyrox::core::log::Log::err(format!("Property {} is not a collection!", $self.path))
+                        }
+                    });
+                })
+            }
+
+            fn revert(&mut $self, $ctx_ident: &mut $ctx) {
+                // The item is kept, if it wasn't inserted.
+                if $self.item.is_some() {
+                    return;
+                }
+
+                try_modify_property($entity_getter, &$self.path, |field| {
+                    field.as_list_mut(&mut |result| {
+                        if let Some(list) = result {
+                            if let Some(item) = list.reflect_remove($self.index) {
+                                $self.item = Some(item);
+                            } else {
+                                fyrox::core::log::Log::err(format!(
+                                    "Failed to remove item {} from {} collection!",
+                                    $self.index, $self.path
+                                ))
+                            }
+                        } else {
+                            fyrox::core::log::Log::err(format!("Property {} is not a collection!", $self.path))
+                        }
+                    });
+                })
+            }
+
+            fn is_noop(&$self) -> bool {
+                $self.item.is_some()
+            }
+        }
+
+        /// Moves the item of the collection at the given path to another index, out-of-range index
+        /// is clamped to the last index. Returns the index, that the item was moved to.
+        fn move_collection_item(
+            entity: &mut dyn fyrox::core::reflect::Reflect,
+            path: &str,
+            from: usize,
+            to: usize,
+        ) -> Option<usize> {
+            let mut new_index = None;
+            try_modify_property(entity, path, |field| {
+                field.as_list_mut(&mut |result| {
+                    if let Some(list) = result {
+                        if let Some(item) = list.reflect_remove(from) {
+                            let to = to.min(list.reflect_len());
+                            match list.reflect_insert(to, item) {
+                                Ok(()) => new_index = Some(to),
+                                Err(item) => {
+                                    fyrox::core::log::Log::err(format!(
+                                        "Failed to move item {} of {} collection. Type mismatch!",
+                                        from, path
+                                    ));
+                                    let _ = list.reflect_insert(from, item);
+                                }
+                            }
+                        } else {
+                            fyrox::core::log::Log::err(format!(
+                                "Failed to move item {} of {} collection. There is no such item!",
+                                from, path
+                            ))
+                        }
+                    } else {
+                        fyrox::core::log::Log::err(format!("Property {} is not a collection!", path))
+                    }
+                })
+            });
+            new_index
+        }
+
+        #[derive(Debug)]
+        pub struct MoveCollectionItemCommand {
+            $handle_ident: $handle,
+            path: String,
+            from: usize,
+            to: usize,
+            // Whether the item was moved, so it must be moved back on revert.
+            moved: bool,
+            $($field_name: $field_type),*
+        }
+
+        impl MoveCollectionItemCommand {
+            pub fn new($handle_ident: $handle, path: String, from: usize, to: usize, $($field_name: $field_type),*) -> Self {
+                Self {
+                    $handle_ident,
+                    path,
+                    from,
+                    to,
+                    moved: false,
+                    $($field_name),*
+                }
+            }
+        }
+
+        impl $command for MoveCollectionItemCommand {
+            fn name(&mut $self, $ctx_ident: &$ctx) -> String {
+                match entity_name($ctx_ident, $self.$handle_ident) {
+                    Some(name) => format!("Move collection {} item {} to {} of '{}'", $self.path, $self.from, $self.to, name),
+                    None => format!("Move collection {} item {} to {}", $self.path, $self.from, $self.to),
+                }
+            }
+
+            fn execute(&mut $self, $ctx_ident: &mut $ctx) {
+                if let Some(to) = move_collection_item($entity_getter, &$self.path, $self.from, $self.to) {
+                    $self.to = to;
+                    $self.moved = true;
+                }
+            }
+
+            fn revert(&mut $self, $ctx_ident: &mut $ctx) {
+                if $self.moved {
+                    move_collection_item($entity_getter, &$self.path, $self.to, $self.from);
+                    $self.moved = false;
+                }
+            }
+
+            fn is_noop(&$self) -> bool {
+                !$self.moved
+            }
         }
 
         #[derive(Debug)]
         pub struct RemoveCollectionItemCommand {
-            #[allow(dead_code)]
             $handle_ident: $handle,
             path: String,
             index: usize,
@@ -208,8 +763,11 @@ macro_rules! define_universal_commands {
         }
 
         impl $command for RemoveCollectionItemCommand {
-            fn name(&mut $self, _: &$ctx) -> String {
-                format!("Remove collection {} item {}", $self.path, $self.index)
+            fn name(&mut $self, $ctx_ident: &$ctx) -> String {
+                match entity_name($ctx_ident, $self.$handle_ident) {
+                    Some(name) => format!("Remove collection {} item {} of '{}'", $self.path, $self.index, name),
+                    None => format!("Remove collection {} item {}", $self.path, $self.index),
+                }
             }
 
             fn execute(&mut $self, $ctx_ident: &mut $ctx) {
@@ -217,6 +775,12 @@ macro_rules! define_universal_commands {
                     field.as_list_mut(&mut |result| {
                         if let Some(list) = result {
                             $self.value = list.reflect_remove($self.index);
+                            if $self.value.is_none() {
+                                fyrox::core::log::Log::err(format!(
+                                    "Failed to remove item {} from {} collection. There is no such item!",
+                                    $self.index, $self.path
+                                ))
+                            }
                         } else {
                             fyrox::core::log::Log::err(format!("Property {} is not a collection!", $self.path))
                         }
@@ -225,12 +789,18 @@ macro_rules! define_universal_commands {
             }
 
             fn revert(&mut $self, $ctx_ident: &mut $ctx) {
+                // Nothing was removed.
+                if $self.value.is_none() {
+                    return;
+                }
+
+                let mut value = $self.value.take();
+
                 try_modify_property($entity_getter, &$self.path, |field| {
                     field.as_list_mut(&mut |result| {
-                         if let Some(list) = result {
-                            if let Err(item) =
-                                list.reflect_insert($self.index, $self.value.take().unwrap())
-                            {
+                        if let Some(list) = result {
+                            let index = $self.index.min(list.reflect_len());
+                            if let Err(item) = list.reflect_insert(index, value.take().unwrap()) {
                                 $self.value = Some(item);
                                 fyrox::core::log::Log::err(format!(
                                     "Failed to insert item to {} collection. Type mismatch!",
@@ -241,8 +811,635 @@ macro_rules! define_universal_commands {
                             fyrox::core::log::Log::err(format!("Property {} is not a collection!", $self.path))
                         }
                     });
-                })
+                });
+
+                // Keep the value, if it wasn't inserted back.
+                if let Some(value) = value {
+                    $self.value = Some(value);
+                }
+            }
+
+            fn is_noop(&$self) -> bool {
+                $self.value.is_none()
             }
         }
     };
+    ($name:ident, $command:ident, $command_wrapper:ty, $ctx:ty, $handle:ty, $ctx_ident:ident, $handle_ident:ident, $self:ident, $entity_getter:block, parent_value: $parent_value_getter:block, $($field_name:ident: $field_type:ty),*) => {
+        $crate::define_universal_commands!($name, $command, $command_wrapper, $ctx, $handle, $ctx_ident, $handle_ident, $self, $entity_getter, parent_value: $parent_value_getter, entity_name: $crate::command::NoEntityNames, $($field_name: $field_type),*);
+    };
+    ($name:ident, $command:ident, $command_wrapper:ty, $ctx:ty, $handle:ty, $ctx_ident:ident, $handle_ident:ident, $self:ident, $entity_getter:block, entity_name: $name_provider:ty, $($field_name:ident: $field_type:ty),*) => {
+        $crate::define_universal_commands!($name, $command, $command_wrapper, $ctx, $handle, $ctx_ident, $handle_ident, $self, $entity_getter, parent_value: { None }, entity_name: $name_provider, $($field_name: $field_type),*);
+    };
+    ($name:ident, $command:ident, $command_wrapper:ty, $ctx:ty, $handle:ty, $ctx_ident:ident, $handle_ident:ident, $self:ident, $entity_getter:block, $($field_name:ident: $field_type:ty),*) => {
+        $crate::define_universal_commands!($name, $command, $command_wrapper, $ctx, $handle, $ctx_ident, $handle_ident, $self, $entity_getter, parent_value: { None }, entity_name: $crate::command::NoEntityNames, $($field_name: $field_type),*);
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::command::{CommandAsAny, EntityNameProvider};
+    use fyrox::{
+        core::{reflect::prelude::*, variable::InheritableVariable},
+        gui::inspector::{
+            CollectionChanged, FieldKind, InheritableAction, ObjectValue, PropertyChanged,
+        },
+    };
+    use std::{any::TypeId, fmt::Debug};
+
+    #[derive(Reflect, Debug)]
+    struct Light {
+        intensity: f32,
+    }
+
+    #[derive(Reflect, Debug)]
+    struct Switch {
+        intensity: bool,
+    }
+
+    #[derive(Reflect, Debug)]
+    struct Tag {
+        enabled: bool,
+    }
+
+    #[derive(Reflect, Debug)]
+    struct Lods {
+        distances: Vec<f32>,
+    }
+
+    #[derive(Reflect, Debug)]
+    struct Prefab {
+        size: InheritableVariable<f32>,
+        mass: f32,
+    }
+
+    pub struct Context {
+        entities: Vec<Box<dyn Reflect>>,
+        // Parent of every entity.
+        parent: Option<Box<dyn Reflect>>,
+    }
+
+    pub trait TestCommand: Debug + CommandAsAny + 'static {
+        fn name(&mut self, context: &Context) -> String;
+        fn execute(&mut self, context: &mut Context);
+        fn revert(&mut self, context: &mut Context);
+        fn can_merge(&self, _other: &dyn TestCommand) -> bool {
+            false
+        }
+        fn merge(&mut self, _other: &dyn TestCommand) {}
+        fn is_noop(&self) -> bool {
+            false
+        }
+    }
+
+    /// Names entities after their indices.
+    pub struct EntityNames;
+
+    impl EntityNameProvider<Context, usize> for EntityNames {
+        fn entity_name(context: &Context, handle: usize) -> Option<String> {
+            (handle < context.entities.len()).then(|| format!("Entity{}", handle))
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct TestCommandWrapper(Box<dyn TestCommand>);
+
+    impl TestCommandWrapper {
+        fn new<C: TestCommand>(cmd: C) -> Self {
+            Self(Box::new(cmd))
+        }
+    }
+
+    define_universal_commands!(
+        make_set_entity_property_command,
+        TestCommand,
+        TestCommandWrapper,
+        Context,
+        usize,
+        ctx,
+        handle,
+        self,
+        { &mut *ctx.entities[self.handle] },
+        parent_value: {
+            ctx.parent
+                .as_ref()
+                .and_then(|parent| clone_inheritable_value(&**parent, &self.path))
+        },
+        entity_name: EntityNames,
+    );
+
+    fn intensity_changed(value: f32) -> PropertyChanged {
+        PropertyChanged {
+            name: "intensity".to_owned(),
+            owner_type_id: TypeId::of::<Light>(),
+            value: FieldKind::object(value),
+        }
+    }
+
+    fn entities(context: &Context) -> Vec<String> {
+        context
+            .entities
+            .iter()
+            .map(|entity| format!("{:?}", entity))
+            .collect()
+    }
+
+    #[test]
+    fn test_set_property_of_several_entities() {
+        let mut context = Context {
+            entities: vec![
+                Box::new(Light { intensity: 1.0 }),
+                Box::new(Switch { intensity: true }),
+                Box::new(Light { intensity: 2.0 }),
+                Box::new(Tag { enabled: false }),
+            ],
+            parent: None,
+        };
+        let initial = entities(&context);
+
+        let mut command = make_set_property_multi_command(&[0, 1, 2, 3], &intensity_changed(5.0))
+            .unwrap()
+            .0;
+        assert_eq!(
+            command.name(&context),
+            "Set intensity property of 4 objects"
+        );
+
+        // The property of the switch has another type and the last entity has no such property,
+        // they are skipped.
+        command.execute(&mut context);
+        assert_eq!(
+            entities(&context),
+            [
+                "Light { intensity: 5.0 }",
+                "Switch { intensity: true }",
+                "Light { intensity: 5.0 }",
+                "Tag { enabled: false }",
+            ]
+        );
+
+        // Every entity gets its own previous value back.
+        command.revert(&mut context);
+        assert_eq!(entities(&context), initial);
+        command.execute(&mut context);
+        command.revert(&mut context);
+        assert_eq!(entities(&context), initial);
+
+        // A single entity is changed by the regular command.
+        let mut command =
+            make_set_entity_property_command(2, &*context.entities[2], &intensity_changed(3.0))
+                .unwrap()
+                .0;
+        command.execute(&mut context);
+        assert_eq!(entities(&context)[2], "Light { intensity: 3.0 }");
+        command.revert(&mut context);
+        assert_eq!(entities(&context), initial);
+    }
+
+    #[test]
+    fn test_merge_property_changes() {
+        let mut context = Context {
+            entities: vec![
+                Box::new(Light { intensity: 1.0 }),
+                Box::new(Light { intensity: 2.0 }),
+                Box::new(Tag { enabled: false }),
+            ],
+            parent: None,
+        };
+        let initial = entities(&context);
+
+        let set = |handle, value, context: &mut Context| {
+            let mut command = make_set_entity_property_command(
+                handle,
+                &*context.entities[handle],
+                &intensity_changed(value),
+            )
+            .unwrap()
+            .0;
+            command.execute(context);
+            command
+        };
+
+        // Consecutive changes of the same property of the same entity are merged, the merged
+        // command reverts to the value before the first change and restores the last one.
+        let mut command = set(0, 3.0, &mut context);
+        for value in [4.0, 5.0] {
+            let next = set(0, value, &mut context);
+            assert!(command.can_merge(&*next));
+            command.merge(&*next);
+        }
+        command.revert(&mut context);
+        assert_eq!(entities(&context), initial);
+        command.execute(&mut context);
+        assert_eq!(entities(&context)[0], "Light { intensity: 5.0 }");
+        command.revert(&mut context);
+
+        // Changes of another entity or another property are not merged.
+        let command = set(0, 3.0, &mut context);
+        assert!(!command.can_merge(&*set(1, 4.0, &mut context)));
+        assert!(!command.can_merge(&SetPropertyCommand::new(
+            0,
+            "enabled".to_owned(),
+            Box::new(true)
+        )));
+        assert!(!command.can_merge(
+            &*make_set_property_multi_command(&[0], &intensity_changed(5.0))
+                .unwrap()
+                .0
+        ));
+        assert!(!command.can_merge(&AddCollectionItemCommand::new(
+            0,
+            "intensity".to_owned(),
+            Box::new(5.0f32)
+        )));
+
+        // Change that was not applied is not merged, and nothing is merged into it.
+        let not_applied = SetPropertyCommand::new(0, "intensity".to_owned(), Box::new(4.0f32));
+        assert!(!command.can_merge(&not_applied));
+        assert!(!not_applied.can_merge(&*command));
+
+        // Changes of several entities are merged, if they're made to the same entities.
+        let multi = |handles: &[usize]| {
+            make_set_property_multi_command(handles, &intensity_changed(6.0))
+                .unwrap()
+                .0
+        };
+        assert!(multi(&[0, 1]).can_merge(&*multi(&[0, 1])));
+        assert!(!multi(&[0, 1]).can_merge(&*multi(&[0])));
+        assert!(!multi(&[0, 1]).can_merge(&*multi(&[1, 0])));
+    }
+
+    fn prefab(context: &Context, handle: usize) -> (f32, bool, f32) {
+        let mut state = None;
+        context.entities[handle].downcast_ref::<Prefab>(&mut |prefab| {
+            state = prefab.map(|prefab| (*prefab.size, prefab.size.is_modified(), prefab.mass));
+        });
+        state.unwrap()
+    }
+
+    #[test]
+    fn test_revert_property_to_parent() {
+        let mut context = Context {
+            entities: vec![Box::new(Prefab {
+                size: InheritableVariable::new_modified(2.0),
+                mass: 2.0,
+            })],
+            parent: Some(Box::new(Prefab {
+                size: InheritableVariable::new_non_modified(1.0),
+                mass: 1.0,
+            })),
+        };
+
+        let revert_changed = |name: &str| PropertyChanged {
+            name: name.to_owned(),
+            owner_type_id: TypeId::of::<Prefab>(),
+            value: FieldKind::Inheritable(InheritableAction::Revert),
+        };
+
+        let mut command =
+            make_set_entity_property_command(0, &*context.entities[0], &revert_changed("size"))
+                .unwrap()
+                .0;
+        assert_eq!(command.name(&context), "Revert size of 'Entity0'");
+
+        // The property takes the value of the parent and is inherited from it again.
+        command.execute(&mut context);
+        assert_eq!(prefab(&context, 0), (1.0, false, 2.0));
+        command.revert(&mut context);
+        assert_eq!(prefab(&context, 0), (2.0, true, 2.0));
+        command.execute(&mut context);
+        assert_eq!(prefab(&context, 0), (1.0, false, 2.0));
+
+        // Non-modified property is kept as is, reverting such command does nothing too.
+        let mut command = RevertPropertyCommand::new(0, "size".to_owned());
+        command.execute(&mut context);
+        command.revert(&mut context);
+        assert_eq!(prefab(&context, 0), (1.0, false, 2.0));
+
+        // Non-inheritable property can't be reverted.
+        let mut command = RevertPropertyCommand::new(0, "mass".to_owned());
+        command.execute(&mut context);
+        assert_eq!(prefab(&context, 0), (1.0, false, 2.0));
+
+        // Nothing is changed, if there's no parent.
+        context.parent = None;
+        context.entities[0] = Box::new(Prefab {
+            size: InheritableVariable::new_modified(2.0),
+            mass: 2.0,
+        });
+        let mut command = RevertPropertyCommand::new(0, "size".to_owned());
+        command.execute(&mut context);
+        command.revert(&mut context);
+        assert_eq!(prefab(&context, 0), (2.0, true, 2.0));
+    }
+
+    #[test]
+    fn test_insert_collection_item() {
+        let mut context = Context {
+            entities: vec![Box::new(Lods {
+                distances: vec![1.0, 3.0],
+            })],
+            parent: None,
+        };
+
+        let insert = |index: usize, value: f32, context: &Context| {
+            make_set_entity_property_command(
+                0,
+                &*context.entities[0],
+                &PropertyChanged {
+                    name: "distances".to_owned(),
+                    owner_type_id: TypeId::of::<Lods>(),
+                    value: FieldKind::Collection(Box::new(CollectionChanged::Insert {
+                        index,
+                        value: ObjectValue {
+                            value: Box::new(value),
+                        },
+                    })),
+                },
+            )
+            .unwrap()
+            .0
+        };
+
+        let mut command = insert(1, 2.0, &context);
+        assert_eq!(
+            command.name(&context),
+            "Insert item 1 to distances collection of 'Entity0'"
+        );
+        command.execute(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 2.0, 3.0] }"]);
+        command.revert(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 3.0] }"]);
+
+        // Out-of-range index means the end of the collection.
+        let mut command = insert(10, 4.0, &context);
+        command.execute(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 3.0, 4.0] }"]);
+        command.revert(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 3.0] }"]);
+        command.execute(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 3.0, 4.0] }"]);
+
+        // Item of another type is not inserted, reverting such command keeps the collection.
+        let mut command =
+            InsertCollectionItemCommand::new(0, "distances".to_owned(), 0, Box::new(true));
+        command.execute(&mut context);
+        command.revert(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 3.0, 4.0] }"]);
+    }
+
+    #[test]
+    fn test_move_collection_item() {
+        let mut context = Context {
+            entities: vec![Box::new(Lods {
+                distances: vec![1.0, 2.0, 3.0],
+            })],
+            parent: None,
+        };
+
+        let move_item = |from: usize, to: usize, context: &Context| {
+            make_set_entity_property_command(
+                0,
+                &*context.entities[0],
+                &PropertyChanged {
+                    name: "distances".to_owned(),
+                    owner_type_id: TypeId::of::<Lods>(),
+                    value: FieldKind::Collection(Box::new(CollectionChanged::Move { from, to })),
+                },
+            )
+            .map(|command| command.0)
+        };
+
+        // Moving to the same index is not a command at all.
+        assert!(move_item(1, 1, &context).is_none());
+
+        let mut command = move_item(0, 2, &context).unwrap();
+        assert_eq!(
+            command.name(&context),
+            "Move collection distances item 0 to 2 of 'Entity0'"
+        );
+        command.execute(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [2.0, 3.0, 1.0] }"]);
+        command.revert(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 2.0, 3.0] }"]);
+
+        let mut command = move_item(2, 1, &context).unwrap();
+        command.execute(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 3.0, 2.0] }"]);
+        command.revert(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 2.0, 3.0] }"]);
+
+        // Out-of-range index means the last one.
+        let mut command = move_item(1, 10, &context).unwrap();
+        command.execute(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 3.0, 2.0] }"]);
+        command.revert(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 2.0, 3.0] }"]);
+
+        // Nothing is moved, if there's no such item.
+        let mut command = move_item(5, 0, &context).unwrap();
+        command.execute(&mut context);
+        assert!(command.is_noop());
+        command.revert(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 2.0, 3.0] }"]);
+    }
+
+    #[test]
+    fn test_remove_missing_collection_item() {
+        let mut context = Context {
+            entities: vec![Box::new(Lods {
+                distances: vec![1.0, 2.0, 3.0],
+            })],
+            parent: None,
+        };
+
+        for _ in 0..2 {
+            let mut command = make_set_entity_property_command(
+                0,
+                &*context.entities[0],
+                &PropertyChanged {
+                    name: "distances".to_owned(),
+                    owner_type_id: TypeId::of::<Lods>(),
+                    value: FieldKind::Collection(Box::new(CollectionChanged::Remove(5))),
+                },
+            )
+            .unwrap()
+            .0;
+
+            command.execute(&mut context);
+            assert!(command.is_noop());
+            assert_eq!(entities(&context), ["Lods { distances: [1.0, 2.0, 3.0] }"]);
+
+            command.revert(&mut context);
+            assert_eq!(entities(&context), ["Lods { distances: [1.0, 2.0, 3.0] }"]);
+        }
+
+        let mut command = RemoveCollectionItemCommand::new(0, "distances".to_owned(), 1);
+        command.execute(&mut context);
+        assert!(!command.is_noop());
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 3.0] }"]);
+        command.revert(&mut context);
+        assert_eq!(entities(&context), ["Lods { distances: [1.0, 2.0, 3.0] }"]);
+    }
+
+    #[test]
+    fn test_command_names() {
+        let context = Context {
+            entities: vec![Box::new(Lods {
+                distances: vec![1.0, 2.0, 3.0],
+            })],
+            parent: None,
+        };
+
+        // Changes are checked against the only entity, even if a command is made for a handle of
+        // a deleted entity.
+        let name = |handle: usize, value: FieldKind| {
+            make_set_entity_property_command(
+                handle,
+                &*context.entities[0],
+                &PropertyChanged {
+                    name: "distances".to_owned(),
+                    owner_type_id: TypeId::of::<Lods>(),
+                    value,
+                },
+            )
+            .unwrap()
+            .0
+            .name(&context)
+        };
+
+        assert_eq!(
+            name(0, FieldKind::object(vec![4.0f32])),
+            "Set distances of 'Entity0'"
+        );
+        assert_eq!(
+            name(
+                0,
+                FieldKind::Collection(Box::new(CollectionChanged::Add(ObjectValue {
+                    value: Box::new(4.0f32),
+                })))
+            ),
+            "Add item to distances collection of 'Entity0'"
+        );
+        assert_eq!(
+            name(
+                0,
+                FieldKind::Collection(Box::new(CollectionChanged::Remove(1)))
+            ),
+            "Remove collection distances item 1 of 'Entity0'"
+        );
+
+        // Names of the commands of deleted entities are not resolved.
+        assert_eq!(
+            name(1, FieldKind::object(vec![4.0f32])),
+            "Set distances property"
+        );
+        assert_eq!(
+            name(
+                1,
+                FieldKind::Collection(Box::new(CollectionChanged::Remove(1)))
+            ),
+            "Remove collection distances item 1"
+        );
+    }
+
+    #[test]
+    fn test_invalid_property_changes_make_no_commands() {
+        let mut context = Context {
+            entities: vec![Box::new(Light { intensity: 1.0 })],
+            parent: None,
+        };
+
+        let make = |name: &str, value: FieldKind, context: &Context| {
+            make_set_entity_property_command(
+                0,
+                &*context.entities[0],
+                &PropertyChanged {
+                    name: name.to_owned(),
+                    owner_type_id: TypeId::of::<Light>(),
+                    value,
+                },
+            )
+            .map(|command| command.0)
+        };
+
+        // Stale path.
+        assert!(make("radius", FieldKind::object(2.0f32), &context).is_none());
+        assert!(make(
+            "radius",
+            FieldKind::Collection(Box::new(CollectionChanged::Remove(0))),
+            &context
+        )
+        .is_none());
+
+        // Value of another type.
+        assert!(make("intensity", FieldKind::object(2.0f64), &context).is_none());
+        assert!(make("intensity", FieldKind::object(true), &context).is_none());
+        assert_eq!(entities(&context), ["Light { intensity: 1.0 }"]);
+
+        let mut command = make("intensity", FieldKind::object(2.0f32), &context).unwrap();
+        command.execute(&mut context);
+        assert!(!command.is_noop());
+        assert_eq!(entities(&context), ["Light { intensity: 2.0 }"]);
+        command.revert(&mut context);
+        assert_eq!(entities(&context), ["Light { intensity: 1.0 }"]);
+    }
+
+    #[test]
+    fn test_reset_property_to_default() {
+        let mut context = Context {
+            entities: vec![
+                Box::new(Prefab {
+                    size: InheritableVariable::new_non_modified(2.0),
+                    mass: 2.0,
+                }),
+                Box::new(Lods {
+                    distances: vec![1.0, 2.0],
+                }),
+            ],
+            parent: None,
+        };
+
+        let reset = |handle: usize, name: &str, context: &Context| {
+            make_set_entity_property_command(
+                handle,
+                &*context.entities[handle],
+                &PropertyChanged {
+                    name: name.to_owned(),
+                    owner_type_id: TypeId::of::<Prefab>(),
+                    value: FieldKind::ResetToDefault,
+                },
+            )
+            .map(|command| command.0)
+        };
+
+        let mut command = reset(0, "mass", &context).unwrap();
+        assert_eq!(command.name(&context), "Reset mass of 'Entity0' to default");
+        command.execute(&mut context);
+        assert_eq!(prefab(&context, 0), (2.0, false, 0.0));
+        command.revert(&mut context);
+        assert_eq!(prefab(&context, 0), (2.0, false, 2.0));
+
+        // Inheritable property takes the default value of its inner type and becomes modified,
+        // revert makes it inherited again.
+        let mut command = reset(0, "size", &context).unwrap();
+        command.execute(&mut context);
+        assert_eq!(prefab(&context, 0), (0.0, true, 2.0));
+        command.revert(&mut context);
+        assert_eq!(prefab(&context, 0), (2.0, false, 2.0));
+
+        // Modified inheritable property stays modified after revert.
+        context.entities[0] = Box::new(Prefab {
+            size: InheritableVariable::new_modified(3.0),
+            mass: 2.0,
+        });
+        let mut command = reset(0, "size", &context).unwrap();
+        command.execute(&mut context);
+        assert_eq!(prefab(&context, 0), (0.0, true, 2.0));
+        command.revert(&mut context);
+        assert_eq!(prefab(&context, 0), (3.0, true, 2.0));
+
+        // There's no default value of collections and no such property at all.
+        assert!(reset(1, "distances", &context).is_none());
+        assert!(reset(0, "radius", &context).is_none());
+    }
 }
@@ -36,11 +36,15 @@ use std::{
 pub enum HandlePropertyEditorMessage {
     Value(Handle<Node>),
     Name(Option<String>),
+    /// Sent by the editor when its pick button is clicked. The owner of the inspector should let
+    /// the user pick a node and assign it using [`HandlePropertyEditorMessage::Value`].
+    Pick,
 }
 
 impl HandlePropertyEditorMessage {
     define_constructor!(HandlePropertyEditorMessage:Value => fn value(Handle<Node>), layout: false);
     define_constructor!(HandlePropertyEditorMessage:Name => fn name(Option<String>), layout: false);
+    define_constructor!(HandlePropertyEditorMessage:Pick => fn pick(), layout: false);
 }
 
 #[derive(Debug)]
@@ -49,6 +53,7 @@ pub struct HandlePropertyEditor {
     text: Handle<UiNode>,
     locate: Handle<UiNode>,
     select: Handle<UiNode>,
+    pick: Handle<UiNode>,
     value: Handle<Node>,
     sender: MessageSender,
 }
@@ -62,6 +67,7 @@ impl Clone for HandlePropertyEditor {
             sender: self.sender.clone(),
             locate: self.locate,
             select: self.select,
+            pick: self.pick,
         }
     }
 }
@@ -155,6 +161,7 @@ impl Control for HandlePropertyEditor {
                             ));
                         };
                     }
+                    HandlePropertyEditorMessage::Pick => (),
                 }
             }
         } else if let Some(WidgetMessage::Drop(dropped)) = message.data() {
@@ -178,6 +185,11 @@ impl Control for HandlePropertyEditor {
                     type_id: TypeId::of::<Node>(),
                     handle: self.value.into(),
                 });
+            } else if message.destination == self.pick {
+                ui.send_message(HandlePropertyEditorMessage::pick(
+                    self.handle(),
+                    MessageDirection::FromWidget,
+                ));
             }
         }
     }
@@ -187,6 +199,7 @@ struct HandlePropertyEditorBuilder {
     widget_builder: WidgetBuilder,
     value: Handle<Node>,
     sender: MessageSender,
+    with_pick: bool,
 }
 
 impl HandlePropertyEditorBuilder {
@@ -195,6 +208,7 @@ impl HandlePropertyEditorBuilder {
             widget_builder,
             sender,
             value: Default::default(),
+            with_pick: false,
         }
     }
 
@@ -203,10 +217,28 @@ impl HandlePropertyEditorBuilder {
         self
     }
 
+    pub fn with_pick(mut self, with_pick: bool) -> Self {
+        self.with_pick = with_pick;
+        self
+    }
+
     pub fn build(self, ctx: &mut BuildContext) -> Handle<UiNode> {
         let text;
         let locate;
         let select;
+        let pick = if self.with_pick {
+            ButtonBuilder::new(
+                WidgetBuilder::new()
+                    .with_tooltip(make_simple_tooltip(ctx, "Pick in Viewport"))
+                    .with_width(20.0)
+                    .with_height(20.0)
+                    .on_column(3),
+            )
+            .with_text("+")
+            .build(ctx)
+        } else {
+            Handle::NONE
+        };
         let grid = GridBuilder::new(
             WidgetBuilder::new()
                 .with_child({
@@ -243,12 +275,14 @@ impl HandlePropertyEditorBuilder {
                     .with_text("*")
                     .build(ctx);
                     select
-                }),
+                })
+                .with_child(pick),
         )
         .add_row(Row::stretch())
         .add_column(Column::stretch())
         .add_column(Column::auto())
         .add_column(Column::auto())
+        .add_column(Column::auto())
         .build(ctx);
 
         let editor = HandlePropertyEditor {
@@ -266,6 +300,7 @@ impl HandlePropertyEditorBuilder {
             sender: self.sender,
             locate,
             select,
+            pick,
         };
 
         ctx.add_node(UiNode::new(editor))
@@ -275,14 +310,23 @@ impl HandlePropertyEditorBuilder {
 #[derive(Debug)]
 pub struct NodeHandlePropertyEditorDefinition {
     sender: Mutex<MessageSender>,
+    with_pick: bool,
 }
 
 impl NodeHandlePropertyEditorDefinition {
     pub fn new(sender: MessageSender) -> Self {
         Self {
             sender: Mutex::new(sender),
+            with_pick: false,
         }
     }
+
+    /// Adds a button to pick the node in the viewport to every editor. Clicking the button sends
+    /// [`HandlePropertyEditorMessage::Pick`], it must be handled by the owner of the inspector.
+    pub fn with_pick(mut self) -> Self {
+        self.with_pick = true;
+        self
+    }
 }
 
 impl PropertyEditorDefinition for NodeHandlePropertyEditorDefinition {
@@ -300,6 +344,7 @@ impl PropertyEditorDefinition for NodeHandlePropertyEditorDefinition {
 
         let editor = HandlePropertyEditorBuilder::new(WidgetBuilder::new(), sender.clone())
             .with_value(*value)
+            .with_pick(self.with_pick)
             .build(ctx.build_context);
 
         request_name_sync(&sender, editor, *value);
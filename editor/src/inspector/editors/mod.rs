@@ -13,6 +13,11 @@ use crate::{
         texture::TexturePropertyEditorDefinition,
     },
     message::MessageSender,
+    utils::ragdoll::preset::{
+        RagdollBodyType, RagdollBoneChain, RagdollHingeAxis, RagdollJointAnchor,
+        RagdollJointLimits, RagdollKinematicKind, RagdollLimbGroupOverrides, RagdollLimbOverrides,
+        RagdollLimitShape, RagdollOutputMode, RagdollPhysics, RagdollProportions,
+    },
 };
 use fyrox::scene::ragdoll::Limb;
 use fyrox::{
@@ -258,7 +263,7 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
     container.register_inheritable_inspectable::<CuboidEmitter>();
     container.register_inheritable_inspectable::<PerspectiveProjection>();
     container.register_inheritable_inspectable::<OrthographicProjection>();
-    container.register_inheritable_inspectable::<Transform>();
+    container.register_inheritable_cloneable_inspectable::<Transform>();
     container.register_inheritable_inspectable::<CsmOptions>();
 
     container.register_inheritable_inspectable::<Chunk>();
@@ -358,6 +363,22 @@ pub fn make_property_editors_container(sender: MessageSender) -> PropertyEditorD
 
     container.register_inheritable_inspectable::<Limb>();
     container.insert(VecCollectionPropertyEditorDefinition::<Limb>::new());
+    container.insert(InspectablePropertyEditorDefinition::<RagdollProportions>::new());
+    container.insert(EnumPropertyEditorDefinition::<RagdollBodyType>::new());
+    container.insert(EnumPropertyEditorDefinition::<RagdollKinematicKind>::new());
+    container.insert(InspectablePropertyEditorDefinition::<RagdollPhysics>::new());
+    container.insert(InspectablePropertyEditorDefinition::<RagdollJointLimits>::new());
+    container.insert(EnumPropertyEditorDefinition::<RagdollLimitShape>::new());
+    container.insert(EnumPropertyEditorDefinition::<RagdollHingeAxis>::new());
+    container.insert(EnumPropertyEditorDefinition::<RagdollJointAnchor>::new());
+    container.insert(EnumPropertyEditorDefinition::<RagdollOutputMode>::new());
+    container.insert(InspectablePropertyEditorDefinition::<
+        RagdollLimbGroupOverrides,
+    >::new());
+    container.insert(InspectablePropertyEditorDefinition::<RagdollLimbOverrides>::new());
+    container.insert(InspectablePropertyEditorDefinition::<RagdollBoneChain>::new());
+    container.insert(VecCollectionPropertyEditorDefinition::<RagdollBoneChain>::new());
+    container.insert(EnumPropertyEditorDefinition::<bool>::new_optional());
 
     container
 }
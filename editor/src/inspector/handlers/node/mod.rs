@@ -1,15 +1,14 @@
-use crate::scene::commands::RevertSceneNodePropertyCommand;
 use crate::{
     scene::commands::{
-        make_set_node_property_command,
-        terrain::{AddTerrainLayerCommand, DeleteTerrainLayerCommand},
+        make_set_node_property_command, make_set_property_multi_command,
+        terrain::{AddTerrainLayerCommand, DeleteTerrainLayerCommand, MoveTerrainLayerCommand},
     },
     SceneCommand,
 };
 use fyrox::{
-    core::pool::Handle,
+    core::{pool::Handle, reflect::prelude::*},
     gui::inspector::{CollectionChanged, FieldKind, PropertyChanged},
-    scene::{node::Node, terrain::Terrain},
+    scene::{graph::Graph, node::Node, terrain::Terrain},
 };
 use std::any::TypeId;
 
@@ -32,6 +31,12 @@ impl SceneNodePropertyChangedHandler {
                     CollectionChanged::Remove(index) => Some(SceneCommand::new(
                         DeleteTerrainLayerCommand::new(handle, index),
                     )),
+                    CollectionChanged::Insert { index, .. } => Some(SceneCommand::new(
+                        AddTerrainLayerCommand::new(handle).with_index(index),
+                    )),
+                    CollectionChanged::Move { from, to } => Some(SceneCommand::new(
+                        MoveTerrainLayerCommand::new(handle, from, to),
+                    )),
                     CollectionChanged::ItemChanged { .. } => None,
                 },
                 _ => None,
@@ -50,19 +55,42 @@ impl SceneNodePropertyChangedHandler {
         node: &mut Node,
     ) -> Option<SceneCommand> {
         self.try_get_command(args, handle, node).or_else(|| {
-            if args.is_inheritable() {
-                // Prevent reverting property value if there's no parent resource.
-                if node.resource().is_some() {
-                    Some(SceneCommand::new(RevertSceneNodePropertyCommand::new(
-                        args.path(),
-                        handle,
-                    )))
-                } else {
-                    None
-                }
+            // Prevent reverting property value if there's no parent resource.
+            if args.is_inheritable() && node.resource().is_none() {
+                None
             } else {
-                make_set_node_property_command(handle, args)
+                make_set_node_property_command(handle, &*node, args)
             }
         })
     }
+
+    /// Handles the change of the property of several nodes. Nodes of the same type take the new
+    /// value by one command, other changes are handled for every node separately.
+    pub fn handle_many(
+        &self,
+        args: &PropertyChanged,
+        nodes: &[Handle<Node>],
+        graph: &mut Graph,
+    ) -> Vec<SceneCommand> {
+        if nodes.len() > 1 && is_homogeneous(graph, nodes) {
+            if let Some(command) = make_set_property_multi_command(nodes, args) {
+                return vec![command];
+            }
+        }
+
+        nodes
+            .iter()
+            .filter_map(|&node| self.handle(args, node, &mut graph[node]))
+            .collect()
+    }
+}
+
+fn is_homogeneous(graph: &Graph, nodes: &[Handle<Node>]) -> bool {
+    let type_name = |handle: Handle<Node>| {
+        let mut name = "";
+        graph[handle].as_reflect(&mut |node| name = node.type_name());
+        name
+    };
+    let first = type_name(nodes[0]);
+    nodes.iter().all(|&node| type_name(node) == first)
 }
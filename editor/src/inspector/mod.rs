@@ -28,23 +28,26 @@ use fyrox::{
         reflect::prelude::*,
     },
     engine::SerializationContext,
+    fxhash::FxHashSet,
     gui::{
         button::ButtonMessage,
         grid::{Column, GridBuilder, Row},
         inspector::{
-            editors::PropertyEditorDefinitionContainer, InspectorBuilder, InspectorContext,
-            InspectorEnvironment, InspectorMessage,
+            editors::PropertyEditorDefinitionContainer, ContextEntry, CopiedValue,
+            InspectorBuilder, InspectorContext, InspectorEnvironment, InspectorMessage,
         },
         message::{MessageDirection, UiMessage},
         scroll_viewer::ScrollViewerBuilder,
-        text::{TextBuilder, TextMessage},
+        text::{Text, TextBuilder, TextMessage},
+        utils::make_simple_tooltip,
         widget::WidgetBuilder,
         window::{WindowBuilder, WindowTitle},
-        BuildContext, Thickness, UiNode, UserInterface,
+        BuildContext, Thickness, UiNode, UserInterface, BRUSH_FOREGROUND,
     },
     scene::{
         animation::{absm::AnimationBlendingStateMachine, AnimationPlayer},
         graph::Graph,
+        Scene,
     },
 };
 use std::{any::Any, rc::Rc, sync::Arc};
@@ -90,10 +93,15 @@ pub struct Inspector {
     // got new context - in this case we don't need to sync with model, because
     // inspector is already in correct state.
     needs_sync: bool,
+    // Mixed properties of a multi-selection are computed only when the selection is changed or
+    // a command is executed, comparing the values on every sync is too slow.
+    mixed_properties_outdated: bool,
     node_property_changed_handler: SceneNodePropertyChangedHandler,
     warning_text: Handle<UiNode>,
     type_name_text: Handle<UiNode>,
     docs_button: Handle<UiNode>,
+    /// A value, that was copied using the context menu of the inspector.
+    copied_value: Option<CopiedValue>,
 }
 
 #[macro_export]
@@ -135,13 +143,184 @@ macro_rules! handle_property_changed {
     }
 }
 
+/// Collects names of the shown properties, that have different values across the selected scene
+/// nodes. The inspector shows the values of the first node only, so such properties must be shown
+/// in an indeterminate state to not mislead the user. Nodes of different types have no mixed
+/// properties, because only common properties are editable in this case.
+fn mixed_properties(
+    graph: &Graph,
+    selection: &Selection,
+    entries: &[ContextEntry],
+) -> FxHashSet<String> {
+    let mut mixed = FxHashSet::default();
+
+    if let Selection::Graph(selection) = selection {
+        let nodes = selection
+            .nodes()
+            .iter()
+            .filter_map(|&handle| graph.try_get(handle))
+            .collect::<Vec<_>>();
+
+        let mut type_names = Vec::new();
+        for node in nodes.iter() {
+            node.as_reflect(&mut |node| type_names.push(node.type_name()));
+        }
+        if type_names.windows(2).any(|pair| pair[0] != pair[1]) {
+            return mixed;
+        }
+
+        for entry in entries {
+            let mut first_value = None;
+            for node in nodes.iter() {
+                let mut value = None;
+                node.as_reflect(&mut |node| {
+                    node.field(&entry.property_name, &mut |field| {
+                        value = field.map(|field| format!("{:?}", field));
+                    })
+                });
+
+                match first_value {
+                    None => first_value = Some(value),
+                    Some(ref first_value) if *first_value != value => {
+                        mixed.insert(entry.property_name.clone());
+                        break;
+                    }
+                    _ => (),
+                }
+            }
+        }
+    }
+
+    mixed
+}
+
+/// Highlights headers of the properties with mixed values and dims their editors. Only simple
+/// properties (a header and an editor) are affected, compound properties are left as is.
+fn mark_mixed_properties(
+    entries: &[ContextEntry],
+    mixed: &FxHashSet<String>,
+    ui: &mut UserInterface,
+) {
+    for entry in entries {
+        let header = ui
+            .try_get_node(entry.property_container)
+            .and_then(|container| container.children().first().cloned())
+            .filter(|header| ui.node(*header).cast::<Text>().is_some());
+
+        if let Some(header) = header {
+            let is_mixed = mixed.contains(&entry.property_name);
+
+            ui.send_message(WidgetMessage::foreground(
+                header,
+                MessageDirection::ToWidget,
+                if is_mixed {
+                    Brush::Solid(Color::opaque(255, 190, 0))
+                } else {
+                    BRUSH_FOREGROUND
+                },
+            ));
+            let tooltip = if is_mixed {
+                Some(make_simple_tooltip(
+                    &mut ui.build_ctx(),
+                    "Selected objects have different values of this property. \
+                    Editing it will set the same value for every selected object.",
+                ))
+            } else {
+                None
+            };
+            ui.send_message(WidgetMessage::tooltip(
+                header,
+                MessageDirection::ToWidget,
+                tooltip,
+            ));
+            ui.send_message(WidgetMessage::opacity(
+                entry.property_editor,
+                MessageDirection::ToWidget,
+                if is_mixed { Some(0.4) } else { None },
+            ));
+        }
+    }
+}
+
+/// Calls the function with the first selected object, the inspector shows properties of this
+/// object.
+fn first_selected_object(
+    editor_scene: &EditorScene,
+    scene: &Scene,
+    func: &mut dyn FnMut(&dyn Reflect),
+) {
+    match &editor_scene.selection {
+        Selection::Graph(selection) => {
+            if let Some(node) = selection
+                .nodes()
+                .first()
+                .and_then(|&node| scene.graph.try_get(node))
+            {
+                node.as_reflect(&mut |node| func(node))
+            }
+        }
+        Selection::AudioBus(selection) => {
+            let state = scene.graph.sound_context.state();
+            if let Some(effect) = selection
+                .buses
+                .first()
+                .and_then(|&bus| state.bus_graph_ref().try_get_bus_ref(bus))
+            {
+                func(effect as &dyn Reflect);
+            }
+        }
+        Selection::Animation(selection) => {
+            if let Some(animation) = scene
+                .graph
+                .try_get_of_type::<AnimationPlayer>(selection.animation_player)
+                .and_then(|player| player.animations().try_get(selection.animation))
+            {
+                if let Some(animation::selection::SelectedEntity::Signal(id)) =
+                    selection.entities.first()
+                {
+                    if let Some(signal) = animation.signals().iter().find(|s| s.id == *id) {
+                        func(signal as &dyn Reflect);
+                    }
+                }
+            }
+        }
+        Selection::Absm(selection) => {
+            if let Some(node) = scene
+                .graph
+                .try_get_of_type::<AnimationBlendingStateMachine>(selection.absm_node_handle)
+            {
+                if let Some(first) = selection.entities.first() {
+                    let machine = node.machine();
+                    if let Some(layer_index) = selection.layer {
+                        if let Some(layer) = machine.layers().get(layer_index) {
+                            match first {
+                                SelectedEntity::Transition(transition) => {
+                                    func(&layer.transitions()[*transition] as &dyn Reflect);
+                                }
+                                SelectedEntity::State(state) => {
+                                    func(&layer.states()[*state] as &dyn Reflect);
+                                }
+                                SelectedEntity::PoseNode(pose) => {
+                                    func(&layer.nodes()[*pose] as &dyn Reflect);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        _ => (),
+    }
+}
+
 impl Inspector {
     pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
         let property_editors = Rc::new(make_property_editors_container(sender));
 
         let warning_text_str =
             "Multiple objects are selected, showing properties of the first object only!\
-            Only common properties will be editable!";
+            Only common properties will be editable! Properties with different values are \
+            highlighted.";
 
         let warning_text;
         let type_name_text;
@@ -222,10 +401,12 @@ impl Inspector {
             inspector,
             property_editors,
             needs_sync: true,
+            mixed_properties_outdated: false,
             node_property_changed_handler: SceneNodePropertyChangedHandler,
             warning_text,
             type_name_text,
             docs_button,
+            copied_value: None,
         }
     }
 
@@ -252,82 +433,23 @@ impl Inspector {
 
         if self.needs_sync {
             if editor_scene.selection.is_single_selection() {
-                match &editor_scene.selection {
-                    Selection::Graph(selection) => {
-                        if let Some(node) = scene.graph.try_get(selection.nodes()[0]) {
-                            node.as_reflect(&mut |node| {
-                                self.sync_to(node, &mut engine.user_interface)
-                            })
-                        }
-                    }
-
-                    Selection::AudioBus(selection) => {
-                        let state = scene.graph.sound_context.state();
-                        if let Some(effect) =
-                            state.bus_graph_ref().try_get_bus_ref(selection.buses[0])
-                        {
-                            self.sync_to(effect as &dyn Reflect, &mut engine.user_interface);
-                        }
-                    }
-                    Selection::Animation(selection) => {
-                        if let Some(animation) = scene
-                            .graph
-                            .try_get_of_type::<AnimationPlayer>(selection.animation_player)
-                            .and_then(|player| player.animations().try_get(selection.animation))
-                        {
-                            if let Some(animation::selection::SelectedEntity::Signal(id)) =
-                                selection.entities.first()
-                            {
-                                if let Some(signal) =
-                                    animation.signals().iter().find(|s| s.id == *id)
-                                {
-                                    self.sync_to(
-                                        signal as &dyn Reflect,
-                                        &mut engine.user_interface,
-                                    );
-                                }
-                            }
-                        }
-                    }
-                    Selection::Absm(selection) => {
-                        if let Some(node) = scene
-                            .graph
-                            .try_get_of_type::<AnimationBlendingStateMachine>(
-                                selection.absm_node_handle,
-                            )
-                        {
-                            if let Some(first) = selection.entities.first() {
-                                let machine = node.machine();
-                                if let Some(layer_index) = selection.layer {
-                                    if let Some(layer) = machine.layers().get(layer_index) {
-                                        match first {
-                                            SelectedEntity::Transition(transition) => {
-                                                self.sync_to(
-                                                    &layer.transitions()[*transition]
-                                                        as &dyn Reflect,
-                                                    &mut engine.user_interface,
-                                                );
-                                            }
-                                            SelectedEntity::State(state) => {
-                                                self.sync_to(
-                                                    &layer.states()[*state] as &dyn Reflect,
-                                                    &mut engine.user_interface,
-                                                );
-                                            }
-                                            SelectedEntity::PoseNode(pose) => {
-                                                self.sync_to(
-                                                    &layer.nodes()[*pose] as &dyn Reflect,
-                                                    &mut engine.user_interface,
-                                                );
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                    _ => (),
-                };
+                let ui = &mut engine.user_interface;
+                first_selected_object(editor_scene, scene, &mut |object| self.sync_to(object, ui));
+            } else if self.mixed_properties_outdated
+                && matches!(editor_scene.selection, Selection::Graph(_))
+            {
+                self.mixed_properties_outdated = false;
+
+                let context = engine
+                    .user_interface
+                    .node(self.inspector)
+                    .cast::<fyrox::gui::inspector::Inspector>()
+                    .unwrap()
+                    .context()
+                    .clone();
+                let mixed =
+                    mixed_properties(&scene.graph, &editor_scene.selection, &context.entries);
+                mark_mixed_properties(&context.entries, &mixed, &mut engine.user_interface);
             }
         } else {
             self.needs_sync = true;
@@ -384,6 +506,12 @@ impl Inspector {
 
         self.needs_sync = false;
 
+        if selection.len() > 1 {
+            let mixed = mixed_properties(graph, selection, &context.entries);
+            mark_mixed_properties(&context.entries, &mixed, ui);
+        }
+        self.mixed_properties_outdated = false;
+
         ui.send_message(InspectorMessage::context(
             self.inspector,
             MessageDirection::ToWidget,
@@ -407,6 +535,15 @@ impl Inspector {
         engine: &mut Engine,
         sender: &MessageSender,
     ) {
+        if let Message::DoSceneCommand(_)
+        | Message::UndoSceneCommand
+        | Message::RedoSceneCommand
+        | Message::StepSceneCommand
+        | Message::AbortSceneCommand = message
+        {
+            self.mixed_properties_outdated = true;
+        }
+
         if let Message::SelectionChanged { .. } = message {
             let scene = &engine.scenes[editor_scene.scene];
 
@@ -562,40 +699,48 @@ impl Inspector {
                 message.data::<InspectorMessage>()
             {
                 let group = match &editor_scene.selection {
-                    Selection::Graph(selection) => selection
-                        .nodes
-                        .iter()
-                        .filter_map(|&node_handle| {
-                            if scene.graph.is_valid_handle(node_handle) {
-                                self.node_property_changed_handler.handle(
-                                    args,
-                                    node_handle,
-                                    &mut scene.graph[node_handle],
-                                )
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>(),
-                    Selection::AudioBus(selection) => selection
-                        .buses
-                        .iter()
-                        .filter_map(|&handle| make_set_audio_bus_property_command(handle, args))
-                        .collect::<Vec<_>>(),
+                    Selection::Graph(selection) => {
+                        let nodes = selection
+                            .nodes
+                            .iter()
+                            .cloned()
+                            .filter(|&node_handle| scene.graph.is_valid_handle(node_handle))
+                            .collect::<Vec<_>>();
+                        self.node_property_changed_handler.handle_many(
+                            args,
+                            &nodes,
+                            &mut scene.graph,
+                        )
+                    }
+                    Selection::AudioBus(selection) => {
+                        let state = scene.graph.sound_context.state();
+                        let bus_graph = state.bus_graph_ref();
+                        selection
+                            .buses
+                            .iter()
+                            .filter_map(|&handle| {
+                                bus_graph.try_get_bus_ref(handle).and_then(|bus| {
+                                    make_set_audio_bus_property_command(handle, bus, args)
+                                })
+                            })
+                            .collect::<Vec<_>>()
+                    }
                     Selection::Animation(selection) => {
-                        if scene
+                        if let Some(animation) = scene
                             .graph
                             .try_get_of_type::<AnimationPlayer>(selection.animation_player)
                             .and_then(|player| player.animations().try_get(selection.animation))
-                            .is_some()
                         {
                             selection
                                 .entities
                                 .iter()
                                 .filter_map(|e| {
                                     if let animation::selection::SelectedEntity::Signal(id) = e {
+                                        let signal =
+                                            animation.signals().iter().find(|s| s.id == *id)?;
                                         make_animation_signal_property_command(
                                             *id,
+                                            signal,
                                             args,
                                             selection.animation_player,
                                             selection.animation,
@@ -610,46 +755,50 @@ impl Inspector {
                         }
                     }
                     Selection::Absm(selection) => {
-                        if scene
-                            .graph
-                            .try_get(selection.absm_node_handle)
-                            .and_then(|n| n.query_component_ref::<AnimationBlendingStateMachine>())
-                            .is_some()
-                        {
-                            if let Some(layer_index) = selection.layer {
-                                selection
-                                    .entities
-                                    .iter()
-                                    .filter_map(|ent| match ent {
-                                        SelectedEntity::Transition(transition) => {
-                                            make_set_transition_property_command(
-                                                *transition,
-                                                args,
-                                                selection.absm_node_handle,
-                                                layer_index,
-                                            )
-                                        }
-                                        SelectedEntity::State(state) => {
-                                            make_set_state_property_command(
-                                                *state,
-                                                args,
-                                                selection.absm_node_handle,
-                                                layer_index,
-                                            )
-                                        }
-                                        SelectedEntity::PoseNode(pose) => {
-                                            make_set_pose_property_command(
-                                                *pose,
-                                                args,
-                                                selection.absm_node_handle,
-                                                layer_index,
-                                            )
-                                        }
-                                    })
-                                    .collect()
-                            } else {
-                                vec![]
-                            }
+                        let layer = selection.layer.and_then(|layer_index| {
+                            scene
+                                .graph
+                                .try_get(selection.absm_node_handle)
+                                .and_then(|n| {
+                                    n.query_component_ref::<AnimationBlendingStateMachine>()
+                                })
+                                .and_then(|absm| absm.machine().layers().get(layer_index))
+                                .map(|layer| (layer_index, layer))
+                        });
+                        if let Some((layer_index, layer)) = layer {
+                            selection
+                                .entities
+                                .iter()
+                                .filter_map(|ent| match ent {
+                                    SelectedEntity::Transition(transition) => {
+                                        make_set_transition_property_command(
+                                            *transition,
+                                            layer.transitions().try_borrow(*transition)?,
+                                            args,
+                                            selection.absm_node_handle,
+                                            layer_index,
+                                        )
+                                    }
+                                    SelectedEntity::State(state) => {
+                                        make_set_state_property_command(
+                                            *state,
+                                            layer.states().try_borrow(*state)?,
+                                            args,
+                                            selection.absm_node_handle,
+                                            layer_index,
+                                        )
+                                    }
+                                    SelectedEntity::PoseNode(pose) => {
+                                        make_set_pose_property_command(
+                                            *pose,
+                                            layer.nodes().try_borrow(*pose)?,
+                                            args,
+                                            selection.absm_node_handle,
+                                            layer_index,
+                                        )
+                                    }
+                                })
+                                .collect()
                         } else {
                             vec![]
                         }
@@ -666,6 +815,46 @@ impl Inspector {
                 } else {
                     sender.do_scene_command(CommandGroup::from(group));
                 }
+            } else if let Some(InspectorMessage::CopyValue(path)) =
+                message.data::<InspectorMessage>()
+            {
+                let mut copied_value = None;
+                first_selected_object(editor_scene, scene, &mut |object| {
+                    copied_value = CopiedValue::copy(object, path, &self.property_editors);
+                });
+                if copied_value.is_some() {
+                    self.copied_value = copied_value;
+                } else {
+                    Log::err(format!("Unable to copy the value of {} property!", path));
+                }
+            } else if let Some(InspectorMessage::PasteValue(path)) =
+                message.data::<InspectorMessage>()
+            {
+                if let Some(copied_value) = self.copied_value.as_ref() {
+                    // The value is pasted like it was set by the property editor, so every
+                    // selected object gets it.
+                    let mut property_changed = None;
+                    first_selected_object(editor_scene, scene, &mut |object| {
+                        property_changed = copied_value.paste(object, path);
+                    });
+                    if let Some(property_changed) = property_changed {
+                        engine
+                            .user_interface
+                            .send_message(InspectorMessage::property_changed(
+                                self.inspector,
+                                MessageDirection::FromWidget,
+                                property_changed,
+                            ));
+                    } else {
+                        Log::err(format!(
+                            "Unable to paste a value of type {} to {} property of another type!",
+                            copied_value.value.value.type_name(),
+                            path
+                        ));
+                    }
+                } else {
+                    Log::err("There is no copied value to paste!");
+                }
             }
         } else if let Some(ButtonMessage::Click) = message.data() {
             if message.destination() == self.docs_button {
@@ -18,6 +18,7 @@ pub mod rotate_mode;
 pub mod scale_mode;
 pub mod select_mode;
 pub mod terrain;
+pub mod viewport_label;
 
 pub trait BaseInteractionMode {
     fn as_any(&self) -> &dyn Any;
@@ -54,6 +55,18 @@ pub trait InteractionMode: BaseInteractionMode {
         settings: &Settings,
     );
 
+    /// Called when the left mouse button is double-clicked, right after the button down event of
+    /// the second click.
+    fn on_left_mouse_button_double_click(
+        &mut self,
+        _editor_scene: &mut EditorScene,
+        _engine: &mut Engine,
+        _mouse_pos: Vector2<f32>,
+        _frame_size: Vector2<f32>,
+        _settings: &Settings,
+    ) {
+    }
+
     fn on_mouse_move(
         &mut self,
         mouse_offset: Vector2<f32>,
@@ -74,6 +87,12 @@ pub trait InteractionMode: BaseInteractionMode {
     ) {
     }
 
+    /// Should return `false` if the mode cannot be used with the current content of the scene.
+    /// For example, navmesh editing makes no sense if there's no navmesh in the scene.
+    fn can_activate(&self, _editor_scene: &EditorScene, _engine: &Engine) -> bool {
+        true
+    }
+
     fn activate(&mut self, _editor_scene: &EditorScene, _engine: &mut Engine) {}
 
     fn deactivate(&mut self, editor_scene: &EditorScene, engine: &mut Engine);
@@ -85,6 +104,7 @@ pub trait InteractionMode: BaseInteractionMode {
         _key: KeyCode,
         _editor_scene: &mut EditorScene,
         _engine: &mut Engine,
+        _settings: &Settings,
     ) -> bool {
         false
     }
@@ -0,0 +1,197 @@
+//! Agent radius preview for navmesh edit mode. It shows how the walkable area shrinks for an agent
+//! of the given radius, so a navmesh that is fine for a point agent, but too narrow for a real
+//! character, could be spotted right away.
+
+use fyrox::{
+    core::{algebra::Vector3, math::TriangleDefinition},
+    utils::navmesh::Navmesh,
+};
+use std::collections::HashMap;
+
+/// Returns the edges, that are used by exactly one triangle. Every edge is returned as a pair of
+/// vertex indices in the winding order of its triangle, along with the index of the triangle.
+pub fn boundary_edges(triangles: &[TriangleDefinition]) -> Vec<(u32, u32, usize)> {
+    let mut edges = HashMap::<(u32, u32), Vec<(u32, u32, usize)>>::new();
+    for (index, triangle) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            let (a, b) = (triangle[k], triangle[(k + 1) % 3]);
+            edges
+                .entry((a.min(b), a.max(b)))
+                .or_default()
+                .push((a, b, index));
+        }
+    }
+
+    let mut boundary = edges
+        .into_values()
+        .filter_map(|uses| {
+            if uses.len() == 1 {
+                uses.first().copied()
+            } else {
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    // Hash map has no stable order, keep the output deterministic.
+    boundary.sort_unstable();
+    boundary
+}
+
+/// Moves every boundary edge inward (towards the rest of its triangle) by `radius`, along the plane
+/// of the triangle. Edges of degenerate triangles are skipped.
+pub fn inset_boundary(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    radius: f32,
+) -> Vec<[Vector3<f32>; 2]> {
+    boundary_edges(triangles)
+        .into_iter()
+        .filter_map(|(a, b, triangle)| {
+            let triangle = &triangles[triangle];
+            let opposite = triangle
+                .0
+                .iter()
+                .find(|i| **i != a && **i != b)
+                .map(|i| vertices[*i as usize])?;
+            let (a, b) = (vertices[a as usize], vertices[b as usize]);
+
+            // Projection of the opposite vertex onto the edge line gives the inward direction.
+            let edge = (b - a).try_normalize(f32::EPSILON)?;
+            let to_opposite = opposite - a;
+            let inward = (to_opposite - edge.scale(to_opposite.dot(&edge)))
+                .try_normalize(f32::EPSILON)?
+                .scale(radius);
+
+            Some([a + inward, b + inward])
+        })
+        .collect()
+}
+
+/// Returns indices of triangles, which have at least one altitude shorter than `width`.
+pub fn narrow_triangles(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    width: f32,
+) -> Vec<usize> {
+    triangles
+        .iter()
+        .enumerate()
+        .filter(|(_, triangle)| {
+            let [a, b, c] = triangle.0.map(|i| vertices[i as usize]);
+            let double_area = (b - a).cross(&(c - a)).norm();
+            let longest_side = (b - a).norm().max((c - b).norm()).max((a - c).norm());
+            // The shortest altitude is the one that falls on the longest side.
+            double_area < width * longest_side
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+// Everything the preview depends on, used to recompute it only when something has changed.
+#[derive(PartialEq)]
+struct AgentPreviewInput {
+    vertices: Vec<Vector3<f32>>,
+    triangles: Vec<TriangleDefinition>,
+    radius: f32,
+}
+
+/// Cached agent radius preview of a navmesh.
+#[derive(Default)]
+pub struct AgentRadiusPreview {
+    input: Option<AgentPreviewInput>,
+    inset_boundary: Vec<[Vector3<f32>; 2]>,
+    narrow_triangles: Vec<usize>,
+}
+
+impl AgentRadiusPreview {
+    /// Recomputes the preview if the navmesh or the radius were changed since the last call.
+    pub fn update(&mut self, navmesh: &Navmesh, radius: f32) {
+        let input = AgentPreviewInput {
+            vertices: navmesh.vertices().iter().map(|v| v.position).collect(),
+            triangles: navmesh.triangles().to_vec(),
+            radius,
+        };
+        if self.input.as_ref() == Some(&input) {
+            return;
+        }
+
+        self.inset_boundary = inset_boundary(&input.vertices, &input.triangles, radius);
+        self.narrow_triangles = narrow_triangles(&input.vertices, &input.triangles, 2.0 * radius);
+        self.input = Some(input);
+    }
+
+    /// Returns boundary edges of the walkable area moved inward by the agent radius.
+    pub fn inset_boundary(&self) -> &[[Vector3<f32>; 2]] {
+        &self.inset_boundary
+    }
+
+    /// Returns indices of triangles, that are narrower than the agent diameter.
+    pub fn narrow_triangles(&self) -> &[usize] {
+        &self.narrow_triangles
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // 3 - 2
+    // | / |
+    // 0 - 1
+    fn quad() -> (Vec<Vector3<f32>>, Vec<TriangleDefinition>) {
+        (
+            vec![
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(2.0, 0.0, 0.0),
+                Vector3::new(2.0, 0.0, 2.0),
+                Vector3::new(0.0, 0.0, 2.0),
+            ],
+            vec![TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])],
+        )
+    }
+
+    fn approx_eq(a: Vector3<f32>, b: Vector3<f32>) -> bool {
+        a.metric_distance(&b) < 1.0e-5
+    }
+
+    #[test]
+    fn test_boundary_edges() {
+        let (_, triangles) = quad();
+
+        // Diagonal 0-2 is shared by both triangles.
+        assert_eq!(
+            boundary_edges(&triangles),
+            vec![(0, 1, 0), (1, 2, 0), (2, 3, 1), (3, 0, 1)]
+        );
+    }
+
+    #[test]
+    fn test_inset_boundary() {
+        let (vertices, triangles) = quad();
+
+        let inset = inset_boundary(&vertices, &triangles, 0.5);
+        let expected = [
+            [Vector3::new(0.0, 0.0, 0.5), Vector3::new(2.0, 0.0, 0.5)],
+            [Vector3::new(1.5, 0.0, 0.0), Vector3::new(1.5, 0.0, 2.0)],
+            [Vector3::new(2.0, 0.0, 1.5), Vector3::new(0.0, 0.0, 1.5)],
+            [Vector3::new(0.5, 0.0, 2.0), Vector3::new(0.5, 0.0, 0.0)],
+        ];
+        assert_eq!(inset.len(), expected.len());
+        for (edge, expected) in inset.iter().zip(expected.iter()) {
+            assert!(approx_eq(edge[0], expected[0]) && approx_eq(edge[1], expected[1]));
+        }
+    }
+
+    #[test]
+    fn test_narrow_triangles() {
+        let (mut vertices, triangles) = quad();
+
+        // Altitudes of both triangles are sqrt(2).
+        assert!(narrow_triangles(&vertices, &triangles, 1.4).is_empty());
+        assert_eq!(narrow_triangles(&vertices, &triangles, 1.5), vec![0, 1]);
+
+        // Squash the second triangle into a thin sliver.
+        vertices[3] = Vector3::new(0.9, 0.0, 1.0);
+        assert_eq!(narrow_triangles(&vertices, &triangles, 1.0), vec![1]);
+    }
+}
@@ -0,0 +1,152 @@
+//! Binding of navmesh edit mode to a navmesh of the scene. The mode edits a single navmesh at a
+//! time, so it has to be picked among the selected nodes, the last edited navmesh and the rest of
+//! the navmeshes of the scene.
+
+use crate::scene::EditorScene;
+use fyrox::{
+    core::pool::Handle,
+    scene::{graph::Graph, navmesh::NavigationalMesh, node::Node},
+};
+
+/// Result of an attempt to find a navmesh, that should be edited by the navmesh interaction mode.
+#[derive(Debug, PartialEq, Eq)]
+pub enum NavmeshBinding {
+    /// There's no navmesh in the scene, there's nothing to edit.
+    None,
+    /// A navmesh was found either in the current selection, or it was edited last time, or it is
+    /// the only one in the scene.
+    Bound(Handle<Node>),
+    /// There are multiple navmeshes in the scene and none of them is selected, so the user must
+    /// pick one of them.
+    Ambiguous(Vec<Handle<Node>>),
+}
+
+/// Selects a navmesh to edit. Navmeshes from the current selection have priority, then the last
+/// edited navmesh is picked. If the last edited navmesh does not exist anymore, the first navmesh
+/// of the scene is picked instead. Otherwise the only navmesh in the scene is picked and if there
+/// are multiple navmeshes, the choice is left to the user.
+pub fn resolve_navmesh_binding(
+    selected: &[Handle<Node>],
+    last_edited: Handle<Node>,
+    navmeshes: &[Handle<Node>],
+) -> NavmeshBinding {
+    if let Some(selected) = selected.iter().find(|h| navmeshes.contains(h)) {
+        return NavmeshBinding::Bound(*selected);
+    }
+
+    if last_edited.is_some() {
+        if navmeshes.contains(&last_edited) {
+            return NavmeshBinding::Bound(last_edited);
+        } else if let Some(first) = navmeshes.first() {
+            return NavmeshBinding::Bound(*first);
+        }
+    }
+
+    match navmeshes {
+        [] => NavmeshBinding::None,
+        [single] => NavmeshBinding::Bound(*single),
+        _ => NavmeshBinding::Ambiguous(navmeshes.to_vec()),
+    }
+}
+
+/// Collects handles of every navigational mesh in the scene (editor objects are ignored).
+pub fn scene_navmeshes(editor_scene: &EditorScene, graph: &Graph) -> Vec<Handle<Node>> {
+    graph
+        .traverse_handle_iter(editor_scene.scene_content_root)
+        .filter(|h| graph.try_get_of_type::<NavigationalMesh>(*h).is_some())
+        .collect()
+}
+
+/// Returns the only navigational mesh among the selected nodes, `None` if there's no navmesh in the
+/// selection or there are several of them.
+pub fn single_selected_navmesh(
+    selected: &[Handle<Node>],
+    is_navmesh: impl Fn(Handle<Node>) -> bool,
+) -> Option<Handle<Node>> {
+    let mut navmeshes = selected.iter().filter(|h| is_navmesh(**h));
+    match (navmeshes.next(), navmeshes.next()) {
+        (Some(navmesh), None) => Some(*navmesh),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_binding_prefers_selected_navmesh() {
+        let a = Handle::new(1, 1);
+        let b = Handle::new(2, 1);
+        let other = Handle::new(3, 1);
+
+        assert_eq!(
+            resolve_navmesh_binding(&[other, b], a, &[a, b]),
+            NavmeshBinding::Bound(b)
+        );
+    }
+
+    #[test]
+    fn test_binding_picks_the_only_navmesh() {
+        let a = Handle::new(1, 1);
+        let other = Handle::new(3, 1);
+
+        assert_eq!(
+            resolve_navmesh_binding(&[other], Handle::NONE, &[a]),
+            NavmeshBinding::Bound(a)
+        );
+        assert_eq!(
+            resolve_navmesh_binding(&[], Handle::NONE, &[a]),
+            NavmeshBinding::Bound(a)
+        );
+    }
+
+    #[test]
+    fn test_binding_is_ambiguous_for_multiple_navmeshes() {
+        let a = Handle::new(1, 1);
+        let b = Handle::new(2, 1);
+
+        assert_eq!(
+            resolve_navmesh_binding(&[], Handle::NONE, &[a, b]),
+            NavmeshBinding::Ambiguous(vec![a, b])
+        );
+        assert_eq!(
+            resolve_navmesh_binding(&[], Handle::NONE, &[]),
+            NavmeshBinding::None
+        );
+    }
+
+    #[test]
+    fn test_binding_restores_last_edited_navmesh() {
+        let a = Handle::new(1, 1);
+        let b = Handle::new(2, 1);
+        let deleted = Handle::new(3, 1);
+
+        assert_eq!(
+            resolve_navmesh_binding(&[], b, &[a, b]),
+            NavmeshBinding::Bound(b)
+        );
+        // Invalid handle falls back to the first navmesh.
+        assert_eq!(
+            resolve_navmesh_binding(&[], deleted, &[a, b]),
+            NavmeshBinding::Bound(a)
+        );
+        assert_eq!(
+            resolve_navmesh_binding(&[], deleted, &[]),
+            NavmeshBinding::None
+        );
+    }
+
+    #[test]
+    fn test_single_selected_navmesh() {
+        let a = Handle::new(1, 1);
+        let b = Handle::new(2, 1);
+        let other = Handle::new(3, 1);
+        let is_navmesh = |h: Handle<Node>| h == a || h == b;
+
+        assert_eq!(single_selected_navmesh(&[other, a], is_navmesh), Some(a));
+        assert_eq!(single_selected_navmesh(&[a, b], is_navmesh), None);
+        assert_eq!(single_selected_navmesh(&[other], is_navmesh), None);
+        assert_eq!(single_selected_navmesh(&[], is_navmesh), None);
+    }
+}
@@ -0,0 +1,92 @@
+//! Deletion of selected navmesh entities. Edges remove the triangles they belong to, vertices
+//! remove every triangle that uses them, and vertices left without triangles are removed too.
+
+use crate::interaction::navmesh::selection::NavmeshEntity;
+use fyrox::core::math::{TriangleDefinition, TriangleEdge};
+use std::collections::BTreeSet;
+
+/// Splits selected entities into edges, whose triangles must be removed, and vertices, that must
+/// be removed (in descending order). Vertices of triangles removed with edges are deleted only if
+/// they're not used by any remaining triangle.
+pub fn plan_deletion(
+    entities: &[NavmeshEntity],
+    triangles: &[TriangleDefinition],
+) -> (Vec<TriangleEdge>, Vec<usize>) {
+    let mut edges = Vec::new();
+    let mut vertices = BTreeSet::new();
+    for entity in entities {
+        match entity {
+            NavmeshEntity::Vertex(vertex) => {
+                vertices.insert(*vertex);
+            }
+            NavmeshEntity::Edge(edge) => {
+                if !edges.contains(edge) {
+                    edges.push(*edge);
+                }
+            }
+            // Links do not depend on triangles and vertices.
+            NavmeshEntity::Link(_) => (),
+        }
+    }
+
+    let (removed, remaining): (Vec<_>, Vec<_>) = triangles
+        .iter()
+        .partition(|triangle| triangle.edges().iter().any(|edge| edges.contains(edge)));
+    for triangle in removed {
+        for &vertex in triangle.indices() {
+            if !remaining.iter().any(|t| t.indices().contains(&vertex)) {
+                vertices.insert(vertex as usize);
+            }
+        }
+    }
+
+    (edges, vertices.into_iter().rev().collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_deletion_of_edges_keeps_shared_vertices() {
+        // 3 - 2
+        // | / |
+        // 0 - 1
+        let triangles = [TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])];
+
+        // Diagonal is shared by both triangles, so every vertex becomes unused.
+        assert_eq!(
+            plan_deletion(
+                &[NavmeshEntity::Edge(TriangleEdge { a: 2, b: 0 })],
+                &triangles
+            ),
+            (vec![TriangleEdge { a: 2, b: 0 }], vec![3, 2, 1, 0])
+        );
+
+        // Outer edge removes a single triangle, vertex 0 is still used by the other one.
+        assert_eq!(
+            plan_deletion(
+                &[NavmeshEntity::Edge(TriangleEdge { a: 0, b: 1 })],
+                &triangles
+            ),
+            (vec![TriangleEdge { a: 0, b: 1 }], vec![1])
+        );
+    }
+
+    #[test]
+    fn test_deletion_of_mixed_selection() {
+        let triangles = [TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])];
+
+        assert_eq!(
+            plan_deletion(
+                &[
+                    NavmeshEntity::Vertex(3),
+                    NavmeshEntity::Edge(TriangleEdge { a: 1, b: 2 }),
+                    NavmeshEntity::Edge(TriangleEdge { a: 2, b: 1 }),
+                ],
+                &triangles
+            ),
+            (vec![TriangleEdge { a: 1, b: 2 }], vec![3, 1])
+        );
+    }
+}
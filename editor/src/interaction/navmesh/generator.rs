@@ -0,0 +1,467 @@
+//! Automatic navmesh generation from scene geometry. Triangles of the scene are rasterized into a
+//! grid of columns, then every walkable surface with enough free space above it becomes a floor
+//! cell. Floors are eroded by the agent radius and the rest is triangulated into a navmesh.
+
+use fyrox::{
+    core::{
+        algebra::{Point3, Vector2, Vector3},
+        math::TriangleDefinition,
+        pool::Handle,
+    },
+    scene::{
+        graph::Graph,
+        mesh::{
+            buffer::{VertexAttributeUsage, VertexReadTrait},
+            Mesh,
+        },
+        node::Node,
+    },
+    utils::navmesh::Navmesh,
+};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, VecDeque},
+};
+
+/// Amount of triangles rasterized per [`NavmeshGenerator::step`].
+const TRIANGLES_PER_STEP: usize = 512;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NavmeshGenerationSettings {
+    /// Minimal distance between agents and non-walkable geometry.
+    pub agent_radius: f32,
+    /// Minimal free space above walkable surfaces.
+    pub agent_height: f32,
+    /// Maximum angle (in degrees) between a walkable surface and the horizon.
+    pub max_slope: f32,
+    /// Size of a grid cell. Smaller cells give more precise results, but produce more triangles.
+    pub cell_size: f32,
+}
+
+impl Default for NavmeshGenerationSettings {
+    fn default() -> Self {
+        Self {
+            agent_radius: 0.4,
+            agent_height: 1.8,
+            max_slope: 45.0,
+            cell_size: 0.25,
+        }
+    }
+}
+
+/// Collects world-space triangles of every mesh in the sub-graph with the given root.
+pub fn collect_scene_geometry(graph: &Graph, root: Handle<Node>) -> Vec<[Vector3<f32>; 3]> {
+    let mut triangles = Vec::new();
+    for handle in graph.traverse_handle_iter(root) {
+        if let Some(mesh) = graph.try_get_of_type::<Mesh>(handle) {
+            let transform = mesh.global_transform();
+            for surface in mesh.surfaces() {
+                let data = surface.data();
+                let data = data.lock();
+                let position = |index: u32| {
+                    data.vertex_buffer
+                        .get(index as usize)
+                        .and_then(|v| v.read_3_f32(VertexAttributeUsage::Position).ok())
+                        .map(|p| transform.transform_point(&Point3::from(p)).coords)
+                };
+                for triangle in data.geometry_buffer.iter() {
+                    if let (Some(a), Some(b), Some(c)) = (
+                        position(triangle[0]),
+                        position(triangle[1]),
+                        position(triangle[2]),
+                    ) {
+                        triangles.push([a, b, c]);
+                    }
+                }
+            }
+        }
+    }
+    triangles
+}
+
+/// Vertical interval of geometry in a column.
+#[derive(Debug)]
+struct Span {
+    min: f32,
+    max: f32,
+    walkable: bool,
+}
+
+#[derive(Debug)]
+struct Floor {
+    x: usize,
+    z: usize,
+    height: f32,
+}
+
+pub enum NavmeshGenerationStatus {
+    /// Generation is in progress, contains progress in `[0; 1]` range.
+    InProgress(f32),
+    Finished(Navmesh),
+}
+
+/// Generates a navmesh in small steps, so it could be done across multiple frames without
+/// freezing the editor.
+pub struct NavmeshGenerator {
+    settings: NavmeshGenerationSettings,
+    triangles: Vec<[Vector3<f32>; 3]>,
+    origin: Vector2<f32>,
+    width: usize,
+    depth: usize,
+    columns: Vec<Vec<Span>>,
+    next_triangle: usize,
+}
+
+impl NavmeshGenerator {
+    pub fn new(triangles: Vec<[Vector3<f32>; 3]>, mut settings: NavmeshGenerationSettings) -> Self {
+        settings.cell_size = settings.cell_size.max(0.01);
+        settings.max_slope = settings.max_slope.clamp(0.0, 89.0);
+
+        let mut min = Vector2::repeat(f32::MAX);
+        let mut max = Vector2::repeat(-f32::MAX);
+        for vertex in triangles.iter().flatten() {
+            min = min.inf(&vertex.xz());
+            max = max.sup(&vertex.xz());
+        }
+
+        let (width, depth) = if triangles.is_empty() {
+            min = Vector2::default();
+            (0, 0)
+        } else {
+            let size = (max - min) / settings.cell_size;
+            (size.x.ceil() as usize + 1, size.y.ceil() as usize + 1)
+        };
+
+        Self {
+            settings,
+            triangles,
+            origin: min,
+            width,
+            depth,
+            columns: (0..(width * depth)).map(|_| Vec::new()).collect(),
+            next_triangle: 0,
+        }
+    }
+
+    /// Performs next portion of work.
+    pub fn step(&mut self) -> NavmeshGenerationStatus {
+        if self.next_triangle < self.triangles.len() {
+            let end = (self.next_triangle + TRIANGLES_PER_STEP).min(self.triangles.len());
+            for i in self.next_triangle..end {
+                let triangle = self.triangles[i];
+                self.rasterize(&triangle);
+            }
+            self.next_triangle = end;
+
+            // Rest of the work is much faster than the rasterization.
+            NavmeshGenerationStatus::InProgress(
+                0.9 * self.next_triangle as f32 / self.triangles.len() as f32,
+            )
+        } else {
+            NavmeshGenerationStatus::Finished(self.build())
+        }
+    }
+
+    /// Runs generation to the end.
+    pub fn generate(mut self) -> Navmesh {
+        loop {
+            if let NavmeshGenerationStatus::Finished(navmesh) = self.step() {
+                return navmesh;
+            }
+        }
+    }
+
+    fn cell_center(&self, x: usize, z: usize) -> Vector2<f32> {
+        self.origin + Vector2::new(x as f32 + 0.5, z as f32 + 0.5).scale(self.settings.cell_size)
+    }
+
+    fn cell_of(&self, point: Vector2<f32>) -> (usize, usize) {
+        let local = (point - self.origin) / self.settings.cell_size;
+        (
+            (local.x.max(0.0) as usize).min(self.width - 1),
+            (local.y.max(0.0) as usize).min(self.depth - 1),
+        )
+    }
+
+    fn rasterize(&mut self, triangle: &[Vector3<f32>; 3]) {
+        let [a, b, c] = *triangle;
+        let normal = (b - a).cross(&(c - a));
+        let normal = match normal.try_normalize(f32::EPSILON) {
+            Some(normal) => normal,
+            None => return,
+        };
+
+        let walkable = normal.y.abs() >= self.settings.max_slope.to_radians().cos();
+
+        if walkable {
+            // Walkable surfaces are sampled at cell centers, this gives exact height of the floor.
+            let (min_x, min_z) = self.cell_of(a.xz().inf(&b.xz()).inf(&c.xz()));
+            let (max_x, max_z) = self.cell_of(a.xz().sup(&b.xz()).sup(&c.xz()));
+            for z in min_z..=max_z {
+                for x in min_x..=max_x {
+                    let center = self.cell_center(x, z);
+                    if let Some(height) = height_at(triangle, center) {
+                        self.columns[z * self.width + x].push(Span {
+                            min: height,
+                            max: height,
+                            walkable: true,
+                        });
+                    }
+                }
+            }
+        } else {
+            // Steep surfaces (walls) could have degenerate projection on the grid, so they're
+            // sampled densely and every touched cell is blocked in the covered height range.
+            let longest_edge = (b - a).norm().max((c - b).norm()).max((a - c).norm());
+            let steps = ((2.0 * longest_edge / self.settings.cell_size).ceil() as usize).max(1);
+            let mut touched = HashMap::<usize, (f32, f32)>::new();
+            for i in 0..=steps {
+                for j in 0..=(steps - i) {
+                    let u = i as f32 / steps as f32;
+                    let v = j as f32 / steps as f32;
+                    let point = a + (b - a).scale(u) + (c - a).scale(v);
+                    let (x, z) = self.cell_of(point.xz());
+                    let (min, max) = touched
+                        .entry(z * self.width + x)
+                        .or_insert((point.y, point.y));
+                    *min = min.min(point.y);
+                    *max = max.max(point.y);
+                }
+            }
+            for (index, (min, max)) in touched {
+                self.columns[index].push(Span {
+                    min,
+                    max,
+                    walkable: false,
+                });
+            }
+        }
+    }
+
+    fn build(&self) -> Navmesh {
+        let settings = &self.settings;
+        let climb = settings.cell_size * settings.max_slope.to_radians().tan() + 0.001;
+
+        // Find floors - walkable spans with enough free space above them.
+        let mut floors = Vec::new();
+        let mut column_floors = vec![Vec::new(); self.columns.len()];
+        for z in 0..self.depth {
+            for x in 0..self.width {
+                let column = &self.columns[z * self.width + x];
+                let mut heights = column
+                    .iter()
+                    .filter(|span| span.walkable)
+                    .map(|span| span.max)
+                    .filter(|&height| {
+                        column.iter().all(|other| {
+                            other.max <= height + climb
+                                || other.min >= height + settings.agent_height
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                heights.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                // Overlapping walkable surfaces produce the same floor multiple times.
+                heights.dedup_by(|a, b| (*a - *b).abs() <= climb);
+
+                for height in heights {
+                    column_floors[z * self.width + x].push(floors.len());
+                    floors.push(Floor { x, z, height });
+                }
+            }
+        }
+
+        let all = vec![true; floors.len()];
+        let neighbour = |floor: usize, dx: isize, dz: isize, alive: &[bool]| -> Option<usize> {
+            let floor_ref = &floors[floor];
+            let x = floor_ref.x as isize + dx;
+            let z = floor_ref.z as isize + dz;
+            if x < 0 || z < 0 || x as usize >= self.width || z as usize >= self.depth {
+                return None;
+            }
+            let (x, z) = (x as usize, z as usize);
+            column_floors[z * self.width + x]
+                .iter()
+                .filter(|&&other| alive[other])
+                .map(|&other| (other, (floors[other].height - floor_ref.height).abs()))
+                .filter(|(_, difference)| *difference <= climb)
+                .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+                .map(|(other, _)| other)
+        };
+
+        // Erode floors by the agent radius - find distance from every floor to the border (a floor
+        // with less than 4 neighbours) and keep only the floors that are far enough.
+        let radius = (settings.agent_radius / settings.cell_size).ceil().max(0.0) as usize;
+        let mut distance = vec![usize::MAX; floors.len()];
+        let mut queue = VecDeque::new();
+        for floor in 0..floors.len() {
+            let neighbours = [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .iter()
+                .filter(|(dx, dz)| neighbour(floor, *dx, *dz, &all).is_some())
+                .count();
+            if neighbours < 4 {
+                distance[floor] = 0;
+                queue.push_back(floor);
+            }
+        }
+        while let Some(floor) = queue.pop_front() {
+            for (dx, dz) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+                if let Some(other) = neighbour(floor, dx, dz, &all) {
+                    if distance[other] == usize::MAX {
+                        distance[other] = distance[floor] + 1;
+                        queue.push_back(other);
+                    }
+                }
+            }
+        }
+        let alive = distance.iter().map(|d| *d >= radius).collect::<Vec<_>>();
+
+        // Every floor becomes a vertex at the center of its cell.
+        let mut vertex_indices = vec![u32::MAX; floors.len()];
+        let mut vertices = Vec::new();
+        for (index, floor) in floors.iter().enumerate() {
+            if alive[index] {
+                vertex_indices[index] = vertices.len() as u32;
+                let center = self.cell_center(floor.x, floor.z);
+                vertices.push(Vector3::new(center.x, floor.height, center.y));
+            }
+        }
+
+        // Connect vertices of neighbouring floors into quads (or into single triangles, if a quad
+        // misses one corner).
+        let mut triangles = Vec::new();
+        let mut add = |a: usize, b: usize, c: usize| {
+            triangles.push(TriangleDefinition([
+                vertex_indices[a],
+                vertex_indices[b],
+                vertex_indices[c],
+            ]))
+        };
+        for floor in (0..floors.len()).filter(|f| alive[*f]) {
+            let right = neighbour(floor, 1, 0, &alive);
+            let down = neighbour(floor, 0, 1, &alive);
+            let diagonal = right
+                .and_then(|r| neighbour(r, 0, 1, &alive))
+                .or_else(|| down.and_then(|d| neighbour(d, 1, 0, &alive)));
+
+            match (right, down, diagonal) {
+                (Some(r), Some(d), Some(rd)) => {
+                    add(floor, d, rd);
+                    add(floor, rd, r);
+                }
+                (Some(r), Some(d), None) => add(floor, d, r),
+                (Some(r), None, Some(rd)) => add(floor, rd, r),
+                (None, Some(d), Some(rd)) => add(floor, d, rd),
+                _ => (),
+            }
+
+            // The quad above, which misses its first corner, can't be built from that corner, so
+            // build its triangle from here.
+            if let Some(r) = right {
+                if let Some(ur) = neighbour(r, 0, -1, &alive) {
+                    if neighbour(floor, 0, -1, &alive).is_none()
+                        && neighbour(ur, -1, 0, &alive).is_none()
+                    {
+                        add(floor, r, ur);
+                    }
+                }
+            }
+        }
+
+        Navmesh::new(&triangles, &vertices)
+    }
+}
+
+/// Height of the triangle at the given point of XZ plane, if the point is inside the projection of
+/// the triangle.
+fn height_at(triangle: &[Vector3<f32>; 3], point: Vector2<f32>) -> Option<f32> {
+    let [a, b, c] = *triangle;
+    let (pa, pb, pc) = (a.xz(), b.xz(), c.xz());
+    let v0 = pb - pa;
+    let v1 = pc - pa;
+    let v2 = point - pa;
+    let denom = v0.x * v1.y - v1.x * v0.y;
+    if denom.abs() <= f32::EPSILON {
+        return None;
+    }
+    let v = (v2.x * v1.y - v1.x * v2.y) / denom;
+    let w = (v0.x * v2.y - v2.x * v0.y) / denom;
+    let u = 1.0 - v - w;
+    let eps = 1.0e-5;
+    if u >= -eps && v >= -eps && w >= -eps {
+        Some(a.y * u + b.y * v + c.y * w)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn quad(size: f32, height: f32, tilt: f32) -> Vec<[Vector3<f32>; 3]> {
+        let a = Vector3::new(0.0, height, 0.0);
+        let b = Vector3::new(size, height + size * tilt, 0.0);
+        let c = Vector3::new(size, height + size * tilt, size);
+        let d = Vector3::new(0.0, height, size);
+        vec![[a, c, b], [a, d, c]]
+    }
+
+    fn settings() -> NavmeshGenerationSettings {
+        NavmeshGenerationSettings {
+            agent_radius: 0.0,
+            agent_height: 1.8,
+            max_slope: 45.0,
+            cell_size: 0.5,
+        }
+    }
+
+    #[test]
+    fn test_flat_floor() {
+        let navmesh = NavmeshGenerator::new(quad(4.0, 1.0, 0.0), settings()).generate();
+        assert!(!navmesh.triangles().is_empty());
+        assert!(navmesh
+            .vertices()
+            .iter()
+            .all(|v| (v.position.y - 1.0).abs() < 1.0e-4));
+
+        // Every triangle must face up.
+        for triangle in navmesh.triangles() {
+            let [a, b, c] = triangle.0.map(|i| navmesh.vertices()[i as usize].position);
+            assert!((b - a).cross(&(c - a)).y > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_steep_slope_is_not_walkable() {
+        let navmesh = NavmeshGenerator::new(quad(4.0, 0.0, 2.0), settings()).generate();
+        assert!(navmesh.triangles().is_empty());
+
+        let navmesh = NavmeshGenerator::new(quad(4.0, 0.0, 0.5), settings()).generate();
+        assert!(!navmesh.triangles().is_empty());
+    }
+
+    #[test]
+    fn test_low_ceiling_is_not_walkable() {
+        let mut geometry = quad(4.0, 0.0, 0.0);
+        geometry.extend(quad(4.0, 1.0, 0.0));
+        let navmesh = NavmeshGenerator::new(geometry, settings()).generate();
+        // Only the top of the ceiling is walkable.
+        assert!(!navmesh.vertices().is_empty());
+        assert!(navmesh
+            .vertices()
+            .iter()
+            .all(|v| (v.position.y - 1.0).abs() < 1.0e-4));
+    }
+
+    #[test]
+    fn test_erosion() {
+        let mut settings = settings();
+        settings.agent_radius = 1.0;
+        let navmesh = NavmeshGenerator::new(quad(4.0, 0.0, 0.0), settings).generate();
+        assert!(!navmesh.vertices().is_empty());
+        assert!(navmesh.vertices().iter().all(|v| {
+            let p = v.position;
+            p.x >= 1.0 && p.x <= 3.0 && p.z >= 1.0 && p.z <= 3.0
+        }));
+    }
+}
@@ -0,0 +1,411 @@
+//! Keyboard shortcuts of navmesh edit mode. Both the key handling and the help overlay use the same
+//! table of bindings, so the overlay always shows what the keys actually do.
+
+use fyrox::gui::message::{KeyCode, KeyboardModifiers};
+
+/// Group of bindings, that are active at the moment.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HotkeyContext {
+    /// Regular editing.
+    Edit,
+    /// Rotation or scaling of vertices is in progress. Keys without bindings are swallowed, so
+    /// nothing else could change the navmesh in the middle of the transform.
+    Transform,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NavmeshAction {
+    ToggleHelp,
+    Rotate,
+    /// Scales selected vertices, or splits the selected edge if it is the only selected entity.
+    ScaleOrSplitEdge,
+    /// Cancels vertex extrusion or placement of a link.
+    Cancel,
+    Delete,
+    SelectAll,
+    ToggleLockY,
+    Duplicate,
+    AddTriangle,
+    SelectLinked,
+    InvertSelection,
+    CancelTransform,
+    ConfirmTransform,
+    /// Constrains the transform to the axis with the given index.
+    ConstrainTransform(usize),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub context: HotkeyContext,
+    pub key: KeyCode,
+    /// Ctrl must be in the given state, plain keys and Ctrl+keys are different actions.
+    pub control: bool,
+    /// Shift must be held if it is required, otherwise it does not matter.
+    pub shift: bool,
+    pub action: NavmeshAction,
+    pub description: &'static str,
+}
+
+impl KeyBinding {
+    fn new(
+        context: HotkeyContext,
+        key: KeyCode,
+        action: NavmeshAction,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            context,
+            key,
+            control: false,
+            shift: false,
+            action,
+            description,
+        }
+    }
+
+    fn with_control(mut self) -> Self {
+        self.control = true;
+        self
+    }
+
+    fn with_shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    pub fn matches(
+        &self,
+        context: HotkeyContext,
+        key: KeyCode,
+        modifiers: KeyboardModifiers,
+    ) -> bool {
+        self.context == context
+            && self.key == key
+            && self.control == modifiers.control
+            && (!self.shift || modifiers.shift)
+    }
+
+    /// Returns a readable name of the key combination, for example `Ctrl+Shift+I`.
+    pub fn shortcut(&self) -> String {
+        let key = format!("{:?}", self.key);
+        let key = key.strip_prefix("Key").unwrap_or(&key);
+        let mut shortcut = String::new();
+        if self.control {
+            shortcut += "Ctrl+";
+        }
+        if self.shift {
+            shortcut += "Shift+";
+        }
+        shortcut + key
+    }
+}
+
+/// Mouse gestures of navmesh edit mode. They are handled by the mouse handlers of the mode, the
+/// list is only used by the help overlay.
+pub const MOUSE_GESTURES: &[(&str, &str)] = &[
+    ("Click", "Select a vertex, an edge or a link"),
+    ("Shift+Click", "Add to the selection"),
+    (
+        "Ctrl+Click",
+        "Deselect, or add a triangle from the selected edge to the geometry",
+    ),
+    ("Alt+Click", "Select an edge loop"),
+    ("Double Click", "Select a triangle fan or an island"),
+    ("Shift+Drag", "Extrude the selected edge or vertex"),
+];
+
+/// Keyboard shortcuts of navmesh edit mode.
+pub struct NavmeshHotkeys {
+    bindings: Vec<KeyBinding>,
+}
+
+impl Default for NavmeshHotkeys {
+    fn default() -> Self {
+        use HotkeyContext::{Edit, Transform};
+        use NavmeshAction::*;
+
+        Self {
+            bindings: vec![
+                KeyBinding::new(Edit, KeyCode::F1, ToggleHelp, "Show or hide this help"),
+                KeyBinding::new(
+                    Edit,
+                    KeyCode::KeyR,
+                    Rotate,
+                    "Rotate selected vertices around their centroid",
+                ),
+                KeyBinding::new(
+                    Edit,
+                    KeyCode::KeyS,
+                    ScaleOrSplitEdge,
+                    "Scale selected vertices, or split the only selected edge",
+                ),
+                KeyBinding::new(
+                    Edit,
+                    KeyCode::Escape,
+                    Cancel,
+                    "Cancel vertex extrusion or link placement",
+                ),
+                KeyBinding::new(Edit, KeyCode::Delete, Delete, "Delete selected entities"),
+                KeyBinding::new(Edit, KeyCode::KeyA, SelectAll, "Select all vertices")
+                    .with_control(),
+                KeyBinding::new(
+                    Edit,
+                    KeyCode::KeyY,
+                    ToggleLockY,
+                    "Lock the movement to Y axis",
+                ),
+                KeyBinding::new(
+                    Edit,
+                    KeyCode::KeyD,
+                    Duplicate,
+                    "Duplicate selected triangles",
+                )
+                .with_control(),
+                KeyBinding::new(
+                    Edit,
+                    KeyCode::KeyT,
+                    AddTriangle,
+                    "Add a triangle under the cursor",
+                )
+                .with_control(),
+                KeyBinding::new(
+                    Edit,
+                    KeyCode::KeyL,
+                    SelectLinked,
+                    "Select every vertex connected to the selection",
+                )
+                .with_control(),
+                KeyBinding::new(
+                    Edit,
+                    KeyCode::KeyI,
+                    InvertSelection,
+                    "Invert the vertex selection",
+                )
+                .with_control()
+                .with_shift(),
+                KeyBinding::new(
+                    Transform,
+                    KeyCode::Escape,
+                    CancelTransform,
+                    "Cancel the transform",
+                ),
+                KeyBinding::new(
+                    Transform,
+                    KeyCode::Enter,
+                    ConfirmTransform,
+                    "Apply the transform",
+                ),
+                KeyBinding::new(
+                    Transform,
+                    KeyCode::NumpadEnter,
+                    ConfirmTransform,
+                    "Apply the transform",
+                ),
+                KeyBinding::new(
+                    Transform,
+                    KeyCode::KeyX,
+                    ConstrainTransform(0),
+                    "Constrain the transform to X axis",
+                ),
+                KeyBinding::new(
+                    Transform,
+                    KeyCode::KeyY,
+                    ConstrainTransform(1),
+                    "Constrain the transform to Y axis",
+                ),
+                KeyBinding::new(
+                    Transform,
+                    KeyCode::KeyZ,
+                    ConstrainTransform(2),
+                    "Constrain the transform to Z axis",
+                ),
+            ],
+        }
+    }
+}
+
+impl NavmeshHotkeys {
+    pub fn bindings(&self) -> &[KeyBinding] {
+        &self.bindings
+    }
+
+    /// Returns the action bound to the key in the given context.
+    pub fn action(
+        &self,
+        context: HotkeyContext,
+        key: KeyCode,
+        modifiers: KeyboardModifiers,
+    ) -> Option<NavmeshAction> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.matches(context, key, modifiers))
+            .map(|binding| binding.action)
+    }
+
+    /// Returns the text of the help overlay. Bindings of the same action in the same context are
+    /// shown in one line.
+    pub fn help_text(&self) -> String {
+        let mut text = String::new();
+        for (context, title) in [
+            (HotkeyContext::Edit, "Keys"),
+            (HotkeyContext::Transform, "Rotation and scaling (R, S)"),
+        ] {
+            text += title;
+            text += ":\n";
+
+            let bindings = self
+                .bindings
+                .iter()
+                .filter(|binding| binding.context == context)
+                .collect::<Vec<_>>();
+            for (index, binding) in bindings.iter().enumerate() {
+                if bindings[..index].iter().any(|b| b.action == binding.action) {
+                    continue;
+                }
+                let shortcuts = bindings[index..]
+                    .iter()
+                    .filter(|b| b.action == binding.action)
+                    .map(|b| b.shortcut())
+                    .collect::<Vec<_>>();
+                text += &format!("  {} - {}\n", shortcuts.join(", "), binding.description);
+            }
+        }
+
+        text += "Mouse:\n";
+        for (gesture, description) in MOUSE_GESTURES {
+            text += &format!("  {} - {}\n", gesture, description);
+        }
+
+        text.trim_end().to_owned()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn modifiers(control: bool, shift: bool) -> KeyboardModifiers {
+        KeyboardModifiers {
+            control,
+            shift,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_hotkey_lookup() {
+        let hotkeys = NavmeshHotkeys::default();
+        let edit = HotkeyContext::Edit;
+
+        assert_eq!(
+            hotkeys.action(edit, KeyCode::KeyA, modifiers(true, false)),
+            Some(NavmeshAction::SelectAll)
+        );
+        // Shift is not required, but does not prevent the action.
+        assert_eq!(
+            hotkeys.action(edit, KeyCode::KeyA, modifiers(true, true)),
+            Some(NavmeshAction::SelectAll)
+        );
+        assert_eq!(
+            hotkeys.action(edit, KeyCode::KeyA, modifiers(false, false)),
+            None
+        );
+        assert_eq!(
+            hotkeys.action(edit, KeyCode::KeyI, modifiers(true, false)),
+            None
+        );
+        assert_eq!(
+            hotkeys.action(edit, KeyCode::KeyI, modifiers(true, true)),
+            Some(NavmeshAction::InvertSelection)
+        );
+
+        // The same key does different things in different contexts.
+        assert_eq!(
+            hotkeys.action(edit, KeyCode::KeyY, modifiers(false, false)),
+            Some(NavmeshAction::ToggleLockY)
+        );
+        assert_eq!(
+            hotkeys.action(
+                HotkeyContext::Transform,
+                KeyCode::KeyY,
+                modifiers(false, false)
+            ),
+            Some(NavmeshAction::ConstrainTransform(1))
+        );
+    }
+
+    #[test]
+    fn test_hotkey_table_is_consistent() {
+        let hotkeys = NavmeshHotkeys::default();
+        let bindings = hotkeys.bindings();
+
+        // No binding is shadowed by another one.
+        for (index, binding) in bindings.iter().enumerate() {
+            let modifiers = modifiers(binding.control, binding.shift);
+            assert_eq!(
+                hotkeys.action(binding.context, binding.key, modifiers),
+                Some(binding.action),
+                "{} is shadowed",
+                binding.shortcut()
+            );
+            assert!(!bindings[..index].iter().any(|b| b == binding));
+        }
+
+        let help = hotkeys.help_text();
+        for binding in bindings {
+            assert!(help.contains(&binding.shortcut()));
+            assert!(help.contains(binding.description));
+        }
+        assert!(help.contains("Ctrl+Shift+I - Invert the vertex selection"));
+        assert!(help.contains("Enter, NumpadEnter - Apply the transform"));
+    }
+
+    #[test]
+    fn test_every_action_is_bound() {
+        use NavmeshAction::*;
+
+        let hotkeys = NavmeshHotkeys::default();
+        let actions = [
+            ToggleHelp,
+            Rotate,
+            ScaleOrSplitEdge,
+            Cancel,
+            Delete,
+            SelectAll,
+            ToggleLockY,
+            Duplicate,
+            AddTriangle,
+            SelectLinked,
+            InvertSelection,
+            CancelTransform,
+            ConfirmTransform,
+            ConstrainTransform(0),
+            ConstrainTransform(1),
+            ConstrainTransform(2),
+        ];
+        for action in actions {
+            // The match fails to compile when a new action is added, so it can't be forgotten here.
+            match action {
+                ToggleHelp
+                | Rotate
+                | ScaleOrSplitEdge
+                | Cancel
+                | Delete
+                | SelectAll
+                | ToggleLockY
+                | Duplicate
+                | AddTriangle
+                | SelectLinked
+                | InvertSelection
+                | CancelTransform
+                | ConfirmTransform
+                | ConstrainTransform(_) => {}
+            }
+            assert!(
+                hotkeys.bindings().iter().any(|b| b.action == action),
+                "{:?} has no key",
+                action
+            );
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Vertex labels for navmesh edit mode. Every vertex near the camera is annotated with its index
+//! (and optionally its coordinates), so runtime logs like "stuck at vertex 137" could be matched
+//! with the edited navmesh.
+
+use crate::interaction::viewport_label::ViewportLabel;
+use fyrox::{
+    core::{algebra::Vector3, color::Color, math::Rect},
+    gui::UserInterface,
+    scene::camera::Camera,
+};
+use std::collections::BTreeSet;
+
+/// Maximum number of labels, that are shown at once. Closest vertices are labeled first.
+pub const MAX_VERTEX_LABELS: usize = 256;
+
+/// Returns indices of the vertices, that are closer than `max_distance` to the `viewer`, sorted from
+/// the closest one. No more than `max_count` indices are returned.
+pub fn labeled_vertices(
+    vertices: &[Vector3<f32>],
+    viewer: Vector3<f32>,
+    max_distance: f32,
+    max_count: usize,
+) -> Vec<usize> {
+    let mut visible = vertices
+        .iter()
+        .enumerate()
+        .map(|(index, position)| (index, position.metric_distance(&viewer)))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .collect::<Vec<_>>();
+    visible.sort_by(|a, b| {
+        a.1.partial_cmp(&b.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.0.cmp(&b.0))
+    });
+    visible.truncate(max_count);
+    visible.into_iter().map(|(index, _)| index).collect()
+}
+
+/// Returns the text of the label of a vertex.
+pub fn vertex_label_text(index: usize, position: Vector3<f32>, show_coordinates: bool) -> String {
+    if show_coordinates {
+        format!(
+            "{} ({:.2}, {:.2}, {:.2})",
+            index, position.x, position.y, position.z
+        )
+    } else {
+        index.to_string()
+    }
+}
+
+/// Pool of viewport labels, that show indices of navmesh vertices. Labels are created on demand and
+/// reused, the ones that are not needed at the moment are hidden.
+#[derive(Default)]
+pub struct VertexLabels {
+    labels: Vec<ViewportLabel>,
+    // Number of labels, that were shown by the last update.
+    visible: usize,
+}
+
+impl VertexLabels {
+    /// Shows labels of the vertices close to the camera at the screen projections of the vertices.
+    /// `frame` is the screen bounds of the frame, that shows the image from the `camera`.
+    pub fn update(
+        &mut self,
+        ui: &mut UserInterface,
+        camera: &Camera,
+        frame: Rect<f32>,
+        vertices: &[Vector3<f32>],
+        selected_vertices: &BTreeSet<usize>,
+        settings: &VertexLabelSettings,
+    ) {
+        let visible = labeled_vertices(
+            vertices,
+            camera.global_position(),
+            settings.max_distance,
+            MAX_VERTEX_LABELS,
+        );
+
+        while self.labels.len() < visible.len() {
+            self.labels.push(ViewportLabel::new(&mut ui.build_ctx(), 1));
+        }
+
+        for (label, &index) in self.labels.iter().zip(visible.iter()) {
+            let color = if selected_vertices.contains(&index) {
+                settings.selected_color
+            } else {
+                settings.color
+            };
+            label.set_line(
+                ui,
+                0,
+                vertex_label_text(index, vertices[index], settings.show_coordinates),
+                color,
+            );
+            label.place(
+                ui,
+                camera,
+                vertices[index] + settings.offset,
+                frame.position,
+                frame.size,
+            );
+        }
+
+        for label in self.labels.iter().take(self.visible).skip(visible.len()) {
+            label.set_visible(ui, false);
+        }
+        self.visible = visible.len();
+    }
+
+    /// Hides every label.
+    pub fn hide(&mut self, ui: &UserInterface) {
+        for label in self.labels.iter().take(self.visible) {
+            label.set_visible(ui, false);
+        }
+        self.visible = 0;
+    }
+
+    pub fn destroy(&mut self, ui: &UserInterface) {
+        for label in self.labels.drain(..) {
+            label.destroy(ui);
+        }
+        self.visible = 0;
+    }
+}
+
+/// Appearance of vertex labels.
+pub struct VertexLabelSettings {
+    pub show_coordinates: bool,
+    /// Vertices farther than this distance from the camera are not labeled.
+    pub max_distance: f32,
+    pub color: Color,
+    pub selected_color: Color,
+    /// Offset of labels from the vertices, it matches the offset of the navmesh drawing.
+    pub offset: Vector3<f32>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_labeled_vertices() {
+        let vertices = [
+            Vector3::new(5.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 0.0, 20.0),
+            Vector3::new(0.0, 3.0, 0.0),
+        ];
+
+        // Far vertex is culled, the rest is sorted by distance.
+        assert_eq!(
+            labeled_vertices(&vertices, Vector3::default(), 10.0, 10),
+            vec![1, 3, 0]
+        );
+        assert_eq!(
+            labeled_vertices(&vertices, Vector3::default(), 10.0, 2),
+            vec![1, 3]
+        );
+        assert!(labeled_vertices(&vertices, Vector3::default(), 0.5, 10).is_empty());
+    }
+
+    #[test]
+    fn test_vertex_label_text() {
+        let position = Vector3::new(1.0, -2.5, 0.123);
+        assert_eq!(vertex_label_text(137, position, false), "137");
+        assert_eq!(
+            vertex_label_text(137, position, true),
+            "137 (1.00, -2.50, 0.12)"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! Off-mesh links editing. Links connect points of a navmesh, that can't be connected with triangles
+//! (a jump over a gap, a drop off a ledge, etc.). Both ends of a link are placed at vertices of the
+//! navmesh and follow them, when the vertices are moved.
+
+use crate::interaction::navmesh::validation::NAVMESH_VALIDATION_EPSILON;
+use fyrox::{
+    core::{algebra::Vector3, color::Color},
+    scene::navmesh::NavmeshLink,
+};
+
+/// Number of segments of the arc, that represents a link.
+pub const LINK_ARC_SEGMENTS: usize = 12;
+
+/// Color of links, that are not selected. It matches the color used by the navmesh debug drawing.
+pub const LINK_COLOR: Color = Color::opaque(0, 200, 255);
+
+/// Returns points of the arc, that goes from the beginning of the link to its end. The arc is a
+/// parabola with the height of a quarter of the distance between the ends.
+pub fn link_arc(link: &NavmeshLink) -> Vec<Vector3<f32>> {
+    let height = 0.25 * (link.end - link.begin).norm();
+    (0..=LINK_ARC_SEGMENTS)
+        .map(|i| {
+            let t = i as f32 / LINK_ARC_SEGMENTS as f32;
+            link.begin.lerp(&link.end, t) + Vector3::new(0.0, 4.0 * height * t * (1.0 - t), 0.0)
+        })
+        .collect()
+}
+
+/// Moves every end of the links, that is attached to the `from` point, to the `to` point.
+pub fn move_link_ends(links: &mut [NavmeshLink], from: Vector3<f32>, to: Vector3<f32>) {
+    for link in links.iter_mut() {
+        for end in [&mut link.begin, &mut link.end] {
+            if end.metric_distance(&from) <= NAVMESH_VALIDATION_EPSILON {
+                *end = to;
+            }
+        }
+    }
+}
+
+/// State of the link placement tool, it is shared between the navmesh panel and the navmesh edit
+/// mode.
+#[derive(Default)]
+pub struct NavmeshLinkTool {
+    enabled: bool,
+    bidirectional: bool,
+    begin: Option<Vector3<f32>>,
+}
+
+impl NavmeshLinkTool {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables the tool, the beginning of an unfinished link is discarded in both cases.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        self.begin = None;
+    }
+
+    /// Sets whether new links could be traversed in both directions.
+    pub fn set_bidirectional(&mut self, bidirectional: bool) {
+        self.bidirectional = bidirectional;
+    }
+
+    /// Returns the beginning of the link, that is being placed.
+    pub fn begin(&self) -> Option<Vector3<f32>> {
+        self.begin
+    }
+
+    /// Discards the beginning of an unfinished link.
+    pub fn cancel(&mut self) {
+        self.begin = None;
+    }
+
+    /// Places an end of a link at the given point. The first call sets the beginning of the link, the
+    /// second one returns the finished link. Clicking the beginning again does nothing.
+    pub fn place(&mut self, point: Vector3<f32>) -> Option<NavmeshLink> {
+        match self.begin {
+            Some(begin) if begin.metric_distance(&point) > NAVMESH_VALIDATION_EPSILON => {
+                self.begin = None;
+                Some(NavmeshLink {
+                    begin,
+                    end: point,
+                    bidirectional: self.bidirectional,
+                })
+            }
+            Some(_) => None,
+            None => {
+                self.begin = Some(point);
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_link_arc() {
+        let link = NavmeshLink {
+            begin: Vector3::new(0.0, 1.0, 0.0),
+            end: Vector3::new(4.0, 1.0, 0.0),
+            bidirectional: false,
+        };
+
+        let arc = link_arc(&link);
+        assert_eq!(arc.len(), LINK_ARC_SEGMENTS + 1);
+        assert_eq!(arc.first(), Some(&link.begin));
+        assert_eq!(arc.last(), Some(&link.end));
+        // The top of the arc is in the middle.
+        assert!(arc[LINK_ARC_SEGMENTS / 2].metric_distance(&Vector3::new(2.0, 2.0, 0.0)) < 1.0e-5);
+    }
+
+    #[test]
+    fn test_move_link_ends() {
+        let a = Vector3::new(0.0, 0.0, 0.0);
+        let b = Vector3::new(1.0, 0.0, 0.0);
+        let c = Vector3::new(0.0, 0.0, 1.0);
+        let mut links = vec![
+            NavmeshLink {
+                begin: a,
+                end: b,
+                bidirectional: false,
+            },
+            NavmeshLink {
+                begin: b,
+                end: a,
+                bidirectional: true,
+            },
+        ];
+
+        move_link_ends(&mut links, a, c);
+        assert_eq!((links[0].begin, links[0].end), (c, b));
+        assert_eq!((links[1].begin, links[1].end), (b, c));
+    }
+
+    #[test]
+    fn test_link_placement() {
+        let mut tool = NavmeshLinkTool::default();
+        tool.set_enabled(true);
+        tool.set_bidirectional(true);
+
+        let a = Vector3::new(0.0, 0.0, 0.0);
+        let b = Vector3::new(1.0, 0.0, 0.0);
+
+        assert_eq!(tool.place(a), None);
+        assert_eq!(tool.begin(), Some(a));
+        // Zero length links are not allowed.
+        assert_eq!(tool.place(a), None);
+        assert_eq!(
+            tool.place(b),
+            Some(NavmeshLink {
+                begin: a,
+                end: b,
+                bidirectional: true
+            })
+        );
+        assert_eq!(tool.begin(), None);
+
+        tool.place(a);
+        tool.set_enabled(false);
+        assert_eq!(tool.begin(), None);
+    }
+}
@@ -1,56 +1,108 @@
 use crate::message::MessageSender;
 use crate::{
-    camera::PickingOptions,
+    camera::{ray_cast_scene_geometry, PickingOptions},
+    gui::make_dropdown_list_option,
+    interaction::navmesh::{
+        agent_preview::AgentRadiusPreview,
+        binding::{
+            resolve_navmesh_binding, scene_navmeshes, single_selected_navmesh, NavmeshBinding,
+        },
+        deletion::plan_deletion,
+        hotkeys::{HotkeyContext, NavmeshAction, NavmeshHotkeys},
+        labels::{VertexLabelSettings, VertexLabels},
+        links::{link_arc, NavmeshLinkTool, LINK_COLOR},
+        path_test::{ray_cast_navmesh, NavmeshPathTest, PathMarker, PathTestStatus},
+        slope::SlopePreview,
+        transform::{centroid, VertexTransform, VertexTransformKind},
+        validation::{overlapping_triangles, NavmeshOverlaps, NAVMESH_VALIDATION_EPSILON},
+    },
     interaction::{
         calculate_gizmo_distance_scaling,
         gizmo::move_gizmo::MoveGizmo,
-        navmesh::selection::{NavmeshEntity, NavmeshSelection},
+        navmesh::selection::{
+            connected_vertices, edge_loop, entity_triangles, pick_entity, pick_polyline,
+            pick_triangle, triangle_entities, triangle_island, EdgeAdjacency, NavmeshEntity,
+            NavmeshSelection, PickOptions,
+        },
         plane::PlaneKind,
+        viewport_label::ViewportLabel,
         InteractionMode,
     },
     scene::{
         commands::{
             navmesh::{
-                AddNavmeshEdgeCommand, ConnectNavmeshEdgesCommand, DeleteNavmeshVertexCommand,
-                MoveNavmeshVertexCommand,
+                duplicate_triangles, extrusion_triangle, AddNavmeshEdgeCommand,
+                AddNavmeshLinkCommand, AddNavmeshTriangleCommand,
+                AddNavmeshTriangleFromEdgeCommand, DeleteNavmeshEdgeCommand,
+                DeleteNavmeshLinkCommand, DeleteNavmeshVertexCommand,
+                DuplicateNavmeshTrianglesCommand, ExtrudeNavmeshVertexCommand,
+                MoveNavmeshVertexCommand, SplitNavmeshEdgeCommand, WeldNavmeshVerticesCommand,
             },
             ChangeSelectionCommand, CommandGroup, SceneCommand,
         },
-        EditorScene, Selection,
+        draw_hatching, EditorScene, Selection,
+    },
+    settings::{
+        navmesh::{NavmeshMoveConstraint, NavmeshSceneSettings},
+        Settings,
     },
-    settings::Settings,
-    utils::window_content,
-    Mode,
 };
 use fyrox::{
     core::{
         algebra::{Vector2, Vector3},
         color::Color,
-        math::{ray::CylinderKind, TriangleEdge},
+        log::Log,
+        math::{self, plane::Plane, ray::Ray, TriangleDefinition, TriangleEdge},
         pool::Handle,
-        scope_profile,
     },
     engine::Engine,
     gui::{
-        button::{ButtonBuilder, ButtonMessage},
-        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
         message::{KeyCode, MessageDirection, UiMessage},
-        stack_panel::StackPanelBuilder,
         widget::{WidgetBuilder, WidgetMessage},
         window::{WindowBuilder, WindowMessage, WindowTitle},
-        BuildContext, Orientation, Thickness, UiNode, UserInterface,
+        BuildContext, Thickness, UiNode, UserInterface,
+    },
+    scene::{
+        camera::Camera,
+        debug::{Line, SceneDrawingContext},
+        graph::Graph,
+        navmesh::NavigationalMesh,
+        node::Node,
     },
-    scene::{camera::Camera, navmesh::NavigationalMesh, node::Node},
     utils::astar::PathVertex,
 };
-use std::collections::HashMap;
+use std::{
+    cell::RefCell,
+    collections::{BTreeSet, HashMap},
+    rc::Rc,
+};
 
+pub mod agent_preview;
+pub mod binding;
+pub mod deletion;
+pub mod generator;
+pub mod hotkeys;
+pub mod labels;
+pub mod links;
+pub mod obj;
+pub mod panel;
+pub mod path_test;
 pub mod selection;
+pub mod simplify;
+pub mod slope;
+pub mod transform;
+pub mod validation;
+
+/// Duplicated triangles are shifted by this offset, otherwise they would be hidden by the originals.
+const DUPLICATE_OFFSET: Vector3<f32> = Vector3::new(0.5, 0.0, 0.5);
 
-pub struct NavmeshPanel {
-    pub window: Handle<UiNode>,
-    connect_edges: Handle<UiNode>,
-    sender: MessageSender,
+fn selected_nodes(editor_selection: &Selection) -> Vec<Handle<Node>> {
+    match editor_selection {
+        Selection::Navmesh(selection) => vec![selection.navmesh_node()],
+        Selection::Graph(selection) => selection.nodes().to_vec(),
+        _ => vec![],
+    }
 }
 
 fn fetch_selection(editor_selection: &Selection) -> Option<NavmeshSelection> {
@@ -66,101 +118,81 @@ fn fetch_selection(editor_selection: &Selection) -> Option<NavmeshSelection> {
     }
 }
 
-impl NavmeshPanel {
-    pub fn new(ctx: &mut BuildContext, sender: MessageSender) -> Self {
-        let connect_edges;
-        let window = WindowBuilder::new(WidgetBuilder::new().with_name("NavmeshPanel"))
+struct NavmeshChooser {
+    window: Handle<UiNode>,
+    list: Handle<UiNode>,
+    navmeshes: Vec<Handle<Node>>,
+}
+
+impl NavmeshChooser {
+    fn new(ctx: &mut BuildContext) -> Self {
+        let list;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(250.0).with_height(300.0))
             .open(false)
-            .with_title(WindowTitle::text("Navmesh"))
-            .with_content(
-                GridBuilder::new(
-                    WidgetBuilder::new().with_child(
-                        StackPanelBuilder::new(WidgetBuilder::new().with_child({
-                            connect_edges = ButtonBuilder::new(
-                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
-                            )
-                            .with_text("Connect Edges")
-                            .build(ctx);
-                            connect_edges
-                        }))
-                        .with_orientation(Orientation::Horizontal)
-                        .build(ctx),
-                    ),
-                )
-                .add_column(Column::stretch())
-                .add_row(Row::strict(20.0))
-                .build(ctx),
-            )
+            .with_title(WindowTitle::text("Select Navmesh To Edit"))
+            .with_content({
+                list =
+                    ListViewBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+                        .build(ctx);
+                list
+            })
             .build(ctx);
 
         Self {
             window,
-            sender,
-            connect_edges,
+            list,
+            navmeshes: Default::default(),
         }
     }
 
-    pub fn handle_message(&mut self, message: &UiMessage, editor_scene: &EditorScene) {
-        scope_profile!();
-
-        if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
-            if message.destination() == self.connect_edges {
-                if let Some(selection) = fetch_selection(&editor_scene.selection) {
-                    let vertices = selection
-                        .entities()
-                        .iter()
-                        .filter_map(|entity| {
-                            if let NavmeshEntity::Edge(v) = *entity {
-                                Some(v)
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>();
+    fn open(&mut self, ui: &mut UserInterface, graph: &Graph, navmeshes: Vec<Handle<Node>>) {
+        let items = {
+            let ctx = &mut ui.build_ctx();
+            navmeshes
+                .iter()
+                .map(|h| make_dropdown_list_option(ctx, &format!("{} ({})", graph[*h].name(), h)))
+                .collect::<Vec<_>>()
+        };
+        self.navmeshes = navmeshes;
 
-                    self.sender
-                        .do_scene_command(ConnectNavmeshEdgesCommand::new(
-                            selection.navmesh_node(),
-                            [vertices[0], vertices[1]],
-                        ));
-                }
-            }
-        }
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
     }
 
-    pub fn sync_to_model(&mut self, engine: &Engine, editor_scene: &EditorScene) {
-        let mut navmesh_selected = false;
-
-        let graph = &engine.scenes[editor_scene.scene].graph;
-        if let Some(selection) = fetch_selection(&editor_scene.selection) {
-            navmesh_selected = graph
-                .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
-                .is_some();
-        }
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+        ui: &UserInterface,
+    ) {
+        if let Some(ListViewMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(navmesh) = self.navmeshes.get(*index) {
+                    sender.do_scene_command(ChangeSelectionCommand::new(
+                        Selection::Navmesh(NavmeshSelection::empty(*navmesh)),
+                        editor_scene.selection.clone(),
+                    ));
+                }
 
-        if navmesh_selected {
-            engine.user_interface.send_message(WindowMessage::open(
-                self.window,
-                MessageDirection::ToWidget,
-                false,
-            ));
-        } else {
-            engine.user_interface.send_message(WindowMessage::close(
-                self.window,
-                MessageDirection::ToWidget,
-            ));
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
         }
     }
-
-    pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
-        ui.send_message(WidgetMessage::enabled(
-            window_content(self.window, ui),
-            MessageDirection::ToWidget,
-            mode.is_edit(),
-        ));
-    }
 }
-
 enum DragContext {
     MoveSelection {
         initial_positions: HashMap<usize, Vector3<f32>>,
@@ -169,6 +201,285 @@ enum DragContext {
         vertices: [PathVertex; 2],
         opposite_edge: TriangleEdge,
     },
+    VertexExtrusion {
+        source: usize,
+        vertex: PathVertex,
+        target: Option<usize>,
+    },
+    PathMarker(PathMarker),
+    /// Selected vertices are rotated or scaled around their centroid. Unlike other drags, it is
+    /// started by a key and finished by a click.
+    Transform(VertexTransform),
+}
+
+/// Returns the closest vertex, that is not farther than `radius` from the given vertex.
+fn find_weld_target(vertices: &[Vector3<f32>], vertex: usize, radius: f32) -> Option<usize> {
+    let position = vertices[vertex];
+    vertices
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != vertex)
+        .map(|(i, other)| (i, other.metric_distance(&position)))
+        .filter(|(_, distance)| *distance <= radius)
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, _)| i)
+}
+
+/// Returns the normal of the first triangle, that contains the given edge. `None` if the edge
+/// belongs to no triangle or the triangle is degenerate.
+fn edge_surface_normal(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    edge: TriangleEdge,
+) -> Option<Vector3<f32>> {
+    let triangle = triangles
+        .iter()
+        .find(|triangle| triangle.0.contains(&edge.a) && triangle.0.contains(&edge.b))?;
+    let [a, b, c] = triangle.0.map(|i| vertices[i as usize]);
+    (b - a).cross(&(c - a)).try_normalize(f32::EPSILON)
+}
+
+/// Maximal distance from an extruded vertex to a vertex it could be connected with, in vertex
+/// radii.
+const EXTRUSION_CONNECT_DISTANCE: f32 = 5.0;
+
+/// Returns the closest vertex, that is not farther than `radius` from the position of a vertex
+/// extruded from `source` and forms a non-degenerate triangle with both of them.
+fn find_extrusion_target(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    source: usize,
+    position: Vector3<f32>,
+    radius: f32,
+) -> Option<usize> {
+    let mut positions = vertices.to_vec();
+    positions.push(position);
+    let new = vertices.len() as u32;
+
+    vertices
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != source)
+        .map(|(i, other)| (i, other.metric_distance(&position)))
+        .filter(|(i, distance)| {
+            *distance <= radius
+                && extrusion_triangle(&positions, triangles, source as u32, new, *i as u32)
+                    .is_some()
+        })
+        .min_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, _)| i)
+}
+
+/// Returns the center of the given vertices or `None` if there are no vertices.
+fn vertices_centroid(vertices: &[PathVertex], selected: &BTreeSet<usize>) -> Option<Vector3<f32>> {
+    if selected.is_empty() {
+        return None;
+    }
+    let sum = selected
+        .iter()
+        .fold(Vector3::default(), |sum, v| sum + vertices[*v].position);
+    Some(sum.scale(1.0 / selected.len() as f32))
+}
+
+/// Returns the moves (index, old position, new position) of the vertices, that put the center of the
+/// vertices at the given position and keep their relative positions. A single vertex is put
+/// exactly at the position.
+fn place_vertices(
+    vertices: &[PathVertex],
+    selected: &BTreeSet<usize>,
+    position: Vector3<f32>,
+) -> Vec<(usize, Vector3<f32>, Vector3<f32>)> {
+    let centroid = match vertices_centroid(vertices, selected) {
+        Some(centroid) => centroid,
+        None => return Vec::new(),
+    };
+
+    selected
+        .iter()
+        .map(|v| {
+            let old_position = vertices[*v].position;
+            let new_position = if selected.len() == 1 {
+                position
+            } else {
+                old_position + (position - centroid)
+            };
+            (*v, old_position, new_position)
+        })
+        .filter(|(_, old_position, new_position)| old_position != new_position)
+        .collect()
+}
+
+fn path_marker_radius(scene_settings: &NavmeshSceneSettings) -> f32 {
+    1.5 * scene_settings.vertex_radius
+}
+
+/// Moves the lines into the scene drawing context, either on top of the scene geometry or not,
+/// depending on the settings.
+fn commit_navmesh_lines(
+    drawing_context: &mut SceneDrawingContext,
+    mut lines: SceneDrawingContext,
+    settings: &Settings,
+) {
+    if settings.navmesh.draw_on_top {
+        drawing_context.overlay_lines.append(&mut lines.lines);
+    } else {
+        drawing_context.lines.append(&mut lines.lines);
+    }
+}
+
+/// Moves the point vertically onto the scene geometry below (or slightly above) it. The point is
+/// left untouched if there's no geometry nearby.
+fn snap_to_geometry(
+    position: Vector3<f32>,
+    graph: &Graph,
+    editor_scene: &EditorScene,
+    settings: &Settings,
+) -> Vector3<f32> {
+    project_onto_geometry(position, graph, editor_scene, settings).unwrap_or(position)
+}
+
+/// Returns the position of the point projected vertically onto the scene geometry, or `None` if
+/// there's no geometry within the snap distance.
+fn project_onto_geometry(
+    position: Vector3<f32>,
+    graph: &Graph,
+    editor_scene: &EditorScene,
+    settings: &Settings,
+) -> Option<Vector3<f32>> {
+    let distance = settings.navmesh.snap_distance;
+    let ray = Ray::new(
+        position + Vector3::new(0.0, distance, 0.0),
+        Vector3::new(0.0, -2.0 * distance, 0.0),
+    );
+
+    // Back faces are ignored, because vertices should snap onto floors, not onto the ceilings.
+    ray_cast_scene_geometry(
+        graph,
+        &ray,
+        editor_scene.scene_content_root,
+        editor_scene.editor_objects_root,
+        true,
+        |_, _| true,
+    )
+    .map(|result| {
+        Vector3::new(
+            position.x,
+            result.position.y + editor_scene.navmesh_settings.snap_offset,
+            position.z,
+        )
+    })
+}
+
+/// Returns limits of picking of navmesh entities by a ray, that goes from the camera.
+fn pick_options(
+    graph: &Graph,
+    ray: &Ray,
+    editor_scene: &EditorScene,
+    settings: &Settings,
+) -> PickOptions {
+    let occluder_distance = if settings.navmesh.occlusion_picking {
+        ray_cast_scene_geometry(
+            graph,
+            ray,
+            editor_scene.scene_content_root,
+            editor_scene.editor_objects_root,
+            settings.selection.ignore_back_faces,
+            |_, _| true,
+        )
+        .map(|result| result.position.metric_distance(&ray.origin))
+    } else {
+        None
+    };
+
+    PickOptions {
+        radius: editor_scene.navmesh_settings.vertex_radius,
+        max_distance: settings.navmesh.max_pick_distance,
+        occluder_distance,
+    }
+}
+
+/// Distance from the camera, at which a new triangle is placed if there's no geometry to put it on.
+const NEW_TRIANGLE_DISTANCE: f32 = 5.0;
+
+/// Distance from the center of a new triangle to its vertices, in vertex radii.
+const NEW_TRIANGLE_SIZE: f32 = 5.0;
+
+/// Returns vertices of an equilateral triangle lying in the horizontal plane. Vertices go
+/// counterclockwise, when looking from above.
+fn equilateral_triangle(center: Vector3<f32>, radius: f32) -> [Vector3<f32>; 3] {
+    let vertex = |angle: f32| center + Vector3::new(angle.cos(), 0.0, -angle.sin()).scale(radius);
+    let step = 2.0 * std::f32::consts::FRAC_PI_3;
+    [
+        vertex(std::f32::consts::FRAC_PI_2),
+        vertex(std::f32::consts::FRAC_PI_2 + step),
+        vertex(std::f32::consts::FRAC_PI_2 + 2.0 * step),
+    ]
+}
+
+/// Creates a command, that adds a standalone triangle to the selected navmesh. The triangle is
+/// centered at the point where the ray hits the scene geometry or, if nothing is hit, at a fixed
+/// distance along the ray.
+fn add_triangle_command(
+    ray: &Ray,
+    graph: &Graph,
+    editor_scene: &EditorScene,
+) -> Option<AddNavmeshTriangleCommand> {
+    let selection = fetch_selection(&editor_scene.selection)?;
+    graph.try_get_of_type::<NavigationalMesh>(selection.navmesh_node())?;
+
+    let center = match ray_cast_scene_geometry(
+        graph,
+        ray,
+        editor_scene.scene_content_root,
+        editor_scene.editor_objects_root,
+        false,
+        |_, _| true,
+    ) {
+        Some(result) => {
+            result.position + Vector3::new(0.0, editor_scene.navmesh_settings.snap_offset, 0.0)
+        }
+        None => {
+            ray.origin
+                + ray
+                    .dir
+                    .try_normalize(f32::EPSILON)
+                    .unwrap_or_default()
+                    .scale(NEW_TRIANGLE_DISTANCE)
+        }
+    };
+
+    Some(AddNavmeshTriangleCommand::new(
+        selection.navmesh_node(),
+        equilateral_triangle(
+            center,
+            NEW_TRIANGLE_SIZE * editor_scene.navmesh_settings.vertex_radius,
+        ),
+    ))
+}
+
+fn draw_dashed_line(
+    drawing_context: &mut SceneDrawingContext,
+    begin: Vector3<f32>,
+    end: Vector3<f32>,
+    dash_length: f32,
+    color: Color,
+) {
+    let delta = end - begin;
+    let length = delta.norm();
+    if length <= f32::EPSILON || dash_length <= f32::EPSILON {
+        return;
+    }
+
+    let dir = delta.scale(1.0 / length);
+    let mut distance = 0.0;
+    while distance < length {
+        drawing_context.add_line(Line {
+            begin: begin + dir.scale(distance),
+            end: begin + dir.scale((distance + dash_length).min(length)),
+            color,
+        });
+        distance += 2.0 * dash_length;
+    }
 }
 
 impl DragContext {
@@ -177,11 +488,128 @@ impl DragContext {
     }
 }
 
+/// Constraint of the movement of dragged vertices. It is chosen when a drag starts and stays the
+/// same until the drag ends.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum DragConstraint {
+    Plane(PlaneKind),
+    ScreenPlane,
+}
+
+impl DragConstraint {
+    fn new(picked: PlaneKind, lock_y: bool, center_constraint: NavmeshMoveConstraint) -> Self {
+        if lock_y {
+            DragConstraint::Plane(PlaneKind::Y)
+        } else if picked == PlaneKind::SMART {
+            match center_constraint {
+                NavmeshMoveConstraint::ScreenPlane => DragConstraint::ScreenPlane,
+                NavmeshMoveConstraint::HorizontalPlane => DragConstraint::Plane(PlaneKind::ZX),
+            }
+        } else {
+            DragConstraint::Plane(picked)
+        }
+    }
+
+    /// Part of the move gizmo, that should be highlighted.
+    fn gizmo_mode(self) -> PlaneKind {
+        match self {
+            DragConstraint::Plane(plane_kind) => plane_kind,
+            DragConstraint::ScreenPlane => PlaneKind::SMART,
+        }
+    }
+}
+
+/// Calculates movement of the point in the plane, that goes through the point and is parallel to
+/// the view plane of the camera.
+fn screen_plane_offset(
+    camera: &Camera,
+    point: Vector3<f32>,
+    mouse_position: Vector2<f32>,
+    mouse_offset: Vector2<f32>,
+    frame_size: Vector2<f32>,
+) -> Vector3<f32> {
+    let plane = match Plane::from_normal_and_point(&camera.look_vector(), &point) {
+        Some(plane) => plane,
+        None => return Vector3::default(),
+    };
+
+    let initial_point = camera
+        .make_ray(mouse_position, frame_size)
+        .plane_intersection_point(&plane);
+    let next_point = camera
+        .make_ray(mouse_position + mouse_offset, frame_size)
+        .plane_intersection_point(&plane);
+    match (initial_point, next_point) {
+        (Some(initial_point), Some(next_point)) => next_point - initial_point,
+        _ => Vector3::default(),
+    }
+}
+
+/// Drops the handle of the edited navmesh, if the node was deleted (by another editor action or by
+/// undo) or is not a navmesh anymore. An unfinished drag is discarded, so the mouse up commits
+/// nothing, and the selection of the entities of the node is cleared. Returns `true` if the handle
+/// was dropped.
+fn drop_deleted_navmesh(
+    navmesh: &mut Handle<Node>,
+    drag_context: &mut Option<DragContext>,
+    selection: &Selection,
+    graph: &Graph,
+    sender: &MessageSender,
+) -> bool {
+    if navmesh.is_none()
+        || graph
+            .try_get_of_type::<NavigationalMesh>(*navmesh)
+            .is_some()
+    {
+        return false;
+    }
+
+    Log::warn(format!(
+        "Edited navmesh {} was deleted, navmesh editing is stopped.",
+        navmesh
+    ));
+
+    if matches!(selection, Selection::Navmesh(s) if s.navmesh_node() == *navmesh) {
+        sender.do_scene_command(ChangeSelectionCommand::new(
+            Selection::None,
+            selection.clone(),
+        ));
+    }
+
+    *navmesh = Handle::NONE;
+    *drag_context = None;
+    true
+}
+
 pub struct EditNavmeshMode {
     move_gizmo: MoveGizmo,
     message_sender: MessageSender,
     drag_context: Option<DragContext>,
-    plane_kind: PlaneKind,
+    drag_constraint: DragConstraint,
+    // Locks the movement to Y axis, it is switched by Y key.
+    lock_y: bool,
+    chooser: NavmeshChooser,
+    drag_readout: ViewportLabel,
+    path_test: Rc<RefCell<NavmeshPathTest>>,
+    link_tool: Rc<RefCell<NavmeshLinkTool>>,
+    overlaps: Rc<RefCell<NavmeshOverlaps>>,
+    // Whether overlapping triangles were searched automatically on the previous update.
+    auto_overlaps: bool,
+    agent_preview: AgentRadiusPreview,
+    slope_preview: SlopePreview,
+    // Frame of the scene viewer, vertex labels are placed relative to it.
+    preview: Handle<UiNode>,
+    vertex_labels: VertexLabels,
+    // Every key of the mode is dispatched through this table, it is shown by the help overlay too.
+    hotkeys: NavmeshHotkeys,
+    help: ViewportLabel,
+    show_help: bool,
+    // Last known position of the cursor in the scene viewer and the size of the viewer, used by
+    // keyboard actions that place something under the cursor.
+    mouse_position: Vector2<f32>,
+    frame_size: Vector2<f32>,
+    // Navmesh node, that is edited right now. It is `NONE` if there's nothing to edit.
+    navmesh: Handle<Node>,
 }
 
 impl EditNavmeshMode {
@@ -189,12 +617,272 @@ impl EditNavmeshMode {
         editor_scene: &EditorScene,
         engine: &mut Engine,
         message_sender: MessageSender,
+        preview: Handle<UiNode>,
+        path_test: Rc<RefCell<NavmeshPathTest>>,
+        link_tool: Rc<RefCell<NavmeshLinkTool>>,
+        overlaps: Rc<RefCell<NavmeshOverlaps>>,
     ) -> Self {
+        let hotkeys = NavmeshHotkeys::default();
+        let help = ViewportLabel::new(&mut engine.user_interface.build_ctx(), 1);
+        help.set_line(
+            &engine.user_interface,
+            0,
+            hotkeys.help_text(),
+            Color::opaque(220, 220, 220),
+        );
+
         Self {
             move_gizmo: MoveGizmo::new(editor_scene, engine),
             message_sender,
             drag_context: None,
-            plane_kind: PlaneKind::X,
+            drag_constraint: DragConstraint::Plane(PlaneKind::X),
+            lock_y: false,
+            chooser: NavmeshChooser::new(&mut engine.user_interface.build_ctx()),
+            drag_readout: ViewportLabel::new(&mut engine.user_interface.build_ctx(), 3),
+            path_test,
+            link_tool,
+            overlaps,
+            auto_overlaps: false,
+            agent_preview: Default::default(),
+            slope_preview: Default::default(),
+            preview,
+            vertex_labels: Default::default(),
+            hotkeys,
+            help,
+            show_help: false,
+            mouse_position: Default::default(),
+            frame_size: Default::default(),
+            navmesh: Default::default(),
+        }
+    }
+
+    /// Follows changes of the editor selection. Selecting a single navmesh node (in the world
+    /// viewer, for example) switches the mode to that navmesh, and the handle of a navmesh, that
+    /// was deleted, is dropped. It is called at the start of every callback, so none of them works
+    /// with a stale handle.
+    fn sync_navmesh(&mut self, editor_scene: &mut EditorScene, engine: &mut Engine) {
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        let is_navmesh = |h: Handle<Node>| graph.try_get_of_type::<NavigationalMesh>(h).is_some();
+
+        let navmesh = match &editor_scene.selection {
+            Selection::Navmesh(selection) if is_navmesh(selection.navmesh_node()) => {
+                selection.navmesh_node()
+            }
+            Selection::Graph(selection) => {
+                match single_selected_navmesh(selection.nodes(), is_navmesh) {
+                    Some(navmesh) if navmesh != self.navmesh => {
+                        // Entities of the old navmesh must not stay selected. Selection is changed
+                        // only when the navmesh is switched, otherwise undo of this change would
+                        // be immediately followed by the same change.
+                        self.message_sender
+                            .do_scene_command(ChangeSelectionCommand::new(
+                                Selection::Navmesh(NavmeshSelection::empty(navmesh)),
+                                editor_scene.selection.clone(),
+                            ));
+                        navmesh
+                    }
+                    _ => self.navmesh,
+                }
+            }
+            _ => self.navmesh,
+        };
+
+        if navmesh != self.navmesh {
+            self.navmesh = navmesh;
+            self.drag_context = None;
+        }
+
+        if self.navmesh.is_some() && is_navmesh(self.navmesh) {
+            editor_scene.navmesh = self.navmesh;
+        }
+
+        let deleted = self.navmesh;
+        if drop_deleted_navmesh(
+            &mut self.navmesh,
+            &mut self.drag_context,
+            &editor_scene.selection,
+            graph,
+            &self.message_sender,
+        ) {
+            if editor_scene.navmesh == deleted {
+                editor_scene.navmesh = Handle::NONE;
+            }
+            self.move_gizmo.set_visible(graph, false);
+            self.drag_readout.set_visible(&engine.user_interface, false);
+        }
+    }
+
+    /// Reprojects labels of the vertices of the edited navmesh, they follow the camera every frame.
+    fn update_vertex_labels(
+        &mut self,
+        editor_scene: &EditorScene,
+        camera: Handle<Node>,
+        engine: &mut Engine,
+        settings: &Settings,
+    ) {
+        let graph = &engine.scenes[editor_scene.scene].graph;
+        let ui = &mut engine.user_interface;
+
+        let (navmesh, camera) = match (
+            graph.try_get_of_type::<NavigationalMesh>(self.navmesh),
+            graph.try_get_of_type::<Camera>(camera),
+        ) {
+            (Some(navmesh), Some(camera)) if settings.navmesh.show_vertex_labels => {
+                (navmesh.navmesh_ref(), camera)
+            }
+            _ => {
+                self.vertex_labels.hide(ui);
+                return;
+            }
+        };
+
+        let selected_vertices = match &editor_scene.selection {
+            Selection::Navmesh(selection) if selection.navmesh_node() == self.navmesh => {
+                selection.unique_vertices().clone()
+            }
+            _ => Default::default(),
+        };
+        let positions = navmesh
+            .vertices()
+            .iter()
+            .map(|v| v.position)
+            .collect::<Vec<_>>();
+        let frame = ui.node(self.preview).screen_bounds();
+
+        self.vertex_labels.update(
+            ui,
+            camera,
+            frame,
+            &positions,
+            &selected_vertices,
+            &VertexLabelSettings {
+                show_coordinates: settings.navmesh.show_label_coordinates,
+                max_distance: settings.navmesh.label_distance,
+                color: settings.navmesh.vertex_color,
+                selected_color: settings.navmesh.selected_vertex_color,
+                offset: Vector3::new(0.0, editor_scene.navmesh_settings.height_offset, 0.0),
+            },
+        );
+    }
+
+    /// Starts rotation or scaling of the selected vertices around their centroid. It follows the
+    /// cursor, the cursor position at the start of the transform is the reference one.
+    fn start_transform(
+        &mut self,
+        kind: VertexTransformKind,
+        selection: &NavmeshSelection,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+    ) {
+        let graph = &engine.scenes[editor_scene.scene].graph;
+        let (navmesh, camera) = match (
+            graph.try_get_of_type::<NavigationalMesh>(selection.navmesh_node()),
+            graph.try_get_of_type::<Camera>(editor_scene.camera_controller.camera),
+        ) {
+            (Some(navmesh), Some(camera)) => (navmesh.navmesh_ref(), camera),
+            _ => return,
+        };
+
+        let initial_positions = selection
+            .unique_vertices()
+            .iter()
+            .filter_map(|&vertex| navmesh.vertices().get(vertex).map(|v| (vertex, v.position)))
+            .collect::<Vec<_>>();
+        let positions = initial_positions
+            .iter()
+            .map(|(_, position)| *position)
+            .collect::<Vec<_>>();
+
+        // Vertices are drawn with the offset, so the pivot is projected the same way.
+        let offset = Vector3::new(0.0, editor_scene.navmesh_settings.height_offset, 0.0);
+        if let Some(pivot) = camera.project(centroid(&positions) + offset, self.frame_size) {
+            self.drag_context =
+                VertexTransform::new(kind, initial_positions, self.mouse_position - pivot)
+                    .map(DragContext::Transform);
+        }
+    }
+
+    /// Moves transformed vertices according to the current cursor position.
+    fn update_transform(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
+        let transform = match self.drag_context.as_ref() {
+            Some(DragContext::Transform(transform)) => transform,
+            _ => return,
+        };
+
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        let offset = Vector3::new(0.0, editor_scene.navmesh_settings.height_offset, 0.0);
+        let positions = match graph
+            .try_get_of_type::<Camera>(editor_scene.camera_controller.camera)
+            .and_then(|camera| {
+                camera
+                    .project(transform.pivot + offset, self.frame_size)
+                    .map(|pivot| transform.apply(self.mouse_position - pivot, camera.look_vector()))
+            }) {
+            Some(positions) => positions,
+            None => return,
+        };
+
+        if let Some(navmesh) = graph
+            .try_get_mut_of_type::<NavigationalMesh>(self.navmesh)
+            .map(|n| n.navmesh_mut())
+        {
+            for (vertex, position) in positions {
+                if let Some(vertex) = navmesh.vertices_mut().get_mut(vertex) {
+                    vertex.position = position;
+                }
+            }
+        }
+    }
+
+    /// Finishes the transform of the vertices, if there's any. New positions are committed as one
+    /// group of commands, otherwise the vertices are moved back.
+    fn finish_transform(&mut self, commit: bool, editor_scene: &EditorScene, engine: &mut Engine) {
+        let transform = match self.drag_context.take() {
+            Some(DragContext::Transform(transform)) => transform,
+            drag_context => {
+                self.drag_context = drag_context;
+                return;
+            }
+        };
+
+        let navmesh = match engine.scenes[editor_scene.scene]
+            .graph
+            .try_get_mut_of_type::<NavigationalMesh>(self.navmesh)
+            .map(|n| n.navmesh_mut())
+        {
+            Some(navmesh) => navmesh,
+            None => return,
+        };
+
+        if commit {
+            let commands = transform
+                .initial_positions
+                .iter()
+                .filter_map(|&(vertex, initial_position)| {
+                    navmesh.vertices().get(vertex).map(|v| {
+                        SceneCommand::new(MoveNavmeshVertexCommand::new(
+                            self.navmesh,
+                            vertex,
+                            initial_position,
+                            v.position,
+                        ))
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            self.message_sender
+                .do_scene_command(CommandGroup::from(commands).with_custom_name(
+                    match transform.kind {
+                        VertexTransformKind::Rotate => "Rotate Navmesh Vertices",
+                        VertexTransformKind::Scale => "Scale Navmesh Vertices",
+                    },
+                ));
+        } else {
+            for (vertex, initial_position) in transform.initial_positions {
+                if let Some(vertex) = navmesh.vertices_mut().get_mut(vertex) {
+                    vertex.position = initial_position;
+                }
+            }
         }
     }
 }
@@ -208,6 +896,14 @@ impl InteractionMode for EditNavmeshMode {
         frame_size: Vector2<f32>,
         settings: &Settings,
     ) {
+        self.sync_navmesh(editor_scene, engine);
+
+        if let Some(DragContext::Transform(_)) = self.drag_context {
+            self.finish_transform(true, editor_scene, engine);
+            return;
+        }
+
+        let scene_settings = editor_scene.navmesh_settings.clone();
         let scene = &mut engine.scenes[editor_scene.scene];
         let camera: &Camera = scene.graph[editor_scene.camera_controller.camera].as_camera();
         let ray = camera.make_ray(mouse_pos, frame_size);
@@ -237,6 +933,61 @@ impl InteractionMode for EditNavmeshMode {
         if let Some(selection) = fetch_selection(&editor_scene.selection) {
             let graph = &mut engine.scenes[editor_scene.scene].graph;
 
+            // Markers of the path preview have priority over everything else.
+            if self.path_test.borrow().is_enabled() {
+                if let Some(navmesh) = graph
+                    .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                    .map(|n| n.navmesh_ref())
+                {
+                    let mut path_test = self.path_test.borrow_mut();
+                    let offset = Vector3::new(0.0, scene_settings.height_offset, 0.0);
+                    let picked_marker =
+                        [PathMarker::Start, PathMarker::End]
+                            .into_iter()
+                            .find(|marker| {
+                                path_test.marker(*marker).map_or(false, |position| {
+                                    ray.sphere_intersection(
+                                        &(position + offset),
+                                        path_marker_radius(&scene_settings),
+                                    )
+                                    .is_some()
+                                })
+                            });
+
+                    if let Some(marker) = picked_marker {
+                        self.drag_context = Some(DragContext::PathMarker(marker));
+                        return;
+                    } else if engine.user_interface.keyboard_modifiers().control {
+                        if let Some(point) = ray_cast_navmesh(navmesh, &ray) {
+                            path_test.place_marker(selection.navmesh_node(), point);
+                        }
+                        return;
+                    }
+                }
+            }
+
+            if self.link_tool.borrow().is_enabled() {
+                if let Some(navmesh) = graph
+                    .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                    .map(|n| n.navmesh_ref())
+                {
+                    // Ends of links are attached to vertices, so they could follow them.
+                    let vertex = ray_cast_navmesh(navmesh, &ray)
+                        .and_then(|point| math::get_closest_point(navmesh.vertices(), point));
+                    if let Some(vertex) = vertex {
+                        let point = navmesh.vertices()[vertex].position;
+                        if let Some(link) = self.link_tool.borrow_mut().place(point) {
+                            self.message_sender
+                                .do_scene_command(AddNavmeshLinkCommand::new(
+                                    selection.navmesh_node(),
+                                    link,
+                                ));
+                        }
+                    }
+                    return;
+                }
+            }
+
             if let Some(plane_kind) = self.move_gizmo.handle_pick(editor_node, graph) {
                 if let Some(navmesh) = graph
                     .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
@@ -246,51 +997,107 @@ impl InteractionMode for EditNavmeshMode {
                     for (index, vertex) in navmesh.vertices().iter().enumerate() {
                         initial_positions.insert(index, vertex.position);
                     }
-                    self.plane_kind = plane_kind;
+                    self.drag_constraint = DragConstraint::new(
+                        plane_kind,
+                        self.lock_y,
+                        settings.navmesh.move_constraint,
+                    );
+                    self.move_gizmo
+                        .apply_mode(Some(self.drag_constraint.gizmo_mode()), graph);
                     self.drag_context = Some(DragContext::MoveSelection { initial_positions });
                 }
             } else if let Some(navmesh) = graph
                 .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
                 .map(|n| n.navmesh_ref())
             {
-                let mut new_selection = if engine.user_interface.keyboard_modifiers().shift {
-                    selection
-                } else {
-                    NavmeshSelection::empty(selection.navmesh_node())
+                // Entities are picked where they're drawn.
+                let offset = Vector3::new(0.0, scene_settings.height_offset, 0.0);
+                let positions = navmesh
+                    .vertices()
+                    .iter()
+                    .map(|v| v.position + offset)
+                    .collect::<Vec<_>>();
+                let arcs = graph[selection.navmesh_node()]
+                    .as_navigational_mesh()
+                    .links()
+                    .iter()
+                    .map(|link| link_arc(link).into_iter().map(|p| p + offset).collect())
+                    .collect::<Vec<_>>();
+                // Links are drawn above the navmesh, so they have priority.
+                let picked = pick_polyline(&ray, &arcs, scene_settings.vertex_radius)
+                    .map(NavmeshEntity::Link)
+                    .or_else(|| {
+                        pick_entity(
+                            &ray,
+                            &positions,
+                            navmesh.triangles(),
+                            &pick_options(graph, &ray, editor_scene, settings),
+                        )
+                    });
+
+                let modifiers = engine.user_interface.keyboard_modifiers();
+                // Alt+Click on an edge picks the whole edge loop going through it.
+                let picked = match picked {
+                    Some(NavmeshEntity::Edge(edge)) if modifiers.alt => {
+                        edge_loop(&positions, &EdgeAdjacency::new(navmesh.triangles()), edge)
+                            .into_iter()
+                            .map(NavmeshEntity::Edge)
+                            .collect::<Vec<_>>()
+                    }
+                    picked => picked.into_iter().collect(),
                 };
 
-                let mut picked = false;
-                for (index, vertex) in navmesh.vertices().iter().enumerate() {
-                    if ray
-                        .sphere_intersection(&vertex.position, settings.navmesh.vertex_radius)
-                        .is_some()
-                    {
-                        new_selection.add(NavmeshEntity::Vertex(index));
-                        picked = true;
-                        break;
-                    }
-                }
-
-                if !picked {
-                    for triangle in navmesh.triangles().iter() {
-                        for edge in &triangle.edges() {
-                            let begin = navmesh.vertices()[edge.a as usize].position;
-                            let end = navmesh.vertices()[edge.b as usize].position;
-                            if ray
-                                .cylinder_intersection(
-                                    &begin,
-                                    &end,
-                                    settings.navmesh.vertex_radius,
-                                    CylinderKind::Finite,
-                                )
-                                .is_some()
-                            {
-                                new_selection.add(NavmeshEntity::Edge(*edge));
-                                break;
+                let new_selection = if modifiers.control {
+                    // Ctrl+Click removes selected entities from the selection. Otherwise, if a
+                    // single edge is selected, it adds a triangle from the edge to the clicked
+                    // point of the scene geometry.
+                    if !picked.iter().any(|entity| selection.contains(entity)) {
+                        if let (true, Some(&NavmeshEntity::Edge(edge))) =
+                            (selection.is_single_selection(), selection.first())
+                        {
+                            if let Some(result) = ray_cast_scene_geometry(
+                                graph,
+                                &ray,
+                                editor_scene.scene_content_root,
+                                editor_scene.editor_objects_root,
+                                settings.selection.ignore_back_faces,
+                                |_, _| true,
+                            ) {
+                                let position = result.position
+                                    + Vector3::new(
+                                        0.0,
+                                        editor_scene.navmesh_settings.snap_offset,
+                                        0.0,
+                                    );
+                                self.message_sender.do_scene_command(
+                                    AddNavmeshTriangleFromEdgeCommand::new(
+                                        selection.navmesh_node(),
+                                        edge,
+                                        position,
+                                    ),
+                                );
                             }
                         }
+                        return;
                     }
-                }
+                    let mut new_selection = selection;
+                    for entity in picked.iter() {
+                        new_selection.remove(entity);
+                    }
+                    new_selection
+                } else {
+                    let mut new_selection = if modifiers.shift {
+                        selection
+                    } else {
+                        NavmeshSelection::empty(selection.navmesh_node())
+                    };
+                    for entity in picked {
+                        if !new_selection.contains(&entity) {
+                            new_selection.add(entity);
+                        }
+                    }
+                    new_selection
+                };
 
                 let new_selection = Selection::Navmesh(new_selection);
 
@@ -305,17 +1112,124 @@ impl InteractionMode for EditNavmeshMode {
         }
     }
 
+    fn on_left_mouse_button_double_click(
+        &mut self,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+        mouse_pos: Vector2<f32>,
+        frame_size: Vector2<f32>,
+        settings: &Settings,
+    ) {
+        self.sync_navmesh(editor_scene, engine);
+
+        let modifiers = engine.user_interface.keyboard_modifiers();
+        // Ctrl+Click is used by the path preview and to deselect entities.
+        if self.link_tool.borrow().is_enabled() || modifiers.control {
+            return;
+        }
+
+        match self.drag_context {
+            // The second click usually hits the gizmo of the entity selected by the first click.
+            // Nothing was moved yet, so the drag is just dropped.
+            Some(DragContext::MoveSelection { .. }) => {
+                self.drag_context = None;
+                self.move_gizmo
+                    .reset_state(&mut engine.scenes[editor_scene.scene].graph);
+            }
+            Some(_) => return,
+            None => (),
+        }
+
+        let selection = match fetch_selection(&editor_scene.selection) {
+            Some(selection) => selection,
+            None => return,
+        };
+        let graph = &engine.scenes[editor_scene.scene].graph;
+        let navmesh = match graph
+            .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+            .map(|n| n.navmesh_ref())
+        {
+            Some(navmesh) => navmesh,
+            None => return,
+        };
+        let ray = graph[editor_scene.camera_controller.camera]
+            .as_camera()
+            .make_ray(mouse_pos, frame_size);
+
+        // Entities are picked where they're drawn.
+        let offset = Vector3::new(0.0, editor_scene.navmesh_settings.height_offset, 0.0);
+        let positions = navmesh
+            .vertices()
+            .iter()
+            .map(|v| v.position + offset)
+            .collect::<Vec<_>>();
+
+        // A vertex selects its fan, an edge selects its triangles and a triangle selects its island.
+        let triangles = match pick_entity(
+            &ray,
+            &positions,
+            navmesh.triangles(),
+            &pick_options(graph, &ray, editor_scene, settings),
+        ) {
+            Some(entity) => entity_triangles(navmesh.triangles(), &entity),
+            None => pick_triangle(&ray, &positions, navmesh.triangles())
+                .map(|triangle| triangle_island(navmesh.triangles(), positions.len(), triangle))
+                .unwrap_or_default(),
+        };
+        if triangles.is_empty() {
+            return;
+        }
+
+        let mut new_selection = if modifiers.shift {
+            selection
+        } else {
+            NavmeshSelection::empty(selection.navmesh_node())
+        };
+        for entity in triangle_entities(navmesh.triangles(), triangles) {
+            if !new_selection.contains(&entity) {
+                new_selection.add(entity);
+            }
+        }
+
+        let new_selection = Selection::Navmesh(new_selection);
+        if new_selection != editor_scene.selection {
+            self.message_sender
+                .do_scene_command(ChangeSelectionCommand::new(
+                    new_selection,
+                    editor_scene.selection.clone(),
+                ));
+        }
+    }
+
     fn on_left_mouse_button_up(
         &mut self,
         editor_scene: &mut EditorScene,
         engine: &mut Engine,
         _mouse_pos: Vector2<f32>,
         _frame_size: Vector2<f32>,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
-        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        self.sync_navmesh(editor_scene, engine);
+        self.drag_readout.set_visible(&engine.user_interface, false);
 
-        self.move_gizmo.reset_state(graph);
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+
+        if self.lock_y {
+            self.move_gizmo.apply_mode(Some(PlaneKind::Y), graph);
+        } else {
+            self.move_gizmo.reset_state(graph);
+        }
+
+        match self.drag_context {
+            // Path markers are not a part of the navmesh, there's nothing to commit.
+            Some(DragContext::PathMarker(_)) => {
+                self.drag_context = None;
+                return;
+            }
+            // Transform of vertices is finished by a click, not by a release of the button.
+            Some(DragContext::Transform(_)) => return,
+            _ => (),
+        }
 
         if let Some(selection) = fetch_selection(&editor_scene.selection) {
             if let Some(navmesh) = graph
@@ -335,6 +1249,32 @@ impl InteractionMode for EditNavmeshMode {
                                     navmesh.vertices()[*vertex].position,
                                 )));
                             }
+
+                            // A single vertex dropped onto another one is welded with it. Both
+                            // commands are in the same group, so undo restores the state before
+                            // the drag at once.
+                            let unique_vertices = selection.unique_vertices();
+                            if settings.navmesh.weld_on_drop && unique_vertices.len() == 1 {
+                                let vertex = *unique_vertices.iter().next().unwrap();
+                                let positions = navmesh
+                                    .vertices()
+                                    .iter()
+                                    .map(|v| v.position)
+                                    .collect::<Vec<_>>();
+                                if let Some(target) = find_weld_target(
+                                    &positions,
+                                    vertex,
+                                    editor_scene.navmesh_settings.vertex_radius,
+                                ) {
+                                    commands.push(SceneCommand::new(
+                                        WeldNavmeshVerticesCommand::new(
+                                            selection.navmesh_node(),
+                                            vertex,
+                                            target,
+                                        ),
+                                    ));
+                                }
+                            }
                         }
                         DragContext::EdgeDuplication {
                             vertices,
@@ -350,6 +1290,19 @@ impl InteractionMode for EditNavmeshMode {
                                 true,
                             )));
                         }
+                        DragContext::VertexExtrusion {
+                            source,
+                            vertex,
+                            target,
+                        } => {
+                            commands.push(SceneCommand::new(ExtrudeNavmeshVertexCommand::new(
+                                selection.navmesh_node(),
+                                source,
+                                vertex.position,
+                                target,
+                            )));
+                        }
+                        DragContext::PathMarker(_) | DragContext::Transform(_) => (),
                     }
 
                     self.message_sender
@@ -367,21 +1320,65 @@ impl InteractionMode for EditNavmeshMode {
         editor_scene: &mut EditorScene,
         engine: &mut Engine,
         frame_size: Vector2<f32>,
-        _settings: &Settings,
+        settings: &Settings,
     ) {
+        self.mouse_position = mouse_position;
+        self.frame_size = frame_size;
+
+        self.sync_navmesh(editor_scene, engine);
+
         if self.drag_context.is_none() {
             return;
         }
 
-        let offset = self.move_gizmo.calculate_offset(
-            editor_scene,
-            camera,
-            mouse_offset,
-            mouse_position,
-            engine,
-            frame_size,
-            self.plane_kind,
-        );
+        if let Some(DragContext::Transform(_)) = self.drag_context {
+            self.update_transform(editor_scene, engine);
+            return;
+        }
+
+        if let Some(DragContext::PathMarker(marker)) = self.drag_context {
+            let graph = &engine.scenes[editor_scene.scene].graph;
+            if let (Some(selection), Some(camera)) = (
+                fetch_selection(&editor_scene.selection),
+                graph.try_get_of_type::<Camera>(camera),
+            ) {
+                if let Some(navmesh) = graph
+                    .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                    .map(|n| n.navmesh_ref())
+                {
+                    let ray = camera.make_ray(mouse_position, frame_size);
+                    if let Some(point) = ray_cast_navmesh(navmesh, &ray) {
+                        self.path_test.borrow_mut().move_marker(marker, point);
+                    }
+                }
+            }
+            return;
+        }
+
+        let offset = match self.drag_constraint {
+            DragConstraint::Plane(plane_kind) => self.move_gizmo.calculate_offset(
+                editor_scene,
+                camera,
+                mouse_offset,
+                mouse_position,
+                engine,
+                frame_size,
+                plane_kind,
+            ),
+            DragConstraint::ScreenPlane => {
+                let graph = &engine.scenes[editor_scene.scene].graph;
+                match graph.try_get_of_type::<Camera>(camera) {
+                    Some(camera) => screen_plane_offset(
+                        camera,
+                        graph[self.move_gizmo.origin].global_position(),
+                        mouse_position,
+                        mouse_offset,
+                        frame_size,
+                    ),
+                    None => Vector3::default(),
+                }
+            }
+        };
 
         let graph = &mut engine.scenes[editor_scene.scene].graph;
 
@@ -417,6 +1414,27 @@ impl InteractionMode for EditNavmeshMode {
                     }
                 }
 
+                // The same way a single vertex could be extruded into a new one.
+                if let (true, Some(&NavmeshEntity::Vertex(source))) =
+                    (selection.is_single_selection(), selection.first())
+                {
+                    if engine.user_interface.keyboard_modifiers().shift {
+                        if let Some(DragContext::MoveSelection { initial_positions }) =
+                            self.drag_context.as_ref()
+                        {
+                            // The source vertex stays where it was before the drag.
+                            let initial_position = initial_positions[&source];
+                            navmesh.vertices_mut()[source].position = initial_position;
+
+                            self.drag_context = Some(DragContext::VertexExtrusion {
+                                source,
+                                vertex: PathVertex::new(initial_position),
+                                target: None,
+                            });
+                        }
+                    }
+                }
+
                 if let Some(drag_context) = self.drag_context.as_mut() {
                     match drag_context {
                         DragContext::MoveSelection { .. } => {
@@ -424,12 +1442,160 @@ impl InteractionMode for EditNavmeshMode {
                                 navmesh.vertices_mut()[vertex].position += offset;
                             }
                         }
-                        DragContext::EdgeDuplication { vertices, .. } => {
+                        DragContext::EdgeDuplication {
+                            vertices,
+                            opposite_edge,
+                        } => {
+                            let positions = navmesh
+                                .vertices()
+                                .iter()
+                                .map(|v| v.position)
+                                .collect::<Vec<_>>();
+                            let offset = match edge_surface_normal(
+                                &positions,
+                                navmesh.triangles(),
+                                *opposite_edge,
+                            ) {
+                                // Isolated edges have no surface, they follow the gizmo.
+                                Some(normal) if settings.navmesh.extrude_along_surface => {
+                                    offset - normal.scale(normal.dot(&offset))
+                                }
+                                _ => offset,
+                            };
                             for vertex in vertices.iter_mut() {
                                 vertex.position += offset;
                             }
                         }
+                        DragContext::VertexExtrusion { vertex, .. } => {
+                            vertex.position += offset;
+                        }
+                        DragContext::PathMarker(_) | DragContext::Transform(_) => (),
+                    }
+                }
+            }
+
+            if settings.navmesh.snap_to_geometry {
+                match self.drag_context.as_mut() {
+                    Some(DragContext::MoveSelection { .. }) => {
+                        let snapped_positions = graph
+                            .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                            .map(|n| {
+                                selection
+                                    .unique_vertices()
+                                    .iter()
+                                    .map(|&vertex| {
+                                        (
+                                            vertex,
+                                            snap_to_geometry(
+                                                n.navmesh_ref().vertices()[vertex].position,
+                                                graph,
+                                                editor_scene,
+                                                settings,
+                                            ),
+                                        )
+                                    })
+                                    .collect::<Vec<_>>()
+                            })
+                            .unwrap_or_default();
+
+                        if let Some(navmesh) = graph
+                            .try_get_mut_of_type::<NavigationalMesh>(selection.navmesh_node())
+                            .map(|n| n.navmesh_mut())
+                        {
+                            for (vertex, position) in snapped_positions {
+                                navmesh.vertices_mut()[vertex].position = position;
+                            }
+                        }
+                    }
+                    Some(DragContext::EdgeDuplication { vertices, .. }) => {
+                        for vertex in vertices.iter_mut() {
+                            vertex.position =
+                                snap_to_geometry(vertex.position, graph, editor_scene, settings);
+                        }
+                    }
+                    Some(DragContext::VertexExtrusion { vertex, .. }) => {
+                        vertex.position =
+                            snap_to_geometry(vertex.position, graph, editor_scene, settings);
+                    }
+                    Some(DragContext::PathMarker(_)) | Some(DragContext::Transform(_)) | None => (),
+                }
+            }
+
+            if let (
+                Some(DragContext::VertexExtrusion {
+                    source,
+                    vertex,
+                    target,
+                }),
+                Some(navmesh),
+            ) = (
+                self.drag_context.as_mut(),
+                graph
+                    .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                    .map(|n| n.navmesh_ref()),
+            ) {
+                let positions = navmesh
+                    .vertices()
+                    .iter()
+                    .map(|v| v.position)
+                    .collect::<Vec<_>>();
+                *target = find_extrusion_target(
+                    &positions,
+                    navmesh.triangles(),
+                    *source,
+                    vertex.position,
+                    EXTRUSION_CONNECT_DISTANCE * editor_scene.navmesh_settings.vertex_radius,
+                );
+            }
+
+            if let (
+                Some(DragContext::MoveSelection { initial_positions }),
+                Some(navmesh),
+                Some(camera_ref),
+            ) = (
+                self.drag_context.as_ref(),
+                graph
+                    .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                    .map(|n| n.navmesh_ref()),
+                graph.try_get_of_type::<Camera>(camera),
+            ) {
+                let vertices = selection.unique_vertices();
+                if !vertices.is_empty() {
+                    let mut initial_center = Vector3::default();
+                    let mut current_center = Vector3::default();
+                    for vertex in vertices.iter() {
+                        initial_center += initial_positions[vertex];
+                        current_center += navmesh.vertices()[*vertex].position;
                     }
+                    initial_center = initial_center.scale(1.0 / vertices.len() as f32);
+                    current_center = current_center.scale(1.0 / vertices.len() as f32);
+
+                    // Positions are already snapped at this point, so the readout shows snapped
+                    // values as well.
+                    let total_offset = current_center - initial_center;
+                    let ui = &engine.user_interface;
+                    for (index, (axis, color)) in
+                        [("X", Color::RED), ("Y", Color::GREEN), ("Z", Color::BLUE)]
+                            .into_iter()
+                            .enumerate()
+                    {
+                        self.drag_readout.set_line(
+                            ui,
+                            index,
+                            format!("{}: {:.3}", axis, total_offset[index]),
+                            color,
+                        );
+                    }
+
+                    // Mouse position is relative to the frame, so the frame position could be
+                    // restored from the cursor position.
+                    self.drag_readout.place(
+                        ui,
+                        camera_ref,
+                        current_center,
+                        ui.cursor_position() - mouse_position,
+                        frame_size,
+                    );
                 }
             }
         }
@@ -442,12 +1608,38 @@ impl InteractionMode for EditNavmeshMode {
         engine: &mut Engine,
         settings: &Settings,
     ) {
+        self.sync_navmesh(editor_scene, engine);
+        self.update_vertex_labels(editor_scene, camera, engine, settings);
+        if self.show_help {
+            // The overlay follows the frame of the scene viewer.
+            let frame = engine.user_interface.node(self.preview).screen_bounds();
+            self.help.place_on_screen(
+                &engine.user_interface,
+                frame.position + Vector2::new(10.0, 10.0),
+            );
+        }
+
+        let scene_settings = editor_scene.navmesh_settings.clone();
         let scene = &mut engine.scenes[editor_scene.scene];
         self.move_gizmo.set_visible(&mut scene.graph, false);
 
+        if self.navmesh.is_none() {
+            return;
+        }
+
         let scale = calculate_gizmo_distance_scaling(&scene.graph, camera, self.move_gizmo.origin);
 
-        if let Some(selection) = fetch_selection(&editor_scene.selection) {
+        if let Some(selection) = fetch_selection(&editor_scene.selection)
+            .filter(|selection| selection.navmesh_node() == self.navmesh)
+        {
+            // Links are stored in the node, not in the navmesh, so they're copied before borrowing
+            // the navmesh.
+            let links = scene
+                .graph
+                .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                .map(|n| n.links().to_vec())
+                .unwrap_or_default();
+
             if let Some(navmesh) = scene
                 .graph
                 .try_get_mut_of_type::<NavigationalMesh>(selection.navmesh_node())
@@ -461,13 +1653,17 @@ impl InteractionMode for EditNavmeshMode {
                     opposite_edge,
                 }) = self.drag_context.as_ref()
                 {
+                    let navmesh_settings = &settings.navmesh;
+                    let offset = Vector3::new(0.0, scene_settings.height_offset, 0.0);
+                    let mut ctx = SceneDrawingContext::default();
+
                     for vertex in vertices.iter() {
-                        scene.drawing_context.draw_sphere(
-                            vertex.position,
+                        ctx.draw_sphere(
+                            vertex.position + offset,
                             10,
                             10,
-                            settings.navmesh.vertex_radius,
-                            Color::RED,
+                            scene_settings.vertex_radius,
+                            navmesh_settings.selected_vertex_color,
                         );
                     }
 
@@ -476,34 +1672,327 @@ impl InteractionMode for EditNavmeshMode {
                     let oe = navmesh.vertices()[opposite_edge.b as usize].position;
                     let ne = vertices[1].position;
 
-                    scene.drawing_context.add_line(fyrox::scene::debug::Line {
-                        begin: nb,
-                        end: ne,
-                        color: Color::RED,
+                    ctx.add_line(Line {
+                        begin: nb + offset,
+                        end: ne + offset,
+                        color: navmesh_settings.selected_edge_color,
                     });
 
                     for &(begin, end) in &[(ob, oe), (ob, nb), (nb, oe), (oe, ne)] {
-                        scene.drawing_context.add_line(fyrox::scene::debug::Line {
-                            begin,
-                            end,
-                            color: Color::GREEN,
+                        ctx.add_line(Line {
+                            begin: begin + offset,
+                            end: end + offset,
+                            color: navmesh_settings.edge_color,
                         });
                     }
 
+                    commit_navmesh_lines(&mut scene.drawing_context, ctx, settings);
+
                     gizmo_visible = true;
                     gizmo_position = (nb + ne).scale(0.5);
                 }
 
-                if let Some(first) = selection.first() {
+                if let Some(DragContext::MoveSelection { initial_positions }) =
+                    self.drag_context.as_ref()
+                {
+                    let offset = Vector3::new(0.0, scene_settings.height_offset, 0.0);
+                    let mut ctx = SceneDrawingContext::default();
+                    for vertex in selection.unique_vertices().iter() {
+                        draw_dashed_line(
+                            &mut ctx,
+                            initial_positions[vertex] + offset,
+                            navmesh.vertices()[*vertex].position + offset,
+                            0.1 * scale.x,
+                            Color::opaque(255, 255, 0),
+                        );
+                    }
+                    commit_navmesh_lines(&mut scene.drawing_context, ctx, settings);
+                }
+
+                // Links can't be moved with the gizmo, their ends follow the vertices instead.
+                if let Some(position) = selection.entities().iter().find_map(|entity| match *entity
+                {
+                    NavmeshEntity::Vertex(v) => Some(navmesh.vertices()[v].position),
+                    NavmeshEntity::Edge(edge) => {
+                        let a = navmesh.vertices()[edge.a as usize].position;
+                        let b = navmesh.vertices()[edge.b as usize].position;
+                        Some((a + b).scale(0.5))
+                    }
+                    NavmeshEntity::Link(_) => None,
+                }) {
                     gizmo_visible = true;
-                    gizmo_position = match *first {
-                        NavmeshEntity::Vertex(v) => navmesh.vertices()[v].position,
-                        NavmeshEntity::Edge(edge) => {
-                            let a = navmesh.vertices()[edge.a as usize].position;
-                            let b = navmesh.vertices()[edge.b as usize].position;
-                            (a + b).scale(0.5)
+                    gizmo_position = position;
+                }
+
+                if let Some(DragContext::VertexExtrusion {
+                    source,
+                    vertex,
+                    target,
+                }) = self.drag_context.as_ref()
+                {
+                    let navmesh_settings = &settings.navmesh;
+                    let offset = Vector3::new(0.0, scene_settings.height_offset, 0.0);
+                    let mut ctx = SceneDrawingContext::default();
+
+                    ctx.draw_sphere(
+                        vertex.position + offset,
+                        10,
+                        10,
+                        scene_settings.vertex_radius,
+                        navmesh_settings.selected_vertex_color,
+                    );
+
+                    let source_position = navmesh.vertices()[*source].position;
+                    ctx.add_line(Line {
+                        begin: source_position + offset,
+                        end: vertex.position + offset,
+                        color: navmesh_settings.selected_edge_color,
+                    });
+
+                    if let Some(target) = target {
+                        let target_position = navmesh.vertices()[*target].position;
+                        for &(begin, end) in &[
+                            (vertex.position, target_position),
+                            (target_position, source_position),
+                        ] {
+                            ctx.add_line(Line {
+                                begin: begin + offset,
+                                end: end + offset,
+                                color: navmesh_settings.edge_color,
+                            });
                         }
-                    };
+                    }
+
+                    commit_navmesh_lines(&mut scene.drawing_context, ctx, settings);
+
+                    // Source vertex is still selected, but the gizmo must follow the new one.
+                    gizmo_visible = true;
+                    gizmo_position = vertex.position;
+                }
+
+                let mut path_test = self.path_test.borrow_mut();
+                if path_test.is_enabled() {
+                    path_test.update(selection.navmesh_node(), navmesh);
+
+                    let offset = Vector3::new(0.0, scene_settings.height_offset, 0.0);
+                    let found = path_test.status() != PathTestStatus::NotFound;
+                    let mut ctx = SceneDrawingContext::default();
+
+                    for (marker, color) in [
+                        (PathMarker::Start, Color::opaque(0, 200, 0)),
+                        (PathMarker::End, Color::opaque(0, 120, 255)),
+                    ] {
+                        if let Some(position) = path_test.marker(marker) {
+                            ctx.draw_sphere(
+                                position + offset,
+                                10,
+                                10,
+                                path_marker_radius(&scene_settings),
+                                if found { color } else { Color::RED },
+                            );
+                        }
+                    }
+
+                    for segment in path_test.path().windows(2) {
+                        ctx.add_line(Line {
+                            begin: segment[0] + offset,
+                            end: segment[1] + offset,
+                            color: Color::opaque(255, 200, 0),
+                        });
+                    }
+
+                    commit_navmesh_lines(&mut scene.drawing_context, ctx, settings);
+                }
+
+                let link_begin = self.link_tool.borrow().begin();
+                if !links.is_empty() || link_begin.is_some() {
+                    let navmesh_settings = &settings.navmesh;
+                    let offset = Vector3::new(0.0, scene_settings.height_offset, 0.0);
+                    let mut ctx = SceneDrawingContext::default();
+
+                    for (index, link) in links.iter().enumerate() {
+                        let color = if selection.contains(&NavmeshEntity::Link(index)) {
+                            navmesh_settings.selected_edge_color
+                        } else {
+                            LINK_COLOR
+                        };
+
+                        for segment in link_arc(link).windows(2) {
+                            ctx.add_line(Line {
+                                begin: segment[0] + offset,
+                                end: segment[1] + offset,
+                                color,
+                            });
+                        }
+
+                        // One-way links are marked at the end, so the direction is visible.
+                        if !link.bidirectional {
+                            ctx.draw_sphere(
+                                link.end + offset,
+                                6,
+                                6,
+                                0.5 * scene_settings.vertex_radius,
+                                color,
+                            );
+                        }
+                    }
+
+                    if let Some(begin) = link_begin {
+                        ctx.draw_sphere(
+                            begin + offset,
+                            10,
+                            10,
+                            scene_settings.vertex_radius,
+                            LINK_COLOR,
+                        );
+                    }
+
+                    commit_navmesh_lines(&mut scene.drawing_context, ctx, settings);
+                }
+
+                if let Some(DragContext::Transform(transform)) = self.drag_context.as_ref() {
+                    let pivot =
+                        transform.pivot + Vector3::new(0.0, scene_settings.height_offset, 0.0);
+                    let mut ctx = SceneDrawingContext::default();
+
+                    ctx.draw_sphere(
+                        pivot,
+                        10,
+                        10,
+                        0.5 * scene_settings.vertex_radius,
+                        Color::opaque(255, 255, 0),
+                    );
+
+                    // Constraint axis goes through the whole transformed area.
+                    if let Some(axis) = transform.axis {
+                        let extent = transform
+                            .initial_positions
+                            .iter()
+                            .map(|(_, position)| position.metric_distance(&transform.pivot))
+                            .fold(0.0, f32::max)
+                            + scene_settings.vertex_radius;
+                        let mut direction = Vector3::default();
+                        direction[axis] = extent;
+                        ctx.add_line(Line {
+                            begin: pivot - direction,
+                            end: pivot + direction,
+                            color: [Color::RED, Color::GREEN, Color::BLUE][axis],
+                        });
+                    }
+
+                    commit_navmesh_lines(&mut scene.drawing_context, ctx, settings);
+                }
+
+                let mut overlaps = self.overlaps.borrow_mut();
+                if settings.navmesh.highlight_overlaps {
+                    if overlaps.is_outdated(self.navmesh) {
+                        let positions = navmesh
+                            .vertices()
+                            .iter()
+                            .map(|v| v.position)
+                            .collect::<Vec<_>>();
+                        overlaps.set(
+                            self.navmesh,
+                            &overlapping_triangles(
+                                &positions,
+                                navmesh.triangles(),
+                                NAVMESH_VALIDATION_EPSILON,
+                            ),
+                        );
+                    }
+                } else if self.auto_overlaps {
+                    overlaps.clear();
+                }
+                self.auto_overlaps = settings.navmesh.highlight_overlaps;
+
+                if !overlaps.triangles(self.navmesh).is_empty() {
+                    let offset = Vector3::new(0.0, scene_settings.height_offset, 0.0);
+                    let mut ctx = SceneDrawingContext::default();
+
+                    for triangle in overlaps
+                        .triangles(self.navmesh)
+                        .iter()
+                        .filter_map(|t| navmesh.triangles().get(*t))
+                    {
+                        // Indices could be outdated, if the navmesh was changed after the search.
+                        let corners = triangle.0.map(|i| {
+                            navmesh
+                                .vertices()
+                                .get(i as usize)
+                                .map(|v| v.position + offset)
+                        });
+                        if let [Some(a), Some(b), Some(c)] = corners {
+                            draw_hatching(&mut ctx, a, b, c, Color::RED);
+                            for &(begin, end) in &[(a, b), (b, c), (c, a)] {
+                                ctx.add_line(Line {
+                                    begin,
+                                    end,
+                                    color: Color::RED,
+                                });
+                            }
+                        }
+                    }
+
+                    commit_navmesh_lines(&mut scene.drawing_context, ctx, settings);
+                }
+
+                if settings.navmesh.show_slope {
+                    self.slope_preview
+                        .update(navmesh, settings.navmesh.max_walkable_slope);
+
+                    let offset = Vector3::new(0.0, scene_settings.height_offset, 0.0);
+                    let mut ctx = SceneDrawingContext::default();
+
+                    for (triangle, &steep) in navmesh
+                        .triangles()
+                        .iter()
+                        .zip(self.slope_preview.steep_triangles())
+                    {
+                        let [a, b, c] = triangle
+                            .0
+                            .map(|i| navmesh.vertices()[i as usize].position + offset);
+                        let color = if steep {
+                            draw_hatching(&mut ctx, a, b, c, Color::RED);
+                            Color::RED
+                        } else {
+                            Color::GREEN
+                        };
+                        for &(begin, end) in &[(a, b), (b, c), (c, a)] {
+                            ctx.add_line(Line { begin, end, color });
+                        }
+                    }
+
+                    commit_navmesh_lines(&mut scene.drawing_context, ctx, settings);
+                }
+
+                if settings.navmesh.show_agent_radius {
+                    self.agent_preview
+                        .update(navmesh, settings.navmesh.agent_radius);
+
+                    let offset = Vector3::new(0.0, scene_settings.height_offset, 0.0);
+                    let mut ctx = SceneDrawingContext::default();
+
+                    for [begin, end] in self.agent_preview.inset_boundary() {
+                        ctx.add_line(Line {
+                            begin: *begin + offset,
+                            end: *end + offset,
+                            color: Color::opaque(255, 140, 0),
+                        });
+                    }
+
+                    for &index in self.agent_preview.narrow_triangles() {
+                        let triangle = &navmesh.triangles()[index];
+                        for k in 0..3 {
+                            ctx.add_line(Line {
+                                begin: navmesh.vertices()[triangle[k] as usize].position + offset,
+                                end: navmesh.vertices()[triangle[(k + 1) % 3] as usize].position
+                                    + offset,
+                                color: Color::RED,
+                            });
+                        }
+                    }
+
+                    commit_navmesh_lines(&mut scene.drawing_context, ctx, settings);
                 }
 
                 self.move_gizmo.set_visible(&mut scene.graph, gizmo_visible);
@@ -515,7 +2004,47 @@ impl InteractionMode for EditNavmeshMode {
         }
     }
 
+    fn can_activate(&self, editor_scene: &EditorScene, engine: &Engine) -> bool {
+        !scene_navmeshes(editor_scene, &engine.scenes[editor_scene.scene].graph).is_empty()
+    }
+
+    fn activate(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
+        let graph = &engine.scenes[editor_scene.scene].graph;
+
+        match resolve_navmesh_binding(
+            &selected_nodes(&editor_scene.selection),
+            editor_scene.navmesh,
+            &scene_navmeshes(editor_scene, graph),
+        ) {
+            NavmeshBinding::None => {}
+            NavmeshBinding::Bound(navmesh) => {
+                self.navmesh = navmesh;
+
+                let already_bound = matches!(&editor_scene.selection,
+                    Selection::Navmesh(selection) if selection.navmesh_node() == navmesh);
+
+                if !already_bound {
+                    self.message_sender
+                        .do_scene_command(ChangeSelectionCommand::new(
+                            Selection::Navmesh(NavmeshSelection::empty(navmesh)),
+                            editor_scene.selection.clone(),
+                        ));
+                }
+            }
+            NavmeshBinding::Ambiguous(navmeshes) => {
+                self.chooser
+                    .open(&mut engine.user_interface, graph, navmeshes);
+            }
+        }
+    }
+
     fn deactivate(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
+        self.drag_readout.set_visible(&engine.user_interface, false);
+        self.vertex_labels.hide(&engine.user_interface);
+        self.help.set_visible(&engine.user_interface, false);
+        self.link_tool.borrow_mut().cancel();
+        self.finish_transform(false, editor_scene, engine);
+
         let scene = &mut engine.scenes[editor_scene.scene];
         self.move_gizmo.set_visible(&mut scene.graph, false);
     }
@@ -525,12 +2054,81 @@ impl InteractionMode for EditNavmeshMode {
         key: KeyCode,
         editor_scene: &mut EditorScene,
         engine: &mut Engine,
+        _settings: &Settings,
     ) -> bool {
+        self.sync_navmesh(editor_scene, engine);
+
+        let modifiers = engine.user_interface.keyboard_modifiers();
+
+        if let Some(DragContext::Transform(transform)) = self.drag_context.as_mut() {
+            match self
+                .hotkeys
+                .action(HotkeyContext::Transform, key, modifiers)
+            {
+                Some(NavmeshAction::CancelTransform) => {
+                    self.finish_transform(false, editor_scene, engine);
+                }
+                Some(NavmeshAction::ConfirmTransform) => {
+                    self.finish_transform(true, editor_scene, engine);
+                }
+                Some(NavmeshAction::ConstrainTransform(axis)) => {
+                    transform.toggle_axis(axis);
+                    self.update_transform(editor_scene, engine);
+                }
+                // Other keys are swallowed, so undo or any other action doesn't change the
+                // navmesh in the middle of the transform.
+                _ => (),
+            }
+            return true;
+        }
+
+        let action = match self.hotkeys.action(HotkeyContext::Edit, key, modifiers) {
+            Some(action) => action,
+            None => return false,
+        };
+
+        if action == NavmeshAction::ToggleHelp {
+            self.show_help = !self.show_help;
+            // The overlay is placed by the next update.
+            if !self.show_help {
+                self.help.set_visible(&engine.user_interface, false);
+            }
+            return true;
+        }
+
         let scene = &mut engine.scenes[editor_scene.scene];
 
         if let Some(selection) = fetch_selection(&editor_scene.selection) {
-            return match key {
-                KeyCode::Delete => {
+            return match action {
+                NavmeshAction::Rotate | NavmeshAction::ScaleOrSplitEdge
+                    if self.drag_context.is_none()
+                        && selection.unique_vertices().len() > 1
+                        // S splits a single selected edge.
+                        && !(action == NavmeshAction::ScaleOrSplitEdge
+                            && selection.is_single_selection()
+                            && matches!(selection.first(), Some(NavmeshEntity::Edge(_)))) =>
+                {
+                    let kind = if action == NavmeshAction::Rotate {
+                        VertexTransformKind::Rotate
+                    } else {
+                        VertexTransformKind::Scale
+                    };
+                    self.start_transform(kind, &selection, editor_scene, engine);
+                    true
+                }
+                NavmeshAction::Cancel
+                    if matches!(self.drag_context, Some(DragContext::VertexExtrusion { .. })) =>
+                {
+                    // Nothing is added to the navmesh until the drag ends, so dropping the drag
+                    // context is enough to cancel the extrusion.
+                    self.drag_context = None;
+                    true
+                }
+                NavmeshAction::Cancel if self.link_tool.borrow().begin().is_some() => {
+                    self.link_tool.borrow_mut().cancel();
+                    true
+                }
+                NavmeshAction::Delete => {
                     if scene
                         .graph
                         .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
@@ -540,12 +2138,35 @@ impl InteractionMode for EditNavmeshMode {
                     {
                         let mut commands = Vec::new();
 
-                        for vertex in selection.unique_vertices().iter().rev().cloned() {
+                        // Edges must be deleted first - vertex deletion shifts indices of
+                        // the vertices, which edges refer to.
+                        let (edges, vertices) = plan_deletion(
+                            selection.entities(),
+                            scene.graph[selection.navmesh_node()]
+                                .as_navigational_mesh()
+                                .navmesh_ref()
+                                .triangles(),
+                        );
+                        for edge in edges {
+                            commands.push(SceneCommand::new(DeleteNavmeshEdgeCommand::new(
+                                selection.navmesh_node(),
+                                edge,
+                            )));
+                        }
+                        for vertex in vertices {
                             commands.push(SceneCommand::new(DeleteNavmeshVertexCommand::new(
                                 selection.navmesh_node(),
                                 vertex,
                             )));
                         }
+                        // Indices of the links are in descending order, so deletion of a link
+                        // does not shift indices of the rest.
+                        for link in selection.links() {
+                            commands.push(SceneCommand::new(DeleteNavmeshLinkCommand::new(
+                                selection.navmesh_node(),
+                                link,
+                            )));
+                        }
 
                         commands.push(SceneCommand::new(ChangeSelectionCommand::new(
                             Selection::Navmesh(NavmeshSelection::empty(selection.navmesh_node())),
@@ -558,7 +2179,28 @@ impl InteractionMode for EditNavmeshMode {
 
                     true
                 }
-                KeyCode::KeyA if engine.user_interface.keyboard_modifiers().control => {
+                NavmeshAction::ScaleOrSplitEdge => {
+                    if let (true, Some(NavmeshEntity::Edge(edge))) =
+                        (selection.is_single_selection(), selection.first())
+                    {
+                        if scene
+                            .graph
+                            .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                            .is_some()
+                        {
+                            self.message_sender
+                                .do_scene_command(SplitNavmeshEdgeCommand::new(
+                                    selection.navmesh_node(),
+                                    *edge,
+                                ));
+                        }
+
+                        true
+                    } else {
+                        false
+                    }
+                }
+                NavmeshAction::SelectAll => {
                     if let Some(navmesh) = scene
                         .graph
                         .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
@@ -583,10 +2225,354 @@ impl InteractionMode for EditNavmeshMode {
 
                     true
                 }
+                NavmeshAction::ToggleLockY => {
+                    self.lock_y = !self.lock_y;
+
+                    // Constraint of the current drag (if any) stays the same.
+                    if self.drag_context.is_none() {
+                        self.move_gizmo.apply_mode(
+                            if self.lock_y {
+                                Some(PlaneKind::Y)
+                            } else {
+                                None
+                            },
+                            &mut scene.graph,
+                        );
+                    }
+
+                    true
+                }
+                NavmeshAction::Duplicate => {
+                    if let Some(navmesh) = scene
+                        .graph
+                        .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                        .map(|n| n.navmesh_ref())
+                    {
+                        let vertices = selection.unique_vertices().clone();
+                        let (_, triangles) = duplicate_triangles(navmesh.triangles(), &vertices, 0);
+                        if !triangles.is_empty() {
+                            self.message_sender.do_scene_command(
+                                DuplicateNavmeshTrianglesCommand::new(
+                                    selection.navmesh_node(),
+                                    vertices,
+                                    DUPLICATE_OFFSET,
+                                ),
+                            );
+                        }
+                    }
+
+                    true
+                }
+                NavmeshAction::AddTriangle => {
+                    if let Some(camera) = scene
+                        .graph
+                        .try_get_of_type::<Camera>(editor_scene.camera_controller.camera)
+                    {
+                        let ray = camera.make_ray(self.mouse_position, self.frame_size);
+                        if let Some(command) =
+                            add_triangle_command(&ray, &scene.graph, editor_scene)
+                        {
+                            self.message_sender.do_scene_command(command);
+                        }
+                    }
+
+                    true
+                }
+                NavmeshAction::SelectLinked => {
+                    if let Some(navmesh) = scene
+                        .graph
+                        .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                        .map(|n| n.navmesh_ref())
+                    {
+                        let selected = selection.unique_vertices().clone();
+                        let mut new_selection = selection.clone();
+                        // Vertices of selected edges are used as seeds as well.
+                        for vertex in connected_vertices(
+                            navmesh.triangles(),
+                            navmesh.vertices().len(),
+                            selected.iter().cloned(),
+                        ) {
+                            if !selected.contains(&vertex) {
+                                new_selection.add(NavmeshEntity::Vertex(vertex));
+                            }
+                        }
+
+                        if new_selection.len() != selection.len() {
+                            self.message_sender
+                                .do_scene_command(ChangeSelectionCommand::new(
+                                    Selection::Navmesh(new_selection),
+                                    editor_scene.selection.clone(),
+                                ));
+                        }
+                    }
+
+                    true
+                }
+                NavmeshAction::InvertSelection => {
+                    if let Some(navmesh) = scene
+                        .graph
+                        .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                        .map(|n| n.navmesh_ref())
+                    {
+                        let selected = selection.unique_vertices();
+                        let new_selection = NavmeshSelection::new(
+                            selection.navmesh_node(),
+                            (0..navmesh.vertices().len())
+                                .filter(|vertex| !selected.contains(vertex))
+                                .map(NavmeshEntity::Vertex)
+                                .collect(),
+                        );
+
+                        self.message_sender
+                            .do_scene_command(ChangeSelectionCommand::new(
+                                Selection::Navmesh(new_selection),
+                                editor_scene.selection.clone(),
+                            ));
+                    }
+
+                    true
+                }
                 _ => false,
             };
         } else {
             false
         }
     }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+    ) {
+        self.chooser.handle_ui_message(
+            message,
+            editor_scene,
+            &self.message_sender,
+            &engine.user_interface,
+        );
+    }
+
+    fn on_drop(&mut self, engine: &mut Engine) {
+        engine.user_interface.send_message(WidgetMessage::remove(
+            self.chooser.window,
+            MessageDirection::ToWidget,
+        ));
+        self.drag_readout.destroy(&engine.user_interface);
+        self.vertex_labels.destroy(&engine.user_interface);
+        self.help.destroy(&engine.user_interface);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::message::Message;
+    use fyrox::scene::{base::BaseBuilder, navmesh::NavigationalMeshBuilder};
+
+    #[test]
+    fn test_place_vertices() {
+        let vertices = [
+            PathVertex::new(Vector3::new(0.0, 0.0, 0.0)),
+            PathVertex::new(Vector3::new(2.0, 0.0, 0.0)),
+            PathVertex::new(Vector3::new(0.1, 1.0, 0.0)),
+        ];
+
+        assert_eq!(
+            vertices_centroid(&vertices, &[0, 1].into()),
+            Some(Vector3::new(1.0, 0.0, 0.0))
+        );
+        assert_eq!(vertices_centroid(&vertices, &BTreeSet::new()), None);
+
+        // Relative positions are kept, vertices, that aren't moved, are skipped.
+        assert_eq!(
+            place_vertices(&vertices, &[0, 1].into(), Vector3::new(1.0, 0.0, 3.0)),
+            vec![
+                (0, Vector3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 3.0)),
+                (1, Vector3::new(2.0, 0.0, 0.0), Vector3::new(2.0, 0.0, 3.0)),
+            ]
+        );
+        assert!(place_vertices(&vertices, &[0, 1].into(), Vector3::new(1.0, 0.0, 0.0)).is_empty());
+
+        let target = Vector3::new(12.5, 1.0, 0.0);
+        assert_eq!(
+            place_vertices(&vertices, &[2].into(), target),
+            vec![(2, vertices[2].position, target)]
+        );
+    }
+
+    #[test]
+    fn test_find_weld_target() {
+        let vertices = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(0.3, 0.0, 0.0),
+            Vector3::new(0.1, 0.0, 0.0),
+            Vector3::new(5.0, 0.0, 0.0),
+        ];
+
+        assert_eq!(find_weld_target(&vertices, 0, 0.2), Some(2));
+        assert_eq!(find_weld_target(&vertices, 1, 0.5), Some(2));
+        assert_eq!(find_weld_target(&vertices, 3, 0.5), None);
+    }
+
+    #[test]
+    fn test_find_extrusion_target() {
+        let vertices = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 1.0),
+            Vector3::new(3.0, 0.0, 0.0),
+            Vector3::new(0.5, 0.0, 3.0),
+        ];
+
+        // Vertex 2 is on the same line with the source and the new vertex.
+        assert_eq!(
+            find_extrusion_target(&vertices, &[], 0, Vector3::new(2.0, 0.0, 0.0), 2.0),
+            Some(1)
+        );
+        assert_eq!(
+            find_extrusion_target(&vertices, &[], 0, Vector3::new(0.0, 0.0, 2.5), 1.0),
+            Some(3)
+        );
+        assert_eq!(
+            find_extrusion_target(&vertices, &[], 0, Vector3::new(0.0, 0.0, -5.0), 1.0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_edge_surface_normal() {
+        // A ramp, that rises along Z axis by 45 degrees.
+        let vertices = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 1.0),
+            Vector3::new(5.0, 0.0, 0.0),
+        ];
+        let triangles = [TriangleDefinition([0, 2, 1])];
+
+        let normal =
+            edge_surface_normal(&vertices, &triangles, TriangleEdge { a: 1, b: 0 }).unwrap();
+        let expected = Vector3::new(0.0, 1.0, -1.0).normalize();
+        assert!(normal.metric_distance(&expected) < 1.0e-5);
+
+        // An offset along Z axis is projected onto the ramp.
+        let offset = Vector3::new(0.0, 0.0, 2.0);
+        let projected = offset - normal.scale(normal.dot(&offset));
+        assert!(projected.metric_distance(&Vector3::new(0.0, 1.0, 1.0)) < 1.0e-5);
+
+        // Isolated edge has no surface.
+        assert_eq!(
+            edge_surface_normal(&vertices, &triangles, TriangleEdge { a: 1, b: 3 }),
+            None
+        );
+    }
+
+    #[test]
+    fn test_equilateral_triangle() {
+        let center = Vector3::new(1.0, 2.0, 3.0);
+        let [a, b, c] = equilateral_triangle(center, 2.0);
+
+        for vertex in [a, b, c] {
+            assert_eq!(vertex.y, center.y);
+            assert!((vertex.metric_distance(&center) - 2.0).abs() < 1.0e-5);
+        }
+        let side = a.metric_distance(&b);
+        assert!((b.metric_distance(&c) - side).abs() < 1.0e-5);
+        assert!((c.metric_distance(&a) - side).abs() < 1.0e-5);
+
+        // Counterclockwise when looking from above, so the normal points up.
+        assert!((b - a).cross(&(c - a)).y > 0.0);
+    }
+
+    #[test]
+    fn test_drag_constraint() {
+        for center_constraint in [
+            NavmeshMoveConstraint::ScreenPlane,
+            NavmeshMoveConstraint::HorizontalPlane,
+        ] {
+            // Axes and planes of the gizmo are used as is.
+            for picked in [PlaneKind::X, PlaneKind::Y, PlaneKind::Z, PlaneKind::ZX] {
+                assert_eq!(
+                    DragConstraint::new(picked, false, center_constraint),
+                    DragConstraint::Plane(picked)
+                );
+            }
+
+            // Y lock wins over everything.
+            for picked in [PlaneKind::X, PlaneKind::XY, PlaneKind::SMART] {
+                let constraint = DragConstraint::new(picked, true, center_constraint);
+                assert_eq!(constraint, DragConstraint::Plane(PlaneKind::Y));
+                assert_eq!(constraint.gizmo_mode(), PlaneKind::Y);
+            }
+        }
+
+        // The center of the gizmo uses the constraint from the settings.
+        let constraint =
+            DragConstraint::new(PlaneKind::SMART, false, NavmeshMoveConstraint::ScreenPlane);
+        assert_eq!(constraint, DragConstraint::ScreenPlane);
+        assert_eq!(constraint.gizmo_mode(), PlaneKind::SMART);
+
+        let constraint = DragConstraint::new(
+            PlaneKind::SMART,
+            false,
+            NavmeshMoveConstraint::HorizontalPlane,
+        );
+        assert_eq!(constraint, DragConstraint::Plane(PlaneKind::ZX));
+        assert_eq!(constraint.gizmo_mode(), PlaneKind::ZX);
+    }
+
+    #[test]
+    fn test_navmesh_deleted_during_drag() {
+        let mut graph = Graph::new();
+        let node = NavigationalMeshBuilder::new(BaseBuilder::new()).build(&mut graph);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let sender = MessageSender(sender);
+
+        let selection =
+            Selection::Navmesh(NavmeshSelection::new(node, vec![NavmeshEntity::Vertex(0)]));
+        let mut navmesh = node;
+        let mut drag_context = Some(DragContext::MoveSelection {
+            initial_positions: Default::default(),
+        });
+
+        // Navmesh is alive, nothing changes.
+        assert!(!drop_deleted_navmesh(
+            &mut navmesh,
+            &mut drag_context,
+            &selection,
+            &graph,
+            &sender
+        ));
+        assert_eq!(navmesh, node);
+        assert!(drag_context.is_some());
+        assert!(receiver.try_recv().is_err());
+
+        // Node is deleted in the middle of the drag.
+        graph.remove_node(node);
+        assert!(drop_deleted_navmesh(
+            &mut navmesh,
+            &mut drag_context,
+            &selection,
+            &graph,
+            &sender
+        ));
+        assert_eq!(navmesh, Handle::NONE);
+        assert!(drag_context.is_none());
+        // The only command clears the selection of the deleted navmesh.
+        assert!(matches!(
+            receiver.try_recv(),
+            Ok(Message::DoSceneCommand(_))
+        ));
+        assert!(receiver.try_recv().is_err());
+
+        // Stale handle is gone, nothing else is emitted.
+        assert!(!drop_deleted_navmesh(
+            &mut navmesh,
+            &mut drag_context,
+            &selection,
+            &graph,
+            &sender
+        ));
+        assert!(receiver.try_recv().is_err());
+    }
 }
@@ -0,0 +1,330 @@
+//! Import and export of navigational meshes in Wavefront OBJ format. Only vertex positions and
+//! faces are supported, everything else (normals, texture coordinates, groups, materials, etc.) is
+//! ignored on import.
+
+use fyrox::{
+    core::{algebra::Vector3, math::TriangleDefinition},
+    utils::navmesh::Navmesh,
+};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter, Write},
+};
+
+/// Vertices closer to each other than this distance are merged on import.
+pub const OBJ_VERTEX_EPSILON: f32 = 1.0e-5;
+
+/// An error, that may occur while parsing OBJ source. Every variant holds the number of the line
+/// (starting from 1) with the error.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ObjError {
+    /// A token can't be parsed as a number.
+    InvalidNumber { line: usize, token: String },
+    /// Vertex definition has less than three coordinates.
+    NotEnoughCoordinates { line: usize },
+    /// Face refers to a vertex, that wasn't defined.
+    InvalidIndex { line: usize, index: i64 },
+    /// Face has less than three vertices.
+    DegenerateFace { line: usize },
+}
+
+impl Display for ObjError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ObjError::InvalidNumber { line, token } => {
+                write!(f, "Line {}: {} is not a valid number.", line, token)
+            }
+            ObjError::NotEnoughCoordinates { line } => {
+                write!(f, "Line {}: vertex must have at least 3 coordinates.", line)
+            }
+            ObjError::InvalidIndex { line, index } => {
+                write!(f, "Line {}: there's no vertex with {} index.", line, index)
+            }
+            ObjError::DegenerateFace { line } => {
+                write!(f, "Line {}: face must have at least 3 vertices.", line)
+            }
+        }
+    }
+}
+
+/// Triangle mesh, that could be converted to (or from) a navmesh.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ObjMesh {
+    pub vertices: Vec<Vector3<f32>>,
+    pub triangles: Vec<TriangleDefinition>,
+}
+
+fn parse_number<T: std::str::FromStr>(token: &str, line: usize) -> Result<T, ObjError> {
+    token.parse().map_err(|_| ObjError::InvalidNumber {
+        line,
+        token: token.to_owned(),
+    })
+}
+
+impl ObjMesh {
+    pub fn from_navmesh(navmesh: &Navmesh) -> Self {
+        Self {
+            vertices: navmesh.vertices().iter().map(|v| v.position).collect(),
+            triangles: navmesh.triangles().to_vec(),
+        }
+    }
+
+    pub fn into_navmesh(self) -> Navmesh {
+        Navmesh::new(&self.triangles, &self.vertices)
+    }
+
+    /// Parses OBJ source. Polygons are triangulated as fans (which is correct for quads and any
+    /// other convex polygon) and vertices closer than `epsilon` to each other are merged.
+    /// Triangles, that become degenerate after merging, are removed.
+    pub fn parse(source: &str, epsilon: f32) -> Result<Self, ObjError> {
+        let mut positions = Vec::new();
+        let mut triangles = Vec::new();
+
+        for (i, line) in source.lines().enumerate() {
+            let line_number = i + 1;
+            let line = match line.find('#') {
+                Some(comment) => &line[..comment],
+                None => line,
+            };
+
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("v") => {
+                    let mut position = Vector3::default();
+                    for k in 0..3 {
+                        let token = tokens
+                            .next()
+                            .ok_or(ObjError::NotEnoughCoordinates { line: line_number })?;
+                        position[k] = parse_number(token, line_number)?;
+                    }
+                    positions.push(position);
+                }
+                Some("f") => {
+                    let mut face = Vec::new();
+                    for token in tokens {
+                        // Each vertex of a face could be in `v`, `v/vt`, `v/vt/vn` or `v//vn` form.
+                        let index_token = token.split('/').next().unwrap_or_default();
+                        let index: i64 = parse_number(index_token, line_number)?;
+
+                        // Negative indices are relative to the end of the vertex list.
+                        let resolved = if index < 0 {
+                            positions.len() as i64 + index
+                        } else {
+                            index - 1
+                        };
+                        if resolved < 0 || resolved >= positions.len() as i64 {
+                            return Err(ObjError::InvalidIndex {
+                                line: line_number,
+                                index,
+                            });
+                        }
+                        face.push(resolved as u32);
+                    }
+
+                    if face.len() < 3 {
+                        return Err(ObjError::DegenerateFace { line: line_number });
+                    }
+
+                    for k in 1..face.len() - 1 {
+                        triangles.push(TriangleDefinition([face[0], face[k], face[k + 1]]));
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        Ok(Self::deduplicated(positions, triangles, epsilon))
+    }
+
+    fn deduplicated(
+        positions: Vec<Vector3<f32>>,
+        triangles: Vec<TriangleDefinition>,
+        epsilon: f32,
+    ) -> Self {
+        // Vertices are put in a grid with cells of epsilon size, so only neighbour cells have to
+        // be checked to find a close vertex.
+        let cell_size = epsilon.max(f32::EPSILON);
+        let cell_of = |p: &Vector3<f32>| {
+            (
+                (p.x / cell_size).floor() as i64,
+                (p.y / cell_size).floor() as i64,
+                (p.z / cell_size).floor() as i64,
+            )
+        };
+
+        let mut grid = HashMap::<(i64, i64, i64), Vec<u32>>::new();
+        let mut vertices = Vec::<Vector3<f32>>::new();
+        let mut remap = Vec::with_capacity(positions.len());
+        for position in positions {
+            let (x, y, z) = cell_of(&position);
+
+            let mut existing = None;
+            'search: for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let key = (
+                            x.saturating_add(dx),
+                            y.saturating_add(dy),
+                            z.saturating_add(dz),
+                        );
+                        if let Some(cell) = grid.get(&key) {
+                            for &index in cell {
+                                if (vertices[index as usize] - position).norm() <= epsilon {
+                                    existing = Some(index);
+                                    break 'search;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            let index = match existing {
+                Some(index) => index,
+                None => {
+                    let index = vertices.len() as u32;
+                    vertices.push(position);
+                    grid.entry((x, y, z)).or_default().push(index);
+                    index
+                }
+            };
+            remap.push(index);
+        }
+
+        let triangles = triangles
+            .into_iter()
+            .map(|t| {
+                TriangleDefinition([
+                    remap[t[0] as usize],
+                    remap[t[1] as usize],
+                    remap[t[2] as usize],
+                ])
+            })
+            .filter(|t| t[0] != t[1] && t[1] != t[2] && t[2] != t[0])
+            .collect();
+
+        Self {
+            vertices,
+            triangles,
+        }
+    }
+
+    /// Writes the mesh in OBJ format. Coordinates are written with the shortest representation,
+    /// that parses back to exactly the same value, so the output could be imported without any
+    /// loss of precision.
+    pub fn write(&self) -> String {
+        let mut output = String::new();
+
+        // Writing to a string never fails.
+        let _ = writeln!(output, "# Navigational mesh exported from Fyrox");
+        for v in self.vertices.iter() {
+            let _ = writeln!(output, "v {} {} {}", v.x, v.y, v.z);
+        }
+        for t in self.triangles.iter() {
+            let _ = writeln!(output, "f {} {} {}", t[0] + 1, t[1] + 1, t[2] + 1);
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn quad() -> ObjMesh {
+        ObjMesh {
+            vertices: vec![
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 1.0),
+                Vector3::new(0.0, 0.0, 1.0),
+            ],
+            triangles: vec![TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])],
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mesh = ObjMesh {
+            vertices: vec![
+                Vector3::new(0.1, -0.2, 1.0 / 3.0),
+                Vector3::new(12345.678, 1.0e-7, -0.0),
+                Vector3::new(-2.5, std::f32::consts::PI, 7.0),
+                Vector3::new(f32::MAX, f32::MIN_POSITIVE, 100.001),
+            ],
+            triangles: vec![TriangleDefinition([0, 1, 2]), TriangleDefinition([2, 1, 3])],
+        };
+
+        let parsed = ObjMesh::parse(&mesh.write(), OBJ_VERTEX_EPSILON).unwrap();
+        assert_eq!(parsed, mesh);
+    }
+
+    #[test]
+    fn test_quads_are_triangulated() {
+        let source = "v 0 0 0\nv 1 0 0\nv 1 0 1\nv 0 0 1\nf 1 2 3 4\n";
+        assert_eq!(ObjMesh::parse(source, OBJ_VERTEX_EPSILON).unwrap(), quad());
+    }
+
+    #[test]
+    fn test_face_formats_and_ignored_elements() {
+        let source = "\
+            # A quad, split in two triangles.\n\
+            mtllib quad.mtl\n\
+            o Quad\n\
+            v 0 0 0 1.0\n\
+            v 1 0 0\n\
+            v 1 0 1 # Comment after a vertex.\n\
+            v 0 0 1\n\
+            vn 0 1 0\n\
+            vt 0 0\n\
+            usemtl Floor\n\
+            s off\n\
+            f 1/1/1 2/1/1 3//1\n\
+            f -4 -2 -1\n";
+        assert_eq!(ObjMesh::parse(source, OBJ_VERTEX_EPSILON).unwrap(), quad());
+    }
+
+    #[test]
+    fn test_vertices_are_deduplicated() {
+        // Two separate triangles, that share an edge.
+        let source = "\
+            v 0 0 0\nv 1 0 0\nv 1 0 1\n\
+            v 0.000001 0 0\nv 1 0 1.000001\nv 0 0 1\n\
+            f 1 2 3\nf 4 5 6\n";
+        assert_eq!(ObjMesh::parse(source, OBJ_VERTEX_EPSILON).unwrap(), quad());
+
+        // Triangle collapses into a point and must be removed.
+        let source = "v 0 0 0\nv 0.000001 0 0\nv 0 0.000001 0\nf 1 2 3\n";
+        let mesh = ObjMesh::parse(source, OBJ_VERTEX_EPSILON).unwrap();
+        assert_eq!(mesh.vertices.len(), 1);
+        assert!(mesh.triangles.is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            ObjMesh::parse("v 0 0 0\nv 1 x 0\n", OBJ_VERTEX_EPSILON),
+            Err(ObjError::InvalidNumber {
+                line: 2,
+                token: "x".to_owned()
+            })
+        );
+        assert_eq!(
+            ObjMesh::parse("v 0 0\n", OBJ_VERTEX_EPSILON),
+            Err(ObjError::NotEnoughCoordinates { line: 1 })
+        );
+        assert_eq!(
+            ObjMesh::parse("v 0 0 0\nv 1 0 0\nv 1 0 1\nf 1 2 4\n", OBJ_VERTEX_EPSILON),
+            Err(ObjError::InvalidIndex { line: 4, index: 4 })
+        );
+        assert_eq!(
+            ObjMesh::parse("v 0 0 0\nf 0 1 1\n", OBJ_VERTEX_EPSILON),
+            Err(ObjError::InvalidIndex { line: 2, index: 0 })
+        );
+        assert_eq!(
+            ObjMesh::parse("v 0 0 0\nv 1 0 0\nf 1 2\n", OBJ_VERTEX_EPSILON),
+            Err(ObjError::DegenerateFace { line: 3 })
+        );
+    }
+}
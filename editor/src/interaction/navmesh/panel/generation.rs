@@ -0,0 +1,242 @@
+//! Generation (baking) of the navmesh from the scene geometry. Generation is done in small steps,
+//! one step per frame, so the editor stays responsive.
+
+use crate::{
+    interaction::navmesh::{
+        fetch_selection,
+        generator::{
+            collect_scene_geometry, NavmeshGenerationSettings, NavmeshGenerationStatus,
+            NavmeshGenerator,
+        },
+        panel::{make_button, make_parameter, PanelSelection},
+    },
+    message::{Message, MessageSender},
+    scene::{
+        commands::{
+            navmesh::ReplaceNavmeshDataCommand, ChangeSelectionCommand, CommandGroup, SceneCommand,
+        },
+        EditorScene, Selection,
+    },
+    world::graph::selection::GraphSelection,
+};
+use fyrox::{
+    core::{log::Log, pool::Handle},
+    engine::Engine,
+    gui::{
+        button::ButtonMessage,
+        message::{MessageDirection, UiMessage},
+        numeric::NumericUpDownMessage,
+        progress_bar::{ProgressBarBuilder, ProgressBarMessage},
+        text::TextMessage,
+        text_box::TextBoxBuilder,
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+    scene::{navmesh::NavigationalMesh, node::Node, Scene},
+};
+
+struct NavmeshGeneration {
+    scene: Handle<Scene>,
+    navmesh_node: Handle<Node>,
+    generator: NavmeshGenerator,
+}
+
+pub struct GenerationSection {
+    root: Handle<UiNode>,
+    agent_radius: Handle<UiNode>,
+    agent_height: Handle<UiNode>,
+    max_slope: Handle<UiNode>,
+    cell_size: Handle<UiNode>,
+    generate: Handle<UiNode>,
+    progress: Handle<UiNode>,
+    settings: NavmeshGenerationSettings,
+    root_name: String,
+    generation: Option<NavmeshGeneration>,
+}
+
+impl GenerationSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let settings = NavmeshGenerationSettings::default();
+
+        Self {
+            root: TextBoxBuilder::new(
+                WidgetBuilder::new()
+                    .with_width(100.0)
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_tooltip(make_simple_tooltip(
+                        ctx,
+                        "Name of the node, which meshes will be used to generate the navmesh. \
+                        Leave it empty to use the entire scene.",
+                    )),
+            )
+            .with_vertical_text_alignment(VerticalAlignment::Center)
+            .build(ctx),
+            agent_radius: make_parameter(ctx, settings.agent_radius, 0.0, "Agent Radius"),
+            agent_height: make_parameter(ctx, settings.agent_height, 0.0, "Agent Height"),
+            max_slope: make_parameter(ctx, settings.max_slope, 0.0, "Max Slope (degrees)"),
+            cell_size: make_parameter(ctx, settings.cell_size, 0.01, "Cell Size"),
+            generate: make_button(
+                ctx,
+                "Generate",
+                "Replace the content of the selected navmesh with a navmesh generated from the \
+                scene geometry.",
+            ),
+            progress: ProgressBarBuilder::new(
+                WidgetBuilder::new()
+                    .with_width(100.0)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .build(ctx),
+            settings,
+            root_name: Default::default(),
+            generation: None,
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![
+            self.root,
+            self.agent_radius,
+            self.agent_height,
+            self.max_slope,
+            self.cell_size,
+            self.generate,
+            self.progress,
+        ]
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+    ) {
+        if let Some(&NumericUpDownMessage::Value(value)) =
+            message.data::<NumericUpDownMessage<f32>>()
+        {
+            if message.direction() == MessageDirection::FromWidget {
+                let settings = &mut self.settings;
+                if message.destination() == self.agent_radius {
+                    settings.agent_radius = value;
+                } else if message.destination() == self.agent_height {
+                    settings.agent_height = value;
+                } else if message.destination() == self.max_slope {
+                    settings.max_slope = value;
+                } else if message.destination() == self.cell_size {
+                    settings.cell_size = value;
+                }
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data() {
+            if message.destination() == self.root
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.root_name = text.clone();
+            }
+        } else if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
+            if message.destination() == self.generate {
+                self.start_generation(editor_scene, engine);
+            }
+        }
+    }
+
+    fn start_generation(&mut self, editor_scene: &EditorScene, engine: &Engine) {
+        let navmesh_node = match fetch_selection(&editor_scene.selection) {
+            Some(selection) => selection.navmesh_node(),
+            None => return,
+        };
+
+        let graph = &engine.scenes[editor_scene.scene].graph;
+        if graph
+            .try_get_of_type::<NavigationalMesh>(navmesh_node)
+            .is_none()
+        {
+            return;
+        }
+
+        let root = if self.root_name.is_empty() {
+            editor_scene.scene_content_root
+        } else {
+            match graph.find_by_name(editor_scene.scene_content_root, &self.root_name) {
+                Some((root, _)) => root,
+                None => {
+                    Log::err(format!(
+                        "Unable to generate navmesh: there's no node with {} name!",
+                        self.root_name
+                    ));
+                    return;
+                }
+            }
+        };
+
+        self.generation = Some(NavmeshGeneration {
+            scene: editor_scene.scene,
+            navmesh_node,
+            generator: NavmeshGenerator::new(
+                collect_scene_geometry(graph, root),
+                self.settings.clone(),
+            ),
+        });
+
+        engine.user_interface.send_message(WidgetMessage::enabled(
+            self.generate,
+            MessageDirection::ToWidget,
+            false,
+        ));
+    }
+
+    /// Advances navmesh generation (if any), the progress is reported via
+    /// [`Message::NavmeshGenerationProgress`] message, which also makes the editor to process the
+    /// next frame even if the user does nothing.
+    pub fn update(&mut self, editor_scene: &EditorScene, sender: &MessageSender) {
+        if let Some(generation) = self.generation.as_mut() {
+            if generation.scene != editor_scene.scene {
+                // Scene was changed or closed, the result can't be applied.
+                self.generation = None;
+                sender.send(Message::NavmeshGenerationProgress(0.0));
+                return;
+            }
+
+            match generation.generator.step() {
+                NavmeshGenerationStatus::InProgress(progress) => {
+                    sender.send(Message::NavmeshGenerationProgress(progress));
+                }
+                NavmeshGenerationStatus::Finished(navmesh) => {
+                    let navmesh_node = generation.navmesh_node;
+                    self.generation = None;
+
+                    sender.send(Message::NavmeshGenerationProgress(1.0));
+                    sender.do_scene_command(
+                        CommandGroup::from(vec![
+                            SceneCommand::new(ChangeSelectionCommand::new(
+                                Selection::Graph(GraphSelection::single_or_empty(navmesh_node)),
+                                editor_scene.selection.clone(),
+                            )),
+                            SceneCommand::new(ReplaceNavmeshDataCommand::new(
+                                navmesh_node,
+                                navmesh,
+                            )),
+                        ])
+                        .with_custom_name("Generate Navmesh"),
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn set_progress(&self, ui: &UserInterface, progress: f32) {
+        ui.send_message(ProgressBarMessage::progress(
+            self.progress,
+            MessageDirection::ToWidget,
+            progress,
+        ));
+    }
+
+    pub fn sync_to_model(&self, ui: &UserInterface, selection: PanelSelection) {
+        ui.send_message(WidgetMessage::enabled(
+            self.generate,
+            MessageDirection::ToWidget,
+            selection.navmesh_selected && self.generation.is_none(),
+        ));
+    }
+}
@@ -0,0 +1,201 @@
+//! Import and export of navmeshes in Wavefront OBJ format.
+
+use crate::{
+    interaction::navmesh::{
+        fetch_selection,
+        obj::{ObjMesh, OBJ_VERTEX_EPSILON},
+        panel::{make_button, PanelSelection},
+    },
+    message::MessageSender,
+    scene::{
+        commands::{
+            navmesh::ReplaceNavmeshDataCommand, ChangeSelectionCommand, CommandGroup, SceneCommand,
+        },
+        EditorScene, Selection,
+    },
+    world::graph::selection::GraphSelection,
+};
+use fyrox::{
+    core::{log::Log, pool::Handle},
+    engine::Engine,
+    gui::{
+        button::ButtonMessage,
+        file_browser::{FileBrowserMode, FileSelectorBuilder, FileSelectorMessage, Filter},
+        message::{MessageDirection, UiMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, UiNode, UserInterface,
+    },
+    scene::{graph::Graph, navmesh::NavigationalMesh, node::Node},
+};
+use std::path::{Path, PathBuf};
+
+fn make_obj_file_selector(
+    ctx: &mut BuildContext,
+    title: &str,
+    mode: FileBrowserMode,
+) -> Handle<UiNode> {
+    FileSelectorBuilder::new(
+        WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+            .with_title(WindowTitle::text(title))
+            .open(false),
+    )
+    .with_mode(mode)
+    .with_path("./")
+    .with_filter(Filter::new(|p: &Path| {
+        if let Some(ext) = p.extension() {
+            ext.to_string_lossy().eq_ignore_ascii_case("obj")
+        } else {
+            p.is_dir()
+        }
+    }))
+    .build(ctx)
+}
+
+pub struct ObjSection {
+    import: Handle<UiNode>,
+    export: Handle<UiNode>,
+    import_selector: Handle<UiNode>,
+    export_selector: Handle<UiNode>,
+    // Navmesh, that was selected when a file selector was opened.
+    io_navmesh: Handle<Node>,
+}
+
+impl ObjSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        Self {
+            import: make_button(
+                ctx,
+                "Import...",
+                "Replace selected navmesh with the one from a Wavefront OBJ file.",
+            ),
+            export: make_button(
+                ctx,
+                "Export...",
+                "Save selected navmesh as a Wavefront OBJ file.",
+            ),
+            import_selector: make_obj_file_selector(ctx, "Import Navmesh", FileBrowserMode::Open),
+            export_selector: make_obj_file_selector(
+                ctx,
+                "Export Navmesh",
+                FileBrowserMode::Save {
+                    default_file_name: PathBuf::from("navmesh.obj"),
+                },
+            ),
+            io_navmesh: Default::default(),
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![self.import, self.export]
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &MessageSender,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
+            if message.destination() == self.import || message.destination() == self.export {
+                if let Some(selection) = fetch_selection(&editor_scene.selection) {
+                    self.io_navmesh = selection.navmesh_node();
+                    engine
+                        .user_interface
+                        .send_message(WindowMessage::open_modal(
+                            if message.destination() == self.import {
+                                self.import_selector
+                            } else {
+                                self.export_selector
+                            },
+                            MessageDirection::ToWidget,
+                            true,
+                        ));
+                }
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            let graph = &engine.scenes[editor_scene.scene].graph;
+            if message.destination() == self.import_selector {
+                self.import_obj(path, graph, editor_scene, sender);
+            } else if message.destination() == self.export_selector {
+                self.export_obj(path, graph);
+            }
+        }
+    }
+
+    pub fn sync_to_model(&self, ui: &UserInterface, selection: PanelSelection) {
+        for widget in [self.import, self.export] {
+            ui.send_message(WidgetMessage::enabled(
+                widget,
+                MessageDirection::ToWidget,
+                selection.navmesh_selected,
+            ));
+        }
+    }
+
+    fn import_obj(
+        &self,
+        path: &Path,
+        graph: &Graph,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+    ) {
+        if graph
+            .try_get_of_type::<NavigationalMesh>(self.io_navmesh)
+            .is_none()
+        {
+            return;
+        }
+
+        let source = match std::fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to read navmesh from {}. Reason: {:?}",
+                    path.display(),
+                    e
+                ));
+                return;
+            }
+        };
+
+        match ObjMesh::parse(&source, OBJ_VERTEX_EPSILON) {
+            Ok(mesh) => {
+                // Selected vertices won't exist in the new navmesh, so select the node instead.
+                sender.do_scene_command(
+                    CommandGroup::from(vec![
+                        SceneCommand::new(ChangeSelectionCommand::new(
+                            Selection::Graph(GraphSelection::single_or_empty(self.io_navmesh)),
+                            editor_scene.selection.clone(),
+                        )),
+                        SceneCommand::new(ReplaceNavmeshDataCommand::new(
+                            self.io_navmesh,
+                            mesh.into_navmesh(),
+                        )),
+                    ])
+                    .with_custom_name("Import Navmesh"),
+                );
+            }
+            Err(e) => Log::err(format!(
+                "Unable to import navmesh from {}. Reason: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    fn export_obj(&self, path: &Path, graph: &Graph) {
+        if let Some(navmesh) = graph.try_get_of_type::<NavigationalMesh>(self.io_navmesh) {
+            let source = ObjMesh::from_navmesh(navmesh.navmesh_ref()).write();
+            match std::fs::write(path, source) {
+                Ok(_) => Log::info(format!("Navmesh was exported to {}.", path.display())),
+                Err(e) => Log::err(format!(
+                    "Unable to export navmesh to {}. Reason: {:?}",
+                    path.display(),
+                    e
+                )),
+            }
+        }
+    }
+}
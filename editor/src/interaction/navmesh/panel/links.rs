@@ -0,0 +1,58 @@
+//! Options of placement of off-mesh links, the links are placed by the navmesh edit mode.
+
+use crate::interaction::navmesh::{links::NavmeshLinkTool, panel::make_check_box};
+use fyrox::{
+    core::pool::Handle,
+    gui::{
+        check_box::CheckBoxMessage,
+        message::{MessageDirection, UiMessage},
+        BuildContext, UiNode,
+    },
+};
+use std::{cell::RefCell, rc::Rc};
+
+pub struct LinksSection {
+    add_link: Handle<UiNode>,
+    bidirectional: Handle<UiNode>,
+    pub link_tool: Rc<RefCell<NavmeshLinkTool>>,
+}
+
+impl LinksSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        Self {
+            add_link: make_check_box(
+                ctx,
+                "Add Links",
+                Some(
+                    "Add off-mesh links (jumps, drops, etc.). Click on the navmesh in the edit \
+                    mode to set the start of a link, then click again to set its end. Ends of \
+                    links are attached to the closest vertices.",
+                ),
+                false,
+            ),
+            bidirectional: make_check_box(
+                ctx,
+                "Two-Way",
+                Some("New links could be traversed in both directions."),
+                false,
+            ),
+            link_tool: Default::default(),
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![self.add_link, self.bidirectional]
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage) {
+        if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if message.destination() == self.add_link {
+                    self.link_tool.borrow_mut().set_enabled(*value);
+                } else if message.destination() == self.bidirectional {
+                    self.link_tool.borrow_mut().set_bidirectional(*value);
+                }
+            }
+        }
+    }
+}
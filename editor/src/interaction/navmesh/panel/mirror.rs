@@ -0,0 +1,169 @@
+//! Mirroring of the navmesh around a plane, that is perpendicular to one of the axes.
+
+use crate::{
+    gui::make_dropdown_list_option,
+    interaction::navmesh::{fetch_selection, panel::make_button},
+    message::MessageSender,
+    scene::{commands::navmesh::MirrorNavmeshCommand, EditorScene},
+    settings::Settings,
+};
+use fyrox::{
+    core::pool::Handle,
+    engine::Engine,
+    gui::{
+        button::ButtonMessage,
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        message::{MessageDirection, UiMessage},
+        text::TextBuilder,
+        utils::make_simple_tooltip,
+        widget::WidgetBuilder,
+        BuildContext, Thickness, UiNode, VerticalAlignment,
+    },
+    scene::navmesh::NavigationalMesh,
+};
+
+pub struct MirrorSection {
+    mirror: Handle<UiNode>,
+    axis: Handle<UiNode>,
+    pivot: Handle<UiNode>,
+    weld: Handle<UiNode>,
+    // Index of the axis, that is perpendicular to the mirror plane.
+    axis_index: usize,
+    // Whether the mirror plane goes through the origin of the navmesh node or the world origin.
+    around_node: bool,
+    weld_on_mirror: bool,
+}
+
+impl MirrorSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let mirror = make_button(
+            ctx,
+            "Mirror",
+            "Add a mirrored copy of the navmesh. Mirror plane is perpendicular to the chosen \
+            axis.",
+        );
+        let axis = DropdownListBuilder::new(
+            WidgetBuilder::new()
+                .with_width(40.0)
+                .with_margin(Thickness::uniform(1.0))
+                .with_tooltip(make_simple_tooltip(ctx, "Mirror Axis")),
+        )
+        .with_items(
+            ["X", "Y", "Z"]
+                .iter()
+                .map(|axis| make_dropdown_list_option(ctx, axis))
+                .collect(),
+        )
+        .with_selected(0)
+        .build(ctx);
+        let pivot = DropdownListBuilder::new(
+            WidgetBuilder::new()
+                .with_width(100.0)
+                .with_margin(Thickness::uniform(1.0))
+                .with_tooltip(make_simple_tooltip(
+                    ctx,
+                    "Point, that the mirror plane goes through.",
+                )),
+        )
+        .with_items(vec![
+            make_dropdown_list_option(ctx, "Node Origin"),
+            make_dropdown_list_option(ctx, "World Origin"),
+        ])
+        .with_selected(0)
+        .build(ctx);
+        let weld = CheckBoxBuilder::new(
+            WidgetBuilder::new()
+                .with_vertical_alignment(VerticalAlignment::Center)
+                .with_margin(Thickness::uniform(1.0))
+                .with_tooltip(make_simple_tooltip(
+                    ctx,
+                    "Share vertices, that lie on the mirror plane, between both halves. Merge \
+                    threshold from the settings is used as the maximum distance to the plane.",
+                )),
+        )
+        .with_content(
+            TextBuilder::new(WidgetBuilder::new())
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_text("Weld")
+                .build(ctx),
+        )
+        .checked(Some(true))
+        .build(ctx);
+
+        Self {
+            mirror,
+            axis,
+            pivot,
+            weld,
+            axis_index: 0,
+            around_node: true,
+            weld_on_mirror: true,
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![self.mirror, self.axis, self.pivot, self.weld]
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        settings: &Settings,
+        sender: &MessageSender,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
+            if message.destination() == self.mirror {
+                self.mirror(editor_scene, engine, settings, sender);
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.weld
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.weld_on_mirror = *value;
+            }
+        } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if message.destination() == self.axis {
+                    self.axis_index = *index;
+                } else if message.destination() == self.pivot {
+                    self.around_node = *index == 0;
+                }
+            }
+        }
+    }
+
+    fn mirror(
+        &self,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        settings: &Settings,
+        sender: &MessageSender,
+    ) {
+        if let Some(selection) = fetch_selection(&editor_scene.selection) {
+            if let Some(navmesh_node) = engine.scenes[editor_scene.scene]
+                .graph
+                .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+            {
+                let pivot = if self.around_node {
+                    navmesh_node.global_position()[self.axis_index]
+                } else {
+                    0.0
+                };
+
+                sender.do_scene_command(MirrorNavmeshCommand::new(
+                    selection.navmesh_node(),
+                    self.axis_index,
+                    pivot,
+                    if self.weld_on_mirror {
+                        Some(settings.navmesh.merge_threshold)
+                    } else {
+                        None
+                    },
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,390 @@
+//! Navmesh panel - a window with the tools and the parameters of navmesh editing. Every group of
+//! the tools lives in its own submodule with its widgets, state and message handling, the panel
+//! only lays the groups out and passes messages to them.
+
+use crate::{
+    interaction::navmesh::{
+        fetch_selection,
+        links::NavmeshLinkTool,
+        panel::{
+            generation::GenerationSection, io::ObjSection, links::LinksSection,
+            mirror::MirrorSection, navmesh_list::NavmeshListSection, preview::PreviewSection,
+            query::QuerySection, scene_settings::SceneSettingsSection, simplify::SimplifySection,
+            snapping::SnappingSection, statistics::StatisticsSection, topology::TopologySection,
+            validation::ValidationSection, vertex_position::VertexPositionSection,
+            weld::WeldSection, winding::WindingSection,
+        },
+        path_test::NavmeshPathTest,
+        selection::NavmeshEntity,
+        validation::NavmeshOverlaps,
+    },
+    message::MessageSender,
+    scene::EditorScene,
+    settings::Settings,
+    utils::window_content,
+    Mode,
+};
+use fyrox::{
+    core::{pool::Handle, scope_profile},
+    engine::Engine,
+    gui::{
+        button::ButtonBuilder,
+        check_box::CheckBoxBuilder,
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        numeric::NumericUpDownBuilder,
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, Orientation, Thickness, UiNode, UserInterface, VerticalAlignment,
+    },
+    scene::navmesh::NavigationalMesh,
+};
+use std::{cell::RefCell, rc::Rc};
+
+pub mod generation;
+pub mod io;
+pub mod links;
+pub mod mirror;
+pub mod navmesh_list;
+pub mod preview;
+pub mod query;
+pub mod scene_settings;
+pub mod simplify;
+pub mod snapping;
+pub mod statistics;
+pub mod topology;
+pub mod validation;
+pub mod vertex_position;
+pub mod weld;
+pub mod winding;
+
+/// Summary of the current selection, that defines which tools are available.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct PanelSelection {
+    /// Whether an existing navmesh is selected.
+    pub navmesh_selected: bool,
+    pub edges: usize,
+    pub vertices: usize,
+}
+
+impl PanelSelection {
+    fn new(editor_scene: &EditorScene, engine: &Engine) -> Self {
+        match fetch_selection(&editor_scene.selection) {
+            Some(selection) => Self {
+                navmesh_selected: engine.scenes[editor_scene.scene]
+                    .graph
+                    .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                    .is_some(),
+                edges: selection
+                    .entities()
+                    .iter()
+                    .filter(|e| matches!(e, NavmeshEntity::Edge(_)))
+                    .count(),
+                vertices: selection.unique_vertices().len(),
+            },
+            None => Default::default(),
+        }
+    }
+}
+
+pub fn make_parameter(
+    ctx: &mut BuildContext,
+    value: f32,
+    min_value: f32,
+    tooltip: &str,
+) -> Handle<UiNode> {
+    NumericUpDownBuilder::<f32>::new(
+        WidgetBuilder::new()
+            .with_width(60.0)
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_min_value(min_value)
+    .with_value(value)
+    .build(ctx)
+}
+
+pub fn make_button(ctx: &mut BuildContext, text: &str, tooltip: &str) -> Handle<UiNode> {
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_margin(Thickness::uniform(1.0))
+            .with_tooltip(make_simple_tooltip(ctx, tooltip)),
+    )
+    .with_text(text)
+    .build(ctx)
+}
+
+pub fn make_check_box(
+    ctx: &mut BuildContext,
+    text: &str,
+    tooltip: Option<&str>,
+    checked: bool,
+) -> Handle<UiNode> {
+    let mut widget = WidgetBuilder::new()
+        .with_vertical_alignment(VerticalAlignment::Center)
+        .with_margin(Thickness::uniform(1.0));
+    if let Some(tooltip) = tooltip {
+        widget = widget.with_tooltip(make_simple_tooltip(ctx, tooltip));
+    }
+
+    CheckBoxBuilder::new(widget)
+        .with_content(
+            TextBuilder::new(WidgetBuilder::new())
+                .with_vertical_text_alignment(VerticalAlignment::Center)
+                .with_text(text)
+                .build(ctx),
+        )
+        .checked(Some(checked))
+        .build(ctx)
+}
+
+/// Creates a caption for a row of the panel.
+pub fn make_caption(ctx: &mut BuildContext, text: &str, tooltip: Option<&str>) -> Handle<UiNode> {
+    let mut widget = WidgetBuilder::new()
+        .with_vertical_alignment(VerticalAlignment::Center)
+        .with_margin(Thickness::uniform(1.0));
+    if let Some(tooltip) = tooltip {
+        widget = widget.with_tooltip(make_simple_tooltip(ctx, tooltip));
+    }
+
+    TextBuilder::new(widget).with_text(text).build(ctx)
+}
+
+fn make_row(
+    ctx: &mut BuildContext,
+    row: usize,
+    widgets: impl IntoIterator<Item = Handle<UiNode>>,
+) -> Handle<UiNode> {
+    StackPanelBuilder::new(WidgetBuilder::new().on_row(row).with_children(widgets))
+        .with_orientation(Orientation::Horizontal)
+        .build(ctx)
+}
+
+pub struct NavmeshPanel {
+    pub window: Handle<UiNode>,
+    sender: MessageSender,
+    topology: TopologySection,
+    weld: WeldSection,
+    winding: WindingSection,
+    mirror: MirrorSection,
+    snapping: SnappingSection,
+    validation: ValidationSection,
+    io: ObjSection,
+    generation: GenerationSection,
+    simplify: SimplifySection,
+    preview: PreviewSection,
+    query: QuerySection,
+    links: LinksSection,
+    navmesh_list: NavmeshListSection,
+    vertex_position: VertexPositionSection,
+    statistics: StatisticsSection,
+    scene_settings: SceneSettingsSection,
+}
+
+impl NavmeshPanel {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender, settings: &Settings) -> Self {
+        let topology = TopologySection::new(ctx);
+        let weld = WeldSection::new(ctx);
+        let winding = WindingSection::new(ctx);
+        let mirror = MirrorSection::new(ctx);
+        let snapping = SnappingSection::new(ctx, settings);
+        let validation = ValidationSection::new(ctx);
+        let io = ObjSection::new(ctx);
+        let generation = GenerationSection::new(ctx);
+        let simplify = SimplifySection::new(ctx);
+        let preview = PreviewSection::new(ctx, settings);
+        let query = QuerySection::new(ctx);
+        let links = LinksSection::new(ctx);
+        let navmesh_list = NavmeshListSection::new(ctx);
+        let vertex_position = VertexPositionSection::new(ctx);
+        let statistics = StatisticsSection::new(ctx);
+        let scene_settings = SceneSettingsSection::new(ctx, settings);
+
+        let rows = [
+            make_row(
+                ctx,
+                0,
+                topology
+                    .widgets()
+                    .into_iter()
+                    .chain(weld.widgets())
+                    .chain(winding.widgets())
+                    .chain(mirror.widgets())
+                    .chain(snapping.widgets())
+                    .chain(validation.widgets())
+                    .chain(io.widgets()),
+            ),
+            make_row(
+                ctx,
+                1,
+                generation.widgets().into_iter().chain(simplify.widgets()),
+            ),
+            make_row(
+                ctx,
+                2,
+                preview
+                    .widgets()
+                    .into_iter()
+                    .chain(query.widgets())
+                    .chain(links.widgets()),
+            ),
+            make_row(
+                ctx,
+                3,
+                navmesh_list
+                    .widgets()
+                    .into_iter()
+                    .chain(vertex_position.widgets())
+                    .chain(statistics.widgets()),
+            ),
+            make_row(ctx, 4, scene_settings.widgets()),
+        ];
+
+        let window = WindowBuilder::new(WidgetBuilder::new().with_name("NavmeshPanel"))
+            .open(false)
+            .with_title(WindowTitle::text("Navmesh"))
+            .with_content(
+                GridBuilder::new(WidgetBuilder::new().with_children(rows))
+                    .add_column(Column::stretch())
+                    .add_row(Row::strict(20.0))
+                    .add_row(Row::strict(20.0))
+                    .add_row(Row::strict(20.0))
+                    .add_row(Row::strict(20.0))
+                    .add_row(Row::strict(20.0))
+                    .build(ctx),
+            )
+            .with_default_button(topology.connect_edges)
+            .build(ctx);
+
+        Self {
+            window,
+            sender,
+            topology,
+            weld,
+            winding,
+            mirror,
+            snapping,
+            validation,
+            io,
+            generation,
+            simplify,
+            preview,
+            query,
+            links,
+            navmesh_list,
+            vertex_position,
+            statistics,
+            scene_settings,
+        }
+    }
+
+    /// Returns the state of the path preview tool, which is shared with the navmesh edit mode.
+    pub fn path_test(&self) -> Rc<RefCell<NavmeshPathTest>> {
+        self.query.path_test.clone()
+    }
+
+    /// Returns the state of the link placement tool, which is shared with the navmesh edit mode.
+    pub fn link_tool(&self) -> Rc<RefCell<NavmeshLinkTool>> {
+        self.links.link_tool.clone()
+    }
+
+    /// Returns overlapping triangles found by the validation, which are highlighted by the navmesh
+    /// edit mode.
+    pub fn overlaps(&self) -> Rc<RefCell<NavmeshOverlaps>> {
+        self.validation.overlaps.clone()
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &mut EditorScene,
+        engine: &mut Engine,
+        settings: &mut Settings,
+    ) {
+        scope_profile!();
+
+        let sender = &self.sender;
+        self.topology
+            .handle_ui_message(message, editor_scene, engine, sender);
+        self.weld
+            .handle_ui_message(message, editor_scene, engine, settings, sender);
+        self.winding
+            .handle_ui_message(message, editor_scene, engine, sender);
+        self.mirror
+            .handle_ui_message(message, editor_scene, engine, settings, sender);
+        self.snapping
+            .handle_ui_message(message, editor_scene, engine, settings, sender);
+        self.validation
+            .handle_ui_message(message, editor_scene, engine, sender);
+        self.io
+            .handle_ui_message(message, editor_scene, engine, sender);
+        self.generation
+            .handle_ui_message(message, editor_scene, engine);
+        self.simplify
+            .handle_ui_message(message, editor_scene, engine, sender);
+        self.preview.handle_ui_message(message, settings);
+        self.query.handle_ui_message(message);
+        self.links.handle_ui_message(message);
+        self.navmesh_list
+            .handle_ui_message(message, editor_scene, sender);
+        self.vertex_position
+            .handle_ui_message(message, self.window, editor_scene, engine, sender);
+        self.statistics.handle_ui_message(message, engine);
+        self.scene_settings
+            .handle_ui_message(message, editor_scene, settings);
+    }
+
+    /// Advances navmesh generation (if any) and shows the state of the edited navmesh: its
+    /// statistics, the position of the selected vertices and the status of the path preview.
+    pub fn update(&mut self, editor_scene: &EditorScene, engine: &Engine) {
+        self.statistics.update(editor_scene, engine);
+        self.vertex_position.update(editor_scene, engine);
+        self.query.update(&engine.user_interface);
+        self.generation.update(editor_scene, &self.sender);
+    }
+
+    pub fn set_generation_progress(&self, ui: &UserInterface, progress: f32) {
+        self.generation.set_progress(ui, progress);
+    }
+
+    pub fn sync_to_model(&mut self, engine: &mut Engine, editor_scene: &EditorScene) {
+        let selection = PanelSelection::new(editor_scene, engine);
+
+        self.topology.sync_to_model(engine, editor_scene, selection);
+        let ui = &mut engine.user_interface;
+        let graph = &engine.scenes[editor_scene.scene].graph;
+        self.weld.sync_to_model(ui, selection);
+        self.winding.sync_to_model(ui, selection);
+        self.validation.sync_to_model(ui, graph, selection);
+        self.io.sync_to_model(ui, selection);
+        self.generation.sync_to_model(ui, selection);
+        self.simplify.sync_to_model(ui, selection);
+        self.navmesh_list.sync_to_model(ui, editor_scene, graph);
+        self.statistics.sync_to_model();
+        self.scene_settings.sync_to_model(ui, editor_scene);
+
+        if selection.navmesh_selected {
+            ui.send_message(WindowMessage::open(
+                self.window,
+                MessageDirection::ToWidget,
+                false,
+            ));
+        } else {
+            ui.send_message(WindowMessage::close(
+                self.window,
+                MessageDirection::ToWidget,
+            ));
+        }
+    }
+
+    pub fn on_mode_changed(&mut self, ui: &UserInterface, mode: &Mode) {
+        ui.send_message(WidgetMessage::enabled(
+            window_content(self.window, ui),
+            MessageDirection::ToWidget,
+            mode.is_edit(),
+        ));
+    }
+}
@@ -0,0 +1,114 @@
+//! List of the navmeshes of the scene, that allows to choose the edited one.
+
+use crate::{
+    gui::make_dropdown_list_option,
+    interaction::navmesh::{
+        binding::scene_navmeshes, fetch_selection, panel::make_caption, selection::NavmeshSelection,
+    },
+    message::MessageSender,
+    scene::{commands::ChangeSelectionCommand, EditorScene, Selection},
+};
+use fyrox::{
+    core::pool::Handle,
+    gui::{
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        message::{MessageDirection, UiMessage},
+        utils::make_simple_tooltip,
+        widget::WidgetBuilder,
+        BuildContext, Thickness, UiNode, UserInterface,
+    },
+    scene::{graph::Graph, node::Node},
+};
+
+pub struct NavmeshListSection {
+    caption: Handle<UiNode>,
+    list: Handle<UiNode>,
+    // Navmeshes of the scene with their names, in the same order as in the list.
+    navmeshes: Vec<(Handle<Node>, String)>,
+}
+
+impl NavmeshListSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        Self {
+            caption: make_caption(ctx, "Navmesh", None),
+            list: DropdownListBuilder::new(
+                WidgetBuilder::new()
+                    .with_width(150.0)
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_tooltip(make_simple_tooltip(
+                        ctx,
+                        "Navmesh, that is currently edited.",
+                    )),
+            )
+            .build(ctx),
+            navmeshes: Default::default(),
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![self.caption, self.list]
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+    ) {
+        if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some((navmesh, _)) = self.navmeshes.get(*index) {
+                    let current = fetch_selection(&editor_scene.selection)
+                        .map(|s| s.navmesh_node())
+                        .unwrap_or_default();
+                    if *navmesh != current {
+                        sender.do_scene_command(ChangeSelectionCommand::new(
+                            Selection::Navmesh(NavmeshSelection::empty(*navmesh)),
+                            editor_scene.selection.clone(),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn sync_to_model(
+        &mut self,
+        ui: &mut UserInterface,
+        editor_scene: &EditorScene,
+        graph: &Graph,
+    ) {
+        let navmeshes = scene_navmeshes(editor_scene, graph)
+            .into_iter()
+            .map(|h| (h, graph[h].name().to_owned()))
+            .collect::<Vec<_>>();
+        if navmeshes != self.navmeshes {
+            let items = {
+                let ctx = &mut ui.build_ctx();
+                navmeshes
+                    .iter()
+                    .map(|(_, name)| make_dropdown_list_option(ctx, name))
+                    .collect::<Vec<_>>()
+            };
+            ui.send_message(DropdownListMessage::items(
+                self.list,
+                MessageDirection::ToWidget,
+                items,
+            ));
+            self.navmeshes = navmeshes;
+        }
+
+        let current = fetch_selection(&editor_scene.selection).and_then(|selection| {
+            self.navmeshes
+                .iter()
+                .position(|(h, _)| *h == selection.navmesh_node())
+        });
+        ui.send_message(DropdownListMessage::selection(
+            self.list,
+            MessageDirection::ToWidget,
+            current,
+        ));
+    }
+}
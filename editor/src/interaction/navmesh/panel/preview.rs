@@ -0,0 +1,95 @@
+//! Options of the previews, that are drawn over the navmesh: the area, that is walkable for an
+//! agent of some radius, and the slope of the triangles.
+
+use crate::{
+    interaction::navmesh::panel::{make_check_box, make_parameter},
+    settings::Settings,
+};
+use fyrox::{
+    core::{log::Log, pool::Handle},
+    gui::{
+        check_box::CheckBoxMessage,
+        message::{MessageDirection, UiMessage},
+        numeric::NumericUpDownMessage,
+        BuildContext, UiNode,
+    },
+};
+
+pub struct PreviewSection {
+    show_agent_radius: Handle<UiNode>,
+    agent_radius: Handle<UiNode>,
+    show_slope: Handle<UiNode>,
+    max_walkable_slope: Handle<UiNode>,
+}
+
+impl PreviewSection {
+    pub fn new(ctx: &mut BuildContext, settings: &Settings) -> Self {
+        Self {
+            show_agent_radius: make_check_box(
+                ctx,
+                "Agent Radius",
+                Some(
+                    "Show the boundary of the area, that is walkable for an agent of the given \
+                    radius, and highlight triangles narrower than the agent in red.",
+                ),
+                settings.navmesh.show_agent_radius,
+            ),
+            agent_radius: make_parameter(
+                ctx,
+                settings.navmesh.agent_radius,
+                0.0,
+                "Radius of the agent for the preview",
+            ),
+            show_slope: make_check_box(
+                ctx,
+                "Slope",
+                Some(
+                    "Highlight triangles, that are steeper than the given slope, in red and the \
+                    rest in green.",
+                ),
+                settings.navmesh.show_slope,
+            ),
+            max_walkable_slope: make_parameter(
+                ctx,
+                settings.navmesh.max_walkable_slope,
+                0.0,
+                "Maximal walkable slope in degrees",
+            ),
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![
+            self.show_agent_radius,
+            self.agent_radius,
+            self.show_slope,
+            self.max_walkable_slope,
+        ]
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, settings: &mut Settings) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        if let Some(&NumericUpDownMessage::Value(value)) =
+            message.data::<NumericUpDownMessage<f32>>()
+        {
+            if message.destination() == self.agent_radius {
+                settings.navmesh.agent_radius = value;
+                Log::verify(settings.save());
+            } else if message.destination() == self.max_walkable_slope {
+                settings.navmesh.max_walkable_slope = value;
+                Log::verify(settings.save());
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.show_agent_radius {
+                settings.navmesh.show_agent_radius = *value;
+                Log::verify(settings.save());
+            } else if message.destination() == self.show_slope {
+                settings.navmesh.show_slope = *value;
+                Log::verify(settings.save());
+            }
+        }
+    }
+}
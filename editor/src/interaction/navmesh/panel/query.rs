@@ -0,0 +1,88 @@
+//! Path query preview: a path between two points, that are placed on the navmesh in the edit mode,
+//! and the status of the search.
+
+use crate::interaction::navmesh::{
+    panel::make_check_box,
+    path_test::{NavmeshPathTest, PathTestStatus},
+};
+use fyrox::{
+    core::{color::Color, pool::Handle},
+    gui::{
+        brush::Brush,
+        check_box::CheckBoxMessage,
+        message::{MessageDirection, UiMessage},
+        text::{TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, Thickness, UiNode, UserInterface, VerticalAlignment, BRUSH_TEXT,
+    },
+};
+use std::{cell::RefCell, rc::Rc};
+
+pub struct QuerySection {
+    test_path: Handle<UiNode>,
+    path_status: Handle<UiNode>,
+    pub path_test: Rc<RefCell<NavmeshPathTest>>,
+    // Status, that is currently shown in the panel.
+    shown_path_status: Option<PathTestStatus>,
+}
+
+impl QuerySection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        Self {
+            test_path: make_check_box(
+                ctx,
+                "Test Path",
+                Some(
+                    "Preview a path between two points. Ctrl+Click on the navmesh in the edit \
+                    mode to set the start and the end points, then drag them around.",
+                ),
+                false,
+            ),
+            path_status: TextBuilder::new(
+                WidgetBuilder::new()
+                    .with_vertical_alignment(VerticalAlignment::Center)
+                    .with_margin(Thickness::uniform(1.0)),
+            )
+            .build(ctx),
+            path_test: Default::default(),
+            shown_path_status: None,
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![self.test_path, self.path_status]
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage) {
+        if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.test_path
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.path_test.borrow_mut().set_enabled(*value);
+            }
+        }
+    }
+
+    /// Shows the status of the path preview, which is computed by the navmesh edit mode.
+    pub fn update(&mut self, ui: &UserInterface) {
+        let path_test = self.path_test.borrow();
+        let status = path_test.is_enabled().then(|| path_test.status());
+        if status != self.shown_path_status {
+            ui.send_message(TextMessage::text(
+                self.path_status,
+                MessageDirection::ToWidget,
+                status.map(|s| s.to_string()).unwrap_or_default(),
+            ));
+            ui.send_message(WidgetMessage::foreground(
+                self.path_status,
+                MessageDirection::ToWidget,
+                if status == Some(PathTestStatus::NotFound) {
+                    Brush::Solid(Color::RED)
+                } else {
+                    BRUSH_TEXT
+                },
+            ));
+            self.shown_path_status = status;
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Editing parameters of the current scene and options of vertex labels.
+
+use crate::{
+    interaction::navmesh::panel::{make_caption, make_check_box, make_parameter},
+    scene::EditorScene,
+    send_sync_message,
+    settings::Settings,
+};
+use fyrox::{
+    core::{log::Log, pool::Handle},
+    gui::{
+        check_box::CheckBoxMessage,
+        message::{MessageDirection, UiMessage},
+        numeric::NumericUpDownMessage,
+        BuildContext, UiNode, UserInterface,
+    },
+};
+
+pub struct SceneSettingsSection {
+    caption: Handle<UiNode>,
+    vertex_radius: Handle<UiNode>,
+    snap_offset: Handle<UiNode>,
+    height_offset: Handle<UiNode>,
+    show_vertex_labels: Handle<UiNode>,
+    show_label_coordinates: Handle<UiNode>,
+    label_distance: Handle<UiNode>,
+}
+
+impl SceneSettingsSection {
+    pub fn new(ctx: &mut BuildContext, settings: &Settings) -> Self {
+        Self {
+            caption: make_caption(
+                ctx,
+                "Scene",
+                Some(
+                    "Editing parameters of the current scene. They are remembered for every \
+                    scene, the defaults for new scenes are in the editor settings.",
+                ),
+            ),
+            vertex_radius: make_parameter(
+                ctx,
+                settings.navmesh.vertex_radius,
+                0.001,
+                "Vertex Radius",
+            ),
+            snap_offset: make_parameter(
+                ctx,
+                settings.navmesh.snap_offset,
+                f32::MIN,
+                "Snap Offset - vertical offset of a snapped vertex from the geometry surface",
+            ),
+            height_offset: make_parameter(
+                ctx,
+                settings.navmesh.height_offset,
+                f32::MIN,
+                "Draw Offset - vertical offset of the drawn navmesh",
+            ),
+            show_vertex_labels: make_check_box(
+                ctx,
+                "Vertex Labels",
+                Some(
+                    "Show indices of the vertices, that are closer than the given distance to \
+                    the camera.",
+                ),
+                settings.navmesh.show_vertex_labels,
+            ),
+            show_label_coordinates: make_check_box(
+                ctx,
+                "Coordinates",
+                Some("Show coordinates of the vertices in the labels."),
+                settings.navmesh.show_label_coordinates,
+            ),
+            label_distance: make_parameter(
+                ctx,
+                settings.navmesh.label_distance,
+                0.0,
+                "Maximum distance from the camera to a labeled vertex",
+            ),
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![
+            self.caption,
+            self.vertex_radius,
+            self.snap_offset,
+            self.height_offset,
+            self.show_vertex_labels,
+            self.show_label_coordinates,
+            self.label_distance,
+        ]
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &mut EditorScene,
+        settings: &mut Settings,
+    ) {
+        if message.direction() != MessageDirection::FromWidget {
+            return;
+        }
+
+        if let Some(&NumericUpDownMessage::Value(value)) =
+            message.data::<NumericUpDownMessage<f32>>()
+        {
+            let scene_settings = &mut editor_scene.navmesh_settings;
+            if message.destination() == self.vertex_radius {
+                scene_settings.vertex_radius = value;
+            } else if message.destination() == self.snap_offset {
+                scene_settings.snap_offset = value;
+            } else if message.destination() == self.height_offset {
+                scene_settings.height_offset = value;
+            } else if message.destination() == self.label_distance {
+                settings.navmesh.label_distance = value;
+                Log::verify(settings.save());
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.show_vertex_labels {
+                settings.navmesh.show_vertex_labels = *value;
+                Log::verify(settings.save());
+            } else if message.destination() == self.show_label_coordinates {
+                settings.navmesh.show_label_coordinates = *value;
+                Log::verify(settings.save());
+            }
+        }
+    }
+
+    pub fn sync_to_model(&self, ui: &UserInterface, editor_scene: &EditorScene) {
+        let scene_settings = &editor_scene.navmesh_settings;
+        for (widget, value) in [
+            (self.vertex_radius, scene_settings.vertex_radius),
+            (self.snap_offset, scene_settings.snap_offset),
+            (self.height_offset, scene_settings.height_offset),
+        ] {
+            send_sync_message(
+                ui,
+                NumericUpDownMessage::value(widget, MessageDirection::ToWidget, value),
+            );
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Simplification of the navmesh, which reduces the number of its triangles.
+
+use crate::{
+    interaction::navmesh::{
+        fetch_selection,
+        panel::{make_button, make_check_box, make_parameter, PanelSelection},
+        simplify::NavmeshSimplification,
+    },
+    message::MessageSender,
+    scene::{commands::navmesh::SimplifyNavmeshCommand, EditorScene},
+};
+use fyrox::{
+    core::pool::Handle,
+    engine::Engine,
+    gui::{
+        button::ButtonMessage,
+        check_box::CheckBoxMessage,
+        message::{MessageDirection, UiMessage},
+        numeric::NumericUpDownMessage,
+        widget::WidgetMessage,
+        BuildContext, UiNode, UserInterface,
+    },
+    scene::navmesh::NavigationalMesh,
+};
+
+pub struct SimplifySection {
+    simplify: Handle<UiNode>,
+    error: Handle<UiNode>,
+    boundary: Handle<UiNode>,
+    simplification: NavmeshSimplification,
+}
+
+impl SimplifySection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let simplification = NavmeshSimplification::default();
+
+        Self {
+            simplify: make_button(
+                ctx,
+                "Simplify",
+                "Reduce the number of triangles of the selected navmesh by collapsing its edges, \
+                while the surface stays closer than the given error to the original one.",
+            ),
+            error: make_parameter(
+                ctx,
+                simplification.max_error,
+                0.0,
+                "Maximum Simplification Error",
+            ),
+            boundary: make_check_box(
+                ctx,
+                "Allow Boundary Collapse",
+                Some(
+                    "Allow collapse of the boundary edges. Otherwise the boundary of the navmesh \
+                    stays exactly as it was.",
+                ),
+                simplification.allow_boundary_collapse,
+            ),
+            simplification,
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![self.simplify, self.error, self.boundary]
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &MessageSender,
+    ) {
+        if let Some(&NumericUpDownMessage::Value(value)) =
+            message.data::<NumericUpDownMessage<f32>>()
+        {
+            if message.destination() == self.error
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.simplification.max_error = value;
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.boundary
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.simplification.allow_boundary_collapse = *value;
+            }
+        } else if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
+            if message.destination() == self.simplify {
+                if let Some(selection) = fetch_selection(&editor_scene.selection) {
+                    if engine.scenes[editor_scene.scene]
+                        .graph
+                        .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                        .is_some()
+                    {
+                        sender.do_scene_command(SimplifyNavmeshCommand::new(
+                            selection.navmesh_node(),
+                            self.simplification,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn sync_to_model(&self, ui: &UserInterface, selection: PanelSelection) {
+        ui.send_message(WidgetMessage::enabled(
+            self.simplify,
+            MessageDirection::ToWidget,
+            selection.navmesh_selected,
+        ));
+    }
+}
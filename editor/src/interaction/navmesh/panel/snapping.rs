@@ -0,0 +1,149 @@
+//! Snapping of navmesh vertices to the scene geometry: options of snapping during editing and
+//! projection of existing vertices onto the geometry.
+
+use crate::{
+    interaction::navmesh::{
+        fetch_selection,
+        panel::{make_button, make_check_box},
+        project_onto_geometry,
+    },
+    message::MessageSender,
+    scene::{
+        commands::{navmesh::MoveNavmeshVertexCommand, CommandGroup, SceneCommand},
+        EditorScene,
+    },
+    settings::Settings,
+};
+use fyrox::{
+    core::{log::Log, pool::Handle},
+    engine::Engine,
+    gui::{
+        button::ButtonMessage,
+        check_box::CheckBoxMessage,
+        message::{MessageDirection, UiMessage},
+        BuildContext, UiNode,
+    },
+    scene::navmesh::NavigationalMesh,
+};
+
+pub struct SnappingSection {
+    snap_to_geometry: Handle<UiNode>,
+    extrude_along_surface: Handle<UiNode>,
+    project_onto_geometry: Handle<UiNode>,
+}
+
+impl SnappingSection {
+    pub fn new(ctx: &mut BuildContext, settings: &Settings) -> Self {
+        Self {
+            snap_to_geometry: make_check_box(
+                ctx,
+                "Snap To Geometry",
+                None,
+                settings.navmesh.snap_to_geometry,
+            ),
+            extrude_along_surface: make_check_box(
+                ctx,
+                "Extrude Along Surface",
+                Some(
+                    "Move an edge duplicated by Shift+Drag along the plane of the triangle of \
+                    the source edge, instead of the plane of the move gizmo.",
+                ),
+                settings.navmesh.extrude_along_surface,
+            ),
+            project_onto_geometry: make_button(
+                ctx,
+                "Project Onto Geometry",
+                "Move selected vertices (or every vertex of the navmesh, if nothing is selected) \
+                vertically onto the scene geometry. Search distance and offset are taken from \
+                the snap settings.",
+            ),
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![
+            self.snap_to_geometry,
+            self.extrude_along_surface,
+            self.project_onto_geometry,
+        ]
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        settings: &mut Settings,
+        sender: &MessageSender,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
+            if message.destination() == self.project_onto_geometry {
+                project_navmesh_onto_geometry(editor_scene, engine, settings, sender);
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                if message.destination() == self.snap_to_geometry {
+                    settings.navmesh.snap_to_geometry = *value;
+                    Log::verify(settings.save());
+                } else if message.destination() == self.extrude_along_surface {
+                    settings.navmesh.extrude_along_surface = *value;
+                    Log::verify(settings.save());
+                }
+            }
+        }
+    }
+}
+
+/// Moves selected vertices (or every vertex, if nothing is selected) vertically onto the scene
+/// geometry.
+fn project_navmesh_onto_geometry(
+    editor_scene: &EditorScene,
+    engine: &Engine,
+    settings: &Settings,
+    sender: &MessageSender,
+) {
+    let selection = match fetch_selection(&editor_scene.selection) {
+        Some(selection) => selection,
+        None => return,
+    };
+
+    let graph = &engine.scenes[editor_scene.scene].graph;
+    let navmesh = match graph
+        .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+        .map(|n| n.navmesh_ref())
+    {
+        Some(navmesh) => navmesh,
+        None => return,
+    };
+
+    let vertices = if selection.is_empty() {
+        (0..navmesh.vertices().len()).collect()
+    } else {
+        selection.unique_vertices().clone()
+    };
+
+    let mut commands = Vec::new();
+    for &vertex in vertices.iter() {
+        let position = navmesh.vertices()[vertex].position;
+        if let Some(projected) = project_onto_geometry(position, graph, editor_scene, settings) {
+            commands.push(SceneCommand::new(MoveNavmeshVertexCommand::new(
+                selection.navmesh_node(),
+                vertex,
+                position,
+                projected,
+            )));
+        }
+    }
+
+    Log::info(format!(
+        "{} navmesh vertices were projected onto the geometry, {} were skipped.",
+        commands.len(),
+        vertices.len() - commands.len()
+    ));
+
+    if !commands.is_empty() {
+        sender.do_scene_command(
+            CommandGroup::from(commands).with_custom_name("Project Navmesh Onto Geometry"),
+        );
+    }
+}
@@ -0,0 +1,116 @@
+//! Statistics of the edited navmesh, which could be copied to the clipboard by a click.
+
+use crate::{
+    interaction::navmesh::{fetch_selection, validation::navmesh_area},
+    scene::EditorScene,
+};
+use fyrox::{
+    core::{log::Log, pool::Handle},
+    engine::Engine,
+    gui::{
+        copypasta::ClipboardProvider,
+        message::{MessageDirection, UiMessage},
+        text::{TextBuilder, TextMessage},
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, Thickness, UiNode, VerticalAlignment,
+    },
+    scene::{graph::Graph, navmesh::NavigationalMesh},
+};
+use std::time::{Duration, Instant};
+
+/// Statistics are refreshed a few times per second, because area calculation could be slow on big
+/// navmeshes.
+const STATISTICS_UPDATE_INTERVAL: Duration = Duration::from_millis(250);
+
+fn navmesh_statistics(editor_scene: &EditorScene, graph: &Graph) -> String {
+    let selection = match fetch_selection(&editor_scene.selection) {
+        Some(selection) => selection,
+        None => return String::new(),
+    };
+
+    match graph
+        .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+        .map(|n| n.navmesh_ref())
+    {
+        Some(navmesh) => {
+            let positions = navmesh
+                .vertices()
+                .iter()
+                .map(|v| v.position)
+                .collect::<Vec<_>>();
+            format!(
+                "Vertices: {}  Triangles: {}  Selected: {}  Area: {:.2}",
+                navmesh.vertices().len(),
+                navmesh.triangles().len(),
+                selection.len(),
+                navmesh_area(&positions, navmesh.triangles())
+            )
+        }
+        None => String::new(),
+    }
+}
+
+pub struct StatisticsSection {
+    text: Handle<UiNode>,
+    statistics: String,
+    last_update: Option<Instant>,
+}
+
+impl StatisticsSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        Self {
+            text: TextBuilder::new(
+                WidgetBuilder::new()
+                    .with_vertical_alignment(VerticalAlignment::Center)
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_tooltip(make_simple_tooltip(
+                        ctx,
+                        "Click to copy the statistics to the clipboard.",
+                    )),
+            )
+            .build(ctx),
+            statistics: Default::default(),
+            last_update: None,
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![self.text]
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, engine: &mut Engine) {
+        if let Some(WidgetMessage::MouseDown { .. }) = message.data() {
+            if message.destination() == self.text && !self.statistics.is_empty() {
+                if let Some(mut clipboard) = engine.user_interface.clipboard_mut() {
+                    Log::verify(clipboard.set_contents(self.statistics.clone()));
+                }
+            }
+        }
+    }
+
+    pub fn update(&mut self, editor_scene: &EditorScene, engine: &Engine) {
+        if self
+            .last_update
+            .map_or(true, |time| time.elapsed() >= STATISTICS_UPDATE_INTERVAL)
+        {
+            self.last_update = Some(Instant::now());
+
+            let statistics =
+                navmesh_statistics(editor_scene, &engine.scenes[editor_scene.scene].graph);
+            if statistics != self.statistics {
+                engine.user_interface.send_message(TextMessage::text(
+                    self.text,
+                    MessageDirection::ToWidget,
+                    statistics.clone(),
+                ));
+                self.statistics = statistics;
+            }
+        }
+    }
+
+    /// Shows actual statistics right after any change.
+    pub fn sync_to_model(&mut self) {
+        self.last_update = None;
+    }
+}
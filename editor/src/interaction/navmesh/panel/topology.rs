@@ -0,0 +1,468 @@
+//! Tools, that change topology of the navmesh: connection and bridging of edges, extrusion,
+//! standalone triangles, toggling of triangles and splitting of the navmesh into two.
+
+use crate::{
+    interaction::navmesh::{
+        add_triangle_command, fetch_selection,
+        panel::{make_button, PanelSelection},
+        selection::{NavmeshEntity, NavmeshSelection},
+    },
+    message::MessageSender,
+    scene::{
+        commands::{
+            graph::AddModelCommand,
+            navmesh::{
+                plan_edge_connections, plan_navmesh_split, AddNavmeshEdgeCommand,
+                BridgeNavmeshEdgesCommand, ConnectNavmeshEdgesCommand,
+                DeleteNavmeshTriangleCommand, DeleteNavmeshVertexCommand, EdgeConnectionPlan,
+                ToggleNavmeshTrianglesCommand,
+            },
+            ChangeSelectionCommand, CommandGroup, SceneCommand,
+        },
+        EditorScene, Selection,
+    },
+};
+use fyrox::{
+    core::{
+        algebra::Vector3,
+        log::Log,
+        math::{ray::Ray, TriangleEdge},
+        pool::Handle,
+    },
+    engine::Engine,
+    gui::{
+        button::ButtonMessage,
+        message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, Thickness, UiNode,
+    },
+    scene::{
+        base::BaseBuilder,
+        camera::Camera,
+        navmesh::{NavigationalMesh, NavigationalMeshBuilder},
+    },
+    utils::navmesh::Navmesh,
+};
+
+fn selected_edges(selection: &NavmeshSelection) -> Vec<TriangleEdge> {
+    selection
+        .entities()
+        .iter()
+        .filter_map(|entity| {
+            if let NavmeshEntity::Edge(edge) = *entity {
+                Some(edge)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+fn plan_selected_connections(
+    navmesh: &Navmesh,
+    selection: &NavmeshSelection,
+) -> EdgeConnectionPlan {
+    let positions = navmesh
+        .vertices()
+        .iter()
+        .map(|v| v.position)
+        .collect::<Vec<_>>();
+    plan_edge_connections(&positions, navmesh.triangles(), &selected_edges(selection))
+}
+
+fn connect_tooltip(plan: &EdgeConnectionPlan) -> String {
+    let mut tooltip = "Connect selected edges pairwise with two triangles per pair, the closest \
+        edges are paired first. Pairs, whose triangles would overlap other triangles, are skipped."
+        .to_owned();
+    if !plan.pairs.is_empty() || !plan.skipped.is_empty() {
+        tooltip += &format!(
+            "\n\n{} pair(s) of edges will be connected, {} will be skipped.",
+            plan.pairs.len(),
+            plan.skipped.len()
+        );
+    }
+    tooltip
+}
+
+/// Calculates a direction, that lies in the plane of the triangle, that owns the edge, is perpendicular
+/// to the edge and points away from the triangle. Camera side vector is used for isolated edges.
+fn extrusion_direction(
+    navmesh: &Navmesh,
+    edge: TriangleEdge,
+    camera_side: Vector3<f32>,
+) -> Vector3<f32> {
+    let vertices = navmesh.vertices();
+    let a = vertices[edge.a as usize].position;
+    let b = vertices[edge.b as usize].position;
+
+    let opposite = navmesh.triangles().iter().find_map(|triangle| {
+        if triangle.edges().contains(&edge) {
+            triangle
+                .indices()
+                .iter()
+                .find(|i| **i != edge.a && **i != edge.b)
+                .map(|i| vertices[*i as usize].position)
+        } else {
+            None
+        }
+    });
+
+    let direction = opposite.and_then(|c| {
+        let edge_vector = b - a;
+        let normal = edge_vector.cross(&(c - a));
+        let direction = normal.cross(&edge_vector).try_normalize(f32::EPSILON)?;
+        let to_opposite = c - (a + b).scale(0.5);
+        Some(if direction.dot(&to_opposite) > 0.0 {
+            -direction
+        } else {
+            direction
+        })
+    });
+
+    direction
+        .or_else(|| camera_side.try_normalize(f32::EPSILON))
+        .unwrap_or_else(Vector3::x)
+}
+
+pub struct TopologySection {
+    pub connect_edges: Handle<UiNode>,
+    // Tooltip, that is currently shown for the Connect button.
+    connect_tooltip: String,
+    bridge_edges: Handle<UiNode>,
+    add_triangle: Handle<UiNode>,
+    extrude_distance: Handle<UiNode>,
+    extrude: Handle<UiNode>,
+    toggle_triangles: Handle<UiNode>,
+    split: Handle<UiNode>,
+    distance: f32,
+}
+
+impl TopologySection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let connect_tooltip = connect_tooltip(&Default::default());
+
+        Self {
+            connect_edges: make_button(ctx, "Connect Edges", &connect_tooltip),
+            connect_tooltip,
+            bridge_edges: make_button(
+                ctx,
+                "Bridge",
+                "Fill the gap between two selected edges with a quad made of two triangles (or \
+                with a single triangle if the edges share a vertex).",
+            ),
+            add_triangle: make_button(
+                ctx,
+                "Add Triangle",
+                "Add a standalone triangle on the scene geometry in the center of the viewport. \
+                Ctrl+T adds it under the cursor.",
+            ),
+            extrude_distance: NumericUpDownBuilder::<f32>::new(
+                WidgetBuilder::new()
+                    .with_width(60.0)
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_tooltip(make_simple_tooltip(ctx, "Extrusion Distance")),
+            )
+            .with_min_value(0.0)
+            .with_value(1.0)
+            .build(ctx),
+            extrude: make_button(
+                ctx,
+                "Extrude",
+                "Extrude selected edge by the given distance. The edge is moved away from its \
+                triangle (or along the camera side vector if the edge has no triangles).",
+            ),
+            toggle_triangles: make_button(
+                ctx,
+                "Toggle Triangles",
+                "Enable or disable triangles, that have all their vertices selected. Paths never \
+                go through disabled triangles.",
+            ),
+            split: make_button(
+                ctx,
+                "Split",
+                "Move triangles, that have all their vertices selected, to a new navmesh.",
+            ),
+            distance: 1.0,
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![
+            self.connect_edges,
+            self.bridge_edges,
+            self.toggle_triangles,
+            self.split,
+            self.add_triangle,
+            self.extrude_distance,
+            self.extrude,
+        ]
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
+        if let Some(&NumericUpDownMessage::Value(value)) =
+            message.data::<NumericUpDownMessage<f32>>()
+        {
+            if message.destination() == self.extrude_distance
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.distance = value;
+            }
+        } else if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
+            if message.destination() == self.connect_edges {
+                self.connect_edges(editor_scene, engine, sender);
+            } else if message.destination() == self.bridge_edges {
+                if let Some(selection) = fetch_selection(&editor_scene.selection) {
+                    if let [a, b] = selected_edges(&selection).as_slice() {
+                        if selection.navmesh_node().is_some() {
+                            sender.do_scene_command(BridgeNavmeshEdgesCommand::new(
+                                selection.navmesh_node(),
+                                [*a, *b],
+                            ));
+                        }
+                    }
+                }
+            } else if message.destination() == self.extrude {
+                self.extrude(editor_scene, engine, sender);
+            } else if message.destination() == self.add_triangle {
+                let graph = &engine.scenes[editor_scene.scene].graph;
+                if let Some(camera) =
+                    graph.try_get_of_type::<Camera>(editor_scene.camera_controller.camera)
+                {
+                    // Ray through the center of the viewport.
+                    let ray = Ray::new(
+                        camera.global_position(),
+                        camera
+                            .look_vector()
+                            .try_normalize(f32::EPSILON)
+                            .unwrap_or_default()
+                            .scale(camera.projection().z_far()),
+                    );
+                    if let Some(command) = add_triangle_command(&ray, graph, editor_scene) {
+                        sender.do_scene_command(command);
+                    }
+                }
+            } else if message.destination() == self.toggle_triangles {
+                if let Some(selection) = fetch_selection(&editor_scene.selection) {
+                    if selection.navmesh_node().is_some() {
+                        sender.do_scene_command(ToggleNavmeshTrianglesCommand::new(
+                            selection.navmesh_node(),
+                            selection.unique_vertices().clone(),
+                        ));
+                    }
+                }
+            } else if message.destination() == self.split {
+                split_navmesh(editor_scene, engine, sender);
+            }
+        }
+    }
+
+    fn connect_edges(&self, editor_scene: &EditorScene, engine: &Engine, sender: &MessageSender) {
+        if let Some(selection) = fetch_selection(&editor_scene.selection) {
+            if let Some(navmesh) = engine.scenes[editor_scene.scene]
+                .graph
+                .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+            {
+                let plan = plan_selected_connections(navmesh.navmesh_ref(), &selection);
+
+                for [a, b] in plan.skipped.iter() {
+                    Log::warn(format!(
+                        "Edges {}-{} and {}-{} were not connected, because new triangles \
+                        would be degenerate or would overlap other triangles.",
+                        a.a, a.b, b.a, b.b
+                    ));
+                }
+
+                if !plan.pairs.is_empty() {
+                    let commands = plan
+                        .pairs
+                        .into_iter()
+                        .map(|pair| {
+                            SceneCommand::new(ConnectNavmeshEdgesCommand::new(
+                                selection.navmesh_node(),
+                                pair,
+                            ))
+                        })
+                        .collect::<Vec<_>>();
+                    sender.do_scene_command(
+                        CommandGroup::from(commands).with_custom_name("Connect Navmesh Edges"),
+                    );
+                }
+            }
+        }
+    }
+
+    fn extrude(&self, editor_scene: &EditorScene, engine: &Engine, sender: &MessageSender) {
+        if let Some(selection) = fetch_selection(&editor_scene.selection) {
+            if let (true, Some(&NavmeshEntity::Edge(edge))) =
+                (selection.is_single_selection(), selection.first())
+            {
+                let graph = &engine.scenes[editor_scene.scene].graph;
+                if let Some(navmesh) = graph
+                    .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                    .map(|n| n.navmesh_ref())
+                {
+                    let camera_side = graph
+                        .try_get(editor_scene.camera_controller.camera)
+                        .map(|c| c.side_vector())
+                        .unwrap_or_else(Vector3::x);
+                    let offset =
+                        extrusion_direction(navmesh, edge, camera_side).scale(self.distance);
+
+                    let mut begin = navmesh.vertices()[edge.a as usize].clone();
+                    let mut end = navmesh.vertices()[edge.b as usize].clone();
+                    begin.position += offset;
+                    end.position += offset;
+
+                    sender.do_scene_command(AddNavmeshEdgeCommand::new(
+                        selection.navmesh_node(),
+                        (begin, end),
+                        edge,
+                        true,
+                    ));
+                }
+            }
+        }
+    }
+
+    pub fn sync_to_model(
+        &mut self,
+        engine: &mut Engine,
+        editor_scene: &EditorScene,
+        selection: PanelSelection,
+    ) {
+        let ui = &mut engine.user_interface;
+
+        ui.send_message(WidgetMessage::enabled(
+            self.connect_edges,
+            MessageDirection::ToWidget,
+            selection.navmesh_selected && selection.edges >= 2,
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.bridge_edges,
+            MessageDirection::ToWidget,
+            selection.navmesh_selected && selection.edges == 2,
+        ));
+
+        let plan = fetch_selection(&editor_scene.selection)
+            .filter(|_| selection.edges >= 2)
+            .and_then(|selection| {
+                engine.scenes[editor_scene.scene]
+                    .graph
+                    .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                    .map(|navmesh| plan_selected_connections(navmesh.navmesh_ref(), &selection))
+            })
+            .unwrap_or_default();
+        let tooltip = connect_tooltip(&plan);
+        if tooltip != self.connect_tooltip {
+            let handle = make_simple_tooltip(&mut ui.build_ctx(), &tooltip);
+            ui.send_message(WidgetMessage::tooltip(
+                self.connect_edges,
+                MessageDirection::ToWidget,
+                Some(handle),
+            ));
+            self.connect_tooltip = tooltip;
+        }
+
+        let single_edge_selected =
+            selection.navmesh_selected && selection.edges == 1 && selection.vertices == 2;
+        for widget in [self.extrude, self.extrude_distance] {
+            ui.send_message(WidgetMessage::enabled(
+                widget,
+                MessageDirection::ToWidget,
+                single_edge_selected,
+            ));
+        }
+
+        for widget in [self.toggle_triangles, self.split] {
+            ui.send_message(WidgetMessage::enabled(
+                widget,
+                MessageDirection::ToWidget,
+                selection.navmesh_selected && selection.vertices >= 3,
+            ));
+        }
+    }
+}
+
+/// Moves fully selected triangles to a new navmesh, which is added next to the selected one.
+fn split_navmesh(editor_scene: &EditorScene, engine: &mut Engine, sender: &MessageSender) {
+    let selection = match fetch_selection(&editor_scene.selection) {
+        Some(selection) => selection,
+        None => return,
+    };
+    let navmesh_node = selection.navmesh_node();
+
+    let graph = &mut engine.scenes[editor_scene.scene].graph;
+    let (split, navmesh) = match graph.try_get_of_type::<NavigationalMesh>(navmesh_node) {
+        Some(node) => {
+            let navmesh = node.navmesh_ref();
+            let split = plan_navmesh_split(navmesh.triangles(), &selection.unique_vertices());
+            if split.triangles.is_empty() {
+                Log::warn(
+                    "There are no triangles with all three vertices selected, nothing to split."
+                        .to_owned(),
+                );
+                return;
+            }
+
+            let positions = split
+                .new_vertices
+                .iter()
+                .map(|v| navmesh.vertices()[*v].position)
+                .collect::<Vec<_>>();
+            let triangles = split
+                .new_triangles
+                .iter()
+                .map(|(_, triangle)| triangle.clone())
+                .collect::<Vec<_>>();
+            let mut new_navmesh = Navmesh::new(&triangles, &positions);
+            for (index, (source, _)) in split.new_triangles.iter().enumerate() {
+                new_navmesh.set_triangle_enabled(index, navmesh.is_triangle_enabled(*source));
+            }
+
+            (split, new_navmesh)
+        }
+        None => return,
+    };
+
+    let base = &graph[navmesh_node];
+    let parent = base.parent();
+    let new_node = NavigationalMeshBuilder::new(
+        BaseBuilder::new()
+            .with_name(format!("{} Split", base.name()))
+            .with_local_transform(base.local_transform().clone()),
+    )
+    .with_navmesh(navmesh)
+    .build(graph);
+    graph.link_nodes(new_node, parent);
+
+    // Only commands are allowed to modify the scene, so the new node is taken out of the graph
+    // right away and is put back by the command.
+    let sub_graph = graph.take_reserve_sub_graph(new_node);
+
+    let mut commands = split
+        .triangles
+        .iter()
+        .map(|t| SceneCommand::new(DeleteNavmeshTriangleCommand::new(navmesh_node, *t)))
+        .collect::<Vec<_>>();
+    commands.extend(
+        split
+            .vertices
+            .iter()
+            .map(|v| SceneCommand::new(DeleteNavmeshVertexCommand::new(navmesh_node, *v))),
+    );
+    commands.push(SceneCommand::new(AddModelCommand::new(sub_graph)));
+    commands.push(SceneCommand::new(ChangeSelectionCommand::new(
+        Selection::Navmesh(NavmeshSelection::empty(new_node)),
+        editor_scene.selection.clone(),
+    )));
+
+    sender.do_scene_command(CommandGroup::from(commands).with_custom_name("Split Navmesh"));
+}
@@ -0,0 +1,357 @@
+//! Validation of the navmesh: a window with the list of issues of the navmesh, that allows to
+//! inspect each of them and to fix some of them automatically.
+
+use crate::{
+    gui::make_dropdown_list_option,
+    interaction::navmesh::{
+        fetch_selection,
+        panel::{make_button, PanelSelection},
+        selection::NavmeshSelection,
+        validation::{
+            plan_navmesh_fix, validate_navmesh, NavmeshIssue, NavmeshOverlaps,
+            NAVMESH_VALIDATION_EPSILON,
+        },
+    },
+    message::{Message, MessageSender},
+    scene::{
+        commands::{
+            navmesh::{DeleteNavmeshTriangleCommand, DeleteNavmeshVertexCommand},
+            ChangeSelectionCommand, CommandGroup, SceneCommand,
+        },
+        EditorScene, Selection,
+    },
+};
+use fyrox::{
+    core::{
+        algebra::Vector3,
+        math::{aabb::AxisAlignedBoundingBox, TriangleDefinition},
+        pool::Handle,
+    },
+    engine::Engine,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        text::{TextBuilder, TextMessage},
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Thickness, UiNode, UserInterface,
+    },
+    scene::{graph::Graph, navmesh::NavigationalMesh, node::Node},
+};
+use std::{cell::RefCell, rc::Rc};
+
+pub struct ValidationSection {
+    validate: Handle<UiNode>,
+    window: NavmeshValidationWindow,
+    pub overlaps: Rc<RefCell<NavmeshOverlaps>>,
+}
+
+impl ValidationSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let overlaps = Rc::new(RefCell::new(NavmeshOverlaps::default()));
+
+        Self {
+            validate: make_button(
+                ctx,
+                "Validate",
+                "Check selected navmesh for degenerate triangles, duplicated or unused vertices \
+                and edges shared by more than two triangles.",
+            ),
+            window: NavmeshValidationWindow::new(ctx, overlaps.clone()),
+            overlaps,
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![self.validate]
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &mut Engine,
+        sender: &MessageSender,
+    ) {
+        self.window
+            .handle_ui_message(message, editor_scene, engine, sender);
+
+        if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
+            if message.destination() == self.validate {
+                if let Some(selection) = fetch_selection(&editor_scene.selection) {
+                    self.window.open(
+                        &mut engine.user_interface,
+                        &engine.scenes[editor_scene.scene].graph,
+                        selection.navmesh_node(),
+                    );
+                }
+            }
+        }
+    }
+
+    pub fn sync_to_model(
+        &mut self,
+        ui: &mut UserInterface,
+        graph: &Graph,
+        selection: PanelSelection,
+    ) {
+        ui.send_message(WidgetMessage::enabled(
+            self.validate,
+            MessageDirection::ToWidget,
+            selection.navmesh_selected,
+        ));
+
+        self.window.sync_to_model(ui, graph);
+    }
+}
+
+/// Shows issues of a navmesh, allows to inspect each of them and to fix some of them automatically.
+struct NavmeshValidationWindow {
+    window: Handle<UiNode>,
+    summary: Handle<UiNode>,
+    list: Handle<UiNode>,
+    fix_all: Handle<UiNode>,
+    navmesh_node: Handle<Node>,
+    issues: Vec<NavmeshIssue>,
+    is_open: bool,
+    overlaps: Rc<RefCell<NavmeshOverlaps>>,
+}
+
+fn navmesh_geometry(
+    graph: &Graph,
+    navmesh_node: Handle<Node>,
+) -> Option<(Vec<Vector3<f32>>, Vec<TriangleDefinition>)> {
+    graph
+        .try_get_of_type::<NavigationalMesh>(navmesh_node)
+        .map(|navmesh| {
+            let navmesh = navmesh.navmesh_ref();
+            (
+                navmesh.vertices().iter().map(|v| v.position).collect(),
+                navmesh.triangles().to_vec(),
+            )
+        })
+}
+
+impl NavmeshValidationWindow {
+    fn new(ctx: &mut BuildContext, overlaps: Rc<RefCell<NavmeshOverlaps>>) -> Self {
+        let summary;
+        let list;
+        let fix_all;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(350.0).with_height(300.0))
+            .open(false)
+            .with_title(WindowTitle::text("Navmesh Validation"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            summary = TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .build(ctx);
+                            summary
+                        })
+                        .with_child({
+                            list = ListViewBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .build(ctx);
+                            list
+                        })
+                        .with_child({
+                            fix_all = ButtonBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_width(100.0)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_margin(Thickness::uniform(1.0))
+                                    .with_tooltip(make_simple_tooltip(
+                                        ctx,
+                                        "Remove degenerate triangles and vertices, that are not \
+                                        used by any triangle.",
+                                    )),
+                            )
+                            .with_text("Fix All")
+                            .build(ctx);
+                            fix_all
+                        }),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_row(Row::strict(24.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            summary,
+            list,
+            fix_all,
+            navmesh_node: Default::default(),
+            issues: Default::default(),
+            is_open: false,
+            overlaps,
+        }
+    }
+
+    fn open(&mut self, ui: &mut UserInterface, graph: &Graph, navmesh_node: Handle<Node>) {
+        self.navmesh_node = navmesh_node;
+        self.is_open = true;
+        // Force the list to be rebuilt.
+        self.issues.clear();
+        self.refresh(ui, graph, true);
+
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    /// Validates the navmesh again, since it could be changed by some command.
+    fn sync_to_model(&mut self, ui: &mut UserInterface, graph: &Graph) {
+        if self.is_open {
+            self.refresh(ui, graph, false);
+        }
+    }
+
+    fn refresh(&mut self, ui: &mut UserInterface, graph: &Graph, force: bool) {
+        let (vertices, triangles) = navmesh_geometry(graph, self.navmesh_node).unwrap_or_default();
+        let issues = validate_navmesh(&vertices, &triangles, NAVMESH_VALIDATION_EPSILON);
+
+        let overlaps = issues
+            .iter()
+            .filter_map(|issue| {
+                if let NavmeshIssue::OverlappingTriangles(a, b) = *issue {
+                    Some((a, b))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        self.overlaps.borrow_mut().set(self.navmesh_node, &overlaps);
+
+        ui.send_message(WidgetMessage::enabled(
+            self.fix_all,
+            MessageDirection::ToWidget,
+            !plan_navmesh_fix(&vertices, &triangles, NAVMESH_VALIDATION_EPSILON).is_empty(),
+        ));
+
+        // Rebuild the list only if something has changed, otherwise selected row will be lost.
+        if !force && issues == self.issues {
+            return;
+        }
+
+        ui.send_message(TextMessage::text(
+            self.summary,
+            MessageDirection::ToWidget,
+            if issues.is_empty() {
+                "No issues found.".to_owned()
+            } else {
+                format!(
+                    "{} issue(s) found. Click an issue to select it in the scene.",
+                    issues.len()
+                )
+            },
+        ));
+
+        let items = {
+            let ctx = &mut ui.build_ctx();
+            issues
+                .iter()
+                .map(|issue| make_dropdown_list_option(ctx, &issue.to_string()))
+                .collect::<Vec<_>>()
+        };
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+
+        self.issues = issues;
+    }
+
+    fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &MessageSender,
+    ) {
+        let graph = &engine.scenes[editor_scene.scene].graph;
+
+        if let Some(ListViewMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let (Some(issue), Some((vertices, triangles))) = (
+                    self.issues.get(*index),
+                    navmesh_geometry(graph, self.navmesh_node),
+                ) {
+                    sender.do_scene_command(ChangeSelectionCommand::new(
+                        Selection::Navmesh(NavmeshSelection::new(
+                            self.navmesh_node,
+                            issue.entities(&triangles),
+                        )),
+                        editor_scene.selection.clone(),
+                    ));
+
+                    let points = issue
+                        .vertices(&triangles)
+                        .into_iter()
+                        .filter_map(|i| vertices.get(i).cloned())
+                        .collect::<Vec<_>>();
+                    if !points.is_empty() {
+                        let mut bounds = AxisAlignedBoundingBox::from_points(&points);
+                        // Show some surroundings of the issue.
+                        bounds.inflate(Vector3::repeat(1.0));
+                        sender.send(Message::FocusBounds(bounds));
+                    }
+                }
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.fix_all {
+                if let Some((vertices, triangles)) = navmesh_geometry(graph, self.navmesh_node) {
+                    let fix = plan_navmesh_fix(&vertices, &triangles, NAVMESH_VALIDATION_EPSILON);
+                    if !fix.is_empty() {
+                        // Selected entities may be removed, so select the navmesh itself.
+                        let mut commands = vec![SceneCommand::new(ChangeSelectionCommand::new(
+                            Selection::Navmesh(NavmeshSelection::empty(self.navmesh_node)),
+                            editor_scene.selection.clone(),
+                        ))];
+                        commands.extend(fix.triangles.iter().map(|t| {
+                            SceneCommand::new(DeleteNavmeshTriangleCommand::new(
+                                self.navmesh_node,
+                                *t,
+                            ))
+                        }));
+                        commands.extend(fix.vertices.iter().map(|v| {
+                            SceneCommand::new(DeleteNavmeshVertexCommand::new(
+                                self.navmesh_node,
+                                *v,
+                            ))
+                        }));
+                        sender.do_scene_command(
+                            CommandGroup::from(commands).with_custom_name("Fix Navmesh"),
+                        );
+                    }
+                }
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window
+                && message.direction() == MessageDirection::ToWidget
+            {
+                self.is_open = false;
+                self.overlaps.borrow_mut().clear();
+            }
+        }
+    }
+}
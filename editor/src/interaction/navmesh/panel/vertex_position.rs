@@ -0,0 +1,171 @@
+//! Editor of the position of the selected vertices.
+
+use crate::{
+    interaction::navmesh::{fetch_selection, place_vertices, vertices_centroid},
+    message::MessageSender,
+    scene::{
+        commands::{navmesh::MoveNavmeshVertexCommand, CommandGroup, SceneCommand},
+        EditorScene,
+    },
+};
+use fyrox::{
+    core::{algebra::Vector3, log::Log, pool::Handle},
+    engine::Engine,
+    gui::{
+        message::{MessageDirection, UiMessage},
+        utils::make_simple_tooltip,
+        vec::{Vec3EditorBuilder, Vec3EditorMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, Thickness, UiNode,
+    },
+    scene::navmesh::NavigationalMesh,
+};
+
+pub struct VertexPositionSection {
+    editor: Handle<UiNode>,
+    // Position, that is shown in the vertex position editor, `None` if nothing is selected.
+    shown_position: Option<Vector3<f32>>,
+}
+
+impl VertexPositionSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        Self {
+            editor: Vec3EditorBuilder::<f32>::new(
+                WidgetBuilder::new()
+                    .with_width(200.0)
+                    .with_enabled(false)
+                    .with_margin(Thickness::uniform(1.0))
+                    .with_tooltip(make_simple_tooltip(
+                        ctx,
+                        "Position of the selected vertex. If there are multiple selected \
+                        vertices, it is the position of their center, and the vertices are moved \
+                        together.",
+                    )),
+            )
+            .build(ctx),
+            shown_position: None,
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![self.editor]
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        panel: Handle<UiNode>,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &MessageSender,
+    ) {
+        if let Some(&Vec3EditorMessage::Value(value)) = message.data::<Vec3EditorMessage<f32>>() {
+            if message.destination() == self.editor
+                && message.direction() == MessageDirection::FromWidget
+                // The editor sends back every value, that was set from the code.
+                && Some(value) != self.shown_position
+            {
+                self.set_position(value, panel, editor_scene, engine, sender);
+            }
+        }
+    }
+
+    /// Moves selected vertices, so their center is at the given position.
+    fn set_position(
+        &mut self,
+        position: Vector3<f32>,
+        panel: Handle<UiNode>,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &MessageSender,
+    ) {
+        let ui = &engine.user_interface;
+
+        // Vertices must not be moved under the gizmo, while they're dragged in the scene viewer.
+        let captured = ui.captured_node();
+        let dragging =
+            captured.is_some() && captured != panel && !ui.is_node_child_of(captured, panel);
+
+        let selection = fetch_selection(&editor_scene.selection);
+        let navmesh = selection.as_ref().and_then(|selection| {
+            engine.scenes[editor_scene.scene]
+                .graph
+                .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                .map(|n| n.navmesh_ref())
+        });
+
+        match (selection, navmesh) {
+            (Some(selection), Some(navmesh))
+                if !dragging && position.iter().all(|c| c.is_finite()) =>
+            {
+                let commands =
+                    place_vertices(navmesh.vertices(), &selection.unique_vertices(), position)
+                        .into_iter()
+                        .map(|(vertex, old_position, new_position)| {
+                            SceneCommand::new(MoveNavmeshVertexCommand::new(
+                                selection.navmesh_node(),
+                                vertex,
+                                old_position,
+                                new_position,
+                            ))
+                        })
+                        .collect::<Vec<_>>();
+
+                if !commands.is_empty() {
+                    sender.do_scene_command(
+                        CommandGroup::from(commands)
+                            .with_custom_name("Set Navmesh Vertex Position"),
+                    );
+                }
+            }
+            _ => {
+                if !position.iter().all(|c| c.is_finite()) {
+                    Log::err(format!(
+                        "Invalid navmesh vertex position {:?}, every coordinate must be a finite number.",
+                        position
+                    ));
+                }
+
+                // Bring back the actual position.
+                if let Some(shown) = self.shown_position {
+                    ui.send_message(Vec3EditorMessage::value(
+                        self.editor,
+                        MessageDirection::ToWidget,
+                        shown,
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Positions are checked every frame, so the editor follows vertices while they're dragged.
+    pub fn update(&mut self, editor_scene: &EditorScene, engine: &Engine) {
+        let position = fetch_selection(&editor_scene.selection).and_then(|selection| {
+            engine.scenes[editor_scene.scene]
+                .graph
+                .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                .and_then(|navmesh| {
+                    vertices_centroid(
+                        navmesh.navmesh_ref().vertices(),
+                        &selection.unique_vertices(),
+                    )
+                })
+        });
+        if position != self.shown_position {
+            let ui = &engine.user_interface;
+            if let Some(position) = position {
+                ui.send_message(Vec3EditorMessage::value(
+                    self.editor,
+                    MessageDirection::ToWidget,
+                    position,
+                ));
+            }
+            ui.send_message(WidgetMessage::enabled(
+                self.editor,
+                MessageDirection::ToWidget,
+                position.is_some(),
+            ));
+            self.shown_position = position;
+        }
+    }
+}
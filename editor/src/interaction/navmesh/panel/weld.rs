@@ -0,0 +1,113 @@
+//! Welding of navmesh vertices, that are close to each other, and removal of vertices, that are
+//! left without triangles.
+
+use crate::{
+    interaction::navmesh::{
+        fetch_selection,
+        panel::{make_button, PanelSelection},
+    },
+    message::MessageSender,
+    scene::{
+        commands::navmesh::{
+            orphan_vertices_remap, MergeNavmeshVerticesCommand, RemoveOrphanNavmeshVerticesCommand,
+        },
+        EditorScene,
+    },
+    settings::Settings,
+};
+use fyrox::{
+    core::pool::Handle,
+    engine::Engine,
+    gui::{
+        button::ButtonMessage,
+        message::{MessageDirection, UiMessage},
+        widget::WidgetMessage,
+        BuildContext, UiNode, UserInterface,
+    },
+    scene::navmesh::NavigationalMesh,
+};
+
+pub struct WeldSection {
+    merge_vertices: Handle<UiNode>,
+    remove_orphans: Handle<UiNode>,
+}
+
+impl WeldSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        Self {
+            merge_vertices: make_button(
+                ctx,
+                "Merge",
+                "Merge selected vertices (or every vertex of the navmesh, if nothing is \
+                selected), that are closer to each other than the merge threshold from the \
+                settings.",
+            ),
+            remove_orphans: make_button(
+                ctx,
+                "Remove Unused Vertices",
+                "Remove vertices, that are not used by any triangle. Such vertices could be left \
+                after deletion of triangles, they are useless for path finding, but still could \
+                be picked.",
+            ),
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![self.merge_vertices, self.remove_orphans]
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        settings: &Settings,
+        sender: &MessageSender,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
+            if message.destination() == self.merge_vertices {
+                if let Some(selection) = fetch_selection(&editor_scene.selection) {
+                    if selection.navmesh_node().is_some() {
+                        let vertices = selection.unique_vertices().clone();
+                        sender.do_scene_command(MergeNavmeshVerticesCommand::new(
+                            selection.navmesh_node(),
+                            if vertices.is_empty() {
+                                None
+                            } else {
+                                Some(vertices)
+                            },
+                            settings.navmesh.merge_threshold,
+                        ));
+                    }
+                }
+            } else if message.destination() == self.remove_orphans {
+                if let Some(selection) = fetch_selection(&editor_scene.selection) {
+                    if let Some(navmesh) = engine.scenes[editor_scene.scene]
+                        .graph
+                        .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+                        .map(|n| n.navmesh_ref())
+                    {
+                        // Don't put a command, that does nothing, into the history.
+                        if orphan_vertices_remap(navmesh.vertices().len(), navmesh.triangles())
+                            .contains(&None)
+                        {
+                            sender.do_scene_command(RemoveOrphanNavmeshVerticesCommand::new(
+                                selection.navmesh_node(),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn sync_to_model(&self, ui: &UserInterface, selection: PanelSelection) {
+        for widget in [self.merge_vertices, self.remove_orphans] {
+            ui.send_message(WidgetMessage::enabled(
+                widget,
+                MessageDirection::ToWidget,
+                selection.navmesh_selected,
+            ));
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Flipping of winding of navmesh triangles, either of the selected ones or of the ones, that are
+//! wound differently from their neighbours.
+
+use crate::{
+    interaction::navmesh::{
+        fetch_selection,
+        panel::{make_button, PanelSelection},
+    },
+    message::MessageSender,
+    scene::{
+        commands::navmesh::{inconsistent_triangles, FlipNavmeshTrianglesCommand},
+        EditorScene,
+    },
+};
+use fyrox::{
+    core::{log::Log, pool::Handle},
+    engine::Engine,
+    gui::{
+        button::ButtonMessage,
+        message::{MessageDirection, UiMessage},
+        widget::WidgetMessage,
+        BuildContext, UiNode, UserInterface,
+    },
+    scene::navmesh::NavigationalMesh,
+};
+use std::collections::BTreeSet;
+
+pub struct WindingSection {
+    flip_winding: Handle<UiNode>,
+    fix_winding: Handle<UiNode>,
+}
+
+impl WindingSection {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        Self {
+            flip_winding: make_button(
+                ctx,
+                "Flip Winding",
+                "Flip winding of triangles, that have all their vertices selected (or of every \
+                triangle, if nothing is selected).",
+            ),
+            fix_winding: make_button(
+                ctx,
+                "Make Winding Consistent",
+                "Make winding of triangles, that have all their vertices selected (or of every \
+                triangle, if nothing is selected), the same as winding of their neighbours. The \
+                first triangle of every connected group is used as the reference.",
+            ),
+        }
+    }
+
+    pub fn widgets(&self) -> Vec<Handle<UiNode>> {
+        vec![self.flip_winding, self.fix_winding]
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &MessageSender,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
+            if message.destination() == self.flip_winding
+                || message.destination() == self.fix_winding
+            {
+                flip_winding(
+                    message.destination() == self.fix_winding,
+                    editor_scene,
+                    engine,
+                    sender,
+                );
+            }
+        }
+    }
+
+    pub fn sync_to_model(&self, ui: &UserInterface, selection: PanelSelection) {
+        for widget in [self.flip_winding, self.fix_winding] {
+            ui.send_message(WidgetMessage::enabled(
+                widget,
+                MessageDirection::ToWidget,
+                selection.navmesh_selected,
+            ));
+        }
+    }
+}
+
+/// Flips winding of selected triangles (or of every triangle, if nothing is selected). If
+/// `only_inconsistent` is set, only the triangles with the winding, that differs from their
+/// neighbours, are flipped.
+fn flip_winding(
+    only_inconsistent: bool,
+    editor_scene: &EditorScene,
+    engine: &Engine,
+    sender: &MessageSender,
+) {
+    let selection = match fetch_selection(&editor_scene.selection) {
+        Some(selection) => selection,
+        None => return,
+    };
+
+    let graph = &engine.scenes[editor_scene.scene].graph;
+    let navmesh = match graph
+        .try_get_of_type::<NavigationalMesh>(selection.navmesh_node())
+        .map(|n| n.navmesh_ref())
+    {
+        Some(navmesh) => navmesh,
+        None => return,
+    };
+
+    let subset = if selection.is_empty() {
+        None
+    } else {
+        let vertices = selection.unique_vertices();
+        Some(
+            navmesh
+                .triangles()
+                .iter()
+                .enumerate()
+                .filter(|(_, triangle)| {
+                    triangle
+                        .indices()
+                        .iter()
+                        .all(|i| vertices.contains(&(*i as usize)))
+                })
+                .map(|(index, _)| index)
+                .collect::<BTreeSet<_>>(),
+        )
+    };
+
+    let triangles = if only_inconsistent {
+        inconsistent_triangles(navmesh.triangles(), subset.as_ref())
+    } else {
+        match subset {
+            Some(subset) => subset.into_iter().collect(),
+            None => (0..navmesh.triangles().len()).collect(),
+        }
+    };
+
+    if triangles.is_empty() {
+        if only_inconsistent {
+            Log::info("Winding of the navmesh triangles is already consistent.");
+        }
+    } else {
+        sender.do_scene_command(FlipNavmeshTrianglesCommand::new(
+            selection.navmesh_node(),
+            triangles,
+        ));
+    }
+}
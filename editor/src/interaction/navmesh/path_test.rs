@@ -0,0 +1,283 @@
+//! Path preview for navmesh edit mode. It runs A* search between two markers placed on the navmesh,
+//! so the result of editing could be checked right away without running the game.
+
+use fyrox::{
+    core::{
+        algebra::Vector3,
+        math::{self, ray::Ray, TriangleDefinition},
+        pool::Handle,
+    },
+    scene::node::Node,
+    utils::{astar::PathKind, navmesh::Navmesh},
+};
+use std::fmt::{Display, Formatter};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PathMarker {
+    Start,
+    End,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PathTestStatus {
+    /// Waiting for the start point.
+    NoStart,
+    /// Waiting for the end point.
+    NoEnd,
+    /// Path was found, its length is stored.
+    Found(f32),
+    /// There's no path between the markers.
+    NotFound,
+}
+
+impl Display for PathTestStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathTestStatus::NoStart => write!(f, "Ctrl+Click on the navmesh to set start point."),
+            PathTestStatus::NoEnd => write!(f, "Ctrl+Click on the navmesh to set end point."),
+            PathTestStatus::Found(length) => write!(f, "Path found, length: {:.2}", length),
+            PathTestStatus::NotFound => write!(f, "No path!"),
+        }
+    }
+}
+
+// Everything the path depends on, used to recompute the path only when something has changed.
+#[derive(PartialEq)]
+struct PathTestInput {
+    start: Vector3<f32>,
+    end: Vector3<f32>,
+    vertices: Vec<Vector3<f32>>,
+    triangles: Vec<TriangleDefinition>,
+    enabled: Vec<bool>,
+}
+
+impl PathTestInput {
+    fn new(start: Vector3<f32>, end: Vector3<f32>, navmesh: &Navmesh) -> Self {
+        Self {
+            start,
+            end,
+            vertices: navmesh.vertices().iter().map(|v| v.position).collect(),
+            triangles: navmesh.triangles().to_vec(),
+            enabled: (0..navmesh.triangles().len())
+                .map(|i| navmesh.is_triangle_enabled(i))
+                .collect(),
+        }
+    }
+}
+
+/// State of the path preview tool, it is shared between the navmesh panel and the navmesh edit
+/// mode.
+pub struct NavmeshPathTest {
+    enabled: bool,
+    navmesh_node: Handle<Node>,
+    start: Option<Vector3<f32>>,
+    end: Option<Vector3<f32>>,
+    path: Vec<Vector3<f32>>,
+    status: PathTestStatus,
+    input: Option<PathTestInput>,
+}
+
+impl Default for NavmeshPathTest {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            navmesh_node: Default::default(),
+            start: None,
+            end: None,
+            path: Default::default(),
+            status: PathTestStatus::NoStart,
+            input: None,
+        }
+    }
+}
+
+impl NavmeshPathTest {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Enables or disables the tool, markers are removed in both cases.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        *self = Self {
+            enabled,
+            ..Default::default()
+        };
+    }
+
+    pub fn status(&self) -> PathTestStatus {
+        self.status
+    }
+
+    /// Returns the polyline from the start marker to the end marker. It is empty if there's no
+    /// path.
+    pub fn path(&self) -> &[Vector3<f32>] {
+        &self.path
+    }
+
+    pub fn marker(&self, marker: PathMarker) -> Option<Vector3<f32>> {
+        match marker {
+            PathMarker::Start => self.start,
+            PathMarker::End => self.end,
+        }
+    }
+
+    /// Places a marker at the given point. The first call sets the start point, the second one sets
+    /// the end point, the next one starts over. Markers are reset if the navmesh was changed.
+    pub fn place_marker(&mut self, navmesh_node: Handle<Node>, point: Vector3<f32>) {
+        if navmesh_node != self.navmesh_node {
+            self.set_enabled(self.enabled);
+            self.navmesh_node = navmesh_node;
+        }
+
+        if self.start.is_none() || self.end.is_some() {
+            self.start = Some(point);
+            self.end = None;
+        } else {
+            self.end = Some(point);
+        }
+    }
+
+    /// Moves existing marker to the given point.
+    pub fn move_marker(&mut self, marker: PathMarker, point: Vector3<f32>) {
+        match marker {
+            PathMarker::Start => self.start = Some(point),
+            PathMarker::End => self.end = Some(point),
+        }
+    }
+
+    /// Recomputes the path if the markers or the navmesh were changed since the last call.
+    pub fn update(&mut self, navmesh_node: Handle<Node>, navmesh: &mut Navmesh) {
+        if navmesh_node != self.navmesh_node {
+            // Markers belong to some other navmesh.
+            self.set_enabled(self.enabled);
+            self.navmesh_node = navmesh_node;
+        }
+
+        let (start, end) = match (self.start, self.end) {
+            (None, _) => {
+                self.status = PathTestStatus::NoStart;
+                self.path.clear();
+                return;
+            }
+            (Some(_), None) => {
+                self.status = PathTestStatus::NoEnd;
+                self.path.clear();
+                return;
+            }
+            (Some(start), Some(end)) => (start, end),
+        };
+
+        let input = PathTestInput::new(start, end, navmesh);
+        if self.input.as_ref() == Some(&input) {
+            return;
+        }
+        self.input = Some(input);
+
+        match find_path(navmesh, start, end) {
+            Some(path) => {
+                self.status = PathTestStatus::Found(
+                    path.windows(2)
+                        .map(|segment| (segment[1] - segment[0]).norm())
+                        .sum(),
+                );
+                self.path = path;
+            }
+            None => {
+                self.status = PathTestStatus::NotFound;
+                self.path.clear();
+            }
+        }
+    }
+}
+
+/// Returns the closest intersection point of the ray with the navmesh triangles.
+pub fn ray_cast_navmesh(navmesh: &Navmesh, ray: &Ray) -> Option<Vector3<f32>> {
+    // Octree of the navmesh is not updated while editing, so every triangle has to be checked.
+    navmesh
+        .triangles()
+        .iter()
+        .filter_map(|triangle| {
+            let [a, b, c] = triangle.0.map(|i| navmesh.vertices()[i as usize].position);
+            ray.triangle_intersection_point(&[a, b, c])
+        })
+        .min_by(|a, b| {
+            a.metric_distance(&ray.origin)
+                .partial_cmp(&b.metric_distance(&ray.origin))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+}
+
+/// Searches a path between the closest vertices of the given points. Returns `None` if the points
+/// are not connected.
+fn find_path(
+    navmesh: &mut Navmesh,
+    start: Vector3<f32>,
+    end: Vector3<f32>,
+) -> Option<Vec<Vector3<f32>>> {
+    let from = math::get_closest_point(navmesh.vertices(), start)?;
+    let to = math::get_closest_point(navmesh.vertices(), end)?;
+
+    let mut vertices = Vec::new();
+    match navmesh.build_path(from, to, &mut vertices) {
+        Ok(PathKind::Full) => {
+            // Path is built from the end to the beginning.
+            vertices.reverse();
+
+            let mut path = Vec::with_capacity(vertices.len() + 2);
+            path.push(start);
+            path.extend(vertices);
+            path.push(end);
+            Some(path)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_marker_placement() {
+        let navmesh = Handle::new(1, 1);
+        let mut path_test = NavmeshPathTest::default();
+        path_test.set_enabled(true);
+
+        let a = Vector3::new(1.0, 0.0, 0.0);
+        let b = Vector3::new(2.0, 0.0, 0.0);
+        let c = Vector3::new(3.0, 0.0, 0.0);
+
+        path_test.place_marker(navmesh, a);
+        assert_eq!(path_test.marker(PathMarker::Start), Some(a));
+        assert_eq!(path_test.marker(PathMarker::End), None);
+
+        path_test.place_marker(navmesh, b);
+        assert_eq!(path_test.marker(PathMarker::Start), Some(a));
+        assert_eq!(path_test.marker(PathMarker::End), Some(b));
+
+        // Third click starts over.
+        path_test.place_marker(navmesh, c);
+        assert_eq!(path_test.marker(PathMarker::Start), Some(c));
+        assert_eq!(path_test.marker(PathMarker::End), None);
+    }
+
+    #[test]
+    fn test_markers_are_reset() {
+        let mut path_test = NavmeshPathTest::default();
+        path_test.set_enabled(true);
+
+        path_test.place_marker(Handle::new(1, 1), Vector3::new(1.0, 0.0, 0.0));
+        path_test.place_marker(Handle::new(1, 1), Vector3::new(2.0, 0.0, 0.0));
+
+        // Other navmesh.
+        let c = Vector3::new(3.0, 0.0, 0.0);
+        path_test.place_marker(Handle::new(2, 1), c);
+        assert_eq!(path_test.marker(PathMarker::Start), Some(c));
+        assert_eq!(path_test.marker(PathMarker::End), None);
+
+        path_test.set_enabled(false);
+        assert!(!path_test.is_enabled());
+        assert_eq!(path_test.marker(PathMarker::Start), None);
+        assert_eq!(path_test.status(), PathTestStatus::NoStart);
+    }
+}
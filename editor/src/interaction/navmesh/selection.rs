@@ -1,16 +1,25 @@
 use fyrox::{
-    core::{math::TriangleEdge, pool::Handle},
+    core::{
+        algebra::Vector3,
+        math::{
+            ray::{CylinderKind, IntersectionResult, Ray},
+            TriangleDefinition, TriangleEdge,
+        },
+        pool::Handle,
+    },
     scene::node::Node,
 };
 use std::{
     cell::{Cell, Ref, RefCell},
-    collections::BTreeSet,
+    collections::{BTreeSet, HashMap, HashSet},
 };
 
 #[derive(PartialEq, Clone, Debug, Eq)]
 pub enum NavmeshEntity {
     Vertex(usize),
     Edge(TriangleEdge),
+    /// Index of an off-mesh link.
+    Link(usize),
 }
 
 #[derive(PartialEq, Clone, Debug, Eq)]
@@ -49,6 +58,16 @@ impl NavmeshSelection {
         self.dirty.set(true);
     }
 
+    /// Removes every occurrence of the entity from the selection.
+    pub fn remove(&mut self, entity: &NavmeshEntity) {
+        self.entities.retain(|e| e != entity);
+        self.dirty.set(true);
+    }
+
+    pub fn contains(&self, entity: &NavmeshEntity) -> bool {
+        self.entities.contains(entity)
+    }
+
     pub fn clear(&mut self) {
         self.entities.clear();
         self.unique_vertices.borrow_mut().clear();
@@ -84,6 +103,7 @@ impl NavmeshSelection {
                         unique_vertices.insert(edge.a as usize);
                         unique_vertices.insert(edge.b as usize);
                     }
+                    NavmeshEntity::Link(_) => (),
                 }
             }
         }
@@ -98,4 +118,831 @@ impl NavmeshSelection {
     pub fn contains_edge(&self, edge: TriangleEdge) -> bool {
         self.entities.contains(&NavmeshEntity::Edge(edge))
     }
+
+    /// Returns indices of the selected off-mesh links in descending order.
+    pub fn links(&self) -> Vec<usize> {
+        self.entities
+            .iter()
+            .filter_map(|entity| {
+                if let NavmeshEntity::Link(link) = *entity {
+                    Some(link)
+                } else {
+                    None
+                }
+            })
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .rev()
+            .collect()
+    }
+
+    /// Returns the selection with vertex indices changed according to the `remap` table (old index
+    /// to new index). Entities with removed vertices (mapped to `None`) are dropped.
+    pub fn remapped(&self, remap: &[Option<u32>]) -> Self {
+        let new_index = |vertex: usize| remap.get(vertex).copied().flatten();
+        Self::new(
+            self.navmesh_node,
+            self.entities
+                .iter()
+                .filter_map(|entity| match entity {
+                    NavmeshEntity::Vertex(vertex) => {
+                        Some(NavmeshEntity::Vertex(new_index(*vertex)? as usize))
+                    }
+                    NavmeshEntity::Edge(edge) => Some(NavmeshEntity::Edge(TriangleEdge {
+                        a: new_index(edge.a as usize)?,
+                        b: new_index(edge.b as usize)?,
+                    })),
+                    NavmeshEntity::Link(link) => Some(NavmeshEntity::Link(*link)),
+                })
+                .collect(),
+        )
+    }
+}
+
+// Returns the ray parameter of the first intersection point in front of the ray origin.
+fn hit_distance(result: Option<IntersectionResult>) -> Option<f32> {
+    let result = result?;
+    if result.min >= 0.0 {
+        Some(result.min)
+    } else if result.max >= 0.0 {
+        // Ray origin is inside the shape.
+        Some(0.0)
+    } else {
+        None
+    }
+}
+
+/// Returns the index of the closest polyline hit by the ray. Every segment of a polyline is
+/// represented as a cylinder of the given radius.
+pub fn pick_polyline(ray: &Ray, polylines: &[Vec<Vector3<f32>>], radius: f32) -> Option<usize> {
+    polylines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, polyline)| {
+            polyline
+                .windows(2)
+                .filter_map(|segment| {
+                    hit_distance(ray.cylinder_intersection(
+                        &segment[0],
+                        &segment[1],
+                        radius,
+                        CylinderKind::Finite,
+                    ))
+                })
+                .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+                .map(|t| (t, index))
+        })
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, index)| index)
+}
+
+/// Limits of picking of navmesh entities.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PickOptions {
+    /// Radius of the spheres of vertices and the cylinders of edges.
+    pub radius: f32,
+    /// Entities farther than this distance from the origin of the ray could not be picked.
+    pub max_distance: f32,
+    /// Distance from the origin of the ray to the closest obstacle on its way (usually the scene
+    /// geometry). Entities behind the obstacle could not be picked, unless they touch it.
+    pub occluder_distance: Option<f32>,
+}
+
+impl PickOptions {
+    /// Options without any limits except the radius of entities.
+    pub fn unlimited(radius: f32) -> Self {
+        Self {
+            radius,
+            max_distance: f32::MAX,
+            occluder_distance: None,
+        }
+    }
+}
+
+/// Returns the closest vertex or edge hit by the ray. Vertices are represented as spheres and edges
+/// as cylinders of the given radius. A vertex wins over an edge if it is closer along the ray or if
+/// the edge is attached to the vertex (edge cylinders always stick out of the sphere of their
+/// vertices, so they would otherwise make vertices almost impossible to pick). Entities, that are
+/// too far or occluded, are ignored.
+pub fn pick_entity(
+    ray: &Ray,
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    options: &PickOptions,
+) -> Option<NavmeshEntity> {
+    fn closest<T>(a: &(f32, T), b: &(f32, T)) -> std::cmp::Ordering {
+        a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal)
+    }
+
+    let radius = options.radius;
+    // Hits are measured in ray parameters, limits are converted to them too. Entities lie on the
+    // geometry, so the ones that touch an obstacle are still visible.
+    let ray_length = ray.dir.norm();
+    let max_distance = match options.occluder_distance {
+        Some(occluder_distance) => options.max_distance.min(occluder_distance + radius),
+        None => options.max_distance,
+    };
+    let max_t = if ray_length > f32::EPSILON {
+        max_distance / ray_length
+    } else {
+        f32::MAX
+    };
+    let visible_hit = |result| hit_distance(result).filter(|t| *t <= max_t);
+
+    let vertex = vertices
+        .iter()
+        .enumerate()
+        .filter_map(|(index, position)| {
+            visible_hit(ray.sphere_intersection(position, radius)).map(|t| (t, index))
+        })
+        .min_by(closest);
+
+    let edge = triangles
+        .iter()
+        .flat_map(|triangle| triangle.edges())
+        .filter_map(|edge| {
+            let begin = vertices.get(edge.a as usize)?;
+            let end = vertices.get(edge.b as usize)?;
+            visible_hit(ray.cylinder_intersection(begin, end, radius, CylinderKind::Finite))
+                .map(|t| (t, edge))
+        })
+        .min_by(closest);
+
+    match (vertex, edge) {
+        (Some((vertex_distance, vertex)), Some((edge_distance, edge))) => {
+            if edge_distance < vertex_distance
+                && edge.a as usize != vertex
+                && edge.b as usize != vertex
+            {
+                Some(NavmeshEntity::Edge(edge))
+            } else {
+                Some(NavmeshEntity::Vertex(vertex))
+            }
+        }
+        (Some((_, vertex)), None) => Some(NavmeshEntity::Vertex(vertex)),
+        (None, Some((_, edge))) => Some(NavmeshEntity::Edge(edge)),
+        (None, None) => None,
+    }
+}
+
+/// Returns every vertex, that could be reached from the given vertices through the edges of the
+/// triangles (seed vertices are included too). Out-of-bounds indices are ignored.
+pub fn connected_vertices(
+    triangles: &[TriangleDefinition],
+    vertex_count: usize,
+    seeds: impl IntoIterator<Item = usize>,
+) -> BTreeSet<usize> {
+    let mut adjacency = vec![Vec::new(); vertex_count];
+    for triangle in triangles {
+        for edge in triangle.edges() {
+            let (a, b) = (edge.a as usize, edge.b as usize);
+            if a < vertex_count && b < vertex_count {
+                adjacency[a].push(b);
+                adjacency[b].push(a);
+            }
+        }
+    }
+
+    let mut visited = vec![false; vertex_count];
+    let mut stack = Vec::new();
+    for seed in seeds {
+        if seed < vertex_count && !visited[seed] {
+            visited[seed] = true;
+            stack.push(seed);
+        }
+    }
+
+    while let Some(vertex) = stack.pop() {
+        for &neighbour in adjacency[vertex].iter() {
+            if !visited[neighbour] {
+                visited[neighbour] = true;
+                stack.push(neighbour);
+            }
+        }
+    }
+
+    visited
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, visited)| if visited { Some(index) } else { None })
+        .collect()
+}
+
+/// Returns the index of the closest triangle hit by the ray.
+pub fn pick_triangle(
+    ray: &Ray,
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+) -> Option<usize> {
+    triangles
+        .iter()
+        .enumerate()
+        .filter_map(|(index, triangle)| {
+            let [a, b, c] = [
+                vertices.get(triangle[0] as usize)?,
+                vertices.get(triangle[1] as usize)?,
+                vertices.get(triangle[2] as usize)?,
+            ];
+            ray.triangle_intersection_point(&[*a, *b, *c])
+                .map(|point| (point.metric_distance(&ray.origin), index))
+        })
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, index)| index)
+}
+
+/// Returns indices of the triangles, that a double click on the entity selects: every triangle
+/// around a vertex or the triangles of an edge. Links have no triangles.
+pub fn entity_triangles(triangles: &[TriangleDefinition], entity: &NavmeshEntity) -> Vec<usize> {
+    triangles
+        .iter()
+        .enumerate()
+        .filter(|(_, triangle)| match *entity {
+            NavmeshEntity::Vertex(vertex) => triangle.0.contains(&(vertex as u32)),
+            NavmeshEntity::Edge(edge) => triangle.edges().contains(&edge),
+            NavmeshEntity::Link(_) => false,
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Returns indices of the triangles of the island, that contains the given triangle. Triangles of
+/// an island are connected through their vertices.
+pub fn triangle_island(
+    triangles: &[TriangleDefinition],
+    vertex_count: usize,
+    triangle: usize,
+) -> Vec<usize> {
+    let seeds = match triangles.get(triangle) {
+        Some(triangle) => triangle.0.map(|i| i as usize),
+        None => return Vec::new(),
+    };
+    let island = connected_vertices(triangles, vertex_count, seeds);
+    triangles
+        .iter()
+        .enumerate()
+        .filter(|(_, triangle)| triangle.0.iter().all(|i| island.contains(&(*i as usize))))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Returns the edges of the given triangles as selection entities, an edge shared by several
+/// triangles is returned once.
+pub fn triangle_entities(
+    triangles: &[TriangleDefinition],
+    indices: impl IntoIterator<Item = usize>,
+) -> Vec<NavmeshEntity> {
+    let mut entities = Vec::new();
+    for triangle in indices.into_iter().filter_map(|i| triangles.get(i)) {
+        for edge in triangle.edges() {
+            let entity = NavmeshEntity::Edge(edge);
+            if !entities.contains(&entity) {
+                entities.push(entity);
+            }
+        }
+    }
+    entities
+}
+
+/// Minimal cosine of the angle between two consecutive edges of an interior edge loop. The loop
+/// stops if there's no continuation, that is straight enough.
+pub const EDGE_LOOP_MIN_COS: f32 = 0.5;
+
+/// Stores which triangles share every edge of a mesh. Edges are keyed by sorted vertex indices, so
+/// the adjacency doesn't depend on triangle winding.
+#[derive(Clone, Debug, Default)]
+pub struct EdgeAdjacency {
+    edges: HashMap<(u32, u32), Vec<usize>>,
+    vertex_edges: HashMap<u32, Vec<(u32, u32)>>,
+}
+
+fn edge_key(edge: TriangleEdge) -> (u32, u32) {
+    (edge.a.min(edge.b), edge.a.max(edge.b))
+}
+
+impl EdgeAdjacency {
+    pub fn new(triangles: &[TriangleDefinition]) -> Self {
+        let mut adjacency = Self::default();
+        for (index, triangle) in triangles.iter().enumerate() {
+            for edge in triangle.edges() {
+                if edge.a == edge.b {
+                    continue;
+                }
+                let key = edge_key(edge);
+                let edge_triangles = adjacency.edges.entry(key).or_default();
+                if edge_triangles.is_empty() {
+                    for vertex in [key.0, key.1] {
+                        adjacency.vertex_edges.entry(vertex).or_default().push(key);
+                    }
+                }
+                if !edge_triangles.contains(&index) {
+                    edge_triangles.push(index);
+                }
+            }
+        }
+        // Hash map has no stable order, keep the traversal deterministic.
+        for edges in adjacency.vertex_edges.values_mut() {
+            edges.sort_unstable();
+        }
+        adjacency
+    }
+
+    /// Returns indices of the triangles, that share the edge. The slice is empty if there's no such
+    /// edge.
+    pub fn triangles(&self, edge: TriangleEdge) -> &[usize] {
+        self.edges
+            .get(&edge_key(edge))
+            .map(|triangles| triangles.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if the edge is used by exactly one triangle.
+    pub fn is_boundary(&self, edge: TriangleEdge) -> bool {
+        self.triangles(edge).len() == 1
+    }
+
+    /// Returns every edge attached to the vertex. Every edge starts at the vertex.
+    pub fn edges_at(&self, vertex: u32) -> impl Iterator<Item = TriangleEdge> + '_ {
+        self.vertex_edges
+            .get(&vertex)
+            .into_iter()
+            .flatten()
+            .map(move |&(a, b)| TriangleEdge {
+                a: vertex,
+                b: if a == vertex { b } else { a },
+            })
+    }
+}
+
+// Picks the edge, that continues the loop after the given edge (at its `b` end).
+fn next_loop_edge(
+    vertices: &[Vector3<f32>],
+    adjacency: &EdgeAdjacency,
+    edge: TriangleEdge,
+    boundary: bool,
+    visited: &HashSet<(u32, u32)>,
+) -> Option<TriangleEdge> {
+    let direction = |edge: TriangleEdge| {
+        let a = vertices.get(edge.a as usize)?;
+        let b = vertices.get(edge.b as usize)?;
+        (b - a).try_normalize(f32::EPSILON)
+    };
+    let current_direction = direction(edge)?;
+    let current_triangles = adjacency.triangles(edge);
+
+    adjacency
+        .edges_at(edge.b)
+        .filter(|next| {
+            !visited.contains(&edge_key(*next))
+                && adjacency.is_boundary(*next) == boundary
+                // Edges of an interior loop go across the triangles, not along them.
+                && (boundary
+                    || !adjacency
+                        .triangles(*next)
+                        .iter()
+                        .any(|t| current_triangles.contains(t)))
+        })
+        .filter_map(|next| Some((direction(next)?.dot(&current_direction), next)))
+        .filter(|(cos, _)| boundary || *cos >= EDGE_LOOP_MIN_COS)
+        // The straightest continuation wins.
+        .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, next)| next)
+}
+
+/// Returns the loop of edges, that goes through the given edge. For a boundary edge (used by one
+/// triangle only) it is the connected chain of boundary edges, which is closed for a well-formed
+/// mesh. For an interior edge it is the chain of the straightest interior edges, that go across
+/// the triangles, until the chain hits the boundary or turns too much. Edges are returned in the
+/// order of traversal, the list is empty if there's no such edge in the mesh.
+pub fn edge_loop(
+    vertices: &[Vector3<f32>],
+    adjacency: &EdgeAdjacency,
+    edge: TriangleEdge,
+) -> Vec<TriangleEdge> {
+    if adjacency.triangles(edge).is_empty() {
+        return Vec::new();
+    }
+    let boundary = adjacency.is_boundary(edge);
+
+    let mut visited = HashSet::new();
+    visited.insert(edge_key(edge));
+
+    let mut forward = Vec::new();
+    let mut current = edge;
+    while let Some(next) = next_loop_edge(vertices, adjacency, current, boundary, &visited) {
+        visited.insert(edge_key(next));
+        forward.push(next);
+        current = next;
+    }
+
+    // The loop is closed, there's nothing to look for in the opposite direction.
+    if matches!(forward.last(), Some(last) if last.b == edge.a) {
+        let mut result = vec![edge];
+        result.extend(forward);
+        return result;
+    }
+
+    let mut backward = Vec::new();
+    let mut current = TriangleEdge {
+        a: edge.b,
+        b: edge.a,
+    };
+    while let Some(next) = next_loop_edge(vertices, adjacency, current, boundary, &visited) {
+        visited.insert(edge_key(next));
+        backward.push(TriangleEdge {
+            a: next.b,
+            b: next.a,
+        });
+        current = next;
+    }
+
+    backward.reverse();
+    backward.push(edge);
+    backward.extend(forward);
+    backward
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_remove_and_contains() {
+        let mut selection = NavmeshSelection::new(
+            Handle::new(1, 1),
+            vec![
+                NavmeshEntity::Vertex(0),
+                NavmeshEntity::Edge(TriangleEdge { a: 1, b: 2 }),
+                NavmeshEntity::Vertex(3),
+            ],
+        );
+        assert_eq!(
+            *selection.unique_vertices(),
+            [0, 1, 2, 3].into_iter().collect()
+        );
+
+        assert!(selection.contains(&NavmeshEntity::Vertex(0)));
+        assert!(!selection.contains(&NavmeshEntity::Vertex(1)));
+        // Edges are the same regardless of direction.
+        assert!(selection.contains(&NavmeshEntity::Edge(TriangleEdge { a: 2, b: 1 })));
+
+        selection.remove(&NavmeshEntity::Edge(TriangleEdge { a: 2, b: 1 }));
+        assert!(!selection.contains(&NavmeshEntity::Edge(TriangleEdge { a: 1, b: 2 })));
+        assert_eq!(*selection.unique_vertices(), [0, 3].into_iter().collect());
+
+        // Removal of an entity, that isn't selected, does nothing.
+        selection.remove(&NavmeshEntity::Vertex(5));
+        assert_eq!(selection.len(), 2);
+
+        selection.remove(&NavmeshEntity::Vertex(0));
+        selection.remove(&NavmeshEntity::Vertex(3));
+        assert!(selection.is_empty());
+        assert!(selection.unique_vertices().is_empty());
+    }
+
+    #[test]
+    fn test_remapped() {
+        let selection = NavmeshSelection::new(
+            Handle::new(1, 1),
+            vec![
+                NavmeshEntity::Vertex(0),
+                NavmeshEntity::Vertex(1),
+                NavmeshEntity::Edge(TriangleEdge { a: 2, b: 3 }),
+                NavmeshEntity::Edge(TriangleEdge { a: 3, b: 1 }),
+                NavmeshEntity::Link(0),
+            ],
+        );
+        // Vertex 0 is removed, the rest are shifted down. Links do not depend on vertices.
+        let remapped = selection.remapped(&[None, Some(0), Some(1), Some(2)]);
+
+        assert_eq!(remapped.navmesh_node(), Handle::new(1, 1));
+        assert_eq!(
+            remapped.entities(),
+            &[
+                NavmeshEntity::Vertex(0),
+                NavmeshEntity::Edge(TriangleEdge { a: 1, b: 2 }),
+                NavmeshEntity::Edge(TriangleEdge { a: 2, b: 0 }),
+                NavmeshEntity::Link(0),
+            ]
+        );
+        assert_eq!(*remapped.unique_vertices(), [0, 1, 2].into_iter().collect());
+
+        // Indices out of the table are removed too.
+        assert_eq!(
+            selection.remapped(&[]).entities(),
+            &[NavmeshEntity::Link(0)]
+        );
+    }
+
+    #[test]
+    fn test_selected_links() {
+        let selection = NavmeshSelection::new(
+            Handle::new(1, 1),
+            vec![
+                NavmeshEntity::Link(1),
+                NavmeshEntity::Vertex(0),
+                NavmeshEntity::Link(3),
+                NavmeshEntity::Link(1),
+            ],
+        );
+        assert_eq!(selection.links(), vec![3, 1]);
+        assert_eq!(*selection.unique_vertices(), [0].into_iter().collect());
+    }
+
+    #[test]
+    fn test_pick_polyline() {
+        let polylines = vec![
+            vec![Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0)],
+            vec![
+                Vector3::new(0.0, 1.0, 0.0),
+                Vector3::new(0.5, 1.0, 0.0),
+                Vector3::new(1.0, 1.0, 0.0),
+            ],
+        ];
+        let down =
+            |x: f32, z: f32| Ray::new(Vector3::new(x, 10.0, z), Vector3::new(0.0, -20.0, 0.0));
+
+        // The upper polyline is closer.
+        assert_eq!(pick_polyline(&down(0.75, 0.0), &polylines, 0.1), Some(1));
+        assert_eq!(pick_polyline(&down(0.75, 0.5), &polylines, 0.1), None);
+    }
+
+    #[test]
+    fn test_pick_entity() {
+        // Two quads one above the other, the camera looks down from above.
+        let vertices = [
+            // Upper quad.
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(1.0, 1.0, 0.0),
+            Vector3::new(1.0, 1.0, 1.0),
+            Vector3::new(0.0, 1.0, 1.0),
+            // Lower quad.
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 1.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        ];
+        let triangles = [
+            TriangleDefinition([0, 1, 2]),
+            TriangleDefinition([0, 2, 3]),
+            TriangleDefinition([4, 5, 6]),
+            TriangleDefinition([4, 6, 7]),
+        ];
+        let options = PickOptions::unlimited(0.1);
+        let down =
+            |x: f32, z: f32| Ray::new(Vector3::new(x, 10.0, z), Vector3::new(0.0, -20.0, 0.0));
+
+        // Overlapping vertices - the upper one is closer.
+        assert_eq!(
+            pick_entity(&down(1.0, 1.0), &vertices, &triangles, &options),
+            Some(NavmeshEntity::Vertex(2))
+        );
+        // Overlapping edges - the upper one is closer, regardless of triangle order.
+        let mut reversed = triangles.clone();
+        reversed.reverse();
+        for triangles in [&triangles, &reversed] {
+            assert_eq!(
+                pick_entity(&down(0.5, 0.0), &vertices, triangles, &options),
+                Some(NavmeshEntity::Edge(TriangleEdge { a: 0, b: 1 }))
+            );
+        }
+        // Vertex wins over the edges attached to it.
+        assert_eq!(
+            pick_entity(&down(0.05, 0.05), &vertices, &triangles, &options),
+            Some(NavmeshEntity::Vertex(0))
+        );
+        // Nothing is hit.
+        assert_eq!(
+            pick_entity(&down(0.5, 0.3), &vertices, &triangles, &options),
+            None
+        );
+
+        // The camera looks from the side, an edge is in front of the opposite vertex.
+        let ray = Ray::new(Vector3::new(0.5, 1.0, -10.0), Vector3::new(0.0, 0.0, 20.0));
+        let vertices = [
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(1.0, 1.0, 0.0),
+            Vector3::new(0.5, 1.0, 1.0),
+        ];
+        let triangles = [TriangleDefinition([0, 1, 2])];
+        assert_eq!(
+            pick_entity(&ray, &vertices, &triangles, &options),
+            Some(NavmeshEntity::Edge(TriangleEdge { a: 0, b: 1 }))
+        );
+        // Ray hits nothing behind its origin.
+        let ray = Ray::new(Vector3::new(0.5, 1.0, 5.0), Vector3::new(0.0, 0.0, 20.0));
+        assert_eq!(pick_entity(&ray, &vertices, &triangles, &options), None);
+    }
+
+    #[test]
+    fn test_pick_entity_limits() {
+        // A vertex behind another one, both are in front of a wall. The camera looks along Z axis.
+        let vertices = [Vector3::new(0.0, 0.0, 5.0), Vector3::new(0.0, 0.0, 10.0)];
+        let ray = Ray::new(Vector3::default(), Vector3::new(0.0, 0.0, 100.0));
+        let options = PickOptions::unlimited(0.1);
+
+        // The closest vertex along the ray wins.
+        assert_eq!(
+            pick_entity(&ray, &vertices, &[], &options),
+            Some(NavmeshEntity::Vertex(0))
+        );
+        assert_eq!(
+            pick_entity(&ray, &vertices[1..], &[], &options),
+            Some(NavmeshEntity::Vertex(0))
+        );
+
+        // Too far.
+        let limited = PickOptions {
+            max_distance: 4.0,
+            ..options
+        };
+        assert_eq!(pick_entity(&ray, &vertices, &[], &limited), None);
+
+        // Occluded by geometry in front of the vertices.
+        let occluded = PickOptions {
+            occluder_distance: Some(3.0),
+            ..options
+        };
+        assert_eq!(pick_entity(&ray, &vertices, &[], &occluded), None);
+
+        // The vertex lies on the occluding surface, the one behind it is hidden.
+        let touching = PickOptions {
+            occluder_distance: Some(5.0),
+            ..options
+        };
+        assert_eq!(
+            pick_entity(&ray, &vertices, &[], &touching),
+            Some(NavmeshEntity::Vertex(0))
+        );
+        assert_eq!(pick_entity(&ray, &vertices[1..], &[], &touching), None);
+    }
+
+    #[test]
+    fn test_connected_vertices() {
+        // Two islands: a quad (0, 1, 2, 3) and a triangle (4, 5, 6). Vertex 7 is isolated.
+        let triangles = [
+            TriangleDefinition([0, 1, 2]),
+            TriangleDefinition([0, 2, 3]),
+            TriangleDefinition([4, 5, 6]),
+        ];
+
+        assert_eq!(
+            connected_vertices(&triangles, 8, [1]),
+            [0, 1, 2, 3].into_iter().collect()
+        );
+        assert_eq!(
+            connected_vertices(&triangles, 8, [3, 6]),
+            [0, 1, 2, 3, 4, 5, 6].into_iter().collect()
+        );
+        assert_eq!(
+            connected_vertices(&triangles, 8, [7]),
+            [7].into_iter().collect()
+        );
+        assert!(connected_vertices(&triangles, 8, [100]).is_empty());
+    }
+
+    #[test]
+    fn test_double_click_triangles() {
+        let (vertices, mut triangles) = grid();
+        // A separate island.
+        triangles.push(TriangleDefinition([9, 10, 11]));
+        let vertex_count = vertices.len() + 3;
+
+        assert_eq!(
+            entity_triangles(&triangles, &NavmeshEntity::Vertex(4)),
+            vec![0, 1, 3, 4, 6, 7]
+        );
+        assert_eq!(
+            entity_triangles(
+                &triangles,
+                &NavmeshEntity::Edge(TriangleEdge { a: 3, b: 4 })
+            ),
+            vec![1, 4]
+        );
+        assert_eq!(
+            entity_triangles(
+                &triangles,
+                &NavmeshEntity::Edge(TriangleEdge { a: 0, b: 1 })
+            ),
+            vec![0]
+        );
+        assert!(entity_triangles(&triangles, &NavmeshEntity::Link(0)).is_empty());
+
+        assert_eq!(
+            triangle_island(&triangles, vertex_count, 3),
+            (0..8).collect::<Vec<_>>()
+        );
+        assert_eq!(triangle_island(&triangles, vertex_count, 8), vec![8]);
+        assert!(triangle_island(&triangles, vertex_count, 100).is_empty());
+
+        // Shared diagonal is returned once.
+        assert_eq!(
+            triangle_entities(&triangles, [0, 1]),
+            edges(&[(0, 1), (1, 4), (4, 0), (4, 3), (3, 0)])
+                .into_iter()
+                .map(NavmeshEntity::Edge)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_pick_triangle() {
+        let (vertices, triangles) = grid();
+        let ray = Ray::new(Vector3::new(1.8, 5.0, 0.5), Vector3::new(0.0, -10.0, 0.0));
+        assert_eq!(pick_triangle(&ray, &vertices, &triangles), Some(2));
+        let ray = Ray::new(Vector3::new(5.0, 5.0, 5.0), Vector3::new(0.0, -10.0, 0.0));
+        assert_eq!(pick_triangle(&ray, &vertices, &triangles), None);
+    }
+
+    #[test]
+    fn test_connected_vertices_of_a_long_strip() {
+        // Must not overflow the stack or take too long on big meshes.
+        let count = 100_000u32;
+        let triangles = (0..count - 2)
+            .map(|i| TriangleDefinition([i, i + 1, i + 2]))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            connected_vertices(&triangles, count as usize, [0]).len(),
+            count as usize
+        );
+    }
+
+    // 6 - 7 - 8
+    // | / | / |
+    // 3 - 4 - 5
+    // | / | / |
+    // 0 - 1 - 2
+    fn grid() -> (Vec<Vector3<f32>>, Vec<TriangleDefinition>) {
+        let vertices = (0..9)
+            .map(|i| Vector3::new((i % 3) as f32, 0.0, (i / 3) as f32))
+            .collect();
+        let mut triangles = Vec::new();
+        for z in 0..2 {
+            for x in 0..2 {
+                let i = z * 3 + x;
+                triangles.push(TriangleDefinition([i, i + 1, i + 4]));
+                triangles.push(TriangleDefinition([i, i + 4, i + 3]));
+            }
+        }
+        (vertices, triangles)
+    }
+
+    fn edges(pairs: &[(u32, u32)]) -> Vec<TriangleEdge> {
+        pairs.iter().map(|&(a, b)| TriangleEdge { a, b }).collect()
+    }
+
+    #[test]
+    fn test_edge_adjacency() {
+        let (_, triangles) = grid();
+        let adjacency = EdgeAdjacency::new(&triangles);
+
+        assert_eq!(adjacency.triangles(TriangleEdge { a: 4, b: 3 }), &[1, 4]);
+        assert!(adjacency.is_boundary(TriangleEdge { a: 0, b: 1 }));
+        assert!(!adjacency.is_boundary(TriangleEdge { a: 0, b: 4 }));
+        assert!(adjacency.triangles(TriangleEdge { a: 0, b: 8 }).is_empty());
+        assert_eq!(
+            adjacency.edges_at(2).collect::<Vec<_>>(),
+            edges(&[(2, 1), (2, 5)])
+        );
+    }
+
+    #[test]
+    fn test_boundary_edge_loop() {
+        let (vertices, triangles) = grid();
+        let adjacency = EdgeAdjacency::new(&triangles);
+
+        // The loop starts at the picked edge and goes in its direction.
+        assert_eq!(
+            edge_loop(&vertices, &adjacency, TriangleEdge { a: 8, b: 5 }),
+            edges(&[
+                (8, 5),
+                (5, 2),
+                (2, 1),
+                (1, 0),
+                (0, 3),
+                (3, 6),
+                (6, 7),
+                (7, 8)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_interior_edge_loop() {
+        let (vertices, triangles) = grid();
+        let adjacency = EdgeAdjacency::new(&triangles);
+
+        // Middle row, both ends are on the boundary.
+        assert_eq!(
+            edge_loop(&vertices, &adjacency, TriangleEdge { a: 4, b: 5 }),
+            edges(&[(3, 4), (4, 5)])
+        );
+        // Diagonal.
+        assert_eq!(
+            edge_loop(&vertices, &adjacency, TriangleEdge { a: 0, b: 4 }),
+            edges(&[(0, 4), (4, 8)])
+        );
+        // There's no such edge.
+        assert!(edge_loop(&vertices, &adjacency, TriangleEdge { a: 0, b: 8 }).is_empty());
+    }
 }
@@ -0,0 +1,541 @@
+//! Simplification of navigational meshes. Generated or imported navmeshes are often much denser than
+//! needed, which makes path finding slower and editing harder. The simplifier collapses edges (the
+//! shortest ones first) one by one, until every further collapse would move the surface too far
+//! away from the original one.
+
+use crate::{
+    interaction::navmesh::validation::NAVMESH_VALIDATION_EPSILON,
+    scene::commands::navmesh::MergedNavmesh,
+};
+use fyrox::core::{
+    algebra::Vector3,
+    math::{plane::Plane, TriangleDefinition},
+};
+use std::{cmp::Ordering, collections::BTreeSet};
+
+/// Parameters of navmesh simplification.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct NavmeshSimplification {
+    /// Maximum distance from the simplified surface to the planes of the original triangles (and
+    /// to the original boundary, if it could be collapsed).
+    pub max_error: f32,
+    /// Whether vertices on the boundary of the mesh could be removed. Otherwise the boundary stays
+    /// exactly as it was. Borders between enabled and disabled triangles are treated as boundary.
+    pub allow_boundary_collapse: bool,
+}
+
+impl Default for NavmeshSimplification {
+    fn default() -> Self {
+        Self {
+            max_error: 0.05,
+            allow_boundary_collapse: false,
+        }
+    }
+}
+
+// Planes, that differ less than this, are considered the same. Keeps the list of planes of a vertex
+// short on flat areas.
+const SAME_PLANE_EPSILON: f32 = 1.0e-5;
+
+fn add_plane(planes: &mut Vec<Plane>, plane: Plane) {
+    let exists = planes.iter().any(|p| {
+        (p.normal - plane.normal).norm() < SAME_PLANE_EPSILON
+            && (p.d - plane.d).abs() < SAME_PLANE_EPSILON
+    });
+    if !exists {
+        planes.push(plane);
+    }
+}
+
+struct Simplifier<'a> {
+    positions: &'a [Vector3<f32>],
+    options: NavmeshSimplification,
+    triangles: Vec<Option<[u32; 3]>>,
+    enabled: Vec<bool>,
+    // Triangles, that use every vertex. Triangles with repeated indices are not listed.
+    vertex_triangles: Vec<Vec<usize>>,
+    // Planes of the original triangles and boundary edges around every vertex.
+    planes: Vec<Vec<Plane>>,
+    // Vertices, that must never be removed.
+    locked: Vec<bool>,
+    // Vertex, that every removed vertex was collapsed into.
+    collapsed_into: Vec<Option<usize>>,
+}
+
+impl<'a> Simplifier<'a> {
+    fn new(
+        positions: &'a [Vector3<f32>],
+        triangles: &[TriangleDefinition],
+        enabled: &[bool],
+        options: NavmeshSimplification,
+    ) -> Self {
+        let mut simplifier = Self {
+            positions,
+            options,
+            triangles: triangles.iter().map(|t| Some(t.0)).collect(),
+            enabled: (0..triangles.len())
+                .map(|i| enabled.get(i).copied().unwrap_or(true))
+                .collect(),
+            vertex_triangles: vec![Vec::new(); positions.len()],
+            planes: vec![Vec::new(); positions.len()],
+            locked: vec![false; positions.len()],
+            collapsed_into: vec![None; positions.len()],
+        };
+
+        for (index, triangle) in triangles.iter().enumerate() {
+            let [a, b, c] = triangle.0;
+            if a == b || b == c || a == c {
+                // Such triangles are kept as is.
+                for &vertex in triangle.indices() {
+                    simplifier.locked[vertex as usize] = true;
+                }
+                continue;
+            }
+            for &vertex in triangle.indices() {
+                simplifier.vertex_triangles[vertex as usize].push(index);
+            }
+        }
+
+        for (index, triangle) in triangles.iter().enumerate() {
+            if simplifier.vertex_triangles[triangle[0] as usize].contains(&index) {
+                let normal = simplifier.normal(triangle.0);
+                for k in 0..3 {
+                    let (a, b) = (triangle[k] as usize, triangle[(k + 1) % 3] as usize);
+
+                    if simplifier.edge_triangles(a, b).len() > 2 {
+                        // Non-manifold edge.
+                        simplifier.locked[a] = true;
+                        simplifier.locked[b] = true;
+                    }
+
+                    if let Some(plane) = Plane::from_normal_and_point(&normal, &positions[a]) {
+                        add_plane(&mut simplifier.planes[a], plane);
+                    }
+
+                    // Plane, that goes through a boundary edge perpendicular to its triangle,
+                    // keeps the boundary in place.
+                    if simplifier.is_constrained_edge(a, b) {
+                        let side = (positions[b] - positions[a]).cross(&normal);
+                        if let Some(plane) = Plane::from_normal_and_point(&side, &positions[a]) {
+                            add_plane(&mut simplifier.planes[a], plane);
+                            add_plane(&mut simplifier.planes[b], plane);
+                        }
+                    }
+                }
+            }
+        }
+
+        simplifier
+    }
+
+    fn normal(&self, triangle: [u32; 3]) -> Vector3<f32> {
+        let [a, b, c] = triangle.map(|i| self.positions[i as usize]);
+        (b - a).cross(&(c - a))
+    }
+
+    fn edge_triangles(&self, a: usize, b: usize) -> Vec<usize> {
+        self.vertex_triangles[a]
+            .iter()
+            .copied()
+            .filter(
+                |t| matches!(self.triangles[*t], Some(triangle) if triangle.contains(&(b as u32))),
+            )
+            .collect()
+    }
+
+    // An edge is constrained, if it lies on the boundary or between an enabled and a disabled
+    // triangle.
+    fn is_constrained_edge(&self, a: usize, b: usize) -> bool {
+        match self.edge_triangles(a, b).as_slice() {
+            [first, second] => self.enabled[*first] != self.enabled[*second],
+            _ => true,
+        }
+    }
+
+    fn neighbours(&self, vertex: usize) -> BTreeSet<usize> {
+        self.vertex_triangles[vertex]
+            .iter()
+            .filter_map(|t| self.triangles[*t])
+            .flatten()
+            .map(|i| i as usize)
+            .filter(|i| *i != vertex)
+            .collect()
+    }
+
+    fn is_constrained_vertex(&self, vertex: usize) -> bool {
+        self.neighbours(vertex)
+            .into_iter()
+            .any(|neighbour| self.is_constrained_edge(vertex, neighbour))
+    }
+
+    /// Returns the error of the collapse of `u` vertex into `v` vertex, or `None` if the collapse
+    /// is not allowed.
+    fn collapse_error(&self, u: usize, v: usize) -> Option<f32> {
+        if self.locked[u] {
+            return None;
+        }
+
+        let shared = self.edge_triangles(u, v);
+        if shared.is_empty() {
+            return None;
+        }
+
+        // Boundary vertices could only slide along the boundary.
+        if self.is_constrained_vertex(u)
+            && (!self.options.allow_boundary_collapse || !self.is_constrained_edge(u, v))
+        {
+            return None;
+        }
+
+        // Vertices, that are connected with both ends of the edge, must be the opposite vertices
+        // of the triangles of the edge. Otherwise the collapse produces non-manifold edges.
+        let opposite = shared
+            .iter()
+            .filter_map(|t| self.triangles[*t])
+            .flatten()
+            .map(|i| i as usize)
+            .filter(|i| *i != u && *i != v)
+            .collect::<BTreeSet<_>>();
+        let common = self
+            .neighbours(u)
+            .intersection(&self.neighbours(v))
+            .copied()
+            .collect::<BTreeSet<_>>();
+        if common != opposite {
+            return None;
+        }
+
+        // Vertices must not lose all of their triangles.
+        for &vertex in opposite.iter().chain([u, v].iter()) {
+            if vertex != u
+                && self.vertex_triangles[vertex]
+                    .iter()
+                    .all(|t| shared.contains(t))
+            {
+                return None;
+            }
+        }
+
+        for &t in self.vertex_triangles[u].iter() {
+            if shared.contains(&t) {
+                continue;
+            }
+            let old = match self.triangles[t] {
+                Some(triangle) => triangle,
+                None => continue,
+            };
+            let new = old.map(|i| if i as usize == u { v as u32 } else { i });
+
+            let new_normal = self.normal(new);
+            if new_normal.norm() * 0.5 <= NAVMESH_VALIDATION_EPSILON * NAVMESH_VALIDATION_EPSILON
+                || new_normal.dot(&self.normal(old)) <= 0.0
+            {
+                // Degenerate or flipped triangle.
+                return None;
+            }
+        }
+
+        let error = self.planes[u]
+            .iter()
+            .map(|plane| plane.distance(&self.positions[v]))
+            .fold(0.0, f32::max);
+        if error <= self.options.max_error {
+            Some(error)
+        } else {
+            None
+        }
+    }
+
+    fn collapse(&mut self, u: usize, v: usize) {
+        for t in self.edge_triangles(u, v) {
+            if let Some(triangle) = self.triangles[t].take() {
+                for i in triangle {
+                    self.vertex_triangles[i as usize].retain(|other| *other != t);
+                }
+            }
+        }
+
+        for t in std::mem::take(&mut self.vertex_triangles[u]) {
+            if let Some(triangle) = self.triangles[t].as_mut() {
+                for i in triangle.iter_mut() {
+                    if *i as usize == u {
+                        *i = v as u32;
+                    }
+                }
+                self.vertex_triangles[v].push(t);
+            }
+        }
+
+        for plane in std::mem::take(&mut self.planes[u]) {
+            add_plane(&mut self.planes[v], plane);
+        }
+
+        self.collapsed_into[u] = Some(v);
+    }
+
+    fn edges(&self) -> Vec<(usize, usize)> {
+        let mut edges = self
+            .vertex_triangles
+            .iter()
+            .flatten()
+            .filter_map(|t| self.triangles[*t])
+            .flat_map(|[a, b, c]| [(a, b), (b, c), (c, a)])
+            .map(|(a, b)| (a.min(b) as usize, a.max(b) as usize))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        let length = |(a, b): (usize, usize)| self.positions[a].metric_distance(&self.positions[b]);
+        edges.sort_by(|a, b| {
+            length(*a)
+                .partial_cmp(&length(*b))
+                .unwrap_or(Ordering::Equal)
+                .then(a.cmp(b))
+        });
+        edges
+    }
+
+    fn finish(self) -> MergedNavmesh {
+        let mut new_indices = vec![0; self.positions.len()];
+        let mut vertices = Vec::new();
+        for (index, position) in self.positions.iter().enumerate() {
+            if self.collapsed_into[index].is_none() {
+                new_indices[index] = vertices.len() as u32;
+                vertices.push(*position);
+            }
+        }
+
+        let remap = (0..self.positions.len())
+            .map(|mut index| {
+                while let Some(target) = self.collapsed_into[index] {
+                    index = target;
+                }
+                new_indices[index]
+            })
+            .collect::<Vec<_>>();
+
+        let (source_triangles, triangles) = self
+            .triangles
+            .iter()
+            .enumerate()
+            .filter_map(|(index, triangle)| {
+                triangle.map(|t| (index, TriangleDefinition(t.map(|i| remap[i as usize]))))
+            })
+            .unzip();
+
+        MergedNavmesh {
+            vertices,
+            triangles,
+            source_triangles,
+            remap,
+        }
+    }
+}
+
+/// Collapses edges of the mesh, while the surface stays closer than the maximum error to the planes
+/// of the original triangles. Vertices are never moved, so every remaining vertex keeps its original
+/// position. Collapses, that would produce degenerate or flipped triangles, or non-manifold edges,
+/// are skipped. `enabled` holds the state of every triangle, borders between enabled and disabled
+/// triangles are kept the same way as the boundary. A mesh with invalid indices is returned as is.
+pub fn simplify_navmesh(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    enabled: &[bool],
+    options: NavmeshSimplification,
+) -> MergedNavmesh {
+    if triangles
+        .iter()
+        .flat_map(|t| t.indices())
+        .any(|i| *i as usize >= vertices.len())
+    {
+        return MergedNavmesh {
+            vertices: vertices.to_vec(),
+            triangles: triangles.to_vec(),
+            source_triangles: (0..triangles.len()).collect(),
+            remap: (0..vertices.len() as u32).collect(),
+        };
+    }
+
+    let mut simplifier = Simplifier::new(vertices, triangles, enabled, options);
+    loop {
+        let mut collapsed = false;
+        for (a, b) in simplifier.edges() {
+            if simplifier.collapsed_into[a].is_some() || simplifier.collapsed_into[b].is_some() {
+                continue;
+            }
+
+            let best = [(a, b), (b, a)]
+                .iter()
+                .filter_map(|&(u, v)| simplifier.collapse_error(u, v).map(|e| (e, u, v)))
+                .min_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(Ordering::Equal));
+            if let Some((_, u, v)) = best {
+                simplifier.collapse(u, v);
+                collapsed = true;
+            }
+        }
+
+        if !collapsed {
+            break;
+        }
+    }
+
+    simplifier.finish()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::interaction::navmesh::validation::navmesh_area;
+
+    // Grid of `size` x `size` quads with unit sides, every quad is split in two triangles.
+    fn grid<F>(size: u32, height: F) -> (Vec<Vector3<f32>>, Vec<TriangleDefinition>)
+    where
+        F: Fn(f32) -> f32,
+    {
+        let vertices = (0..(size + 1) * (size + 1))
+            .map(|i| {
+                let (x, z) = ((i % (size + 1)) as f32, (i / (size + 1)) as f32);
+                Vector3::new(x, height(x), z)
+            })
+            .collect();
+        let mut triangles = Vec::new();
+        for z in 0..size {
+            for x in 0..size {
+                let i = z * (size + 1) + x;
+                triangles.push(TriangleDefinition([i, i + size + 2, i + 1]));
+                triangles.push(TriangleDefinition([i, i + size + 1, i + size + 2]));
+            }
+        }
+        (vertices, triangles)
+    }
+
+    fn contains(mesh: &MergedNavmesh, position: Vector3<f32>) -> bool {
+        mesh.vertices.contains(&position)
+    }
+
+    // Every triangle must be non-degenerate and face the same side as the original grid.
+    fn assert_not_flipped(mesh: &MergedNavmesh) {
+        for triangle in mesh.triangles.iter() {
+            let [a, b, c] = triangle.0.map(|i| mesh.vertices[i as usize]);
+            let normal = (b - a).cross(&(c - a));
+            assert!(normal.norm() > 1.0e-3);
+            assert!(normal.y > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_flat_grid() {
+        let (vertices, triangles) = grid(4, |_| 0.0);
+        let simplified =
+            simplify_navmesh(&vertices, &triangles, &[], NavmeshSimplification::default());
+
+        assert!(simplified.triangles.len() < triangles.len());
+        assert_not_flipped(&simplified);
+        assert!((navmesh_area(&simplified.vertices, &simplified.triangles) - 16.0).abs() < 1.0e-4);
+        // Boundary is kept as is.
+        for position in vertices.iter() {
+            if position.x == 0.0 || position.x == 4.0 || position.z == 0.0 || position.z == 4.0 {
+                assert!(contains(&simplified, *position));
+            }
+        }
+        assert_eq!(
+            simplified.source_triangles.len(),
+            simplified.triangles.len()
+        );
+        assert_eq!(simplified.remap.len(), vertices.len());
+
+        let collapsed = simplify_navmesh(
+            &vertices,
+            &triangles,
+            &[],
+            NavmeshSimplification {
+                allow_boundary_collapse: true,
+                ..Default::default()
+            },
+        );
+        assert!(collapsed.triangles.len() < simplified.triangles.len());
+        assert_not_flipped(&collapsed);
+        assert!((navmesh_area(&collapsed.vertices, &collapsed.triangles) - 16.0).abs() < 1.0e-4);
+        // Corners can't be collapsed without changing the shape.
+        for corner in [(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)] {
+            assert!(contains(&collapsed, Vector3::new(corner.0, 0.0, corner.1)));
+        }
+    }
+
+    #[test]
+    fn test_bent_grid() {
+        // The grid is bent along x = 2 line.
+        let (vertices, triangles) = grid(4, |x| (x - 2.0).max(0.0));
+        let options = NavmeshSimplification {
+            allow_boundary_collapse: true,
+            ..Default::default()
+        };
+        let straddles_bend = |mesh: &MergedNavmesh| {
+            mesh.triangles.iter().any(|triangle| {
+                let xs = triangle.0.map(|i| mesh.vertices[i as usize].x);
+                xs.iter().any(|x| *x < 2.0) && xs.iter().any(|x| *x > 2.0)
+            })
+        };
+
+        let simplified = simplify_navmesh(&vertices, &triangles, &[], options);
+        assert!(simplified.triangles.len() < triangles.len());
+        assert_not_flipped(&simplified);
+        // Vertices on the bend could be collapsed along it, because the bend is a straight line,
+        // but no triangle could go across it.
+        assert!(!straddles_bend(&simplified));
+        assert!(contains(&simplified, Vector3::new(2.0, 0.0, 0.0)));
+        assert!(contains(&simplified, Vector3::new(2.0, 0.0, 4.0)));
+
+        // Big error flattens the bend.
+        let flattened = simplify_navmesh(
+            &vertices,
+            &triangles,
+            &[],
+            NavmeshSimplification {
+                max_error: 10.0,
+                ..options
+            },
+        );
+        assert!(flattened.triangles.len() < simplified.triangles.len());
+        assert!(straddles_bend(&flattened));
+    }
+
+    #[test]
+    fn test_disabled_triangles_are_kept_apart() {
+        let (vertices, triangles) = grid(4, |_| 0.0);
+        // Left half of the grid is disabled.
+        let enabled = triangles
+            .iter()
+            .map(|t| t.indices().iter().any(|i| vertices[*i as usize].x > 2.0))
+            .collect::<Vec<_>>();
+        let simplified = simplify_navmesh(
+            &vertices,
+            &triangles,
+            &enabled,
+            NavmeshSimplification::default(),
+        );
+
+        assert!(simplified.triangles.len() < triangles.len());
+        for (triangle, source) in simplified
+            .triangles
+            .iter()
+            .zip(simplified.source_triangles.iter())
+        {
+            for &i in triangle.indices() {
+                let x = simplified.vertices[i as usize].x;
+                assert!(if enabled[*source] { x >= 2.0 } else { x <= 2.0 });
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_mesh_is_not_changed() {
+        let (vertices, mut triangles) = grid(2, |_| 0.0);
+        triangles.push(TriangleDefinition([0, 1, 100]));
+
+        let simplified =
+            simplify_navmesh(&vertices, &triangles, &[], NavmeshSimplification::default());
+        assert_eq!(simplified.vertices, vertices);
+        assert_eq!(simplified.triangles, triangles);
+    }
+}
@@ -0,0 +1,117 @@
+//! Walkable slope preview for navmesh edit mode. It shows which triangles are steeper than the
+//! maximal slope, that agents of a game could walk on.
+
+use fyrox::{
+    core::{algebra::Vector3, math::TriangleDefinition},
+    utils::navmesh::Navmesh,
+};
+
+/// Returns the slope of every triangle, it is the angle (in degrees) between the normal of the
+/// triangle and the world up vector. Winding of triangles does not matter, degenerate triangles
+/// are considered vertical.
+pub fn triangle_slopes(vertices: &[Vector3<f32>], triangles: &[TriangleDefinition]) -> Vec<f32> {
+    triangles
+        .iter()
+        .map(|triangle| {
+            let [a, b, c] = triangle.0.map(|i| vertices[i as usize]);
+            match (b - a).cross(&(c - a)).try_normalize(f32::EPSILON) {
+                Some(normal) => normal.y.abs().min(1.0).acos().to_degrees(),
+                None => 90.0,
+            }
+        })
+        .collect()
+}
+
+/// Returns `true` for every triangle, which is steeper than `max_slope` (in degrees).
+pub fn steep_triangles(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    max_slope: f32,
+) -> Vec<bool> {
+    triangle_slopes(vertices, triangles)
+        .into_iter()
+        .map(|slope| slope > max_slope)
+        .collect()
+}
+
+// Everything the classification depends on, used to recompute it only when something has
+// changed.
+#[derive(PartialEq)]
+struct SlopePreviewInput {
+    vertices: Vec<Vector3<f32>>,
+    triangles: Vec<TriangleDefinition>,
+    max_slope: f32,
+}
+
+/// Cached classification of the triangles of a navmesh by their slope.
+#[derive(Default)]
+pub struct SlopePreview {
+    input: Option<SlopePreviewInput>,
+    steep_triangles: Vec<bool>,
+}
+
+impl SlopePreview {
+    /// Reclassifies triangles if the navmesh or the maximal slope were changed since the last call.
+    pub fn update(&mut self, navmesh: &Navmesh, max_slope: f32) {
+        let input = SlopePreviewInput {
+            vertices: navmesh.vertices().iter().map(|v| v.position).collect(),
+            triangles: navmesh.triangles().to_vec(),
+            max_slope,
+        };
+        if self.input.as_ref() == Some(&input) {
+            return;
+        }
+
+        self.steep_triangles = steep_triangles(&input.vertices, &input.triangles, max_slope);
+        self.input = Some(input);
+    }
+
+    /// Returns `true` for every triangle, which is steeper than the maximal slope. Flags are in the
+    /// same order as the triangles of the navmesh.
+    pub fn steep_triangles(&self) -> &[bool] {
+        &self.steep_triangles
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_triangle_slopes() {
+        let vertices = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+            // Above vertex 2, makes a 45 degrees ramp.
+            Vector3::new(0.0, 1.0, 1.0),
+            // Above vertex 0, makes a wall.
+            Vector3::new(0.0, 1.0, 0.0),
+        ];
+        let triangles = [
+            TriangleDefinition([0, 2, 1]),
+            // Opposite winding.
+            TriangleDefinition([0, 1, 2]),
+            TriangleDefinition([0, 3, 1]),
+            TriangleDefinition([0, 4, 1]),
+            // Degenerate.
+            TriangleDefinition([0, 1, 1]),
+        ];
+
+        let slopes = triangle_slopes(&vertices, &triangles);
+        let expected = [0.0, 0.0, 45.0, 90.0, 90.0];
+        assert_eq!(slopes.len(), expected.len());
+        for (slope, expected) in slopes.iter().zip(expected.iter()) {
+            assert!((slope - expected).abs() < 1.0e-3);
+        }
+
+        assert_eq!(
+            steep_triangles(&vertices, &triangles, 30.0),
+            vec![false, false, true, true, true]
+        );
+        assert_eq!(
+            steep_triangles(&vertices, &triangles, 50.0),
+            vec![false, false, false, true, true]
+        );
+    }
+}
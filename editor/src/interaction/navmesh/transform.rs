@@ -0,0 +1,210 @@
+//! Rotation and scaling of selected navmesh vertices around their centroid. The transform follows
+//! the cursor: the distance from the cursor to the pivot on the screen sets the scale and the angle
+//! of the cursor around the pivot sets the rotation.
+
+use fyrox::core::algebra::{UnitQuaternion, Vector2, Vector3};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VertexTransformKind {
+    Rotate,
+    Scale,
+}
+
+/// Returns the average of the positions.
+pub fn centroid(positions: &[Vector3<f32>]) -> Vector3<f32> {
+    positions
+        .iter()
+        .fold(Vector3::default(), |sum, position| sum + position)
+        .scale(1.0 / positions.len().max(1) as f32)
+}
+
+/// Rotation or scaling of a set of vertices, that is in progress.
+#[derive(Clone, Debug)]
+pub struct VertexTransform {
+    pub kind: VertexTransformKind,
+    /// Centroid of the vertices.
+    pub pivot: Vector3<f32>,
+    /// Index of the axis, that the transform is constrained to. It is the rotation axis or the only
+    /// scaled axis. `None` means rotation around Y axis or uniform scaling.
+    pub axis: Option<usize>,
+    /// Indices and positions of the vertices before the transform.
+    pub initial_positions: Vec<(usize, Vector3<f32>)>,
+    // Offset of the cursor from the pivot on the screen, when the transform was started.
+    start_offset: Vector2<f32>,
+}
+
+impl VertexTransform {
+    /// Starts a transform of the given vertices. `start_offset` is the offset of the cursor from
+    /// the screen projection of the centroid of the vertices. Returns `None` if there's nothing to
+    /// transform.
+    pub fn new(
+        kind: VertexTransformKind,
+        initial_positions: Vec<(usize, Vector3<f32>)>,
+        start_offset: Vector2<f32>,
+    ) -> Option<Self> {
+        if initial_positions.is_empty() {
+            return None;
+        }
+
+        let positions = initial_positions
+            .iter()
+            .map(|(_, position)| *position)
+            .collect::<Vec<_>>();
+
+        Some(Self {
+            kind,
+            pivot: centroid(&positions),
+            axis: None,
+            initial_positions,
+            start_offset,
+        })
+    }
+
+    /// Constrains the transform to the given axis. Constraining to the same axis again removes the
+    /// constraint.
+    pub fn toggle_axis(&mut self, axis: usize) {
+        self.axis = if self.axis == Some(axis) {
+            None
+        } else {
+            Some(axis)
+        };
+    }
+
+    /// Returns new positions of the vertices for the given offset of the cursor from the pivot on
+    /// the screen. `look` is the look direction of the camera, rotation follows the cursor when it
+    /// is seen from the camera.
+    pub fn apply(
+        &self,
+        cursor_offset: Vector2<f32>,
+        look: Vector3<f32>,
+    ) -> Vec<(usize, Vector3<f32>)> {
+        match self.kind {
+            VertexTransformKind::Scale => {
+                let start_distance = self.start_offset.norm();
+                let scale = if start_distance > f32::EPSILON {
+                    cursor_offset.norm() / start_distance
+                } else {
+                    1.0
+                };
+                let scale = match self.axis {
+                    Some(axis) => {
+                        let mut scale_vector = Vector3::repeat(1.0);
+                        scale_vector[axis] = scale;
+                        scale_vector
+                    }
+                    None => Vector3::repeat(scale),
+                };
+
+                self.initial_positions
+                    .iter()
+                    .map(|(vertex, position)| {
+                        (
+                            *vertex,
+                            self.pivot + (position - self.pivot).component_mul(&scale),
+                        )
+                    })
+                    .collect()
+            }
+            VertexTransformKind::Rotate => {
+                let axis = Vector3::ith_axis(self.axis.unwrap_or(1));
+                // Screen Y axis goes down, so the angle grows clockwise on the screen. Positive
+                // rotation around an axis, that points to the viewer, is counterclockwise.
+                let screen_angle = cursor_offset.y.atan2(cursor_offset.x)
+                    - self.start_offset.y.atan2(self.start_offset.x);
+                let angle = if axis.dot(&look) < 0.0 {
+                    -screen_angle
+                } else {
+                    screen_angle
+                };
+                let rotation = UnitQuaternion::from_axis_angle(&axis, angle);
+
+                self.initial_positions
+                    .iter()
+                    .map(|(vertex, position)| {
+                        (
+                            *vertex,
+                            self.pivot + rotation.transform_vector(&(position - self.pivot)),
+                        )
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn approx_eq(a: Vector3<f32>, b: Vector3<f32>) -> bool {
+        a.metric_distance(&b) < 1.0e-5
+    }
+
+    fn square() -> Vec<(usize, Vector3<f32>)> {
+        vec![
+            (0, Vector3::new(1.0, 0.0, 1.0)),
+            (1, Vector3::new(3.0, 0.0, 1.0)),
+            (2, Vector3::new(3.0, 0.0, 3.0)),
+            (3, Vector3::new(1.0, 0.0, 3.0)),
+        ]
+    }
+
+    #[test]
+    fn test_centroid() {
+        let positions = square().into_iter().map(|(_, p)| p).collect::<Vec<_>>();
+        assert!(approx_eq(centroid(&positions), Vector3::new(2.0, 0.0, 2.0)));
+        assert_eq!(centroid(&[]), Vector3::default());
+        assert!(VertexTransform::new(VertexTransformKind::Scale, vec![], Vector2::x()).is_none());
+    }
+
+    #[test]
+    fn test_scale() {
+        let mut transform = VertexTransform::new(
+            VertexTransformKind::Scale,
+            square(),
+            Vector2::new(10.0, 0.0),
+        )
+        .unwrap();
+
+        // Cursor is twice as far from the pivot.
+        let scaled = transform.apply(Vector2::new(0.0, 20.0), Vector3::new(0.0, -1.0, 0.0));
+        assert_eq!(scaled[0].0, 0);
+        assert!(approx_eq(scaled[0].1, Vector3::new(0.0, 0.0, 0.0)));
+        assert!(approx_eq(scaled[2].1, Vector3::new(4.0, 0.0, 4.0)));
+
+        transform.toggle_axis(0);
+        let scaled = transform.apply(Vector2::new(0.0, 20.0), Vector3::new(0.0, -1.0, 0.0));
+        assert!(approx_eq(scaled[0].1, Vector3::new(0.0, 0.0, 1.0)));
+        assert!(approx_eq(scaled[2].1, Vector3::new(4.0, 0.0, 3.0)));
+
+        // Second toggle removes the constraint.
+        transform.toggle_axis(0);
+        assert_eq!(transform.axis, None);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let transform = VertexTransform::new(
+            VertexTransformKind::Rotate,
+            square(),
+            Vector2::new(10.0, 0.0),
+        )
+        .unwrap();
+
+        // The camera looks down, the cursor goes a quarter of a turn counterclockwise on the screen.
+        let look = Vector3::new(0.0, -1.0, 0.0);
+        let rotated = transform.apply(Vector2::new(0.0, -10.0), look);
+        assert!(approx_eq(rotated[0].1, Vector3::new(1.0, 0.0, 3.0)));
+        assert!(approx_eq(rotated[1].1, Vector3::new(1.0, 0.0, 1.0)));
+
+        // The same cursor movement seen from below rotates the other way.
+        let rotated = transform.apply(Vector2::new(0.0, -10.0), -look);
+        assert!(approx_eq(rotated[0].1, Vector3::new(3.0, 0.0, 1.0)));
+
+        // No cursor movement, no rotation.
+        let rotated = transform.apply(Vector2::new(10.0, 0.0), look);
+        for ((_, rotated), (_, initial)) in rotated.iter().zip(square().iter()) {
+            assert!(approx_eq(*rotated, *initial));
+        }
+    }
+}
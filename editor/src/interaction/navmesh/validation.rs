@@ -0,0 +1,705 @@
+//! Topology checks for navigational meshes. Hand-edited navmeshes could easily end up with broken
+//! geometry, which is hard to spot visually, but makes agents behave weird.
+
+use crate::interaction::navmesh::selection::NavmeshEntity;
+use fyrox::{
+    core::{
+        algebra::{Vector2, Vector3},
+        math::{TriangleDefinition, TriangleEdge},
+        pool::Handle,
+    },
+    scene::node::Node,
+};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeSet, HashMap},
+    fmt::{Display, Formatter},
+    time::{Duration, Instant},
+};
+
+/// Distance at which two vertices are considered the same by the validation.
+pub const NAVMESH_VALIDATION_EPSILON: f32 = 1.0e-3;
+
+/// Minimal cosine of the angle between normals of two triangles, which are checked for overlapping.
+/// Steeper pairs (a wall next to a floor, for example) have no meaningful common plane.
+const OVERLAP_MIN_NORMAL_COSINE: f32 = 0.7;
+
+/// How often overlapping triangles are searched, when it is done automatically.
+pub const OVERLAP_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NavmeshIssue {
+    /// Triangle has repeated (or invalid) vertex indices, or its area is zero.
+    DegenerateTriangle(usize),
+    /// Two vertices share the same position.
+    DuplicatedVertices(usize, usize),
+    /// Vertex is not used by any triangle.
+    UnreferencedVertex(usize),
+    /// Edge is shared by more than two triangles.
+    NonManifoldEdge {
+        edge: TriangleEdge,
+        triangles: Vec<usize>,
+    },
+    /// Two triangles cover the same area.
+    OverlappingTriangles(usize, usize),
+}
+
+impl Display for NavmeshIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NavmeshIssue::DegenerateTriangle(triangle) => {
+                write!(f, "Degenerate Triangle: {}", triangle)
+            }
+            NavmeshIssue::DuplicatedVertices(a, b) => {
+                write!(f, "Duplicated Vertices: {}, {}", a, b)
+            }
+            NavmeshIssue::UnreferencedVertex(vertex) => {
+                write!(f, "Unreferenced Vertex: {}", vertex)
+            }
+            NavmeshIssue::NonManifoldEdge { edge, triangles } => {
+                write!(
+                    f,
+                    "Non-Manifold Edge: {}-{} (triangles {:?})",
+                    edge.a, edge.b, triangles
+                )
+            }
+            NavmeshIssue::OverlappingTriangles(a, b) => {
+                write!(f, "Overlapping Triangles: {}, {}", a, b)
+            }
+        }
+    }
+}
+
+impl NavmeshIssue {
+    /// Returns indices of every vertex, that is involved in the issue.
+    pub fn vertices(&self, triangles: &[TriangleDefinition]) -> Vec<usize> {
+        match self {
+            NavmeshIssue::DegenerateTriangle(triangle) => triangles
+                .get(*triangle)
+                .map(|t| {
+                    t.indices()
+                        .iter()
+                        .map(|i| *i as usize)
+                        .collect::<BTreeSet<_>>()
+                        .into_iter()
+                        .collect()
+                })
+                .unwrap_or_default(),
+            NavmeshIssue::DuplicatedVertices(a, b) => vec![*a, *b],
+            NavmeshIssue::UnreferencedVertex(vertex) => vec![*vertex],
+            NavmeshIssue::NonManifoldEdge { edge, .. } => vec![edge.a as usize, edge.b as usize],
+            NavmeshIssue::OverlappingTriangles(a, b) => [*a, *b]
+                .iter()
+                .filter_map(|t| triangles.get(*t))
+                .flat_map(|t| t.indices().iter().map(|i| *i as usize))
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Returns navmesh entities, that should be selected to show the issue.
+    pub fn entities(&self, triangles: &[TriangleDefinition]) -> Vec<NavmeshEntity> {
+        match self {
+            NavmeshIssue::NonManifoldEdge { edge, .. } => vec![NavmeshEntity::Edge(*edge)],
+            // There's no way to select a triangle, so its edges are selected instead.
+            NavmeshIssue::OverlappingTriangles(a, b) => [*a, *b]
+                .iter()
+                .filter_map(|t| triangles.get(*t))
+                .flat_map(|t| t.edges())
+                .map(NavmeshEntity::Edge)
+                .collect(),
+            _ => self
+                .vertices(triangles)
+                .into_iter()
+                .map(NavmeshEntity::Vertex)
+                .collect(),
+        }
+    }
+}
+
+fn is_degenerate(vertices: &[Vector3<f32>], triangle: &TriangleDefinition, epsilon: f32) -> bool {
+    let [a, b, c] = triangle.0;
+    if a == b || b == c || a == c {
+        return true;
+    }
+
+    match (
+        vertices.get(a as usize),
+        vertices.get(b as usize),
+        vertices.get(c as usize),
+    ) {
+        (Some(a), Some(b), Some(c)) => (b - a).cross(&(c - a)).norm() * 0.5 <= epsilon * epsilon,
+        _ => true,
+    }
+}
+
+/// Finds every issue of the mesh. `epsilon` defines the distance at which two vertices are
+/// considered the same, triangles with area less than `epsilon²` are considered degenerate.
+pub fn validate_navmesh(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    epsilon: f32,
+) -> Vec<NavmeshIssue> {
+    let mut issues = Vec::new();
+
+    for (index, triangle) in triangles.iter().enumerate() {
+        if is_degenerate(vertices, triangle, epsilon) {
+            issues.push(NavmeshIssue::DegenerateTriangle(index));
+        }
+    }
+
+    // Sort vertices along X axis, so only a small window of neighbours has to be checked for
+    // every vertex.
+    let mut sorted = (0..vertices.len()).collect::<Vec<_>>();
+    sorted.sort_by(|a, b| {
+        vertices[*a]
+            .x
+            .partial_cmp(&vertices[*b].x)
+            .unwrap_or(Ordering::Equal)
+    });
+    let mut duplicates = Vec::new();
+    for (i, &a) in sorted.iter().enumerate() {
+        for &b in sorted[i + 1..].iter() {
+            if vertices[b].x - vertices[a].x > epsilon {
+                break;
+            }
+            if (vertices[b] - vertices[a]).norm() <= epsilon {
+                duplicates.push((a.min(b), a.max(b)));
+            }
+        }
+    }
+    duplicates.sort_unstable();
+    issues.extend(
+        duplicates
+            .into_iter()
+            .map(|(a, b)| NavmeshIssue::DuplicatedVertices(a, b)),
+    );
+
+    let mut referenced = vec![false; vertices.len()];
+    for triangle in triangles {
+        for &index in triangle.indices() {
+            if let Some(referenced) = referenced.get_mut(index as usize) {
+                *referenced = true;
+            }
+        }
+    }
+    issues.extend(
+        referenced
+            .iter()
+            .enumerate()
+            .filter(|(_, referenced)| !**referenced)
+            .map(|(index, _)| NavmeshIssue::UnreferencedVertex(index)),
+    );
+
+    // Edges are stored with sorted indices to make the result independent of triangle winding.
+    let mut edges = HashMap::<(u32, u32), Vec<usize>>::new();
+    for (index, triangle) in triangles.iter().enumerate() {
+        for edge in triangle.edges() {
+            if edge.a != edge.b {
+                let key = (edge.a.min(edge.b), edge.a.max(edge.b));
+                let edge_triangles = edges.entry(key).or_default();
+                if !edge_triangles.contains(&index) {
+                    edge_triangles.push(index);
+                }
+            }
+        }
+    }
+    let mut non_manifold = edges
+        .into_iter()
+        .filter(|(_, triangles)| triangles.len() > 2)
+        .collect::<Vec<_>>();
+    non_manifold.sort_unstable();
+    issues.extend(non_manifold.into_iter().map(|((a, b), triangles)| {
+        NavmeshIssue::NonManifoldEdge {
+            edge: TriangleEdge { a, b },
+            triangles,
+        }
+    }));
+
+    issues.extend(
+        overlapping_triangles(vertices, triangles, epsilon)
+            .into_iter()
+            .map(|(a, b)| NavmeshIssue::OverlappingTriangles(a, b)),
+    );
+
+    issues
+}
+
+/// Checks whether two triangles overlap in a plane, using separating axis test. Triangles, that
+/// only touch each other (by a vertex or by an edge) or overlap by less than `epsilon`, do not
+/// overlap.
+pub fn triangles_overlap_2d(a: &[Vector2<f32>; 3], b: &[Vector2<f32>; 3], epsilon: f32) -> bool {
+    let project = |triangle: &[Vector2<f32>; 3], axis: &Vector2<f32>| {
+        triangle
+            .iter()
+            .map(|p| p.dot(axis))
+            .fold((f32::MAX, f32::MIN), |(min, max), d| {
+                (min.min(d), max.max(d))
+            })
+    };
+
+    // Convex polygons don't overlap, if there's a separating axis among the normals of their edges.
+    for triangle in [a, b] {
+        for k in 0..3 {
+            let edge = triangle[(k + 1) % 3] - triangle[k];
+            if let Some(axis) = Vector2::new(-edge.y, edge.x).try_normalize(f32::EPSILON) {
+                let (a_min, a_max) = project(a, &axis);
+                let (b_min, b_max) = project(b, &axis);
+                if a_max <= b_min + epsilon || b_max <= a_min + epsilon {
+                    return false;
+                }
+            }
+        }
+    }
+
+    true
+}
+
+/// Checks whether two triangles overlap, when they're projected onto their common plane (the plane
+/// with the average normal of the triangles). Triangles, that are far from each other along the
+/// normal (floors of different storeys, for example), or that are too steep to each other, do not
+/// overlap. Degenerate triangles do not overlap anything.
+pub fn triangles_overlap_on_common_plane(
+    a: &[Vector3<f32>; 3],
+    b: &[Vector3<f32>; 3],
+    epsilon: f32,
+) -> bool {
+    let normal = |t: &[Vector3<f32>; 3]| {
+        (t[1] - t[0])
+            .cross(&(t[2] - t[0]))
+            .try_normalize(f32::EPSILON)
+    };
+    let (a_normal, mut b_normal) = match (normal(a), normal(b)) {
+        (Some(a_normal), Some(b_normal)) => (a_normal, b_normal),
+        _ => return false,
+    };
+
+    // Winding does not matter, a triangle folded over its neighbour overlaps it as well.
+    if a_normal.dot(&b_normal) < 0.0 {
+        b_normal = -b_normal;
+    }
+    if a_normal.dot(&b_normal) < OVERLAP_MIN_NORMAL_COSINE {
+        return false;
+    }
+    let normal = match (a_normal + b_normal).try_normalize(f32::EPSILON) {
+        Some(normal) => normal,
+        None => return false,
+    };
+
+    let range = |t: &[Vector3<f32>; 3]| {
+        t.iter()
+            .map(|p| p.dot(&normal))
+            .fold((f32::MAX, f32::MIN), |(min, max), d| {
+                (min.min(d), max.max(d))
+            })
+    };
+    let ((a_min, a_max), (b_min, b_max)) = (range(a), range(b));
+    if a_max + epsilon < b_min || b_max + epsilon < a_min {
+        return false;
+    }
+
+    // Any pair of orthogonal axes in the plane will do.
+    let u = if normal.x.abs() < 0.9 {
+        Vector3::x()
+    } else {
+        Vector3::y()
+    };
+    let u = normal.cross(&u).normalize();
+    let v = normal.cross(&u);
+    let project = |t: &[Vector3<f32>; 3]| t.map(|p| Vector2::new(p.dot(&u), p.dot(&v)));
+
+    triangles_overlap_2d(&project(a), &project(b), epsilon)
+}
+
+/// Finds pairs of overlapping triangles (see [`triangles_overlap_on_common_plane`]). Every pair is
+/// sorted, as well as the list itself.
+pub fn overlapping_triangles(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    epsilon: f32,
+) -> Vec<(usize, usize)> {
+    let corners = triangles
+        .iter()
+        .map(|t| {
+            Some([
+                *vertices.get(t[0] as usize)?,
+                *vertices.get(t[1] as usize)?,
+                *vertices.get(t[2] as usize)?,
+            ])
+        })
+        .collect::<Vec<_>>();
+
+    // Sort triangles by the beginning of their bounds along X axis, so only the triangles, whose
+    // bounds intersect along X axis, are checked against each other.
+    let x_range = |t: &[Vector3<f32>; 3]| {
+        (
+            t[0].x.min(t[1].x).min(t[2].x),
+            t[0].x.max(t[1].x).max(t[2].x),
+        )
+    };
+    let mut sorted = corners
+        .iter()
+        .enumerate()
+        .filter_map(|(index, t)| t.as_ref().map(|t| (x_range(t), index)))
+        .collect::<Vec<_>>();
+    sorted.sort_by(|a, b| (a.0).0.partial_cmp(&(b.0).0).unwrap_or(Ordering::Equal));
+
+    let mut pairs = Vec::new();
+    for (i, &((_, a_max), a)) in sorted.iter().enumerate() {
+        for &((b_min, _), b) in sorted[i + 1..].iter() {
+            if b_min > a_max + epsilon {
+                break;
+            }
+            if let (Some(ta), Some(tb)) = (&corners[a], &corners[b]) {
+                if triangles_overlap_on_common_plane(ta, tb, epsilon) {
+                    pairs.push((a.min(b), a.max(b)));
+                }
+            }
+        }
+    }
+    pairs.sort_unstable();
+    pairs
+}
+
+/// Overlapping triangles of a navmesh, that are highlighted in the navmesh edit mode. The state is
+/// shared between the validation window and the navmesh edit mode, both of them search overlaps.
+#[derive(Default)]
+pub struct NavmeshOverlaps {
+    navmesh_node: Handle<Node>,
+    triangles: Vec<usize>,
+    last_check: Option<Instant>,
+}
+
+impl NavmeshOverlaps {
+    /// Remembers triangles of the overlapping pairs of the given navmesh.
+    pub fn set(&mut self, navmesh_node: Handle<Node>, pairs: &[(usize, usize)]) {
+        self.navmesh_node = navmesh_node;
+        self.triangles = pairs
+            .iter()
+            .flat_map(|(a, b)| [*a, *b])
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        self.last_check = Some(Instant::now());
+    }
+
+    pub fn clear(&mut self) {
+        *self = Default::default();
+    }
+
+    /// Returns `true` if overlaps of the given navmesh were never searched or were searched too
+    /// long ago.
+    pub fn is_outdated(&self, navmesh_node: Handle<Node>) -> bool {
+        navmesh_node != self.navmesh_node
+            || !matches!(self.last_check, Some(time) if time.elapsed() < OVERLAP_CHECK_INTERVAL)
+    }
+
+    /// Returns sorted indices of overlapping triangles of the given navmesh.
+    pub fn triangles(&self, navmesh_node: Handle<Node>) -> &[usize] {
+        if navmesh_node == self.navmesh_node {
+            &self.triangles
+        } else {
+            &[]
+        }
+    }
+}
+
+/// A set of elements, that have to be removed to fix the mesh. Both lists are sorted in descending
+/// order, so the elements could be removed one-by-one without breaking indices of the rest.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NavmeshFix {
+    pub triangles: Vec<usize>,
+    pub vertices: Vec<usize>,
+}
+
+impl NavmeshFix {
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty() && self.vertices.is_empty()
+    }
+}
+
+/// Collects degenerate triangles and vertices, that are not used by any triangle once degenerate
+/// triangles are removed.
+pub fn plan_navmesh_fix(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    epsilon: f32,
+) -> NavmeshFix {
+    let degenerate = triangles
+        .iter()
+        .map(|t| is_degenerate(vertices, t, epsilon))
+        .collect::<Vec<_>>();
+
+    let mut referenced = vec![false; vertices.len()];
+    for (triangle, _) in triangles
+        .iter()
+        .zip(degenerate.iter())
+        .filter(|(_, degenerate)| !**degenerate)
+    {
+        for &index in triangle.indices() {
+            if let Some(referenced) = referenced.get_mut(index as usize) {
+                *referenced = true;
+            }
+        }
+    }
+
+    NavmeshFix {
+        triangles: (0..triangles.len())
+            .rev()
+            .filter(|i| degenerate[*i])
+            .collect(),
+        vertices: (0..vertices.len())
+            .rev()
+            .filter(|i| !referenced[*i])
+            .collect(),
+    }
+}
+
+/// Calculates total area of the triangles. Triangles with invalid indices or non-finite area are
+/// ignored.
+pub fn navmesh_area(vertices: &[Vector3<f32>], triangles: &[TriangleDefinition]) -> f32 {
+    triangles
+        .iter()
+        .filter_map(|triangle| {
+            let [a, b, c] = triangle.0;
+            match (
+                vertices.get(a as usize),
+                vertices.get(b as usize),
+                vertices.get(c as usize),
+            ) {
+                (Some(a), Some(b), Some(c)) => {
+                    let area = (b - a).cross(&(c - a)).norm() * 0.5;
+                    if area.is_finite() {
+                        Some(area)
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // 3 - 2
+    // | / |
+    // 0 - 1
+    fn quad() -> (Vec<Vector3<f32>>, Vec<TriangleDefinition>) {
+        (
+            vec![
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 1.0),
+                Vector3::new(0.0, 0.0, 1.0),
+            ],
+            vec![TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])],
+        )
+    }
+
+    #[test]
+    fn test_valid_mesh() {
+        let (vertices, triangles) = quad();
+        assert!(validate_navmesh(&vertices, &triangles, 0.001).is_empty());
+        assert!(plan_navmesh_fix(&vertices, &triangles, 0.001).is_empty());
+    }
+
+    #[test]
+    fn test_degenerate_triangles() {
+        let (mut vertices, mut triangles) = quad();
+        // Repeated index.
+        triangles.push(TriangleDefinition([1, 1, 2]));
+        // Zero area.
+        vertices.push(Vector3::new(0.5, 0.0, 0.0));
+        triangles.push(TriangleDefinition([0, 4, 1]));
+
+        assert_eq!(
+            validate_navmesh(&vertices, &triangles, 0.001),
+            vec![
+                NavmeshIssue::DegenerateTriangle(2),
+                NavmeshIssue::DegenerateTriangle(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_duplicated_and_unreferenced_vertices() {
+        let (mut vertices, triangles) = quad();
+        vertices.push(Vector3::new(1.0, 0.0, 1.0005));
+        vertices.push(Vector3::new(5.0, 0.0, 5.0));
+
+        assert_eq!(
+            validate_navmesh(&vertices, &triangles, 0.001),
+            vec![
+                NavmeshIssue::DuplicatedVertices(2, 4),
+                NavmeshIssue::UnreferencedVertex(4),
+                NavmeshIssue::UnreferencedVertex(5),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_non_manifold_edge() {
+        let (mut vertices, mut triangles) = quad();
+        // Third triangle on the diagonal edge.
+        vertices.push(Vector3::new(0.5, 1.0, 0.5));
+        triangles.push(TriangleDefinition([2, 0, 4]));
+
+        let issues = validate_navmesh(&vertices, &triangles, 0.001);
+        assert_eq!(
+            issues,
+            vec![NavmeshIssue::NonManifoldEdge {
+                edge: TriangleEdge { a: 0, b: 2 },
+                triangles: vec![0, 1, 2],
+            }]
+        );
+        assert_eq!(
+            issues[0].entities(&triangles),
+            vec![NavmeshEntity::Edge(TriangleEdge { a: 0, b: 2 })]
+        );
+    }
+
+    #[test]
+    fn test_area() {
+        let (mut vertices, mut triangles) = quad();
+        assert_eq!(navmesh_area(&vertices, &triangles), 1.0);
+
+        // Degenerate, invalid and infinite triangles add nothing.
+        vertices.push(Vector3::new(f32::INFINITY, 0.0, 0.0));
+        triangles.push(TriangleDefinition([0, 0, 1]));
+        triangles.push(TriangleDefinition([0, 1, 100]));
+        triangles.push(TriangleDefinition([0, 1, 4]));
+        assert_eq!(navmesh_area(&vertices, &triangles), 1.0);
+    }
+
+    #[test]
+    fn test_fix_plan() {
+        let (mut vertices, mut triangles) = quad();
+        // Vertex 4 is used only by a degenerate triangle, vertex 5 is not used at all.
+        vertices.push(Vector3::new(0.5, 0.0, 0.0));
+        vertices.push(Vector3::new(5.0, 0.0, 5.0));
+        triangles.push(TriangleDefinition([0, 4, 1]));
+        triangles.push(TriangleDefinition([3, 3, 2]));
+
+        assert_eq!(
+            plan_navmesh_fix(&vertices, &triangles, 0.001),
+            NavmeshFix {
+                triangles: vec![3, 2],
+                vertices: vec![5, 4],
+            }
+        );
+    }
+
+    // Right triangle with legs along X and Z axes.
+    fn right_triangle(origin: Vector3<f32>, size: f32) -> [Vector3<f32>; 3] {
+        [
+            origin,
+            origin + Vector3::new(size, 0.0, 0.0),
+            origin + Vector3::new(0.0, 0.0, size),
+        ]
+    }
+
+    #[test]
+    fn test_coplanar_overlap() {
+        let a = right_triangle(Vector3::new(0.0, 0.0, 0.0), 2.0);
+        let b = right_triangle(Vector3::new(0.5, 0.0, 0.5), 2.0);
+        assert!(triangles_overlap_on_common_plane(&a, &b, 0.001));
+        // Winding does not matter.
+        assert!(triangles_overlap_on_common_plane(
+            &a,
+            &[b[0], b[2], b[1]],
+            0.001
+        ));
+
+        // Same triangles on a tilted plane.
+        let tilt = |p: Vector3<f32>| Vector3::new(p.x, 0.5 * p.x + 0.25 * p.z, p.z);
+        assert!(triangles_overlap_on_common_plane(
+            &a.map(tilt),
+            &b.map(tilt),
+            0.001
+        ));
+    }
+
+    #[test]
+    fn test_shared_edge_is_not_overlap() {
+        let (vertices, triangles) = quad();
+        let corners = |t: &TriangleDefinition| t.0.map(|i| vertices[i as usize]);
+        assert!(!triangles_overlap_on_common_plane(
+            &corners(&triangles[0]),
+            &corners(&triangles[1]),
+            0.001
+        ));
+
+        // Triangle folded over the shared edge covers its neighbour.
+        let folded = [vertices[0], vertices[2], Vector3::new(0.9, 0.0, 0.1)];
+        assert!(triangles_overlap_on_common_plane(
+            &corners(&triangles[0]),
+            &folded,
+            0.001
+        ));
+    }
+
+    #[test]
+    fn test_disjoint_triangles() {
+        let a = right_triangle(Vector3::new(0.0, 0.0, 0.0), 1.0);
+        let b = right_triangle(Vector3::new(5.0, 0.0, 0.0), 1.0);
+        assert!(!triangles_overlap_on_common_plane(&a, &b, 0.001));
+
+        // Floor of the next storey is right above, but it is not an overlap.
+        let c = right_triangle(Vector3::new(0.0, 3.0, 0.0), 1.0);
+        assert!(!triangles_overlap_on_common_plane(&a, &c, 0.001));
+
+        // Degenerate triangle overlaps nothing.
+        let d = [a[0], a[1], a[1]];
+        assert!(!triangles_overlap_on_common_plane(&a, &d, 0.001));
+    }
+
+    #[test]
+    fn test_overlapping_triangles() {
+        let (mut vertices, mut triangles) = quad();
+        assert!(overlapping_triangles(&vertices, &triangles, 0.001).is_empty());
+
+        // Triangle over the middle of the quad.
+        vertices.extend(right_triangle(Vector3::new(0.25, 0.0, 0.25), 0.5));
+        triangles.push(TriangleDefinition([4, 6, 5]));
+
+        assert_eq!(
+            overlapping_triangles(&vertices, &triangles, 0.001),
+            vec![(0, 2), (1, 2)]
+        );
+
+        let issues = validate_navmesh(&vertices, &triangles, 0.001);
+        assert_eq!(
+            issues,
+            vec![
+                NavmeshIssue::OverlappingTriangles(0, 2),
+                NavmeshIssue::OverlappingTriangles(1, 2),
+            ]
+        );
+        assert_eq!(issues[0].vertices(&triangles), vec![0, 1, 2, 4, 5, 6]);
+        assert_eq!(issues[0].entities(&triangles).len(), 6);
+    }
+
+    #[test]
+    fn test_overlaps_state() {
+        let navmesh = Handle::new(1, 1);
+        let mut overlaps = NavmeshOverlaps::default();
+        assert!(overlaps.is_outdated(navmesh));
+
+        overlaps.set(navmesh, &[(2, 0), (1, 2)]);
+        assert!(!overlaps.is_outdated(navmesh));
+        assert_eq!(overlaps.triangles(navmesh), &[0, 1, 2]);
+
+        // Overlaps of some other navmesh are not shown.
+        let other = Handle::new(2, 1);
+        assert!(overlaps.is_outdated(other));
+        assert!(overlaps.triangles(other).is_empty());
+
+        overlaps.clear();
+        assert!(overlaps.triangles(navmesh).is_empty());
+    }
+}
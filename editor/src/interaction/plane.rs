@@ -1,7 +1,7 @@
 use fyrox::core::{algebra::Vector3, math::plane::Plane, num_traits::Zero};
 use strum_macros::EnumIter;
 
-#[derive(Copy, Clone, Debug, EnumIter)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, EnumIter)]
 pub enum PlaneKind {
     X,
     Y,
@@ -0,0 +1,97 @@
+use fyrox::{
+    core::{algebra::Vector2, algebra::Vector3, color::Color, pool::Handle},
+    gui::{
+        brush::Brush,
+        message::MessageDirection,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        BuildContext, UiNode, UserInterface,
+    },
+    scene::camera::Camera,
+};
+
+/// A few lines of text, that are attached to a point in the world and drawn on top of the scene
+/// viewer. Interaction modes and viewport tools use it to annotate the scene (distances, offsets,
+/// etc.) while the user is working with it.
+pub struct ViewportLabel {
+    panel: Handle<UiNode>,
+    lines: Vec<Handle<UiNode>>,
+}
+
+impl ViewportLabel {
+    pub fn new(ctx: &mut BuildContext, line_count: usize) -> Self {
+        let lines = (0..line_count)
+            .map(|_| TextBuilder::new(WidgetBuilder::new()).build(ctx))
+            .collect::<Vec<_>>();
+
+        let panel = StackPanelBuilder::new(
+            WidgetBuilder::new()
+                .with_visibility(false)
+                .with_hit_test_visibility(false)
+                .with_children(lines.iter().cloned()),
+        )
+        .build(ctx);
+
+        Self { panel, lines }
+    }
+
+    pub fn set_line(&self, ui: &UserInterface, index: usize, text: String, color: Color) {
+        if let Some(line) = self.lines.get(index) {
+            ui.send_message(TextMessage::text(*line, MessageDirection::ToWidget, text));
+            ui.send_message(WidgetMessage::foreground(
+                *line,
+                MessageDirection::ToWidget,
+                Brush::Solid(color),
+            ));
+        }
+    }
+
+    /// Moves the label to the screen projection of `world_position`. `frame_position` and
+    /// `frame_size` are the screen bounds of the frame, that shows the image from the `camera`.
+    /// The label is hidden if the point is behind the camera.
+    pub fn place(
+        &self,
+        ui: &UserInterface,
+        camera: &Camera,
+        world_position: Vector3<f32>,
+        frame_position: Vector2<f32>,
+        frame_size: Vector2<f32>,
+    ) {
+        match camera.project(world_position, frame_size) {
+            Some(screen_position) => {
+                self.place_on_screen(ui, frame_position + screen_position);
+            }
+            None => self.set_visible(ui, false),
+        }
+    }
+
+    /// Shows the label at the given screen position.
+    pub fn place_on_screen(&self, ui: &UserInterface, screen_position: Vector2<f32>) {
+        ui.send_message(WidgetMessage::desired_position(
+            self.panel,
+            MessageDirection::ToWidget,
+            screen_position,
+        ));
+        ui.send_message(WidgetMessage::topmost(
+            self.panel,
+            MessageDirection::ToWidget,
+        ));
+        self.set_visible(ui, true);
+    }
+
+    pub fn set_visible(&self, ui: &UserInterface, visible: bool) {
+        ui.send_message(WidgetMessage::visibility(
+            self.panel,
+            MessageDirection::ToWidget,
+            visible,
+        ));
+    }
+
+    pub fn destroy(&self, ui: &UserInterface) {
+        ui.send_message(WidgetMessage::remove(
+            self.panel,
+            MessageDirection::ToWidget,
+        ));
+    }
+}
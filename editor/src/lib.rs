@@ -50,7 +50,10 @@ use crate::{
     inspector::{editors::handle::HandlePropertyEditorMessage, Inspector},
     interaction::{
         move_mode::MoveInteractionMode,
-        navmesh::{EditNavmeshMode, NavmeshPanel},
+        navmesh::{
+            links::NavmeshLinkTool, panel::NavmeshPanel, path_test::NavmeshPathTest,
+            validation::NavmeshOverlaps, EditNavmeshMode,
+        },
         rotate_mode::RotateInteractionMode,
         scale_mode::ScaleInteractionMode,
         select_mode::SelectInteractionMode,
@@ -441,7 +444,21 @@ pub struct EditorSceneEntry {
 }
 
 impl EditorSceneEntry {
+    pub fn can_activate_interaction_mode(
+        &self,
+        engine: &Engine,
+        mode: InteractionModeKind,
+    ) -> bool {
+        self.interaction_modes[mode as usize].can_activate(&self.editor_scene, engine)
+    }
+
     fn set_interaction_mode(&mut self, engine: &mut Engine, mode: Option<InteractionModeKind>) {
+        if let Some(mode) = mode {
+            if !self.can_activate_interaction_mode(engine, mode) {
+                return;
+            }
+        }
+
         if self.current_interaction_mode != mode {
             // Deactivate current first.
             if let Some(current_mode) = self.current_interaction_mode {
@@ -566,6 +583,9 @@ impl SceneContainer {
         settings: &Settings,
         message_sender: MessageSender,
         scene_viewer: &SceneViewer,
+        navmesh_path_test: Rc<RefCell<NavmeshPathTest>>,
+        navmesh_link_tool: Rc<RefCell<NavmeshLinkTool>>,
+        navmesh_overlaps: Rc<RefCell<NavmeshOverlaps>>,
     ) {
         self.current_scene = Some(self.scenes.len());
 
@@ -597,6 +617,10 @@ impl SceneContainer {
                     &editor_scene,
                     engine,
                     message_sender.clone(),
+                    scene_viewer.frame(),
+                    navmesh_path_test,
+                    navmesh_link_tool,
+                    navmesh_overlaps,
                 )),
                 Box::new(TerrainInteractionMode::new(
                     &editor_scene,
@@ -833,7 +857,7 @@ impl Editor {
         let audio_panel = AudioPanel::new(&mut engine);
 
         let ctx = &mut engine.user_interface.build_ctx();
-        let navmesh_panel = NavmeshPanel::new(ctx, message_sender.clone());
+        let navmesh_panel = NavmeshPanel::new(ctx, message_sender.clone(), &settings);
         let world_outliner = WorldViewer::new(ctx, message_sender.clone(), &settings);
         let command_stack_viewer = CommandStackViewer::new(ctx, message_sender.clone());
         let log = LogPanel::new(ctx, log_message_receiver);
@@ -845,7 +869,7 @@ impl Editor {
         let audio_preview_panel = AudioPreviewPanel::new(ctx);
         let doc_window = DocWindow::new(ctx);
         let node_removal_dialog = NodeRemovalDialog::new(ctx);
-        let ragdoll_wizard = RagdollWizard::new(ctx, message_sender.clone());
+        let ragdoll_wizard = RagdollWizard::new(ctx, message_sender.clone(), &settings);
 
         let docking_manager;
         let root_grid = GridBuilder::new(
@@ -1158,6 +1182,9 @@ impl Editor {
             &self.settings,
             self.message_sender.clone(),
             &self.scene_viewer,
+            self.navmesh_panel.path_test(),
+            self.navmesh_panel.link_tool(),
+            self.navmesh_panel.overlaps(),
         );
 
         if let Some(path) = path.as_ref() {
@@ -1201,6 +1228,10 @@ impl Editor {
 
             if hot_key == key_bindings.redo {
                 sender.send(Message::RedoSceneCommand);
+            } else if hot_key == key_bindings.step_command {
+                sender.send(Message::StepSceneCommand);
+            } else if hot_key == key_bindings.abort_command {
+                sender.send(Message::AbortSceneCommand);
             } else if hot_key == key_bindings.undo {
                 sender.send(Message::UndoSceneCommand);
             } else if hot_key == key_bindings.enable_select_mode {
@@ -1369,6 +1400,10 @@ impl Editor {
                 &mut engine.scenes[editor_scene.scene].graph,
                 editor_scene,
                 &self.message_sender,
+                &engine.resource_manager,
+                self.scene_viewer.frame(),
+                &mut self.settings,
+                current_scene_entry.current_interaction_mode,
             );
             self.particle_system_control_panel
                 .handle_ui_message(message, editor_scene, engine);
@@ -1386,10 +1421,15 @@ impl Editor {
                 engine,
                 &self.message_sender,
             );
-            self.scene_settings
-                .handle_ui_message(message, &self.message_sender);
+            self.scene_settings.handle_ui_message(
+                message,
+                editor_scene,
+                engine,
+                &self.message_sender,
+            );
 
-            self.navmesh_panel.handle_message(message, editor_scene);
+            self.navmesh_panel
+                .handle_message(message, editor_scene, engine, &mut self.settings);
 
             self.inspector
                 .handle_ui_message(message, editor_scene, engine, &self.message_sender);
@@ -1653,16 +1693,33 @@ impl Editor {
         if let Some(current_scene_entry) = self.scenes.current_scene_entry_mut() {
             let editor_scene = &mut current_scene_entry.editor_scene;
 
-            current_scene_entry.command_stack.do_command(
-                command.into_inner(),
-                SceneContext {
-                    scene: &mut engine.scenes[editor_scene.scene],
-                    message_sender: self.message_sender.clone(),
-                    editor_scene,
-                    resource_manager: engine.resource_manager.clone(),
-                    serialization_context: engine.serialization_context.clone(),
-                },
-            );
+            let step_through =
+                self.command_stack_viewer.is_step_through_enabled() && command.step_count() > 1;
+
+            current_scene_entry
+                .command_stack
+                .set_limits(self.settings.general.command_stack_limits());
+            current_scene_entry
+                .command_stack
+                .set_merge_window(self.settings.general.command_merge_window());
+
+            let context = SceneContext {
+                scene: &mut engine.scenes[editor_scene.scene],
+                message_sender: self.message_sender.clone(),
+                editor_scene,
+                resource_manager: engine.resource_manager.clone(),
+                serialization_context: engine.serialization_context.clone(),
+            };
+
+            if step_through {
+                current_scene_entry
+                    .command_stack
+                    .begin_stepping(command.into_inner(), context);
+            } else {
+                current_scene_entry
+                    .command_stack
+                    .do_command(command.into_inner(), context);
+            }
 
             editor_scene.has_unsaved_changes = true;
 
@@ -1693,6 +1750,46 @@ impl Editor {
         }
     }
 
+    fn step_scene_command(&mut self) -> bool {
+        let engine = &mut self.engine;
+        if let Some(current_scene_entry) = self.scenes.current_scene_entry_mut() {
+            let editor_scene = &mut current_scene_entry.editor_scene;
+
+            current_scene_entry.command_stack.step(SceneContext {
+                scene: &mut engine.scenes[editor_scene.scene],
+                message_sender: self.message_sender.clone(),
+                editor_scene,
+                resource_manager: engine.resource_manager.clone(),
+                serialization_context: engine.serialization_context.clone(),
+            });
+
+            editor_scene.has_unsaved_changes = true;
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn abort_scene_command(&mut self) -> bool {
+        let engine = &mut self.engine;
+        if let Some(current_scene_entry) = self.scenes.current_scene_entry_mut() {
+            let editor_scene = &mut current_scene_entry.editor_scene;
+
+            current_scene_entry.command_stack.abort(SceneContext {
+                scene: &mut engine.scenes[editor_scene.scene],
+                message_sender: self.message_sender.clone(),
+                editor_scene,
+                resource_manager: engine.resource_manager.clone(),
+                serialization_context: engine.serialization_context.clone(),
+            });
+
+            true
+        } else {
+            false
+        }
+    }
+
     fn redo_scene_command(&mut self) -> bool {
         let engine = &mut self.engine;
         if let Some(current_scene_entry) = self.scenes.current_scene_entry_mut() {
@@ -1732,6 +1829,25 @@ impl Editor {
         }
     }
 
+    fn show_command_stack_statistics(&mut self) {
+        let engine = &mut self.engine;
+        if let Some(current_scene_entry) = self.scenes.current_scene_entry_mut() {
+            let editor_scene = &mut current_scene_entry.editor_scene;
+
+            let statistics = current_scene_entry.command_stack.statistics(&SceneContext {
+                scene: &mut engine.scenes[editor_scene.scene],
+                message_sender: self.message_sender.clone(),
+                editor_scene,
+                resource_manager: engine.resource_manager.clone(),
+                serialization_context: engine.serialization_context.clone(),
+            });
+
+            Log::info(format!("Command stack statistics. {}", statistics));
+        } else {
+            Log::warn("There is no scene to show command stack statistics of!");
+        }
+    }
+
     fn try_leave_preview_mode(&mut self) {
         if let Some(editor_scene) = self.scenes.current_editor_scene_mut() {
             let engine = &mut self.engine;
@@ -2134,7 +2250,8 @@ impl Editor {
                         .handle_message(&message, editor_scene, &mut self.engine);
                 }
 
-                self.scene_viewer.handle_message(&message, &mut self.engine);
+                self.scene_viewer
+                    .handle_message(&message, &self.scenes, &mut self.engine);
 
                 match message {
                     Message::DoSceneCommand(command) => {
@@ -2149,6 +2266,15 @@ impl Editor {
                     Message::ClearSceneCommandStack => {
                         needs_sync |= self.clear_scene_command_stack();
                     }
+                    Message::ShowCommandStackStatistics => {
+                        self.show_command_stack_statistics();
+                    }
+                    Message::StepSceneCommand => {
+                        needs_sync |= self.step_scene_command();
+                    }
+                    Message::AbortSceneCommand => {
+                        needs_sync |= self.abort_scene_command();
+                    }
                     Message::SelectionChanged { .. } => {
                         self.world_viewer.sync_selection = true;
                     }
@@ -2212,6 +2338,12 @@ impl Editor {
                             editor_scene.camera_controller.fit_object(scene, handle);
                         }
                     }
+                    Message::FocusBounds(aabb) => {
+                        if let Some(editor_scene) = self.scenes.current_editor_scene_ref() {
+                            let scene = &mut self.engine.scenes[editor_scene.scene];
+                            editor_scene.camera_controller.fit_aabb(scene, &aabb);
+                        }
+                    }
                     Message::SetEditorCameraProjection(projection) => {
                         if let Some(editor_scene) = self.scenes.current_editor_scene_ref() {
                             editor_scene.camera_controller.set_projection(
@@ -2276,6 +2408,14 @@ impl Editor {
                     Message::LoadLayout => {
                         self.load_layout();
                     }
+                    Message::NavmeshGenerationProgress(progress) => {
+                        self.navmesh_panel
+                            .set_generation_progress(&self.engine.user_interface, progress);
+                    }
+                    Message::RagdollGenerationProgress(progress) => {
+                        self.ragdoll_wizard
+                            .set_generation_progress(&self.engine.user_interface, progress);
+                    }
                 }
             }
 
@@ -2303,6 +2443,10 @@ impl Editor {
 
             self.absm_editor.update(editor_scene, &mut self.engine);
 
+            self.ragdoll_wizard.update(editor_scene, &mut self.engine);
+
+            self.navmesh_panel.update(editor_scene, &self.engine);
+
             let scene = &self.engine.scenes[editor_scene.scene];
 
             // Save camera current camera settings for current scene to be able to load them
@@ -2322,6 +2466,20 @@ impl Editor {
                         .camera_settings
                         .insert(path.clone(), last_settings);
                 }
+
+                self.settings
+                    .navmesh
+                    .set_scene_settings(path, editor_scene.navmesh_settings.clone());
+
+                if editor_scene.navmesh.is_some() {
+                    self.settings.navmesh.edited_navmeshes.insert(
+                        path.clone(),
+                        [
+                            editor_scene.navmesh.index(),
+                            editor_scene.navmesh.generation(),
+                        ],
+                    );
+                }
             }
 
             if let Some(mode) = editor_scene_entry.current_interaction_mode {
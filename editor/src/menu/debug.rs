@@ -0,0 +1,42 @@
+use crate::{
+    menu::{create_menu_item, create_root_menu_item},
+    message::MessageSender,
+    Message,
+};
+use fyrox::{
+    core::pool::Handle,
+    gui::{menu::MenuItemMessage, message::UiMessage, BuildContext, UiNode},
+};
+
+pub struct DebugMenu {
+    pub menu: Handle<UiNode>,
+    command_stack_statistics: Handle<UiNode>,
+}
+
+impl DebugMenu {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let command_stack_statistics;
+        let menu = create_root_menu_item(
+            "Debug",
+            vec![{
+                command_stack_statistics =
+                    create_menu_item("Command Stack Statistics", vec![], ctx);
+                command_stack_statistics
+            }],
+            ctx,
+        );
+
+        Self {
+            menu,
+            command_stack_statistics,
+        }
+    }
+
+    pub fn handle_ui_message(&mut self, message: &UiMessage, sender: &MessageSender) {
+        if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
+            if message.destination() == self.command_stack_statistics {
+                sender.send(Message::ShowCommandStackStatistics);
+            }
+        }
+    }
+}
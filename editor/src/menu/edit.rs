@@ -1,7 +1,8 @@
 use crate::{
-    menu::{create_menu_item_shortcut, create_root_menu_item},
+    menu::{create_menu_item, create_menu_item_shortcut, create_root_menu_item},
     message::MessageSender,
     scene::{commands::PasteCommand, EditorScene, Selection},
+    world::graph::query::NodeQueryDialog,
     Engine, Message, Mode,
 };
 use fyrox::{
@@ -20,6 +21,8 @@ pub struct EditMenu {
     redo: Handle<UiNode>,
     copy: Handle<UiNode>,
     paste: Handle<UiNode>,
+    select_by_query: Handle<UiNode>,
+    query_dialog: NodeQueryDialog,
 }
 
 impl EditMenu {
@@ -28,6 +31,7 @@ impl EditMenu {
         let undo;
         let copy;
         let paste;
+        let select_by_query;
         let menu = create_root_menu_item(
             "Edit",
             vec![
@@ -47,6 +51,10 @@ impl EditMenu {
                     paste = create_menu_item_shortcut("Paste", "Ctrl+V", vec![], ctx);
                     paste
                 },
+                {
+                    select_by_query = create_menu_item("Select By Query...", vec![], ctx);
+                    select_by_query
+                },
             ],
             ctx,
         );
@@ -57,6 +65,8 @@ impl EditMenu {
             redo,
             copy,
             paste,
+            select_by_query,
+            query_dialog: NodeQueryDialog::new(ctx),
         }
     }
 
@@ -67,6 +77,9 @@ impl EditMenu {
         editor_scene: &mut EditorScene,
         engine: &mut Engine,
     ) {
+        self.query_dialog
+            .handle_ui_message(message, editor_scene, engine, sender);
+
         if let Some(MenuItemMessage::Click) = message.data::<MenuItemMessage>() {
             if message.destination() == self.copy {
                 if let Selection::Graph(selection) = &editor_scene.selection {
@@ -84,6 +97,8 @@ impl EditMenu {
                 sender.send(Message::UndoSceneCommand);
             } else if message.destination() == self.redo {
                 sender.send(Message::RedoSceneCommand);
+            } else if message.destination() == self.select_by_query {
+                self.query_dialog.open(&engine.user_interface);
             }
         }
     }
@@ -1,8 +1,8 @@
 use crate::{
     animation::AnimationEditor,
     menu::{
-        create::CreateEntityRootMenu, edit::EditMenu, file::FileMenu, help::HelpMenu,
-        utils::UtilsMenu, view::ViewMenu,
+        create::CreateEntityRootMenu, debug::DebugMenu, edit::EditMenu, file::FileMenu,
+        help::HelpMenu, utils::UtilsMenu, view::ViewMenu,
     },
     message::MessageSender,
     scene::EditorScene,
@@ -23,6 +23,7 @@ use fyrox::{
 
 pub mod animation;
 pub mod create;
+pub mod debug;
 pub mod dim2;
 pub mod edit;
 pub mod file;
@@ -40,6 +41,7 @@ pub struct Menu {
     view_menu: ViewMenu,
     message_sender: MessageSender,
     utils_menu: UtilsMenu,
+    debug_menu: DebugMenu,
     help_menu: HelpMenu,
 }
 
@@ -110,6 +112,7 @@ impl Menu {
         let edit_menu = EditMenu::new(ctx);
         let view_menu = ViewMenu::new(ctx);
         let utils_menu = UtilsMenu::new(ctx);
+        let debug_menu = DebugMenu::new(ctx);
         let help_menu = HelpMenu::new(ctx);
 
         let menu = MenuBuilder::new(WidgetBuilder::new().on_row(0))
@@ -119,6 +122,7 @@ impl Menu {
                 create_entity_menu.menu,
                 view_menu.menu,
                 utils_menu.menu,
+                debug_menu.menu,
                 help_menu.menu,
             ])
             .build(ctx);
@@ -131,6 +135,7 @@ impl Menu {
             file_menu,
             view_menu,
             utils_menu,
+            debug_menu,
             help_menu,
         }
     }
@@ -193,6 +198,8 @@ impl Menu {
             &ctx.panels,
             &self.message_sender,
         );
+        self.debug_menu
+            .handle_ui_message(message, &self.message_sender);
         self.help_menu.handle_ui_message(message);
     }
 
@@ -5,6 +5,7 @@ use crate::{
 use fyrox::{
     core::{
         log::Log,
+        math::aabb::AxisAlignedBoundingBox,
         pool::{ErasedHandle, Handle},
     },
     gui::UiNode,
@@ -19,6 +20,12 @@ pub enum Message {
     UndoSceneCommand,
     RedoSceneCommand,
     ClearSceneCommandStack,
+    /// Executes next step of the scene command, that is being executed step-by-step.
+    StepSceneCommand,
+    /// Reverts every executed step of the scene command, that is being executed step-by-step.
+    AbortSceneCommand,
+    /// Prints memory usage of the command stack of the current scene to the log.
+    ShowCommandStackStatistics,
     SelectionChanged {
         old_selection: Selection,
     },
@@ -53,6 +60,8 @@ pub enum Message {
     },
     SetCurrentScene(Handle<Scene>),
     FocusObject(Handle<Node>),
+    /// Moves the editor camera so that the given world-space bounds are visible.
+    FocusBounds(AxisAlignedBoundingBox),
     SetEditorCameraProjection(Projection),
     SwitchToBuildMode,
     SwitchToEditMode,
@@ -73,6 +82,11 @@ pub enum Message {
     ShowDocumentation(String),
     SaveLayout,
     LoadLayout,
+    /// Sent every frame while a navmesh is being generated, the value is in `[0; 1]` range.
+    NavmeshGenerationProgress(f32),
+    /// Sent after every character while ragdolls are generated for the selected characters, the
+    /// value is in `[0; 1]` range.
+    RagdollGenerationProgress(f32),
 }
 
 #[derive(Clone, Debug)]
@@ -1,4 +1,7 @@
-use crate::{define_universal_commands, scene::commands::SceneCommand, Command, SceneContext};
+use crate::{
+    command::EntityNameProvider, define_universal_commands, scene::commands::SceneCommand, Command,
+    SceneContext,
+};
 use fyrox::{
     core::{
         pool::{Handle, Ticket},
@@ -7,6 +10,22 @@ use fyrox::{
     scene::sound::AudioBus,
 };
 
+/// Names audio buses, so the names of the commands tell which bus they change.
+pub struct AudioBusNames;
+
+impl<'a> EntityNameProvider<SceneContext<'a>, Handle<AudioBus>> for AudioBusNames {
+    fn entity_name(context: &SceneContext<'a>, handle: Handle<AudioBus>) -> Option<String> {
+        context
+            .scene
+            .graph
+            .sound_context
+            .state()
+            .bus_graph_ref()
+            .try_get_bus_ref(handle)
+            .map(|bus| bus.name().to_owned())
+    }
+}
+
 define_universal_commands!(
     make_set_audio_bus_property_command,
     Command,
@@ -25,6 +44,7 @@ define_universal_commands!(
             .try_get_bus_mut(self.handle)
             .unwrap()
     },
+    entity_name: AudioBusNames,
 );
 
 #[derive(Debug)]
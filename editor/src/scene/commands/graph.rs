@@ -221,6 +221,14 @@ impl Command for DeleteNodeCommand {
     }
 }
 
+/// Estimated amount of memory occupied by the nodes of the sub-graph.
+fn sub_graph_size(sub_graph: &SubGraph) -> usize {
+    std::iter::once(&sub_graph.root)
+        .chain(sub_graph.descendants.iter())
+        .map(|(_, node)| std::mem::size_of_val(&**node))
+        .sum()
+}
+
 #[derive(Debug)]
 pub struct AddModelCommand {
     model: Handle<Node>,
@@ -259,6 +267,10 @@ impl Command for AddModelCommand {
             context.scene.graph.forget_sub_graph(sub_graph)
         }
     }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of_val(self) + self.sub_graph.as_ref().map_or(0, sub_graph_size)
+    }
 }
 
 #[derive(Debug)]
@@ -309,6 +321,10 @@ impl Command for DeleteSubGraphCommand {
             context.scene.graph.forget_sub_graph(sub_graph)
         }
     }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of_val(self) + self.sub_graph.as_ref().map_or(0, sub_graph_size)
+    }
 }
 
 #[derive(Debug)]
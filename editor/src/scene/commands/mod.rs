@@ -1,6 +1,6 @@
 use crate::message::MessageSender;
 use crate::{
-    command::Command,
+    command::{Command, EntityNameProvider},
     define_universal_commands,
     scene::{
         clipboard::DeepCloneResult, commands::graph::DeleteSubGraphCommand, EditorScene,
@@ -8,10 +8,9 @@ use crate::{
     },
     Engine, Message,
 };
-use fyrox::core::variable::mark_inheritable_properties_non_modified;
 use fyrox::{
     asset::manager::ResourceManager,
-    core::{log::Log, pool::Handle, reflect::prelude::*},
+    core::{pool::Handle, reflect::prelude::*},
     engine::SerializationContext,
     scene::{graph::SubGraph, node::Node, Scene},
 };
@@ -129,11 +128,62 @@ impl Command for CommandGroup {
         }
     }
 
+    fn step_count(&self) -> usize {
+        self.commands.len()
+    }
+
+    fn execute_step(&mut self, step: usize, context: &mut SceneContext) {
+        self.commands[step].execute(context);
+    }
+
+    fn revert_step(&mut self, step: usize, context: &mut SceneContext) {
+        self.commands[step].revert(context);
+    }
+
     fn finalize(&mut self, context: &mut SceneContext) {
         for mut cmd in self.commands.drain(..) {
             cmd.finalize(context);
         }
     }
+
+    fn is_noop(&self) -> bool {
+        self.commands.iter().all(|cmd| cmd.is_noop())
+    }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + self
+                .commands
+                .iter()
+                .map(|cmd| cmd.estimated_size())
+                .sum::<usize>()
+    }
+
+    fn can_merge(&self, other: &dyn Command) -> bool {
+        // Groups are merged only if every command of the other group could be merged into the
+        // respective command of this group.
+        match other.as_any().downcast_ref::<CommandGroup>() {
+            Some(other) => {
+                !self.commands.is_empty()
+                    && self.custom_name == other.custom_name
+                    && self.commands.len() == other.commands.len()
+                    && self
+                        .commands
+                        .iter()
+                        .zip(other.commands.iter())
+                        .all(|(cmd, other)| cmd.can_merge(&**other))
+            }
+            None => false,
+        }
+    }
+
+    fn merge(&mut self, other: &dyn Command) {
+        if let Some(other) = other.as_any().downcast_ref::<CommandGroup>() {
+            for (cmd, other) in self.commands.iter_mut().zip(other.commands.iter()) {
+                cmd.merge(&**other);
+            }
+        }
+    }
 }
 
 pub fn selection_to_delete(editor_scene: &EditorScene) -> GraphSelection {
@@ -360,130 +410,16 @@ impl Command for PasteCommand {
     }
 }
 
-#[derive(Debug)]
-pub struct RevertSceneNodePropertyCommand {
-    path: String,
-    handle: Handle<Node>,
-    value: Option<Box<dyn Reflect>>,
-}
+/// Names scene nodes, so the names of the commands tell which node they change.
+pub struct NodeNames;
 
-impl RevertSceneNodePropertyCommand {
-    pub fn new(path: String, handle: Handle<Node>) -> Self {
-        Self {
-            path,
-            handle,
-            value: None,
-        }
-    }
-}
-
-fn reset_property_modified_flag(entity: &mut dyn Reflect, path: &str) {
-    entity.as_reflect_mut(&mut |entity| {
-        entity.resolve_path_mut(path, &mut |result| {
-            mark_inheritable_properties_non_modified(result.unwrap());
-        })
-    })
-}
-
-impl Command for RevertSceneNodePropertyCommand {
-    fn name(&mut self, _context: &SceneContext) -> String {
-        format!("Revert {} Property", self.path)
-    }
-
-    fn execute(&mut self, context: &mut SceneContext) {
-        let child = &mut context.scene.graph[self.handle];
-
-        // Revert only if there's parent resource (the node is an instance of some resource).
-        if let Some(resource) = child.resource().as_ref() {
-            let resource_data = resource.data_ref();
-            let parent = &resource_data.get_scene().graph[child.original_handle_in_resource()];
-
-            let mut parent_value = None;
-
-            // Find and clone parent's value first.
-            parent.as_reflect(&mut |parent| {
-                parent.resolve_path(&self.path, &mut |result| match result {
-                    Ok(parent_field) => parent_field.as_inheritable_variable(&mut |parent_field| {
-                        if let Some(parent_inheritable) = parent_field {
-                            parent_value = Some(parent_inheritable.clone_value_box());
-                        }
-                    }),
-                    Err(e) => Log::err(format!(
-                        "Failed to resolve parent path {}. Reason: {:?}",
-                        self.path, e
-                    )),
-                })
-            });
-
-            // Check whether the child's field is inheritable and modified.
-            let mut need_revert = false;
-
-            child.as_reflect_mut(&mut |child| {
-                child.resolve_path_mut(&self.path, &mut |result| match result {
-                    Ok(child_field) => {
-                        child_field.as_inheritable_variable_mut(&mut |child_inheritable| {
-                            if let Some(child_inheritable) = child_inheritable {
-                                need_revert = child_inheritable.is_modified();
-                            } else {
-                                Log::err(format!("Property {} is not inheritable!", self.path))
-                            }
-                        })
-                    }
-                    Err(e) => Log::err(format!(
-                        "Failed to resolve child path {}. Reason: {:?}",
-                        self.path, e
-                    )),
-                });
-            });
-
-            // Try to apply it to the child.
-            if need_revert {
-                if let Some(parent_value) = parent_value {
-                    let mut was_set = false;
-
-                    let mut parent_value = Some(parent_value);
-                    child.as_reflect_mut(&mut |child| {
-                        child.set_field_by_path(
-                            &self.path,
-                            parent_value.take().unwrap(),
-                            &mut |result| match result {
-                                Ok(old_value) => {
-                                    self.value = Some(old_value);
-
-                                    was_set = true;
-                                }
-                                Err(_) => Log::err(format!(
-                                    "Failed to revert property {}. Reason: no such property!",
-                                    self.path
-                                )),
-                            },
-                        );
-                    });
-
-                    if was_set {
-                        // Reset modified flag.
-                        reset_property_modified_flag(child, &self.path);
-                    }
-                }
-            }
-        }
-    }
-
-    fn revert(&mut self, context: &mut SceneContext) {
-        // If the property was modified, then simply set it to previous value to make it modified again.
-        if let Some(old_value) = self.value.take() {
-            let mut old_value = Some(old_value);
-            context.scene.graph[self.handle].as_reflect_mut(&mut |node| {
-                node.set_field_by_path(&self.path, old_value.take().unwrap(), &mut |result| {
-                    if result.is_err() {
-                        Log::err(format!(
-                            "Failed to revert property {}. Reason: no such property!",
-                            self.path
-                        ))
-                    }
-                });
-            })
-        }
+impl<'a> EntityNameProvider<SceneContext<'a>, Handle<Node>> for NodeNames {
+    fn entity_name(context: &SceneContext<'a>, handle: Handle<Node>) -> Option<String> {
+        context
+            .scene
+            .graph
+            .try_get(handle)
+            .map(|node| node.name_owned())
     }
 }
 
@@ -497,4 +433,61 @@ define_universal_commands!(
     handle,
     self,
     { &mut ctx.scene.graph[self.handle] as &mut dyn Reflect },
+    parent_value: {
+        // Only instances of some resource have parent values.
+        let node = &ctx.scene.graph[self.handle];
+        node.resource().and_then(|resource| {
+            let resource_data = resource.data_ref();
+            let parent = &resource_data.get_scene().graph[node.original_handle_in_resource()];
+            clone_inheritable_value(parent, &self.path)
+        })
+    },
+    entity_name: NodeNames,
 );
+
+#[cfg(test)]
+pub mod test {
+    use crate::{
+        camera::CameraController, message::MessageSender, scene::commands::SceneContext,
+        scene::EditorScene,
+    };
+    use fyrox::{
+        asset::manager::ResourceManager,
+        core::pool::Handle,
+        engine::SerializationContext,
+        scene::{base::BaseBuilder, pivot::PivotBuilder, Scene},
+    };
+    use std::sync::Arc;
+
+    pub fn make_editor_scene(scene: &mut Scene) -> EditorScene {
+        let editor_objects_root = PivotBuilder::new(BaseBuilder::new()).build(&mut scene.graph);
+        EditorScene {
+            has_unsaved_changes: false,
+            path: None,
+            scene: Handle::NONE,
+            camera_controller: CameraController::new(&mut scene.graph, editor_objects_root, None),
+            editor_objects_root,
+            scene_content_root: scene.graph.get_root(),
+            selection: Default::default(),
+            clipboard: Default::default(),
+            preview_camera: Default::default(),
+            graph_switches: Default::default(),
+            navmesh: Default::default(),
+            navmesh_settings: Default::default(),
+        }
+    }
+
+    pub fn scene_context<'a>(
+        editor_scene: &'a mut EditorScene,
+        scene: &'a mut Scene,
+        sender: &MessageSender,
+    ) -> SceneContext<'a> {
+        SceneContext {
+            editor_scene,
+            scene,
+            message_sender: sender.clone(),
+            resource_manager: ResourceManager::new(),
+            serialization_context: Arc::new(SerializationContext::new()),
+        }
+    }
+}
@@ -1,17 +1,27 @@
 use crate::{
     command::Command,
-    interaction::navmesh::selection::{NavmeshEntity, NavmeshSelection},
+    interaction::navmesh::{
+        links::move_link_ends,
+        selection::{NavmeshEntity, NavmeshSelection},
+        simplify::{simplify_navmesh, NavmeshSimplification},
+        validation::{triangles_overlap_2d, NAVMESH_VALIDATION_EPSILON},
+    },
     scene::{commands::SceneContext, Selection},
 };
 use fyrox::{
     core::{
-        algebra::Vector3,
+        algebra::{Vector2, Vector3},
+        log::Log,
         math::{TriangleDefinition, TriangleEdge},
         pool::Handle,
     },
-    scene::node::Node,
+    scene::{navmesh::NavmeshLink, node::Node},
     utils::{astar::PathVertex, navmesh::Navmesh},
 };
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap},
+};
 
 #[derive(Debug)]
 pub struct AddNavmeshEdgeCommand {
@@ -28,6 +38,116 @@ fn fetch_navmesh<'a>(ctx: &'a mut SceneContext, node: Handle<Node>) -> &'a mut N
         .navmesh_mut()
 }
 
+fn fetch_links<'a>(ctx: &'a mut SceneContext, node: Handle<Node>) -> &'a mut Vec<NavmeshLink> {
+    ctx.scene.graph[node].as_navigational_mesh_mut().links_mut()
+}
+
+/// Copy of the entire navmesh: vertices with their connections, triangles, enabled flags and area
+/// tags of the triangles. Commands, that rebuild a navmesh from scratch, capture it before the
+/// change and restore it on revert.
+#[derive(Debug)]
+pub struct NavmeshSnapshot {
+    navmesh: Navmesh,
+}
+
+impl NavmeshSnapshot {
+    pub fn capture(navmesh: &Navmesh) -> Self {
+        Self {
+            navmesh: navmesh.clone(),
+        }
+    }
+
+    pub fn restore(self, navmesh: &mut Navmesh) {
+        *navmesh = self.navmesh;
+    }
+
+    /// Returns approximate amount of memory (in bytes) occupied by the snapshot.
+    pub fn estimated_size(&self) -> usize {
+        self.navmesh.allocated_size()
+    }
+
+    pub fn vertices(&self) -> &[PathVertex] {
+        self.navmesh.vertices()
+    }
+
+    pub fn positions(&self) -> Vec<Vector3<f32>> {
+        self.navmesh.vertices().iter().map(|v| v.position).collect()
+    }
+
+    pub fn triangles(&self) -> &[TriangleDefinition] {
+        self.navmesh.triangles()
+    }
+
+    pub fn enabled_triangles(&self) -> Vec<bool> {
+        (0..self.navmesh.triangles().len())
+            .map(|i| self.navmesh.is_triangle_enabled(i))
+            .collect()
+    }
+
+    /// Replaces the content of the navmesh with the given vertices and triangles. Every new triangle
+    /// takes the enabled flag and the area of its source triangle in the snapshot.
+    pub fn rebuild(
+        &self,
+        navmesh: &mut Navmesh,
+        vertices: &[Vector3<f32>],
+        triangles: &[TriangleDefinition],
+        source_triangles: &[usize],
+    ) {
+        *navmesh = Navmesh::new(triangles, vertices);
+        for (index, &source) in source_triangles.iter().enumerate() {
+            navmesh.set_triangle_enabled(index, self.navmesh.is_triangle_enabled(source));
+            if let Some(area) = self.navmesh.triangle_area(source) {
+                navmesh.set_triangle_area(index, area);
+            }
+        }
+    }
+}
+
+/// A triangle of a navmesh with its index, enabled flag and area tag. It is used to put removed
+/// triangles back at their places and to pass the flags of source triangles to derived ones.
+#[derive(Debug)]
+pub struct SavedTriangle {
+    index: usize,
+    triangle: TriangleDefinition,
+    enabled: bool,
+    area: u32,
+}
+
+impl SavedTriangle {
+    /// Remembers the triangle at the given index without removing it.
+    pub fn capture(navmesh: &Navmesh, index: usize) -> Self {
+        Self {
+            index,
+            triangle: navmesh.triangles()[index].clone(),
+            enabled: navmesh.is_triangle_enabled(index),
+            area: navmesh.triangle_area(index).unwrap_or_default(),
+        }
+    }
+
+    pub fn remove(navmesh: &mut Navmesh, index: usize) -> Self {
+        let removed = Self::capture(navmesh, index);
+        navmesh.remove_triangle(index);
+        removed
+    }
+
+    pub fn triangle(&self) -> &TriangleDefinition {
+        &self.triangle
+    }
+
+    /// Adds a triangle, that inherits the flags of the saved one, at the end of the navmesh.
+    pub fn add_derived(&self, navmesh: &mut Navmesh, triangle: TriangleDefinition) {
+        let index = navmesh.add_triangle(triangle) as usize;
+        navmesh.set_triangle_enabled(index, self.enabled);
+        navmesh.set_triangle_area(index, self.area);
+    }
+
+    pub fn restore(self, navmesh: &mut Navmesh) {
+        navmesh.insert_triangle(self.index, self.triangle);
+        navmesh.set_triangle_enabled(self.index, self.enabled);
+        navmesh.set_triangle_area(self.index, self.area);
+    }
+}
+
 #[derive(Debug)]
 enum AddNavmeshEdgeCommandState {
     Undefined,
@@ -116,6 +236,258 @@ pub enum ConnectNavmeshEdgesCommandState {
     Reverted { triangles: [TriangleDefinition; 2] },
 }
 
+/// Adds a standalone triangle made of three new vertices and selects the vertices.
+#[derive(Debug)]
+pub struct AddNavmeshTriangleCommand {
+    navmesh_node: Handle<Node>,
+    vertices: [Vector3<f32>; 3],
+    new_selection: Selection,
+}
+
+impl AddNavmeshTriangleCommand {
+    pub fn new(navmesh_node: Handle<Node>, vertices: [Vector3<f32>; 3]) -> Self {
+        Self {
+            navmesh_node,
+            vertices,
+            new_selection: Default::default(),
+        }
+    }
+}
+
+impl Command for AddNavmeshTriangleCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Add Navmesh Triangle".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        let first = navmesh.vertices().len() as u32;
+        for position in self.vertices.iter() {
+            navmesh.add_vertex(PathVertex::new(*position));
+        }
+        navmesh.add_triangle(TriangleDefinition([first, first + 1, first + 2]));
+
+        self.new_selection = Selection::Navmesh(NavmeshSelection::new(
+            self.navmesh_node,
+            (first as usize..first as usize + 3)
+                .map(NavmeshEntity::Vertex)
+                .collect(),
+        ));
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+        navmesh.pop_triangle();
+        for _ in 0..3 {
+            navmesh.pop_vertex();
+        }
+    }
+}
+
+/// Returns the triangle, that connects an extruded vertex with its source vertex and a third
+/// vertex, or `None` if such triangle would be degenerate. If there's a triangle with the
+/// `source`-`third` edge, the new triangle goes the opposite way along this edge, so both triangles
+/// have the same winding. Otherwise the triangle goes counterclockwise, when looking from above.
+pub fn extrusion_triangle(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    source: u32,
+    new: u32,
+    third: u32,
+) -> Option<TriangleDefinition> {
+    if source == new || new == third || third == source {
+        return None;
+    }
+    let a = vertices.get(source as usize)?;
+    let b = vertices.get(new as usize)?;
+    let c = vertices.get(third as usize)?;
+    let normal = (b - a).cross(&(c - a));
+    if normal.norm() * 0.5 <= NAVMESH_VALIDATION_EPSILON * NAVMESH_VALIDATION_EPSILON {
+        return None;
+    }
+
+    let has_directed_edge = |from: u32, to: u32| {
+        triangles
+            .iter()
+            .any(|t| (0..3).any(|k| t[k] == from && t[(k + 1) % 3] == to))
+    };
+    let forward = TriangleDefinition([source, new, third]);
+    let backward = TriangleDefinition([source, third, new]);
+    if has_directed_edge(source, third) {
+        Some(forward)
+    } else if has_directed_edge(third, source) || normal.y < 0.0 {
+        Some(backward)
+    } else {
+        Some(forward)
+    }
+}
+
+/// Adds a vertex extruded from another one. The new vertex is connected with its source by a
+/// triangle only if a third vertex is given and the triangle isn't degenerate, otherwise the new
+/// vertex stays alone.
+#[derive(Debug)]
+pub struct ExtrudeNavmeshVertexCommand {
+    navmesh_node: Handle<Node>,
+    source: usize,
+    position: Vector3<f32>,
+    third: Option<usize>,
+    added_triangle: bool,
+    new_selection: Selection,
+}
+
+impl ExtrudeNavmeshVertexCommand {
+    pub fn new(
+        navmesh_node: Handle<Node>,
+        source: usize,
+        position: Vector3<f32>,
+        third: Option<usize>,
+    ) -> Self {
+        Self {
+            navmesh_node,
+            source,
+            position,
+            third,
+            added_triangle: false,
+            new_selection: Default::default(),
+        }
+    }
+}
+
+impl Command for ExtrudeNavmeshVertexCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Extrude Navmesh Vertex".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        let new = navmesh.vertices().len();
+        navmesh.add_vertex(PathVertex::new(self.position));
+
+        let triangle = self.third.and_then(|third| {
+            let positions = navmesh
+                .vertices()
+                .iter()
+                .map(|v| v.position)
+                .collect::<Vec<_>>();
+            extrusion_triangle(
+                &positions,
+                navmesh.triangles(),
+                self.source as u32,
+                new as u32,
+                third as u32,
+            )
+        });
+        self.added_triangle = triangle.is_some();
+        if let Some(triangle) = triangle {
+            navmesh.add_triangle(triangle);
+        }
+
+        // The new vertex is selected, so it could be extruded further right away.
+        self.new_selection = Selection::Navmesh(NavmeshSelection::new(
+            self.navmesh_node,
+            vec![NavmeshEntity::Vertex(new)],
+        ));
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+        if self.added_triangle {
+            navmesh.pop_triangle();
+        }
+        navmesh.pop_vertex();
+    }
+}
+
+/// Returns the edge between the new vertex and the end of the `edge`, that is closer to the new
+/// vertex. When a strip of triangles is grown from an edge, this edge is the next one to grow from.
+pub fn grown_edge(vertices: &[Vector3<f32>], edge: TriangleEdge, new: u32) -> TriangleEdge {
+    let distance = |vertex: u32| vertices[vertex as usize].metric_distance(&vertices[new as usize]);
+    let end = if distance(edge.b) < distance(edge.a) {
+        edge.b
+    } else {
+        edge.a
+    };
+    TriangleEdge { a: end, b: new }
+}
+
+/// Adds a vertex and connects it with both ends of an edge by a triangle. The new edge from the
+/// closest end of the source edge to the new vertex becomes selected, so the triangles could be
+/// added one by one along a strip. If the triangle would be degenerate, the new vertex stays alone.
+#[derive(Debug)]
+pub struct AddNavmeshTriangleFromEdgeCommand {
+    navmesh_node: Handle<Node>,
+    edge: TriangleEdge,
+    position: Vector3<f32>,
+    added_triangle: bool,
+    new_selection: Selection,
+}
+
+impl AddNavmeshTriangleFromEdgeCommand {
+    pub fn new(navmesh_node: Handle<Node>, edge: TriangleEdge, position: Vector3<f32>) -> Self {
+        Self {
+            navmesh_node,
+            edge,
+            position,
+            added_triangle: false,
+            new_selection: Default::default(),
+        }
+    }
+}
+
+impl Command for AddNavmeshTriangleFromEdgeCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Add Navmesh Triangle From Edge".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        let new = navmesh.vertices().len() as u32;
+        navmesh.add_vertex(PathVertex::new(self.position));
+
+        let positions = navmesh
+            .vertices()
+            .iter()
+            .map(|v| v.position)
+            .collect::<Vec<_>>();
+        let triangle = extrusion_triangle(
+            &positions,
+            navmesh.triangles(),
+            self.edge.a,
+            new,
+            self.edge.b,
+        );
+        self.added_triangle = triangle.is_some();
+        if let Some(triangle) = triangle {
+            navmesh.add_triangle(triangle);
+        }
+
+        self.new_selection = Selection::Navmesh(NavmeshSelection::new(
+            self.navmesh_node,
+            vec![NavmeshEntity::Edge(grown_edge(&positions, self.edge, new))],
+        ));
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+        if self.added_triangle {
+            navmesh.pop_triangle();
+        }
+        navmesh.pop_vertex();
+    }
+}
+
 #[derive(Debug)]
 pub struct ConnectNavmeshEdgesCommand {
     navmesh_node: Handle<Node>,
@@ -141,8 +513,9 @@ impl Command for ConnectNavmeshEdgesCommand {
 
         match std::mem::replace(&mut self.state, ConnectNavmeshEdgesCommandState::Undefined) {
             ConnectNavmeshEdgesCommandState::NonExecuted { edges } => {
-                navmesh.add_triangle(TriangleDefinition([edges[0].a, edges[0].b, edges[1].a]));
-                navmesh.add_triangle(TriangleDefinition([edges[1].a, edges[1].b, edges[0].a]));
+                for triangle in connection_triangles(edges) {
+                    navmesh.add_triangle(triangle);
+                }
 
                 self.state = ConnectNavmeshEdgesCommandState::Executed;
             }
@@ -173,6 +546,212 @@ impl Command for ConnectNavmeshEdgesCommand {
     }
 }
 
+/// Returns triangles, that are added by [`ConnectNavmeshEdgesCommand`] for the given edges.
+pub fn connection_triangles(edges: [TriangleEdge; 2]) -> [TriangleDefinition; 2] {
+    [
+        TriangleDefinition([edges[0].a, edges[0].b, edges[1].a]),
+        TriangleDefinition([edges[1].a, edges[1].b, edges[0].a]),
+    ]
+}
+
+/// Splits edges into pairs for [`ConnectNavmeshEdgesCommand`]. Edges with the closest midpoints
+/// are paired first (greedy matching). The second edge of every pair is oriented so that the
+/// connection forms a quad, not a bow-tie. An edge is left unpaired if the number of edges is odd.
+pub fn pair_edges<F>(edges: &[TriangleEdge], position: F) -> Vec<[TriangleEdge; 2]>
+where
+    F: Fn(u32) -> Vector3<f32>,
+{
+    let distance = |a: u32, b: u32| (position(a) - position(b)).norm();
+    let midpoint = |edge: &TriangleEdge| (position(edge.a) + position(edge.b)).scale(0.5);
+
+    let mut candidates = Vec::new();
+    for (i, first) in edges.iter().enumerate() {
+        for (j, second) in edges.iter().enumerate().skip(i + 1) {
+            candidates.push(((midpoint(first) - midpoint(second)).norm(), i, j));
+        }
+    }
+    // Stable sort keeps the order of the selection for equally distant pairs.
+    candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    let mut paired = vec![false; edges.len()];
+    let mut pairs = Vec::new();
+    for (_, i, j) in candidates {
+        if paired[i] || paired[j] {
+            continue;
+        }
+        paired[i] = true;
+        paired[j] = true;
+
+        let (first, second) = (edges[i], edges[j]);
+        // Connection goes first.a -> first.b -> second.a -> second.b, see `connection_triangles`.
+        let second = if distance(first.b, second.a) + distance(second.b, first.a)
+            <= distance(first.b, second.b) + distance(second.a, first.a)
+        {
+            second
+        } else {
+            TriangleEdge {
+                a: second.b,
+                b: second.a,
+            }
+        };
+        pairs.push([first, second]);
+    }
+    pairs
+}
+
+/// Checks whether two triangles overlap, when they're projected onto the horizontal (XZ) plane.
+/// Triangles, that only touch each other (by a vertex or by an edge), do not overlap.
+pub fn triangles_overlap(a: &[Vector3<f32>; 3], b: &[Vector3<f32>; 3]) -> bool {
+    triangles_overlap_2d(
+        &a.map(|p| Vector2::new(p.x, p.z)),
+        &b.map(|p| Vector2::new(p.x, p.z)),
+        NAVMESH_VALIDATION_EPSILON,
+    )
+}
+
+/// Pairs of edges, that should be connected by the Connect action.
+#[derive(Debug, Default, PartialEq)]
+pub struct EdgeConnectionPlan {
+    pub pairs: Vec<[TriangleEdge; 2]>,
+    /// Pairs, whose connection would produce degenerate triangles or triangles, that overlap
+    /// existing ones (or each other).
+    pub skipped: Vec<[TriangleEdge; 2]>,
+}
+
+/// Pairs the edges (see [`pair_edges`]) and checks triangles of every connection against the
+/// triangles of the mesh and the triangles of the previous connections.
+pub fn plan_edge_connections(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    edges: &[TriangleEdge],
+) -> EdgeConnectionPlan {
+    let corners = |triangle: &TriangleDefinition| -> Option<[Vector3<f32>; 3]> {
+        Some([
+            *vertices.get(triangle[0] as usize)?,
+            *vertices.get(triangle[1] as usize)?,
+            *vertices.get(triangle[2] as usize)?,
+        ])
+    };
+
+    let mut occupied = triangles.iter().filter_map(corners).collect::<Vec<_>>();
+    let mut plan = EdgeConnectionPlan::default();
+    for pair in pair_edges(edges, |i| {
+        vertices.get(i as usize).cloned().unwrap_or_default()
+    }) {
+        let new = connection_triangles(pair)
+            .iter()
+            .map(corners)
+            .collect::<Option<Vec<_>>>()
+            .filter(|new| {
+                new.iter().all(|[a, b, c]| {
+                    (b - a).cross(&(c - a)).norm() * 0.5
+                        > NAVMESH_VALIDATION_EPSILON * NAVMESH_VALIDATION_EPSILON
+                }) && !triangles_overlap(&new[0], &new[1])
+                    && !occupied.iter().any(|existing| {
+                        new.iter()
+                            .any(|triangle| triangles_overlap(triangle, existing))
+                    })
+            });
+
+        match new {
+            Some(new) => {
+                occupied.extend(new);
+                plan.pairs.push(pair);
+            }
+            None => plan.skipped.push(pair),
+        }
+    }
+    plan
+}
+
+/// Returns triangles, that fill the gap between two edges. Vertices are paired so the connecting
+/// sides are as short as possible, which prevents bow-tie quads, then the quad is split along its
+/// shorter diagonal. Edges sharing a vertex produce a single triangle, equal edges produce nothing.
+pub fn bridge_triangles<F>(edges: [TriangleEdge; 2], position: F) -> Vec<TriangleDefinition>
+where
+    F: Fn(u32) -> Vector3<f32>,
+{
+    let [first, second] = edges;
+    if first == second {
+        return vec![];
+    }
+
+    let distance = |a: u32, b: u32| (position(a) - position(b)).norm();
+
+    let (p0, p1) = (first.a, first.b);
+    for &(shared, other) in &[(second.a, second.b), (second.b, second.a)] {
+        if shared == p0 || shared == p1 {
+            return vec![TriangleDefinition([p0, p1, other])];
+        }
+    }
+
+    let (q0, q1) = if distance(p0, second.a) + distance(p1, second.b)
+        <= distance(p0, second.b) + distance(p1, second.a)
+    {
+        (second.a, second.b)
+    } else {
+        (second.b, second.a)
+    };
+
+    // Quad is p0 -> p1 -> q1 -> q0.
+    if distance(p0, q1) <= distance(p1, q0) {
+        vec![
+            TriangleDefinition([p0, p1, q1]),
+            TriangleDefinition([p0, q1, q0]),
+        ]
+    } else {
+        vec![
+            TriangleDefinition([p0, p1, q0]),
+            TriangleDefinition([p1, q1, q0]),
+        ]
+    }
+}
+
+/// Fills the gap between two edges with triangles, see [`bridge_triangles`] for details.
+#[derive(Debug)]
+pub struct BridgeNavmeshEdgesCommand {
+    navmesh_node: Handle<Node>,
+    edges: [TriangleEdge; 2],
+    triangles: Vec<TriangleDefinition>,
+}
+
+impl BridgeNavmeshEdgesCommand {
+    pub fn new(navmesh_node: Handle<Node>, edges: [TriangleEdge; 2]) -> Self {
+        Self {
+            navmesh_node,
+            edges,
+            triangles: Default::default(),
+        }
+    }
+}
+
+impl Command for BridgeNavmeshEdgesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Bridge Navmesh Edges".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        if self.triangles.is_empty() {
+            let vertices = navmesh.vertices();
+            self.triangles = bridge_triangles(self.edges, |i| vertices[i as usize].position);
+        }
+
+        for triangle in self.triangles.iter() {
+            navmesh.add_triangle(triangle.clone());
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        for _ in self.triangles.iter() {
+            navmesh.pop_triangle();
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct DeleteNavmeshVertexCommand {
     navmesh_node: Handle<Node>,
@@ -188,7 +767,8 @@ pub enum DeleteNavmeshVertexCommandState {
     Executed {
         vertex: PathVertex,
         vertex_index: usize,
-        triangles: Vec<TriangleDefinition>,
+        // Removed triangles in ascending order of indices.
+        triangles: Vec<SavedTriangle>,
     },
     Reverted {
         vertex: usize,
@@ -202,26 +782,18 @@ impl DeleteNavmeshVertexCommand {
             state: DeleteNavmeshVertexCommandState::NonExecuted { vertex },
         }
     }
-}
-
-impl Command for DeleteNavmeshVertexCommand {
-    fn name(&mut self, _context: &SceneContext) -> String {
-        "Delete Navmesh Vertex".to_owned()
-    }
-
-    fn execute(&mut self, context: &mut SceneContext) {
-        let navmesh = fetch_navmesh(context, self.navmesh_node);
 
+    fn delete(&mut self, navmesh: &mut Navmesh) {
         match std::mem::replace(&mut self.state, DeleteNavmeshVertexCommandState::Undefined) {
             DeleteNavmeshVertexCommandState::NonExecuted { vertex }
             | DeleteNavmeshVertexCommandState::Reverted { vertex } => {
-                let mut triangles = Vec::new();
-
-                for triangle in navmesh.triangles() {
-                    if triangle.indices().contains(&(vertex as u32)) {
-                        triangles.push(triangle.clone());
-                    }
-                }
+                let triangles = navmesh
+                    .triangles()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, triangle)| triangle.indices().contains(&(vertex as u32)))
+                    .map(|(index, _)| SavedTriangle::capture(navmesh, index))
+                    .collect();
 
                 self.state = DeleteNavmeshVertexCommandState::Executed {
                     vertex: navmesh.remove_vertex(vertex),
@@ -233,9 +805,7 @@ impl Command for DeleteNavmeshVertexCommand {
         }
     }
 
-    fn revert(&mut self, context: &mut SceneContext) {
-        let navmesh = fetch_navmesh(context, self.navmesh_node);
-
+    fn restore(&mut self, navmesh: &mut Navmesh) {
         match std::mem::replace(&mut self.state, DeleteNavmeshVertexCommandState::Undefined) {
             DeleteNavmeshVertexCommandState::Executed {
                 vertex,
@@ -244,8 +814,10 @@ impl Command for DeleteNavmeshVertexCommand {
             } => {
                 navmesh.insert_vertex(vertex_index as u32, vertex);
 
+                // Triangles are restored at their original places, so the indices of the rest of
+                // the triangles are the same as before the deletion.
                 for triangle in triangles {
-                    navmesh.add_triangle(triangle);
+                    triangle.restore(navmesh);
                 }
 
                 self.state = DeleteNavmeshVertexCommandState::Reverted {
@@ -257,37 +829,136 @@ impl Command for DeleteNavmeshVertexCommand {
     }
 }
 
+impl Command for DeleteNavmeshVertexCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Delete Navmesh Vertex".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.delete(fetch_navmesh(context, self.navmesh_node));
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.restore(fetch_navmesh(context, self.navmesh_node));
+    }
+}
+
+/// Removes a single triangle and keeps its vertices, even if they aren't used by anything else.
 #[derive(Debug)]
-pub struct MoveNavmeshVertexCommand {
+pub struct DeleteNavmeshTriangleCommand {
     navmesh_node: Handle<Node>,
-    vertex: usize,
-    old_position: Vector3<f32>,
-    new_position: Vector3<f32>,
+    index: usize,
+    removed: Option<SavedTriangle>,
 }
 
-impl MoveNavmeshVertexCommand {
-    pub fn new(
-        navmesh_node: Handle<Node>,
-        vertex: usize,
-        old_position: Vector3<f32>,
-        new_position: Vector3<f32>,
-    ) -> Self {
+impl DeleteNavmeshTriangleCommand {
+    pub fn new(navmesh_node: Handle<Node>, index: usize) -> Self {
         Self {
             navmesh_node,
-            vertex,
-            old_position,
-            new_position,
+            index,
+            removed: None,
         }
     }
+}
 
-    fn swap(&mut self) -> Vector3<f32> {
-        let position = self.new_position;
-        std::mem::swap(&mut self.new_position, &mut self.old_position);
-        position
+impl Command for DeleteNavmeshTriangleCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Delete Navmesh Triangle".to_owned()
     }
 
-    fn set_position(&self, navmesh: &mut Navmesh, position: Vector3<f32>) {
-        navmesh.vertices_mut()[self.vertex].position = position;
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+        self.removed = Some(SavedTriangle::remove(navmesh, self.index));
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+        if let Some(removed) = self.removed.take() {
+            removed.restore(navmesh);
+        }
+    }
+}
+
+/// Removes every triangle, that shares the given edge. Vertices of the edge are kept.
+#[derive(Debug)]
+pub struct DeleteNavmeshEdgeCommand {
+    navmesh_node: Handle<Node>,
+    edge: TriangleEdge,
+    // Removed triangles in descending order of indices.
+    removed: Vec<SavedTriangle>,
+}
+
+impl DeleteNavmeshEdgeCommand {
+    pub fn new(navmesh_node: Handle<Node>, edge: TriangleEdge) -> Self {
+        Self {
+            navmesh_node,
+            edge,
+            removed: Default::default(),
+        }
+    }
+}
+
+impl Command for DeleteNavmeshEdgeCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Delete Navmesh Edge".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+        let indices = navmesh
+            .triangles()
+            .iter()
+            .enumerate()
+            .filter(|(_, triangle)| triangle.edges().contains(&self.edge))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        // Remove from the end, so indices of the rest of the triangles stay the same.
+        for index in indices.into_iter().rev() {
+            self.removed.push(SavedTriangle::remove(navmesh, index));
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+        for removed in self.removed.drain(..).rev() {
+            removed.restore(navmesh);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MoveNavmeshVertexCommand {
+    navmesh_node: Handle<Node>,
+    vertex: usize,
+    old_position: Vector3<f32>,
+    new_position: Vector3<f32>,
+}
+
+impl MoveNavmeshVertexCommand {
+    pub fn new(
+        navmesh_node: Handle<Node>,
+        vertex: usize,
+        old_position: Vector3<f32>,
+        new_position: Vector3<f32>,
+    ) -> Self {
+        Self {
+            navmesh_node,
+            vertex,
+            old_position,
+            new_position,
+        }
+    }
+
+    fn swap(&mut self) -> Vector3<f32> {
+        let position = self.new_position;
+        std::mem::swap(&mut self.new_position, &mut self.old_position);
+        position
+    }
+
+    fn set_position(&self, context: &mut SceneContext, from: Vector3<f32>, to: Vector3<f32>) {
+        fetch_navmesh(context, self.navmesh_node).vertices_mut()[self.vertex].position = to;
+        // Off-mesh links stay attached to the vertex.
+        move_link_ends(fetch_links(context, self.navmesh_node), from, to);
     }
 }
 
@@ -297,12 +968,2086 @@ impl Command for MoveNavmeshVertexCommand {
     }
 
     fn execute(&mut self, context: &mut SceneContext) {
-        let position = self.swap();
-        self.set_position(fetch_navmesh(context, self.navmesh_node), position);
+        let from = self.old_position;
+        let to = self.swap();
+        self.set_position(context, from, to);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let from = self.old_position;
+        let to = self.swap();
+        self.set_position(context, from, to);
+    }
+
+    // Positions are swapped on execution, so `old_position` of an executed command is the current
+    // position of the vertex and `new_position` is the position it will be reverted to.
+
+    fn can_merge(&self, other: &dyn Command) -> bool {
+        matches!(other.as_any().downcast_ref::<MoveNavmeshVertexCommand>(),
+            Some(other) if other.navmesh_node == self.navmesh_node
+                && other.vertex == self.vertex
+                && other.new_position == self.old_position)
+    }
+
+    fn merge(&mut self, other: &dyn Command) {
+        if let Some(other) = other.as_any().downcast_ref::<MoveNavmeshVertexCommand>() {
+            self.old_position = other.old_position;
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AddNavmeshLinkCommand {
+    navmesh_node: Handle<Node>,
+    link: Option<NavmeshLink>,
+}
+
+impl AddNavmeshLinkCommand {
+    pub fn new(navmesh_node: Handle<Node>, link: NavmeshLink) -> Self {
+        Self {
+            navmesh_node,
+            link: Some(link),
+        }
+    }
+}
+
+impl Command for AddNavmeshLinkCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Add Navmesh Link".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let link = self.link.take().unwrap();
+        fetch_links(context, self.navmesh_node).push(link);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.link = fetch_links(context, self.navmesh_node).pop();
+    }
+}
+
+#[derive(Debug)]
+pub struct DeleteNavmeshLinkCommand {
+    navmesh_node: Handle<Node>,
+    index: usize,
+    link: Option<NavmeshLink>,
+}
+
+impl DeleteNavmeshLinkCommand {
+    pub fn new(navmesh_node: Handle<Node>, index: usize) -> Self {
+        Self {
+            navmesh_node,
+            index,
+            link: None,
+        }
+    }
+}
+
+impl Command for DeleteNavmeshLinkCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Delete Navmesh Link".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let links = fetch_links(context, self.navmesh_node);
+        if self.index < links.len() {
+            self.link = Some(links.remove(self.index));
+        } else {
+            Log::err(format!(
+                "Failed to delete navmesh link {}! There are only {} links.",
+                self.index,
+                links.len()
+            ));
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        // Nothing was deleted if the index was out of bounds.
+        if let Some(link) = self.link.take() {
+            fetch_links(context, self.navmesh_node).insert(self.index, link);
+        }
+    }
+
+    fn is_noop(&self) -> bool {
+        self.link.is_none()
+    }
+}
+
+/// Result of vertex merging, see [`merge_navmesh_vertices`].
+#[derive(Debug, PartialEq)]
+pub struct MergedNavmesh {
+    pub vertices: Vec<Vector3<f32>>,
+    pub triangles: Vec<TriangleDefinition>,
+    /// Index of a source triangle for every triangle in `triangles`.
+    pub source_triangles: Vec<usize>,
+    /// Maps an index of a vertex before merging to its index after merging.
+    pub remap: Vec<u32>,
+}
+
+/// Welds vertices that are closer than `threshold` to each other. Only vertices from `candidates`
+/// are merged, `None` means that every vertex is a candidate. Every group of close vertices collapses
+/// into its first vertex, triangle indices are rewritten and triangles that became degenerate are
+/// dropped.
+pub fn merge_navmesh_vertices(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    candidates: Option<&BTreeSet<usize>>,
+    threshold: f32,
+) -> MergedNavmesh {
+    let is_candidate = |i: usize| candidates.map_or(true, |c| c.contains(&i));
+    let threshold_sqr = threshold * threshold;
+
+    // Index of a surviving vertex for every vertex of the mesh.
+    let mut target = (0..vertices.len()).collect::<Vec<_>>();
+    let mut survivors = Vec::new();
+    for (i, position) in vertices.iter().enumerate() {
+        if is_candidate(i) {
+            if let Some(survivor) = survivors
+                .iter()
+                .find(|s: &&usize| (vertices[**s] - position).norm_squared() <= threshold_sqr)
+            {
+                target[i] = *survivor;
+                continue;
+            }
+            survivors.push(i);
+        }
+    }
+
+    let mut new_vertices = Vec::new();
+    let mut new_indices = vec![0u32; vertices.len()];
+    for (i, position) in vertices.iter().enumerate() {
+        if target[i] == i {
+            new_indices[i] = new_vertices.len() as u32;
+            new_vertices.push(*position);
+        }
+    }
+
+    let remap = target.iter().map(|t| new_indices[*t]).collect::<Vec<_>>();
+
+    let (source_triangles, new_triangles) = triangles
+        .iter()
+        .enumerate()
+        .filter_map(|(index, triangle)| {
+            let [a, b, c] = triangle.0.map(|i| remap[i as usize]);
+            if a != b && b != c && a != c {
+                Some((index, TriangleDefinition([a, b, c])))
+            } else {
+                None
+            }
+        })
+        .unzip();
+
+    MergedNavmesh {
+        vertices: new_vertices,
+        triangles: new_triangles,
+        source_triangles,
+        remap,
+    }
+}
+
+#[derive(Debug)]
+enum MergeNavmeshVerticesCommandState {
+    Undefined,
+    NonExecuted,
+    Executed { snapshot: NavmeshSnapshot },
+    Reverted,
+}
+
+#[derive(Debug)]
+pub struct MergeNavmeshVerticesCommand {
+    navmesh_node: Handle<Node>,
+    vertices: Option<BTreeSet<usize>>,
+    threshold: f32,
+    state: MergeNavmeshVerticesCommandState,
+    new_selection: Selection,
+}
+
+impl MergeNavmeshVerticesCommand {
+    /// Creates a command, that merges given vertices (or every vertex of the navmesh if `vertices`
+    /// is `None`) that are closer than `threshold` to each other.
+    pub fn new(
+        navmesh_node: Handle<Node>,
+        vertices: Option<BTreeSet<usize>>,
+        threshold: f32,
+    ) -> Self {
+        Self {
+            navmesh_node,
+            vertices,
+            threshold,
+            state: MergeNavmeshVerticesCommandState::NonExecuted,
+            new_selection: Default::default(),
+        }
+    }
+}
+
+impl Command for MergeNavmeshVerticesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Merge Navmesh Vertices".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(&mut self.state, MergeNavmeshVerticesCommandState::Undefined) {
+            MergeNavmeshVerticesCommandState::NonExecuted
+            | MergeNavmeshVerticesCommandState::Reverted => {
+                let snapshot = NavmeshSnapshot::capture(navmesh);
+
+                let merged = merge_navmesh_vertices(
+                    &snapshot.positions(),
+                    snapshot.triangles(),
+                    self.vertices.as_ref(),
+                    self.threshold,
+                );
+
+                snapshot.rebuild(
+                    navmesh,
+                    &merged.vertices,
+                    &merged.triangles,
+                    &merged.source_triangles,
+                );
+
+                let entities = self
+                    .vertices
+                    .iter()
+                    .flatten()
+                    .filter_map(|v| merged.remap.get(*v))
+                    .map(|v| *v as usize)
+                    .collect::<BTreeSet<_>>()
+                    .into_iter()
+                    .map(NavmeshEntity::Vertex)
+                    .collect();
+                self.new_selection =
+                    Selection::Navmesh(NavmeshSelection::new(self.navmesh_node, entities));
+
+                self.state = MergeNavmeshVerticesCommandState::Executed { snapshot };
+            }
+            _ => unreachable!(),
+        }
+
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(&mut self.state, MergeNavmeshVerticesCommandState::Undefined) {
+            MergeNavmeshVerticesCommandState::Executed { snapshot } => {
+                snapshot.restore(navmesh);
+                self.state = MergeNavmeshVerticesCommandState::Reverted;
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + match self.state {
+                MergeNavmeshVerticesCommandState::Executed { ref snapshot } => {
+                    snapshot.estimated_size()
+                }
+                _ => 0,
+            }
+    }
+}
+
+/// Returns the new index of every vertex after removal of the vertices, that are not used by any
+/// triangle. Removed vertices are mapped to `None`, the rest keep their order.
+pub fn orphan_vertices_remap(
+    vertex_count: usize,
+    triangles: &[TriangleDefinition],
+) -> Vec<Option<u32>> {
+    let mut referenced = vec![false; vertex_count];
+    for triangle in triangles {
+        for &index in triangle.indices() {
+            if let Some(referenced) = referenced.get_mut(index as usize) {
+                *referenced = true;
+            }
+        }
+    }
+
+    let mut next = 0;
+    referenced
+        .into_iter()
+        .map(|referenced| {
+            if referenced {
+                next += 1;
+                Some(next - 1)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+enum RemoveOrphanNavmeshVerticesCommandState {
+    Undefined,
+    NonExecuted,
+    Executed { snapshot: NavmeshSnapshot },
+    Reverted,
+}
+
+/// Removes every vertex, that is not used by any triangle, see [`orphan_vertices_remap`]. Selected
+/// entities are remapped to the new vertex indices, removed vertices are deselected.
+#[derive(Debug)]
+pub struct RemoveOrphanNavmeshVerticesCommand {
+    navmesh_node: Handle<Node>,
+    state: RemoveOrphanNavmeshVerticesCommandState,
+    new_selection: Selection,
+}
+
+impl RemoveOrphanNavmeshVerticesCommand {
+    pub fn new(navmesh_node: Handle<Node>) -> Self {
+        Self {
+            navmesh_node,
+            state: RemoveOrphanNavmeshVerticesCommandState::NonExecuted,
+            new_selection: Default::default(),
+        }
+    }
+}
+
+impl Command for RemoveOrphanNavmeshVerticesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Remove Unused Navmesh Vertices".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        let remap = match std::mem::replace(
+            &mut self.state,
+            RemoveOrphanNavmeshVerticesCommandState::Undefined,
+        ) {
+            RemoveOrphanNavmeshVerticesCommandState::NonExecuted
+            | RemoveOrphanNavmeshVerticesCommandState::Reverted => {
+                let snapshot = NavmeshSnapshot::capture(navmesh);
+
+                let remap = orphan_vertices_remap(snapshot.vertices().len(), snapshot.triangles());
+                let positions = snapshot
+                    .vertices()
+                    .iter()
+                    .zip(remap.iter())
+                    .filter(|(_, new_index)| new_index.is_some())
+                    .map(|(vertex, _)| vertex.position)
+                    .collect::<Vec<_>>();
+                // Every vertex of a triangle is referenced, so none of them is removed.
+                let new_triangles = snapshot
+                    .triangles()
+                    .iter()
+                    .map(|triangle| {
+                        TriangleDefinition(triangle.0.map(|i| remap[i as usize].unwrap()))
+                    })
+                    .collect::<Vec<_>>();
+                let sources = (0..new_triangles.len()).collect::<Vec<_>>();
+
+                snapshot.rebuild(navmesh, &positions, &new_triangles, &sources);
+
+                self.state = RemoveOrphanNavmeshVerticesCommandState::Executed { snapshot };
+
+                remap
+            }
+            _ => unreachable!(),
+        };
+
+        self.new_selection = match &context.editor_scene.selection {
+            Selection::Navmesh(selection) if selection.navmesh_node() == self.navmesh_node => {
+                Selection::Navmesh(selection.remapped(&remap))
+            }
+            selection => selection.clone(),
+        };
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
     }
 
     fn revert(&mut self, context: &mut SceneContext) {
-        let position = self.swap();
-        self.set_position(fetch_navmesh(context, self.navmesh_node), position);
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(
+            &mut self.state,
+            RemoveOrphanNavmeshVerticesCommandState::Undefined,
+        ) {
+            RemoveOrphanNavmeshVerticesCommandState::Executed { snapshot } => {
+                snapshot.restore(navmesh);
+                self.state = RemoveOrphanNavmeshVerticesCommandState::Reverted;
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + match self.state {
+                RemoveOrphanNavmeshVerticesCommandState::Executed { ref snapshot } => {
+                    snapshot.estimated_size()
+                }
+                _ => 0,
+            }
+    }
+}
+
+#[derive(Debug)]
+enum SimplifyNavmeshCommandState {
+    Undefined,
+    NonExecuted,
+    Executed { snapshot: NavmeshSnapshot },
+    Reverted,
+}
+
+/// Simplifies a navmesh, see [`simplify_navmesh`]. Selection is cleared, because most of the
+/// vertices are either removed or get new indices.
+#[derive(Debug)]
+pub struct SimplifyNavmeshCommand {
+    navmesh_node: Handle<Node>,
+    options: NavmeshSimplification,
+    state: SimplifyNavmeshCommandState,
+    new_selection: Selection,
+}
+
+impl SimplifyNavmeshCommand {
+    pub fn new(navmesh_node: Handle<Node>, options: NavmeshSimplification) -> Self {
+        Self {
+            navmesh_node,
+            options,
+            state: SimplifyNavmeshCommandState::NonExecuted,
+            new_selection: Default::default(),
+        }
+    }
+}
+
+impl Command for SimplifyNavmeshCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Simplify Navmesh".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(&mut self.state, SimplifyNavmeshCommandState::Undefined) {
+            state @ SimplifyNavmeshCommandState::NonExecuted
+            | state @ SimplifyNavmeshCommandState::Reverted => {
+                let snapshot = NavmeshSnapshot::capture(navmesh);
+
+                let simplified = simplify_navmesh(
+                    &snapshot.positions(),
+                    snapshot.triangles(),
+                    &snapshot.enabled_triangles(),
+                    self.options,
+                );
+
+                // Redo gives exactly the same result, there's no need to report it again.
+                if matches!(state, SimplifyNavmeshCommandState::NonExecuted) {
+                    Log::info(format!(
+                        "Navmesh was simplified from {} to {} triangles ({} to {} vertices).",
+                        snapshot.triangles().len(),
+                        simplified.triangles.len(),
+                        snapshot.vertices().len(),
+                        simplified.vertices.len()
+                    ));
+                }
+
+                snapshot.rebuild(
+                    navmesh,
+                    &simplified.vertices,
+                    &simplified.triangles,
+                    &simplified.source_triangles,
+                );
+
+                self.new_selection = Selection::Navmesh(NavmeshSelection::empty(self.navmesh_node));
+
+                self.state = SimplifyNavmeshCommandState::Executed { snapshot };
+            }
+            _ => unreachable!(),
+        }
+
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(&mut self.state, SimplifyNavmeshCommandState::Undefined) {
+            SimplifyNavmeshCommandState::Executed { snapshot } => {
+                snapshot.restore(navmesh);
+                self.state = SimplifyNavmeshCommandState::Reverted;
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + match self.state {
+                SimplifyNavmeshCommandState::Executed { ref snapshot } => snapshot.estimated_size(),
+                _ => 0,
+            }
+    }
+}
+
+/// Welds `source` vertex into `target` vertex: triangles that referenced the source vertex are
+/// rewritten to use the target one, the source vertex is removed and triangles that became
+/// degenerate are dropped. Indices of vertices after the source one are shifted down by one.
+pub fn weld_navmesh_vertex(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    source: usize,
+    target: usize,
+) -> MergedNavmesh {
+    let shifted = |i: usize| (if i > source { i - 1 } else { i }) as u32;
+    let remap = (0..vertices.len())
+        .map(|i| {
+            if i == source {
+                shifted(target)
+            } else {
+                shifted(i)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let new_vertices = vertices
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| *i != source)
+        .map(|(_, position)| *position)
+        .collect();
+
+    let (source_triangles, new_triangles) = triangles
+        .iter()
+        .enumerate()
+        .filter_map(|(index, triangle)| {
+            let [a, b, c] = triangle.0.map(|i| remap[i as usize]);
+            if a != b && b != c && a != c {
+                Some((index, TriangleDefinition([a, b, c])))
+            } else {
+                None
+            }
+        })
+        .unzip();
+
+    MergedNavmesh {
+        vertices: new_vertices,
+        triangles: new_triangles,
+        source_triangles,
+        remap,
+    }
+}
+
+#[derive(Debug)]
+enum WeldNavmeshVerticesCommandState {
+    Undefined,
+    NonExecuted,
+    Executed { snapshot: NavmeshSnapshot },
+    Reverted,
+}
+
+/// Welds one vertex into another, see [`weld_navmesh_vertex`]. The target vertex is selected
+/// afterwards.
+#[derive(Debug)]
+pub struct WeldNavmeshVerticesCommand {
+    navmesh_node: Handle<Node>,
+    source: usize,
+    target: usize,
+    state: WeldNavmeshVerticesCommandState,
+    new_selection: Selection,
+}
+
+impl WeldNavmeshVerticesCommand {
+    pub fn new(navmesh_node: Handle<Node>, source: usize, target: usize) -> Self {
+        Self {
+            navmesh_node,
+            source,
+            target,
+            state: WeldNavmeshVerticesCommandState::NonExecuted,
+            new_selection: Default::default(),
+        }
+    }
+}
+
+impl Command for WeldNavmeshVerticesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Weld Navmesh Vertices".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(&mut self.state, WeldNavmeshVerticesCommandState::Undefined) {
+            WeldNavmeshVerticesCommandState::NonExecuted
+            | WeldNavmeshVerticesCommandState::Reverted => {
+                let snapshot = NavmeshSnapshot::capture(navmesh);
+
+                let welded = weld_navmesh_vertex(
+                    &snapshot.positions(),
+                    snapshot.triangles(),
+                    self.source,
+                    self.target,
+                );
+
+                snapshot.rebuild(
+                    navmesh,
+                    &welded.vertices,
+                    &welded.triangles,
+                    &welded.source_triangles,
+                );
+
+                self.new_selection = Selection::Navmesh(NavmeshSelection::new(
+                    self.navmesh_node,
+                    vec![NavmeshEntity::Vertex(welded.remap[self.target] as usize)],
+                ));
+
+                self.state = WeldNavmeshVerticesCommandState::Executed { snapshot };
+            }
+            _ => unreachable!(),
+        }
+
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(&mut self.state, WeldNavmeshVerticesCommandState::Undefined) {
+            WeldNavmeshVerticesCommandState::Executed { snapshot } => {
+                snapshot.restore(navmesh);
+                self.state = WeldNavmeshVerticesCommandState::Reverted;
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + match self.state {
+                WeldNavmeshVerticesCommandState::Executed { ref snapshot } => {
+                    snapshot.estimated_size()
+                }
+                _ => 0,
+            }
+    }
+}
+
+#[derive(Debug)]
+enum ToggleNavmeshTrianglesCommandState {
+    Undefined,
+    NonExecuted {
+        vertices: BTreeSet<usize>,
+    },
+    Executed {
+        // Index of a triangle and its previous state.
+        triangles: Vec<(usize, bool)>,
+        enabled: bool,
+    },
+    Reverted {
+        triangles: Vec<(usize, bool)>,
+        enabled: bool,
+    },
+}
+
+/// Enables or disables every triangle, that has all its vertices selected. If any of such triangles
+/// is enabled, all of them will be disabled, otherwise all of them will be enabled.
+#[derive(Debug)]
+pub struct ToggleNavmeshTrianglesCommand {
+    navmesh_node: Handle<Node>,
+    state: ToggleNavmeshTrianglesCommandState,
+}
+
+impl ToggleNavmeshTrianglesCommand {
+    pub fn new(navmesh_node: Handle<Node>, vertices: BTreeSet<usize>) -> Self {
+        Self {
+            navmesh_node,
+            state: ToggleNavmeshTrianglesCommandState::NonExecuted { vertices },
+        }
+    }
+}
+
+impl Command for ToggleNavmeshTrianglesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Toggle Navmesh Triangles".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(
+            &mut self.state,
+            ToggleNavmeshTrianglesCommandState::Undefined,
+        ) {
+            ToggleNavmeshTrianglesCommandState::NonExecuted { vertices } => {
+                let triangles = navmesh
+                    .triangles()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, triangle)| {
+                        triangle
+                            .indices()
+                            .iter()
+                            .all(|i| vertices.contains(&(*i as usize)))
+                    })
+                    .map(|(index, _)| (index, navmesh.is_triangle_enabled(index)))
+                    .collect::<Vec<_>>();
+
+                let enabled = !triangles.iter().any(|(_, enabled)| *enabled);
+                for &(triangle, _) in triangles.iter() {
+                    navmesh.set_triangle_enabled(triangle, enabled);
+                }
+
+                self.state = ToggleNavmeshTrianglesCommandState::Executed { triangles, enabled };
+            }
+            ToggleNavmeshTrianglesCommandState::Reverted { triangles, enabled } => {
+                for &(triangle, _) in triangles.iter() {
+                    navmesh.set_triangle_enabled(triangle, enabled);
+                }
+
+                self.state = ToggleNavmeshTrianglesCommandState::Executed { triangles, enabled };
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(
+            &mut self.state,
+            ToggleNavmeshTrianglesCommandState::Undefined,
+        ) {
+            ToggleNavmeshTrianglesCommandState::Executed { triangles, enabled } => {
+                for &(triangle, was_enabled) in triangles.iter() {
+                    navmesh.set_triangle_enabled(triangle, was_enabled);
+                }
+
+                self.state = ToggleNavmeshTrianglesCommandState::Reverted { triangles, enabled };
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Returns indices of triangles, that must be flipped to make the winding of every triangle the
+/// same as the winding of its edge-connected neighbours. Neighbours have the same winding, if they
+/// go through their shared edge in opposite directions. The winding is propagated from the first
+/// triangle of every group of connected triangles. Only triangles from `subset` are checked,
+/// `None` means every triangle of the mesh.
+pub fn inconsistent_triangles(
+    triangles: &[TriangleDefinition],
+    subset: Option<&BTreeSet<usize>>,
+) -> Vec<usize> {
+    let indices = match subset {
+        Some(subset) => subset.iter().cloned().collect::<Vec<_>>(),
+        None => (0..triangles.len()).collect(),
+    };
+
+    let mut edges = HashMap::<(u32, u32), Vec<usize>>::new();
+    for &index in indices.iter() {
+        for edge in triangles[index].edges() {
+            edges
+                .entry((edge.a.min(edge.b), edge.a.max(edge.b)))
+                .or_default()
+                .push(index);
+        }
+    }
+
+    let has_directed_edge = |triangle: &TriangleDefinition, a: u32, b: u32| {
+        triangle.edges().iter().any(|e| e.a == a && e.b == b)
+    };
+
+    let mut flipped = HashMap::<usize, bool>::new();
+    for &reference in indices.iter() {
+        if flipped.contains_key(&reference) {
+            continue;
+        }
+        flipped.insert(reference, false);
+
+        let mut stack = vec![reference];
+        while let Some(index) = stack.pop() {
+            let mut triangle = triangles[index].clone();
+            if flipped[&index] {
+                triangle.0.swap(1, 2);
+            }
+
+            for edge in triangle.edges() {
+                let key = (edge.a.min(edge.b), edge.a.max(edge.b));
+                for &neighbour in edges[&key].iter() {
+                    if let Entry::Vacant(entry) = flipped.entry(neighbour) {
+                        // Consistently wound neighbour goes through the edge backwards.
+                        entry.insert(has_directed_edge(&triangles[neighbour], edge.a, edge.b));
+                        stack.push(neighbour);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut result = flipped
+        .into_iter()
+        .filter(|(_, flipped)| *flipped)
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    result.sort_unstable();
+    result
+}
+
+/// Flips the winding of the given triangles by swapping two of their indices. Vertices are not
+/// changed at all. Flipping is its own inverse, so the command just flips the triangles again on
+/// revert.
+#[derive(Debug)]
+pub struct FlipNavmeshTrianglesCommand {
+    navmesh_node: Handle<Node>,
+    triangles: Vec<usize>,
+}
+
+impl FlipNavmeshTrianglesCommand {
+    pub fn new(navmesh_node: Handle<Node>, triangles: Vec<usize>) -> Self {
+        Self {
+            navmesh_node,
+            triangles,
+        }
+    }
+
+    fn flip(&self, navmesh: &mut Navmesh) {
+        let snapshot = NavmeshSnapshot::capture(navmesh);
+
+        let mut triangles = snapshot.triangles().to_vec();
+        for &index in self.triangles.iter() {
+            triangles[index].0.swap(1, 2);
+        }
+        let sources = (0..triangles.len()).collect::<Vec<_>>();
+
+        snapshot.rebuild(navmesh, &snapshot.positions(), &triangles, &sources);
+        navmesh.vertices_mut().clone_from_slice(snapshot.vertices());
+    }
+}
+
+impl Command for FlipNavmeshTrianglesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Flip Navmesh Triangles".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.flip(fetch_navmesh(context, self.navmesh_node));
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.flip(fetch_navmesh(context, self.navmesh_node));
+    }
+}
+
+#[derive(Debug)]
+enum SplitNavmeshEdgeCommandState {
+    Undefined,
+    NonExecuted,
+    Executed {
+        // Every split triangle in ascending order of indices.
+        triangles: Vec<SavedTriangle>,
+    },
+    Reverted,
+}
+
+/// Inserts a new vertex at the middle of the edge and splits every triangle, that shares the edge,
+/// into two triangles. The new vertex becomes selected.
+#[derive(Debug)]
+pub struct SplitNavmeshEdgeCommand {
+    navmesh_node: Handle<Node>,
+    edge: TriangleEdge,
+    state: SplitNavmeshEdgeCommandState,
+    new_selection: Selection,
+}
+
+impl SplitNavmeshEdgeCommand {
+    pub fn new(navmesh_node: Handle<Node>, edge: TriangleEdge) -> Self {
+        Self {
+            navmesh_node,
+            edge,
+            state: SplitNavmeshEdgeCommandState::NonExecuted,
+            new_selection: Default::default(),
+        }
+    }
+}
+
+impl Command for SplitNavmeshEdgeCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Split Navmesh Edge".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(&mut self.state, SplitNavmeshEdgeCommandState::Undefined) {
+            SplitNavmeshEdgeCommandState::NonExecuted | SplitNavmeshEdgeCommandState::Reverted => {
+                let a = navmesh.vertices()[self.edge.a as usize].position;
+                let b = navmesh.vertices()[self.edge.b as usize].position;
+                let middle = navmesh.add_vertex(PathVertex::new((a + b).scale(0.5)));
+
+                let indices = navmesh
+                    .triangles()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, triangle)| triangle.edges().contains(&self.edge))
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>();
+                let mut triangles = indices
+                    .into_iter()
+                    .rev()
+                    .map(|index| SavedTriangle::remove(navmesh, index))
+                    .collect::<Vec<_>>();
+                triangles.reverse();
+
+                for removed in triangles.iter() {
+                    // Replace one of the edge ends with the middle vertex at a time, this way
+                    // winding of the new triangles stays the same.
+                    for end in [self.edge.a, self.edge.b] {
+                        let half = TriangleDefinition(removed.triangle().0.map(|i| {
+                            if i == end {
+                                middle
+                            } else {
+                                i
+                            }
+                        }));
+                        removed.add_derived(navmesh, half);
+                    }
+                }
+
+                self.new_selection = Selection::Navmesh(NavmeshSelection::new(
+                    self.navmesh_node,
+                    vec![NavmeshEntity::Vertex(middle as usize)],
+                ));
+
+                self.state = SplitNavmeshEdgeCommandState::Executed { triangles };
+            }
+            _ => unreachable!(),
+        }
+
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(&mut self.state, SplitNavmeshEdgeCommandState::Undefined) {
+            SplitNavmeshEdgeCommandState::Executed { triangles } => {
+                // The middle vertex is the last one, removing it also removes every new triangle.
+                navmesh.pop_vertex();
+
+                for triangle in triangles {
+                    triangle.restore(navmesh);
+                }
+
+                self.state = SplitNavmeshEdgeCommandState::Reverted;
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+pub struct MirroredNavmesh {
+    pub vertices: Vec<Vector3<f32>>,
+    pub triangles: Vec<TriangleDefinition>,
+    /// Index of a source triangle for every triangle in `triangles`.
+    pub source_triangles: Vec<usize>,
+}
+
+/// Appends a mirrored copy of the navmesh, the mirror plane is perpendicular to the given axis
+/// (0 - X, 1 - Y, 2 - Z) and goes through the `pivot` coordinate on it. Winding of the mirrored
+/// triangles is flipped to keep their normals consistent. If `weld_threshold` is set, vertices
+/// closer than the threshold to the mirror plane are not copied and shared by both halves instead.
+/// Triangles, that lie on the plane entirely, are not copied. Original vertices and triangles keep
+/// their indices.
+pub fn mirror_navmesh(
+    vertices: &[Vector3<f32>],
+    triangles: &[TriangleDefinition],
+    axis: usize,
+    pivot: f32,
+    weld_threshold: Option<f32>,
+) -> MirroredNavmesh {
+    let mut new_vertices = vertices.to_vec();
+    let remap = vertices
+        .iter()
+        .enumerate()
+        .map(|(index, position)| {
+            let distance = position[axis] - pivot;
+            if matches!(weld_threshold, Some(threshold) if distance.abs() <= threshold) {
+                index as u32
+            } else {
+                let mut mirrored = *position;
+                mirrored[axis] = pivot - distance;
+                new_vertices.push(mirrored);
+                new_vertices.len() as u32 - 1
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut new_triangles = triangles.to_vec();
+    let mut source_triangles = (0..triangles.len()).collect::<Vec<_>>();
+    for (index, triangle) in triangles.iter().enumerate() {
+        if triangle.0.iter().all(|i| remap[*i as usize] == *i) {
+            continue;
+        }
+
+        let [a, b, c] = triangle.0.map(|i| remap[i as usize]);
+        new_triangles.push(TriangleDefinition([a, c, b]));
+        source_triangles.push(index);
+    }
+
+    MirroredNavmesh {
+        vertices: new_vertices,
+        triangles: new_triangles,
+        source_triangles,
+    }
+}
+
+#[derive(Debug)]
+enum MirrorNavmeshCommandState {
+    Undefined,
+    NonExecuted,
+    Executed { snapshot: NavmeshSnapshot },
+    Reverted,
+}
+
+/// Adds a mirrored copy of the navmesh (see [`mirror_navmesh`]), new vertices become selected.
+#[derive(Debug)]
+pub struct MirrorNavmeshCommand {
+    navmesh_node: Handle<Node>,
+    axis: usize,
+    pivot: f32,
+    weld_threshold: Option<f32>,
+    state: MirrorNavmeshCommandState,
+    new_selection: Selection,
+}
+
+impl MirrorNavmeshCommand {
+    pub fn new(
+        navmesh_node: Handle<Node>,
+        axis: usize,
+        pivot: f32,
+        weld_threshold: Option<f32>,
+    ) -> Self {
+        Self {
+            navmesh_node,
+            axis,
+            pivot,
+            weld_threshold,
+            state: MirrorNavmeshCommandState::NonExecuted,
+            new_selection: Default::default(),
+        }
+    }
+}
+
+impl Command for MirrorNavmeshCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Mirror Navmesh".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(&mut self.state, MirrorNavmeshCommandState::Undefined) {
+            MirrorNavmeshCommandState::NonExecuted | MirrorNavmeshCommandState::Reverted => {
+                let snapshot = NavmeshSnapshot::capture(navmesh);
+
+                let mirrored = mirror_navmesh(
+                    &snapshot.positions(),
+                    snapshot.triangles(),
+                    self.axis,
+                    self.pivot,
+                    self.weld_threshold,
+                );
+
+                snapshot.rebuild(
+                    navmesh,
+                    &mirrored.vertices,
+                    &mirrored.triangles,
+                    &mirrored.source_triangles,
+                );
+
+                self.new_selection = Selection::Navmesh(NavmeshSelection::new(
+                    self.navmesh_node,
+                    (snapshot.vertices().len()..mirrored.vertices.len())
+                        .map(NavmeshEntity::Vertex)
+                        .collect(),
+                ));
+
+                self.state = MirrorNavmeshCommandState::Executed { snapshot };
+            }
+            _ => unreachable!(),
+        }
+
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        match std::mem::replace(&mut self.state, MirrorNavmeshCommandState::Undefined) {
+            MirrorNavmeshCommandState::Executed { snapshot } => {
+                snapshot.restore(navmesh);
+                self.state = MirrorNavmeshCommandState::Reverted;
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of_val(self)
+            + match self.state {
+                MirrorNavmeshCommandState::Executed { ref snapshot } => snapshot.estimated_size(),
+                _ => 0,
+            }
+    }
+}
+
+/// Copies of the triangles, that have all three vertices selected. Returns source vertex for each
+/// new vertex and source triangle index with the new definition for each new triangle. Indices of
+/// new vertices start from `first_vertex`. Vertices shared by the source triangles are shared in
+/// the copy too.
+pub fn duplicate_triangles(
+    triangles: &[TriangleDefinition],
+    selected: &BTreeSet<usize>,
+    first_vertex: u32,
+) -> (Vec<usize>, Vec<(usize, TriangleDefinition)>) {
+    let mut remap = BTreeMap::new();
+    let mut vertices = Vec::new();
+    let mut new_triangles = Vec::new();
+
+    for (index, triangle) in triangles.iter().enumerate() {
+        if triangle
+            .indices()
+            .iter()
+            .all(|i| selected.contains(&(*i as usize)))
+        {
+            let copy = TriangleDefinition(triangle.0.map(|i| {
+                *remap.entry(i).or_insert_with(|| {
+                    vertices.push(i as usize);
+                    first_vertex + vertices.len() as u32 - 1
+                })
+            }));
+            new_triangles.push((index, copy));
+        }
+    }
+
+    (vertices, new_triangles)
+}
+
+/// Triangles, that are moved from a navmesh to a new one, see [`plan_navmesh_split`].
+#[derive(Debug, Default, PartialEq)]
+pub struct NavmeshSplit {
+    /// Triangles to remove from the source navmesh, in descending order.
+    pub triangles: Vec<usize>,
+    /// Vertices, that are used only by the removed triangles, in descending order.
+    pub vertices: Vec<usize>,
+    /// Source vertex for each vertex of the new navmesh.
+    pub new_vertices: Vec<usize>,
+    /// Source triangle index with the new definition for each triangle of the new navmesh.
+    pub new_triangles: Vec<(usize, TriangleDefinition)>,
+}
+
+/// Plans moving of the triangles, that have all three vertices selected, to a new navmesh. Vertex
+/// array of the new navmesh contains only the vertices of the moved triangles. Vertices, that are
+/// no longer used by the source navmesh, are removed from it (vertices, that weren't used by any
+/// triangle before, are left as is).
+pub fn plan_navmesh_split(
+    triangles: &[TriangleDefinition],
+    selected: &BTreeSet<usize>,
+) -> NavmeshSplit {
+    let (new_vertices, new_triangles) = duplicate_triangles(triangles, selected, 0);
+
+    let moved = new_triangles
+        .iter()
+        .map(|(index, _)| *index)
+        .collect::<BTreeSet<_>>();
+    let used = triangles
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !moved.contains(index))
+        .flat_map(|(_, triangle)| triangle.indices().iter().map(|i| *i as usize))
+        .collect::<BTreeSet<_>>();
+
+    let mut vertices = new_vertices
+        .iter()
+        .copied()
+        .filter(|vertex| !used.contains(vertex))
+        .collect::<Vec<_>>();
+    vertices.sort_unstable_by(|a, b| b.cmp(a));
+
+    NavmeshSplit {
+        triangles: moved.into_iter().rev().collect(),
+        vertices,
+        new_vertices,
+        new_triangles,
+    }
+}
+
+#[derive(Debug)]
+struct NavmeshCopy {
+    positions: Vec<Vector3<f32>>,
+    // New triangles with their source triangles.
+    triangles: Vec<(TriangleDefinition, SavedTriangle)>,
+}
+
+/// Duplicates every triangle, that has all its vertices selected (see [`duplicate_triangles`]).
+/// The copy is shifted by the given offset and its vertices become selected.
+#[derive(Debug)]
+pub struct DuplicateNavmeshTrianglesCommand {
+    navmesh_node: Handle<Node>,
+    vertices: BTreeSet<usize>,
+    offset: Vector3<f32>,
+    copy: Option<NavmeshCopy>,
+    new_selection: Selection,
+}
+
+impl DuplicateNavmeshTrianglesCommand {
+    pub fn new(
+        navmesh_node: Handle<Node>,
+        vertices: BTreeSet<usize>,
+        offset: Vector3<f32>,
+    ) -> Self {
+        Self {
+            navmesh_node,
+            vertices,
+            offset,
+            copy: None,
+            new_selection: Default::default(),
+        }
+    }
+}
+
+impl Command for DuplicateNavmeshTrianglesCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Duplicate Navmesh Triangles".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+
+        let first_vertex = navmesh.vertices().len() as u32;
+        let NavmeshCopy {
+            positions,
+            triangles,
+        } = self.copy.get_or_insert_with(|| {
+            let (vertices, triangles) =
+                duplicate_triangles(navmesh.triangles(), &self.vertices, first_vertex);
+            NavmeshCopy {
+                positions: vertices
+                    .into_iter()
+                    .map(|v| navmesh.vertices()[v].position + self.offset)
+                    .collect(),
+                triangles: triangles
+                    .into_iter()
+                    .map(|(source, triangle)| (triangle, SavedTriangle::capture(navmesh, source)))
+                    .collect(),
+            }
+        });
+
+        for position in positions.iter() {
+            navmesh.add_vertex(PathVertex::new(*position));
+        }
+        for (triangle, source) in triangles.iter() {
+            source.add_derived(navmesh, triangle.clone());
+        }
+
+        self.new_selection = Selection::Navmesh(NavmeshSelection::new(
+            self.navmesh_node,
+            (first_vertex as usize..first_vertex as usize + positions.len())
+                .map(NavmeshEntity::Vertex)
+                .collect(),
+        ));
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        std::mem::swap(&mut context.editor_scene.selection, &mut self.new_selection);
+
+        let navmesh = fetch_navmesh(context, self.navmesh_node);
+        if let Some(NavmeshCopy {
+            positions,
+            triangles,
+        }) = self.copy.as_ref()
+        {
+            for _ in triangles.iter() {
+                navmesh.pop_triangle();
+            }
+            for _ in positions.iter() {
+                navmesh.pop_vertex();
+            }
+        }
+    }
+}
+
+/// Replaces the entire content of a navmesh, it is used by navmesh generation.
+#[derive(Debug)]
+pub struct ReplaceNavmeshDataCommand {
+    navmesh_node: Handle<Node>,
+    navmesh: Navmesh,
+}
+
+impl ReplaceNavmeshDataCommand {
+    pub fn new(navmesh_node: Handle<Node>, navmesh: Navmesh) -> Self {
+        Self {
+            navmesh_node,
+            navmesh,
+        }
+    }
+
+    fn swap(&mut self, context: &mut SceneContext) {
+        std::mem::swap(fetch_navmesh(context, self.navmesh_node), &mut self.navmesh);
+    }
+}
+
+impl Command for ReplaceNavmeshDataCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Replace Navmesh Data".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.swap(context);
+    }
+
+    fn estimated_size(&self) -> usize {
+        std::mem::size_of_val(self) + self.navmesh.allocated_size()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        message::MessageSender,
+        scene::{
+            commands::{
+                test::{make_editor_scene, scene_context},
+                CommandGroup, SceneCommand,
+            },
+            EditorScene,
+        },
+    };
+    use fyrox::scene::{base::BaseBuilder, navmesh::NavigationalMeshBuilder, Scene};
+    use std::sync::mpsc::channel;
+
+    fn edge(a: u32, b: u32) -> TriangleEdge {
+        TriangleEdge { a, b }
+    }
+
+    fn bridge(edges: [TriangleEdge; 2], positions: &[Vector3<f32>]) -> Vec<TriangleDefinition> {
+        bridge_triangles(edges, |i| positions[i as usize])
+    }
+
+    fn oriented(pairs: &[[TriangleEdge; 2]]) -> Vec<[(u32, u32); 2]> {
+        // Edges are equal regardless of their direction, so compare them as tuples.
+        pairs
+            .iter()
+            .map(|[a, b]| [(a.a, a.b), (b.a, b.b)])
+            .collect()
+    }
+
+    // 0 - 1   2 - 3
+    //
+    // 4 - 5   6 - 7
+    fn two_gaps() -> Vec<Vector3<f32>> {
+        vec![
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(1.0, 0.0, 1.0),
+            Vector3::new(3.0, 0.0, 1.0),
+            Vector3::new(4.0, 0.0, 1.0),
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(3.0, 0.0, 0.0),
+            Vector3::new(4.0, 0.0, 0.0),
+        ]
+    }
+
+    #[test]
+    fn test_pair_edges() {
+        let positions = two_gaps();
+
+        // Edge 1-2 is too far from the rest and stays unpaired. Second edge of the first pair goes
+        // in the same direction as the first edge, so it is flipped.
+        let pairs = pair_edges(
+            &[edge(0, 1), edge(2, 3), edge(1, 2), edge(4, 5), edge(7, 6)],
+            |i| positions[i as usize],
+        );
+        assert_eq!(oriented(&pairs), vec![[(0, 1), (5, 4)], [(2, 3), (7, 6)]]);
+    }
+
+    #[test]
+    fn test_triangles_overlap() {
+        let a = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        ];
+
+        // Shared edge.
+        let b = [a[1], Vector3::new(1.0, 0.0, 1.0), a[2]];
+        assert!(!triangles_overlap(&a, &b));
+
+        // Height does not matter.
+        let c = a.map(|p| p + Vector3::new(0.2, 5.0, 0.2));
+        assert!(triangles_overlap(&a, &c));
+
+        let d = a.map(|p| p + Vector3::new(2.0, 0.0, 0.0));
+        assert!(!triangles_overlap(&a, &d));
+    }
+
+    #[test]
+    fn test_plan_edge_connections() {
+        let mut vertices = two_gaps();
+        // A triangle in the right gap.
+        vertices.extend([
+            Vector3::new(3.2, 0.0, 0.4),
+            Vector3::new(3.8, 0.0, 0.4),
+            Vector3::new(3.5, 0.0, 0.6),
+        ]);
+        let triangles = [TriangleDefinition([8, 9, 10])];
+
+        let plan = plan_edge_connections(
+            &vertices,
+            &triangles,
+            &[edge(0, 1), edge(2, 3), edge(4, 5), edge(6, 7)],
+        );
+        assert_eq!(oriented(&plan.pairs), vec![[(0, 1), (5, 4)]]);
+        assert_eq!(oriented(&plan.skipped), vec![[(2, 3), (7, 6)]]);
+
+        // Edges sharing a vertex would produce a degenerate triangle.
+        let plan = plan_edge_connections(&vertices, &[], &[edge(0, 1), edge(1, 5)]);
+        assert!(plan.pairs.is_empty());
+        assert_eq!(plan.skipped.len(), 1);
+    }
+
+    #[test]
+    fn test_bridge_avoids_bow_tie() {
+        // 2 - 3
+        // |   |
+        // 0 - 1
+        let positions = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 0.0, 2.0),
+            Vector3::new(1.0, 0.0, 2.0),
+        ];
+
+        // Second edge goes in the opposite direction, naive pairing would produce a bow-tie.
+        let triangles = bridge([edge(0, 1), edge(3, 2)], &positions);
+        assert_eq!(
+            triangles,
+            vec![TriangleDefinition([0, 1, 3]), TriangleDefinition([0, 3, 2])]
+        );
+        assert_eq!(triangles, bridge([edge(0, 1), edge(2, 3)], &positions));
+    }
+
+    #[test]
+    fn test_bridge_uses_shorter_diagonal() {
+        // Skewed quad, diagonal 1-2 is much shorter than 0-3.
+        //         2 - 3
+        //        /   /
+        // 0 - 1
+        let positions = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(1.5, 0.0, 1.0),
+            Vector3::new(2.5, 0.0, 1.0),
+        ];
+
+        assert_eq!(
+            bridge([edge(0, 1), edge(2, 3)], &positions),
+            vec![TriangleDefinition([0, 1, 2]), TriangleDefinition([1, 3, 2])]
+        );
+    }
+
+    #[test]
+    fn test_bridge_degenerate_cases() {
+        let positions = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        ];
+
+        // Shared vertex.
+        assert_eq!(
+            bridge([edge(0, 1), edge(2, 0)], &positions),
+            vec![TriangleDefinition([0, 1, 2])]
+        );
+        // Same edge.
+        assert!(bridge([edge(0, 1), edge(1, 0)], &positions).is_empty());
+    }
+
+    #[test]
+    fn test_mirror_navmesh() {
+        // 2
+        // | \
+        // 0 - 1
+        let vertices = [
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 1.0),
+        ];
+        let triangles = [TriangleDefinition([0, 1, 2])];
+
+        let mirrored = mirror_navmesh(&vertices, &triangles, 0, 0.0, None);
+        assert_eq!(&mirrored.vertices[..3], &vertices);
+        assert_eq!(
+            &mirrored.vertices[3..],
+            &[
+                Vector3::new(-1.0, 0.0, 0.0),
+                Vector3::new(-2.0, 0.0, 0.0),
+                Vector3::new(-1.0, 0.0, 1.0),
+            ]
+        );
+        // Winding is flipped.
+        assert_eq!(
+            mirrored.triangles,
+            vec![TriangleDefinition([0, 1, 2]), TriangleDefinition([3, 5, 4])]
+        );
+        assert_eq!(mirrored.source_triangles, vec![0, 0]);
+
+        // Vertices 0 and 2 lie on the mirror plane and must be shared.
+        let mirrored = mirror_navmesh(&vertices, &triangles, 0, 1.0, Some(0.01));
+        assert_eq!(
+            mirrored.vertices,
+            vec![
+                vertices[0],
+                vertices[1],
+                vertices[2],
+                Vector3::new(0.0, 0.0, 0.0)
+            ]
+        );
+        assert_eq!(
+            mirrored.triangles,
+            vec![TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])]
+        );
+
+        // The triangle lies on the mirror plane entirely.
+        let mirrored = mirror_navmesh(&vertices, &triangles, 1, 0.0, Some(0.01));
+        assert_eq!(mirrored.vertices, vertices.to_vec());
+        assert_eq!(mirrored.triangles, triangles.to_vec());
+    }
+
+    #[test]
+    fn test_duplicate_triangles() {
+        // 3 - 2 - 5
+        // | / | /
+        // 0 - 1 - 4
+        let triangles = [
+            TriangleDefinition([0, 1, 2]),
+            TriangleDefinition([0, 2, 3]),
+            TriangleDefinition([1, 4, 5]),
+            TriangleDefinition([1, 5, 2]),
+        ];
+
+        // The last two triangles aren't fully selected.
+        let (vertices, copy) = duplicate_triangles(&triangles, &[0, 1, 2, 3, 4].into(), 6);
+        assert_eq!(vertices, vec![0, 1, 2, 3]);
+        assert_eq!(
+            copy,
+            vec![
+                (0, TriangleDefinition([6, 7, 8])),
+                (1, TriangleDefinition([6, 8, 9]))
+            ]
+        );
+
+        let (vertices, copy) = duplicate_triangles(&triangles, &[3, 4, 5].into(), 6);
+        assert!(vertices.is_empty());
+        assert!(copy.is_empty());
+    }
+
+    #[test]
+    fn test_plan_navmesh_split() {
+        // 0 - 1 - 4
+        // | / | / |
+        // 3 - 2 - 5   6 (unused)
+        let triangles = vec![
+            TriangleDefinition([0, 1, 2]),
+            TriangleDefinition([0, 2, 3]),
+            TriangleDefinition([1, 4, 5]),
+            TriangleDefinition([1, 5, 2]),
+        ];
+
+        // The right half is moved, vertices 1 and 2 are still used by the left half.
+        let split = plan_navmesh_split(&triangles, &[1, 2, 4, 5, 6].into());
+        assert_eq!(
+            split,
+            NavmeshSplit {
+                triangles: vec![3, 2],
+                vertices: vec![5, 4],
+                new_vertices: vec![1, 4, 5, 2],
+                new_triangles: vec![
+                    (2, TriangleDefinition([0, 1, 2])),
+                    (3, TriangleDefinition([0, 2, 3])),
+                ],
+            }
+        );
+
+        // Nothing is moved without fully selected triangles.
+        assert_eq!(
+            plan_navmesh_split(&triangles, &[0, 1, 4].into()),
+            NavmeshSplit::default()
+        );
+    }
+    #[test]
+    fn test_weld_navmesh_vertex() {
+        // 3 - 2   4
+        // | / |
+        // 0 - 1
+        let vertices = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 1.0),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(2.0, 0.0, 1.0),
+        ];
+        let triangles = [TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])];
+
+        // Vertex 3 is dropped onto vertex 2, the second triangle collapses.
+        let welded = weld_navmesh_vertex(&vertices, &triangles, 3, 2);
+        assert_eq!(
+            welded.vertices,
+            vec![vertices[0], vertices[1], vertices[2], vertices[4]]
+        );
+        assert_eq!(welded.triangles, vec![TriangleDefinition([0, 1, 2])]);
+        assert_eq!(welded.source_triangles, vec![0]);
+        assert_eq!(welded.remap, vec![0, 1, 2, 2, 3]);
+
+        // Vertex 0 is dropped onto the free vertex 4, indices after it are shifted.
+        let welded = weld_navmesh_vertex(&vertices, &triangles, 0, 4);
+        assert_eq!(welded.vertices, vertices[1..].to_vec());
+        assert_eq!(
+            welded.triangles,
+            vec![TriangleDefinition([3, 0, 1]), TriangleDefinition([3, 1, 2])]
+        );
+        assert_eq!(welded.remap, vec![3, 0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_inconsistent_triangles() {
+        // 3 - 2
+        // | / |
+        // 0 - 1
+        let consistent = [TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])];
+        assert!(inconsistent_triangles(&consistent, None).is_empty());
+
+        let flipped = [TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 3, 2])];
+        assert_eq!(inconsistent_triangles(&flipped, None), vec![1]);
+        // Winding of the first triangle is used as the reference.
+        let flipped = [TriangleDefinition([0, 2, 1]), TriangleDefinition([0, 2, 3])];
+        assert_eq!(inconsistent_triangles(&flipped, None), vec![1]);
+        // A single triangle has nothing to be consistent with.
+        assert!(inconsistent_triangles(&flipped, Some(&[1].into())).is_empty());
+
+        // The winding is propagated through the whole strip, so the last triangle is fine once
+        // the middle one is flipped. The separate triangle is checked on its own.
+        let strip = [
+            TriangleDefinition([0, 1, 2]),
+            TriangleDefinition([1, 2, 3]),
+            TriangleDefinition([2, 3, 4]),
+            TriangleDefinition([5, 7, 6]),
+        ];
+        assert_eq!(inconsistent_triangles(&strip, None), vec![1]);
+    }
+
+    // Makes a command in the same state as if it was executed.
+    fn executed_move(
+        node: Handle<Node>,
+        vertex: usize,
+        from: Vector3<f32>,
+        to: Vector3<f32>,
+    ) -> MoveNavmeshVertexCommand {
+        let mut command = MoveNavmeshVertexCommand::new(node, vertex, from, to);
+        command.swap();
+        command
+    }
+
+    #[test]
+    fn test_merge_vertex_moves() {
+        let node = Handle::new(1, 1);
+        let p0 = Vector3::new(0.0, 0.0, 0.0);
+        let p1 = Vector3::new(1.0, 0.0, 0.0);
+        let p2 = Vector3::new(2.0, 0.0, 0.0);
+
+        let mut first = executed_move(node, 0, p0, p1);
+        let second = executed_move(node, 0, p1, p2);
+        assert!(first.can_merge(&second));
+        first.merge(&second);
+        let merged = executed_move(node, 0, p0, p2);
+        assert_eq!(first.old_position, merged.old_position);
+        assert_eq!(first.new_position, merged.new_position);
+
+        // Other vertex, other navmesh or the move doesn't continue the previous one.
+        assert!(!first.can_merge(&executed_move(node, 1, p2, p0)));
+        assert!(!first.can_merge(&executed_move(Handle::new(2, 1), 0, p2, p0)));
+        assert!(!first.can_merge(&executed_move(node, 0, p1, p0)));
+        assert!(!first.can_merge(&WeldNavmeshVerticesCommand::new(node, 0, 1)));
+    }
+
+    #[test]
+    fn test_merge_move_groups() {
+        let node = Handle::new(1, 1);
+        let p0 = Vector3::new(0.0, 0.0, 0.0);
+        let p1 = Vector3::new(1.0, 0.0, 0.0);
+
+        let group = |vertices: &[usize], from: Vector3<f32>, to: Vector3<f32>| {
+            CommandGroup::from(
+                vertices
+                    .iter()
+                    .map(|v| SceneCommand::new(executed_move(node, *v, from, to)))
+                    .collect::<Vec<_>>(),
+            )
+        };
+
+        let mut first = group(&[0, 1], p0, p1);
+        assert!(first.can_merge(&group(&[0, 1], p1, p0)));
+        // The set of vertices must be exactly the same.
+        assert!(!first.can_merge(&group(&[0], p1, p0)));
+        assert!(!first.can_merge(&group(&[0, 2], p1, p0)));
+        assert!(!first.can_merge(&group(&[0, 1], p1, p0).with_custom_name("Named")));
+
+        // Drop onto another vertex.
+        let mut weld = group(&[0], p1, p0);
+        weld.push(SceneCommand::new(WeldNavmeshVerticesCommand::new(
+            node, 0, 1,
+        )));
+        assert!(!group(&[0, 1], p0, p1).can_merge(&weld));
+
+        first.merge(&group(&[0, 1], p1, p0));
+        assert!(first.can_merge(&group(&[0, 1], p0, p1)));
+    }
+
+    #[test]
+    fn test_extrusion_triangle() {
+        // 2
+        // | \
+        // 0 - 1   3
+        let vertices = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 0.0, -1.0),
+            Vector3::new(2.0, 0.0, 0.0),
+        ];
+
+        // No neighbour triangles - counterclockwise, when looking from above.
+        let triangle = extrusion_triangle(&vertices, &[], 0, 1, 2).unwrap();
+        let [a, b, c] = triangle.0.map(|i| vertices[i as usize]);
+        assert!((b - a).cross(&(c - a)).y > 0.0);
+        assert_eq!(
+            extrusion_triangle(&vertices, &[], 0, 2, 1),
+            Some(TriangleDefinition([0, 1, 2]))
+        );
+
+        // Winding follows the triangle, that shares an edge with the new one.
+        let existing = [TriangleDefinition([2, 0, 3])];
+        assert_eq!(
+            extrusion_triangle(&vertices, &existing, 0, 1, 2),
+            Some(TriangleDefinition([0, 2, 1]))
+        );
+        let existing = [TriangleDefinition([0, 2, 3])];
+        assert_eq!(
+            extrusion_triangle(&vertices, &existing, 0, 1, 2),
+            Some(TriangleDefinition([0, 1, 2]))
+        );
+
+        // Degenerate triangles.
+        assert_eq!(extrusion_triangle(&vertices, &[], 0, 1, 3), None);
+        assert_eq!(extrusion_triangle(&vertices, &[], 0, 1, 1), None);
+        assert_eq!(extrusion_triangle(&vertices, &[], 0, 1, 10), None);
+    }
+
+    #[test]
+    fn test_grown_edge() {
+        // 0 - 1
+        //      \
+        //       2
+        let vertices = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 1.0),
+        ];
+        let grown = grown_edge(&vertices, edge(0, 1), 2);
+        assert_eq!((grown.a, grown.b), (1, 2));
+        let grown = grown_edge(&vertices, edge(1, 0), 2);
+        assert_eq!((grown.a, grown.b), (1, 2));
+
+        // The triangle from the edge keeps the winding of its neighbour.
+        let existing = [TriangleDefinition([0, 1, 3])];
+        assert_eq!(
+            extrusion_triangle(&vertices, &existing, 0, 2, 1),
+            Some(TriangleDefinition([0, 2, 1]))
+        );
+    }
+
+    #[test]
+    fn test_orphan_vertices_remap() {
+        let triangles = [TriangleDefinition([1, 3, 4]), TriangleDefinition([4, 3, 6])];
+
+        assert_eq!(
+            orphan_vertices_remap(8, &triangles),
+            vec![None, Some(0), None, Some(1), Some(2), None, Some(3), None]
+        );
+        // Nothing to remove.
+        assert_eq!(
+            orphan_vertices_remap(3, &[TriangleDefinition([0, 1, 2])]),
+            vec![Some(0), Some(1), Some(2)]
+        );
+        assert_eq!(orphan_vertices_remap(2, &[]), vec![None, None]);
+    }
+
+    #[test]
+    fn test_delete_navmesh_vertex_round_trip() {
+        // 3 - 2 - 5
+        // | / | / |
+        // 0 - 1 - 4
+        let positions = [
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 1.0),
+            Vector3::new(0.0, 0.0, 1.0),
+            Vector3::new(2.0, 0.0, 0.0),
+            Vector3::new(2.0, 0.0, 1.0),
+        ];
+        let triangles = [
+            TriangleDefinition([0, 1, 2]),
+            TriangleDefinition([0, 2, 3]),
+            TriangleDefinition([1, 4, 5]),
+            TriangleDefinition([1, 5, 2]),
+        ];
+        let mut navmesh = Navmesh::new(&triangles, &positions);
+        navmesh.set_triangle_enabled(1, false);
+        navmesh.set_triangle_enabled(2, false);
+
+        let enabled = |navmesh: &Navmesh| {
+            (0..navmesh.triangles().len())
+                .map(|i| navmesh.is_triangle_enabled(i))
+                .collect::<Vec<_>>()
+        };
+
+        let mut command = DeleteNavmeshVertexCommand::new(Handle::NONE, 2);
+        for _ in 0..2 {
+            command.delete(&mut navmesh);
+            assert_eq!(navmesh.triangles(), &[TriangleDefinition([1, 3, 4])]);
+            assert_eq!(enabled(&navmesh), vec![false]);
+
+            // Triangles get back to their places with their flags, the disabled one too.
+            command.restore(&mut navmesh);
+            assert_eq!(navmesh.triangles(), &triangles);
+            assert_eq!(enabled(&navmesh), vec![true, false, false, true]);
+            assert_eq!(
+                navmesh
+                    .vertices()
+                    .iter()
+                    .map(|v| v.position)
+                    .collect::<Vec<_>>(),
+                positions
+            );
+        }
+    }
+
+    // 3 - 2
+    // | / |
+    // 0 - 1
+    fn square() -> Navmesh {
+        let mut navmesh = Navmesh::new(
+            &[TriangleDefinition([0, 1, 2]), TriangleDefinition([0, 2, 3])],
+            &[
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 1.0),
+                Vector3::new(0.0, 0.0, 1.0),
+            ],
+        );
+        navmesh.set_triangle_enabled(0, false);
+        navmesh.set_triangle_area(1, 7);
+        navmesh
+    }
+
+    fn navmesh_scene(navmesh: Navmesh) -> (Scene, EditorScene, Handle<Node>) {
+        let mut scene = Scene::new();
+        let editor_scene = make_editor_scene(&mut scene);
+        let node = NavigationalMeshBuilder::new(BaseBuilder::new())
+            .with_navmesh(navmesh)
+            .build(&mut scene.graph);
+        (scene, editor_scene, node)
+    }
+
+    // Triangles with their enabled flags and areas, vertex positions.
+    type NavmeshContent = (Vec<(TriangleDefinition, bool, u32)>, Vec<Vector3<f32>>);
+
+    fn content(scene: &Scene, node: Handle<Node>) -> NavmeshContent {
+        let navmesh = scene.graph[node].as_navigational_mesh().navmesh_ref();
+        (
+            navmesh
+                .triangles()
+                .iter()
+                .enumerate()
+                .map(|(index, triangle)| {
+                    (
+                        triangle.clone(),
+                        navmesh.is_triangle_enabled(index),
+                        navmesh.triangle_area(index).unwrap(),
+                    )
+                })
+                .collect(),
+            navmesh.vertices().iter().map(|v| v.position).collect(),
+        )
+    }
+
+    fn areas(scene: &Scene, node: Handle<Node>) -> Vec<u32> {
+        content(scene, node)
+            .0
+            .iter()
+            .map(|(_, _, area)| *area)
+            .collect()
+    }
+
+    #[test]
+    fn test_edits_keep_triangle_areas() {
+        let (mut scene, mut editor_scene, node) = navmesh_scene(square());
+        let sender = MessageSender(channel().0);
+        let original = content(&scene, node);
+
+        let commands: Vec<(Box<dyn Command>, Vec<u32>)> = vec![
+            (
+                Box::new(FlipNavmeshTrianglesCommand::new(node, vec![0, 1])),
+                vec![0, 7],
+            ),
+            (
+                Box::new(MergeNavmeshVerticesCommand::new(node, None, 0.01)),
+                vec![0, 7],
+            ),
+            (
+                Box::new(RemoveOrphanNavmeshVerticesCommand::new(node)),
+                vec![0, 7],
+            ),
+            (
+                Box::new(WeldNavmeshVerticesCommand::new(node, 1, 2)),
+                vec![7],
+            ),
+            (
+                Box::new(MirrorNavmeshCommand::new(node, 0, 2.0, None)),
+                vec![0, 7, 0, 7],
+            ),
+            (
+                Box::new(SimplifyNavmeshCommand::new(node, Default::default())),
+                areas(&scene, node),
+            ),
+            (
+                Box::new(SplitNavmeshEdgeCommand::new(node, edge(0, 2))),
+                vec![0, 0, 7, 7],
+            ),
+            (
+                Box::new(DuplicateNavmeshTrianglesCommand::new(
+                    node,
+                    [0, 1, 2, 3].into(),
+                    Vector3::new(0.0, 1.0, 0.0),
+                )),
+                vec![0, 7, 0, 7],
+            ),
+            (
+                Box::new(DeleteNavmeshTriangleCommand::new(node, 0)),
+                vec![7],
+            ),
+            (
+                Box::new(DeleteNavmeshEdgeCommand::new(node, edge(0, 2))),
+                vec![],
+            ),
+            (Box::new(DeleteNavmeshVertexCommand::new(node, 1)), vec![7]),
+        ];
+
+        for (mut command, expected) in commands {
+            let mut context = scene_context(&mut editor_scene, &mut scene, &sender);
+            let name = command.name(&context);
+            command.execute(&mut context);
+            assert_eq!(areas(&scene, node), expected, "{}", name);
+
+            let mut context = scene_context(&mut editor_scene, &mut scene, &sender);
+            command.revert(&mut context);
+            assert_eq!(content(&scene, node), original, "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_split_navmesh_edge_round_trip() {
+        let (mut scene, mut editor_scene, node) = navmesh_scene(square());
+        let sender = MessageSender(channel().0);
+        let (triangles, vertices) = content(&scene, node);
+
+        let mut command = SplitNavmeshEdgeCommand::new(node, edge(0, 2));
+        command.execute(&mut scene_context(&mut editor_scene, &mut scene, &sender));
+
+        let (split_triangles, split_vertices) = content(&scene, node);
+        assert_eq!(split_triangles.len(), 4);
+        assert_eq!(split_vertices.len(), 5);
+        assert_eq!(split_vertices[4], Vector3::new(0.5, 0.0, 0.5));
+        assert!(split_triangles
+            .iter()
+            .all(|(triangle, _, _)| triangle.0.contains(&4)
+                && !(triangle.0.contains(&0) && triangle.0.contains(&2))));
+
+        command.revert(&mut scene_context(&mut editor_scene, &mut scene, &sender));
+        assert_eq!(content(&scene, node), (triangles, vertices));
+    }
+
+    #[test]
+    fn test_merge_navmesh_vertices_round_trip() {
+        // Two triangles of a square, that do not share their diagonal vertices.
+        let mut navmesh = Navmesh::new(
+            &[TriangleDefinition([0, 1, 2]), TriangleDefinition([3, 4, 5])],
+            &[
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 1.0),
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0, 0.0, 1.0),
+                Vector3::new(0.0, 0.0, 1.0),
+            ],
+        );
+        navmesh.set_triangle_enabled(1, false);
+        let (mut scene, mut editor_scene, node) = navmesh_scene(navmesh);
+        let sender = MessageSender(channel().0);
+        let original = content(&scene, node);
+
+        let mut command = MergeNavmeshVerticesCommand::new(node, None, 0.01);
+        command.execute(&mut scene_context(&mut editor_scene, &mut scene, &sender));
+
+        let (triangles, vertices) = content(&scene, node);
+        assert_eq!(vertices.len(), 4);
+        assert_eq!(
+            triangles
+                .iter()
+                .map(|(_, enabled, _)| *enabled)
+                .collect::<Vec<_>>(),
+            vec![true, false]
+        );
+
+        command.revert(&mut scene_context(&mut editor_scene, &mut scene, &sender));
+        assert_eq!(content(&scene, node), original);
+    }
+
+    #[test]
+    fn test_estimated_size_includes_navmesh() {
+        let (mut scene, mut editor_scene, node) = navmesh_scene(square());
+        let sender = MessageSender(channel().0);
+
+        let replace = ReplaceNavmeshDataCommand::new(node, square());
+        assert!(
+            replace.estimated_size() >= std::mem::size_of_val(&replace) + square().allocated_size()
+        );
+
+        let mut merge = MergeNavmeshVerticesCommand::new(node, None, 0.01);
+        let size = merge.estimated_size();
+        merge.execute(&mut scene_context(&mut editor_scene, &mut scene, &sender));
+        assert!(merge.estimated_size() > size);
+        merge.revert(&mut scene_context(&mut editor_scene, &mut scene, &sender));
+        assert_eq!(merge.estimated_size(), size);
     }
 }
@@ -12,6 +12,8 @@ pub struct AddTerrainLayerCommand {
     terrain: Handle<Node>,
     layer: Option<Layer>,
     masks: Vec<TextureResource>,
+    // Index of the new layer, `None` means the end of the layers list.
+    index: Option<usize>,
 }
 
 impl AddTerrainLayerCommand {
@@ -23,8 +25,16 @@ impl AddTerrainLayerCommand {
                 ..Default::default()
             }),
             masks: Default::default(),
+            index: None,
         }
     }
+
+    /// Makes the command to insert the layer at the given index, out-of-range index is clamped to
+    /// the amount of layers.
+    pub fn with_index(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
 }
 
 impl Command for AddTerrainLayerCommand {
@@ -34,12 +44,19 @@ impl Command for AddTerrainLayerCommand {
 
     fn execute(&mut self, context: &mut SceneContext) {
         let terrain = context.scene.graph[self.terrain].as_terrain_mut();
-        terrain.add_layer(self.layer.take().unwrap(), std::mem::take(&mut self.masks));
+        let count = terrain.layers().len();
+        let index = self.index.map_or(count, |index| index.min(count));
+        self.index = Some(index);
+        terrain.insert_layer(
+            self.layer.take().unwrap(),
+            std::mem::take(&mut self.masks),
+            index,
+        );
     }
 
     fn revert(&mut self, context: &mut SceneContext) {
         let terrain = context.scene.graph[self.terrain].as_terrain_mut();
-        let (layer, masks) = terrain.pop_layer().unwrap();
+        let (layer, masks) = terrain.remove_layer(self.index.unwrap());
         self.layer = Some(layer);
         self.masks = masks;
     }
@@ -88,6 +105,40 @@ impl Command for DeleteTerrainLayerCommand {
     }
 }
 
+/// Moves the layer to another index together with its blending masks.
+#[derive(Debug)]
+pub struct MoveTerrainLayerCommand {
+    terrain: Handle<Node>,
+    from: usize,
+    to: usize,
+}
+
+impl MoveTerrainLayerCommand {
+    pub fn new(terrain: Handle<Node>, from: usize, to: usize) -> Self {
+        Self { terrain, from, to }
+    }
+
+    fn move_layer(&self, context: &mut SceneContext, from: usize, to: usize) {
+        let terrain = context.scene.graph[self.terrain].as_terrain_mut();
+        let (layer, masks) = terrain.remove_layer(from);
+        terrain.insert_layer(layer, masks, to);
+    }
+}
+
+impl Command for MoveTerrainLayerCommand {
+    fn name(&mut self, _context: &SceneContext) -> String {
+        "Move Terrain Layer".to_owned()
+    }
+
+    fn execute(&mut self, context: &mut SceneContext) {
+        self.move_layer(context, self.from, self.to);
+    }
+
+    fn revert(&mut self, context: &mut SceneContext) {
+        self.move_layer(context, self.to, self.from);
+    }
+}
+
 #[derive(Debug)]
 pub struct ModifyTerrainHeightCommand {
     terrain: Handle<Node>,
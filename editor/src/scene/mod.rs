@@ -2,11 +2,14 @@ use crate::{
     absm::selection::AbsmSelection, animation::selection::AnimationSelection,
     audio::AudioBusSelection, camera::CameraController,
     interaction::navmesh::selection::NavmeshSelection, scene::clipboard::Clipboard,
-    world::graph::selection::GraphSelection, Settings,
+    settings::navmesh::NavmeshSceneSettings, world::graph::selection::GraphSelection, Settings,
 };
 use fyrox::core::log::Log;
 use fyrox::{
-    core::{color::Color, math::aabb::AxisAlignedBoundingBox, pool::Handle, visitor::Visitor},
+    core::{
+        algebra::Vector3, color::Color, math::aabb::AxisAlignedBoundingBox, pool::Handle,
+        visitor::Visitor,
+    },
     engine::Engine,
     scene::{
         base::BaseBuilder,
@@ -47,6 +50,10 @@ pub struct EditorScene {
     pub camera_controller: CameraController,
     pub preview_camera: Handle<Node>,
     pub graph_switches: GraphUpdateSwitches,
+    /// Navmesh, that was edited last time. It is picked by the navmesh edit mode on activation.
+    pub navmesh: Handle<Node>,
+    /// Navmesh editing parameters of the scene. Changing them does not affect the defaults.
+    pub navmesh_settings: NavmeshSceneSettings,
 }
 
 impl EditorScene {
@@ -74,7 +81,17 @@ impl EditorScene {
         scene.graph.physics.integration_parameters.dt = Some(0.0);
         scene.graph.physics2d.integration_parameters.dt = Some(0.0);
 
+        let navmesh = path
+            .as_ref()
+            .and_then(|p| settings.navmesh.edited_navmeshes.get(p))
+            .map(|[index, generation]| Handle::new(*index, *generation))
+            .unwrap_or_default();
+
+        let navmesh_settings = settings.navmesh.scene_settings(path.as_deref());
+
         EditorScene {
+            navmesh,
+            navmesh_settings,
             path,
             editor_objects_root,
             scene_content_root,
@@ -241,6 +258,7 @@ impl EditorScene {
                 }
             } else if let Some(navmesh) = node.query_component_ref::<NavigationalMesh>() {
                 if settings.navmesh.draw_all {
+                    let navmesh_settings = &settings.navmesh;
                     let selection =
                         if let Selection::Navmesh(ref selection) = editor_scene.selection {
                             Some(selection)
@@ -248,37 +266,53 @@ impl EditorScene {
                             None
                         };
 
-                    for (index, vertex) in navmesh.navmesh_ref().vertices().iter().enumerate() {
-                        ctx.draw_sphere(
-                            vertex.position,
+                    let navmesh = navmesh.navmesh_ref();
+                    let scene_settings = &editor_scene.navmesh_settings;
+                    let offset = Vector3::new(0.0, scene_settings.height_offset, 0.0);
+                    let position = |i: usize| navmesh.vertices()[i].position + offset;
+
+                    // Draw into a separate context first, so the lines could be put either on top
+                    // of the scene or not.
+                    let mut navmesh_ctx = SceneDrawingContext::default();
+
+                    for index in 0..navmesh.vertices().len() {
+                        navmesh_ctx.draw_sphere(
+                            position(index),
                             10,
                             10,
-                            settings.navmesh.vertex_radius,
-                            selection.map_or(Color::GREEN, |s| {
-                                if s.unique_vertices().contains(&index) {
-                                    Color::RED
-                                } else {
-                                    Color::GREEN
-                                }
-                            }),
+                            scene_settings.vertex_radius,
+                            if selection.map_or(false, |s| s.unique_vertices().contains(&index)) {
+                                navmesh_settings.selected_vertex_color
+                            } else {
+                                navmesh_settings.vertex_color
+                            },
                         );
                     }
 
-                    for triangle in navmesh.navmesh_ref().triangles().iter() {
+                    for (index, triangle) in navmesh.triangles().iter().enumerate() {
+                        if !navmesh.is_triangle_enabled(index) {
+                            let [a, b, c] = triangle.0.map(|i| position(i as usize));
+                            draw_hatching(&mut navmesh_ctx, a, b, c, Color::opaque(255, 120, 0));
+                        }
+
                         for edge in &triangle.edges() {
-                            ctx.add_line(Line {
-                                begin: navmesh.navmesh_ref().vertices()[edge.a as usize].position,
-                                end: navmesh.navmesh_ref().vertices()[edge.b as usize].position,
-                                color: selection.map_or(Color::GREEN, |s| {
-                                    if s.contains_edge(*edge) {
-                                        Color::RED
-                                    } else {
-                                        Color::GREEN
-                                    }
-                                }),
+                            navmesh_ctx.add_line(Line {
+                                begin: position(edge.a as usize),
+                                end: position(edge.b as usize),
+                                color: if selection.map_or(false, |s| s.contains_edge(*edge)) {
+                                    navmesh_settings.selected_edge_color
+                                } else {
+                                    navmesh_settings.edge_color
+                                },
                             });
                         }
                     }
+
+                    if navmesh_settings.draw_on_top {
+                        ctx.overlay_lines.append(&mut navmesh_ctx.lines);
+                    } else {
+                        ctx.lines.append(&mut navmesh_ctx.lines);
+                    }
                 }
             } else {
                 node.debug_draw(ctx);
@@ -316,6 +350,30 @@ impl EditorScene {
     }
 }
 
+/// Fills the triangle with a set of lines parallel to its `b-c` side.
+pub fn draw_hatching(
+    ctx: &mut SceneDrawingContext,
+    a: Vector3<f32>,
+    b: Vector3<f32>,
+    c: Vector3<f32>,
+    color: Color,
+) {
+    const SPACING: f32 = 0.25;
+
+    let count = ((b - a).norm().max((c - a).norm()) / SPACING)
+        .ceil()
+        .clamp(1.0, 64.0) as usize;
+
+    for i in 1..count {
+        let t = i as f32 / count as f32;
+        ctx.add_line(Line {
+            begin: a.lerp(&b, t),
+            end: a.lerp(&c, t),
+            color,
+        });
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Selection {
     None,
@@ -32,7 +32,7 @@ use fyrox::{
 };
 use std::rc::Rc;
 
-mod command;
+pub mod command;
 
 pub struct SceneSettingsWindow {
     pub window: Handle<UiNode>,
@@ -125,10 +125,18 @@ impl SceneSettingsWindow {
         ));
     }
 
-    pub fn handle_ui_message(&self, message: &UiMessage, sender: &MessageSender) {
+    pub fn handle_ui_message(
+        &self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &MessageSender,
+    ) {
         if let Some(InspectorMessage::PropertyChanged(property_changed)) = message.data() {
             if message.destination() == self.inspector {
-                if let Some(command) = make_set_scene_property_command((), property_changed) {
+                let scene = &engine.scenes[editor_scene.scene];
+                if let Some(command) = make_set_scene_property_command((), scene, property_changed)
+                {
                     sender.send(Message::DoSceneCommand(command));
                 }
             }
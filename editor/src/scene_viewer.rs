@@ -153,7 +153,8 @@ impl SceneViewer {
 
         let navmesh_mode_tooltip =
             "Edit Navmesh\n\nNavmesh edit mode allows you to modify selected \
-        navigational mesh.";
+        navigational mesh.\n\nThis mode is available only if the scene has at least one \
+        navigational mesh.\n\nPress S to split selected edge in half.";
 
         let terrain_mode_tooltip =
             "Edit Terrain\n\nTerrain edit mode allows you to modify selected \
@@ -469,25 +470,31 @@ impl SceneViewer {
         self.selection_frame
     }
 
-    pub fn handle_message(&mut self, message: &Message, engine: &mut Engine) {
+    fn interaction_mode_buttons(&self) -> [(InteractionModeKind, Handle<UiNode>); 6] {
+        [
+            (InteractionModeKind::Select, self.select_mode),
+            (InteractionModeKind::Move, self.move_mode),
+            (InteractionModeKind::Scale, self.scale_mode),
+            (InteractionModeKind::Rotate, self.rotate_mode),
+            (InteractionModeKind::Navmesh, self.navmesh_mode),
+            (InteractionModeKind::Terrain, self.terrain_mode),
+        ]
+    }
+
+    pub fn handle_message(
+        &mut self,
+        message: &Message,
+        scenes: &SceneContainer,
+        engine: &mut Engine,
+    ) {
         if let Message::SetInteractionMode(mode) = message {
-            let active_button = match mode {
-                InteractionModeKind::Select => self.select_mode,
-                InteractionModeKind::Move => self.move_mode,
-                InteractionModeKind::Scale => self.scale_mode,
-                InteractionModeKind::Rotate => self.rotate_mode,
-                InteractionModeKind::Navmesh => self.navmesh_mode,
-                InteractionModeKind::Terrain => self.terrain_mode,
-            };
-
-            for mode_button in [
-                self.select_mode,
-                self.move_mode,
-                self.scale_mode,
-                self.rotate_mode,
-                self.navmesh_mode,
-                self.terrain_mode,
-            ] {
+            if let Some(entry) = scenes.current_scene_entry_ref() {
+                if !entry.can_activate_interaction_mode(engine, *mode) {
+                    return;
+                }
+            }
+
+            for (mode_kind, mode_button) in self.interaction_mode_buttons() {
                 let decorator = engine
                     .user_interface
                     .node(mode_button)
@@ -498,7 +505,7 @@ impl SceneViewer {
                 engine.user_interface.send_message(DecoratorMessage::select(
                     decorator,
                     MessageDirection::ToWidget,
-                    mode_button == active_button,
+                    mode_kind == *mode,
                 ));
             }
         }
@@ -624,6 +631,13 @@ impl SceneViewer {
                             engine,
                             settings,
                         ),
+                        WidgetMessage::DoubleClick { button } => self.on_double_click(
+                            button,
+                            editor_scene,
+                            interaction_mode,
+                            engine,
+                            settings,
+                        ),
                         WidgetMessage::MouseWheel { amount, .. } => {
                             editor_scene.camera_controller.on_mouse_wheel(
                                 amount * settings.camera.zoom_speed,
@@ -654,7 +668,7 @@ impl SceneViewer {
                                 editor_scene,
                                 interaction_mode,
                                 engine,
-                                &settings.key_bindings,
+                                settings,
                             ) {
                                 message.set_handled(true);
                             }
@@ -901,6 +915,16 @@ impl SceneViewer {
 
             self.set_render_target(&engine.user_interface, scene.render_target.clone());
 
+            if let Some(entry) = scenes.current_scene_entry_ref() {
+                for (mode, button) in self.interaction_mode_buttons() {
+                    enable_widget(
+                        button,
+                        entry.can_activate_interaction_mode(engine, mode),
+                        &engine.user_interface,
+                    );
+                }
+            }
+
             if let Selection::Graph(ref selection) = editor_scene.selection {
                 if let Some((_, position)) = selection.global_rotation_position(&scene.graph) {
                     engine.user_interface.send_message(Vec3EditorMessage::value(
@@ -998,17 +1022,17 @@ impl SceneViewer {
         editor_scene: &mut EditorScene,
         active_interaction_mode: Option<&mut Box<dyn InteractionMode>>,
         engine: &mut Engine,
-        key_bindings: &KeyBindings,
+        settings: &Settings,
     ) -> bool {
         if editor_scene
             .camera_controller
-            .on_key_down(key_bindings, key)
+            .on_key_down(&settings.key_bindings, key)
         {
             return true;
         }
 
         if let Some(interaction_mode) = active_interaction_mode {
-            if interaction_mode.on_key_down(key, editor_scene, engine) {
+            if interaction_mode.on_key_down(key, editor_scene, engine, settings) {
                 return true;
             }
         }
@@ -1110,6 +1134,33 @@ impl SceneViewer {
         editor_scene.camera_controller.on_mouse_button_down(button);
     }
 
+    fn on_double_click(
+        &mut self,
+        button: MouseButton,
+        editor_scene: &mut EditorScene,
+        active_interaction_mode: Option<&mut Box<dyn InteractionMode>>,
+        engine: &mut Engine,
+        settings: &Settings,
+    ) {
+        let screen_bounds = self.frame_bounds(&engine.user_interface);
+
+        if button == MouseButton::Left {
+            if let Some(current_im) = active_interaction_mode {
+                // Double click comes right after the mouse down of the second click, so the
+                // cursor is still at the click position.
+                let rel_pos = engine.user_interface.cursor_position() - screen_bounds.position;
+
+                current_im.on_left_mouse_button_double_click(
+                    editor_scene,
+                    engine,
+                    rel_pos,
+                    screen_bounds.size,
+                    settings,
+                );
+            }
+        }
+    }
+
     fn on_drop(
         &mut self,
         handle: Handle<UiNode>,
@@ -1,5 +1,7 @@
+use crate::command::{CommandStackLimits, COMMAND_MERGE_WINDOW};
 use fyrox::core::reflect::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
 pub struct GeneralSettings {
@@ -14,17 +16,63 @@ pub struct GeneralSettings {
     )]
     #[serde(default = "default_suspension_state")]
     pub suspend_unfocused_editor: bool,
+
+    #[reflect(
+        description = "Max amount of commands in the history of changes of a scene, the oldest commands are dropped when it is exceeded."
+    )]
+    #[serde(default = "default_max_command_stack_entries")]
+    pub max_command_stack_entries: usize,
+
+    #[reflect(
+        description = "Max estimated size (in megabytes) of the history of changes of a scene, the oldest commands are dropped when it is exceeded."
+    )]
+    #[serde(default = "default_max_command_stack_size")]
+    pub max_command_stack_size: usize,
+
+    #[reflect(
+        description = "Time window (in milliseconds), within which consecutive changes of the same property are merged into a single command. Zero disables merging."
+    )]
+    #[serde(default = "default_command_merge_window_ms")]
+    pub command_merge_window_ms: u64,
 }
 
 fn default_suspension_state() -> bool {
     true
 }
 
+fn default_max_command_stack_entries() -> usize {
+    512
+}
+
+fn default_max_command_stack_size() -> usize {
+    512
+}
+
+fn default_command_merge_window_ms() -> u64 {
+    COMMAND_MERGE_WINDOW.as_millis() as u64
+}
+
+impl GeneralSettings {
+    pub fn command_stack_limits(&self) -> CommandStackLimits {
+        CommandStackLimits {
+            max_entries: self.max_command_stack_entries,
+            max_size: self.max_command_stack_size.saturating_mul(1024 * 1024),
+        }
+    }
+
+    pub fn command_merge_window(&self) -> Duration {
+        Duration::from_millis(self.command_merge_window_ms)
+    }
+}
+
 impl Default for GeneralSettings {
     fn default() -> Self {
         Self {
             show_node_removal_dialog: true,
             suspend_unfocused_editor: default_suspension_state(),
+            max_command_stack_entries: default_max_command_stack_entries(),
+            max_command_stack_size: default_max_command_stack_size(),
+            command_merge_window_ms: default_command_merge_window_ms(),
         }
     }
 }
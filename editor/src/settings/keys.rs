@@ -35,12 +35,24 @@ pub struct KeyBindings {
     pub remove_selection: HotKey,
     #[serde(default = "default_focus_hotkey")]
     pub focus: HotKey,
+    #[serde(default = "default_step_command_hotkey")]
+    pub step_command: HotKey,
+    #[serde(default = "default_abort_command_hotkey")]
+    pub abort_command: HotKey,
 }
 
 fn default_focus_hotkey() -> HotKey {
     HotKey::from_key_code(KeyCode::KeyF)
 }
 
+fn default_step_command_hotkey() -> HotKey {
+    HotKey::from_key_code(KeyCode::F10)
+}
+
+fn default_abort_command_hotkey() -> HotKey {
+    HotKey::shift_key(KeyCode::F10)
+}
+
 impl Default for KeyBindings {
     fn default() -> Self {
         Self {
@@ -69,6 +81,8 @@ impl Default for KeyBindings {
             close_scene: HotKey::ctrl_key(KeyCode::KeyQ),
             remove_selection: HotKey::from_key_code(KeyCode::Delete),
             focus: default_focus_hotkey(),
+            step_command: default_step_command_hotkey(),
+            abort_command: default_abort_command_hotkey(),
         }
     }
 }
@@ -2,10 +2,18 @@ use crate::{
     inspector::editors::make_property_editors_container,
     message::MessageSender,
     settings::{
-        camera::CameraSettings, debugging::DebuggingSettings, general::GeneralSettings,
-        graphics::GraphicsSettings, keys::KeyBindings, model::ModelSettings,
-        move_mode::MoveInteractionModeSettings, navmesh::NavmeshSettings, recent::RecentFiles,
-        rotate_mode::RotateInteractionModeSettings, selection::SelectionSettings,
+        camera::CameraSettings,
+        debugging::DebuggingSettings,
+        general::GeneralSettings,
+        graphics::GraphicsSettings,
+        keys::KeyBindings,
+        model::ModelSettings,
+        move_mode::MoveInteractionModeSettings,
+        navmesh::{NavmeshMoveConstraint, NavmeshSettings},
+        ragdoll::{RagdollBonePatterns, RagdollSettings},
+        recent::RecentFiles,
+        rotate_mode::RotateInteractionModeSettings,
+        selection::SelectionSettings,
         windows::WindowsSettings,
     },
     Engine, MSG_SYNC_FLAG,
@@ -45,6 +53,7 @@ pub mod keys;
 pub mod model;
 pub mod move_mode;
 pub mod navmesh;
+pub mod ragdoll;
 pub mod recent;
 pub mod rotate_mode;
 pub mod selection;
@@ -69,6 +78,8 @@ pub struct Settings {
     pub model: ModelSettings,
     pub camera: CameraSettings,
     pub navmesh: NavmeshSettings,
+    #[serde(default)]
+    pub ragdoll: RagdollSettings,
     pub key_bindings: KeyBindings,
     #[reflect(hidden)]
     pub recent: RecentFiles,
@@ -142,6 +153,9 @@ impl Settings {
         >::new());
         container.insert(InspectablePropertyEditorDefinition::<ModelSettings>::new());
         container.insert(InspectablePropertyEditorDefinition::<NavmeshSettings>::new());
+        container.insert(EnumPropertyEditorDefinition::<NavmeshMoveConstraint>::new());
+        container.insert(InspectablePropertyEditorDefinition::<RagdollSettings>::new());
+        container.insert(InspectablePropertyEditorDefinition::<RagdollBonePatterns>::new());
         container.insert(InspectablePropertyEditorDefinition::<KeyBindings>::new());
         container.insert(HotKeyPropertyEditorDefinition);
         container.insert(KeyBindingPropertyEditorDefinition);
@@ -1,15 +1,178 @@
-use fyrox::core::reflect::prelude::*;
-use serde::{Deserialize, Serialize};
+use fyrox::core::{color::Color, reflect::prelude::*};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+// Color does not implement serde traits, so it is stored as an RGBA array.
+mod color_serde {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(color: &Color, serializer: S) -> Result<S::Ok, S::Error> {
+        [color.r, color.g, color.b, color.a].serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Color, D::Error> {
+        let [r, g, b, a] = <[u8; 4]>::deserialize(deserializer)?;
+        Ok(Color::from_rgba(r, g, b, a))
+    }
+}
+
+/// Constraint of the movement of navmesh vertices, that is used when the center of the move gizmo
+/// is dragged.
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Debug,
+    Deserialize,
+    Serialize,
+    Reflect,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum NavmeshMoveConstraint {
+    /// Vertices are moved in the plane, that is parallel to the camera view plane.
+    ScreenPlane,
+    /// Vertices are moved in the horizontal (XZ) plane.
+    HorizontalPlane,
+}
 
 #[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
+#[serde(default)]
 pub struct NavmeshSettings {
     #[reflect(
         description = "Show all navigational meshes in scene. With this function turned off, only currently edited navmesh will be shown."
     )]
     pub draw_all: bool,
 
-    #[reflect(description = "Radius of a nav mesh vertex.")]
+    #[reflect(
+        description = "Radius of a nav mesh vertex. It is the default for new scenes, every scene has its own value, which could be changed in the Navmesh panel."
+    )]
     pub vertex_radius: f32,
+
+    #[reflect(
+        description = "Snap dragged vertices to the scene geometry below them. Could also be switched in the Navmesh panel."
+    )]
+    pub snap_to_geometry: bool,
+
+    #[reflect(
+        description = "Vertical offset of a snapped vertex from the geometry surface. It is the default for new scenes, every scene has its own value, which could be changed in the Navmesh panel."
+    )]
+    pub snap_offset: f32,
+
+    #[reflect(
+        description = "Move an edge duplicated by Shift+Drag along the plane of the triangle of the source edge, instead of the plane of the move gizmo. Could also be switched in the Navmesh panel."
+    )]
+    pub extrude_along_surface: bool,
+
+    #[reflect(
+        description = "Maximum vertical distance (both up and down) at which the geometry is searched for snapping.",
+        min_value = 0.0
+    )]
+    pub snap_distance: f32,
+
+    #[reflect(
+        description = "Maximum distance between vertices, that will be merged into one by the Merge action of the Navmesh panel.",
+        min_value = 0.0
+    )]
+    pub merge_threshold: f32,
+
+    #[reflect(
+        description = "Weld a dragged vertex with another vertex, if it was dropped closer than vertex radius to it."
+    )]
+    pub weld_on_drop: bool,
+
+    #[reflect(
+        description = "Do not pick vertices and edges, that are hidden behind the scene geometry. Back faces of the geometry are ignored according to the selection settings. Useful when nav meshes are not drawn on top of the scene."
+    )]
+    pub occlusion_picking: bool,
+
+    #[reflect(
+        description = "Vertices and edges farther than this distance from the camera could not be picked.",
+        min_value = 0.0
+    )]
+    pub max_pick_distance: f32,
+
+    #[serde(with = "color_serde")]
+    #[reflect(description = "Color of a nav mesh vertex.")]
+    pub vertex_color: Color,
+
+    #[serde(with = "color_serde")]
+    #[reflect(description = "Color of a selected nav mesh vertex.")]
+    pub selected_vertex_color: Color,
+
+    #[serde(with = "color_serde")]
+    #[reflect(description = "Color of a nav mesh edge.")]
+    pub edge_color: Color,
+
+    #[serde(with = "color_serde")]
+    #[reflect(description = "Color of a selected nav mesh edge.")]
+    pub selected_edge_color: Color,
+
+    #[reflect(description = "Draw nav meshes on top of the scene geometry.")]
+    pub draw_on_top: bool,
+
+    #[reflect(
+        description = "Vertical offset of drawn nav meshes. Helps to avoid z-fighting with the geometry, that nav meshes lie on. It is the default for new scenes, every scene has its own value, which could be changed in the Navmesh panel."
+    )]
+    pub height_offset: f32,
+
+    #[reflect(
+        description = "Constraint of the movement, when vertices are dragged by the center of the move gizmo. Movement could also be locked to Y axis by Y key in the navmesh edit mode."
+    )]
+    pub move_constraint: NavmeshMoveConstraint,
+
+    #[reflect(
+        description = "Show the boundary of the area, that is walkable for an agent of the given radius, and triangles narrower than the agent. Could also be switched in the Navmesh panel."
+    )]
+    pub show_agent_radius: bool,
+
+    #[reflect(description = "Radius of an agent for the preview.", min_value = 0.0)]
+    pub agent_radius: f32,
+
+    #[reflect(
+        description = "Highlight triangles, that are steeper than the maximal walkable slope, in red and the rest in green. Could also be switched in the Navmesh panel."
+    )]
+    pub show_slope: bool,
+
+    #[reflect(
+        description = "Maximal walkable slope for the preview, in degrees.",
+        min_value = 0.0,
+        max_value = 90.0
+    )]
+    pub max_walkable_slope: f32,
+
+    #[reflect(
+        description = "Search overlapping triangles of the edited navmesh every few seconds and highlight them in red. The search could also be done by the Validate action of the Navmesh panel."
+    )]
+    pub highlight_overlaps: bool,
+
+    #[reflect(
+        description = "Show indices of the vertices of the edited navmesh next to them. Could also be switched in the Navmesh panel."
+    )]
+    pub show_vertex_labels: bool,
+
+    #[reflect(description = "Show coordinates of the vertices in the vertex labels.")]
+    pub show_label_coordinates: bool,
+
+    #[reflect(
+        description = "Vertices farther than this distance from the camera are not labeled.",
+        min_value = 0.0
+    )]
+    pub label_distance: f32,
+
+    /// Index and generation of the handle of the last edited navmesh for every scene.
+    #[reflect(hidden)]
+    pub edited_navmeshes: HashMap<PathBuf, [u32; 2]>,
+
+    /// Navmesh editing parameters of every scene, which differ from the defaults.
+    #[reflect(hidden)]
+    pub scene_settings: HashMap<PathBuf, NavmeshSceneSettings>,
 }
 
 impl Default for NavmeshSettings {
@@ -17,6 +180,151 @@ impl Default for NavmeshSettings {
         Self {
             draw_all: true,
             vertex_radius: 0.2,
+            snap_to_geometry: false,
+            snap_offset: 0.05,
+            extrude_along_surface: false,
+            snap_distance: 2.0,
+            merge_threshold: 0.05,
+            weld_on_drop: true,
+            occlusion_picking: false,
+            max_pick_distance: 1000.0,
+            vertex_color: Color::GREEN,
+            selected_vertex_color: Color::RED,
+            edge_color: Color::GREEN,
+            selected_edge_color: Color::RED,
+            draw_on_top: false,
+            height_offset: 0.0,
+            move_constraint: NavmeshMoveConstraint::ScreenPlane,
+            show_agent_radius: false,
+            agent_radius: 0.5,
+            show_slope: false,
+            max_walkable_slope: 45.0,
+            highlight_overlaps: false,
+            show_vertex_labels: false,
+            show_label_coordinates: false,
+            label_distance: 20.0,
+            edited_navmeshes: Default::default(),
+            scene_settings: Default::default(),
+        }
+    }
+}
+
+impl NavmeshSettings {
+    /// Returns navmesh editing parameters for a scene, that does not have its own ones.
+    pub fn default_scene_settings(&self) -> NavmeshSceneSettings {
+        NavmeshSceneSettings {
+            vertex_radius: self.vertex_radius,
+            snap_offset: self.snap_offset,
+            height_offset: self.height_offset,
         }
     }
+
+    /// Returns navmesh editing parameters of the scene with the given path. Scenes without their
+    /// own parameters (including the ones, that were never saved) use the defaults.
+    pub fn scene_settings(&self, path: Option<&Path>) -> NavmeshSceneSettings {
+        path.and_then(|path| self.scene_settings.get(path))
+            .cloned()
+            .unwrap_or_else(|| self.default_scene_settings())
+    }
+
+    /// Remembers navmesh editing parameters of the scene with the given path. Parameters, that are
+    /// equal to the defaults, are not stored, so the scene will follow changes of the defaults.
+    pub fn set_scene_settings(&mut self, path: &Path, scene_settings: NavmeshSceneSettings) {
+        if scene_settings == self.default_scene_settings() {
+            self.scene_settings.remove(path);
+        } else {
+            self.scene_settings.insert(path.to_owned(), scene_settings);
+        }
+    }
+}
+
+/// Navmesh editing parameters, which depend on the scale of a scene. Every scene has its own copy,
+/// so a vertex radius, that fits a dungeon, does not make editing of an open world unusable.
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug)]
+#[serde(default)]
+pub struct NavmeshSceneSettings {
+    /// Radius of a nav mesh vertex.
+    pub vertex_radius: f32,
+    /// Vertical offset of a snapped vertex from the geometry surface.
+    pub snap_offset: f32,
+    /// Vertical offset of drawn nav meshes.
+    pub height_offset: f32,
+}
+
+impl Default for NavmeshSceneSettings {
+    fn default() -> Self {
+        NavmeshSettings::default().default_scene_settings()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_settings_round_trip() {
+        let settings = NavmeshSettings {
+            vertex_color: Color::from_rgba(1, 2, 3, 4),
+            selected_edge_color: Color::opaque(200, 100, 50),
+            draw_on_top: true,
+            height_offset: 0.25,
+            move_constraint: NavmeshMoveConstraint::HorizontalPlane,
+            edited_navmeshes: [(PathBuf::from("data/level.rgs"), [12, 3])].into(),
+            scene_settings: [(
+                PathBuf::from("data/level.rgs"),
+                NavmeshSceneSettings {
+                    vertex_radius: 1.5,
+                    snap_offset: 0.1,
+                    height_offset: 0.2,
+                },
+            )]
+            .into(),
+            ..Default::default()
+        };
+
+        let source = ron::to_string(&settings).unwrap();
+        assert_eq!(ron::from_str::<NavmeshSettings>(&source).unwrap(), settings);
+    }
+
+    #[test]
+    fn test_missing_colors_are_defaulted() {
+        let settings = ron::from_str::<NavmeshSettings>("(draw_all: false)").unwrap();
+        assert!(!settings.draw_all);
+        assert_eq!(
+            settings.vertex_color,
+            NavmeshSettings::default().vertex_color
+        );
+    }
+
+    #[test]
+    fn test_scene_settings() {
+        let mut settings = NavmeshSettings::default();
+        let path = Path::new("data/level.rgs");
+        assert_eq!(
+            settings.scene_settings(Some(path)),
+            settings.default_scene_settings()
+        );
+
+        let scene_settings = NavmeshSceneSettings {
+            vertex_radius: 2.0,
+            ..settings.default_scene_settings()
+        };
+        settings.set_scene_settings(path, scene_settings.clone());
+        assert_eq!(settings.scene_settings(Some(path)), scene_settings);
+        assert_eq!(
+            settings.scene_settings(None),
+            settings.default_scene_settings()
+        );
+        // Defaults are not changed.
+        assert_eq!(
+            settings.vertex_radius,
+            NavmeshSettings::default().vertex_radius
+        );
+
+        // Scene with default parameters follows the defaults.
+        settings.set_scene_settings(path, settings.default_scene_settings());
+        assert!(settings.scene_settings.is_empty());
+        settings.vertex_radius = 0.5;
+        assert_eq!(settings.scene_settings(Some(path)).vertex_radius, 0.5);
+    }
 }
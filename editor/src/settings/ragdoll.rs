@@ -0,0 +1,365 @@
+use crate::utils::ragdoll::preset::RagdollPresetFile;
+use fyrox::core::reflect::prelude::*;
+use serde::{Deserialize, Serialize};
+use strum_macros::{AsRefStr, EnumString};
+
+/// Bone of a humanoid character, that is used to generate a ragdoll.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, AsRefStr, EnumString)]
+pub enum RagdollBone {
+    Hips,
+    LeftUpLeg,
+    LeftLeg,
+    LeftFoot,
+    RightUpLeg,
+    RightLeg,
+    RightFoot,
+    Spine,
+    Spine1,
+    Spine2,
+    LeftShoulder,
+    LeftArm,
+    LeftForeArm,
+    LeftHand,
+    RightShoulder,
+    RightArm,
+    RightForeArm,
+    RightHand,
+    Neck,
+    Head,
+    LeftThumb,
+    LeftIndex,
+    LeftMiddle,
+    LeftRing,
+    LeftPinky,
+    RightThumb,
+    RightIndex,
+    RightMiddle,
+    RightRing,
+    RightPinky,
+    LeftToeBase,
+    RightToeBase,
+}
+
+impl RagdollBone {
+    pub const ALL: [RagdollBone; 32] = [
+        RagdollBone::Hips,
+        RagdollBone::LeftUpLeg,
+        RagdollBone::LeftLeg,
+        RagdollBone::LeftFoot,
+        RagdollBone::RightUpLeg,
+        RagdollBone::RightLeg,
+        RagdollBone::RightFoot,
+        RagdollBone::Spine,
+        RagdollBone::Spine1,
+        RagdollBone::Spine2,
+        RagdollBone::LeftShoulder,
+        RagdollBone::LeftArm,
+        RagdollBone::LeftForeArm,
+        RagdollBone::LeftHand,
+        RagdollBone::RightShoulder,
+        RagdollBone::RightArm,
+        RagdollBone::RightForeArm,
+        RagdollBone::RightHand,
+        RagdollBone::Neck,
+        RagdollBone::Head,
+        RagdollBone::LeftThumb,
+        RagdollBone::LeftIndex,
+        RagdollBone::LeftMiddle,
+        RagdollBone::LeftRing,
+        RagdollBone::LeftPinky,
+        RagdollBone::RightThumb,
+        RagdollBone::RightIndex,
+        RagdollBone::RightMiddle,
+        RagdollBone::RightRing,
+        RagdollBone::RightPinky,
+        RagdollBone::LeftToeBase,
+        RagdollBone::RightToeBase,
+    ];
+
+    /// Returns the bone of the other side of the body, if the bone has a side.
+    pub fn mirrored(self) -> Option<RagdollBone> {
+        match self {
+            RagdollBone::LeftUpLeg => Some(RagdollBone::RightUpLeg),
+            RagdollBone::LeftLeg => Some(RagdollBone::RightLeg),
+            RagdollBone::LeftFoot => Some(RagdollBone::RightFoot),
+            RagdollBone::LeftShoulder => Some(RagdollBone::RightShoulder),
+            RagdollBone::LeftArm => Some(RagdollBone::RightArm),
+            RagdollBone::LeftForeArm => Some(RagdollBone::RightForeArm),
+            RagdollBone::LeftHand => Some(RagdollBone::RightHand),
+            RagdollBone::RightUpLeg => Some(RagdollBone::LeftUpLeg),
+            RagdollBone::RightLeg => Some(RagdollBone::LeftLeg),
+            RagdollBone::RightFoot => Some(RagdollBone::LeftFoot),
+            RagdollBone::RightShoulder => Some(RagdollBone::LeftShoulder),
+            RagdollBone::RightArm => Some(RagdollBone::LeftArm),
+            RagdollBone::RightForeArm => Some(RagdollBone::LeftForeArm),
+            RagdollBone::RightHand => Some(RagdollBone::LeftHand),
+            RagdollBone::LeftThumb => Some(RagdollBone::RightThumb),
+            RagdollBone::LeftIndex => Some(RagdollBone::RightIndex),
+            RagdollBone::LeftMiddle => Some(RagdollBone::RightMiddle),
+            RagdollBone::LeftRing => Some(RagdollBone::RightRing),
+            RagdollBone::LeftPinky => Some(RagdollBone::RightPinky),
+            RagdollBone::LeftToeBase => Some(RagdollBone::RightToeBase),
+            RagdollBone::RightThumb => Some(RagdollBone::LeftThumb),
+            RagdollBone::RightIndex => Some(RagdollBone::LeftIndex),
+            RagdollBone::RightMiddle => Some(RagdollBone::LeftMiddle),
+            RagdollBone::RightRing => Some(RagdollBone::LeftRing),
+            RagdollBone::RightPinky => Some(RagdollBone::LeftPinky),
+            RagdollBone::RightToeBase => Some(RagdollBone::LeftToeBase),
+            _ => None,
+        }
+    }
+
+    pub fn is_left(self) -> bool {
+        matches!(
+            self,
+            RagdollBone::LeftUpLeg
+                | RagdollBone::LeftLeg
+                | RagdollBone::LeftFoot
+                | RagdollBone::LeftShoulder
+                | RagdollBone::LeftArm
+                | RagdollBone::LeftForeArm
+                | RagdollBone::LeftHand
+                | RagdollBone::LeftThumb
+                | RagdollBone::LeftIndex
+                | RagdollBone::LeftMiddle
+                | RagdollBone::LeftRing
+                | RagdollBone::LeftPinky
+                | RagdollBone::LeftToeBase
+        )
+    }
+
+    /// Returns the hand or the foot, that the finger or the toe belongs to.
+    pub fn digit_parent(self) -> Option<RagdollBone> {
+        match self {
+            RagdollBone::LeftThumb
+            | RagdollBone::LeftIndex
+            | RagdollBone::LeftMiddle
+            | RagdollBone::LeftRing
+            | RagdollBone::LeftPinky => Some(RagdollBone::LeftHand),
+            RagdollBone::RightThumb
+            | RagdollBone::RightIndex
+            | RagdollBone::RightMiddle
+            | RagdollBone::RightRing
+            | RagdollBone::RightPinky => Some(RagdollBone::RightHand),
+            RagdollBone::LeftToeBase => Some(RagdollBone::LeftFoot),
+            RagdollBone::RightToeBase => Some(RagdollBone::RightFoot),
+            _ => None,
+        }
+    }
+
+    /// Returns the bone, that the bone is attached to in the skeleton. Hips are the root of the
+    /// skeleton, they have no parent.
+    pub fn skeleton_parent(self) -> Option<RagdollBone> {
+        match self {
+            RagdollBone::Hips => None,
+            RagdollBone::LeftUpLeg | RagdollBone::RightUpLeg | RagdollBone::Spine => {
+                Some(RagdollBone::Hips)
+            }
+            RagdollBone::LeftLeg => Some(RagdollBone::LeftUpLeg),
+            RagdollBone::LeftFoot => Some(RagdollBone::LeftLeg),
+            RagdollBone::RightLeg => Some(RagdollBone::RightUpLeg),
+            RagdollBone::RightFoot => Some(RagdollBone::RightLeg),
+            RagdollBone::Spine1 => Some(RagdollBone::Spine),
+            RagdollBone::Spine2 => Some(RagdollBone::Spine1),
+            RagdollBone::LeftShoulder | RagdollBone::RightShoulder | RagdollBone::Neck => {
+                Some(RagdollBone::Spine2)
+            }
+            RagdollBone::LeftArm => Some(RagdollBone::LeftShoulder),
+            RagdollBone::LeftForeArm => Some(RagdollBone::LeftArm),
+            RagdollBone::LeftHand => Some(RagdollBone::LeftForeArm),
+            RagdollBone::RightArm => Some(RagdollBone::RightShoulder),
+            RagdollBone::RightForeArm => Some(RagdollBone::RightArm),
+            RagdollBone::RightHand => Some(RagdollBone::RightForeArm),
+            RagdollBone::Head => Some(RagdollBone::Neck),
+            _ => self.digit_parent(),
+        }
+    }
+}
+
+/// Name patterns of the bones, that are used by Autofill of the ragdoll wizard. Every pattern is a
+/// comma-separated list of substrings, a bone matches if its name contains any of them.
+#[derive(Deserialize, Serialize, PartialEq, Eq, Clone, Debug, Reflect)]
+#[serde(default)]
+pub struct RagdollBonePatterns {
+    pub hips: String,
+    pub left_up_leg: String,
+    pub left_leg: String,
+    pub left_foot: String,
+    pub right_up_leg: String,
+    pub right_leg: String,
+    pub right_foot: String,
+    pub spine: String,
+    pub spine1: String,
+    pub spine2: String,
+    pub left_shoulder: String,
+    pub left_arm: String,
+    pub left_fore_arm: String,
+    pub left_hand: String,
+    pub right_shoulder: String,
+    pub right_arm: String,
+    pub right_fore_arm: String,
+    pub right_hand: String,
+    pub neck: String,
+    pub head: String,
+    pub left_thumb: String,
+    pub left_index: String,
+    pub left_middle: String,
+    pub left_ring: String,
+    pub left_pinky: String,
+    pub right_thumb: String,
+    pub right_index: String,
+    pub right_middle: String,
+    pub right_ring: String,
+    pub right_pinky: String,
+    pub left_toe_base: String,
+    pub right_toe_base: String,
+}
+
+impl Default for RagdollBonePatterns {
+    fn default() -> Self {
+        Self::mixamo()
+    }
+}
+
+impl RagdollBonePatterns {
+    /// Bone names of the characters from Mixamo.
+    pub fn mixamo() -> Self {
+        Self {
+            hips: "Hips".to_owned(),
+            left_up_leg: "LeftUpLeg".to_owned(),
+            left_leg: "LeftLeg".to_owned(),
+            left_foot: "LeftFoot".to_owned(),
+            right_up_leg: "RightUpLeg".to_owned(),
+            right_leg: "RightLeg".to_owned(),
+            right_foot: "RightFoot".to_owned(),
+            spine: "Spine".to_owned(),
+            spine1: "Spine1".to_owned(),
+            spine2: "Spine2".to_owned(),
+            left_shoulder: "LeftShoulder".to_owned(),
+            left_arm: "LeftArm".to_owned(),
+            left_fore_arm: "LeftForeArm".to_owned(),
+            left_hand: "LeftHand".to_owned(),
+            right_shoulder: "RightShoulder".to_owned(),
+            right_arm: "RightArm".to_owned(),
+            right_fore_arm: "RightForeArm".to_owned(),
+            right_hand: "RightHand".to_owned(),
+            neck: "Neck".to_owned(),
+            head: "Head".to_owned(),
+            left_thumb: "LeftHandThumb1".to_owned(),
+            left_index: "LeftHandIndex1".to_owned(),
+            left_middle: "LeftHandMiddle1".to_owned(),
+            left_ring: "LeftHandRing1".to_owned(),
+            left_pinky: "LeftHandPinky1".to_owned(),
+            right_thumb: "RightHandThumb1".to_owned(),
+            right_index: "RightHandIndex1".to_owned(),
+            right_middle: "RightHandMiddle1".to_owned(),
+            right_ring: "RightHandRing1".to_owned(),
+            right_pinky: "RightHandPinky1".to_owned(),
+            left_toe_base: "LeftToeBase".to_owned(),
+            right_toe_base: "RightToeBase".to_owned(),
+        }
+    }
+
+    /// Bone names of the Unreal Engine skeleton, they are common for rigs made in other tools too.
+    pub fn unreal() -> Self {
+        Self {
+            hips: "pelvis".to_owned(),
+            left_up_leg: "thigh_l".to_owned(),
+            left_leg: "calf_l".to_owned(),
+            left_foot: "foot_l".to_owned(),
+            right_up_leg: "thigh_r".to_owned(),
+            right_leg: "calf_r".to_owned(),
+            right_foot: "foot_r".to_owned(),
+            spine: "spine_01".to_owned(),
+            spine1: "spine_02".to_owned(),
+            spine2: "spine_03".to_owned(),
+            left_shoulder: "clavicle_l".to_owned(),
+            left_arm: "upperarm_l".to_owned(),
+            left_fore_arm: "lowerarm_l".to_owned(),
+            left_hand: "hand_l".to_owned(),
+            right_shoulder: "clavicle_r".to_owned(),
+            right_arm: "upperarm_r".to_owned(),
+            right_fore_arm: "lowerarm_r".to_owned(),
+            right_hand: "hand_r".to_owned(),
+            neck: "neck_01".to_owned(),
+            head: "head".to_owned(),
+            left_thumb: "thumb_01_l".to_owned(),
+            left_index: "index_01_l".to_owned(),
+            left_middle: "middle_01_l".to_owned(),
+            left_ring: "ring_01_l".to_owned(),
+            left_pinky: "pinky_01_l".to_owned(),
+            right_thumb: "thumb_01_r".to_owned(),
+            right_index: "index_01_r".to_owned(),
+            right_middle: "middle_01_r".to_owned(),
+            right_ring: "ring_01_r".to_owned(),
+            right_pinky: "pinky_01_r".to_owned(),
+            left_toe_base: "ball_l".to_owned(),
+            right_toe_base: "ball_r".to_owned(),
+        }
+    }
+
+    /// Returns the comma-separated patterns of the bone.
+    pub fn patterns(&self, bone: RagdollBone) -> &str {
+        match bone {
+            RagdollBone::Hips => &self.hips,
+            RagdollBone::LeftUpLeg => &self.left_up_leg,
+            RagdollBone::LeftLeg => &self.left_leg,
+            RagdollBone::LeftFoot => &self.left_foot,
+            RagdollBone::RightUpLeg => &self.right_up_leg,
+            RagdollBone::RightLeg => &self.right_leg,
+            RagdollBone::RightFoot => &self.right_foot,
+            RagdollBone::Spine => &self.spine,
+            RagdollBone::Spine1 => &self.spine1,
+            RagdollBone::Spine2 => &self.spine2,
+            RagdollBone::LeftShoulder => &self.left_shoulder,
+            RagdollBone::LeftArm => &self.left_arm,
+            RagdollBone::LeftForeArm => &self.left_fore_arm,
+            RagdollBone::LeftHand => &self.left_hand,
+            RagdollBone::RightShoulder => &self.right_shoulder,
+            RagdollBone::RightArm => &self.right_arm,
+            RagdollBone::RightForeArm => &self.right_fore_arm,
+            RagdollBone::RightHand => &self.right_hand,
+            RagdollBone::Neck => &self.neck,
+            RagdollBone::Head => &self.head,
+            RagdollBone::LeftThumb => &self.left_thumb,
+            RagdollBone::LeftIndex => &self.left_index,
+            RagdollBone::LeftMiddle => &self.left_middle,
+            RagdollBone::LeftRing => &self.left_ring,
+            RagdollBone::LeftPinky => &self.left_pinky,
+            RagdollBone::RightThumb => &self.right_thumb,
+            RagdollBone::RightIndex => &self.right_index,
+            RagdollBone::RightMiddle => &self.right_middle,
+            RagdollBone::RightRing => &self.right_ring,
+            RagdollBone::RightPinky => &self.right_pinky,
+            RagdollBone::LeftToeBase => &self.left_toe_base,
+            RagdollBone::RightToeBase => &self.right_toe_base,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, PartialEq, Clone, Debug, Reflect)]
+#[serde(default)]
+pub struct RagdollSettings {
+    #[reflect(
+        description = "Name patterns of the bones, that are used by Autofill of the ragdoll wizard. Every pattern is a comma-separated list of substrings, case is ignored. Could also be edited in the ragdoll wizard."
+    )]
+    pub bone_patterns: RagdollBonePatterns,
+    #[reflect(
+        description = "Comma-separated pairs of left/right name tokens, that are used to find mirrored bones in the ragdoll wizard. Case matters."
+    )]
+    pub mirror_tokens: String,
+    /// Settings of the preset, that was used last time. Bones are not stored, because they belong
+    /// to a scene.
+    #[reflect(hidden)]
+    pub last_preset: Option<RagdollPresetFile>,
+}
+
+impl Default for RagdollSettings {
+    fn default() -> Self {
+        Self {
+            bone_patterns: Default::default(),
+            mirror_tokens: "Left/Right, _l/_r, .L/.R".to_owned(),
+            last_preset: None,
+        }
+    }
+}
@@ -11,8 +11,11 @@ use fyrox::{
 use std::{fs::File, io::Read, path::Path};
 
 pub mod doc;
+pub mod overlap;
 pub mod path_fixer;
 pub mod ragdoll;
+pub mod ragdoll_command;
+pub mod ragdoll_simulation;
 
 pub fn is_slice_equal_permutation<T: PartialEq>(a: &[T], b: &[T]) -> bool {
     if a.is_empty() && !b.is_empty() {
@@ -0,0 +1,379 @@
+//! Penetration tests for simple convex shapes (balls, capsules and boxes) in world space. It is
+//! meant to be used for quick validation of generated physics setups (ragdolls for example), not
+//! for the simulation itself.
+
+use fyrox::core::algebra::{UnitQuaternion, Vector3};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OverlapShape {
+    Ball {
+        center: Vector3<f32>,
+        radius: f32,
+    },
+    Capsule {
+        begin: Vector3<f32>,
+        end: Vector3<f32>,
+        radius: f32,
+    },
+    Cuboid {
+        center: Vector3<f32>,
+        rotation: UnitQuaternion<f32>,
+        half_extents: Vector3<f32>,
+    },
+}
+
+impl OverlapShape {
+    /// Returns radius of the shape, if it has one.
+    pub fn radius(&self) -> Option<f32> {
+        match self {
+            OverlapShape::Ball { radius, .. } | OverlapShape::Capsule { radius, .. } => {
+                Some(*radius)
+            }
+            OverlapShape::Cuboid { .. } => None,
+        }
+    }
+}
+
+fn closest_point_on_segment(
+    point: Vector3<f32>,
+    begin: Vector3<f32>,
+    end: Vector3<f32>,
+) -> Vector3<f32> {
+    let dir = end - begin;
+    let len_sqr = dir.norm_squared();
+    if len_sqr <= f32::EPSILON {
+        begin
+    } else {
+        begin + dir.scale(((point - begin).dot(&dir) / len_sqr).clamp(0.0, 1.0))
+    }
+}
+
+/// Shortest distance between two segments.
+fn segment_segment_distance(
+    p1: Vector3<f32>,
+    q1: Vector3<f32>,
+    p2: Vector3<f32>,
+    q2: Vector3<f32>,
+) -> f32 {
+    let d1 = q1 - p1;
+    let d2 = q2 - p2;
+    let r = p1 - p2;
+    let a = d1.norm_squared();
+    let e = d2.norm_squared();
+    let f = d2.dot(&r);
+
+    let (s, t) = if a <= f32::EPSILON && e <= f32::EPSILON {
+        (0.0, 0.0)
+    } else if a <= f32::EPSILON {
+        (0.0, (f / e).clamp(0.0, 1.0))
+    } else {
+        let c = d1.dot(&r);
+        if e <= f32::EPSILON {
+            ((-c / a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let b = d1.dot(&d2);
+            let denom = a * e - b * b;
+            let mut s = if denom > f32::EPSILON {
+                ((b * f - c * e) / denom).clamp(0.0, 1.0)
+            } else {
+                // Parallel segments, any point will do.
+                0.0
+            };
+            let mut t = (b * s + f) / e;
+            if t < 0.0 {
+                t = 0.0;
+                s = (-c / a).clamp(0.0, 1.0);
+            } else if t > 1.0 {
+                t = 1.0;
+                s = ((b - c) / a).clamp(0.0, 1.0);
+            }
+            (s, t)
+        }
+    };
+
+    ((p1 + d1.scale(s)) - (p2 + d2.scale(t))).norm()
+}
+
+/// Signed distance from the point to the surface of the box, it is negative if the point is
+/// inside the box.
+fn cuboid_signed_distance(
+    center: Vector3<f32>,
+    rotation: &UnitQuaternion<f32>,
+    half_extents: Vector3<f32>,
+    point: Vector3<f32>,
+) -> f32 {
+    let local = rotation.inverse_transform_vector(&(point - center));
+    let q = local.abs() - half_extents;
+    let outside = q.sup(&Vector3::zeros()).norm();
+    let inside = q.x.max(q.y).max(q.z).min(0.0);
+    outside + inside
+}
+
+/// Minimal signed distance from the segment to the box. Signed distance to a convex shape is a
+/// convex function, so ternary search along the segment gives the minimum.
+fn segment_cuboid_distance(
+    begin: Vector3<f32>,
+    end: Vector3<f32>,
+    center: Vector3<f32>,
+    rotation: &UnitQuaternion<f32>,
+    half_extents: Vector3<f32>,
+) -> f32 {
+    let distance_at =
+        |t: f32| cuboid_signed_distance(center, rotation, half_extents, begin.lerp(&end, t));
+
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    for _ in 0..48 {
+        let m1 = lo + (hi - lo) / 3.0;
+        let m2 = hi - (hi - lo) / 3.0;
+        if distance_at(m1) < distance_at(m2) {
+            hi = m2;
+        } else {
+            lo = m1;
+        }
+    }
+
+    distance_at(0.5 * (lo + hi))
+}
+
+/// Separating axis test for two oriented boxes, returns minimal overlap along all the axes.
+fn cuboid_cuboid_penetration(
+    center_a: Vector3<f32>,
+    rotation_a: &UnitQuaternion<f32>,
+    half_a: Vector3<f32>,
+    center_b: Vector3<f32>,
+    rotation_b: &UnitQuaternion<f32>,
+    half_b: Vector3<f32>,
+) -> f32 {
+    let axes_a = [
+        rotation_a * Vector3::x(),
+        rotation_a * Vector3::y(),
+        rotation_a * Vector3::z(),
+    ];
+    let axes_b = [
+        rotation_b * Vector3::x(),
+        rotation_b * Vector3::y(),
+        rotation_b * Vector3::z(),
+    ];
+
+    let mut axes = Vec::with_capacity(15);
+    axes.extend_from_slice(&axes_a);
+    axes.extend_from_slice(&axes_b);
+    for a in axes_a.iter() {
+        for b in axes_b.iter() {
+            // Parallel edges give no new separating axis.
+            if let Some(axis) = a.cross(b).try_normalize(1.0e-6) {
+                axes.push(axis);
+            }
+        }
+    }
+
+    let offset = center_b - center_a;
+    let mut penetration = f32::MAX;
+    for axis in axes {
+        let projected_a: f32 = (0..3).map(|i| axes_a[i].dot(&axis).abs() * half_a[i]).sum();
+        let projected_b: f32 = (0..3).map(|i| axes_b[i].dot(&axis).abs() * half_b[i]).sum();
+        let overlap = projected_a + projected_b - offset.dot(&axis).abs();
+        if overlap <= 0.0 {
+            return overlap;
+        }
+        penetration = penetration.min(overlap);
+    }
+    penetration
+}
+
+/// Returns penetration depth of two shapes, or `None` if they do not overlap.
+pub fn penetration_depth(a: &OverlapShape, b: &OverlapShape) -> Option<f32> {
+    use OverlapShape::*;
+
+    let depth = match (a, b) {
+        (
+            Ball {
+                center: ca,
+                radius: ra,
+            },
+            Ball {
+                center: cb,
+                radius: rb,
+            },
+        ) => ra + rb - (ca - cb).norm(),
+        (
+            Ball { center, radius },
+            Capsule {
+                begin,
+                end,
+                radius: capsule_radius,
+            },
+        )
+        | (
+            Capsule {
+                begin,
+                end,
+                radius: capsule_radius,
+            },
+            Ball { center, radius },
+        ) => {
+            radius + capsule_radius
+                - (center - closest_point_on_segment(*center, *begin, *end)).norm()
+        }
+        (
+            Ball { center, radius },
+            Cuboid {
+                center: box_center,
+                rotation,
+                half_extents,
+            },
+        )
+        | (
+            Cuboid {
+                center: box_center,
+                rotation,
+                half_extents,
+            },
+            Ball { center, radius },
+        ) => radius - cuboid_signed_distance(*box_center, rotation, *half_extents, *center),
+        (
+            Capsule {
+                begin: ba,
+                end: ea,
+                radius: ra,
+            },
+            Capsule {
+                begin: bb,
+                end: eb,
+                radius: rb,
+            },
+        ) => ra + rb - segment_segment_distance(*ba, *ea, *bb, *eb),
+        (
+            Capsule { begin, end, radius },
+            Cuboid {
+                center,
+                rotation,
+                half_extents,
+            },
+        )
+        | (
+            Cuboid {
+                center,
+                rotation,
+                half_extents,
+            },
+            Capsule { begin, end, radius },
+        ) => radius - segment_cuboid_distance(*begin, *end, *center, rotation, *half_extents),
+        (
+            Cuboid {
+                center: ca,
+                rotation: qa,
+                half_extents: ha,
+            },
+            Cuboid {
+                center: cb,
+                rotation: qb,
+                half_extents: hb,
+            },
+        ) => cuboid_cuboid_penetration(*ca, qa, *ha, *cb, qb, *hb),
+    };
+
+    if depth > 0.0 {
+        Some(depth)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn ball(x: f32, radius: f32) -> OverlapShape {
+        OverlapShape::Ball {
+            center: Vector3::new(x, 0.0, 0.0),
+            radius,
+        }
+    }
+
+    fn capsule(x: f32, radius: f32) -> OverlapShape {
+        OverlapShape::Capsule {
+            begin: Vector3::new(x, -1.0, 0.0),
+            end: Vector3::new(x, 1.0, 0.0),
+            radius,
+        }
+    }
+
+    fn cuboid(x: f32, half_extent: f32) -> OverlapShape {
+        OverlapShape::Cuboid {
+            center: Vector3::new(x, 0.0, 0.0),
+            rotation: UnitQuaternion::identity(),
+            half_extents: Vector3::repeat(half_extent),
+        }
+    }
+
+    fn assert_depth(a: OverlapShape, b: OverlapShape, expected: Option<f32>) {
+        for (a, b) in [(&a, &b), (&b, &a)] {
+            match (penetration_depth(a, b), expected) {
+                (Some(depth), Some(expected)) => assert!(
+                    (depth - expected).abs() < 1.0e-3,
+                    "{:?} vs {:?}: {} != {}",
+                    a,
+                    b,
+                    depth,
+                    expected
+                ),
+                (depth, expected) => assert_eq!(depth, expected, "{:?} vs {:?}", a, b),
+            }
+        }
+    }
+
+    #[test]
+    fn test_ball_ball() {
+        assert_depth(ball(0.0, 1.0), ball(1.5, 1.0), Some(0.5));
+        assert_depth(ball(0.0, 1.0), ball(2.5, 1.0), None);
+    }
+
+    #[test]
+    fn test_ball_capsule() {
+        assert_depth(ball(0.0, 1.0), capsule(1.25, 0.5), Some(0.25));
+        assert_depth(ball(0.0, 1.0), capsule(2.0, 0.5), None);
+    }
+
+    #[test]
+    fn test_ball_cuboid() {
+        assert_depth(ball(0.0, 1.0), cuboid(1.25, 0.5), Some(0.25));
+        assert_depth(ball(0.0, 1.0), cuboid(2.0, 0.5), None);
+        // Center of the ball inside of the box.
+        assert_depth(ball(0.0, 0.1), cuboid(0.0, 0.5), Some(0.6));
+    }
+
+    #[test]
+    fn test_capsule_capsule() {
+        assert_depth(capsule(0.0, 0.5), capsule(0.75, 0.5), Some(0.25));
+        assert_depth(capsule(0.0, 0.5), capsule(1.5, 0.5), None);
+
+        // Crossing capsules.
+        let crossing = OverlapShape::Capsule {
+            begin: Vector3::new(-1.0, 0.0, 0.5),
+            end: Vector3::new(1.0, 0.0, 0.5),
+            radius: 0.5,
+        };
+        assert_depth(capsule(0.0, 0.5), crossing, Some(0.5));
+    }
+
+    #[test]
+    fn test_capsule_cuboid() {
+        assert_depth(capsule(0.0, 0.5), cuboid(0.75, 0.5), Some(0.25));
+        assert_depth(capsule(0.0, 0.5), cuboid(1.5, 0.5), None);
+    }
+
+    #[test]
+    fn test_cuboid_cuboid() {
+        assert_depth(cuboid(0.0, 0.5), cuboid(0.75, 0.5), Some(0.25));
+        assert_depth(cuboid(0.0, 0.5), cuboid(1.5, 0.5), None);
+
+        // Rotated box touches the other one only with its edge.
+        let rotated = OverlapShape::Cuboid {
+            center: Vector3::new(1.0, 0.0, 0.0),
+            rotation: UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 45.0f32.to_radians()),
+            half_extents: Vector3::repeat(0.5),
+        };
+        assert_depth(cuboid(0.0, 0.5), rotated, Some(0.5 + 0.5f32.sqrt() - 1.0));
+    }
+}
@@ -0,0 +1,489 @@
+//! Matching of ragdoll bones with nodes of a character. Bones are found by name patterns (from the
+//! editor settings) or by paths of the nodes under the character root.
+
+use crate::settings::ragdoll::{RagdollBone, RagdollBonePatterns};
+use fyrox::{
+    core::pool::Handle,
+    scene::{graph::Graph, node::Node},
+};
+use std::collections::VecDeque;
+
+/// A node of a character hierarchy, that could be assigned to a bone of a ragdoll.
+#[derive(Clone, Debug)]
+pub struct BoneCandidate {
+    pub handle: Handle<Node>,
+    /// Depth of the node under the character root.
+    pub depth: usize,
+    /// Lowercase name of the node.
+    pub name: String,
+    /// Names of the node and its ancestors up to the character root (excluding the root),
+    /// separated by `/`. Paths of the same bones are equal in every instance of a character.
+    pub path: String,
+}
+
+/// Returns every node of the hierarchy of `root` (including the root), closer nodes go first.
+pub fn collect_bone_candidates(graph: &Graph, root: Handle<Node>) -> Vec<BoneCandidate> {
+    let mut candidates = Vec::new();
+    let mut queue = VecDeque::new();
+    if graph.is_valid_handle(root) {
+        queue.push_back((root, 0, String::new()));
+    }
+    while let Some((handle, depth, path)) = queue.pop_front() {
+        let node = &graph[handle];
+        queue.extend(node.children().iter().map(|&child| {
+            let name = graph[child].name();
+            let child_path = if path.is_empty() {
+                name.to_owned()
+            } else {
+                format!("{}/{}", path, name)
+            };
+            (child, depth + 1, child_path)
+        }));
+        candidates.push(BoneCandidate {
+            handle,
+            depth,
+            name: node.name().to_lowercase(),
+            path,
+        });
+    }
+    candidates
+}
+
+/// Splits comma-separated name patterns. Patterns are lowercase, because names are compared
+/// case-insensitively.
+pub fn parse_bone_patterns(patterns: &str) -> Vec<String> {
+    patterns
+        .split(',')
+        .map(|pattern| pattern.trim().to_lowercase())
+        .filter(|pattern| !pattern.is_empty())
+        .collect()
+}
+
+/// Returns the candidate, whose name contains one of the patterns. The shallowest candidate wins,
+/// so a bone is not confused with its children (like `Hand` and `HandIndex1`). Candidates of the
+/// same depth are ordered by their patterns and then by their order in the hierarchy.
+pub fn find_by_pattern(candidates: &[BoneCandidate], patterns: &[String]) -> Handle<Node> {
+    candidates
+        .iter()
+        .filter_map(|candidate| {
+            patterns
+                .iter()
+                .position(|pattern| candidate.name.contains(pattern.as_str()))
+                .map(|pattern| (candidate.depth, pattern, candidate.handle))
+        })
+        // The first one of the equal matches is taken, it is the earliest in the hierarchy.
+        .min_by_key(|(depth, pattern, _)| (*depth, *pattern))
+        .map(|(_, _, handle)| handle)
+        .unwrap_or_default()
+}
+
+/// How well the name of an assigned node matches the patterns of its bone.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BoneMatch {
+    /// The name is one of the patterns, a namespace prefix (like `mixamorig:`) is ignored.
+    Exact,
+    /// The name contains one of the patterns, it could be a wrong match (like `Headlight`).
+    Substring,
+    /// The name does not match the patterns, the bone was assigned manually.
+    Manual,
+    NotFound,
+}
+
+impl BoneMatch {
+    pub fn description(self) -> &'static str {
+        match self {
+            BoneMatch::Exact => "exact name match",
+            BoneMatch::Substring => "substring match",
+            BoneMatch::Manual => "assigned manually",
+            BoneMatch::NotFound => "not found",
+        }
+    }
+}
+
+/// Returns how well the name of a node matches the patterns (see [`parse_bone_patterns`]).
+pub fn bone_match(name: &str, patterns: &[String]) -> BoneMatch {
+    let name = name.to_lowercase();
+    let short_name = name
+        .rsplit(|c| c == ':' || c == '|')
+        .next()
+        .unwrap_or_default();
+    if patterns
+        .iter()
+        .any(|pattern| *pattern == name || pattern == short_name)
+    {
+        BoneMatch::Exact
+    } else if patterns
+        .iter()
+        .any(|pattern| name.contains(pattern.as_str()))
+    {
+        BoneMatch::Substring
+    } else {
+        BoneMatch::Manual
+    }
+}
+
+/// Parses comma-separated pairs of left/right name tokens, like `Left/Right, _l/_r`. Malformed
+/// pairs are skipped.
+pub fn parse_mirror_tokens(tokens: &str) -> Vec<(String, String)> {
+    tokens
+        .split(',')
+        .filter_map(|pair| {
+            let mut sides = pair.split('/').map(str::trim);
+            match (sides.next(), sides.next(), sides.next()) {
+                (Some(left), Some(right), None) if !left.is_empty() && !right.is_empty() => {
+                    Some((left.to_owned(), right.to_owned()))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Replaces the tokens of one side with the tokens of the other side in every name of the path.
+/// The first pair of tokens, that is found in a name, is used for it. Returns `None` if nothing
+/// was replaced, because such path can't be mirrored.
+pub fn mirror_path(path: &str, tokens: &[(String, String)], left_to_right: bool) -> Option<String> {
+    let mut mirrored_any = false;
+    let mirrored = path
+        .split('/')
+        .map(|name| {
+            for (left, right) in tokens {
+                let (from, to) = if left_to_right {
+                    (left, right)
+                } else {
+                    (right, left)
+                };
+                if name.contains(from.as_str()) {
+                    mirrored_any = true;
+                    return name.replace(from.as_str(), to);
+                }
+            }
+            name.to_owned()
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    if mirrored_any {
+        Some(mirrored)
+    } else {
+        None
+    }
+}
+
+/// Returns true if the path is the ancestor path or a path of one of its descendants.
+fn is_under_path(path: &str, ancestor: &str) -> bool {
+    ancestor.is_empty()
+        || path == ancestor
+        || (path.starts_with(ancestor) && path[ancestor.len()..].starts_with('/'))
+}
+
+/// Finds every bone by its patterns. The hips are found first, then the other bones are searched
+/// under the hips, so a node of another model (or a prop like `Bedhead`) is not taken by mistake.
+/// If a bone is not found under the hips, it is searched everywhere.
+pub fn find_bones(
+    candidates: &[BoneCandidate],
+    patterns: &RagdollBonePatterns,
+) -> Vec<(RagdollBone, Handle<Node>)> {
+    let find = |candidates: &[BoneCandidate], bone: RagdollBone| {
+        find_by_pattern(candidates, &parse_bone_patterns(patterns.patterns(bone)))
+    };
+
+    let hips = find(candidates, RagdollBone::Hips);
+    let under_hips = candidates
+        .iter()
+        .find(|candidate| hips.is_some() && candidate.handle == hips)
+        .map(|hips| {
+            candidates
+                .iter()
+                .filter(|candidate| {
+                    candidate.handle != hips.handle && is_under_path(&candidate.path, &hips.path)
+                })
+                .cloned()
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    RagdollBone::ALL
+        .iter()
+        .map(|&bone| {
+            let handle = if bone == RagdollBone::Hips {
+                hips
+            } else {
+                let handle = find(&under_hips, bone);
+                if handle.is_some() {
+                    handle
+                } else {
+                    find(candidates, bone)
+                }
+            };
+            (bone, handle)
+        })
+        .collect()
+}
+
+/// Returns the candidate with the given path. If there's no such candidate (the hierarchy of the
+/// character is a bit different, for example), the shallowest candidate with the same name is
+/// taken.
+pub fn resolve_bone_path(candidates: &[BoneCandidate], path: &str) -> Handle<Node> {
+    if path.is_empty() {
+        return Handle::NONE;
+    }
+
+    candidates
+        .iter()
+        .find(|candidate| candidate.path == path)
+        .or_else(|| {
+            let name = path.rsplit('/').next().unwrap_or(path).to_lowercase();
+            candidates
+                .iter()
+                .filter(|candidate| candidate.depth > 0)
+                .find(|candidate| candidate.name == name)
+        })
+        .map(|candidate| candidate.handle)
+        .unwrap_or_default()
+}
+
+pub fn is_descendant_of(graph: &Graph, node: Handle<Node>, ancestor: Handle<Node>) -> bool {
+    let mut parent = graph[node].parent();
+    while parent.is_some() {
+        if parent == ancestor {
+            return true;
+        }
+        parent = graph[parent].parent();
+    }
+    false
+}
+
+/// Returns the distinct assigned spine bones from the bottom to the top. A node, that is assigned
+/// to several spine bones, is kept only for the lowest one of them.
+pub fn spine_chain(spine: &[(RagdollBone, Handle<Node>)]) -> Vec<(RagdollBone, Handle<Node>)> {
+    let mut chain: Vec<(RagdollBone, Handle<Node>)> = Vec::new();
+    for &(bone, handle) in spine {
+        if handle.is_some() && !chain.iter().any(|(_, h)| *h == handle) {
+            chain.push((bone, handle));
+        }
+    }
+    chain
+}
+
+/// Returns the closest node, that is an ancestor (or the node itself) of every given node.
+pub fn common_ancestor(graph: &Graph, nodes: &[Handle<Node>]) -> Handle<Node> {
+    let mut ancestor = match nodes.first() {
+        Some(first) => *first,
+        None => return Handle::NONE,
+    };
+    while ancestor.is_some() {
+        if nodes
+            .iter()
+            .all(|&node| node == ancestor || is_descendant_of(graph, node, ancestor))
+        {
+            break;
+        }
+        ancestor = graph[ancestor].parent();
+    }
+    ancestor
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::ragdoll::test::{candidate, candidate_at};
+
+    #[test]
+    fn test_parse_bone_patterns() {
+        assert_eq!(
+            parse_bone_patterns(" UpperArm_R, upperarm.r ,,"),
+            vec!["upperarm_r".to_owned(), "upperarm.r".to_owned()]
+        );
+        assert!(parse_bone_patterns("").is_empty());
+    }
+
+    #[test]
+    fn test_find_by_pattern() {
+        let candidates = [
+            candidate(1, 0, "Character"),
+            candidate(2, 1, "mixamorig:Hips"),
+            candidate(3, 2, "mixamorig:Spine"),
+            candidate(4, 3, "mixamorig:Spine1"),
+            candidate(5, 3, "UpperArm_R"),
+            candidate(6, 4, "UpperArm_R_Twist"),
+            candidate(7, 3, "upper_arm.r"),
+        ];
+        let find = |patterns: &str| find_by_pattern(&candidates, &parse_bone_patterns(patterns));
+
+        // Case is ignored.
+        assert_eq!(find("hips"), Handle::new(2, 1));
+        // The shallowest match wins, children are not confused with their parents.
+        assert_eq!(find("Spine"), Handle::new(3, 1));
+        assert_eq!(find("Spine1"), Handle::new(4, 1));
+        assert_eq!(find("upperarm_r"), Handle::new(5, 1));
+        // On the same depth the first pattern wins.
+        assert_eq!(find("upper_arm.r, upperarm_r"), Handle::new(7, 1));
+        assert_eq!(find("pelvis"), Handle::NONE);
+        assert_eq!(find(""), Handle::NONE);
+    }
+
+    #[test]
+    fn test_bone_pattern_presets() {
+        for patterns in [RagdollBonePatterns::mixamo(), RagdollBonePatterns::unreal()] {
+            for bone in RagdollBone::ALL {
+                assert!(!parse_bone_patterns(patterns.patterns(bone)).is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn test_resolve_bone_path() {
+        let candidates = [
+            candidate_at(1, ""),
+            candidate_at(2, "Armature/Hips"),
+            candidate_at(3, "Armature/Hips/Spine"),
+            candidate_at(4, "Armature/Hips/Spine/Spine1"),
+            candidate_at(5, "Armature/Hips/Spine/Spine1/Head"),
+        ];
+
+        assert_eq!(
+            resolve_bone_path(&candidates, "Armature/Hips/Spine"),
+            Handle::new(3, 1)
+        );
+        // The hierarchy is different, the bone is found by its name.
+        assert_eq!(
+            resolve_bone_path(&candidates, "Root/Hips/Spine/head"),
+            Handle::new(5, 1)
+        );
+        assert_eq!(resolve_bone_path(&candidates, "Hips/Tail"), Handle::NONE);
+        // The character root is never taken as a bone.
+        assert_eq!(resolve_bone_path(&candidates, ""), Handle::NONE);
+    }
+
+    #[test]
+    fn test_spine_chain() {
+        use RagdollBone::{Spine, Spine1, Spine2};
+
+        let a = Handle::<Node>::new(1, 1);
+        let b = Handle::<Node>::new(2, 1);
+        let c = Handle::<Node>::new(3, 1);
+        let none = Handle::NONE;
+
+        // Three distinct bones.
+        assert_eq!(
+            spine_chain(&[(Spine, a), (Spine1, b), (Spine2, c)]),
+            vec![(Spine, a), (Spine1, b), (Spine2, c)]
+        );
+        // Two bones, Autofill may resolve two slots to the same node.
+        assert_eq!(
+            spine_chain(&[(Spine, a), (Spine1, b), (Spine2, b)]),
+            vec![(Spine, a), (Spine1, b)]
+        );
+        assert_eq!(
+            spine_chain(&[(Spine, a), (Spine1, none), (Spine2, c)]),
+            vec![(Spine, a), (Spine2, c)]
+        );
+        // One bone.
+        assert_eq!(
+            spine_chain(&[(Spine, a), (Spine1, a), (Spine2, a)]),
+            vec![(Spine, a)]
+        );
+        assert_eq!(
+            spine_chain(&[(Spine, none), (Spine1, none), (Spine2, c)]),
+            vec![(Spine2, c)]
+        );
+        assert!(spine_chain(&[(Spine, none), (Spine1, none), (Spine2, none)]).is_empty());
+    }
+
+    #[test]
+    fn test_find_bones_prefers_hips_descendants() {
+        // Two characters and a prop in one scene, the bones are searched from the scene root.
+        let candidates = [
+            candidate_at(1, ""),
+            candidate_at(2, "Bedhead_Prop"),
+            candidate_at(3, "Bob"),
+            candidate_at(4, "Bob/Hips"),
+            candidate_at(5, "Bob/Hips/Spine"),
+            candidate_at(6, "Alice"),
+            candidate_at(7, "Alice/Hips"),
+            candidate_at(8, "Alice/Hips/Spine"),
+            candidate_at(9, "Alice/Hips/Spine/Neck/Head"),
+            candidate_at(10, "Alice/Hips/LeftFoot"),
+            candidate_at(11, "Bob/LeftHand"),
+        ];
+        let bones = find_bones(&candidates, &RagdollBonePatterns::mixamo());
+        let bone = |bone: RagdollBone| bones.iter().find(|(b, _)| *b == bone).unwrap().1;
+
+        assert_eq!(bones.len(), RagdollBone::ALL.len());
+        assert_eq!(bone(RagdollBone::Hips), Handle::new(4, 1));
+        assert_eq!(bone(RagdollBone::Spine), Handle::new(5, 1));
+        // Not under the hips, the shallowest match is taken instead.
+        assert_eq!(bone(RagdollBone::Head), Handle::new(2, 1));
+        assert_eq!(bone(RagdollBone::LeftFoot), Handle::new(10, 1));
+        assert_eq!(bone(RagdollBone::LeftHand), Handle::new(11, 1));
+        assert_eq!(bone(RagdollBone::Neck), Handle::NONE);
+
+        // The character root is selected, the other character is not visible.
+        let alice = [
+            candidate_at(6, ""),
+            candidate_at(7, "Hips"),
+            candidate_at(8, "Hips/Spine"),
+            candidate_at(9, "Hips/Spine/Neck/Head"),
+        ];
+        let bones = find_bones(&alice, &RagdollBonePatterns::mixamo());
+        assert!(bones.contains(&(RagdollBone::Head, Handle::new(9, 1))));
+        assert!(bones.contains(&(RagdollBone::Spine, Handle::new(8, 1))));
+    }
+
+    #[test]
+    fn test_is_under_path() {
+        assert!(is_under_path("Hips/Spine", "Hips"));
+        assert!(is_under_path("Hips", "Hips"));
+        assert!(is_under_path("Hips", ""));
+        assert!(!is_under_path("HipsTwist/Spine", "Hips"));
+        assert!(!is_under_path("Spine", "Hips"));
+    }
+
+    #[test]
+    fn test_bone_match() {
+        let patterns = parse_bone_patterns("head, neck");
+        assert_eq!(bone_match("Head", &patterns), BoneMatch::Exact);
+        assert_eq!(bone_match("mixamorig:Head", &patterns), BoneMatch::Exact);
+        assert_eq!(bone_match("Headlight", &patterns), BoneMatch::Substring);
+        assert_eq!(bone_match("HeadTop_End", &patterns), BoneMatch::Substring);
+        assert_eq!(bone_match("Skull", &patterns), BoneMatch::Manual);
+        assert_eq!(bone_match("Skull", &[]), BoneMatch::Manual);
+    }
+
+    #[test]
+    fn test_parse_mirror_tokens() {
+        assert_eq!(
+            parse_mirror_tokens("Left/Right, _l / _r,bad,/x,a/b/c"),
+            vec![
+                ("Left".to_owned(), "Right".to_owned()),
+                ("_l".to_owned(), "_r".to_owned())
+            ]
+        );
+        assert!(parse_mirror_tokens("").is_empty());
+    }
+
+    #[test]
+    fn test_mirror_path() {
+        let tokens = parse_mirror_tokens("Left/Right, _l/_r, .L/.R");
+
+        assert_eq!(
+            mirror_path(
+                "Hips/Spine/mixamorig:LeftShoulder/mixamorig:LeftArm",
+                &tokens,
+                true
+            ),
+            Some("Hips/Spine/mixamorig:RightShoulder/mixamorig:RightArm".to_owned())
+        );
+        assert_eq!(
+            mirror_path("pelvis/thigh_r/calf_r", &tokens, false),
+            Some("pelvis/thigh_l/calf_l".to_owned())
+        );
+        assert_eq!(
+            mirror_path("Hips/upper_arm.L", &tokens, true),
+            Some("Hips/upper_arm.R".to_owned())
+        );
+        // Nothing to replace, the path can't be mirrored.
+        assert_eq!(mirror_path("Hips/Spine", &tokens, true), None);
+    }
+}
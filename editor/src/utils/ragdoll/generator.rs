@@ -0,0 +1,2377 @@
+//! Generation of ragdolls. The bones of a preset are turned into shapes, then rigid bodies with
+//! colliders, joints between them and a ragdoll node with limbs are created. Hitboxes and prefabs
+//! are made from the same shapes.
+
+use crate::{
+    message::MessageSender,
+    scene::{
+        commands::{
+            graph::{AddModelCommand, DeleteSubGraphCommand},
+            ChangeSelectionCommand, CommandGroup, SceneCommand,
+        },
+        EditorScene, Selection,
+    },
+    settings::ragdoll::RagdollBone,
+    utils::ragdoll::{
+        bone_list,
+        bones::spine_chain,
+        preset::{
+            joint_limits, BallJointLimits, RagdollBodyType, RagdollHingeAxis, RagdollOutputMode,
+            RagdollPreset,
+        },
+        validation::{find_ragdoll_overlaps, RagdollOverlap},
+    },
+    world::graph::selection::GraphSelection,
+};
+use fyrox::{
+    core::{
+        algebra::{Matrix3, Matrix4, Point3, Rotation3, UnitQuaternion, Vector3},
+        color::Color,
+        log::Log,
+        math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
+        pool::Handle,
+    },
+    scene::{
+        base::{BaseBuilder, Property, PropertyValue},
+        collider::{BitMask, ColliderBuilder, ColliderShape},
+        debug::SceneDrawingContext,
+        graph::Graph,
+        joint::{BallJoint, JointBuilder, JointParams, RevoluteJoint},
+        node::Node,
+        ragdoll::{Limb, Ragdoll, RagdollBuilder},
+        rigidbody::{RigidBody, RigidBodyBuilder, RigidBodyType},
+        transform::TransformBuilder,
+        Scene,
+    },
+};
+use std::ops::Range;
+
+/// Connects the bodies with a ball joint at the given position (see
+/// [`RagdollJointAnchor`](crate::utils::ragdoll::preset::RagdollJointAnchor)). The
+/// joint is oriented as the first body.
+fn try_make_ball_joint(
+    body1: Handle<Node>,
+    body2: Handle<Node>,
+    position: Vector3<f32>,
+    name: &str,
+    limits: BallJointLimits,
+    ragdoll: Handle<Node>,
+    graph: &mut Graph,
+) -> Handle<Node> {
+    if body1.is_some() && body2.is_some() {
+        let mut joint = BallJoint::default();
+
+        if let Some(x) = limits.x {
+            joint.x_limits_enabled = true;
+            joint.x_limits_angles = x;
+        }
+        if let Some(y) = limits.y {
+            joint.y_limits_enabled = true;
+            joint.y_limits_angles = y;
+        }
+        if let Some(z) = limits.z {
+            joint.z_limits_enabled = true;
+            joint.z_limits_angles = z;
+        }
+
+        let ball_joint = JointBuilder::new(
+            BaseBuilder::new().with_name(name).with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .with_local_rotation(UnitQuaternion::from_matrix_eps(
+                        &graph[body1].global_transform().basis(),
+                        f32::EPSILON,
+                        16,
+                        Default::default(),
+                    ))
+                    .build(),
+            ),
+        )
+        .with_params(JointParams::BallJoint(joint))
+        .with_body1(body1)
+        .with_body2(body2)
+        .with_auto_rebinding_enabled(false)
+        .with_contacts_enabled(false)
+        .build(graph);
+
+        graph.link_nodes(ball_joint, ragdoll);
+
+        ball_joint
+    } else {
+        Default::default()
+    }
+}
+
+/// Connects the bodies with a hinge joint at the given position (see [`try_make_ball_joint`]).
+fn try_make_hinge_joint(
+    body1: Handle<Node>,
+    body2: Handle<Node>,
+    position: Vector3<f32>,
+    name: &str,
+    limits: Option<Range<f32>>,
+    axis: RagdollHingeAxis,
+    ragdoll: Handle<Node>,
+    graph: &mut Graph,
+) -> Handle<Node> {
+    if body1.is_some() && body2.is_some() {
+        let mut joint = RevoluteJoint::default();
+
+        if let Some(limits) = limits {
+            joint.limits_enabled = true;
+            joint.limits = limits;
+        }
+
+        let hinge_joint = JointBuilder::new(
+            BaseBuilder::new().with_name(name).with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .with_local_rotation(
+                        UnitQuaternion::from_matrix_eps(
+                            &graph[body1].global_transform().basis(),
+                            f32::EPSILON,
+                            16,
+                            Default::default(),
+                        ) * axis.joint_rotation(),
+                    )
+                    .build(),
+            ),
+        )
+        .with_params(JointParams::RevoluteJoint(joint))
+        .with_body1(body1)
+        .with_body2(body2)
+        .with_auto_rebinding_enabled(false)
+        .with_contacts_enabled(false)
+        .build(graph);
+
+        graph.link_nodes(hinge_joint, ragdoll);
+
+        hinge_joint
+    } else {
+        Default::default()
+    }
+}
+
+/// Makes the commands, that delete the replaced nodes, add the generated ones and select them.
+/// The generated nodes are taken from the graph, the commands put them back.
+pub fn make_generation_commands(
+    graph: &mut Graph,
+    generated: &[Handle<Node>],
+    replace: &[Handle<Node>],
+    selection: &Selection,
+) -> Vec<SceneCommand> {
+    let mut group = replace
+        .iter()
+        .map(|&node| SceneCommand::new(DeleteSubGraphCommand::new(node)))
+        .collect::<Vec<_>>();
+    for &root in generated.iter() {
+        // Immediately after extract if from the scene to subgraph. This is required to not
+        // violate the rule of one place of execution, only commands allowed to modify the
+        // scene. Sub-graphs remember their parents, so redo puts them back under them.
+        let sub_graph = graph.take_reserve_sub_graph(root);
+        group.push(SceneCommand::new(AddModelCommand::new(sub_graph)));
+    }
+    // We also want to select newly instantiated nodes. The whole previous selection is kept, the
+    // commands are reverted in reverse order, so undo restores it before the generated nodes are
+    // taken away.
+    group.push(SceneCommand::new(ChangeSelectionCommand::new(
+        Selection::Graph(GraphSelection::from_list(generated.to_vec())),
+        selection.clone(),
+    )));
+    group
+}
+
+/// Returns the ragdolls under the given root, that are made for the character with the given hips.
+pub fn find_ragdolls(graph: &Graph, root: Handle<Node>, hips: Handle<Node>) -> Vec<Handle<Node>> {
+    if hips.is_none() {
+        return Vec::new();
+    }
+
+    graph
+        .traverse_handle_iter(root)
+        .filter(|&handle| {
+            matches!(
+                graph[handle].cast::<Ragdoll>(),
+                Some(ragdoll) if ragdoll.hips().bone == hips
+            )
+        })
+        .collect()
+}
+
+/// Prefix of the names of the properties of a ragdoll, that bind its limbs to the bones by names.
+/// The name of such property is the prefix followed by the name of the body of the limb, the value
+/// is the name of the bone. Prefabs do not contain the bones, so the limbs are bound to the bones
+/// of a character when a prefab is instantiated.
+pub const LIMB_BINDING_PREFIX: &str = "RagdollBone:";
+
+fn collect_limb_bindings(graph: &Graph, limb: &Limb, bindings: &mut Vec<Property>) {
+    if let (Some(body), Some(bone)) = (graph.try_get(limb.physical_bone), graph.try_get(limb.bone))
+    {
+        bindings.push(Property {
+            name: format!("{}{}", LIMB_BINDING_PREFIX, body.name()),
+            value: PropertyValue::String(bone.name_owned()),
+        });
+    }
+    for child in limb.children.iter() {
+        collect_limb_bindings(graph, child, bindings);
+    }
+}
+
+/// Returns the table, that binds the limbs of the ragdoll to the names of their bones.
+pub fn limb_bindings(graph: &Graph, ragdoll: Handle<Node>) -> Vec<Property> {
+    let mut bindings = Vec::new();
+    if let Some(ragdoll) = graph.try_get_of_type::<Ragdoll>(ragdoll) {
+        collect_limb_bindings(graph, ragdoll.hips(), &mut bindings);
+    }
+    bindings
+}
+
+fn rebind_limb(
+    graph: &Graph,
+    limb: &mut Limb,
+    bindings: &[Property],
+    character_root: Handle<Node>,
+    unresolved: &mut Vec<String>,
+) {
+    let body_name = graph
+        .try_get(limb.physical_bone)
+        .map(|body| body.name_owned())
+        .unwrap_or_default();
+    let binding_name = format!("{}{}", LIMB_BINDING_PREFIX, body_name);
+    let bone_name = bindings
+        .iter()
+        .find(|binding| binding.name == binding_name)
+        .and_then(|binding| match binding.value {
+            PropertyValue::String(ref bone_name) => Some(bone_name.as_str()),
+            _ => None,
+        });
+    limb.bone = match bone_name.and_then(|name| graph.find_by_name(character_root, name)) {
+        Some((bone, _)) => bone,
+        None => {
+            unresolved.push(format!(
+                "{} ({})",
+                body_name,
+                bone_name.unwrap_or("no binding")
+            ));
+            Handle::NONE
+        }
+    };
+    for child in limb.children.iter_mut() {
+        rebind_limb(graph, child, bindings, character_root, unresolved);
+    }
+}
+
+/// Binds the limbs of the ragdoll to the bones under the character root by the binding table of
+/// the ragdoll (see [`LIMB_BINDING_PREFIX`]). Returns the descriptions of the limbs, that have no
+/// bones, they are left unbound.
+pub fn rebind_limbs(
+    graph: &mut Graph,
+    ragdoll: Handle<Node>,
+    character_root: Handle<Node>,
+) -> Vec<String> {
+    let mut unresolved = Vec::new();
+    let (mut hips, bindings) = match graph.try_get_of_type::<Ragdoll>(ragdoll) {
+        Some(ragdoll) => (ragdoll.hips().clone(), ragdoll.properties.to_vec()),
+        None => return unresolved,
+    };
+    rebind_limb(graph, &mut hips, &bindings, character_root, &mut unresolved);
+    graph[ragdoll].as_ragdoll_mut().set_hips(hips);
+    unresolved
+}
+
+/// Returns the name of a generated node, that is made for the bone. Every kind of nodes has its own
+/// prefix, so the names are unique within one ragdoll.
+pub fn ragdoll_part_name(prefix: &str, bone_name: &str) -> String {
+    format!("{}{}", prefix, bone_name)
+}
+
+/// Collider shape of a generated body, in local coordinates of the body.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RagdollShapeKind {
+    Sphere {
+        radius: f32,
+    },
+    /// Capsule along local Y axis of the body, its cap touches the origin of the body. `length` is
+    /// the length of the segment between the centers of the caps.
+    Capsule {
+        length: f32,
+        radius: f32,
+    },
+    Cuboid {
+        half_extents: Vector3<f32>,
+    },
+}
+
+/// Returns the rotation, that turns local Y axis of a capsule in the direction from one bone to
+/// another.
+pub fn capsule_rotation(from: Vector3<f32>, to: Vector3<f32>) -> UnitQuaternion<f32> {
+    let direction = to - from;
+    UnitQuaternion::rotation_between(&Vector3::y(), &direction).unwrap_or_else(|| {
+        // The direction is either zero or opposite to Y axis.
+        if direction.y < 0.0 {
+            UnitQuaternion::from_axis_angle(&Vector3::x_axis(), std::f32::consts::PI)
+        } else {
+            UnitQuaternion::identity()
+        }
+    })
+}
+
+/// Head is about a third of the distance between the hips and the head, it is used as the base
+/// size when the arms are not assigned.
+const BASE_SIZE_PER_HIPS_TO_HEAD: f32 = 0.35;
+
+/// Returns the scale along every axis of the transform.
+pub fn character_scale(transform: &Matrix4<f32>) -> Vector3<f32> {
+    let basis = transform.basis();
+    Vector3::new(
+        basis.column(0).norm(),
+        basis.column(1).norm(),
+        basis.column(2).norm(),
+    )
+}
+
+/// Returns the rotation of a torso body, that turns its local Y axis along the spine and its local
+/// X axis from the right side of the body to the left one. The basis of the bones can't be used,
+/// because bones may have any orientation in bind pose. `None` if the directions are zero or
+/// parallel.
+pub fn torso_rotation(up: Vector3<f32>, side: Vector3<f32>) -> Option<UnitQuaternion<f32>> {
+    let y = up.try_normalize(f32::EPSILON)?;
+    let x = (side - y.scale(side.dot(&y))).try_normalize(f32::EPSILON)?;
+    let z = x.cross(&y);
+    Some(UnitQuaternion::from_rotation_matrix(
+        &Rotation3::from_matrix_unchecked(Matrix3::from_columns(&[x, y, z])),
+    ))
+}
+
+/// Segment of an extra bone chain, that a body is generated for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RagdollChainSegment {
+    /// Bone at the start of the segment, the body follows it.
+    pub bone: Handle<Node>,
+    /// Name of the chain followed by the index of the segment.
+    pub name: String,
+    /// Index of the segment in its chain, the first segment is attached to the limb of the shape.
+    pub index: usize,
+    /// Limits of the joint with the parent body in degrees.
+    pub joint_limits: Range<f32>,
+}
+
+/// Body of a ragdoll, that is generated for a bone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RagdollShape {
+    /// Bone of the body. Segments of extra chains have the limb, that their chain is attached to,
+    /// its overrides of the physical properties are used for them.
+    pub bone: RagdollBone,
+    pub chain: Option<RagdollChainSegment>,
+    pub position: Vector3<f32>,
+    pub rotation: UnitQuaternion<f32>,
+    pub kind: RagdollShapeKind,
+    /// Share of the total mass of the ragdoll.
+    pub mass: f32,
+}
+
+impl RagdollShapeKind {
+    pub fn volume(&self) -> f32 {
+        let ball_volume = |radius: f32| 4.0 / 3.0 * std::f32::consts::PI * radius.powi(3);
+        match *self {
+            RagdollShapeKind::Sphere { radius } => ball_volume(radius),
+            RagdollShapeKind::Capsule { length, radius } => {
+                std::f32::consts::PI * radius * radius * length.max(0.0) + ball_volume(radius)
+            }
+            RagdollShapeKind::Cuboid { half_extents } => {
+                8.0 * half_extents.x * half_extents.y * half_extents.z
+            }
+        }
+    }
+}
+
+/// Splits the total mass proportionally to the volumes. Every share is at least `min_mass`, the
+/// rest of the mass is split between the other volumes, so the shares always sum up to the total
+/// mass. If the total mass is not enough to give everyone the minimum, it is split evenly.
+pub fn distribute_mass(volumes: &[f32], total_mass: f32, min_mass: f32) -> Vec<f32> {
+    let count = volumes.len();
+    if count == 0 {
+        return Vec::new();
+    }
+
+    if min_mass * count as f32 >= total_mass {
+        return vec![total_mass / count as f32; count];
+    }
+
+    let mut clamped = vec![false; count];
+    loop {
+        let clamped_count = clamped.iter().filter(|c| **c).count();
+        let free_mass = total_mass - min_mass * clamped_count as f32;
+        let free_volume = volumes
+            .iter()
+            .zip(clamped.iter())
+            .filter(|(_, clamped)| !**clamped)
+            .map(|(volume, _)| volume.max(0.0))
+            .sum::<f32>();
+
+        let masses = volumes
+            .iter()
+            .zip(clamped.iter())
+            .map(|(volume, clamped)| {
+                if *clamped {
+                    min_mass
+                } else if free_volume > 0.0 {
+                    free_mass * volume.max(0.0) / free_volume
+                } else {
+                    free_mass / (count - clamped_count) as f32
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // Clamping makes the shares of the others smaller, so it must be repeated until no new
+        // share goes below the minimum. It ends, because at least one share is clamped every time.
+        let mut changed = false;
+        for (mass, clamped) in masses.iter().zip(clamped.iter_mut()) {
+            if !*clamped && *mass < min_mass {
+                *clamped = true;
+                changed = true;
+            }
+        }
+
+        if !changed {
+            return masses;
+        }
+    }
+}
+
+impl RagdollShape {
+    /// Returns the name of the bone or the name of the chain segment, that the body is made for.
+    pub fn name(&self) -> &str {
+        match self.chain {
+            Some(ref segment) => &segment.name,
+            None => self.bone.as_ref(),
+        }
+    }
+
+    /// Returns the density of the collider, that gives the body its share of the mass.
+    pub fn density(&self) -> f32 {
+        self.mass / self.kind.volume().max(f32::EPSILON)
+    }
+
+    pub fn collider_shape(&self) -> ColliderShape {
+        match self.kind {
+            RagdollShapeKind::Sphere { radius } => ColliderShape::ball(radius),
+            RagdollShapeKind::Capsule { length, radius } => ColliderShape::capsule(
+                Vector3::new(0.0, radius, 0.0),
+                Vector3::new(0.0, radius + length, 0.0),
+                radius,
+            ),
+            RagdollShapeKind::Cuboid { half_extents } => {
+                ColliderShape::cuboid(half_extents.x, half_extents.y, half_extents.z)
+            }
+        }
+    }
+
+    pub fn draw(&self, ctx: &mut SceneDrawingContext, color: Color) {
+        let transform = Matrix4::new_translation(&self.position) * self.rotation.to_homogeneous();
+        match self.kind {
+            RagdollShapeKind::Sphere { radius } => {
+                ctx.draw_wire_sphere(self.position, radius, 16, color)
+            }
+            RagdollShapeKind::Capsule { length, radius } => ctx.draw_segment_capsule(
+                Vector3::new(0.0, radius, 0.0),
+                Vector3::new(0.0, radius + length, 0.0),
+                radius,
+                10,
+                10,
+                transform,
+                color,
+            ),
+            RagdollShapeKind::Cuboid { half_extents } => ctx.draw_oob(
+                &AxisAlignedBoundingBox::from_min_max(-half_extents, half_extents),
+                transform,
+                color,
+            ),
+        }
+    }
+}
+
+/// Returns true if the body of the bone is attached to its parent body with a hinge joint, other
+/// bodies use ball joints.
+pub fn uses_hinge_joint(bone: RagdollBone) -> bool {
+    matches!(
+        bone,
+        RagdollBone::LeftLeg
+            | RagdollBone::LeftFoot
+            | RagdollBone::RightLeg
+            | RagdollBone::RightFoot
+            | RagdollBone::Spine
+            | RagdollBone::Spine1
+            | RagdollBone::Spine2
+            | RagdollBone::LeftShoulder
+            | RagdollBone::RightShoulder
+            | RagdollBone::LeftForeArm
+            | RagdollBone::RightForeArm
+    )
+}
+
+/// Returns the closest ancestor of the bone in the skeleton, that has a body. Skipped bones are
+/// bridged this way, for example the shoulders are attached to Spine1 if there's no Spine2.
+pub fn body_parent(bone: RagdollBone, bodies: &[RagdollBone]) -> Option<RagdollBone> {
+    let mut parent = bone.skeleton_parent();
+    while let Some(ancestor) = parent {
+        if bodies.contains(&ancestor) {
+            break;
+        }
+        parent = ancestor.skeleton_parent();
+    }
+    parent
+}
+
+/// Returns the local position and rotation of a node under the parent with the given global
+/// transform, that put the node at the given global position and rotation.
+pub fn local_pose(
+    parent_transform: &Matrix4<f32>,
+    position: Vector3<f32>,
+    rotation: UnitQuaternion<f32>,
+) -> (Vector3<f32>, UnitQuaternion<f32>) {
+    let local_position = parent_transform
+        .try_inverse()
+        .unwrap_or_default()
+        .transform_point(&Point3::from(position))
+        .coords;
+    // The basis may be scaled, physics ignores the scale, so only the rotation is compensated.
+    let parent_rotation = UnitQuaternion::from_matrix_eps(
+        &parent_transform.basis(),
+        f32::EPSILON,
+        16,
+        Default::default(),
+    );
+    (local_position, parent_rotation.inverse() * rotation)
+}
+
+impl RagdollPreset {
+    fn make_body(
+        &self,
+        shape: &RagdollShape,
+        ragdoll: Handle<Node>,
+        graph: &mut Graph,
+    ) -> Handle<Node> {
+        let collider_prefix = match shape.kind {
+            RagdollShapeKind::Sphere { .. } => "RagdollSphereCollider",
+            RagdollShapeKind::Capsule { .. } => "RagdollCapsuleCollider",
+            RagdollShapeKind::Cuboid { .. } => "RagdollCuboidCollider",
+        };
+
+        let mut body = RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_name(ragdoll_part_name("Ragdoll", shape.name()))
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(shape.position)
+                        .with_local_rotation(shape.rotation)
+                        .build(),
+                )
+                .with_children(&[ColliderBuilder::new(
+                    BaseBuilder::new().with_name(ragdoll_part_name(collider_prefix, shape.name())),
+                )
+                .with_shape(shape.collider_shape())
+                .with_friction(self.limb_friction(shape.bone))
+                .with_restitution(self.limb_restitution(shape.bone))
+                .with_collision_groups(self.limb_collision_groups())
+                // The mass comes from the collider, so the body gets the inertia of its shape too.
+                // Mass of the body itself has no inertia, when its colliders are massless, so
+                // such body can't rotate.
+                .with_density(Some(shape.density()))
+                .build(graph)]),
+        )
+        .with_ccd_enabled(self.limb_uses_ccd(shape.bone))
+        .with_body_type(self.physics.rigid_body_type());
+        if self.physics.body_type == RagdollBodyType::Dynamic {
+            body = body
+                .with_lin_damping(self.physics.linear_damping)
+                .with_ang_damping(self.physics.angular_damping)
+                .with_can_sleep(self.physics.can_sleep);
+        }
+        let body = body.build(graph);
+
+        graph.link_nodes(body, ragdoll);
+
+        body
+    }
+
+    /// Returns the space of the character, that the sizes of the bodies are measured in. It is the
+    /// global transform of the parent of the hips.
+    fn character_transform(&self, graph: &Graph) -> Matrix4<f32> {
+        graph
+            .try_get(self.hips)
+            .and_then(|hips| graph.try_get(hips.parent()))
+            .map(|parent| parent.global_transform())
+            .unwrap_or_else(Matrix4::identity)
+    }
+
+    /// Calculates base size (size of the head) in the space of the character using common human
+    /// body proportions. It uses distance between hand and elbow as a head size (it matches 1:1).
+    /// Without the arms a part of the distance between the hips and the head is used, the last
+    /// resort is 0.2 meters.
+    fn measure_base_size(&self, graph: &Graph, character: &Matrix4<f32>) -> f32 {
+        let to_local = character.try_inverse().unwrap_or_else(Matrix4::identity);
+        let local_distance = |a: Handle<Node>, b: Handle<Node>| {
+            let a = graph.try_get(a)?.global_position();
+            let b = graph.try_get(b)?.global_position();
+            Some(to_local.transform_vector(&(a - b)).norm()).filter(|d| *d > f32::EPSILON)
+        };
+        local_distance(self.left_fore_arm, self.left_hand)
+            .or_else(|| local_distance(self.right_fore_arm, self.right_hand))
+            .or_else(|| {
+                local_distance(self.hips, self.head).map(|d| d * BASE_SIZE_PER_HIPS_TO_HEAD)
+            })
+            .unwrap_or_else(|| 0.2 / character_scale(character).mean().max(f32::EPSILON))
+    }
+
+    /// Computes the bodies of the ragdoll, one per assigned bone, in the current pose of the
+    /// character, and splits the total mass between them. Both the generation and the preview
+    /// use it, so the preview shows exactly what will be generated.
+    pub fn make_shapes(&self, graph: &Graph) -> Vec<RagdollShape> {
+        // Sizes are measured in the space of the character and scaled with it, so the bodies fit
+        // characters with any scale. Radii are scaled by the average scale.
+        let character = self.character_transform(graph);
+        let character_scale = character_scale(&character);
+        let local_base_size = self.measure_base_size(graph, &character);
+        let base_size = local_base_size * character_scale.mean();
+
+        let sphere = |bone: RagdollBone, radius: f32, apply_offset: bool| {
+            graph.try_get(self.bone(bone)).map(|from_ref| {
+                let offset = if apply_offset {
+                    from_ref
+                        .up_vector()
+                        .try_normalize(f32::EPSILON)
+                        .unwrap_or_default()
+                        .scale(radius)
+                } else {
+                    Default::default()
+                };
+
+                RagdollShape {
+                    bone,
+                    chain: None,
+                    position: from_ref.global_position() + offset,
+                    rotation: UnitQuaternion::identity(),
+                    kind: RagdollShapeKind::Sphere { radius },
+                    mass: 0.0,
+                }
+            })
+        };
+
+        let capsule_between =
+            |bone: RagdollBone, pos_from: Vector3<f32>, pos_to: Vector3<f32>, radius: f32| {
+                RagdollShape {
+                    bone,
+                    chain: None,
+                    position: pos_from,
+                    // Bones may point along any axis in bind pose, so the basis of the bone can't
+                    // be used.
+                    rotation: capsule_rotation(pos_from, pos_to),
+                    kind: RagdollShapeKind::Capsule {
+                        length: (pos_to - pos_from).norm() - 2.0 * radius,
+                        radius,
+                    },
+                    mass: 0.0,
+                }
+            };
+
+        let capsule = |bone: RagdollBone, to: RagdollBone, radius: f32| match (
+            graph.try_get(self.bone(bone)),
+            graph.try_get(self.bone(to)),
+        ) {
+            (Some(from_ref), Some(to_ref)) => Some(capsule_between(
+                bone,
+                from_ref.global_position(),
+                to_ref.global_position(),
+                radius,
+            )),
+            _ => None,
+        };
+
+        // Fingers and toes have no assigned bones at their ends, their capsules end at the last
+        // bone of their chains.
+        let chain_capsule = |bone: RagdollBone, radius: f32| {
+            let handle = self.bone(bone);
+            graph.try_get(handle)?;
+            let mut end = handle;
+            while let Some(&child) = graph[end].children().first() {
+                end = child;
+            }
+            if end == handle {
+                return None;
+            }
+            Some(capsule_between(
+                bone,
+                graph[handle].global_position(),
+                graph[end].global_position(),
+                radius,
+            ))
+        };
+
+        let cuboid =
+            |bone: RagdollBone, half_extents: Vector3<f32>, rotation: UnitQuaternion<f32>| {
+                graph.try_get(self.bone(bone)).map(|from_ref| RagdollShape {
+                    bone,
+                    chain: None,
+                    position: from_ref.global_position(),
+                    rotation,
+                    kind: RagdollShapeKind::Cuboid { half_extents },
+                    mass: 0.0,
+                })
+            };
+
+        let proportions = &self.proportions;
+        // Thickness of the torso changes its width and depth, but not its height along the spine.
+        let torso_size = Vector3::new(proportions.torso, 1.0, proportions.torso)
+            .component_mul(&character_scale)
+            .scale(local_base_size);
+        let torso_half_extents = Vector3::new(0.45, 0.2, 0.4).component_mul(&torso_size);
+
+        // Duplicate spine bones are skipped, the torso is split between the distinct ones. Every
+        // cuboid spans from its bone to the next one, the topmost one goes up to the neck.
+        let spine = spine_chain(&[
+            (RagdollBone::Spine, self.spine),
+            (RagdollBone::Spine1, self.spine1),
+            (RagdollBone::Spine2, self.spine2),
+        ]);
+        let top = [self.neck, self.head]
+            .iter()
+            .find_map(|&handle| graph.try_get(handle).map(|node| node.global_position()));
+
+        // Torso bodies face the same way as the character, the facing is derived from the sides
+        // of the body. Without them the bodies are aligned with the world axes.
+        let position = |handle: Handle<Node>| graph.try_get(handle).map(|n| n.global_position());
+        let side = [
+            (self.left_up_leg, self.right_up_leg),
+            (self.left_arm, self.right_arm),
+            (self.left_shoulder, self.right_shoulder),
+        ]
+        .iter()
+        .find_map(|&(left, right)| Some(position(left)? - position(right)?));
+        let hips_up = spine
+            .first()
+            .and_then(|&(_, handle)| position(handle))
+            .or(top)
+            .and_then(|to| Some(to - position(self.hips)?))
+            .unwrap_or_else(Vector3::y);
+        let torso_rotation_or = |up: Vector3<f32>, fallback: UnitQuaternion<f32>| {
+            side.and_then(|side| torso_rotation(up, side))
+                .unwrap_or(fallback)
+        };
+        let spine_shapes = spine.iter().enumerate().filter_map(|(i, &(bone, handle))| {
+            let from = graph.try_get(handle)?.global_position();
+            let to = match spine.get(i + 1) {
+                Some(&(_, next)) => graph.try_get(next).map(|node| node.global_position()),
+                None => top,
+            };
+            Some(match to {
+                Some(to) if (to - from).norm() > f32::EPSILON => RagdollShape {
+                    bone,
+                    chain: None,
+                    position: (from + to).scale(0.5),
+                    rotation: torso_rotation_or(to - from, capsule_rotation(from, to)),
+                    kind: RagdollShapeKind::Cuboid {
+                        half_extents: Vector3::new(
+                            torso_half_extents.x,
+                            (to - from).norm() * 0.5,
+                            torso_half_extents.z,
+                        ),
+                    },
+                    mass: 0.0,
+                },
+                _ => RagdollShape {
+                    bone,
+                    chain: None,
+                    position: from,
+                    rotation: torso_rotation_or(hips_up, UnitQuaternion::identity()),
+                    kind: RagdollShapeKind::Cuboid {
+                        half_extents: torso_half_extents,
+                    },
+                    mass: 0.0,
+                },
+            })
+        });
+
+        let mut shapes = [
+            capsule(
+                RagdollBone::LeftUpLeg,
+                RagdollBone::LeftLeg,
+                0.35 * base_size * proportions.legs,
+            ),
+            capsule(
+                RagdollBone::LeftLeg,
+                RagdollBone::LeftFoot,
+                0.3 * base_size * proportions.legs,
+            ),
+            sphere(
+                RagdollBone::LeftFoot,
+                0.2 * base_size * proportions.hands_and_feet,
+                false,
+            ),
+            capsule(
+                RagdollBone::RightUpLeg,
+                RagdollBone::RightLeg,
+                0.35 * base_size * proportions.legs,
+            ),
+            capsule(
+                RagdollBone::RightLeg,
+                RagdollBone::RightFoot,
+                0.3 * base_size * proportions.legs,
+            ),
+            sphere(
+                RagdollBone::RightFoot,
+                0.2 * base_size * proportions.hands_and_feet,
+                false,
+            ),
+            cuboid(
+                RagdollBone::Hips,
+                Vector3::new(0.5, 0.2, 0.4).component_mul(&torso_size),
+                torso_rotation_or(hips_up, UnitQuaternion::identity()),
+            ),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(spine_shapes)
+        .collect::<Vec<_>>();
+        shapes.extend(
+            [
+                // Left arm.
+                capsule(
+                    RagdollBone::LeftShoulder,
+                    RagdollBone::LeftArm,
+                    0.2 * base_size * proportions.arms,
+                ),
+                capsule(
+                    RagdollBone::LeftArm,
+                    RagdollBone::LeftForeArm,
+                    0.2 * base_size * proportions.arms,
+                ),
+                capsule(
+                    RagdollBone::LeftForeArm,
+                    RagdollBone::LeftHand,
+                    0.2 * base_size * proportions.arms,
+                ),
+                sphere(
+                    RagdollBone::LeftHand,
+                    0.3 * base_size * proportions.hands_and_feet,
+                    false,
+                ),
+                // Right arm.
+                capsule(
+                    RagdollBone::RightShoulder,
+                    RagdollBone::RightArm,
+                    0.2 * base_size * proportions.arms,
+                ),
+                capsule(
+                    RagdollBone::RightArm,
+                    RagdollBone::RightForeArm,
+                    0.2 * base_size * proportions.arms,
+                ),
+                capsule(
+                    RagdollBone::RightForeArm,
+                    RagdollBone::RightHand,
+                    0.2 * base_size * proportions.arms,
+                ),
+                sphere(
+                    RagdollBone::RightHand,
+                    0.3 * base_size * proportions.hands_and_feet,
+                    false,
+                ),
+                capsule(
+                    RagdollBone::Neck,
+                    RagdollBone::Head,
+                    0.2 * base_size * proportions.head,
+                ),
+                sphere(RagdollBone::Head, 0.5 * base_size * proportions.head, true),
+            ]
+            .into_iter()
+            .flatten(),
+        );
+
+        if self.include_fingers_and_toes {
+            for bone in RagdollBone::ALL {
+                let radius = match bone.digit_parent() {
+                    Some(RagdollBone::LeftHand) | Some(RagdollBone::RightHand) => 0.04,
+                    Some(_) => 0.1,
+                    None => continue,
+                };
+                shapes.extend(chain_capsule(
+                    bone,
+                    radius * base_size * proportions.hands_and_feet,
+                ));
+            }
+        }
+
+        // Excluded hands and feet are still the ends of the capsules of the arms and the legs, but
+        // they get no bodies, their shares of the mass go to the rest of the bodies.
+        shapes.retain(|shape| self.generates_body(shape.bone));
+
+        for chain in self.extra_chains.iter() {
+            let bones = chain.bones(graph);
+            if bones.len() < 2 {
+                continue;
+            }
+            let limb = self.chain_limb(graph, &shapes, bones[0]);
+            for (index, segment) in bones.windows(2).enumerate() {
+                shapes.push(RagdollShape {
+                    chain: Some(RagdollChainSegment {
+                        bone: segment[0],
+                        name: chain.segment_name(index),
+                        index,
+                        joint_limits: chain.joint_limits.clone(),
+                    }),
+                    ..capsule_between(
+                        limb,
+                        graph[segment[0]].global_position(),
+                        graph[segment[1]].global_position(),
+                        chain.radius * base_size,
+                    )
+                });
+            }
+        }
+
+        let volumes = shapes
+            .iter()
+            .map(|shape| shape.kind.volume())
+            .collect::<Vec<_>>();
+        for (shape, mass) in shapes.iter_mut().zip(distribute_mass(
+            &volumes,
+            self.total_mass,
+            self.min_limb_mass,
+        )) {
+            shape.mass = mass;
+        }
+
+        shapes
+    }
+
+    /// Returns the limb, that an extra chain with the given root is attached to. It is the closest
+    /// ancestor of the root, that has a body, or the hips.
+    fn chain_limb(
+        &self,
+        graph: &Graph,
+        shapes: &[RagdollShape],
+        root: Handle<Node>,
+    ) -> RagdollBone {
+        let mut ancestor = graph[root].parent();
+        while ancestor.is_some() {
+            if let Some(shape) = shapes
+                .iter()
+                .find(|shape| shape.chain.is_none() && self.bone(shape.bone) == ancestor)
+            {
+                return shape.bone;
+            }
+            ancestor = graph[ancestor].parent();
+        }
+        RagdollBone::Hips
+    }
+
+    /// Returns the bone, that the body of the shape follows.
+    fn shape_bone(&self, shape: &RagdollShape) -> Handle<Node> {
+        match shape.chain {
+            Some(ref segment) => segment.bone,
+            None => self.bone(shape.bone),
+        }
+    }
+
+    /// Makes the limb of the bone with the given body, its children are the limbs of the bones,
+    /// that are attached to it. `bodies` must contain the body of the bone.
+    pub fn make_limb(&self, bone: RagdollBone, bodies: &[(RagdollBone, Handle<Node>)]) -> Limb {
+        let bones = bodies.iter().map(|(bone, _)| *bone).collect::<Vec<_>>();
+        Limb {
+            bone: self.bone(bone),
+            physical_bone: bodies
+                .iter()
+                .find(|(b, _)| *b == bone)
+                .map(|(_, body)| *body)
+                .unwrap_or_default(),
+            children: bodies
+                .iter()
+                .filter(|(child, _)| body_parent(*child, &bones) == Some(bone))
+                .map(|(child, _)| self.make_limb(*child, bodies))
+                .collect(),
+        }
+    }
+
+    /// Draws the bodies, that the preset would generate, as wireframe shapes.
+    pub fn draw_preview(&self, graph: &Graph, ctx: &mut SceneDrawingContext) {
+        for shape in self.make_shapes(graph) {
+            shape.draw(ctx, Color::ORANGE);
+        }
+    }
+
+    /// Generates the ragdoll (or the hitboxes) and sends the command, that adds it to the scene.
+    /// The given nodes are deleted by the same command, so one undo brings them back.
+    pub fn create_and_send_command(
+        &self,
+        graph: &mut Graph,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+        replace: &[Handle<Node>],
+    ) -> Result<Vec<RagdollOverlap>, String> {
+        let (roots, overlaps) =
+            self.generate_nodes(graph, editor_scene.scene_content_root, replace)?;
+        let name = match self.output_mode {
+            RagdollOutputMode::Ragdoll => "Generate Ragdoll",
+            RagdollOutputMode::Hitboxes => "Generate Hitboxes",
+        };
+        let group = make_generation_commands(graph, &roots, replace, &editor_scene.selection);
+        sender.do_scene_command(CommandGroup::from(group).with_custom_name(name));
+
+        Ok(overlaps)
+    }
+
+    /// Generates the ragdoll (or the hitboxes) in the graph and returns the roots of the generated
+    /// nodes with the overlaps of the bodies. The nodes must be taken by the command, that adds
+    /// them (see [`make_generation_commands`]).
+    pub fn generate_nodes(
+        &self,
+        graph: &mut Graph,
+        scene_root: Handle<Node>,
+        replace: &[Handle<Node>],
+    ) -> Result<(Vec<Handle<Node>>, Vec<RagdollOverlap>), String> {
+        let check = self.check_bones(graph);
+        if !check.missing.is_empty() {
+            return Err(format!("{} must be assigned.", bone_list(&check.missing)));
+        }
+
+        let shapes = self.make_shapes(graph);
+        if shapes.is_empty() {
+            return Err("no bones are assigned.".to_owned());
+        }
+        self.check_chains(graph, &shapes)?;
+        self.check_numbers()?;
+
+        if self.limb_collision_groups().filter == BitMask(0) {
+            Log::warn(
+                "Ragdoll Wizard: the limbs collide with nothing, because every group of Collision \
+                Mask is removed by disabled self-collision. Set Collision Groups to the groups, \
+                that are used only by ragdolls."
+                    .to_owned(),
+            );
+        }
+
+        match self.output_mode {
+            RagdollOutputMode::Ragdoll => {
+                let parent = self.ragdoll_parent(graph, scene_root, replace)?;
+
+                Log::info(mass_breakdown(&shapes));
+
+                let ragdoll = self.build_ragdoll(&shapes, parent, graph);
+                let overlaps = find_ragdoll_overlaps(graph, ragdoll, self.overlap_threshold);
+                Ok((vec![ragdoll], overlaps))
+            }
+            // Hitboxes are sensors, they may overlap each other.
+            RagdollOutputMode::Hitboxes => Ok((self.build_hitboxes(&shapes, graph), Vec::new())),
+        }
+    }
+
+    /// Creates a hitbox for every shape under its bone, so it follows the animation. Colliders
+    /// must be attached to rigid bodies, so every hitbox is a kinematic body with a sensor
+    /// collider.
+    fn build_hitboxes(&self, shapes: &[RagdollShape], graph: &mut Graph) -> Vec<Handle<Node>> {
+        shapes
+            .iter()
+            .map(|shape| {
+                let bone = self.shape_bone(shape);
+                let (position, rotation) = local_pose(
+                    &graph[bone].global_transform(),
+                    shape.position,
+                    shape.rotation,
+                );
+                let tag = ragdoll_part_name(&self.hitbox_prefix, shape.name());
+
+                let collider = ColliderBuilder::new(
+                    BaseBuilder::new()
+                        .with_name(format!("{}Collider", tag))
+                        .with_tag(tag.clone()),
+                )
+                .with_shape(shape.collider_shape())
+                .with_sensor(true)
+                .with_collision_groups(self.limb_collision_groups())
+                .build(graph);
+
+                let body = RigidBodyBuilder::new(
+                    BaseBuilder::new()
+                        .with_name(&tag)
+                        .with_tag(tag)
+                        .with_local_transform(
+                            TransformBuilder::new()
+                                .with_local_position(position)
+                                .with_local_rotation(rotation)
+                                .build(),
+                        )
+                        .with_children(&[collider]),
+                )
+                .with_body_type(RigidBodyType::KinematicPositionBased)
+                .build(graph);
+
+                graph.link_nodes(body, bone);
+
+                body
+            })
+            .collect()
+    }
+
+    /// Returns the hitboxes with the current prefix under the assigned bones and the bones of the
+    /// extra chains.
+    pub fn find_hitboxes(&self, graph: &Graph) -> Vec<Handle<Node>> {
+        let mut bones = RagdollBone::ALL
+            .iter()
+            .map(|&bone| (self.bone(bone), bone.as_ref().to_owned()))
+            .collect::<Vec<_>>();
+        for chain in self.extra_chains.iter() {
+            // Hitboxes are under the bones at the starts of the segments, the end has none.
+            for (index, segment) in chain.bones(graph).windows(2).enumerate() {
+                bones.push((segment[0], chain.segment_name(index)));
+            }
+        }
+
+        let mut hitboxes = Vec::new();
+        for (bone, name) in bones {
+            if let Some(bone_ref) = graph.try_get(bone) {
+                let tag = ragdoll_part_name(&self.hitbox_prefix, &name);
+                hitboxes.extend(bone_ref.children().iter().cloned().filter(|&child| {
+                    graph[child].tag() == tag && graph[child].cast::<RigidBody>().is_some()
+                }));
+            }
+        }
+        hitboxes
+    }
+
+    /// Creates the ragdoll with the bodies and joints between them under the given parent.
+    pub fn build_ragdoll(
+        &self,
+        shapes: &[RagdollShape],
+        parent: Handle<Node>,
+        graph: &mut Graph,
+    ) -> Handle<Node> {
+        let ragdoll = RagdollBuilder::new(BaseBuilder::new().with_name("Ragdoll"))
+            .with_active(self.active)
+            .build(graph);
+
+        graph.link_nodes(ragdoll, parent);
+
+        let bodies = shapes
+            .iter()
+            .filter(|shape| shape.chain.is_none())
+            .map(|shape| (shape.bone, self.make_body(shape, ragdoll, graph)))
+            .collect::<Vec<_>>();
+        let bones = bodies.iter().map(|(bone, _)| *bone).collect::<Vec<_>>();
+        // Segments of a chain go one after another, starting from the one attached to the limb.
+        let chain_bodies = shapes
+            .iter()
+            .filter_map(|shape| {
+                shape
+                    .chain
+                    .as_ref()
+                    .map(|segment| (shape.bone, segment, self.make_body(shape, ragdoll, graph)))
+            })
+            .collect::<Vec<_>>();
+        let limb_body = |bone: RagdollBone| {
+            bodies
+                .iter()
+                .find(|(b, _)| *b == bone)
+                .map(|(_, body)| *body)
+                .unwrap_or_default()
+        };
+
+        // Link limbs with joints. Bones without bodies are bridged, the joint goes to the closest
+        // ancestor with a body.
+        graph.update_hierarchical_data();
+
+        for &(bone, body) in bodies.iter() {
+            let parent_body = match body_parent(bone, &bones)
+                .and_then(|parent| bodies.iter().find(|(b, _)| *b == parent))
+            {
+                Some((_, parent_body)) => *parent_body,
+                None => continue,
+            };
+            let position =
+                self.joint_anchor
+                    .joint_position(graph, self.bone(bone), body, parent_body);
+            if uses_hinge_joint(bone) {
+                try_make_hinge_joint(
+                    body,
+                    parent_body,
+                    position,
+                    &ragdoll_part_name("RagdollHingeJoint", bone.as_ref()),
+                    joint_limits(self.joint_limits.of(bone)),
+                    self.hinge_axis,
+                    ragdoll,
+                    graph,
+                );
+            } else {
+                try_make_ball_joint(
+                    body,
+                    parent_body,
+                    position,
+                    &ragdoll_part_name("RagdollBallJoint", bone.as_ref()),
+                    self.joint_limits.ball_joint_limits(bone),
+                    ragdoll,
+                    graph,
+                );
+            }
+        }
+
+        for (i, &(limb, segment, body)) in chain_bodies.iter().enumerate() {
+            let parent_body = if segment.index == 0 {
+                limb_body(limb)
+            } else {
+                chain_bodies[i - 1].2
+            };
+            try_make_ball_joint(
+                body,
+                parent_body,
+                self.joint_anchor
+                    .joint_position(graph, segment.bone, body, parent_body),
+                &ragdoll_part_name("RagdollBallJoint", &segment.name),
+                BallJointLimits::uniform(joint_limits(&segment.joint_limits)),
+                ragdoll,
+                graph,
+            );
+        }
+
+        let mut hips = self.make_limb(RagdollBone::Hips, &bodies);
+        // Every segment is the child of the previous one, so the limbs of a chain are built from
+        // the end.
+        let mut chain_limb = None;
+        for &(limb, segment, body) in chain_bodies.iter().rev() {
+            let segment_limb = Limb {
+                bone: segment.bone,
+                physical_bone: body,
+                children: chain_limb.take().into_iter().collect(),
+            };
+            if segment.index == 0 {
+                if let Some(parent) = find_limb_of_body_mut(&mut hips, limb_body(limb)) {
+                    parent.children.push(segment_limb);
+                }
+            } else {
+                chain_limb = Some(segment_limb);
+            }
+        }
+        graph[ragdoll].as_ragdoll_mut().set_hips(hips);
+
+        ragdoll
+    }
+
+    /// Builds the ragdoll in a separate scene, that could be saved as a prefab. The bones are not
+    /// in the prefab, the limbs are bound to them by names (see [`rebind_limbs`]), so the prefab
+    /// could be used with any character with the same bone names.
+    pub fn make_prefab(&self, graph: &mut Graph) -> Result<Scene, String> {
+        if self.output_mode == RagdollOutputMode::Hitboxes {
+            return Err(
+                "hitboxes are parts of the skeleton, only ragdolls could be exported \
+                as prefabs."
+                    .to_owned(),
+            );
+        }
+
+        let check = self.check_bones(graph);
+        if !check.missing.is_empty() {
+            return Err(format!("{} must be assigned.", bone_list(&check.missing)));
+        }
+
+        let shapes = self.make_shapes(graph);
+        if shapes.is_empty() {
+            return Err("no bones are assigned.".to_owned());
+        }
+        self.check_chains(graph, &shapes)?;
+        self.check_numbers()?;
+
+        // Bodies and joints are placed by the bones, so the ragdoll is built next to them and
+        // moved to the prefab right away.
+        let root = graph.get_root();
+        let ragdoll = self.build_ragdoll(&shapes, root, graph);
+        let bindings = limb_bindings(graph, ragdoll);
+        // Handles of the bones are meaningless outside of the scene, the bindings replace them.
+        let mut hips = graph[ragdoll].as_ragdoll().hips().clone();
+        unbind_limb(&mut hips);
+        graph[ragdoll].as_ragdoll_mut().set_hips(hips);
+
+        let mut prefab = Scene::new();
+        let (prefab_ragdoll, _) = graph.copy_node(ragdoll, &mut prefab.graph, &mut |_, _| true);
+        graph.remove_node(ragdoll);
+        prefab.graph[prefab_ragdoll].set_properties(bindings);
+
+        Ok(prefab)
+    }
+}
+
+fn find_limb_of_body_mut(limb: &mut Limb, body: Handle<Node>) -> Option<&mut Limb> {
+    if body.is_some() && limb.physical_bone == body {
+        return Some(limb);
+    }
+    limb.children
+        .iter_mut()
+        .find_map(|child| find_limb_of_body_mut(child, body))
+}
+
+fn unbind_limb(limb: &mut Limb) {
+    limb.bone = Handle::NONE;
+    for child in limb.children.iter_mut() {
+        unbind_limb(child);
+    }
+}
+
+/// Returns a table with masses of the bodies.
+pub fn mass_breakdown(shapes: &[RagdollShape]) -> String {
+    let mut text = "Masses of the ragdoll bodies:".to_owned();
+    for shape in shapes {
+        text += &format!("\n  {} - {:.3}", shape.name(), shape.mass);
+    }
+    text += &format!(
+        "\n  Total - {:.3}",
+        shapes.iter().map(|shape| shape.mass).sum::<f32>()
+    );
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        command::Command,
+        scene::commands::test::{make_editor_scene, scene_context},
+        utils::ragdoll::{
+            preset::{
+                RagdollBoneChain, RagdollJointAnchor, RagdollJointLimits, RagdollLimitShape,
+                RagdollProportions,
+            },
+            test::{make_bone, make_character},
+        },
+    };
+    use fyrox::scene::{collider::Collider, joint::Joint};
+    use std::sync::mpsc::channel;
+
+    #[test]
+    fn test_find_ragdolls() {
+        let mut graph = Graph::new();
+        let hips = make_bone(&mut graph, Vector3::default());
+        let other_hips = make_bone(&mut graph, Vector3::default());
+        let mut make_ragdoll = |hips: Handle<Node>| {
+            RagdollBuilder::new(BaseBuilder::new())
+                .with_hips(Limb {
+                    bone: hips,
+                    physical_bone: Handle::NONE,
+                    children: Vec::new(),
+                })
+                .build(&mut graph)
+        };
+        let first = make_ragdoll(hips);
+        let other = make_ragdoll(other_hips);
+        let second = make_ragdoll(hips);
+        // Ragdolls could be moved under the character.
+        graph.link_nodes(second, hips);
+        let root = graph.get_root();
+
+        assert_eq!(find_ragdolls(&graph, root, hips), vec![first, second]);
+        assert_eq!(find_ragdolls(&graph, root, other_hips), vec![other]);
+        assert_eq!(find_ragdolls(&graph, hips, hips), vec![second]);
+        assert!(find_ragdolls(&graph, root, Handle::NONE).is_empty());
+    }
+
+    #[test]
+    fn test_capsule_follows_bones() {
+        let mut graph = Graph::new();
+        let left_arm = make_bone(&mut graph, Vector3::new(1.0, 2.0, 3.0));
+        // The bone is rotated, its basis must not affect the capsule.
+        graph[left_arm]
+            .local_transform_mut()
+            .set_rotation(UnitQuaternion::from_axis_angle(&Vector3::z_axis(), 1.0));
+        let preset = RagdollPreset {
+            left_arm,
+            left_fore_arm: make_bone(&mut graph, Vector3::new(1.0, 2.0, 2.0)),
+            ..Default::default()
+        };
+        graph.update_hierarchical_data();
+
+        let shape = preset.make_shapes(&graph).remove(0);
+        assert_eq!(shape.bone, RagdollBone::LeftArm);
+        let capsule = match shape.collider_shape() {
+            ColliderShape::Capsule(capsule) => capsule,
+            shape => panic!("unexpected shape {:?}", shape),
+        };
+        let transform = |point: Vector3<f32>| shape.position + shape.rotation * point;
+        let up = Vector3::new(0.0, capsule.radius, 0.0);
+        // Tips of the capsule are at the positions of the bones.
+        assert!((transform(capsule.begin - up) - Vector3::new(1.0, 2.0, 3.0)).norm() < 1.0e-5);
+        assert!((transform(capsule.end + up) - Vector3::new(1.0, 2.0, 2.0)).norm() < 1.0e-5);
+
+        // Bones, that point along any axis.
+        for direction in [
+            Vector3::x(),
+            -Vector3::x(),
+            Vector3::y(),
+            -Vector3::y(),
+            -Vector3::z(),
+            Vector3::new(1.0, -1.0, 2.0).normalize(),
+        ] {
+            let rotation = capsule_rotation(Vector3::default(), direction * 2.0);
+            assert!((rotation * Vector3::y() - direction).norm() < 1.0e-5);
+        }
+        assert_eq!(
+            capsule_rotation(Vector3::default(), Vector3::default()),
+            UnitQuaternion::identity()
+        );
+    }
+
+    #[test]
+    fn test_joint_limits() {
+        assert_eq!(joint_limits(&(-180.0..180.0)), None);
+        assert_eq!(joint_limits(&(-360.0..200.0)), None);
+        let limits = joint_limits(&(-90.0..45.0)).unwrap();
+        assert!((limits.start + std::f32::consts::FRAC_PI_2).abs() < 1.0e-6);
+        assert!((limits.end - std::f32::consts::FRAC_PI_4).abs() < 1.0e-6);
+
+        // Hinge joints rotate around X axis of the joint, it must match the chosen axis of the
+        // body.
+        for (axis, expected) in [
+            (RagdollHingeAxis::X, Vector3::x()),
+            (RagdollHingeAxis::Y, Vector3::y()),
+            (RagdollHingeAxis::Z, Vector3::z()),
+        ] {
+            let actual = axis.joint_rotation() * Vector3::x();
+            assert!((actual - expected).norm() < 1.0e-6, "{:?}", axis);
+        }
+    }
+
+    #[test]
+    fn test_cone_limits() {
+        use std::f32::consts::{FRAC_PI_2, FRAC_PI_3, FRAC_PI_4, PI};
+
+        let assert_range = |range: &Option<Range<f32>>, expected: f32| {
+            let range = range.clone().unwrap();
+            assert!((range.start + expected).abs() < 1.0e-5, "{:?}", range);
+            assert!((range.end - expected).abs() < 1.0e-5, "{:?}", range);
+        };
+
+        // A swing by 45 degrees around both axes turns the bone by 60 degrees.
+        let limits = BallJointLimits::cone(FRAC_PI_3, Some(-0.5..0.5));
+        assert_range(&limits.x, FRAC_PI_4);
+        assert_range(&limits.z, FRAC_PI_4);
+        assert_eq!(limits.y, Some(-0.5..0.5));
+
+        // The diagonal swing is on the cone, the straight swings are inside it.
+        for half_angle in [0.1, 0.5, 1.0, 1.5] {
+            let limits = BallJointLimits::cone(half_angle, None);
+            let swing = limits.x.unwrap().end;
+            let rotation = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), swing)
+                * UnitQuaternion::from_axis_angle(&Vector3::z_axis(), swing);
+            let angle = (rotation * Vector3::y()).angle(&Vector3::y());
+            assert!(
+                (angle - half_angle).abs() < 1.0e-4,
+                "{} {}",
+                half_angle,
+                angle
+            );
+            assert_eq!(limits.y, None);
+        }
+
+        // Cones wider than a hemisphere are limited by the half-angle.
+        assert_range(&BallJointLimits::cone(FRAC_PI_2, None).x, FRAC_PI_2);
+        assert_range(&BallJointLimits::cone(2.0, None).z, 2.0);
+        assert_eq!(BallJointLimits::cone(PI, None), BallJointLimits::default());
+
+        // Cones are used only for the hips, the shoulders and the neck, the largest angle of the
+        // range is the half-angle.
+        let mut limits = RagdollJointLimits {
+            hips: -20.0..60.0,
+            wrists: -60.0..60.0,
+            ..Default::default()
+        };
+        let cone = limits.ball_joint_limits(RagdollBone::LeftUpLeg);
+        assert_range(&cone.x, FRAC_PI_4);
+        assert_range(&cone.y, 45.0f32.to_radians());
+        assert_eq!(
+            limits.ball_joint_limits(RagdollBone::LeftHand),
+            BallJointLimits::uniform(joint_limits(&(-60.0..60.0)))
+        );
+        // Unlimited shoulders stay unlimited, except for the twist.
+        let shoulder = limits.ball_joint_limits(RagdollBone::RightArm);
+        assert_eq!((shoulder.x, shoulder.z), (None, None));
+        assert!(shoulder.y.is_some());
+
+        limits.shape = RagdollLimitShape::Box;
+        assert_eq!(
+            limits.ball_joint_limits(RagdollBone::LeftUpLeg),
+            BallJointLimits::uniform(joint_limits(&(-20.0..60.0)))
+        );
+    }
+
+    #[test]
+    fn test_joint_anchor() {
+        let mut graph = Graph::new();
+        let parent_body = make_bone(&mut graph, Vector3::new(0.0, 1.0, 0.0));
+        let bone = make_bone(&mut graph, Vector3::new(0.0, 0.5, 0.0));
+        let body = make_bone(&mut graph, Vector3::new(0.0, 0.25, 0.0));
+        graph.update_hierarchical_data();
+
+        for (anchor, expected) in [
+            (RagdollJointAnchor::ChildBone, 0.25),
+            (RagdollJointAnchor::ParentBone, 1.0),
+            (RagdollJointAnchor::BoneConnection, 0.5),
+        ] {
+            let position = anchor.joint_position(&graph, bone, body, parent_body);
+            assert_eq!(position, Vector3::new(0.0, expected, 0.0), "{:?}", anchor);
+        }
+        assert_eq!(
+            RagdollJointAnchor::default(),
+            RagdollJointAnchor::BoneConnection
+        );
+    }
+
+    #[test]
+    fn test_make_shapes() {
+        let mut graph = Graph::new();
+        let preset = RagdollPreset {
+            hips: make_bone(&mut graph, Vector3::new(0.0, -1.0, 0.0)),
+            left_arm: make_bone(&mut graph, Vector3::new(0.0, 0.0, 0.0)),
+            left_fore_arm: make_bone(&mut graph, Vector3::new(0.0, 1.0, 0.0)),
+            // Distance between the fore arm and the hand is the base size.
+            left_hand: make_bone(&mut graph, Vector3::new(0.0, 1.5, 0.0)),
+            head: make_bone(&mut graph, Vector3::new(0.0, 2.0, 0.0)),
+            ..Default::default()
+        };
+        graph.update_hierarchical_data();
+
+        let shapes = preset.make_shapes(&graph);
+        // Only assigned bones get bodies, capsules also need the next bone.
+        assert_eq!(
+            shapes.iter().map(|s| s.bone).collect::<Vec<_>>(),
+            vec![
+                RagdollBone::Hips,
+                RagdollBone::LeftArm,
+                RagdollBone::LeftForeArm,
+                RagdollBone::LeftHand,
+                RagdollBone::Head
+            ]
+        );
+
+        assert_eq!(
+            shapes[0].kind,
+            RagdollShapeKind::Cuboid {
+                half_extents: Vector3::new(0.25, 0.1, 0.2)
+            }
+        );
+        // Capsules end at the next bone.
+        match shapes[1].kind {
+            RagdollShapeKind::Capsule { length, radius } => {
+                assert!((length + 2.0 * radius - 1.0).abs() < 1.0e-6);
+                assert_eq!(radius, 0.1);
+            }
+            ref kind => panic!("unexpected shape {:?}", kind),
+        }
+        assert_eq!(shapes[3].kind, RagdollShapeKind::Sphere { radius: 0.15 });
+        // The head is moved up by its radius.
+        assert_eq!(shapes[4].position, Vector3::new(0.0, 2.25, 0.0));
+
+        match shapes[1].collider_shape() {
+            ColliderShape::Capsule(capsule) => {
+                assert_eq!(capsule.begin, Vector3::new(0.0, 0.1, 0.0));
+                assert!((capsule.end - Vector3::new(0.0, 0.9, 0.0)).norm() < 1.0e-6);
+                assert_eq!(capsule.radius, 0.1);
+            }
+            shape => panic!("unexpected shape {:?}", shape),
+        }
+
+        // Default proportions keep the sizes, others scale the radii and the torso thickness.
+        let mut slim = RagdollPreset {
+            proportions: RagdollProportions {
+                arms: 0.5,
+                torso: 2.0,
+                ..Default::default()
+            },
+            ..preset
+        };
+        let slim_shapes = slim.make_shapes(&graph);
+        match slim_shapes[1].kind {
+            RagdollShapeKind::Capsule { radius, .. } => assert_eq!(radius, 0.05),
+            ref kind => panic!("unexpected shape {:?}", kind),
+        }
+        assert_eq!(
+            slim_shapes[0].kind,
+            RagdollShapeKind::Cuboid {
+                half_extents: Vector3::new(0.5, 0.1, 0.4)
+            }
+        );
+        assert_eq!(slim_shapes[3].kind, shapes[3].kind);
+
+        slim.proportions = Default::default();
+        assert_eq!(slim.make_shapes(&graph), shapes);
+    }
+
+    #[test]
+    fn test_torso_spans_spine_bones() {
+        let mut graph = Graph::new();
+        let spine = make_bone(&mut graph, Vector3::new(0.0, 1.0, 0.0));
+        let spine1 = make_bone(&mut graph, Vector3::new(0.0, 1.4, 0.0));
+        let neck = make_bone(&mut graph, Vector3::new(0.0, 2.0, 0.0));
+        let left_shoulder = make_bone(&mut graph, Vector3::new(0.2, 1.9, 0.0));
+        let left_arm = make_bone(&mut graph, Vector3::new(0.4, 1.9, 0.0));
+
+        let mut preset = RagdollPreset {
+            hips: make_bone(&mut graph, Vector3::new(0.0, 0.9, 0.0)),
+            spine,
+            spine1,
+            // The same node as Spine1.
+            spine2: spine1,
+            neck,
+            left_shoulder,
+            left_arm,
+            ..Default::default()
+        };
+        graph.update_hierarchical_data();
+
+        let shapes = preset.make_shapes(&graph);
+        let torso = shapes
+            .iter()
+            .filter(|shape| {
+                matches!(
+                    shape.bone,
+                    RagdollBone::Spine | RagdollBone::Spine1 | RagdollBone::Spine2
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(torso.len(), 2);
+        assert_eq!(torso[0].bone, RagdollBone::Spine);
+        assert!((torso[0].position - Vector3::new(0.0, 1.2, 0.0)).norm() < 1.0e-5);
+        // The topmost cuboid goes up to the neck.
+        assert_eq!(torso[1].bone, RagdollBone::Spine1);
+        assert!((torso[1].position - Vector3::new(0.0, 1.7, 0.0)).norm() < 1.0e-5);
+        for (shape, height) in torso.iter().zip([0.4, 0.6]) {
+            match shape.kind {
+                RagdollShapeKind::Cuboid { half_extents } => {
+                    assert!((half_extents.y * 2.0 - height).abs() < 1.0e-5)
+                }
+                ref kind => panic!("unexpected shape {:?}", kind),
+            }
+        }
+
+        // The shoulder is attached to the topmost spine body, there's no limb for Spine2. The neck
+        // has no body without the head.
+        let ragdoll = preset.build_ragdoll(&shapes, graph.get_root(), &mut graph);
+        let hips = graph[ragdoll].as_ragdoll().hips().clone();
+        assert_eq!(hips.children.len(), 1);
+        assert_eq!(hips.children[0].bone, spine);
+        let spine1_limb = &hips.children[0].children[0];
+        assert_eq!(spine1_limb.bone, spine1);
+        assert_eq!(spine1_limb.children.len(), 1);
+        assert_eq!(spine1_limb.children[0].bone, left_shoulder);
+        assert!(graph
+            .find_by_name(ragdoll, "RagdollHingeJointLeftShoulder")
+            .is_some());
+
+        // A single spine bone spans the whole torso.
+        preset.spine1 = spine;
+        preset.spine2 = Handle::NONE;
+        let shapes = preset.make_shapes(&graph);
+        assert_eq!(
+            shapes
+                .iter()
+                .filter(|shape| shape.bone == RagdollBone::Spine)
+                .map(|shape| shape.position)
+                .collect::<Vec<_>>(),
+            vec![Vector3::new(0.0, 1.5, 0.0)]
+        );
+        assert!(!shapes.iter().any(|shape| shape.bone == RagdollBone::Spine1));
+    }
+
+    #[test]
+    fn test_torso_faces_the_character() {
+        let make_shapes = |yaw: f32| {
+            let mut graph = Graph::new();
+            let root = make_bone(&mut graph, Vector3::default());
+            graph[root]
+                .local_transform_mut()
+                .set_rotation(UnitQuaternion::from_axis_angle(&Vector3::y_axis(), yaw));
+            let mut bone = |position: Vector3<f32>| {
+                let bone = make_bone(&mut graph, position);
+                graph.link_nodes(bone, root);
+                bone
+            };
+            let preset = RagdollPreset {
+                hips: bone(Vector3::new(0.0, 1.0, 0.0)),
+                left_up_leg: bone(Vector3::new(0.1, 0.9, 0.0)),
+                right_up_leg: bone(Vector3::new(-0.1, 0.9, 0.0)),
+                spine: bone(Vector3::new(0.0, 1.2, 0.0)),
+                spine1: bone(Vector3::new(0.0, 1.4, 0.05)),
+                neck: bone(Vector3::new(0.0, 1.7, 0.05)),
+                ..Default::default()
+            };
+            graph.update_hierarchical_data();
+            preset
+                .make_shapes(&graph)
+                .into_iter()
+                .filter(|shape| matches!(shape.kind, RagdollShapeKind::Cuboid { .. }))
+                .collect::<Vec<_>>()
+        };
+
+        // The character faces +Z, the bodies are aligned with the world axes.
+        let shapes = make_shapes(0.0);
+        assert_eq!(shapes.len(), 3);
+        assert!(shapes[0].rotation.angle() < 1.0e-5);
+
+        // The character is turned by 90 degrees, the bodies are turned with it.
+        let turn = UnitQuaternion::from_axis_angle(&Vector3::y_axis(), std::f32::consts::FRAC_PI_2);
+        let turned = make_shapes(std::f32::consts::FRAC_PI_2);
+        for (shape, turned) in shapes.iter().zip(turned.iter()) {
+            assert_eq!(shape.bone, turned.bone);
+            let basis = turned.rotation.to_rotation_matrix();
+            // Width goes from one side of the body to the other, height goes along the spine.
+            assert!((basis * Vector3::x() - Vector3::new(0.0, 0.0, -1.0)).norm() < 1.0e-5);
+            assert!(turned.rotation.angle_to(&(turn * shape.rotation)) < 1.0e-4);
+        }
+        // The upper spine body leans forward, along the bones.
+        let up = turned[2].rotation * Vector3::y();
+        assert!((up - (turn * Vector3::new(0.0, 0.3, 0.0)).normalize()).norm() < 1.0e-5);
+        let up = turned[1].rotation * Vector3::y();
+        assert!((up - (turn * Vector3::new(0.0, 0.2, 0.05)).normalize()).norm() < 1.0e-5);
+
+        assert!(torso_rotation(Vector3::y(), Vector3::y()).is_none());
+        assert!(torso_rotation(Vector3::default(), Vector3::x()).is_none());
+        let rotation = torso_rotation(Vector3::new(0.0, 2.0, 0.0), Vector3::new(1.0, 1.0, 0.0));
+        assert!(rotation.unwrap().angle() < 1.0e-5);
+    }
+
+    #[test]
+    fn test_shapes_scale_with_the_character() {
+        // Positions of the bones are given in meters and converted to the units of the character.
+        let make_shapes = |scale: Vector3<f32>, units: f32, arms: bool| {
+            let mut graph = Graph::new();
+            let root = make_bone(&mut graph, Vector3::default());
+            graph[root].local_transform_mut().set_scale(scale);
+            let mut bone = |position: Vector3<f32>| {
+                let bone = make_bone(&mut graph, position.scale(units));
+                graph.link_nodes(bone, root);
+                bone
+            };
+            let mut preset = RagdollPreset {
+                hips: bone(Vector3::new(0.0, 1.0, 0.0)),
+                left_up_leg: bone(Vector3::new(0.1, 0.95, 0.0)),
+                left_leg: bone(Vector3::new(0.1, 0.5, 0.0)),
+                right_up_leg: bone(Vector3::new(-0.1, 0.95, 0.0)),
+                spine: bone(Vector3::new(0.0, 1.2, 0.0)),
+                head: bone(Vector3::new(0.0, 1.6, 0.0)),
+                ..Default::default()
+            };
+            if arms {
+                preset.right_fore_arm = bone(Vector3::new(-0.5, 1.4, 0.0));
+                preset.right_hand = bone(Vector3::new(-0.75, 1.4, 0.0));
+            }
+            graph.update_hierarchical_data();
+            preset.make_shapes(&graph)
+        };
+        let sizes = |kind: &RagdollShapeKind| match *kind {
+            RagdollShapeKind::Sphere { radius } => Vector3::new(radius, 0.0, 0.0),
+            RagdollShapeKind::Capsule { length, radius } => Vector3::new(length, radius, 0.0),
+            RagdollShapeKind::Cuboid { half_extents } => half_extents,
+        };
+        let head_radius = |shapes: &[RagdollShape]| {
+            let head = shapes.iter().find(|s| s.bone == RagdollBone::Head).unwrap();
+            sizes(&head.kind).x
+        };
+
+        for arms in [true, false] {
+            let meters = make_shapes(Vector3::new(1.0, 1.0, 1.0), 1.0, arms);
+            let centimeters = make_shapes(Vector3::new(0.01, 0.01, 0.01), 100.0, arms);
+            assert_eq!(meters.len(), centimeters.len());
+            for (a, b) in meters.iter().zip(centimeters.iter()) {
+                assert_eq!(a.bone, b.bone);
+                assert!((a.position - b.position).norm() < 1.0e-4);
+                assert!(a.rotation.angle_to(&b.rotation) < 1.0e-3);
+                assert!((sizes(&a.kind) - sizes(&b.kind)).norm() < 1.0e-4);
+            }
+            // The right arm is used when the left one is not assigned, otherwise the base size is a
+            // part of the distance between the hips and the head.
+            let expected = if arms {
+                0.5 * 0.25
+            } else {
+                0.5 * 0.6 * BASE_SIZE_PER_HIPS_TO_HEAD
+            };
+            assert!((head_radius(&meters) - expected).abs() < 1.0e-4);
+            assert!((head_radius(&centimeters) - expected).abs() < 1.0e-4);
+        }
+
+        // Width of the torso follows the scale along its axis.
+        let stretched = make_shapes(Vector3::new(2.0, 1.0, 1.0), 1.0, true);
+        let hips = stretched
+            .iter()
+            .find(|s| s.bone == RagdollBone::Hips)
+            .unwrap();
+        assert!((sizes(&hips.kind) - Vector3::new(0.25, 0.05, 0.1)).norm() < 1.0e-5);
+    }
+
+    #[test]
+    fn test_excluded_hands_and_feet() {
+        let mut graph = Graph::new();
+        let mut preset = RagdollPreset {
+            hips: make_bone(&mut graph, Vector3::new(0.0, 1.0, 0.0)),
+            left_up_leg: make_bone(&mut graph, Vector3::new(0.1, 0.95, 0.0)),
+            left_leg: make_bone(&mut graph, Vector3::new(0.1, 0.5, 0.0)),
+            left_foot: make_bone(&mut graph, Vector3::new(0.1, 0.05, 0.0)),
+            left_fore_arm: make_bone(&mut graph, Vector3::new(0.5, 1.4, 0.0)),
+            left_hand: make_bone(&mut graph, Vector3::new(0.75, 1.4, 0.0)),
+            spine: make_bone(&mut graph, Vector3::new(0.0, 1.2, 0.0)),
+            head: make_bone(&mut graph, Vector3::new(0.0, 1.6, 0.0)),
+            ..Default::default()
+        };
+        graph.update_hierarchical_data();
+        let bones = |shapes: &[RagdollShape]| shapes.iter().map(|s| s.bone).collect::<Vec<_>>();
+
+        let shapes = preset.make_shapes(&graph);
+        assert!(bones(&shapes).contains(&RagdollBone::LeftHand));
+        assert!(bones(&shapes).contains(&RagdollBone::LeftFoot));
+
+        preset.generate_hands = false;
+        preset.generate_feet = false;
+        let excluded = preset.make_shapes(&graph);
+        assert_eq!(excluded.len(), shapes.len() - 2);
+        assert!(!bones(&excluded).contains(&RagdollBone::LeftHand));
+        assert!(!bones(&excluded).contains(&RagdollBone::LeftFoot));
+        // The limbs end at the forearm and the lower leg, they still span to the hand and the foot.
+        let fore_arm = |shapes: &[RagdollShape]| {
+            shapes
+                .iter()
+                .find(|s| s.bone == RagdollBone::LeftForeArm)
+                .unwrap()
+                .kind
+                .clone()
+        };
+        assert_eq!(fore_arm(&shapes), fore_arm(&excluded));
+        // The whole mass goes to the rest of the bodies.
+        let total = excluded.iter().map(|s| s.mass).sum::<f32>();
+        assert!((total - preset.total_mass).abs() < 1.0e-3);
+        let missing = preset.check_bones(&graph).missing;
+        assert!(!missing.contains(&RagdollBone::LeftFoot));
+        assert!(missing.contains(&RagdollBone::RightLeg));
+    }
+
+    #[test]
+    fn test_distribute_mass() {
+        let sum = |masses: &[f32]| masses.iter().sum::<f32>();
+
+        // Proportional split.
+        let masses = distribute_mass(&[1.0, 3.0], 20.0, 0.0);
+        assert!((masses[0] - 5.0).abs() < 1.0e-5);
+        assert!((masses[1] - 15.0).abs() < 1.0e-5);
+
+        // Tiny limbs get the minimum, the others share the rest.
+        let volumes = [0.0001, 0.0002, 1.0, 2.0, 5.0];
+        let masses = distribute_mass(&volumes, 20.0, 0.5);
+        assert!((sum(&masses) - 20.0).abs() < 1.0e-4);
+        assert_eq!(masses[0], 0.5);
+        assert_eq!(masses[1], 0.5);
+        assert!(masses.iter().all(|mass| *mass >= 0.5));
+        assert!((masses[4] / masses[2] - 5.0).abs() < 1.0e-4);
+
+        // Clamping of one limb pushes another one below the minimum.
+        let masses = distribute_mass(&[1.0, 4.0, 95.0], 10.0, 1.0);
+        assert!((sum(&masses) - 10.0).abs() < 1.0e-4);
+        assert_eq!(masses[0], 1.0);
+        assert_eq!(masses[1], 1.0);
+
+        // Not enough mass for the minimum, or nothing to measure.
+        assert_eq!(distribute_mass(&[1.0, 100.0], 1.0, 1.0), vec![0.5, 0.5]);
+        assert_eq!(distribute_mass(&[0.0, 0.0], 4.0, 0.0), vec![2.0, 2.0]);
+        assert!(distribute_mass(&[], 4.0, 0.0).is_empty());
+
+        // Every shape of a ragdoll gets its share.
+        let kinds = [
+            RagdollShapeKind::Sphere { radius: 0.1 },
+            RagdollShapeKind::Capsule {
+                length: 0.5,
+                radius: 0.1,
+            },
+            RagdollShapeKind::Cuboid {
+                half_extents: Vector3::new(0.2, 0.1, 0.15),
+            },
+        ];
+        let volumes = kinds.iter().map(|k| k.volume()).collect::<Vec<_>>();
+        assert!(volumes[1] > volumes[0]);
+        assert!((volumes[2] - 0.024).abs() < 1.0e-6);
+        assert!((sum(&distribute_mass(&volumes, 20.0, 0.5)) - 20.0).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn test_generated_names_are_unique() {
+        let mut graph = Graph::new();
+        let mut preset = RagdollPreset {
+            include_fingers_and_toes: true,
+            ..Default::default()
+        };
+        for (i, bone) in RagdollBone::ALL.iter().enumerate() {
+            let handle = make_bone(&mut graph, Vector3::new(0.0, i as f32 * 0.1, 0.0));
+            if bone.digit_parent().is_some() {
+                // Fingers and toes need the ends of their chains.
+                let end = make_bone(&mut graph, Vector3::new(0.05, 0.0, 0.0));
+                graph.link_nodes(end, handle);
+            }
+            *preset.bone_mut(*bone) = handle;
+        }
+        graph.update_hierarchical_data();
+
+        let shapes = preset.make_shapes(&graph);
+        assert_eq!(shapes.len(), RagdollBone::ALL.len());
+        let ragdoll = preset.build_ragdoll(&shapes, graph.get_root(), &mut graph);
+
+        let names = graph
+            .traverse_handle_iter(ragdoll)
+            .map(|handle| graph[handle].name_owned())
+            .collect::<Vec<_>>();
+        // Ragdoll itself, a body and a collider for every bone and a joint for every bone except
+        // the hips.
+        assert_eq!(names.len(), 1 + 3 * RagdollBone::ALL.len() - 1);
+        for (i, name) in names.iter().enumerate() {
+            assert!(!names[..i].contains(name), "{} is not unique", name);
+        }
+        assert!(names.contains(&"RagdollHead".to_owned()));
+        assert!(names.contains(&"RagdollBallJointRightUpLeg".to_owned()));
+    }
+
+    #[test]
+    fn test_fingers_and_toes() {
+        fn find_limb(limb: &Limb, bone: Handle<Node>) -> Option<&Limb> {
+            if limb.bone == bone {
+                Some(limb)
+            } else {
+                limb.children
+                    .iter()
+                    .find_map(|child| find_limb(child, bone))
+            }
+        }
+
+        let mut graph = Graph::new();
+        let left_hand = make_bone(&mut graph, Vector3::new(1.0, 1.0, 0.0));
+        let left_index = make_bone(&mut graph, Vector3::new(1.1, 1.0, 0.0));
+        // Positions of the bones of the chain are relative to their parents.
+        let left_index2 = make_bone(&mut graph, Vector3::new(0.05, 0.0, 0.0));
+        let left_index3 = make_bone(&mut graph, Vector3::new(0.05, 0.0, 0.0));
+        graph.link_nodes(left_index2, left_index);
+        graph.link_nodes(left_index3, left_index2);
+        let mut preset = RagdollPreset {
+            hips: make_bone(&mut graph, Vector3::default()),
+            left_hand,
+            left_index,
+            // The thumb has no chain, so it gets no body.
+            left_thumb: make_bone(&mut graph, Vector3::new(1.1, 1.1, 0.0)),
+            ..Default::default()
+        };
+        graph.update_hierarchical_data();
+
+        let bones = |preset: &RagdollPreset, graph: &Graph| {
+            preset
+                .make_shapes(graph)
+                .iter()
+                .map(|shape| shape.bone)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            bones(&preset, &graph),
+            vec![RagdollBone::Hips, RagdollBone::LeftHand]
+        );
+
+        preset.include_fingers_and_toes = true;
+        assert_eq!(
+            bones(&preset, &graph),
+            vec![
+                RagdollBone::Hips,
+                RagdollBone::LeftHand,
+                RagdollBone::LeftIndex
+            ]
+        );
+        // The capsule of the finger goes to the end of the chain.
+        let shapes = preset.make_shapes(&graph);
+        match shapes[2].kind {
+            RagdollShapeKind::Capsule { length, radius } => {
+                assert!((length + 2.0 * radius - 0.1).abs() < 1.0e-5)
+            }
+            ref kind => panic!("unexpected shape {:?}", kind),
+        }
+
+        let ragdoll = preset.build_ragdoll(&shapes, graph.get_root(), &mut graph);
+        let hips = graph[ragdoll].as_ragdoll().hips().clone();
+        let hand = find_limb(&hips, left_hand).unwrap();
+        assert_eq!(hand.children.len(), 1);
+        assert_eq!(hand.children[0].bone, left_index);
+        assert!(hand.children[0].physical_bone.is_some());
+        assert!(graph
+            .find_by_name(ragdoll, "RagdollBallJointLeftIndex")
+            .is_some());
+    }
+
+    #[test]
+    fn test_extra_chains() {
+        fn chain_names(limb: &Limb, graph: &Graph) -> Vec<String> {
+            let mut names = Vec::new();
+            let mut limb = limb;
+            while let Some(child) = limb.children.first() {
+                names.push(graph[child.physical_bone].name_owned());
+                limb = child;
+            }
+            names
+        }
+
+        let mut graph = Graph::new();
+        let hips = make_bone(&mut graph, Vector3::new(0.0, 1.0, 0.0));
+        // The tail goes back from the hips, every bone is a bit further.
+        let mut tail = Vec::new();
+        let mut parent = hips;
+        for _ in 0..4 {
+            let bone = make_bone(&mut graph, Vector3::new(0.0, 0.0, -0.2));
+            graph.link_nodes(bone, parent);
+            tail.push(bone);
+            parent = bone;
+        }
+        graph.update_hierarchical_data();
+
+        let chain = RagdollBoneChain {
+            root: tail[0],
+            end: tail[3],
+            ..Default::default()
+        };
+        assert_eq!(chain.bones(&graph), tail);
+        // Without the end the first children are followed, but not further than the last bone.
+        let counted = RagdollBoneChain {
+            end: Handle::NONE,
+            segment_count: 2,
+            ..chain.clone()
+        };
+        assert_eq!(counted.bones(&graph), tail[..3].to_vec());
+        let counted = RagdollBoneChain {
+            segment_count: 10,
+            ..counted
+        };
+        assert_eq!(counted.bones(&graph), tail);
+        // The end must be under the root.
+        let reversed = RagdollBoneChain {
+            root: tail[3],
+            end: tail[0],
+            ..chain.clone()
+        };
+        assert!(reversed.bones(&graph).is_empty());
+        assert!(RagdollBoneChain::default().bones(&graph).is_empty());
+
+        let mut preset = RagdollPreset {
+            hips,
+            extra_chains: vec![chain.clone()],
+            ..Default::default()
+        };
+        let shapes = preset.make_shapes(&graph);
+        assert_eq!(
+            shapes.iter().map(|s| s.name()).collect::<Vec<_>>(),
+            vec!["Hips", "Tail0", "Tail1", "Tail2"]
+        );
+        // Segments are attached to the hips and follow the bones of the chain.
+        for (shape, bone) in shapes[1..].iter().zip(tail.iter()) {
+            assert_eq!(shape.bone, RagdollBone::Hips);
+            assert_eq!(shape.chain.as_ref().unwrap().bone, *bone);
+            match shape.kind {
+                RagdollShapeKind::Capsule { length, radius } => {
+                    assert!((length + 2.0 * radius - 0.2).abs() < 1.0e-5);
+                    assert!((radius - 0.15 * 0.2).abs() < 1.0e-6);
+                }
+                ref kind => panic!("unexpected shape {:?}", kind),
+            }
+        }
+        // The chain gets its share of the total mass.
+        let total = shapes.iter().map(|s| s.mass).sum::<f32>();
+        assert!((total - preset.total_mass).abs() < 1.0e-3);
+        assert!(preset.check_chains(&graph, &shapes).is_ok());
+
+        let ragdoll = preset.build_ragdoll(&shapes, graph.get_root(), &mut graph);
+        let limbs = graph[ragdoll].as_ragdoll().hips().clone();
+        assert_eq!(limbs.bone, hips);
+        assert_eq!(
+            chain_names(&limbs, &graph),
+            vec!["RagdollTail0", "RagdollTail1", "RagdollTail2"]
+        );
+        assert_eq!(limbs.children[0].bone, tail[0]);
+        for name in ["Tail0", "Tail1", "Tail2"] {
+            let joint = graph
+                .find_by_name(ragdoll, &format!("RagdollBallJoint{}", name))
+                .map(|(handle, _)| handle)
+                .unwrap();
+            assert!(graph[joint].as_joint().body2().is_some());
+        }
+
+        // Bodies of the chains are bound to the bones by their names, so they must be unique.
+        preset.extra_chains.push(chain);
+        let shapes = preset.make_shapes(&graph);
+        assert_eq!(shapes.len(), 7);
+        assert!(preset.check_chains(&graph, &shapes).is_err());
+    }
+
+    #[test]
+    fn test_local_pose() {
+        let parent = Matrix4::new_translation(&Vector3::new(1.0, 2.0, 3.0))
+            * UnitQuaternion::from_axis_angle(&Vector3::y_axis(), 90.0f32.to_radians())
+                .to_homogeneous()
+            * Matrix4::new_scaling(0.5);
+        let rotation = UnitQuaternion::from_axis_angle(&Vector3::x_axis(), 0.3);
+        let (position, local_rotation) = local_pose(&parent, Vector3::new(1.0, 3.0, 3.0), rotation);
+        assert!((position - Vector3::new(0.0, 2.0, 0.0)).norm() < 1.0e-5);
+
+        let global = parent * Matrix4::new_translation(&position) * local_rotation.to_homogeneous();
+        let global_position = global.transform_point(&Point3::origin()).coords;
+        assert!((global_position - Vector3::new(1.0, 3.0, 3.0)).norm() < 1.0e-5);
+        let global_rotation =
+            UnitQuaternion::from_matrix_eps(&global.basis(), f32::EPSILON, 16, Default::default());
+        assert!(global_rotation.angle_to(&rotation) < 1.0e-4);
+    }
+
+    #[test]
+    fn test_hitboxes() {
+        let mut graph = Graph::new();
+        let hips = make_bone(&mut graph, Vector3::new(0.0, 1.0, 0.0));
+        let spine = make_bone(&mut graph, Vector3::new(0.0, 0.3, 0.0));
+        let head = make_bone(&mut graph, Vector3::new(0.0, 0.5, 0.0));
+        graph.link_nodes(spine, hips);
+        graph.link_nodes(head, spine);
+        graph.update_hierarchical_data();
+
+        let preset = RagdollPreset {
+            hips,
+            spine,
+            head,
+            output_mode: RagdollOutputMode::Hitboxes,
+            ..Default::default()
+        };
+        // Hitboxes don't need the legs.
+        let check = preset.check_bones(&graph);
+        assert!(check.missing.is_empty());
+        assert!(check.skipped.contains(&RagdollBone::LeftFoot));
+
+        let shapes = preset.make_shapes(&graph);
+        let hitboxes = preset.build_hitboxes(&shapes, &mut graph);
+        graph.update_hierarchical_data();
+        assert_eq!(hitboxes.len(), 3);
+        for (shape, &hitbox) in shapes.iter().zip(hitboxes.iter()) {
+            let hitbox_ref = &graph[hitbox];
+            assert_eq!(hitbox_ref.parent(), preset.bone(shape.bone));
+            assert_eq!(
+                hitbox_ref.tag(),
+                ragdoll_part_name("Hitbox", shape.bone.as_ref())
+            );
+            assert!((hitbox_ref.global_position() - shape.position).norm() < 1.0e-5);
+            let collider = graph
+                .try_get_of_type::<Collider>(hitbox_ref.children()[0])
+                .unwrap();
+            assert!(collider.is_sensor());
+        }
+        assert_eq!(graph[hitboxes[2]].name(), "HitboxHead");
+        // No joints and no ragdoll.
+        assert!(graph
+            .pair_iter()
+            .all(|(_, node)| node.cast::<Joint>().is_none() && node.cast::<Ragdoll>().is_none()));
+
+        assert_eq!(preset.find_hitboxes(&graph), hitboxes);
+        let other = RagdollPreset {
+            hitbox_prefix: "Other".to_owned(),
+            ..preset
+        };
+        assert!(other.find_hitboxes(&graph).is_empty());
+    }
+
+    #[test]
+    fn test_undo_generation() {
+        let mut scene = Scene::new();
+        let mut editor_scene = make_editor_scene(&mut scene);
+        let (_, bones) = make_character(&mut scene.graph, 0.0, "mixamorig:");
+        scene.graph.update_hierarchical_data();
+        let scene_root = editor_scene.scene_content_root;
+        let hips = bones[0].1;
+        let (sender, _receiver) = channel();
+        let sender = MessageSender(sender);
+
+        let mut preset = RagdollPreset::default();
+        for &(bone, handle) in bones.iter() {
+            *preset.bone_mut(bone) = handle;
+        }
+        let generate = |scene: &mut Scene, editor_scene: &EditorScene| {
+            let replace = find_ragdolls(&scene.graph, scene_root, hips);
+            let (roots, _) = preset
+                .generate_nodes(&mut scene.graph, scene_root, &replace)
+                .unwrap();
+            let group = make_generation_commands(
+                &mut scene.graph,
+                &roots,
+                &replace,
+                &editor_scene.selection,
+            );
+            (roots[0], CommandGroup::from(group))
+        };
+
+        // The bones of the character are selected, as it is done by the wizard.
+        let bones_selection = Selection::Graph(GraphSelection::from_list(
+            bones.iter().map(|(_, handle)| *handle).collect(),
+        ));
+        editor_scene.selection = bones_selection.clone();
+        let node_count = scene.graph.node_count();
+
+        let (ragdoll, mut group) = generate(&mut scene, &editor_scene);
+        // Generated nodes are taken until the command is executed.
+        assert_eq!(scene.graph.node_count(), node_count);
+        group.execute(&mut scene_context(&mut editor_scene, &mut scene, &sender));
+        assert_eq!(
+            editor_scene.selection,
+            Selection::Graph(GraphSelection::from_list(vec![ragdoll]))
+        );
+        assert!(scene
+            .graph
+            .traverse_iter(ragdoll)
+            .any(|node| node.is_joint()));
+
+        // Joints are linked to the ragdoll, so they are removed with the bodies.
+        group.revert(&mut scene_context(&mut editor_scene, &mut scene, &sender));
+        assert_eq!(scene.graph.node_count(), node_count);
+        assert!(scene.graph.linear_iter().all(|node| !node.is_joint()));
+        assert_eq!(editor_scene.selection, bones_selection);
+
+        // Replaced ragdoll is selected again, when the replacement is undone.
+        group.execute(&mut scene_context(&mut editor_scene, &mut scene, &sender));
+        let ragdoll_selection = editor_scene.selection.clone();
+        let node_count = scene.graph.node_count();
+        let (replacement, mut replace_group) = generate(&mut scene, &editor_scene);
+        replace_group.execute(&mut scene_context(&mut editor_scene, &mut scene, &sender));
+        assert!(!scene.graph.is_valid_handle(ragdoll));
+        assert_eq!(
+            find_ragdolls(&scene.graph, scene_root, hips),
+            vec![replacement]
+        );
+
+        replace_group.revert(&mut scene_context(&mut editor_scene, &mut scene, &sender));
+        assert_eq!(scene.graph.node_count(), node_count);
+        assert_eq!(find_ragdolls(&scene.graph, scene_root, hips), vec![ragdoll]);
+        assert_eq!(editor_scene.selection, ragdoll_selection);
+    }
+
+    #[test]
+    fn test_prefab_rebinds_limbs_by_names() {
+        fn collect_limbs(limb: &Limb, limbs: &mut Vec<Limb>) {
+            limbs.push(limb.clone());
+            for child in limb.children.iter() {
+                collect_limbs(child, limbs);
+            }
+        }
+
+        let mut graph = Graph::new();
+        let (_, first_bones) = make_character(&mut graph, 0.0, "mixamorig:");
+        let (second_root, second_bones) = make_character(&mut graph, 2.0, "mixamorig:");
+        let (other_root, _) = make_character(&mut graph, 4.0, "Bip01_");
+        graph.update_hierarchical_data();
+
+        let mut preset = RagdollPreset::default();
+        for &(bone, handle) in first_bones.iter() {
+            *preset.bone_mut(bone) = handle;
+        }
+        let node_count = graph.node_count();
+        let prefab = preset.make_prefab(&mut graph).unwrap();
+        // The ragdoll goes only to the prefab.
+        assert_eq!(graph.node_count(), node_count);
+
+        let (prefab_ragdoll, _) = prefab
+            .graph
+            .pair_iter()
+            .find(|(_, node)| node.is_ragdoll())
+            .unwrap();
+        let bindings = prefab.graph[prefab_ragdoll].properties.to_vec();
+        assert_eq!(bindings.len(), first_bones.len());
+        assert!(bindings.contains(&Property {
+            name: "RagdollBone:RagdollLeftFoot".to_owned(),
+            value: PropertyValue::String("mixamorig:LeftFoot".to_owned()),
+        }));
+        let mut limbs = Vec::new();
+        collect_limbs(prefab.graph[prefab_ragdoll].as_ragdoll().hips(), &mut limbs);
+        assert!(limbs.iter().all(|limb| limb.bone.is_none()));
+
+        // Instantiate the prefab onto the second character.
+        let (instance, _) =
+            prefab
+                .graph
+                .copy_node(prefab.graph.get_root(), &mut graph, &mut |_, _| true);
+        let ragdoll = graph
+            .traverse_handle_iter(instance)
+            .find(|&handle| graph[handle].is_ragdoll())
+            .unwrap();
+        assert!(rebind_limbs(&mut graph, ragdoll, second_root).is_empty());
+
+        let mut limbs = Vec::new();
+        collect_limbs(graph[ragdoll].as_ragdoll().hips(), &mut limbs);
+        assert_eq!(limbs.len(), second_bones.len());
+        for limb in limbs.iter() {
+            let (bone, _) = second_bones
+                .iter()
+                .find(|(_, handle)| *handle == limb.bone)
+                .unwrap();
+            assert_eq!(
+                graph[limb.physical_bone].name(),
+                ragdoll_part_name("Ragdoll", bone.as_ref())
+            );
+        }
+        assert_eq!(
+            find_ragdolls(&graph, graph.get_root(), second_bones[0].1),
+            vec![ragdoll]
+        );
+
+        // Bones with other names are not bound.
+        let unresolved = rebind_limbs(&mut graph, ragdoll, other_root);
+        assert_eq!(unresolved.len(), second_bones.len());
+        assert!(unresolved.contains(&"RagdollHead (mixamorig:Head)".to_owned()));
+
+        let hitboxes = RagdollPreset {
+            output_mode: RagdollOutputMode::Hitboxes,
+            ..preset
+        };
+        assert!(hitboxes.make_prefab(&mut graph).is_err());
+    }
+
+    #[test]
+    fn test_skipped_bones_are_bridged() {
+        use RagdollBone::*;
+
+        let bodies = [Hips, LeftUpLeg, Spine, LeftShoulder, LeftHand, Head];
+        // Spine1 and Spine2 are skipped.
+        assert_eq!(body_parent(LeftShoulder, &bodies), Some(Spine));
+        assert_eq!(body_parent(Head, &bodies), Some(Spine));
+        // The arm and the forearm are skipped.
+        assert_eq!(body_parent(LeftHand, &bodies), Some(LeftShoulder));
+        assert_eq!(body_parent(LeftUpLeg, &bodies), Some(Hips));
+        assert_eq!(body_parent(Hips, &bodies), None);
+        // Bones without bodies are also bridged.
+        assert_eq!(body_parent(LeftIndex, &bodies), Some(LeftHand));
+        assert_eq!(body_parent(RightHand, &bodies), Some(Spine));
+        assert_eq!(body_parent(Spine, &[]), None);
+
+        let handle = |index: u32| Handle::<Node>::new(index, 1);
+        let preset = RagdollPreset {
+            hips: handle(1),
+            spine: handle(2),
+            // Assigned, but has no body.
+            spine1: handle(3),
+            left_shoulder: handle(4),
+            head: handle(5),
+            ..Default::default()
+        };
+        let bodies = [
+            (Hips, handle(101)),
+            (Spine, handle(102)),
+            (LeftShoulder, handle(104)),
+            (Head, handle(105)),
+        ];
+        let hips = preset.make_limb(Hips, &bodies);
+        assert_eq!(hips.bone, handle(1));
+        assert_eq!(hips.physical_bone, handle(101));
+        assert_eq!(hips.children.len(), 1);
+        let spine = &hips.children[0];
+        assert_eq!(spine.bone, handle(2));
+        assert_eq!(spine.physical_bone, handle(102));
+        assert_eq!(
+            spine
+                .children
+                .iter()
+                .map(|limb| (limb.bone, limb.physical_bone, limb.children.len()))
+                .collect::<Vec<_>>(),
+            vec![(handle(4), handle(104), 0), (handle(5), handle(105), 0)]
+        );
+    }
+}
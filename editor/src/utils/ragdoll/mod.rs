@@ -0,0 +1,2106 @@
+use crate::{
+    inspector::editors::{
+        handle::{HandlePropertyEditorMessage, NodeHandlePropertyEditorDefinition},
+        make_property_editors_container,
+    },
+    interaction::InteractionModeKind,
+    message::{Message, MessageSender},
+    scene::{
+        commands::{graph::AddModelCommand, ChangeSelectionCommand, CommandGroup, SceneCommand},
+        EditorScene, Selection,
+    },
+    send_sync_message,
+    settings::{
+        ragdoll::{RagdollBone, RagdollBonePatterns},
+        Settings,
+    },
+    utils::{
+        ragdoll::{
+            bones::{collect_bone_candidates, parse_mirror_tokens},
+            generator::{find_ragdolls, make_generation_commands, mass_breakdown, rebind_limbs},
+            preset::{RagdollOutputMode, RagdollPreset, RagdollPresetFile},
+            validation::{
+                clamp_invalid_numbers, find_invalid_numbers, invalid_number_list, BoneCoverage,
+                RagdollOverlap,
+            },
+            windows::{
+                make_file_selector, AutofillReport, AutofillReportAction, RagdollPreflightWindow,
+                RagdollReplaceWindow, RagdollValidationWindow,
+            },
+        },
+        ragdoll_command::{
+            make_set_ragdoll_preset_property_command, RagdollPresetCommandStack,
+            RagdollPresetContext,
+        },
+        ragdoll_simulation::RagdollSimulation,
+    },
+    world::graph::selection::GraphSelection,
+    FIXED_TIMESTEP, MSG_SYNC_FLAG,
+};
+use fyrox::{
+    asset::manager::ResourceManager,
+    core::{
+        color::Color, futures::executor::block_on, log::Log, make_relative_path, pool::Handle,
+        reflect::prelude::*, visitor::Visitor,
+    },
+    engine::Engine,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        expander::{ExpanderBuilder, ExpanderMessage},
+        file_browser::{FileBrowserMode, FileSelectorMessage},
+        grid::{Column, GridBuilder, Row},
+        inspector::{InspectorBuilder, InspectorContext, InspectorMessage, PropertyAction},
+        message::{MessageDirection, UiMessage},
+        numeric::{NumericUpDownBuilder, NumericUpDownMessage},
+        progress_bar::{ProgressBarBuilder, ProgressBarMessage},
+        scroll_bar::{ScrollBarBuilder, ScrollBarMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::TextBuilder,
+        utils::make_simple_tooltip,
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    resource::model::{Model, ModelResourceExtension},
+    scene::{graph::Graph, node::Node, Scene},
+};
+use ron::ser::PrettyConfig;
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+pub mod bones;
+pub mod generator;
+pub mod preset;
+pub mod validation;
+pub mod windows;
+
+/// Returns the root of the character, that the wizard works with. It is the first selected node,
+/// if there's any.
+fn selected_character_root(editor_scene: &EditorScene) -> Option<Handle<Node>> {
+    match &editor_scene.selection {
+        Selection::Graph(selection) => selection.nodes().first().cloned(),
+        _ => None,
+    }
+}
+
+/// Returns the selected ragdoll, that could be tested.
+fn selected_ragdoll(graph: &Graph, editor_scene: &EditorScene) -> Option<Handle<Node>> {
+    selected_character_root(editor_scene)
+        .filter(|&node| graph.try_get(node).map_or(false, |node| node.is_ragdoll()))
+}
+
+/// Returns the root of the character, or the root of the scene if nothing is selected.
+fn character_root(editor_scene: &EditorScene) -> Handle<Node> {
+    selected_character_root(editor_scene).unwrap_or(editor_scene.scene_content_root)
+}
+
+/// Returns the name of the field of the preset, that stores the bone (`LeftUpLeg` is stored in
+/// `left_up_leg`).
+fn bone_field_name(bone: RagdollBone) -> String {
+    let mut name = String::new();
+    for (i, c) in bone.as_ref().chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            name.push('_');
+        }
+        name.extend(c.to_lowercase());
+    }
+    name
+}
+
+/// Returns the names of the node and its ancestors separated by `/`, the root of the graph is
+/// not included.
+fn node_path(graph: &Graph, node: Handle<Node>) -> String {
+    let mut names = Vec::new();
+    let mut current = node;
+    while let Some(node_ref) = graph.try_get(current) {
+        if node_ref.parent().is_none() {
+            break;
+        }
+        names.push(node_ref.name());
+        current = node_ref.parent();
+    }
+    names.reverse();
+    names.join("/")
+}
+
+fn bone_list(bones: &[RagdollBone]) -> String {
+    bones
+        .iter()
+        .map(|bone| bone.as_ref())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn sync_inspector(ui: &mut UserInterface, inspector: Handle<UiNode>, object: &dyn Reflect) {
+    let ctx = ui
+        .node(inspector)
+        .cast::<fyrox::gui::inspector::Inspector>()
+        .unwrap()
+        .context()
+        .clone();
+
+    if let Err(sync_errors) = ctx.sync(object, ui, 0, true, Default::default()) {
+        for error in sync_errors {
+            Log::err(format!("Failed to sync property. Reason: {:?}", error))
+        }
+    }
+}
+
+/// Bone field of the preset, that waits for a node to be picked in the viewport.
+struct NodePick {
+    editor: Handle<UiNode>,
+    // The node, that was selected before the pick, selecting it again is not a pick.
+    initial_selection: Option<Handle<Node>>,
+    previous_mode: Option<InteractionModeKind>,
+}
+
+/// Generation of the ragdolls (or the hitboxes) for several selected characters. The bones of the
+/// preset are resolved by their names under the root of every character, one character is
+/// processed per frame. Generated nodes stay in the graph until every character is processed, then
+/// all of them are added by one command, so one undo removes every generated ragdoll.
+pub struct RagdollBatch {
+    scene: Handle<Scene>,
+    file: RagdollPresetFile,
+    replace_existing: bool,
+    characters: Vec<Handle<Node>>,
+    next: usize,
+    // Hips of the processed characters, a character nested in another one has the same bones.
+    used_hips: Vec<Handle<Node>>,
+    generated: Vec<Handle<Node>>,
+    replace: Vec<Handle<Node>>,
+    overlaps: Vec<RagdollOverlap>,
+}
+
+impl RagdollBatch {
+    /// Prepares the generation for the characters with the given roots. The bones of the preset
+    /// are stored by their paths under their closest common ancestor, so the characters could have
+    /// a slightly different hierarchy, bones are also found by their names.
+    pub fn new(
+        scene: Handle<Scene>,
+        graph: &Graph,
+        preset: &RagdollPreset,
+        characters: Vec<Handle<Node>>,
+        replace_existing: bool,
+    ) -> Result<Self, String> {
+        let candidates = collect_bone_candidates(graph, preset.default_parent(graph));
+        let file = preset.to_file(&candidates);
+        if file.bones.is_empty() {
+            return Err("assign the bones of one of the characters first.".to_owned());
+        }
+        Ok(Self {
+            scene,
+            file,
+            replace_existing,
+            characters,
+            next: 0,
+            used_hips: Vec::new(),
+            generated: Vec::new(),
+            replace: Vec::new(),
+            overlaps: Vec::new(),
+        })
+    }
+
+    /// Returns the part of the characters, that were processed, in `[0; 1]` range.
+    pub fn progress(&self) -> f32 {
+        self.next as f32 / self.characters.len().max(1) as f32
+    }
+
+    /// Returns the number of generated ragdolls (or hitbox sets).
+    pub fn generated_count(&self) -> usize {
+        self.used_hips.len()
+    }
+
+    /// Generates the ragdoll for the next character, the character is skipped with a message in
+    /// the log, if it can't be done. Returns `false`, if every character is processed.
+    pub fn step(&mut self, graph: &mut Graph, scene_root: Handle<Node>) -> bool {
+        let character = match self.characters.get(self.next) {
+            Some(character) => *character,
+            None => return false,
+        };
+        self.next += 1;
+
+        let name = match graph.try_get(character) {
+            Some(character_ref) => character_ref.name_owned(),
+            // Deleted while the previous characters were processed.
+            None => return true,
+        };
+        if let Err(reason) = self.generate_for(graph, character, scene_root) {
+            Log::warn(format!("Ragdoll Wizard: {} was skipped, {}", name, reason));
+        }
+        true
+    }
+
+    fn generate_for(
+        &mut self,
+        graph: &mut Graph,
+        character: Handle<Node>,
+        scene_root: Handle<Node>,
+    ) -> Result<(), String> {
+        let candidates = collect_bone_candidates(graph, character);
+        let mut preset = RagdollPreset::default();
+        let unresolved = preset.apply_file(&self.file, &candidates);
+
+        if preset.hips.is_some() && self.used_hips.contains(&preset.hips) {
+            return Err("its bones belong to another selected character.".to_owned());
+        }
+        let missing = preset.check_bones(graph).missing;
+        if !missing.is_empty() {
+            return Err(format!(
+                "these bones were not found under it: {}.",
+                bone_list(&missing)
+            ));
+        }
+
+        let replace = if !self.replace_existing {
+            Vec::new()
+        } else if preset.output_mode == RagdollOutputMode::Hitboxes {
+            preset.find_hitboxes(graph)
+        } else {
+            find_ragdolls(graph, scene_root, preset.hips)
+        };
+        let (roots, overlaps) = preset.generate_nodes(graph, scene_root, &replace)?;
+        if !unresolved.is_empty() {
+            Log::warn(format!(
+                "Ragdoll Wizard: these bones were not found under {}, they got no bodies: {}.",
+                graph[character].name(),
+                unresolved.join(", ")
+            ));
+        }
+
+        self.used_hips.push(preset.hips);
+        self.generated.extend(roots);
+        self.replace.extend(replace);
+        self.overlaps.extend(overlaps);
+        Ok(())
+    }
+
+    /// Returns the overlaps of the bodies of the generated ragdolls. The names of the bodies are
+    /// available only until the command takes them, see [`Self::finish`].
+    pub fn overlaps(&self) -> &[RagdollOverlap] {
+        &self.overlaps
+    }
+
+    /// Makes the command, that adds every generated node to the scene, it is `None` if nothing was
+    /// generated.
+    pub fn finish(self, graph: &mut Graph, selection: &Selection) -> Option<CommandGroup> {
+        if self.generated.is_empty() {
+            return None;
+        }
+        let name = match self.file.output_mode {
+            RagdollOutputMode::Ragdoll => "Generate Ragdolls for Selection",
+            RagdollOutputMode::Hitboxes => "Generate Hitboxes for Selection",
+        };
+        let group = make_generation_commands(graph, &self.generated, &self.replace, selection);
+        Some(CommandGroup::from(group).with_custom_name(name))
+    }
+
+    /// Removes the generated nodes, when the generation is interrupted.
+    pub fn discard(self, graph: &mut Graph) {
+        for root in self.generated {
+            if graph.is_valid_handle(root) {
+                graph.remove_node(root);
+            }
+        }
+    }
+}
+
+/// Default duration of the test simulation in seconds.
+const DEFAULT_TEST_DURATION: f32 = 3.0;
+
+pub struct RagdollWizard {
+    pub window: Handle<UiNode>,
+    pub preset: RagdollPreset,
+    inspector: Handle<UiNode>,
+    ok: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    undo: Handle<UiNode>,
+    redo: Handle<UiNode>,
+    // Edits of the preset in the inspector, other changes (Autofill, loading and so on) clear it.
+    command_stack: RagdollPresetCommandStack,
+    autofill: Handle<UiNode>,
+    // Name patterns of the bones for Autofill, they're stored in the editor settings.
+    bone_patterns: RagdollBonePatterns,
+    patterns_inspector: Handle<UiNode>,
+    mixamo_patterns: Handle<UiNode>,
+    unreal_patterns: Handle<UiNode>,
+    save_preset: Handle<UiNode>,
+    load_preset: Handle<UiNode>,
+    reset_preset: Handle<UiNode>,
+    clear_bones: Handle<UiNode>,
+    // The preset, that was saved or loaded last time, the title of the window shows whether the
+    // current preset differs from it.
+    saved_preset: RagdollPreset,
+    save_preset_selector: Handle<UiNode>,
+    load_preset_selector: Handle<UiNode>,
+    export_prefab: Handle<UiNode>,
+    instantiate_prefab: Handle<UiNode>,
+    generate_for_selection: Handle<UiNode>,
+    // The button is enabled only when several nodes are selected and no generation is running.
+    generate_for_selection_enabled: bool,
+    generation_progress: Handle<UiNode>,
+    batch: Option<RagdollBatch>,
+    export_prefab_selector: Handle<UiNode>,
+    instantiate_prefab_selector: Handle<UiNode>,
+    test: Handle<UiNode>,
+    stop_test: Handle<UiNode>,
+    test_duration: Handle<UiNode>,
+    test_frame: Handle<UiNode>,
+    test_duration_secs: f32,
+    // Test button is enabled only when a ragdoll is selected.
+    test_enabled: bool,
+    simulation: Option<RagdollSimulation>,
+    simulation_frame: usize,
+    simulation_playing: bool,
+    preview: Handle<UiNode>,
+    show_preview: bool,
+    coverage: Handle<UiNode>,
+    show_coverage: bool,
+    mirror_left_to_right: Handle<UiNode>,
+    mirror_right_to_left: Handle<UiNode>,
+    replace_existing: Handle<UiNode>,
+    replace_existing_ragdoll: bool,
+    replace_window: RagdollReplaceWindow,
+    autofill_report: AutofillReport,
+    preflight: RagdollPreflightWindow,
+    validation: RagdollValidationWindow,
+    pick: Option<NodePick>,
+    sender: MessageSender,
+}
+
+impl RagdollWizard {
+    pub fn new(ctx: &mut BuildContext, sender: MessageSender, settings: &Settings) -> Self {
+        let mut preset = RagdollPreset::default();
+        if let Some(file) = settings.ragdoll.last_preset.as_ref() {
+            preset.apply_file(file, &[]);
+        }
+        let bone_patterns = settings.ragdoll.bone_patterns.clone();
+        let container = Rc::new(make_property_editors_container(sender.clone()));
+        // Bones could also be picked in the viewport, not only dragged from the World Viewer.
+        container.insert(NodeHandlePropertyEditorDefinition::new(sender.clone()).with_pick());
+
+        let inspector;
+        let ok;
+        let cancel;
+        let undo;
+        let redo;
+        let autofill;
+        let patterns_inspector;
+        let mixamo_patterns;
+        let unreal_patterns;
+        let save_preset;
+        let load_preset;
+        let reset_preset;
+        let clear_bones;
+        let export_prefab;
+        let instantiate_prefab;
+        let generate_for_selection;
+        let generation_progress;
+        let test;
+        let stop_test;
+        let test_duration;
+        let test_frame;
+        let preview;
+        let coverage;
+        let mirror_left_to_right;
+        let mirror_right_to_left;
+        let replace_existing;
+        let autofill_report = AutofillReport::new(ctx, 2);
+        let window = WindowBuilder::new(
+            WidgetBuilder::new()
+                .with_width(480.0)
+                .with_height(660.0)
+                .with_name("RagdollWizard"),
+        )
+        .open(false)
+        .with_title(WindowTitle::text("Ragdoll Wizard"))
+        .with_content(
+            GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child({
+                        inspector = InspectorBuilder::new(
+                            WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_context(InspectorContext::from_object(
+                            &preset,
+                            ctx,
+                            container.clone(),
+                            None,
+                            MSG_SYNC_FLAG,
+                            0,
+                            true,
+                            Default::default(),
+                        ))
+                        .build(ctx);
+                        inspector
+                    })
+                    .with_child(
+                        ExpanderBuilder::new(WidgetBuilder::new().on_row(1))
+                            .with_expanded(false)
+                            .with_header(
+                                TextBuilder::new(
+                                    WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                                )
+                                .with_text("Bone Name Patterns")
+                                .build(ctx),
+                            )
+                            .with_content(
+                                GridBuilder::new(
+                                    WidgetBuilder::new()
+                                        .with_child(
+                                            StackPanelBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .with_margin(Thickness::uniform(1.0))
+                                                    .with_child(
+                                                        TextBuilder::new(
+                                                            WidgetBuilder::new().with_margin(
+                                                                Thickness::uniform(1.0),
+                                                            ),
+                                                        )
+                                                        .with_vertical_text_alignment(
+                                                            VerticalAlignment::Center,
+                                                        )
+                                                        .with_text("Presets:")
+                                                        .build(ctx),
+                                                    )
+                                                    .with_child({
+                                                        mixamo_patterns = ButtonBuilder::new(
+                                                            WidgetBuilder::new()
+                                                                .with_width(80.0)
+                                                                .with_margin(Thickness::uniform(
+                                                                    1.0,
+                                                                )),
+                                                        )
+                                                        .with_text("Mixamo")
+                                                        .build(ctx);
+                                                        mixamo_patterns
+                                                    })
+                                                    .with_child({
+                                                        unreal_patterns = ButtonBuilder::new(
+                                                            WidgetBuilder::new()
+                                                                .with_width(80.0)
+                                                                .with_margin(Thickness::uniform(
+                                                                    1.0,
+                                                                )),
+                                                        )
+                                                        .with_text("Unreal")
+                                                        .build(ctx);
+                                                        unreal_patterns
+                                                    }),
+                                            )
+                                            .with_orientation(Orientation::Horizontal)
+                                            .build(ctx),
+                                        )
+                                        .with_child(
+                                            ScrollViewerBuilder::new(
+                                                WidgetBuilder::new()
+                                                    .on_row(1)
+                                                    .with_margin(Thickness::uniform(1.0)),
+                                            )
+                                            .with_content({
+                                                patterns_inspector =
+                                                    InspectorBuilder::new(WidgetBuilder::new())
+                                                        .with_context(
+                                                            InspectorContext::from_object(
+                                                                &bone_patterns,
+                                                                ctx,
+                                                                container,
+                                                                None,
+                                                                MSG_SYNC_FLAG,
+                                                                0,
+                                                                true,
+                                                                Default::default(),
+                                                            ),
+                                                        )
+                                                        .build(ctx);
+                                                patterns_inspector
+                                            })
+                                            .build(ctx),
+                                        ),
+                                )
+                                .add_row(Row::strict(26.0))
+                                .add_row(Row::strict(200.0))
+                                .add_column(Column::stretch())
+                                .build(ctx),
+                            )
+                            .build(ctx),
+                    )
+                    .with_child(autofill_report.expander)
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(3)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child({
+                                    save_preset = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Save Preset...")
+                                    .build(ctx);
+                                    save_preset
+                                })
+                                .with_child({
+                                    load_preset = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Load Preset...")
+                                    .build(ctx);
+                                    load_preset
+                                })
+                                .with_child({
+                                    reset_preset = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Reset the settings of the preset to their \
+                                                default values, assigned bones and extra chains \
+                                                are kept.",
+                                            )),
+                                    )
+                                    .with_text("Reset to Defaults")
+                                    .build(ctx);
+                                    reset_preset
+                                })
+                                .with_child({
+                                    preview = CheckBoxBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Draw the bodies, that will be generated, \
+                                                without creating them.",
+                                            )),
+                                    )
+                                    .with_content(
+                                        TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_vertical_alignment(VerticalAlignment::Center),
+                                        )
+                                        .with_text("Preview")
+                                        .build(ctx),
+                                    )
+                                    .checked(Some(false))
+                                    .build(ctx);
+                                    preview
+                                })
+                                .with_child({
+                                    coverage = CheckBoxBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Draw the bones of the selected ragdoll. Bones, \
+                                                that are driven by the bodies, are green, skinned \
+                                                bones without a body are orange.",
+                                            )),
+                                    )
+                                    .with_content(
+                                        TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_vertical_alignment(VerticalAlignment::Center),
+                                        )
+                                        .with_text("Coverage")
+                                        .build(ctx),
+                                    )
+                                    .checked(Some(false))
+                                    .build(ctx);
+                                    coverage
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(4)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child({
+                                    mirror_left_to_right = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Assign the bones of the right side by the \
+                                                bones of the left side.",
+                                            )),
+                                    )
+                                    .with_text("Mirror L->R")
+                                    .build(ctx);
+                                    mirror_left_to_right
+                                })
+                                .with_child({
+                                    mirror_right_to_left = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Assign the bones of the left side by the \
+                                                bones of the right side.",
+                                            )),
+                                    )
+                                    .with_text("Mirror R->L")
+                                    .build(ctx);
+                                    mirror_right_to_left
+                                })
+                                .with_child({
+                                    clear_bones = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Unassign every bone, the parent and the bones \
+                                                of the extra chains, the settings are kept.",
+                                            )),
+                                    )
+                                    .with_text("Clear Bones")
+                                    .build(ctx);
+                                    clear_bones
+                                })
+                                .with_child({
+                                    replace_existing = CheckBoxBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Delete the ragdoll, that was generated for the \
+                                                same hips before, when a new one is generated. \
+                                                In Hitboxes mode the hitboxes with the same \
+                                                prefix are deleted.",
+                                            )),
+                                    )
+                                    .with_content(
+                                        TextBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_vertical_alignment(VerticalAlignment::Center),
+                                        )
+                                        .with_text("Replace Existing")
+                                        .build(ctx),
+                                    )
+                                    .checked(Some(false))
+                                    .build(ctx);
+                                    replace_existing
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(5)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child({
+                                    export_prefab = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(130.0)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Save the ragdoll as a prefab instead of adding \
+                                                it to the scene. The limbs are bound to the \
+                                                bones by names, so the prefab could be used \
+                                                with any character with the same bone names.",
+                                            )),
+                                    )
+                                    .with_text("Export as Prefab...")
+                                    .build(ctx);
+                                    export_prefab
+                                })
+                                .with_child({
+                                    instantiate_prefab = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(130.0)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Add a ragdoll prefab to the scene and bind its \
+                                                limbs to the bones of the selected character \
+                                                by names.",
+                                            )),
+                                    )
+                                    .with_text("Instantiate Prefab...")
+                                    .build(ctx);
+                                    instantiate_prefab
+                                })
+                                .with_child({
+                                    generate_for_selection = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(150.0)
+                                            .with_enabled(false)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Generate a ragdoll for every selected \
+                                                character. The bones are found by the names of \
+                                                the assigned bones under every selected node, \
+                                                characters without the required bones are \
+                                                skipped. One undo removes every ragdoll.",
+                                            )),
+                                    )
+                                    .with_text("Generate for Selection")
+                                    .build(ctx);
+                                    generate_for_selection
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    )
+                    .with_child(
+                        GridBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(6)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child({
+                                    test = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_enabled(false)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Simulate the selected ragdoll falling on the \
+                                                ground and play the result in the viewport. \
+                                                The scene is not changed.",
+                                            )),
+                                    )
+                                    .with_text("Test")
+                                    .build(ctx);
+                                    test
+                                })
+                                .with_child({
+                                    test_duration = NumericUpDownBuilder::<f32>::new(
+                                        WidgetBuilder::new()
+                                            .on_column(1)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Duration of the test simulation in seconds.",
+                                            )),
+                                    )
+                                    .with_min_value(0.1)
+                                    .with_value(DEFAULT_TEST_DURATION)
+                                    .build(ctx);
+                                    test_duration
+                                })
+                                .with_child({
+                                    test_frame = ScrollBarBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(2)
+                                            .with_enabled(false)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Pose at the frame of the test simulation, \
+                                                dragging it pauses the playback.",
+                                            )),
+                                    )
+                                    .with_min(0.0)
+                                    .with_max(1.0)
+                                    .with_step(1.0)
+                                    .build(ctx);
+                                    test_frame
+                                })
+                                .with_child({
+                                    stop_test = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .on_column(3)
+                                            .with_enabled(false)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Stop")
+                                    .build(ctx);
+                                    stop_test
+                                }),
+                        )
+                        .add_row(Row::stretch())
+                        .add_column(Column::strict(60.0))
+                        .add_column(Column::strict(60.0))
+                        .add_column(Column::stretch())
+                        .add_column(Column::strict(60.0))
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .with_horizontal_alignment(HorizontalAlignment::Left)
+                                .on_row(7)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child({
+                                    undo = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(45.0)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Undo the last change of the preset.",
+                                            )),
+                                    )
+                                    .with_text("Undo")
+                                    .build(ctx);
+                                    undo
+                                })
+                                .with_child({
+                                    redo = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(45.0)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_tooltip(make_simple_tooltip(
+                                                ctx,
+                                                "Redo the last undone change of the preset.",
+                                            )),
+                                    )
+                                    .with_text("Redo")
+                                    .build(ctx);
+                                    redo
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    )
+                    .with_child(
+                        StackPanelBuilder::new(
+                            WidgetBuilder::new()
+                                .with_horizontal_alignment(HorizontalAlignment::Right)
+                                .on_row(7)
+                                .with_margin(Thickness::uniform(1.0))
+                                .with_child({
+                                    autofill = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Autofill")
+                                    .build(ctx);
+                                    autofill
+                                })
+                                .with_child({
+                                    ok = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0))
+                                            .with_enabled(find_invalid_numbers(&preset).is_empty()),
+                                    )
+                                    .with_text("OK")
+                                    .build(ctx);
+                                    ok
+                                })
+                                .with_child({
+                                    cancel = ButtonBuilder::new(
+                                        WidgetBuilder::new()
+                                            .with_width(100.0)
+                                            .with_margin(Thickness::uniform(1.0)),
+                                    )
+                                    .with_text("Cancel")
+                                    .build(ctx);
+                                    cancel
+                                }),
+                        )
+                        .with_orientation(Orientation::Horizontal)
+                        .build(ctx),
+                    )
+                    .with_child({
+                        generation_progress = ProgressBarBuilder::new(
+                            WidgetBuilder::new()
+                                .on_row(8)
+                                .with_height(20.0)
+                                .with_visibility(false)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .build(ctx);
+                        generation_progress
+                    }),
+            )
+            .add_row(Row::stretch())
+            .add_row(Row::auto())
+            .add_row(Row::auto())
+            .add_row(Row::strict(24.0))
+            .add_row(Row::strict(24.0))
+            .add_row(Row::strict(24.0))
+            .add_row(Row::strict(24.0))
+            .add_row(Row::strict(24.0))
+            .add_row(Row::auto())
+            .add_column(Column::stretch())
+            .build(ctx),
+        )
+        .with_default_button(ok)
+        .with_cancel_button(cancel)
+        .build(ctx);
+
+        let saved_preset = preset.clone();
+        Self {
+            window,
+            preset,
+            inspector,
+            ok,
+            cancel,
+            undo,
+            redo,
+            command_stack: RagdollPresetCommandStack::new(false),
+            autofill,
+            bone_patterns,
+            patterns_inspector,
+            mixamo_patterns,
+            unreal_patterns,
+            save_preset,
+            load_preset,
+            reset_preset,
+            clear_bones,
+            saved_preset,
+            save_preset_selector: make_file_selector(
+                ctx,
+                "Save Ragdoll Preset",
+                FileBrowserMode::Save {
+                    default_file_name: PathBuf::from("ragdoll.ron"),
+                },
+                "ron",
+            ),
+            load_preset_selector: make_file_selector(
+                ctx,
+                "Load Ragdoll Preset",
+                FileBrowserMode::Open,
+                "ron",
+            ),
+            export_prefab,
+            instantiate_prefab,
+            generate_for_selection,
+            generate_for_selection_enabled: false,
+            generation_progress,
+            batch: None,
+            export_prefab_selector: make_file_selector(
+                ctx,
+                "Export Ragdoll Prefab",
+                FileBrowserMode::Save {
+                    default_file_name: PathBuf::from("ragdoll.rgs"),
+                },
+                "rgs",
+            ),
+            instantiate_prefab_selector: make_file_selector(
+                ctx,
+                "Instantiate Ragdoll Prefab",
+                FileBrowserMode::Open,
+                "rgs",
+            ),
+            test,
+            stop_test,
+            test_duration,
+            test_frame,
+            test_duration_secs: DEFAULT_TEST_DURATION,
+            test_enabled: false,
+            simulation: None,
+            simulation_frame: 0,
+            simulation_playing: false,
+            preview,
+            show_preview: false,
+            coverage,
+            show_coverage: false,
+            mirror_left_to_right,
+            mirror_right_to_left,
+            replace_existing,
+            replace_existing_ragdoll: false,
+            replace_window: RagdollReplaceWindow::new(ctx),
+            autofill_report,
+            preflight: RagdollPreflightWindow::new(ctx),
+            validation: RagdollValidationWindow::new(ctx),
+            pick: None,
+            sender,
+        }
+    }
+
+    fn save_preset_to_file(&mut self, path: &Path, graph: &Graph, editor_scene: &EditorScene) {
+        let candidates = collect_bone_candidates(graph, character_root(editor_scene));
+        let file = self.preset.to_file(&candidates);
+        let result = ron::ser::to_string_pretty(&file, PrettyConfig::default())
+            .map_err(|e| format!("{:?}", e))
+            .and_then(|source| std::fs::write(path, source).map_err(|e| format!("{:?}", e)));
+        match result {
+            Ok(_) => {
+                self.saved_preset = self.preset.clone();
+                Log::info(format!("Ragdoll preset was saved to {}.", path.display()))
+            }
+            Err(e) => Log::err(format!(
+                "Unable to save ragdoll preset to {}. Reason: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    fn load_preset_from_file(&mut self, path: &Path, graph: &Graph, editor_scene: &EditorScene) {
+        let file = match std::fs::read_to_string(path)
+            .map_err(|e| format!("{:?}", e))
+            .and_then(|source| {
+                ron::from_str::<RagdollPresetFile>(&source).map_err(|e| format!("{:?}", e))
+            }) {
+            Ok(file) => file,
+            Err(e) => {
+                Log::err(format!(
+                    "Unable to load ragdoll preset from {}. Reason: {}",
+                    path.display(),
+                    e
+                ));
+                return;
+            }
+        };
+
+        let candidates = collect_bone_candidates(graph, character_root(editor_scene));
+        let unresolved = self.preset.apply_file(&file, &candidates);
+        self.saved_preset = self.preset.clone();
+        let invalid = find_invalid_numbers(&self.preset);
+        if !invalid.is_empty() {
+            Log::warn(format!(
+                "Ragdoll preset {} has invalid values: {}. Fix them to generate the ragdoll.",
+                path.display(),
+                invalid_number_list(&invalid)
+            ));
+        }
+        if unresolved.is_empty() {
+            Log::info(format!(
+                "Ragdoll preset was loaded from {}.",
+                path.display()
+            ));
+        } else {
+            Log::warn(format!(
+                "Ragdoll preset was loaded from {}, but these bones were not found: {}. Assign \
+                them manually.",
+                path.display(),
+                unresolved.join(", ")
+            ));
+        }
+    }
+
+    fn export_prefab_to_file(&self, path: &Path, graph: &mut Graph) {
+        let result = self.preset.make_prefab(graph).and_then(|mut prefab| {
+            let mut visitor = Visitor::new();
+            prefab
+                .save("Scene", &mut visitor)
+                .and_then(|_| visitor.save_binary(path))
+                .map_err(|e| format!("{:?}", e))
+        });
+        match result {
+            Ok(_) => Log::info(format!(
+                "Ragdoll prefab was exported to {}.",
+                path.display()
+            )),
+            Err(e) => Log::err(format!(
+                "Unable to export ragdoll prefab to {}. Reason: {}",
+                path.display(),
+                e
+            )),
+        }
+    }
+
+    /// Adds the ragdoll prefab to the scene and binds its limbs to the bones of the selected
+    /// character. Nothing is added, if some limbs can't be bound.
+    fn instantiate_prefab_from_file(
+        &self,
+        path: &Path,
+        graph: &mut Graph,
+        editor_scene: &EditorScene,
+        resource_manager: &ResourceManager,
+        sender: &MessageSender,
+    ) -> Result<(), String> {
+        let character_root = selected_character_root(editor_scene)
+            .ok_or_else(|| "select the root of the character first.".to_owned())?;
+        // Prefabs must be referenced by relative paths, so the scene stays portable.
+        let relative_path = make_relative_path(path).map_err(|e| format!("{:?}", e))?;
+        let model = block_on(resource_manager.request::<Model, _>(relative_path))
+            .map_err(|e| format!("{:?}", e))?;
+
+        // Instantiation needs a scene, the instance is copied from a temporary one with the links
+        // to the prefab.
+        let mut temp_scene = Scene::new();
+        let temp_instance = model.instantiate(&mut temp_scene);
+        let (instance, _) = temp_scene
+            .graph
+            .copy_node(temp_instance, graph, &mut |_, _| true);
+        graph.link_nodes(instance, editor_scene.scene_content_root);
+
+        let ragdolls = graph
+            .traverse_handle_iter(instance)
+            .filter(|&handle| graph[handle].is_ragdoll())
+            .collect::<Vec<_>>();
+        let mut unresolved = Vec::new();
+        for &ragdoll in ragdolls.iter() {
+            unresolved.extend(rebind_limbs(graph, ragdoll, character_root));
+        }
+        if ragdolls.is_empty() || !unresolved.is_empty() {
+            graph.remove_node(instance);
+            return Err(if ragdolls.is_empty() {
+                "the prefab has no ragdolls.".to_owned()
+            } else {
+                format!(
+                    "the bones of these limbs were not found under the selected node: {}",
+                    unresolved.join(", ")
+                )
+            });
+        }
+
+        let sub_graph = graph.take_reserve_sub_graph(instance);
+        let group = vec![
+            SceneCommand::new(AddModelCommand::new(sub_graph)),
+            SceneCommand::new(ChangeSelectionCommand::new(
+                Selection::Graph(GraphSelection::single_or_empty(instance)),
+                editor_scene.selection.clone(),
+            )),
+        ];
+        sender.do_scene_command(
+            CommandGroup::from(group).with_custom_name("Instantiate Ragdoll Prefab"),
+        );
+
+        Ok(())
+    }
+
+    /// Shows the current preset in the inspector and in the autofill report.
+    fn sync(&mut self, ui: &mut UserInterface, graph: &Graph) {
+        sync_inspector(ui, self.inspector, &self.preset);
+        self.autofill_report
+            .sync(ui, graph, &self.preset, &self.bone_patterns);
+        // Invalid values could come from a preset file, the ragdoll can't be generated with them.
+        ui.send_message(WidgetMessage::enabled(
+            self.ok,
+            MessageDirection::ToWidget,
+            find_invalid_numbers(&self.preset).is_empty(),
+        ));
+        self.sync_title(ui);
+    }
+
+    /// Marks the title of the window with an asterisk, if the preset was changed since it was
+    /// saved or loaded.
+    fn sync_title(&self, ui: &UserInterface) {
+        let title = if self.preset == self.saved_preset {
+            "Ragdoll Wizard"
+        } else {
+            "Ragdoll Wizard*"
+        };
+        ui.send_message(WindowMessage::title(
+            self.window,
+            MessageDirection::ToWidget,
+            WindowTitle::text(title),
+        ));
+    }
+
+    /// Stores the settings of the preset, so the wizard starts with them next time.
+    fn save_last_preset(&self, settings: &mut Settings) {
+        let last_preset = Some(self.preset.to_file(&[]));
+        if settings.ragdoll.last_preset != last_preset {
+            settings.ragdoll.last_preset = last_preset;
+            Log::verify(settings.save());
+        }
+    }
+
+    fn save_bone_patterns(&self, settings: &mut Settings) {
+        if settings.ragdoll.bone_patterns != self.bone_patterns {
+            settings.ragdoll.bone_patterns = self.bone_patterns.clone();
+            Log::verify(settings.save());
+        }
+    }
+
+    /// Simulates the selected ragdoll and starts playback of the result.
+    fn start_test(&mut self, ui: &UserInterface, graph: &Graph, editor_scene: &EditorScene) {
+        let ragdoll = match selected_ragdoll(graph, editor_scene) {
+            Some(ragdoll) => ragdoll,
+            None => {
+                Log::warn("Ragdoll Wizard: select a ragdoll to test it.".to_owned());
+                return;
+            }
+        };
+        match RagdollSimulation::run(graph, ragdoll, self.test_duration_secs, FIXED_TIMESTEP) {
+            Ok(simulation) => {
+                // The range of the slider must not be empty.
+                let last_frame = simulation.frame_count().saturating_sub(1).max(1);
+                self.sync_test_controls(ui, last_frame as f32, true);
+                self.simulation = Some(simulation);
+                self.simulation_frame = 0;
+                self.simulation_playing = true;
+            }
+            Err(e) => Log::err(format!("Unable to test the ragdoll: {}", e)),
+        }
+    }
+
+    /// Discards the test simulation, the viewport shows only the scene again.
+    fn stop_test(&mut self, ui: &UserInterface) {
+        self.simulation = None;
+        self.simulation_frame = 0;
+        self.simulation_playing = false;
+        self.sync_test_controls(ui, 1.0, false);
+    }
+
+    /// Rewinds the frame slider and enables the controls of the running test.
+    fn sync_test_controls(&self, ui: &UserInterface, last_frame: f32, running: bool) {
+        send_sync_message(
+            ui,
+            ScrollBarMessage::value(self.test_frame, MessageDirection::ToWidget, 0.0),
+        );
+        send_sync_message(
+            ui,
+            ScrollBarMessage::max_value(self.test_frame, MessageDirection::ToWidget, last_frame),
+        );
+        for widget in [self.test_frame, self.stop_test] {
+            ui.send_message(WidgetMessage::enabled(
+                widget,
+                MessageDirection::ToWidget,
+                running,
+            ));
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        ui.send_message(WidgetMessage::focus(self.ok, MessageDirection::ToWidget));
+    }
+
+    /// Finds the ragdoll (or the hitboxes), that must be replaced, and generates the new one. The
+    /// user is asked to choose the ragdoll, if there are several of them.
+    fn find_and_generate(
+        &mut self,
+        ui: &mut UserInterface,
+        graph: &mut Graph,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+    ) {
+        // Generated ragdolls are put under the root of the scene, but they could be moved under
+        // the character later, so the whole scene is searched.
+        let replace = if !self.replace_existing_ragdoll {
+            Vec::new()
+        } else if self.preset.output_mode == RagdollOutputMode::Hitboxes {
+            self.preset.find_hitboxes(graph)
+        } else {
+            find_ragdolls(graph, editor_scene.scene_content_root, self.preset.hips)
+        };
+        if self.preset.output_mode == RagdollOutputMode::Ragdoll && replace.len() > 1 {
+            self.replace_window.open(ui, graph, replace);
+        } else {
+            self.generate(ui, graph, editor_scene, sender, &replace);
+        }
+    }
+
+    fn generate(
+        &mut self,
+        ui: &mut UserInterface,
+        graph: &mut Graph,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+        replace: &[Handle<Node>],
+    ) {
+        let overlaps =
+            match self
+                .preset
+                .create_and_send_command(graph, editor_scene, sender, replace)
+            {
+                Ok(overlaps) => overlaps,
+                Err(reason) => {
+                    Log::err(format!("Unable to generate the ragdoll: {}", reason));
+                    return;
+                }
+            };
+
+        ui.send_message(WindowMessage::close(
+            self.window,
+            MessageDirection::ToWidget,
+        ));
+
+        if !overlaps.is_empty() {
+            self.validation.open(ui, graph, overlaps);
+        }
+    }
+
+    /// Starts generation of the ragdolls for every selected character, it is done in
+    /// [`Self::update`].
+    fn start_batch(&mut self, graph: &Graph, editor_scene: &EditorScene) {
+        let characters = match &editor_scene.selection {
+            Selection::Graph(selection) => selection.nodes().to_vec(),
+            _ => Vec::new(),
+        };
+        match RagdollBatch::new(
+            editor_scene.scene,
+            graph,
+            &self.preset,
+            characters,
+            self.replace_existing_ragdoll,
+        ) {
+            Ok(batch) => {
+                self.batch = Some(batch);
+                self.sender.send(Message::RagdollGenerationProgress(0.0));
+            }
+            Err(reason) => Log::err(format!(
+                "Unable to generate the ragdolls for the selection: {}",
+                reason
+            )),
+        }
+    }
+
+    /// Generates the ragdoll for the next selected character, or adds every generated ragdoll to
+    /// the scene, when every character is processed.
+    fn update_batch(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
+        let batch = match self.batch.as_mut() {
+            Some(batch) => batch,
+            None => return,
+        };
+
+        if batch.scene != editor_scene.scene {
+            // Scene was changed or closed, the generated nodes can't be added by a command.
+            let batch = self.batch.take().unwrap();
+            if let Some(scene) = engine.scenes.try_get_mut(batch.scene) {
+                batch.discard(&mut scene.graph);
+            }
+            self.sender.send(Message::RagdollGenerationProgress(1.0));
+            return;
+        }
+
+        let graph = &mut engine.scenes[editor_scene.scene].graph;
+        if batch.step(graph, editor_scene.scene_content_root) {
+            self.sender
+                .send(Message::RagdollGenerationProgress(batch.progress()));
+            return;
+        }
+
+        let batch = self.batch.take().unwrap();
+        self.sender.send(Message::RagdollGenerationProgress(1.0));
+        let ui = &mut engine.user_interface;
+        let count = batch.generated_count();
+        let total = batch.characters.len();
+        // Names of the bodies are taken from the graph, so the window is opened before the command
+        // takes the generated nodes.
+        let overlaps = batch.overlaps().to_vec();
+        if !overlaps.is_empty() {
+            self.validation.open(ui, graph, overlaps);
+        }
+        match batch.finish(graph, &editor_scene.selection) {
+            Some(group) => {
+                self.sender.do_scene_command(group);
+                Log::info(format!(
+                    "Ragdoll Wizard: ragdolls were generated for {} of {} characters.",
+                    count, total
+                ));
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+            None => Log::err(
+                "Unable to generate the ragdolls for the selection: every character was skipped."
+                    .to_owned(),
+            ),
+        }
+    }
+
+    /// Shows the progress of the generation of the ragdolls for the selected characters, the
+    /// progress bar is hidden, when nothing is generated.
+    pub fn set_generation_progress(&self, ui: &UserInterface, progress: f32) {
+        ui.send_message(ProgressBarMessage::progress(
+            self.generation_progress,
+            MessageDirection::ToWidget,
+            progress,
+        ));
+        ui.send_message(WidgetMessage::visibility(
+            self.generation_progress,
+            MessageDirection::ToWidget,
+            progress < 1.0,
+        ));
+    }
+
+    /// Forgets the edits of the preset. Undoing an edit after the preset was replaced by other
+    /// means would mix the old and the new preset.
+    fn clear_history(&mut self) {
+        self.command_stack.clear(RagdollPresetContext {
+            preset: &mut self.preset,
+        });
+    }
+
+    fn start_pick(
+        &mut self,
+        editor: Handle<UiNode>,
+        editor_scene: &EditorScene,
+        interaction_mode: Option<InteractionModeKind>,
+    ) {
+        // Picking another bone while a pick is in progress keeps the mode, that was active before
+        // the first pick.
+        let previous_mode = match self.pick.take() {
+            Some(pick) => pick.previous_mode,
+            None => interaction_mode,
+        };
+        self.pick = Some(NodePick {
+            editor,
+            initial_selection: selected_character_root(editor_scene),
+            previous_mode,
+        });
+        self.sender
+            .send(Message::SetInteractionMode(InteractionModeKind::Select));
+    }
+
+    fn finish_pick(&mut self) {
+        if let Some(pick) = self.pick.take() {
+            if let Some(mode) = pick.previous_mode {
+                self.sender.send(Message::SetInteractionMode(mode));
+            }
+        }
+    }
+
+    /// Draws the preview of the ragdoll, plays the test simulation and assigns the node, that was
+    /// picked in the viewport. It must be called every frame after the scene has cleared its debug
+    /// drawings.
+    pub fn update(&mut self, editor_scene: &EditorScene, engine: &mut Engine) {
+        self.update_batch(editor_scene, engine);
+
+        let scene = &mut engine.scenes[editor_scene.scene];
+        if self.show_preview {
+            self.preset
+                .draw_preview(&scene.graph, &mut scene.drawing_context);
+        }
+        // The coverage is collected every frame, so edits of the limbs are shown right away.
+        if self.show_coverage {
+            if let Some(ragdoll) = selected_ragdoll(&scene.graph, editor_scene) {
+                BoneCoverage::new(&scene.graph, scene.graph[ragdoll].as_ragdoll())
+                    .draw(&scene.graph, &mut scene.drawing_context);
+            }
+        }
+
+        if let Some(simulation) = self.simulation.as_ref() {
+            simulation.draw(
+                self.simulation_frame,
+                &mut scene.drawing_context,
+                Color::GREEN,
+            );
+            // The editor is updated with the same time step as the simulation, so one frame per
+            // update is played in real time.
+            if self.simulation_playing {
+                if self.simulation_frame + 1 < simulation.frame_count() {
+                    self.simulation_frame += 1;
+                    send_sync_message(
+                        &engine.user_interface,
+                        ScrollBarMessage::value(
+                            self.test_frame,
+                            MessageDirection::ToWidget,
+                            self.simulation_frame as f32,
+                        ),
+                    );
+                } else {
+                    self.simulation_playing = false;
+                }
+            }
+        }
+
+        let test_enabled = selected_ragdoll(&scene.graph, editor_scene).is_some();
+        if test_enabled != self.test_enabled {
+            self.test_enabled = test_enabled;
+            engine.user_interface.send_message(WidgetMessage::enabled(
+                self.test,
+                MessageDirection::ToWidget,
+                test_enabled,
+            ));
+        }
+
+        let several_selected = matches!(
+            &editor_scene.selection,
+            Selection::Graph(selection) if selection.len() > 1
+        );
+        let generate_for_selection_enabled = self.batch.is_none() && several_selected;
+        if generate_for_selection_enabled != self.generate_for_selection_enabled {
+            self.generate_for_selection_enabled = generate_for_selection_enabled;
+            engine.user_interface.send_message(WidgetMessage::enabled(
+                self.generate_for_selection,
+                MessageDirection::ToWidget,
+                generate_for_selection_enabled,
+            ));
+        }
+
+        if let Some(pick) = self.pick.as_ref() {
+            if let Some(selected) = selected_character_root(editor_scene) {
+                if Some(selected) != pick.initial_selection {
+                    // The editor reports the new value back, so it goes the same way as if the
+                    // node was dropped on the editor.
+                    engine
+                        .user_interface
+                        .send_message(HandlePropertyEditorMessage::value(
+                            pick.editor,
+                            MessageDirection::ToWidget,
+                            selected,
+                        ));
+                    self.finish_pick();
+                }
+            }
+        }
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &mut UserInterface,
+        graph: &mut Graph,
+        editor_scene: &EditorScene,
+        sender: &MessageSender,
+        resource_manager: &ResourceManager,
+        viewport: Handle<UiNode>,
+        settings: &mut Settings,
+        interaction_mode: Option<InteractionModeKind>,
+    ) {
+        self.validation
+            .handle_ui_message(message, ui, graph, sender, viewport);
+        if let Some(replace) = self.replace_window.handle_ui_message(message, ui) {
+            self.generate(ui, graph, editor_scene, sender, &[replace]);
+        }
+        if self.preflight.handle_ui_message(message, ui) {
+            self.find_and_generate(ui, graph, editor_scene, sender);
+        }
+        match self.autofill_report.handle_ui_message(message) {
+            Some(AutofillReportAction::Clear(bone)) => {
+                *self.preset.bone_mut(bone) = Handle::NONE;
+                self.clear_history();
+                self.sync(ui, graph);
+            }
+            Some(AutofillReportAction::Pick(bone)) => {
+                let field_name = bone_field_name(bone);
+                let editor = ui
+                    .node(self.inspector)
+                    .cast::<fyrox::gui::inspector::Inspector>()
+                    .unwrap()
+                    .context()
+                    .entries
+                    .iter()
+                    .find(|entry| entry.property_name == field_name)
+                    .map(|entry| entry.property_editor);
+                if let Some(editor) = editor {
+                    Log::info(format!(
+                        "Ragdoll Wizard: click a node in the scene to assign it to {}.",
+                        bone.as_ref()
+                    ));
+                    self.start_pick(editor, editor_scene, interaction_mode);
+                }
+            }
+            None => (),
+        }
+
+        if let Some(InspectorMessage::PropertyChanged(args)) = message.data() {
+            if message.destination() == self.inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                let outside_before = self.preset.bones_outside_hips(graph);
+                // Values, that are not finite, can't be clamped, so they're rejected and the
+                // inspector shows the previous value after the sync below.
+                let rejected = match PropertyAction::from_field_kind(&args.value) {
+                    PropertyAction::Modify { value }
+                    | PropertyAction::AddItem { value }
+                    | PropertyAction::InsertItem { value, .. } => find_invalid_numbers(&*value)
+                        .iter()
+                        .any(|number| number.clamped.is_none()),
+                    _ => false,
+                };
+                if rejected {
+                    Log::err(format!(
+                        "Ragdoll Wizard: {} must be a finite number, the previous value is kept.",
+                        args.path()
+                    ));
+                } else if let Some(command) =
+                    make_set_ragdoll_preset_property_command((), &self.preset, args)
+                {
+                    // Edits go through the command stack of the wizard, so they could be undone.
+                    self.command_stack.do_command(
+                        command.into_inner(),
+                        RagdollPresetContext {
+                            preset: &mut self.preset,
+                        },
+                    );
+                    for number in clamp_invalid_numbers(&mut self.preset) {
+                        if let Some(clamped) = number.clamped {
+                            Log::warn(format!(
+                                "Ragdoll Wizard: {} is out of its range, it is clamped to {}.",
+                                number.path, clamped
+                            ));
+                        }
+                    }
+                }
+
+                let outside = self
+                    .preset
+                    .bones_outside_hips(graph)
+                    .into_iter()
+                    .filter(|bone| !outside_before.contains(bone))
+                    .collect::<Vec<_>>();
+                if !outside.is_empty() {
+                    Log::warn(format!(
+                        "Ragdoll Wizard: {} are not under the assigned Hips, they may belong \
+                        to another character.",
+                        bone_list(&outside)
+                    ));
+                }
+
+                // Names of the assigned nodes must be shown.
+                self.sync(ui, graph);
+            } else if message.destination() == self.patterns_inspector
+                && message.direction() == MessageDirection::FromWidget
+            {
+                PropertyAction::from_field_kind(&args.value).apply(
+                    &args.path(),
+                    &mut self.bone_patterns,
+                    &mut |result| {
+                        Log::verify(result);
+                    },
+                );
+                self.save_bone_patterns(settings);
+                self.autofill_report
+                    .sync(ui, graph, &self.preset, &self.bone_patterns);
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.ok {
+                let check = self.preset.check_bones(graph);
+                if check.is_empty() {
+                    self.find_and_generate(ui, graph, editor_scene, sender);
+                } else {
+                    self.preflight.open(ui, &check, self.preset.output_mode);
+                }
+            } else if message.destination() == self.cancel {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.autofill {
+                let root = selected_character_root(editor_scene).unwrap_or_else(|| {
+                    Log::warn(
+                        "Ragdoll Autofill: no character is selected, bones are searched in the \
+                        whole scene. Select the root of the character to search only under it."
+                            .to_owned(),
+                    );
+                    editor_scene.scene_content_root
+                });
+                let unresolved = self.preset.autofill(graph, root, &self.bone_patterns);
+                self.clear_history();
+                if !unresolved.is_empty() {
+                    Log::warn(format!(
+                        "Ragdoll Autofill: no bones were found for {}, assign them manually or \
+                        change the bone name patterns.",
+                        bone_list(&unresolved)
+                    ));
+                }
+
+                self.sync(ui, graph);
+                // Wrong matches are easier to find in the report, than in the inspector.
+                ui.send_message(ExpanderMessage::expand(
+                    self.autofill_report.expander,
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            } else if message.destination() == self.mixamo_patterns
+                || message.destination() == self.unreal_patterns
+            {
+                self.bone_patterns = if message.destination() == self.mixamo_patterns {
+                    RagdollBonePatterns::mixamo()
+                } else {
+                    RagdollBonePatterns::unreal()
+                };
+                self.save_bone_patterns(settings);
+
+                sync_inspector(ui, self.patterns_inspector, &self.bone_patterns);
+                self.autofill_report
+                    .sync(ui, graph, &self.preset, &self.bone_patterns);
+            } else if message.destination() == self.mirror_left_to_right
+                || message.destination() == self.mirror_right_to_left
+            {
+                let left_to_right = message.destination() == self.mirror_left_to_right;
+                // Bones are found by their paths from the root of the scene, so every assigned
+                // bone could be mirrored, even if it is not under the selected node.
+                let candidates = collect_bone_candidates(graph, editor_scene.scene_content_root);
+                let failed = self.preset.mirror(
+                    &candidates,
+                    &parse_mirror_tokens(&settings.ragdoll.mirror_tokens),
+                    left_to_right,
+                );
+                self.clear_history();
+                if !failed.is_empty() {
+                    Log::warn(format!(
+                        "Ragdoll Mirror: no mirrored bones were found for {}, assign them \
+                        manually or change the mirror tokens in the settings.",
+                        bone_list(&failed)
+                    ));
+                }
+
+                self.sync(ui, graph);
+            } else if message.destination() == self.save_preset
+                || message.destination() == self.load_preset
+            {
+                ui.send_message(WindowMessage::open_modal(
+                    if message.destination() == self.save_preset {
+                        self.save_preset_selector
+                    } else {
+                        self.load_preset_selector
+                    },
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            } else if message.destination() == self.export_prefab
+                || message.destination() == self.instantiate_prefab
+            {
+                ui.send_message(WindowMessage::open_modal(
+                    if message.destination() == self.export_prefab {
+                        self.export_prefab_selector
+                    } else {
+                        self.instantiate_prefab_selector
+                    },
+                    MessageDirection::ToWidget,
+                    true,
+                ));
+            } else if message.destination() == self.clear_bones {
+                self.preset.clear_bones();
+                self.clear_history();
+                self.sync(ui, graph);
+            } else if message.destination() == self.reset_preset {
+                self.preset.reset_to_defaults();
+                self.clear_history();
+                self.sync(ui, graph);
+            } else if message.destination() == self.undo {
+                self.command_stack.undo(RagdollPresetContext {
+                    preset: &mut self.preset,
+                });
+                self.sync(ui, graph);
+            } else if message.destination() == self.redo {
+                self.command_stack.redo(RagdollPresetContext {
+                    preset: &mut self.preset,
+                });
+                self.sync(ui, graph);
+            } else if message.destination() == self.generate_for_selection {
+                self.start_batch(graph, editor_scene);
+            } else if message.destination() == self.test {
+                self.start_test(ui, graph, editor_scene);
+            } else if message.destination() == self.stop_test {
+                self.stop_test(ui);
+            }
+        } else if let Some(ScrollBarMessage::Value(value)) = message.data() {
+            if message.destination() == self.test_frame
+                && message.direction() == MessageDirection::FromWidget
+                && message.flags != MSG_SYNC_FLAG
+            {
+                // Scrubbing stops the playback, so the pose stays where it was dragged to.
+                if let Some(simulation) = self.simulation.as_ref() {
+                    self.simulation_frame =
+                        (value.max(0.0).round() as usize).min(simulation.frame_count() - 1);
+                    self.simulation_playing = false;
+                }
+            }
+        } else if let Some(NumericUpDownMessage::Value(value)) = message.data() {
+            if message.destination() == self.test_duration
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.test_duration_secs = *value;
+            }
+        } else if let Some(HandlePropertyEditorMessage::Pick) = message.data() {
+            if message.direction() == MessageDirection::FromWidget {
+                let ctx = ui
+                    .node(self.inspector)
+                    .cast::<fyrox::gui::inspector::Inspector>()
+                    .unwrap()
+                    .context();
+                let field = ctx
+                    .entries
+                    .iter()
+                    .find(|entry| entry.property_editor == message.destination())
+                    .map(|entry| entry.property_name.clone())
+                    // Bones of the extra chains are in the nested inspectors of the chains.
+                    .or_else(|| {
+                        ui.is_node_child_of(message.destination(), self.inspector)
+                            .then(|| "the extra chain".to_owned())
+                    });
+                if let Some(field) = field {
+                    Log::info(format!(
+                        "Ragdoll Wizard: click a node in the scene to assign it to {}.",
+                        field
+                    ));
+                    self.start_pick(message.destination(), editor_scene, interaction_mode);
+                }
+            }
+        } else if let Some(FileSelectorMessage::Commit(path)) = message.data() {
+            if message.destination() == self.save_preset_selector {
+                self.save_preset_to_file(path, graph, editor_scene);
+                self.sync_title(ui);
+            } else if message.destination() == self.load_preset_selector {
+                self.load_preset_from_file(path, graph, editor_scene);
+                self.clear_history();
+                self.sync(ui, graph);
+            } else if message.destination() == self.export_prefab_selector {
+                self.export_prefab_to_file(path, graph);
+            } else if message.destination() == self.instantiate_prefab_selector {
+                match self.instantiate_prefab_from_file(
+                    path,
+                    graph,
+                    editor_scene,
+                    resource_manager,
+                    sender,
+                ) {
+                    Ok(_) => ui.send_message(WindowMessage::close(
+                        self.window,
+                        MessageDirection::ToWidget,
+                    )),
+                    Err(e) => Log::err(format!(
+                        "Unable to instantiate ragdoll prefab {}: {}",
+                        path.display(),
+                        e
+                    )),
+                }
+            }
+        } else if let Some(CheckBoxMessage::Check(Some(value))) = message.data() {
+            if message.destination() == self.preview
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.show_preview = *value;
+                if *value {
+                    Log::info(mass_breakdown(&self.preset.make_shapes(graph)));
+                }
+            } else if message.destination() == self.coverage
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.show_coverage = *value;
+            } else if message.destination() == self.replace_existing
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.replace_existing_ragdoll = *value;
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window
+                && message.direction() == MessageDirection::ToWidget
+            {
+                self.save_last_preset(settings);
+
+                // The preview and the coverage are shown, the test is played and bones are picked
+                // only while the wizard is open.
+                self.show_preview = false;
+                self.show_coverage = false;
+                self.stop_test(ui);
+                self.finish_pick();
+                for check_box in [self.preview, self.coverage] {
+                    ui.send_message(CheckBoxMessage::checked(
+                        check_box,
+                        MessageDirection::ToWidget,
+                        Some(false),
+                    ));
+                }
+
+                // Give keyboard focus back to the scene, unless the validation window takes it.
+                if !self.validation.is_open {
+                    ui.send_message(WidgetMessage::focus(viewport, MessageDirection::ToWidget));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::ragdoll::bones::BoneCandidate;
+    use fyrox::{
+        core::{
+            algebra::{Matrix4, Vector3},
+            math::Matrix4Ext,
+        },
+        scene::{
+            base::BaseBuilder, pivot::PivotBuilder, rigidbody::RigidBody,
+            transform::TransformBuilder,
+        },
+    };
+
+    pub fn candidate(index: u32, depth: usize, name: &str) -> BoneCandidate {
+        BoneCandidate {
+            handle: Handle::new(index, 1),
+            depth,
+            name: name.to_lowercase(),
+            path: name.to_owned(),
+        }
+    }
+
+    // Builds a candidate from its path under the character root.
+    pub fn candidate_at(index: u32, path: &str) -> BoneCandidate {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        BoneCandidate {
+            handle: Handle::new(index, 1),
+            depth: if path.is_empty() {
+                0
+            } else {
+                path.split('/').count()
+            },
+            name: name.to_lowercase(),
+            path: path.to_owned(),
+        }
+    }
+
+    pub fn make_bone(graph: &mut Graph, position: Vector3<f32>) -> Handle<Node> {
+        PivotBuilder::new(
+            BaseBuilder::new().with_local_transform(
+                TransformBuilder::new()
+                    .with_local_position(position)
+                    .build(),
+            ),
+        )
+        .build(graph)
+    }
+
+    /// Makes a character with the given prefix of the names of the bones, it is moved along X axis.
+    pub fn make_character(
+        graph: &mut Graph,
+        x: f32,
+        prefix: &str,
+    ) -> (Handle<Node>, Vec<(RagdollBone, Handle<Node>)>) {
+        use RagdollBone::*;
+
+        // Bones with the index of the parent bone and the offset from it.
+        let layout = [
+            (Hips, None, Vector3::new(0.0, 1.0, 0.0)),
+            (LeftUpLeg, Some(0), Vector3::new(0.1, 0.0, 0.0)),
+            (LeftLeg, Some(1), Vector3::new(0.0, -0.45, 0.0)),
+            (LeftFoot, Some(2), Vector3::new(0.0, -0.45, 0.0)),
+            (RightUpLeg, Some(0), Vector3::new(-0.1, 0.0, 0.0)),
+            (RightLeg, Some(4), Vector3::new(0.0, -0.45, 0.0)),
+            (RightFoot, Some(5), Vector3::new(0.0, -0.45, 0.0)),
+            (Spine, Some(0), Vector3::new(0.0, 0.3, 0.0)),
+            (Head, Some(7), Vector3::new(0.0, 0.4, 0.0)),
+        ];
+        let root = make_bone(graph, Vector3::new(x, 0.0, 0.0));
+        let mut bones: Vec<(RagdollBone, Handle<Node>)> = Vec::new();
+        for (bone, parent, offset) in layout {
+            let handle = make_bone(graph, offset);
+            graph[handle].set_name(format!("{}{}", prefix, bone.as_ref()));
+            let parent = parent.map_or(root, |parent: usize| bones[parent].1);
+            graph.link_nodes(handle, parent);
+            bones.push((bone, handle));
+        }
+        (root, bones)
+    }
+
+    #[test]
+    fn test_batch_generation() {
+        let mut graph = Graph::new();
+        let (first_root, first_bones) = make_character(&mut graph, 0.0, "mixamorig:");
+        let (second_root, second_bones) = make_character(&mut graph, 2.0, "mixamorig:");
+        let (other_root, _) = make_character(&mut graph, 4.0, "Bip01_");
+        // The first character is nested in the selected group, the bones are found in both.
+        let group = make_bone(&mut graph, Vector3::default());
+        graph.link_nodes(first_root, group);
+        graph.update_hierarchical_data();
+        let scene_root = graph.get_root();
+
+        let mut preset = RagdollPreset::default();
+        assert!(RagdollBatch::new(Handle::NONE, &graph, &preset, vec![first_root], false).is_err());
+        for &(bone, handle) in first_bones.iter() {
+            *preset.bone_mut(bone) = handle;
+        }
+
+        let characters = vec![first_root, second_root, other_root, group];
+        let mut batch =
+            RagdollBatch::new(Handle::NONE, &graph, &preset, characters, false).unwrap();
+        assert_eq!(batch.progress(), 0.0);
+        let mut steps = 0;
+        while batch.step(&mut graph, scene_root) {
+            steps += 1;
+        }
+        assert_eq!(steps, 4);
+        assert_eq!(batch.progress(), 1.0);
+        // The other character has different names of the bones, the group has the same bones as
+        // the first character.
+        assert_eq!(batch.generated_count(), 2);
+
+        // Bones are resolved under every character.
+        for hips in [first_bones[0].1, second_bones[0].1] {
+            let ragdolls = find_ragdolls(&graph, scene_root, hips);
+            assert_eq!(ragdolls.len(), 1);
+            assert!(batch.generated.contains(&ragdolls[0]));
+        }
+
+        // The command takes every generated ragdoll.
+        assert!(batch.finish(&mut graph, &Selection::None).is_some());
+        assert!(find_ragdolls(&graph, scene_root, second_bones[0].1).is_empty());
+    }
+
+    // The editor scene is made without the engine, the commands get the scene directly.
+    #[test]
+    fn test_simulation_leaves_graph_untouched() {
+        let mut graph = Graph::new();
+        // The torso leans far to the side, so the character can't stay on its leg.
+        let preset = RagdollPreset {
+            hips: make_bone(&mut graph, Vector3::new(0.0, 1.0, 0.0)),
+            spine: make_bone(&mut graph, Vector3::new(0.3, 1.3, 0.0)),
+            neck: make_bone(&mut graph, Vector3::new(0.6, 1.6, 0.0)),
+            head: make_bone(&mut graph, Vector3::new(0.8, 1.8, 0.0)),
+            left_up_leg: make_bone(&mut graph, Vector3::new(0.1, 1.0, 0.0)),
+            left_leg: make_bone(&mut graph, Vector3::new(0.1, 0.55, 0.0)),
+            left_foot: make_bone(&mut graph, Vector3::new(0.1, 0.1, 0.0)),
+            ..Default::default()
+        };
+        graph.update_hierarchical_data();
+        let shapes = preset.make_shapes(&graph);
+        let ragdoll = preset.build_ragdoll(&shapes, graph.get_root(), &mut graph);
+        graph.update_hierarchical_data();
+
+        let poses = |graph: &Graph| {
+            graph
+                .pair_iter()
+                .filter_map(|(handle, node)| {
+                    node.cast::<RigidBody>()
+                        .map(|body| (handle, body.body_type(), node.global_transform()))
+                })
+                .collect::<Vec<_>>()
+        };
+        let node_count = graph.node_count();
+        let poses_before = poses(&graph);
+
+        let simulation = RagdollSimulation::run(&graph, ragdoll, 2.0, 1.0 / 60.0).unwrap();
+        assert_eq!(simulation.frame_count(), 121);
+        assert_eq!(simulation.frame(0).len(), shapes.len());
+        // Frames past the end show the last pose.
+        assert_eq!(simulation.frame(200), simulation.frame(120));
+
+        // The ragdoll falls on the ground under its foot and does not fall through it.
+        let heights = |frame: &[Matrix4<f32>]| {
+            let heights = frame.iter().map(|transform| transform.position().y);
+            (
+                heights.clone().fold(f32::MAX, f32::min),
+                heights.fold(f32::MIN, f32::max),
+            )
+        };
+        let (lowest_before, highest_before) = heights(simulation.frame(0));
+        let (lowest_after, highest_after) = heights(simulation.frame(120));
+        assert!(highest_after < highest_before - 0.5);
+        assert!(lowest_after > lowest_before - 0.5);
+
+        assert_eq!(graph.node_count(), node_count);
+        assert_eq!(poses(&graph), poses_before);
+
+        assert!(RagdollSimulation::run(&graph, preset.hips, 1.0, 1.0 / 60.0).is_err());
+    }
+
+    #[test]
+    fn test_report_paths_and_fields() {
+        let mut graph = Graph::new();
+        let character = make_bone(&mut graph, Vector3::default());
+        graph[character].set_name("Character");
+        let hips = make_bone(&mut graph, Vector3::default());
+        graph[hips].set_name("Hips");
+        graph.link_nodes(hips, character);
+        assert_eq!(node_path(&graph, hips), "Character/Hips");
+        assert_eq!(node_path(&graph, Handle::NONE), "");
+
+        // Every bone could be picked from the report, so its field must be found.
+        let mut fields = Vec::new();
+        RagdollPreset::default().fields_info(&mut |info| {
+            fields = info.iter().map(|field| field.name.to_owned()).collect()
+        });
+        for bone in RagdollBone::ALL {
+            assert!(fields.contains(&bone_field_name(bone)), "{:?}", bone);
+        }
+        assert_eq!(bone_field_name(RagdollBone::LeftUpLeg), "left_up_leg");
+        assert_eq!(bone_field_name(RagdollBone::Spine1), "spine1");
+    }
+}
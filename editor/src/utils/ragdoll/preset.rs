@@ -0,0 +1,1836 @@
+//! Ragdoll presets. A preset holds the bones of a character, that the ragdoll is made of, and the
+//! physical properties of the ragdoll. Presets are edited in the ragdoll wizard and could be saved to
+//! files, that refer to the bones by paths, so a preset could be applied to other characters.
+
+use crate::{
+    settings::ragdoll::{RagdollBone, RagdollBonePatterns},
+    utils::ragdoll::bones::{
+        collect_bone_candidates, common_ancestor, find_bones, is_descendant_of, mirror_path,
+        resolve_bone_path, BoneCandidate,
+    },
+};
+use fyrox::{
+    core::{
+        algebra::{UnitQuaternion, Vector3},
+        pool::Handle,
+        reflect::prelude::*,
+    },
+    scene::{
+        collider::{BitMask, InteractionGroups},
+        graph::Graph,
+        node::Node,
+        rigidbody::RigidBodyType,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, ops::Range};
+use strum_macros::{AsRefStr, EnumString, EnumVariantNames};
+
+/// Type of the generated rigid bodies.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Reflect,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum RagdollBodyType {
+    /// Bodies follow the animation of the character, use it if the ragdoll is switched on by
+    /// scripts.
+    Kinematic,
+    /// Bodies are simulated from the start, no scripting is needed for a pure physics ragdoll.
+    Dynamic,
+}
+
+impl Default for RagdollBodyType {
+    fn default() -> Self {
+        Self::Kinematic
+    }
+}
+
+/// Kind of the kinematic bodies.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Reflect,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum RagdollKinematicKind {
+    /// Bodies are moved by setting their poses.
+    PositionBased,
+    /// Bodies are moved by their velocities, it works better with some animation retargeting
+    /// setups.
+    VelocityBased,
+}
+
+impl Default for RagdollKinematicKind {
+    fn default() -> Self {
+        Self::PositionBased
+    }
+}
+
+/// Physical properties of the generated bodies.
+#[derive(Reflect, PartialEq, Clone, Debug)]
+pub struct RagdollPhysics {
+    pub body_type: RagdollBodyType,
+    #[reflect(description = "Kind of kinematic bodies, dynamic bodies ignore it.")]
+    pub kinematic_kind: RagdollKinematicKind,
+    #[reflect(
+        description = "Linear damping of dynamic bodies, kinematic bodies are not damped.",
+        min_value = 0.0
+    )]
+    pub linear_damping: f32,
+    #[reflect(
+        description = "Angular damping of dynamic bodies, kinematic bodies are not damped.",
+        min_value = 0.0
+    )]
+    pub angular_damping: f32,
+    #[reflect(
+        description = "Whether dynamic bodies could fall asleep at rest. Sleeping bodies are not simulated, until something touches them."
+    )]
+    pub can_sleep: bool,
+}
+
+impl Default for RagdollPhysics {
+    fn default() -> Self {
+        Self {
+            body_type: RagdollBodyType::Kinematic,
+            kinematic_kind: Default::default(),
+            linear_damping: 0.1,
+            angular_damping: 0.8,
+            can_sleep: true,
+        }
+    }
+}
+
+impl RagdollPhysics {
+    pub fn rigid_body_type(&self) -> RigidBodyType {
+        match (self.body_type, self.kinematic_kind) {
+            (RagdollBodyType::Kinematic, RagdollKinematicKind::PositionBased) => {
+                RigidBodyType::KinematicPositionBased
+            }
+            (RagdollBodyType::Kinematic, RagdollKinematicKind::VelocityBased) => {
+                RigidBodyType::KinematicVelocityBased
+            }
+            (RagdollBodyType::Dynamic, _) => RigidBodyType::Dynamic,
+        }
+    }
+}
+
+/// What the wizard generates from the bones.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Reflect,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum RagdollOutputMode {
+    /// Rigid bodies connected with joints under a ragdoll node.
+    Ragdoll,
+    /// Sensor colliders under the bones, that follow the animation. They could be used as damage
+    /// zones, there are no joints and no ragdoll node.
+    Hitboxes,
+}
+
+impl Default for RagdollOutputMode {
+    fn default() -> Self {
+        Self::Ragdoll
+    }
+}
+
+/// Multipliers of the sizes of the generated bodies. The sizes are derived from common human body
+/// proportions, the multipliers allow to fit them to slim or bulky characters.
+#[derive(Reflect, Serialize, Deserialize, PartialEq, Clone, Debug)]
+#[serde(default)]
+pub struct RagdollProportions {
+    #[reflect(
+        description = "Radius multiplier of the legs.",
+        min_value = 0.0,
+        step = 0.05
+    )]
+    pub legs: f32,
+    #[reflect(
+        description = "Radius multiplier of the arms and the shoulders.",
+        min_value = 0.0,
+        step = 0.05
+    )]
+    pub arms: f32,
+    #[reflect(
+        description = "Radius multiplier of the hands and the feet.",
+        min_value = 0.0,
+        step = 0.05
+    )]
+    pub hands_and_feet: f32,
+    #[reflect(
+        description = "Radius multiplier of the head and the neck.",
+        min_value = 0.0,
+        step = 0.05
+    )]
+    pub head: f32,
+    #[reflect(
+        description = "Multiplier of the width and the depth of the hips and the spine.",
+        min_value = 0.0,
+        step = 0.05
+    )]
+    pub torso: f32,
+}
+
+impl Default for RagdollProportions {
+    fn default() -> Self {
+        Self {
+            legs: 1.0,
+            arms: 1.0,
+            hands_and_feet: 1.0,
+            head: 1.0,
+            torso: 1.0,
+        }
+    }
+}
+
+/// Shape of the limits of a ball joint.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Reflect,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum RagdollLimitShape {
+    /// The rotation around every axis is limited by the same range, the diagonal swings go further
+    /// than the straight ones.
+    Box,
+    /// The swing of the bone is limited by a cone around it, the rotation around the bone is
+    /// limited separately.
+    Cone,
+}
+
+impl Default for RagdollLimitShape {
+    fn default() -> Self {
+        Self::Cone
+    }
+}
+
+/// Angular limits of the joints of the ragdoll in degrees. A range, that covers the whole circle
+/// (-180..180), means that the joint is not limited.
+#[derive(Reflect, Serialize, Deserialize, PartialEq, Clone, Debug)]
+#[serde(default)]
+pub struct RagdollJointLimits {
+    #[reflect(description = "Limits of the ball joints between the hips and the upper legs.")]
+    pub hips: Range<f32>,
+    #[reflect(
+        description = "Limits of the hinge joints of the knees. Swap and negate the limits if the knees bend backwards."
+    )]
+    pub knees: Range<f32>,
+    #[reflect(description = "Limits of the hinge joints of the ankles.")]
+    pub ankles: Range<f32>,
+    #[reflect(description = "Limits of the hinge joints between the spine and the clavicles.")]
+    pub clavicles: Range<f32>,
+    #[reflect(description = "Limits of the ball joints between the clavicles and the upper arms.")]
+    pub shoulders: Range<f32>,
+    #[reflect(
+        description = "Limits of the hinge joints of the elbows. Swap and negate the limits if the elbows bend backwards."
+    )]
+    pub elbows: Range<f32>,
+    #[reflect(description = "Limits of the ball joints of the wrists.")]
+    pub wrists: Range<f32>,
+    #[reflect(description = "Limits of the hinge joints between the spine segments.")]
+    pub spine: Range<f32>,
+    #[reflect(description = "Limits of the ball joints of the neck and the head.")]
+    pub neck: Range<f32>,
+    #[reflect(description = "Limits of the ball joints between the hands and the fingers.")]
+    pub fingers: Range<f32>,
+    #[reflect(description = "Limits of the ball joints between the feet and the toes.")]
+    pub toes: Range<f32>,
+    #[reflect(
+        description = "Shape of the limits of the ball joints of the hips, the shoulders and the neck. Box limits the rotation around every axis by the same range. Cone limits the swing of the bone in every direction by the largest angle of the range, the rotation around the bone is limited by Twist."
+    )]
+    pub shape: RagdollLimitShape,
+    #[reflect(
+        description = "Limits of the rotation of the bones around themselves, when the limits are cone-shaped."
+    )]
+    pub twist: Range<f32>,
+}
+
+impl Default for RagdollJointLimits {
+    fn default() -> Self {
+        Self {
+            hips: -80.0..80.0,
+            knees: 0.0..140.0,
+            ankles: -45.0..45.0,
+            clavicles: -180.0..180.0,
+            shoulders: -180.0..180.0,
+            elbows: 0.0..145.0,
+            wrists: -45.0..45.0,
+            spine: -180.0..180.0,
+            neck: -180.0..180.0,
+            fingers: -20.0..20.0,
+            toes: -20.0..20.0,
+            shape: Default::default(),
+            twist: -45.0..45.0,
+        }
+    }
+}
+
+impl RagdollJointLimits {
+    /// Returns the limits of the joint, that attaches the body of the bone to its parent body.
+    pub fn of(&self, bone: RagdollBone) -> &Range<f32> {
+        match bone {
+            RagdollBone::Hips | RagdollBone::LeftUpLeg | RagdollBone::RightUpLeg => &self.hips,
+            RagdollBone::LeftLeg | RagdollBone::RightLeg => &self.knees,
+            RagdollBone::LeftFoot | RagdollBone::RightFoot => &self.ankles,
+            RagdollBone::Spine | RagdollBone::Spine1 | RagdollBone::Spine2 => &self.spine,
+            RagdollBone::LeftShoulder | RagdollBone::RightShoulder => &self.clavicles,
+            RagdollBone::LeftArm | RagdollBone::RightArm => &self.shoulders,
+            RagdollBone::LeftForeArm | RagdollBone::RightForeArm => &self.elbows,
+            RagdollBone::LeftHand | RagdollBone::RightHand => &self.wrists,
+            RagdollBone::Neck | RagdollBone::Head => &self.neck,
+            RagdollBone::LeftToeBase | RagdollBone::RightToeBase => &self.toes,
+            _ => &self.fingers,
+        }
+    }
+
+    /// Returns the limits of the ball joint, that attaches the body of the bone to its parent
+    /// body. Only the hips, the shoulders and the neck get cone-shaped limits.
+    pub fn ball_joint_limits(&self, bone: RagdollBone) -> BallJointLimits {
+        let limits = self.of(bone);
+        let uses_cone = matches!(
+            bone,
+            RagdollBone::LeftUpLeg
+                | RagdollBone::RightUpLeg
+                | RagdollBone::LeftArm
+                | RagdollBone::RightArm
+                | RagdollBone::Neck
+                | RagdollBone::Head
+        );
+        if self.shape == RagdollLimitShape::Cone && uses_cone {
+            let half_angle = limits.start.abs().max(limits.end.abs());
+            BallJointLimits::cone(half_angle.to_radians(), joint_limits(&self.twist))
+        } else {
+            BallJointLimits::uniform(joint_limits(limits))
+        }
+    }
+}
+
+/// Physical properties of the colliders of a group of limbs, that override the global properties
+/// of the preset. Unset properties are taken from the preset.
+#[derive(Reflect, Serialize, Deserialize, PartialEq, Clone, Debug, Default)]
+#[serde(default)]
+pub struct RagdollLimbOverrides {
+    #[reflect(
+        description = "Continuous collision detection of the bodies of the group, it prevents fast limbs from passing through thin obstacles."
+    )]
+    pub use_ccd: Option<bool>,
+    #[reflect(
+        description = "Friction of the colliders of the group.",
+        min_value = 0.0,
+        max_value = 1.0e6
+    )]
+    pub friction: Option<f32>,
+    #[reflect(
+        description = "Restitution (bounciness) of the colliders of the group.",
+        min_value = 0.0,
+        max_value = 1.0e6
+    )]
+    pub restitution: Option<f32>,
+}
+
+/// Overrides of the physical properties of the limbs, grouped by the parts of the body.
+#[derive(Reflect, Serialize, Deserialize, PartialEq, Clone, Debug, Default)]
+#[serde(default)]
+pub struct RagdollLimbGroupOverrides {
+    #[reflect(display_name = "Torso: Hips, Spine")]
+    pub torso: RagdollLimbOverrides,
+    #[reflect(display_name = "Arms: Shoulders, Upper Arms, Forearms")]
+    pub arms: RagdollLimbOverrides,
+    #[reflect(display_name = "Legs: Upper Legs, Lower Legs")]
+    pub legs: RagdollLimbOverrides,
+    #[reflect(display_name = "Extremities: Hands, Feet, Fingers, Toes")]
+    pub extremities: RagdollLimbOverrides,
+    #[reflect(display_name = "Head: Neck, Head")]
+    pub head: RagdollLimbOverrides,
+}
+
+impl RagdollLimbGroupOverrides {
+    /// Returns the overrides of the group, that the body of the bone belongs to.
+    pub fn of(&self, bone: RagdollBone) -> &RagdollLimbOverrides {
+        match bone {
+            RagdollBone::Hips | RagdollBone::Spine | RagdollBone::Spine1 | RagdollBone::Spine2 => {
+                &self.torso
+            }
+            RagdollBone::LeftShoulder
+            | RagdollBone::RightShoulder
+            | RagdollBone::LeftArm
+            | RagdollBone::RightArm
+            | RagdollBone::LeftForeArm
+            | RagdollBone::RightForeArm => &self.arms,
+            RagdollBone::LeftUpLeg
+            | RagdollBone::RightUpLeg
+            | RagdollBone::LeftLeg
+            | RagdollBone::RightLeg => &self.legs,
+            RagdollBone::Neck | RagdollBone::Head => &self.head,
+            _ => &self.extremities,
+        }
+    }
+}
+
+/// Extra chain of bones, like a tail, a ponytail or a cape, that is not a part of the humanoid
+/// skeleton. Every segment of the chain gets a capsule, that is connected to the previous segment
+/// with a ball joint.
+#[derive(Reflect, PartialEq, Clone, Debug)]
+pub struct RagdollBoneChain {
+    #[reflect(
+        description = "Name of the chain, the bodies of the segments are named after it (Tail0, Tail1 and so on). It must differ from the names of the bones and of the other chains."
+    )]
+    pub name: String,
+    #[reflect(
+        description = "The first bone of the chain. The chain is attached to the body of the closest ancestor of this bone among the humanoid bones, or to the hips."
+    )]
+    pub root: Handle<Node>,
+    #[reflect(
+        description = "The last bone of the chain, it must be under the first one. If it is not assigned, the chain follows the first children of the bones for Segment Count segments."
+    )]
+    pub end: Handle<Node>,
+    #[reflect(
+        description = "Amount of the segments, it is used only if End is not assigned.",
+        min_value = 1.0
+    )]
+    pub segment_count: usize,
+    #[reflect(
+        description = "Radius of the capsules of the segments, relative to the size of the head.",
+        min_value = 0.0
+    )]
+    pub radius: f32,
+    #[reflect(description = "Limits of the ball joints of the segments in degrees.")]
+    pub joint_limits: Range<f32>,
+}
+
+impl Default for RagdollBoneChain {
+    fn default() -> Self {
+        Self {
+            name: "Tail".to_owned(),
+            root: Default::default(),
+            end: Default::default(),
+            segment_count: 4,
+            radius: 0.15,
+            joint_limits: -30.0..30.0,
+        }
+    }
+}
+
+impl RagdollBoneChain {
+    /// Returns the bones of the chain from the root to the end, every two neighbouring bones make
+    /// a segment. The chain is empty if the root is not assigned or the end is not under the root.
+    pub fn bones(&self, graph: &Graph) -> Vec<Handle<Node>> {
+        if graph.try_get(self.root).is_none() {
+            return Vec::new();
+        }
+
+        let mut bones = Vec::new();
+        if graph.try_get(self.end).is_some() {
+            let mut bone = self.end;
+            while bone != self.root {
+                if bone.is_none() {
+                    return Vec::new();
+                }
+                bones.push(bone);
+                bone = graph[bone].parent();
+            }
+            bones.push(self.root);
+            bones.reverse();
+        } else {
+            let mut bone = self.root;
+            bones.push(bone);
+            for _ in 0..self.segment_count {
+                match graph[bone].children().first() {
+                    Some(&child) => {
+                        bone = child;
+                        bones.push(bone);
+                    }
+                    None => break,
+                }
+            }
+        }
+        bones
+    }
+
+    pub fn segment_name(&self, index: usize) -> String {
+        format!("{}{}", self.name, index)
+    }
+}
+
+/// Converts the limits in degrees to the limits of a joint in radians. The limits, that cover the
+/// whole circle, are not needed.
+pub fn joint_limits(degrees: &Range<f32>) -> Option<Range<f32>> {
+    if degrees.start <= -180.0 && degrees.end >= 180.0 {
+        None
+    } else {
+        Some(degrees.start.to_radians()..degrees.end.to_radians())
+    }
+}
+
+/// Limits of a ball joint around the axes of its frame in radians, `None` means that the rotation
+/// around the axis is not limited. Y axis of the frame goes along the bone.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct BallJointLimits {
+    pub x: Option<Range<f32>>,
+    pub y: Option<Range<f32>>,
+    pub z: Option<Range<f32>>,
+}
+
+impl BallJointLimits {
+    /// The same limits around every axis, they form a box.
+    pub fn uniform(limits: Option<Range<f32>>) -> Self {
+        Self {
+            x: limits.clone(),
+            y: limits.clone(),
+            z: limits,
+        }
+    }
+
+    /// Approximates a cone around the bone with the given half-angle by the limits of the swing
+    /// around X and Z axes, the twist limits the rotation around the bone. A swing by `a` around
+    /// both axes turns the bone by `acos(cos²(a))`, so the limits put the diagonal swings on the
+    /// cone. Cones wider than a hemisphere can't be approximated this way, their half-angle
+    /// limits both axes.
+    pub fn cone(half_angle: f32, twist: Option<Range<f32>>) -> Self {
+        let half_angle = half_angle.abs();
+        let swing = if half_angle >= std::f32::consts::PI {
+            None
+        } else if half_angle < std::f32::consts::FRAC_PI_2 {
+            let angle = half_angle.cos().sqrt().acos();
+            Some(-angle..angle)
+        } else {
+            Some(-half_angle..half_angle)
+        };
+        Self {
+            x: swing.clone(),
+            y: twist,
+            z: swing,
+        }
+    }
+}
+
+/// Axis of a child body, that hinge joints rotate around.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Reflect,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum RagdollHingeAxis {
+    X,
+    Y,
+    Z,
+}
+
+impl Default for RagdollHingeAxis {
+    fn default() -> Self {
+        Self::X
+    }
+}
+
+impl RagdollHingeAxis {
+    /// Returns the rotation of a joint relative to the body, hinge joints rotate around local X
+    /// axis of the joint.
+    pub fn joint_rotation(self) -> UnitQuaternion<f32> {
+        match self {
+            RagdollHingeAxis::X => UnitQuaternion::identity(),
+            RagdollHingeAxis::Y => {
+                UnitQuaternion::from_axis_angle(&Vector3::z_axis(), std::f32::consts::FRAC_PI_2)
+            }
+            RagdollHingeAxis::Z => {
+                UnitQuaternion::from_axis_angle(&Vector3::y_axis(), -std::f32::consts::FRAC_PI_2)
+            }
+        }
+    }
+}
+
+/// Place of a joint between the body of a bone and the body of its parent.
+#[derive(
+    Copy,
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    Reflect,
+    Serialize,
+    Deserialize,
+    AsRefStr,
+    EnumString,
+    EnumVariantNames,
+)]
+pub enum RagdollJointAnchor {
+    /// At the center of the body of the bone.
+    ChildBone,
+    /// At the center of the body of the parent bone.
+    ParentBone,
+    /// At the pivot of the bone, where it is connected to the parent bone (the anatomical joint).
+    BoneConnection,
+}
+
+impl Default for RagdollJointAnchor {
+    fn default() -> Self {
+        Self::BoneConnection
+    }
+}
+
+impl RagdollJointAnchor {
+    /// Returns the global position of the joint between the body of the bone and the body of its
+    /// parent. Missing nodes give the origin, joints are not made for missing bodies anyway.
+    pub fn joint_position(
+        self,
+        graph: &Graph,
+        bone: Handle<Node>,
+        body: Handle<Node>,
+        parent_body: Handle<Node>,
+    ) -> Vector3<f32> {
+        let anchor = match self {
+            RagdollJointAnchor::ChildBone => body,
+            RagdollJointAnchor::ParentBone => parent_body,
+            RagdollJointAnchor::BoneConnection => bone,
+        };
+        graph
+            .try_get(anchor)
+            .map(|node| node.global_position())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Reflect, Clone, PartialEq, Debug)]
+pub struct RagdollPreset {
+    pub hips: Handle<Node>,
+    pub left_up_leg: Handle<Node>,
+    pub left_leg: Handle<Node>,
+    pub left_foot: Handle<Node>,
+    pub right_up_leg: Handle<Node>,
+    pub right_leg: Handle<Node>,
+    pub right_foot: Handle<Node>,
+    pub spine: Handle<Node>,
+    pub spine1: Handle<Node>,
+    pub spine2: Handle<Node>,
+    pub left_shoulder: Handle<Node>,
+    pub left_arm: Handle<Node>,
+    pub left_fore_arm: Handle<Node>,
+    pub left_hand: Handle<Node>,
+    pub right_shoulder: Handle<Node>,
+    pub right_arm: Handle<Node>,
+    pub right_fore_arm: Handle<Node>,
+    pub right_hand: Handle<Node>,
+    pub neck: Handle<Node>,
+    pub head: Handle<Node>,
+    pub left_thumb: Handle<Node>,
+    pub left_index: Handle<Node>,
+    pub left_middle: Handle<Node>,
+    pub left_ring: Handle<Node>,
+    pub left_pinky: Handle<Node>,
+    pub right_thumb: Handle<Node>,
+    pub right_index: Handle<Node>,
+    pub right_middle: Handle<Node>,
+    pub right_ring: Handle<Node>,
+    pub right_pinky: Handle<Node>,
+    pub left_toe_base: Handle<Node>,
+    pub right_toe_base: Handle<Node>,
+    #[reflect(
+        description = "Ragdoll generates rigid bodies connected with joints. Hitboxes generates sensors under the bones, that follow the animation, the same shapes are used."
+    )]
+    pub output_mode: RagdollOutputMode,
+    #[reflect(
+        description = "Prefix of the names and the tags of the hitboxes. The tag of a hitbox is the prefix followed by the name of its bone (for example HitboxHead), so scripts could tell the damage zones apart."
+    )]
+    pub hitbox_prefix: String,
+    #[reflect(
+        description = "Node, that the generated ragdoll is linked to. The root of the scene is used, if it is not assigned. Autofill assigns the closest common ancestor of the bones."
+    )]
+    pub parent: Handle<Node>,
+    #[reflect(
+        description = "Generate bodies for the fingers and the toes. Every finger or toe gets one body from its first bone to the end of its chain, missing ones are skipped."
+    )]
+    pub include_fingers_and_toes: bool,
+    #[reflect(
+        description = "Generate bodies for the hands. Without them the arms end at the forearms, the fingers get no bodies too."
+    )]
+    pub generate_hands: bool,
+    #[reflect(
+        description = "Generate bodies for the feet. Without them the legs end at the lower legs, the toes get no bodies too."
+    )]
+    pub generate_feet: bool,
+    #[reflect(
+        description = "Chains of bones, that are not parts of the humanoid skeleton, like tails, ponytails or capes. Every segment of a chain gets a capsule, the chains get their shares of the total mass."
+    )]
+    pub extra_chains: Vec<RagdollBoneChain>,
+    #[reflect(
+        description = "Mass of the whole ragdoll, it is split between the bodies proportionally to their volumes.",
+        min_value = 0.001
+    )]
+    pub total_mass: f32,
+    #[reflect(
+        description = "Minimal mass of a body, small bodies (like hands) get at least this mass.",
+        min_value = 0.0
+    )]
+    pub min_limb_mass: f32,
+    pub proportions: RagdollProportions,
+    #[reflect(
+        description = "Friction of the colliders of the limbs.",
+        min_value = 0.0,
+        max_value = 1.0e6
+    )]
+    pub friction: f32,
+    #[reflect(
+        description = "Restitution (bounciness) of the colliders of the limbs.",
+        min_value = 0.0,
+        max_value = 1.0e6
+    )]
+    pub restitution: f32,
+    #[reflect(
+        description = "Continuous collision detection of the bodies, it prevents fast limbs from passing through thin obstacles."
+    )]
+    pub use_ccd: bool,
+    #[reflect(
+        description = "Friction, restitution and continuous collision detection of groups of limbs. Unset values are taken from the properties above, for example CCD could be enabled only for the fast extremities."
+    )]
+    pub limb_overrides: RagdollLimbGroupOverrides,
+    #[reflect(description = "Type, damping and sleeping of the generated bodies.")]
+    pub physics: RagdollPhysics,
+    #[reflect(description = "Whether the generated ragdoll is active right away.")]
+    pub active: bool,
+    pub joint_limits: RagdollJointLimits,
+    #[reflect(
+        description = "Axis of the child bodies, that the hinge joints (knees, elbows, etc.) rotate around. Change it if the hinges bend sideways."
+    )]
+    pub hinge_axis: RagdollHingeAxis,
+    #[reflect(
+        display_name = "Joint Anchor (Child Bone, Parent Bone, Bone Connection)",
+        description = "Place of the joints. Child Bone and Parent Bone put a joint at the center of the body of the bone or of its parent, Bone Connection puts it at the pivot of the bone, where it is connected to the parent (the anatomical joint)."
+    )]
+    pub joint_anchor: RagdollJointAnchor,
+    #[reflect(description = "Collision groups, that the colliders of the limbs are members of.")]
+    pub collision_groups: BitMask,
+    #[reflect(description = "Collision groups, that the colliders of the limbs collide with.")]
+    pub collision_mask: BitMask,
+    #[reflect(
+        description = "Removes Collision Groups from Collision Mask of the limbs, so the limbs don't collide with each other. Collision Groups must contain only the groups, that are used by ragdolls."
+    )]
+    pub disable_self_collision: bool,
+    #[reflect(
+        description = "Generated bodies, that penetrate each other deeper than this value, will be reported after generation. Bodies connected with a joint are ignored.",
+        min_value = 0.0
+    )]
+    pub overlap_threshold: f32,
+}
+
+impl Default for RagdollPreset {
+    fn default() -> Self {
+        Self {
+            hips: Default::default(),
+            left_up_leg: Default::default(),
+            left_leg: Default::default(),
+            left_foot: Default::default(),
+            right_up_leg: Default::default(),
+            right_leg: Default::default(),
+            right_foot: Default::default(),
+            spine: Default::default(),
+            spine1: Default::default(),
+            spine2: Default::default(),
+            left_shoulder: Default::default(),
+            left_arm: Default::default(),
+            left_fore_arm: Default::default(),
+            left_hand: Default::default(),
+            right_shoulder: Default::default(),
+            right_arm: Default::default(),
+            right_fore_arm: Default::default(),
+            right_hand: Default::default(),
+            neck: Default::default(),
+            head: Default::default(),
+            left_thumb: Default::default(),
+            left_index: Default::default(),
+            left_middle: Default::default(),
+            left_ring: Default::default(),
+            left_pinky: Default::default(),
+            right_thumb: Default::default(),
+            right_index: Default::default(),
+            right_middle: Default::default(),
+            right_ring: Default::default(),
+            right_pinky: Default::default(),
+            left_toe_base: Default::default(),
+            right_toe_base: Default::default(),
+            output_mode: Default::default(),
+            hitbox_prefix: "Hitbox".to_owned(),
+            parent: Default::default(),
+            include_fingers_and_toes: false,
+            generate_hands: true,
+            generate_feet: true,
+            extra_chains: Vec::new(),
+            total_mass: 20.0,
+            min_limb_mass: 0.5,
+            proportions: Default::default(),
+            friction: 0.5,
+            restitution: 0.0,
+            use_ccd: true,
+            limb_overrides: Default::default(),
+            physics: Default::default(),
+            active: true,
+            joint_limits: Default::default(),
+            hinge_axis: Default::default(),
+            joint_anchor: Default::default(),
+            collision_groups: BitMask(u32::MAX),
+            collision_mask: BitMask(u32::MAX),
+            disable_self_collision: false,
+            overlap_threshold: 0.01,
+        }
+    }
+}
+
+/// Ragdoll preset, that is stored in a file. Bones are stored by their paths under the character
+/// root instead of handles, so the preset could be applied to any character with the same skeleton.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct RagdollPresetFile {
+    /// Paths of the bones by the names of the bones (see [`RagdollBone`]). Missing bones are not
+    /// stored.
+    pub bones: BTreeMap<String, String>,
+    /// Path of the parent of the ragdoll, empty path is the character root. `None` means the root
+    /// of the scene.
+    #[serde(default)]
+    pub parent: Option<String>,
+    #[serde(default)]
+    pub output_mode: RagdollOutputMode,
+    #[serde(default = "default_hitbox_prefix")]
+    pub hitbox_prefix: String,
+    #[serde(default)]
+    pub include_fingers_and_toes: bool,
+    #[serde(default = "default_generate_hands")]
+    pub generate_hands: bool,
+    #[serde(default = "default_generate_feet")]
+    pub generate_feet: bool,
+    #[serde(default)]
+    pub extra_chains: Vec<RagdollBoneChainFile>,
+    pub total_mass: f32,
+    // Presets, that were saved before the minimal mass was added, get the default one.
+    #[serde(default = "default_min_limb_mass")]
+    pub min_limb_mass: f32,
+    #[serde(default)]
+    pub proportions: RagdollProportions,
+    pub friction: f32,
+    #[serde(default)]
+    pub restitution: f32,
+    pub use_ccd: bool,
+    #[serde(default)]
+    pub limb_overrides: RagdollLimbGroupOverrides,
+    #[serde(default)]
+    pub body_type: RagdollBodyType,
+    #[serde(default)]
+    pub kinematic_kind: RagdollKinematicKind,
+    #[serde(default = "default_linear_damping")]
+    pub linear_damping: f32,
+    #[serde(default = "default_angular_damping")]
+    pub angular_damping: f32,
+    #[serde(default = "default_can_sleep")]
+    pub can_sleep: bool,
+    #[serde(default = "default_active")]
+    pub active: bool,
+    #[serde(default)]
+    pub joint_limits: RagdollJointLimits,
+    #[serde(default)]
+    pub hinge_axis: RagdollHingeAxis,
+    #[serde(default)]
+    pub joint_anchor: RagdollJointAnchor,
+    #[serde(default = "default_collision_groups")]
+    pub collision_groups: u32,
+    #[serde(default = "default_collision_mask")]
+    pub collision_mask: u32,
+    #[serde(default)]
+    pub disable_self_collision: bool,
+    pub overlap_threshold: f32,
+}
+
+/// Extra bone chain, that is stored in a file. The bones are stored by their paths under the
+/// character root, like the humanoid bones.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+pub struct RagdollBoneChainFile {
+    pub name: String,
+    pub root: Option<String>,
+    #[serde(default)]
+    pub end: Option<String>,
+    pub segment_count: usize,
+    pub radius: f32,
+    pub joint_limits: Range<f32>,
+}
+
+fn default_min_limb_mass() -> f32 {
+    RagdollPreset::default().min_limb_mass
+}
+
+fn default_linear_damping() -> f32 {
+    RagdollPhysics::default().linear_damping
+}
+
+fn default_angular_damping() -> f32 {
+    RagdollPhysics::default().angular_damping
+}
+
+fn default_can_sleep() -> bool {
+    RagdollPhysics::default().can_sleep
+}
+
+fn default_active() -> bool {
+    RagdollPreset::default().active
+}
+
+fn default_generate_hands() -> bool {
+    RagdollPreset::default().generate_hands
+}
+
+fn default_generate_feet() -> bool {
+    RagdollPreset::default().generate_feet
+}
+
+fn default_hitbox_prefix() -> String {
+    RagdollPreset::default().hitbox_prefix
+}
+
+fn default_collision_groups() -> u32 {
+    RagdollPreset::default().collision_groups.0
+}
+
+fn default_collision_mask() -> u32 {
+    RagdollPreset::default().collision_mask.0
+}
+
+impl RagdollPreset {
+    pub fn bone(&self, bone: RagdollBone) -> Handle<Node> {
+        match bone {
+            RagdollBone::Hips => self.hips,
+            RagdollBone::LeftUpLeg => self.left_up_leg,
+            RagdollBone::LeftLeg => self.left_leg,
+            RagdollBone::LeftFoot => self.left_foot,
+            RagdollBone::RightUpLeg => self.right_up_leg,
+            RagdollBone::RightLeg => self.right_leg,
+            RagdollBone::RightFoot => self.right_foot,
+            RagdollBone::Spine => self.spine,
+            RagdollBone::Spine1 => self.spine1,
+            RagdollBone::Spine2 => self.spine2,
+            RagdollBone::LeftShoulder => self.left_shoulder,
+            RagdollBone::LeftArm => self.left_arm,
+            RagdollBone::LeftForeArm => self.left_fore_arm,
+            RagdollBone::LeftHand => self.left_hand,
+            RagdollBone::RightShoulder => self.right_shoulder,
+            RagdollBone::RightArm => self.right_arm,
+            RagdollBone::RightForeArm => self.right_fore_arm,
+            RagdollBone::RightHand => self.right_hand,
+            RagdollBone::Neck => self.neck,
+            RagdollBone::Head => self.head,
+            RagdollBone::LeftThumb => self.left_thumb,
+            RagdollBone::LeftIndex => self.left_index,
+            RagdollBone::LeftMiddle => self.left_middle,
+            RagdollBone::LeftRing => self.left_ring,
+            RagdollBone::LeftPinky => self.left_pinky,
+            RagdollBone::RightThumb => self.right_thumb,
+            RagdollBone::RightIndex => self.right_index,
+            RagdollBone::RightMiddle => self.right_middle,
+            RagdollBone::RightRing => self.right_ring,
+            RagdollBone::RightPinky => self.right_pinky,
+            RagdollBone::LeftToeBase => self.left_toe_base,
+            RagdollBone::RightToeBase => self.right_toe_base,
+        }
+    }
+
+    pub fn bone_mut(&mut self, bone: RagdollBone) -> &mut Handle<Node> {
+        match bone {
+            RagdollBone::Hips => &mut self.hips,
+            RagdollBone::LeftUpLeg => &mut self.left_up_leg,
+            RagdollBone::LeftLeg => &mut self.left_leg,
+            RagdollBone::LeftFoot => &mut self.left_foot,
+            RagdollBone::RightUpLeg => &mut self.right_up_leg,
+            RagdollBone::RightLeg => &mut self.right_leg,
+            RagdollBone::RightFoot => &mut self.right_foot,
+            RagdollBone::Spine => &mut self.spine,
+            RagdollBone::Spine1 => &mut self.spine1,
+            RagdollBone::Spine2 => &mut self.spine2,
+            RagdollBone::LeftShoulder => &mut self.left_shoulder,
+            RagdollBone::LeftArm => &mut self.left_arm,
+            RagdollBone::LeftForeArm => &mut self.left_fore_arm,
+            RagdollBone::LeftHand => &mut self.left_hand,
+            RagdollBone::RightShoulder => &mut self.right_shoulder,
+            RagdollBone::RightArm => &mut self.right_arm,
+            RagdollBone::RightForeArm => &mut self.right_fore_arm,
+            RagdollBone::RightHand => &mut self.right_hand,
+            RagdollBone::Neck => &mut self.neck,
+            RagdollBone::Head => &mut self.head,
+            RagdollBone::LeftThumb => &mut self.left_thumb,
+            RagdollBone::LeftIndex => &mut self.left_index,
+            RagdollBone::LeftMiddle => &mut self.left_middle,
+            RagdollBone::LeftRing => &mut self.left_ring,
+            RagdollBone::LeftPinky => &mut self.left_pinky,
+            RagdollBone::RightThumb => &mut self.right_thumb,
+            RagdollBone::RightIndex => &mut self.right_index,
+            RagdollBone::RightMiddle => &mut self.right_middle,
+            RagdollBone::RightRing => &mut self.right_ring,
+            RagdollBone::RightPinky => &mut self.right_pinky,
+            RagdollBone::LeftToeBase => &mut self.left_toe_base,
+            RagdollBone::RightToeBase => &mut self.right_toe_base,
+        }
+    }
+
+    /// Returns the assigned bones, that are not descendants of the assigned hips. Such bones most
+    /// likely belong to another character, the ragdoll can't be built correctly with them.
+    pub fn bones_outside_hips(&self, graph: &Graph) -> Vec<RagdollBone> {
+        if graph.try_get(self.hips).is_none() {
+            return Vec::new();
+        }
+
+        RagdollBone::ALL
+            .iter()
+            .filter(|&&bone| {
+                let handle = self.bone(bone);
+                bone != RagdollBone::Hips
+                    && graph.try_get(handle).is_some()
+                    && !is_descendant_of(graph, handle, self.hips)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Assigns the bones of the character with the given root by their names. Returns the bones,
+    /// that were not found.
+    pub fn autofill(
+        &mut self,
+        graph: &Graph,
+        root: Handle<Node>,
+        patterns: &RagdollBonePatterns,
+    ) -> Vec<RagdollBone> {
+        let candidates = collect_bone_candidates(graph, root);
+
+        let mut unresolved = Vec::new();
+        for (bone, handle) in find_bones(&candidates, patterns) {
+            // Many characters have no fingers and toes, they're optional.
+            if handle.is_none() && bone.digit_parent().is_none() {
+                unresolved.push(bone);
+            }
+            *self.bone_mut(bone) = handle;
+        }
+        self.parent = self.default_parent(graph);
+        unresolved
+    }
+
+    fn assigned_bones<'a>(&'a self, graph: &'a Graph) -> impl Iterator<Item = Handle<Node>> + 'a {
+        RagdollBone::ALL
+            .iter()
+            .map(move |&bone| self.bone(bone))
+            .filter(move |&handle| graph.try_get(handle).is_some())
+    }
+
+    /// Returns the closest common ancestor of the assigned bones, that is not a bone itself. The
+    /// ragdoll could be linked to it, so it moves together with the character.
+    pub fn default_parent(&self, graph: &Graph) -> Handle<Node> {
+        let bones = self.assigned_bones(graph).collect::<Vec<_>>();
+        let mut parent = common_ancestor(graph, &bones);
+        while parent.is_some() && bones.contains(&parent) {
+            parent = graph[parent].parent();
+        }
+        parent
+    }
+
+    /// Returns the node, that the ragdoll must be linked to. Ragdoll moves the bones, so it can't
+    /// be linked to a bone (or a descendant of a bone), as well as to the nodes, that are
+    /// replaced.
+    pub fn ragdoll_parent(
+        &self,
+        graph: &Graph,
+        scene_root: Handle<Node>,
+        replace: &[Handle<Node>],
+    ) -> Result<Handle<Node>, String> {
+        if graph.try_get(self.parent).is_none() {
+            return Ok(scene_root);
+        }
+
+        let is_under = |ancestor: Handle<Node>| {
+            self.parent == ancestor || is_descendant_of(graph, self.parent, ancestor)
+        };
+        if let Some(bone) = RagdollBone::ALL.iter().find(|&&bone| {
+            let handle = self.bone(bone);
+            graph.try_get(handle).is_some() && is_under(handle)
+        }) {
+            Err(format!(
+                "the parent is moved by the bone {}, it would be moved by the ragdoll itself.",
+                bone.as_ref()
+            ))
+        } else if replace.iter().any(|&replace| is_under(replace)) {
+            Err("the parent belongs to the replaced ragdoll.".to_owned())
+        } else {
+            Ok(self.parent)
+        }
+    }
+
+    /// Resets every setting of the preset to its default value, the bones, the extra chains and
+    /// the parent are kept.
+    pub fn reset_to_defaults(&mut self) {
+        let mut preset = RagdollPreset {
+            parent: self.parent,
+            extra_chains: self.extra_chains.clone(),
+            ..Default::default()
+        };
+        for bone in RagdollBone::ALL {
+            *preset.bone_mut(bone) = self.bone(bone);
+        }
+        *self = preset;
+    }
+
+    /// Unassigns every bone, the parent and the bones of the extra chains, the settings are kept.
+    pub fn clear_bones(&mut self) {
+        for bone in RagdollBone::ALL {
+            *self.bone_mut(bone) = Handle::NONE;
+        }
+        self.parent = Handle::NONE;
+        for chain in self.extra_chains.iter_mut() {
+            chain.root = Handle::NONE;
+            chain.end = Handle::NONE;
+        }
+    }
+
+    /// Assigns the bones of one side of the body by the assigned bones of the other side. Mirrored
+    /// bones are found by their paths (`candidates` must contain the assigned bones), so they have
+    /// the same parent chain. Returns the bones, that are assigned, but can't be mirrored, their
+    /// counterparts are not changed.
+    pub fn mirror(
+        &mut self,
+        candidates: &[BoneCandidate],
+        tokens: &[(String, String)],
+        left_to_right: bool,
+    ) -> Vec<RagdollBone> {
+        let mut failed = Vec::new();
+        for bone in RagdollBone::ALL {
+            let target = match bone.mirrored() {
+                Some(target) if bone.is_left() == left_to_right => target,
+                _ => continue,
+            };
+
+            let handle = self.bone(bone);
+            if handle.is_none() {
+                continue;
+            }
+
+            let mirrored = candidates
+                .iter()
+                .find(|candidate| candidate.handle == handle)
+                .and_then(|candidate| mirror_path(&candidate.path, tokens, left_to_right))
+                .and_then(|path| {
+                    candidates
+                        .iter()
+                        .find(|candidate| candidate.path == path)
+                        .map(|candidate| candidate.handle)
+                });
+
+            match mirrored {
+                Some(mirrored) => *self.bone_mut(target) = mirrored,
+                None => failed.push(bone),
+            }
+        }
+        failed
+    }
+
+    /// Converts the preset to the file representation. Bones, that are not under the character
+    /// root (`candidates` are collected from it), are not stored.
+    pub fn to_file(&self, candidates: &[BoneCandidate]) -> RagdollPresetFile {
+        let bones = RagdollBone::ALL
+            .iter()
+            .filter_map(|&bone| {
+                let handle = self.bone(bone);
+                candidates
+                    .iter()
+                    .find(|candidate| handle.is_some() && candidate.handle == handle)
+                    .map(|candidate| (bone.as_ref().to_owned(), candidate.path.clone()))
+            })
+            .collect();
+
+        let path_of = |handle: Handle<Node>| {
+            candidates
+                .iter()
+                .find(|candidate| handle.is_some() && candidate.handle == handle)
+                .map(|candidate| candidate.path.clone())
+        };
+
+        RagdollPresetFile {
+            bones,
+            parent: path_of(self.parent),
+            output_mode: self.output_mode,
+            hitbox_prefix: self.hitbox_prefix.clone(),
+            include_fingers_and_toes: self.include_fingers_and_toes,
+            generate_hands: self.generate_hands,
+            generate_feet: self.generate_feet,
+            extra_chains: self
+                .extra_chains
+                .iter()
+                .map(|chain| RagdollBoneChainFile {
+                    name: chain.name.clone(),
+                    root: path_of(chain.root),
+                    end: path_of(chain.end),
+                    segment_count: chain.segment_count,
+                    radius: chain.radius,
+                    joint_limits: chain.joint_limits.clone(),
+                })
+                .collect(),
+            total_mass: self.total_mass,
+            min_limb_mass: self.min_limb_mass,
+            proportions: self.proportions.clone(),
+            friction: self.friction,
+            restitution: self.restitution,
+            use_ccd: self.use_ccd,
+            limb_overrides: self.limb_overrides.clone(),
+            body_type: self.physics.body_type,
+            kinematic_kind: self.physics.kinematic_kind,
+            linear_damping: self.physics.linear_damping,
+            angular_damping: self.physics.angular_damping,
+            can_sleep: self.physics.can_sleep,
+            active: self.active,
+            joint_limits: self.joint_limits.clone(),
+            hinge_axis: self.hinge_axis,
+            joint_anchor: self.joint_anchor,
+            collision_groups: self.collision_groups.0,
+            collision_mask: self.collision_mask.0,
+            disable_self_collision: self.disable_self_collision,
+            overlap_threshold: self.overlap_threshold,
+        }
+    }
+
+    /// Replaces the preset with the one from the file, bones are resolved by their paths under
+    /// the character root (`candidates` are collected from it). Returns the descriptions of the
+    /// bones (including the bones of the extra chains), that were not resolved.
+    pub fn apply_file(
+        &mut self,
+        file: &RagdollPresetFile,
+        candidates: &[BoneCandidate],
+    ) -> Vec<String> {
+        let mut unresolved = Vec::new();
+
+        for bone in RagdollBone::ALL {
+            let handle = match file.bones.get(bone.as_ref()) {
+                Some(path) => {
+                    let handle = resolve_bone_path(candidates, path);
+                    if handle.is_none() {
+                        unresolved.push(format!("{} ({})", bone.as_ref(), path));
+                    }
+                    handle
+                }
+                None => Handle::NONE,
+            };
+            *self.bone_mut(bone) = handle;
+        }
+
+        for name in file.bones.keys() {
+            if name.parse::<RagdollBone>().is_err() {
+                unresolved.push(format!("{} (unknown bone)", name));
+            }
+        }
+
+        self.parent = match file.parent.as_deref() {
+            Some("") => candidates
+                .iter()
+                .find(|candidate| candidate.depth == 0)
+                .map(|candidate| candidate.handle)
+                .unwrap_or_default(),
+            Some(path) => {
+                let handle = resolve_bone_path(candidates, path);
+                if handle.is_none() {
+                    unresolved.push(format!("Parent ({})", path));
+                }
+                handle
+            }
+            None => Handle::NONE,
+        };
+        self.output_mode = file.output_mode;
+        self.hitbox_prefix = file.hitbox_prefix.clone();
+        self.include_fingers_and_toes = file.include_fingers_and_toes;
+        self.generate_hands = file.generate_hands;
+        self.generate_feet = file.generate_feet;
+        self.extra_chains = file
+            .extra_chains
+            .iter()
+            .map(|chain| {
+                let mut resolve = |path: &Option<String>, what: &str| match path {
+                    Some(path) => {
+                        let handle = resolve_bone_path(candidates, path);
+                        if handle.is_none() {
+                            unresolved.push(format!("{} {} ({})", chain.name, what, path));
+                        }
+                        handle
+                    }
+                    None => Handle::NONE,
+                };
+                RagdollBoneChain {
+                    name: chain.name.clone(),
+                    root: resolve(&chain.root, "root"),
+                    end: resolve(&chain.end, "end"),
+                    segment_count: chain.segment_count,
+                    radius: chain.radius,
+                    joint_limits: chain.joint_limits.clone(),
+                }
+            })
+            .collect();
+        self.total_mass = file.total_mass;
+        self.min_limb_mass = file.min_limb_mass;
+        self.proportions = file.proportions.clone();
+        self.friction = file.friction;
+        self.restitution = file.restitution;
+        self.use_ccd = file.use_ccd;
+        self.limb_overrides = file.limb_overrides.clone();
+        self.physics = RagdollPhysics {
+            body_type: file.body_type,
+            kinematic_kind: file.kinematic_kind,
+            linear_damping: file.linear_damping,
+            angular_damping: file.angular_damping,
+            can_sleep: file.can_sleep,
+        };
+        self.active = file.active;
+        self.joint_limits = file.joint_limits.clone();
+        self.hinge_axis = file.hinge_axis;
+        self.joint_anchor = file.joint_anchor;
+        self.collision_groups = BitMask(file.collision_groups);
+        self.collision_mask = BitMask(file.collision_mask);
+        self.disable_self_collision = file.disable_self_collision;
+        self.overlap_threshold = file.overlap_threshold;
+
+        unresolved
+    }
+
+    /// Returns the collision groups of the colliders of the limbs.
+    pub fn limb_collision_groups(&self) -> InteractionGroups {
+        if self.disable_self_collision {
+            // Every limb is a member of the same groups, so the limbs can't collide with each other
+            // when these groups are filtered out.
+            InteractionGroups::new(
+                self.collision_groups,
+                self.collision_mask & !self.collision_groups,
+            )
+        } else {
+            InteractionGroups::new(self.collision_groups, self.collision_mask)
+        }
+    }
+
+    /// Returns the friction of the collider of the bone, the override of its group is preferred.
+    pub fn limb_friction(&self, bone: RagdollBone) -> f32 {
+        self.limb_overrides
+            .of(bone)
+            .friction
+            .unwrap_or(self.friction)
+    }
+
+    /// Returns the restitution of the collider of the bone, the override of its group is
+    /// preferred.
+    pub fn limb_restitution(&self, bone: RagdollBone) -> f32 {
+        self.limb_overrides
+            .of(bone)
+            .restitution
+            .unwrap_or(self.restitution)
+    }
+
+    /// Returns true if the body of the bone uses continuous collision detection, the override of
+    /// its group is preferred.
+    pub fn limb_uses_ccd(&self, bone: RagdollBone) -> bool {
+        self.limb_overrides.of(bone).use_ccd.unwrap_or(self.use_ccd)
+    }
+
+    /// Returns whether the bone gets a body, if it is assigned. Hands and feet could be excluded,
+    /// fingers and toes are excluded with them.
+    pub fn generates_body(&self, bone: RagdollBone) -> bool {
+        let bone = match bone.digit_parent() {
+            Some(parent) if self.include_fingers_and_toes => parent,
+            Some(_) => return false,
+            None => bone,
+        };
+        match bone {
+            RagdollBone::LeftHand | RagdollBone::RightHand => self.generate_hands,
+            RagdollBone::LeftFoot | RagdollBone::RightFoot => self.generate_feet,
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        settings::ragdoll::RagdollSettings,
+        utils::ragdoll::{
+            bones::parse_mirror_tokens,
+            test::{candidate_at, make_bone},
+        },
+    };
+    use ron::ser::PrettyConfig;
+    #[test]
+    fn test_preset_file_round_trip() {
+        let candidates = [
+            candidate_at(1, ""),
+            candidate_at(2, "Armature/Hips"),
+            candidate_at(3, "Armature/Hips/Spine"),
+            candidate_at(4, "Armature/Hips/Spine/Spine1/Head"),
+            candidate_at(5, "Armature/Hips/Tail"),
+        ];
+
+        let mut preset = RagdollPreset {
+            hips: Handle::new(2, 1),
+            spine: Handle::new(3, 1),
+            head: Handle::new(4, 1),
+            // Not under the character root, so it is not saved.
+            left_hand: Handle::new(100, 1),
+            // The character root.
+            parent: Handle::new(1, 1),
+            total_mass: 0.1 + 0.2,
+            min_limb_mass: 0.01,
+            friction: 1.0 / 3.0,
+            restitution: 0.2,
+            use_ccd: false,
+            limb_overrides: RagdollLimbGroupOverrides {
+                extremities: RagdollLimbOverrides {
+                    use_ccd: Some(true),
+                    friction: Some(0.9),
+                    restitution: None,
+                },
+                ..Default::default()
+            },
+            physics: RagdollPhysics {
+                body_type: RagdollBodyType::Dynamic,
+                kinematic_kind: RagdollKinematicKind::VelocityBased,
+                linear_damping: 0.25,
+                can_sleep: false,
+                ..Default::default()
+            },
+            active: false,
+            joint_limits: RagdollJointLimits {
+                knees: -130.0..0.0,
+                ..Default::default()
+            },
+            hinge_axis: RagdollHingeAxis::Z,
+            joint_anchor: RagdollJointAnchor::ParentBone,
+            generate_feet: false,
+            output_mode: RagdollOutputMode::Hitboxes,
+            hitbox_prefix: "Zone".to_owned(),
+            collision_groups: BitMask(0b0100),
+            disable_self_collision: true,
+            overlap_threshold: 0.0123,
+            extra_chains: vec![RagdollBoneChain {
+                root: Handle::new(5, 1),
+                segment_count: 6,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let file = preset.to_file(&candidates);
+        assert_eq!(file.bones.len(), 3);
+        assert_eq!(
+            file.extra_chains[0].root.as_deref(),
+            Some("Armature/Hips/Tail")
+        );
+        assert_eq!(file.extra_chains[0].end, None);
+        assert_eq!(file.bones["Spine"], "Armature/Hips/Spine");
+        assert_eq!(file.parent.as_deref(), Some(""));
+
+        let text = ron::ser::to_string_pretty(&file, PrettyConfig::default()).unwrap();
+        let loaded = ron::from_str::<RagdollPresetFile>(&text).unwrap();
+        // Floats are stored exactly.
+        assert_eq!(loaded, file);
+
+        // The character has a different hierarchy, the head is found by its name.
+        let other = [
+            candidate_at(10, ""),
+            candidate_at(11, "Armature/Hips"),
+            candidate_at(12, "Armature/Hips/Head"),
+        ];
+        let mut edited = loaded.clone();
+        edited
+            .bones
+            .insert("Tail".to_owned(), "Armature/Hips/Tail".to_owned());
+        preset.left_hand = Handle::new(100, 1);
+
+        let unresolved = preset.apply_file(&edited, &other);
+        assert_eq!(
+            unresolved,
+            vec![
+                "Spine (Armature/Hips/Spine)".to_owned(),
+                "Tail (unknown bone)".to_owned(),
+                "Tail root (Armature/Hips/Tail)".to_owned()
+            ]
+        );
+        assert_eq!(preset.extra_chains.len(), 1);
+        assert_eq!(preset.extra_chains[0].root, Handle::NONE);
+        assert_eq!(preset.extra_chains[0].segment_count, 6);
+        assert_eq!(preset.hips, Handle::new(11, 1));
+        assert_eq!(preset.spine, Handle::NONE);
+        assert_eq!(preset.head, Handle::new(12, 1));
+        assert_eq!(preset.left_hand, Handle::NONE);
+        assert_eq!(preset.parent, Handle::new(10, 1));
+        assert_eq!(preset.total_mass, 0.1 + 0.2);
+        assert_eq!(preset.min_limb_mass, 0.01);
+        assert_eq!(preset.friction, 1.0 / 3.0);
+        assert_eq!(preset.restitution, 0.2);
+        assert!(!preset.use_ccd);
+        assert_eq!(preset.limb_overrides.extremities.friction, Some(0.9));
+        assert_eq!(preset.limb_overrides.torso, RagdollLimbOverrides::default());
+        assert_eq!(preset.physics.body_type, RagdollBodyType::Dynamic);
+        assert_eq!(
+            preset.physics.kinematic_kind,
+            RagdollKinematicKind::VelocityBased
+        );
+        assert_eq!(preset.physics.linear_damping, 0.25);
+        assert!(!preset.physics.can_sleep);
+        assert!(!preset.active);
+        assert_eq!(preset.collision_groups, BitMask(0b0100));
+        assert_eq!(preset.collision_mask, BitMask(u32::MAX));
+        assert!(preset.disable_self_collision);
+        assert_eq!(preset.joint_limits.knees, -130.0..0.0);
+        assert_eq!(preset.joint_limits.elbows, 0.0..145.0);
+        assert_eq!(preset.hinge_axis, RagdollHingeAxis::Z);
+        assert_eq!(preset.joint_anchor, RagdollJointAnchor::ParentBone);
+        assert!(preset.generate_hands);
+        assert!(!preset.generate_feet);
+        assert_eq!(preset.output_mode, RagdollOutputMode::Hitboxes);
+        assert_eq!(preset.hitbox_prefix, "Zone");
+        assert_eq!(preset.overlap_threshold, 0.0123);
+    }
+
+    #[test]
+    fn test_clear_bones() {
+        let mut preset = RagdollPreset {
+            hips: Handle::new(1, 1),
+            left_hand: Handle::new(2, 1),
+            parent: Handle::new(3, 1),
+            total_mass: 42.0,
+            extra_chains: vec![RagdollBoneChain {
+                root: Handle::new(4, 1),
+                end: Handle::new(5, 1),
+                radius: 0.3,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let saved = preset.clone();
+
+        preset.clear_bones();
+        assert_ne!(preset, saved);
+        assert!(RagdollBone::ALL
+            .iter()
+            .all(|&bone| preset.bone(bone).is_none()));
+        assert!(preset.parent.is_none());
+        assert_eq!(preset.total_mass, 42.0);
+        assert_eq!(preset.extra_chains.len(), 1);
+        assert!(preset.extra_chains[0].root.is_none());
+        assert!(preset.extra_chains[0].end.is_none());
+        assert_eq!(preset.extra_chains[0].radius, 0.3);
+    }
+
+    #[test]
+    fn test_limb_overrides() {
+        let preset = RagdollPreset {
+            friction: 0.5,
+            restitution: 0.1,
+            use_ccd: false,
+            limb_overrides: RagdollLimbGroupOverrides {
+                extremities: RagdollLimbOverrides {
+                    use_ccd: Some(true),
+                    friction: Some(1.0),
+                    restitution: None,
+                },
+                head: RagdollLimbOverrides {
+                    use_ccd: Some(true),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        for bone in [
+            RagdollBone::LeftFoot,
+            RagdollBone::RightHand,
+            RagdollBone::LeftIndex,
+        ] {
+            assert!(preset.limb_uses_ccd(bone));
+            assert_eq!(preset.limb_friction(bone), 1.0);
+            // Unset values are taken from the preset.
+            assert_eq!(preset.limb_restitution(bone), 0.1);
+        }
+        assert!(preset.limb_uses_ccd(RagdollBone::Head));
+        assert_eq!(preset.limb_friction(RagdollBone::Head), 0.5);
+        for bone in [RagdollBone::Hips, RagdollBone::Spine2, RagdollBone::LeftLeg] {
+            assert!(!preset.limb_uses_ccd(bone));
+            assert_eq!(preset.limb_friction(bone), 0.5);
+        }
+    }
+
+    #[test]
+    fn test_last_preset_keeps_no_bones() {
+        let mut preset = RagdollPreset {
+            hips: Handle::new(2, 1),
+            parent: Handle::new(1, 1),
+            total_mass: 42.0,
+            output_mode: RagdollOutputMode::Hitboxes,
+            ..Default::default()
+        };
+
+        // The wizard stores the preset without the candidates, so no bones are stored.
+        let mut settings = RagdollSettings {
+            last_preset: Some(preset.to_file(&[])),
+            ..Default::default()
+        };
+        let text = ron::ser::to_string_pretty(&settings, PrettyConfig::default()).unwrap();
+        settings = ron::from_str(&text).unwrap();
+        let file = settings.last_preset.unwrap();
+        assert!(file.bones.is_empty());
+        assert_eq!(file.parent, None);
+
+        let mut restored = RagdollPreset::default();
+        assert!(restored.apply_file(&file, &[]).is_empty());
+        assert_eq!(restored.hips, Handle::NONE);
+        assert_eq!(restored.total_mass, 42.0);
+        assert_eq!(restored.output_mode, RagdollOutputMode::Hitboxes);
+
+        preset.reset_to_defaults();
+        assert_eq!(preset.hips, Handle::new(2, 1));
+        assert_eq!(preset.parent, Handle::new(1, 1));
+        assert_eq!(preset.total_mass, RagdollPreset::default().total_mass);
+        assert_eq!(preset.output_mode, RagdollOutputMode::Ragdoll);
+    }
+
+    #[test]
+    fn test_old_preset_file_gets_defaults() {
+        // A preset, that was saved before the body type was added.
+        let file = ron::from_str::<RagdollPresetFile>(
+            "(bones: {}, total_mass: 10.0, friction: 0.5, use_ccd: true, overlap_threshold: 0.01)",
+        )
+        .unwrap();
+        let default = RagdollPreset::default();
+        assert_eq!(file.body_type, RagdollBodyType::Kinematic);
+        assert_eq!(file.linear_damping, default.physics.linear_damping);
+        assert_eq!(file.angular_damping, default.physics.angular_damping);
+        assert_eq!(file.kinematic_kind, RagdollKinematicKind::PositionBased);
+        assert!(file.can_sleep);
+        assert!(file.active);
+        assert_eq!(file.collision_groups, u32::MAX);
+        assert_eq!(file.collision_mask, u32::MAX);
+        assert!(!file.disable_self_collision);
+        assert_eq!(file.joint_limits, RagdollJointLimits::default());
+        assert_eq!(file.hinge_axis, RagdollHingeAxis::X);
+        assert_eq!(file.joint_anchor, RagdollJointAnchor::BoneConnection);
+        assert!(file.generate_hands);
+        assert!(file.generate_feet);
+        assert_eq!(file.min_limb_mass, default.min_limb_mass);
+        assert_eq!(file.output_mode, RagdollOutputMode::Ragdoll);
+        assert_eq!(file.hitbox_prefix, "Hitbox");
+        assert_eq!(file.restitution, 0.0);
+        assert_eq!(file.limb_overrides, RagdollLimbGroupOverrides::default());
+    }
+
+    #[test]
+    fn test_physics_body_types() {
+        let mut physics = RagdollPhysics::default();
+        // Defaults give the same bodies as before the kinematic kind was added.
+        assert_eq!(
+            physics.rigid_body_type(),
+            RigidBodyType::KinematicPositionBased
+        );
+        assert!(physics.can_sleep);
+
+        physics.kinematic_kind = RagdollKinematicKind::VelocityBased;
+        assert_eq!(
+            physics.rigid_body_type(),
+            RigidBodyType::KinematicVelocityBased
+        );
+        physics.body_type = RagdollBodyType::Dynamic;
+        assert_eq!(physics.rigid_body_type(), RigidBodyType::Dynamic);
+    }
+
+    #[test]
+    fn test_limb_collision_groups() {
+        let mut preset = RagdollPreset {
+            collision_groups: BitMask(0b0010),
+            collision_mask: BitMask(0b1011),
+            ..Default::default()
+        };
+        assert_eq!(
+            preset.limb_collision_groups(),
+            InteractionGroups::new(BitMask(0b0010), BitMask(0b1011))
+        );
+
+        preset.disable_self_collision = true;
+        let groups = preset.limb_collision_groups();
+        assert_eq!(
+            groups,
+            InteractionGroups::new(BitMask(0b0010), BitMask(0b1001))
+        );
+        // Two limbs don't interact with each other.
+        assert_eq!(groups.memberships & groups.filter, BitMask(0));
+    }
+
+    #[test]
+    fn test_bones_outside_hips() {
+        let mut graph = Graph::new();
+        let hips = make_bone(&mut graph, Vector3::default());
+        let spine = make_bone(&mut graph, Vector3::default());
+        let head = make_bone(&mut graph, Vector3::default());
+        let other_hand = make_bone(&mut graph, Vector3::default());
+        graph.link_nodes(spine, hips);
+        graph.link_nodes(head, spine);
+
+        let mut preset = RagdollPreset {
+            spine,
+            head,
+            left_hand: other_hand,
+            ..Default::default()
+        };
+        // Nothing can be checked without the hips.
+        assert!(preset.bones_outside_hips(&graph).is_empty());
+
+        preset.hips = hips;
+        assert_eq!(
+            preset.bones_outside_hips(&graph),
+            vec![RagdollBone::LeftHand]
+        );
+
+        // Hips of another character.
+        preset.hips = other_hand;
+        preset.left_hand = Handle::NONE;
+        assert_eq!(
+            preset.bones_outside_hips(&graph),
+            vec![RagdollBone::Spine, RagdollBone::Head]
+        );
+    }
+
+    #[test]
+    fn test_ragdoll_parent() {
+        let mut graph = Graph::new();
+        let character = make_bone(&mut graph, Vector3::default());
+        let armature = make_bone(&mut graph, Vector3::default());
+        let hips = make_bone(&mut graph, Vector3::default());
+        let spine = make_bone(&mut graph, Vector3::default());
+        let head = make_bone(&mut graph, Vector3::default());
+        let attachment = make_bone(&mut graph, Vector3::default());
+        let old_ragdoll = make_bone(&mut graph, Vector3::default());
+        let old_body = make_bone(&mut graph, Vector3::default());
+        graph.link_nodes(armature, character);
+        graph.link_nodes(hips, armature);
+        graph.link_nodes(spine, hips);
+        graph.link_nodes(head, spine);
+        graph.link_nodes(attachment, head);
+        graph.link_nodes(old_ragdoll, character);
+        graph.link_nodes(old_body, old_ragdoll);
+
+        assert_eq!(common_ancestor(&graph, &[head, spine]), spine);
+        assert_eq!(common_ancestor(&graph, &[head, old_body]), character);
+        assert_eq!(common_ancestor(&graph, &[]), Handle::NONE);
+
+        let mut preset = RagdollPreset {
+            hips,
+            spine,
+            head,
+            ..Default::default()
+        };
+        // The common ancestor is a bone, so its parent is used.
+        assert_eq!(preset.default_parent(&graph), armature);
+
+        let scene_root = graph.get_root();
+        assert_eq!(
+            preset.ragdoll_parent(&graph, scene_root, &[]),
+            Ok(scene_root)
+        );
+
+        preset.parent = character;
+        assert_eq!(
+            preset.ragdoll_parent(&graph, scene_root, &[old_ragdoll]),
+            Ok(character)
+        );
+
+        // The bones would move their own ragdoll.
+        preset.parent = spine;
+        assert!(preset.ragdoll_parent(&graph, scene_root, &[]).is_err());
+        preset.parent = attachment;
+        assert!(preset.ragdoll_parent(&graph, scene_root, &[]).is_err());
+
+        // The replaced ragdoll is deleted together with its descendants.
+        preset.parent = old_body;
+        assert!(preset.ragdoll_parent(&graph, scene_root, &[]).is_ok());
+        assert!(preset
+            .ragdoll_parent(&graph, scene_root, &[old_ragdoll])
+            .is_err());
+    }
+
+    #[test]
+    fn test_mirror_preset() {
+        let candidates = [
+            candidate_at(1, ""),
+            candidate_at(2, "Hips"),
+            candidate_at(3, "Hips/LeftUpLeg"),
+            candidate_at(4, "Hips/LeftUpLeg/LeftLeg"),
+            candidate_at(5, "Hips/RightUpLeg"),
+            candidate_at(6, "Hips/RightUpLeg/RightLeg"),
+            candidate_at(7, "Hips/Spine/LeftShoulder"),
+            candidate_at(8, "Hips/Spine/Arm_L"),
+        ];
+        let tokens = parse_mirror_tokens("Left/Right");
+
+        let mut preset = RagdollPreset {
+            hips: Handle::new(2, 1),
+            left_up_leg: Handle::new(3, 1),
+            left_leg: Handle::new(4, 1),
+            // There's no right shoulder.
+            left_shoulder: Handle::new(7, 1),
+            // The token is unknown.
+            left_arm: Handle::new(8, 1),
+            right_arm: Handle::new(100, 1),
+            ..Default::default()
+        };
+
+        let failed = preset.mirror(&candidates, &tokens, true);
+        assert_eq!(
+            failed,
+            vec![RagdollBone::LeftShoulder, RagdollBone::LeftArm]
+        );
+        assert_eq!(preset.right_up_leg, Handle::new(5, 1));
+        assert_eq!(preset.right_leg, Handle::new(6, 1));
+        // Slots, that can't be mirrored, are left untouched.
+        assert_eq!(preset.right_shoulder, Handle::NONE);
+        assert_eq!(preset.right_arm, Handle::new(100, 1));
+        // The source side is not changed.
+        assert_eq!(preset.left_up_leg, Handle::new(3, 1));
+        assert_eq!(preset.hips, Handle::new(2, 1));
+
+        // And back.
+        preset.left_up_leg = Handle::NONE;
+        preset.right_arm = Handle::NONE;
+        let failed = preset.mirror(&candidates, &tokens, false);
+        assert!(failed.is_empty());
+        assert_eq!(preset.left_up_leg, Handle::new(3, 1));
+        assert_eq!(preset.left_arm, Handle::new(8, 1));
+    }
+}
@@ -0,0 +1,653 @@
+//! Checks of ragdoll presets and generated ragdolls: invalid numbers of a preset, missing or
+//! skipped bones, bones that are not covered by bodies, and pairs of overlapping bodies.
+
+use crate::{
+    scene::commands::{SceneCommand, SetPropertyCommand},
+    settings::ragdoll::RagdollBone,
+    utils::{
+        overlap::{penetration_depth, OverlapShape},
+        ragdoll::{
+            bones::is_descendant_of,
+            generator::RagdollShape,
+            preset::{RagdollOutputMode, RagdollPreset},
+        },
+    },
+};
+use fyrox::{
+    core::{
+        algebra::UnitQuaternion, color::Color, log::Log, math::Matrix4Ext, pool::Handle,
+        reflect::prelude::*,
+    },
+    scene::{
+        collider::{Collider, ColliderShape},
+        debug::{Line, SceneDrawingContext},
+        graph::Graph,
+        joint::Joint,
+        mesh::Mesh,
+        node::Node,
+        ragdoll::{Limb, Ragdoll},
+    },
+};
+
+/// Numeric value, that is not finite or is out of the range of its field.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InvalidNumber {
+    /// Path of the field, for example `limb_overrides.arms.friction`.
+    pub path: String,
+    pub value: f32,
+    /// The value clamped to the range of the field, `None` if the value is not finite.
+    pub clamped: Option<f32>,
+}
+
+/// Clamps the value to the range of a field, that is declared with `min_value` and `max_value`
+/// reflection attributes. Returns `None` if the value is not finite, it can't be clamped.
+pub fn clamp_to_range(value: f32, min: Option<f64>, max: Option<f64>) -> Option<f32> {
+    if !value.is_finite() {
+        return None;
+    }
+    let value = min.map_or(value, |min| value.max(min as f32));
+    Some(max.map_or(value, |max| value.min(max as f32)))
+}
+
+// Returns the value of a numeric field, `Some(None)` is an unset optional value.
+fn number_value(value: &dyn Reflect) -> Option<Option<f32>> {
+    let mut number = None;
+    value.as_any(&mut |any| {
+        number = any
+            .downcast_ref::<f32>()
+            .map(|value| Some(*value))
+            .or_else(|| any.downcast_ref::<Option<f32>>().cloned());
+    });
+    number
+}
+
+fn collect_invalid_numbers(
+    value: &dyn Reflect,
+    path: &str,
+    range: (Option<f64>, Option<f64>),
+    invalid: &mut Vec<InvalidNumber>,
+) {
+    match number_value(value) {
+        Some(Some(number)) => {
+            let clamped = clamp_to_range(number, range.0, range.1);
+            if clamped != Some(number) {
+                invalid.push(InvalidNumber {
+                    path: path.to_owned(),
+                    value: number,
+                    clamped,
+                });
+            }
+        }
+        Some(None) => (),
+        None => {
+            value.fields_info(&mut |fields| {
+                for field in fields {
+                    let field_path = if path.is_empty() {
+                        field.name.to_owned()
+                    } else {
+                        format!("{}.{}", path, field.name)
+                    };
+                    collect_invalid_numbers(
+                        field.reflect_value,
+                        &field_path,
+                        (field.min_value, field.max_value),
+                        invalid,
+                    );
+                }
+            });
+            value.as_array(&mut |array| {
+                if let Some(array) = array {
+                    for index in 0..array.reflect_len() {
+                        if let Some(item) = array.reflect_index(index) {
+                            let item_path = format!("{}[{}]", path, index);
+                            collect_invalid_numbers(item, &item_path, (None, None), invalid);
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// Finds the numeric values of the object, including the values in nested structures and
+/// collections, that are not finite or are out of the ranges of their fields. The ranges are
+/// taken from the reflection attributes, so the inspector and the validation share them.
+pub fn find_invalid_numbers(object: &dyn Reflect) -> Vec<InvalidNumber> {
+    let mut invalid = Vec::new();
+    collect_invalid_numbers(object, "", (None, None), &mut invalid);
+    invalid
+}
+
+/// Clamps the values of the object, that are out of the ranges of their fields. Returns every
+/// invalid value, values, that are not finite, are left as is.
+pub fn clamp_invalid_numbers(object: &mut dyn Reflect) -> Vec<InvalidNumber> {
+    let invalid = find_invalid_numbers(object);
+    for number in invalid.iter() {
+        if let Some(clamped) = number.clamped {
+            object.resolve_path_mut(&number.path, &mut |result| {
+                if let Ok(field) = result {
+                    field.as_any_mut(&mut |any| {
+                        if let Some(value) = any.downcast_mut::<f32>() {
+                            *value = clamped;
+                        } else if let Some(Some(value)) = any.downcast_mut::<Option<f32>>() {
+                            *value = clamped;
+                        }
+                    });
+                }
+            });
+        }
+    }
+    invalid
+}
+
+/// Returns a readable list of the invalid values, for example `total_mass (NaN)`.
+pub fn invalid_number_list(invalid: &[InvalidNumber]) -> String {
+    invalid
+        .iter()
+        .map(|number| format!("{} ({})", number.path, number.value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Radius of the spheres, that mark the bones without child bones in the coverage view.
+const COVERAGE_LEAF_RADIUS: f32 = 0.02;
+
+/// Bones of a character, split by whether they are driven by a body of the ragdoll or not.
+#[derive(Default, Debug)]
+pub struct BoneCoverage {
+    /// Bones of the limbs, that have a body.
+    pub covered: Vec<Handle<Node>>,
+    /// Skinned bones, that are not driven by any limb (fingers, twist bones and so on).
+    pub uncovered: Vec<Handle<Node>>,
+}
+
+impl BoneCoverage {
+    /// Collects the coverage of the given ragdoll. Skinned bones are the bones of the surfaces,
+    /// that are bound to the skeleton of the ragdoll, the descendants of the hips are used if no
+    /// surface is bound to it.
+    pub fn new(graph: &Graph, ragdoll: &Ragdoll) -> Self {
+        let mut covered = Vec::new();
+        collect_covered_bones(graph, ragdoll.hips(), &mut covered);
+
+        let hips = ragdoll.hips().bone;
+        if graph.try_get(hips).is_none() {
+            return Self {
+                covered,
+                uncovered: Vec::new(),
+            };
+        }
+
+        let mut skinned = Vec::new();
+        for mesh in graph.linear_iter().filter_map(|node| node.cast::<Mesh>()) {
+            for &bone in mesh.surfaces().iter().flat_map(|surface| surface.bones()) {
+                let is_skeleton_bone = bone == hips
+                    || (graph.try_get(bone).is_some() && is_descendant_of(graph, bone, hips));
+                if is_skeleton_bone && !skinned.contains(&bone) {
+                    skinned.push(bone);
+                }
+            }
+        }
+        if skinned.is_empty() {
+            // Bones of imported models are pivots, other descendants are attachments.
+            skinned = graph
+                .traverse_handle_iter(hips)
+                .filter(|&bone| graph[bone].is_pivot())
+                .collect();
+        }
+
+        Self {
+            uncovered: skinned
+                .into_iter()
+                .filter(|bone| !covered.contains(bone))
+                .collect(),
+            covered,
+        }
+    }
+
+    fn contains(&self, bone: Handle<Node>) -> bool {
+        self.covered.contains(&bone) || self.uncovered.contains(&bone)
+    }
+
+    /// Draws the segments from the bones to their child bones, covered bones are green and
+    /// uncovered ones are orange. Bones without child bones are drawn as small spheres.
+    pub fn draw(&self, graph: &Graph, ctx: &mut SceneDrawingContext) {
+        for (bones, color) in [
+            (&self.covered, Color::GREEN),
+            (&self.uncovered, Color::ORANGE),
+        ] {
+            for &bone in bones.iter() {
+                let node = &graph[bone];
+                let begin = node.global_position();
+                let mut has_child_bones = false;
+                for &child in node.children() {
+                    if self.contains(child) {
+                        ctx.add_line(Line {
+                            begin,
+                            end: graph[child].global_position(),
+                            color,
+                        });
+                        has_child_bones = true;
+                    }
+                }
+                if !has_child_bones {
+                    ctx.draw_wire_sphere(begin, COVERAGE_LEAF_RADIUS, 8, color);
+                }
+            }
+        }
+    }
+}
+
+fn collect_covered_bones(graph: &Graph, limb: &Limb, bones: &mut Vec<Handle<Node>>) {
+    if graph.try_get(limb.bone).is_some() && graph.try_get(limb.physical_bone).is_some() {
+        bones.push(limb.bone);
+    }
+    for child in limb.children.iter() {
+        collect_covered_bones(graph, child, bones);
+    }
+}
+
+/// A pair of generated colliders, that penetrate each other at rest.
+#[derive(Clone, Debug)]
+pub struct RagdollOverlap {
+    pub first: Handle<Node>,
+    pub second: Handle<Node>,
+    pub depth: f32,
+}
+
+fn collider_overlap_shape(graph: &Graph, collider: Handle<Node>) -> Option<OverlapShape> {
+    let collider_ref = graph.try_get_of_type::<Collider>(collider)?;
+    let transform = collider_ref.global_transform();
+    match collider_ref.shape() {
+        ColliderShape::Ball(ball) => Some(OverlapShape::Ball {
+            center: collider_ref.global_position(),
+            radius: ball.radius,
+        }),
+        ColliderShape::Capsule(capsule) => Some(OverlapShape::Capsule {
+            begin: transform.transform_point(&capsule.begin.into()).coords,
+            end: transform.transform_point(&capsule.end.into()).coords,
+            radius: capsule.radius,
+        }),
+        ColliderShape::Cuboid(cuboid) => Some(OverlapShape::Cuboid {
+            center: collider_ref.global_position(),
+            rotation: UnitQuaternion::from_matrix_eps(
+                &transform.basis(),
+                f32::EPSILON,
+                16,
+                Default::default(),
+            ),
+            half_extents: cuboid.half_extents,
+        }),
+        _ => None,
+    }
+}
+
+/// Checks every pair of colliders of the ragdoll bodies in their current poses and returns the
+/// pairs, that penetrate each other deeper than the threshold. Bodies connected with a joint are
+/// skipped, because contacts between them are disabled.
+pub fn find_ragdoll_overlaps(
+    graph: &Graph,
+    ragdoll: Handle<Node>,
+    threshold: f32,
+) -> Vec<RagdollOverlap> {
+    let mut colliders = Vec::new();
+    let mut joints = Vec::new();
+    for &child in graph[ragdoll].children() {
+        if let Some(joint) = graph.try_get_of_type::<Joint>(child) {
+            joints.push((joint.body1(), joint.body2()));
+        } else {
+            for &collider in graph[child].children() {
+                if let Some(shape) = collider_overlap_shape(graph, collider) {
+                    colliders.push((child, collider, shape));
+                }
+            }
+        }
+    }
+
+    let mut overlaps = Vec::new();
+    for (i, (body_a, collider_a, shape_a)) in colliders.iter().enumerate() {
+        for (body_b, collider_b, shape_b) in colliders[(i + 1)..].iter() {
+            if joints.contains(&(*body_a, *body_b)) || joints.contains(&(*body_b, *body_a)) {
+                continue;
+            }
+
+            if let Some(depth) = penetration_depth(shape_a, shape_b) {
+                if depth > threshold {
+                    overlaps.push(RagdollOverlap {
+                        first: *collider_a,
+                        second: *collider_b,
+                        depth,
+                    });
+                }
+            }
+        }
+    }
+    overlaps
+}
+
+/// Creates commands, that shrink radii of the colliders of the pair by the penetration depth. The
+/// depth is split evenly between the colliders, that have a radius.
+pub fn make_shrink_commands(graph: &Graph, overlap: &RagdollOverlap) -> Vec<SceneCommand> {
+    let shapes = [overlap.first, overlap.second]
+        .into_iter()
+        .filter_map(|h| {
+            graph
+                .try_get_of_type::<Collider>(h)
+                .map(|c| (h, c.shape_value()))
+        })
+        .filter(|(_, shape)| matches!(shape, ColliderShape::Ball(_) | ColliderShape::Capsule(_)))
+        .collect::<Vec<_>>();
+
+    let share = overlap.depth / shapes.len().max(1) as f32;
+
+    shapes
+        .into_iter()
+        .map(|(handle, mut shape)| {
+            match shape {
+                ColliderShape::Ball(ref mut ball) => ball.radius = (ball.radius - share).max(0.001),
+                ColliderShape::Capsule(ref mut capsule) => {
+                    capsule.radius = (capsule.radius - share).max(0.001)
+                }
+                _ => (),
+            }
+            SceneCommand::new(SetPropertyCommand::new(
+                handle,
+                "shape".to_string(),
+                Box::new(shape),
+            ))
+        })
+        .collect()
+}
+
+/// Bones, that every ragdoll must have. At least one of the spine bones is also required.
+pub const REQUIRED_BONES: [RagdollBone; 8] = [
+    RagdollBone::Hips,
+    RagdollBone::LeftUpLeg,
+    RagdollBone::LeftLeg,
+    RagdollBone::LeftFoot,
+    RagdollBone::RightUpLeg,
+    RagdollBone::RightLeg,
+    RagdollBone::RightFoot,
+    RagdollBone::Head,
+];
+
+const SPINE_BONES: [RagdollBone; 3] =
+    [RagdollBone::Spine, RagdollBone::Spine1, RagdollBone::Spine2];
+
+/// Result of the check of the bones before the ragdoll is generated.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct RagdollBoneCheck {
+    /// Required bones without bodies, the ragdoll can't be generated without them.
+    pub missing: Vec<RagdollBone>,
+    /// Optional bones without bodies, the joints of their children are bridged to the closest
+    /// ancestors with bodies.
+    pub skipped: Vec<RagdollBone>,
+}
+
+impl RagdollBoneCheck {
+    /// Checks the bones, that have bodies. Bones, that are excluded from the generation (like
+    /// fingers and toes, when they're not included), are not reported.
+    pub fn new(bodies: &[RagdollBone], is_generated: impl Fn(RagdollBone) -> bool) -> Self {
+        let mut check = Self::default();
+        let has_spine = SPINE_BONES.iter().any(|bone| bodies.contains(bone));
+        for bone in RagdollBone::ALL {
+            if bodies.contains(&bone) || !is_generated(bone) {
+                continue;
+            }
+            if REQUIRED_BONES.contains(&bone) || (bone == RagdollBone::Spine && !has_spine) {
+                check.missing.push(bone);
+            } else {
+                check.skipped.push(bone);
+            }
+        }
+        check
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.skipped.is_empty()
+    }
+}
+
+impl RagdollPreset {
+    /// Checks the extra chains before the ragdoll is generated. Chains without segments are
+    /// reported as warnings, the names of the bodies must be unique, because the limbs of prefabs
+    /// are bound to the bones by them.
+    pub fn check_chains(&self, graph: &Graph, shapes: &[RagdollShape]) -> Result<(), String> {
+        for chain in self.extra_chains.iter() {
+            if chain.bones(graph).len() < 2 {
+                Log::warn(format!(
+                    "Ragdoll Wizard: the extra chain {} has no segments, assign its root and make \
+                    sure, that its end is under the root.",
+                    chain.name
+                ));
+            }
+        }
+        for (i, shape) in shapes.iter().enumerate() {
+            if shapes[..i].iter().any(|other| other.name() == shape.name()) {
+                return Err(format!(
+                    "there are several bodies named {}, rename the extra chain.",
+                    shape.name()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the numeric settings before the ragdoll is generated, the bodies would be broken with
+    /// values out of the ranges of the fields (see [`find_invalid_numbers`]).
+    pub fn check_numbers(&self) -> Result<(), String> {
+        let invalid = find_invalid_numbers(self);
+        if invalid.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "the settings have invalid values: {}.",
+                invalid_number_list(&invalid)
+            ))
+        }
+    }
+
+    /// Checks the bones, that get bodies, before the ragdoll is generated.
+    pub fn check_bones(&self, graph: &Graph) -> RagdollBoneCheck {
+        let bones = self
+            .make_shapes(graph)
+            .iter()
+            .filter(|shape| shape.chain.is_none())
+            .map(|shape| shape.bone)
+            .collect::<Vec<_>>();
+        let mut check = RagdollBoneCheck::new(&bones, |bone| self.generates_body(bone));
+        if self.output_mode == RagdollOutputMode::Hitboxes {
+            // Hitboxes are not connected with each other, none of them is required.
+            check.skipped.append(&mut check.missing);
+            check.skipped.sort_by_key(|bone| *bone as usize);
+        }
+        check
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::utils::ragdoll::{
+        preset::RagdollBoneChain,
+        test::{make_bone, make_character},
+    };
+    use fyrox::{
+        core::algebra::{Matrix4, Vector3},
+        scene::{
+            base::BaseBuilder,
+            mesh::{
+                surface::{SurfaceBuilder, SurfaceData, SurfaceSharedData},
+                MeshBuilder,
+            },
+        },
+    };
+
+    #[test]
+    fn test_clamp_to_range() {
+        assert_eq!(clamp_to_range(0.5, Some(0.0), Some(1.0)), Some(0.5));
+        assert_eq!(clamp_to_range(-2.0, Some(0.001), None), Some(0.001));
+        assert_eq!(clamp_to_range(2.0e7, Some(0.0), Some(1.0e6)), Some(1.0e6));
+        assert_eq!(clamp_to_range(-2.0e7, None, None), Some(-2.0e7));
+        assert_eq!(clamp_to_range(f32::NAN, None, None), None);
+        assert_eq!(clamp_to_range(f32::INFINITY, Some(0.0), Some(1.0e6)), None);
+        assert_eq!(clamp_to_range(f32::NEG_INFINITY, Some(0.0), None), None);
+    }
+
+    #[test]
+    fn test_invalid_numbers() {
+        let mut preset = RagdollPreset::default();
+        assert!(find_invalid_numbers(&preset).is_empty());
+        assert!(preset.check_numbers().is_ok());
+
+        preset.total_mass = 0.0;
+        preset.friction = 5.0e6;
+        preset.limb_overrides.arms.restitution = Some(-1.0);
+        preset.joint_limits.knees = f32::NAN..0.0;
+        preset.extra_chains.push(RagdollBoneChain {
+            radius: f32::INFINITY,
+            ..Default::default()
+        });
+
+        let invalid = find_invalid_numbers(&preset);
+        assert_eq!(
+            invalid
+                .iter()
+                .map(|number| (number.path.as_str(), number.clamped))
+                .collect::<Vec<_>>(),
+            vec![
+                ("extra_chains[0].radius", None),
+                ("total_mass", Some(0.001)),
+                ("friction", Some(1.0e6)),
+                ("limb_overrides.arms.restitution", Some(0.0)),
+                ("joint_limits.knees.start", None),
+            ]
+        );
+        assert!(preset
+            .check_numbers()
+            .unwrap_err()
+            .contains("total_mass (0)"));
+
+        // Finite values are clamped, the others are left for the user to fix.
+        let paths = |invalid: &[InvalidNumber]| {
+            invalid
+                .iter()
+                .map(|number| number.path.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(paths(&clamp_invalid_numbers(&mut preset)), paths(&invalid));
+        assert_eq!(preset.total_mass, 0.001);
+        assert_eq!(preset.friction, 1.0e6);
+        assert_eq!(preset.limb_overrides.arms.restitution, Some(0.0));
+        assert_eq!(
+            paths(&find_invalid_numbers(&preset)),
+            vec!["extra_chains[0].radius", "joint_limits.knees.start"]
+        );
+
+        // Values of the inspector are checked the same way.
+        assert!(find_invalid_numbers(&1.0f32).is_empty());
+        assert_eq!(find_invalid_numbers(&Some(f32::NAN))[0].clamped, None);
+        assert!(find_invalid_numbers(&Option::<f32>::None).is_empty());
+    }
+
+    #[test]
+    fn test_bone_coverage() {
+        fn limb_of_bone(limb: &mut Limb, bone: Handle<Node>) -> Option<&mut Limb> {
+            if limb.bone == bone {
+                return Some(limb);
+            }
+            limb.children
+                .iter_mut()
+                .find_map(|child| limb_of_bone(child, bone))
+        }
+
+        let mut graph = Graph::new();
+        let (_, bones) = make_character(&mut graph, 0.0, "mixamorig:");
+        let (_, other_bones) = make_character(&mut graph, 2.0, "Bip01_");
+        let bone = |bone: RagdollBone| bones.iter().find(|(b, _)| *b == bone).unwrap().1;
+        // Toes have no body.
+        let toe = make_bone(&mut graph, Vector3::new(0.0, 0.0, 0.1));
+        graph.link_nodes(toe, bone(RagdollBone::LeftFoot));
+        graph.update_hierarchical_data();
+        let scene_root = graph.get_root();
+
+        let mut preset = RagdollPreset::default();
+        for &(bone, handle) in bones.iter() {
+            *preset.bone_mut(bone) = handle;
+        }
+        let (roots, _) = preset.generate_nodes(&mut graph, scene_root, &[]).unwrap();
+        let ragdoll = roots[0];
+
+        // Nothing is skinned, so every bone under the hips is checked.
+        let coverage = BoneCoverage::new(&graph, graph[ragdoll].as_ragdoll());
+        assert!(coverage.covered.contains(&bone(RagdollBone::Hips)));
+        assert!(coverage.covered.contains(&bone(RagdollBone::LeftLeg)));
+        assert!(coverage.uncovered.contains(&toe));
+        assert!(!coverage.covered.contains(&toe));
+        assert!(coverage
+            .covered
+            .iter()
+            .all(|bone| !coverage.uncovered.contains(bone)));
+
+        // Bones of the surfaces are used, the bones of other characters are ignored.
+        MeshBuilder::new(BaseBuilder::new())
+            .with_surfaces(vec![SurfaceBuilder::new(SurfaceSharedData::new(
+                SurfaceData::make_cube(Matrix4::identity()),
+            ))
+            .with_bones(vec![bone(RagdollBone::LeftLeg), toe, other_bones[0].1])
+            .build()])
+            .build(&mut graph);
+        let coverage = BoneCoverage::new(&graph, graph[ragdoll].as_ragdoll());
+        assert_eq!(coverage.uncovered, vec![toe]);
+
+        // Limbs without a body do not cover their bones.
+        let mut hips = graph[ragdoll].as_ragdoll().hips().clone();
+        limb_of_bone(&mut hips, bone(RagdollBone::LeftLeg))
+            .unwrap()
+            .physical_bone = Handle::NONE;
+        graph[ragdoll].as_ragdoll_mut().set_hips(hips);
+        let coverage = BoneCoverage::new(&graph, graph[ragdoll].as_ragdoll());
+        assert!(!coverage.covered.contains(&bone(RagdollBone::LeftLeg)));
+        assert_eq!(coverage.uncovered, vec![bone(RagdollBone::LeftLeg), toe]);
+    }
+
+    #[test]
+    fn test_bone_check() {
+        use RagdollBone::*;
+
+        let required = [
+            Hips, LeftUpLeg, LeftLeg, LeftFoot, RightUpLeg, RightLeg, RightFoot, Head,
+        ];
+        let mut bodies = required.to_vec();
+        bodies.push(Spine1);
+
+        let without_digits = |bone: RagdollBone| bone.digit_parent().is_none();
+        let check = RagdollBoneCheck::new(&bodies, without_digits);
+        assert!(check.missing.is_empty());
+        assert_eq!(check.skipped.len(), 11);
+        assert!(check.skipped.contains(&Spine));
+        assert!(check.skipped.contains(&LeftHand));
+        assert!(!check.skipped.contains(&LeftThumb));
+
+        let check = RagdollBoneCheck::new(&bodies, |_| true);
+        assert_eq!(check.skipped.len(), 11 + 12);
+
+        // At least one spine bone is required.
+        let check = RagdollBoneCheck::new(&required[1..], without_digits);
+        assert_eq!(check.missing, vec![Hips, Spine]);
+
+        let all = RagdollBone::ALL.to_vec();
+        assert!(RagdollBoneCheck::new(&all, |_| true).is_empty());
+
+        // Excluded feet are not required.
+        let preset = RagdollPreset {
+            generate_feet: false,
+            include_fingers_and_toes: true,
+            ..Default::default()
+        };
+        let bodies = [Hips, LeftUpLeg, LeftLeg, RightUpLeg, RightLeg, Spine, Head];
+        let check = RagdollBoneCheck::new(&bodies, |bone| preset.generates_body(bone));
+        assert!(check.missing.is_empty());
+        assert!(!check.skipped.contains(&LeftToeBase));
+        assert!(check.skipped.contains(&LeftThumb));
+    }
+}
@@ -0,0 +1,669 @@
+//! Dialogs of the ragdoll wizard: overlapping bodies of a generated ragdoll, the autofill report,
+//! choice of the ragdoll to replace and the list of bones, that will be skipped by generation.
+
+use crate::{
+    message::MessageSender,
+    scene::commands::CommandGroup,
+    settings::ragdoll::{RagdollBone, RagdollBonePatterns},
+    utils::ragdoll::{
+        bone_list,
+        bones::{bone_match, parse_bone_patterns, BoneMatch},
+        node_path,
+        preset::{RagdollOutputMode, RagdollPreset},
+        validation::{make_shrink_commands, RagdollBoneCheck, RagdollOverlap, REQUIRED_BONES},
+    },
+};
+use fyrox::{
+    core::pool::Handle,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        expander::ExpanderBuilder,
+        file_browser::{FileBrowserMode, FileSelectorBuilder, Filter},
+        formatted_text::WrapMode,
+        grid::{Column, GridBuilder, Row},
+        list_view::{ListViewBuilder, ListViewMessage},
+        message::{MessageDirection, UiMessage},
+        scroll_viewer::ScrollViewerBuilder,
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        widget::{WidgetBuilder, WidgetMessage},
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    scene::{graph::Graph, node::Node},
+};
+use std::path::Path;
+
+/// Shows the pairs of overlapping bodies of a generated ragdoll and allows to shrink them.
+pub struct RagdollValidationWindow {
+    window: Handle<UiNode>,
+    list: Handle<UiNode>,
+    close: Handle<UiNode>,
+    overlaps: Vec<(RagdollOverlap, Handle<UiNode>, Handle<UiNode>)>,
+    pub is_open: bool,
+}
+
+impl RagdollValidationWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let list;
+        let close;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(400.0).with_height(250.0))
+            .open(false)
+            .with_title(WindowTitle::text("Ragdoll Validation"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_text(
+                                "Some bodies of the ragdoll overlap at rest, this could make the \
+                                ragdoll explode on activation.",
+                            )
+                            .with_wrap(WrapMode::Word)
+                            .build(ctx),
+                        )
+                        .with_child({
+                            list = ListViewBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .build(ctx);
+                            list
+                        })
+                        .with_child({
+                            close = ButtonBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_width(100.0)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_text("Close")
+                            .build(ctx);
+                            close
+                        }),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_row(Row::strict(24.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .with_default_button(close)
+            .with_cancel_button(close)
+            .build(ctx);
+
+        Self {
+            window,
+            list,
+            close,
+            overlaps: Default::default(),
+            is_open: false,
+        }
+    }
+
+    pub fn open(&mut self, ui: &mut UserInterface, graph: &Graph, overlaps: Vec<RagdollOverlap>) {
+        let name = |h: Handle<Node>| {
+            // Colliders have generic names, so use names of their bodies.
+            let parent = graph[h].parent();
+            if parent.is_some() {
+                graph[parent].name_owned()
+            } else {
+                graph[h].name_owned()
+            }
+        };
+
+        self.overlaps = overlaps
+            .into_iter()
+            .map(|overlap| {
+                let ctx = &mut ui.build_ctx();
+                let shrink;
+                let item = GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new().with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .with_text(format!(
+                                "{} - {}: {:.3}",
+                                name(overlap.first),
+                                name(overlap.second),
+                                overlap.depth
+                            ))
+                            .build(ctx),
+                        )
+                        .with_child({
+                            shrink = ButtonBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_column(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_text("Shrink")
+                            .build(ctx);
+                            shrink
+                        }),
+                )
+                .add_row(Row::strict(24.0))
+                .add_column(Column::stretch())
+                .add_column(Column::strict(60.0))
+                .build(ctx);
+                (overlap, item, shrink)
+            })
+            .collect();
+
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            self.overlaps.iter().map(|(_, item, _)| *item).collect(),
+        ));
+
+        ui.send_message(WindowMessage::open(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+        ui.send_message(WidgetMessage::focus(self.close, MessageDirection::ToWidget));
+        self.is_open = true;
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+        graph: &Graph,
+        sender: &MessageSender,
+        viewport: Handle<UiNode>,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.close {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if let Some(index) = self
+                .overlaps
+                .iter()
+                .position(|(_, _, shrink)| *shrink == message.destination())
+            {
+                let (overlap, item, _) = self.overlaps.remove(index);
+
+                let commands = make_shrink_commands(graph, &overlap);
+                if !commands.is_empty() {
+                    sender.do_scene_command(
+                        CommandGroup::from(commands).with_custom_name("Shrink Ragdoll Colliders"),
+                    );
+                }
+
+                ui.send_message(ListViewMessage::remove_item(
+                    self.list,
+                    MessageDirection::ToWidget,
+                    item,
+                ));
+            }
+        } else if let Some(WindowMessage::Close) = message.data() {
+            if message.destination() == self.window
+                && message.direction() == MessageDirection::ToWidget
+            {
+                self.is_open = false;
+                ui.send_message(WidgetMessage::focus(viewport, MessageDirection::ToWidget));
+            }
+        }
+    }
+}
+
+pub enum AutofillReportAction {
+    Clear(RagdollBone),
+    Pick(RagdollBone),
+}
+
+/// Shows the assigned node of every bone with its full path and how well its name matches the
+/// patterns, so wrong matches of Autofill could be found before the generation.
+pub struct AutofillReport {
+    pub expander: Handle<UiNode>,
+    list: Handle<UiNode>,
+    // Bones and their Clear and Pick buttons.
+    rows: Vec<(RagdollBone, Handle<UiNode>, Handle<UiNode>)>,
+}
+
+impl AutofillReport {
+    pub fn new(ctx: &mut BuildContext, row: usize) -> Self {
+        let list;
+        let expander = ExpanderBuilder::new(WidgetBuilder::new().on_row(row))
+            .with_expanded(false)
+            .with_header(
+                TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+                    .with_text("Autofill Report")
+                    .build(ctx),
+            )
+            .with_content({
+                list = ListViewBuilder::new(
+                    WidgetBuilder::new()
+                        .with_height(200.0)
+                        .with_margin(Thickness::uniform(1.0)),
+                )
+                .build(ctx);
+                list
+            })
+            .build(ctx);
+
+        Self {
+            expander,
+            list,
+            rows: Default::default(),
+        }
+    }
+
+    pub fn sync(
+        &mut self,
+        ui: &mut UserInterface,
+        graph: &Graph,
+        preset: &RagdollPreset,
+        patterns: &RagdollBonePatterns,
+    ) {
+        let required = preset.output_mode == RagdollOutputMode::Ragdoll;
+        let mut items = Vec::new();
+        self.rows.clear();
+        for bone in RagdollBone::ALL {
+            if bone.digit_parent().is_some() && !preset.generates_body(bone) {
+                continue;
+            }
+
+            let handle = preset.bone(bone);
+            let text = match graph.try_get(handle) {
+                Some(node) => format!(
+                    "{}: {} - {}",
+                    bone.as_ref(),
+                    node_path(graph, handle),
+                    bone_match(node.name(), &parse_bone_patterns(patterns.patterns(bone)))
+                        .description()
+                ),
+                None if required && REQUIRED_BONES.contains(&bone) => {
+                    format!(
+                        "{}: {} (required)",
+                        bone.as_ref(),
+                        BoneMatch::NotFound.description()
+                    )
+                }
+                None => format!("{}: {}", bone.as_ref(), BoneMatch::NotFound.description()),
+            };
+
+            let ctx = &mut ui.build_ctx();
+            let clear;
+            let pick;
+            let item = GridBuilder::new(
+                WidgetBuilder::new()
+                    .with_child(
+                        TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .with_text(text)
+                            .build(ctx),
+                    )
+                    .with_child({
+                        clear = ButtonBuilder::new(
+                            WidgetBuilder::new()
+                                .on_column(1)
+                                .with_enabled(handle.is_some())
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_text("Clear")
+                        .build(ctx);
+                        clear
+                    })
+                    .with_child({
+                        pick = ButtonBuilder::new(
+                            WidgetBuilder::new()
+                                .on_column(2)
+                                .with_margin(Thickness::uniform(1.0)),
+                        )
+                        .with_text("Pick...")
+                        .build(ctx);
+                        pick
+                    }),
+            )
+            .add_row(Row::strict(24.0))
+            .add_column(Column::stretch())
+            .add_column(Column::strict(50.0))
+            .add_column(Column::strict(50.0))
+            .build(ctx);
+
+            items.push(item);
+            self.rows.push((bone, clear, pick));
+        }
+
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+    }
+
+    pub fn handle_ui_message(&self, message: &UiMessage) -> Option<AutofillReportAction> {
+        if let Some(ButtonMessage::Click) = message.data() {
+            for &(bone, clear, pick) in self.rows.iter() {
+                if message.destination() == clear {
+                    return Some(AutofillReportAction::Clear(bone));
+                } else if message.destination() == pick {
+                    return Some(AutofillReportAction::Pick(bone));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Asks which ragdoll must be replaced, when there are several ragdolls of the same character.
+pub struct RagdollReplaceWindow {
+    window: Handle<UiNode>,
+    list: Handle<UiNode>,
+    replace: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    ragdolls: Vec<Handle<Node>>,
+    selected: Option<usize>,
+}
+
+impl RagdollReplaceWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let list;
+        let replace;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(200.0))
+            .open(false)
+            .with_title(WindowTitle::text("Replace Ragdoll"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_text(
+                                "The character has several ragdolls, select the one to replace.",
+                            )
+                            .with_wrap(WrapMode::Word)
+                            .build(ctx),
+                        )
+                        .with_child({
+                            list = ListViewBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .build(ctx);
+                            list
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        replace = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(100.0)
+                                                .with_enabled(false)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Replace")
+                                        .build(ctx);
+                                        replace
+                                    })
+                                    .with_child({
+                                        cancel = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(100.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Cancel")
+                                        .build(ctx);
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::auto())
+                .add_row(Row::stretch())
+                .add_row(Row::strict(24.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .with_cancel_button(cancel)
+            .build(ctx);
+
+        Self {
+            window,
+            list,
+            replace,
+            cancel,
+            ragdolls: Default::default(),
+            selected: None,
+        }
+    }
+
+    pub fn open(&mut self, ui: &mut UserInterface, graph: &Graph, ragdolls: Vec<Handle<Node>>) {
+        let items = ragdolls
+            .iter()
+            .map(|&ragdoll| {
+                TextBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(1.0)))
+                    .with_vertical_text_alignment(VerticalAlignment::Center)
+                    .with_text(format!("{} ({})", graph[ragdoll].name(), ragdoll))
+                    .build(&mut ui.build_ctx())
+            })
+            .collect();
+        self.ragdolls = ragdolls;
+        self.selected = None;
+
+        ui.send_message(ListViewMessage::items(
+            self.list,
+            MessageDirection::ToWidget,
+            items,
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.replace,
+            MessageDirection::ToWidget,
+            false,
+        ));
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    /// Returns the ragdoll, that was chosen to be replaced.
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        ui: &UserInterface,
+    ) -> Option<Handle<Node>> {
+        if let Some(ListViewMessage::SelectionChanged(selected)) = message.data() {
+            if message.destination() == self.list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.selected = *selected;
+                ui.send_message(WidgetMessage::enabled(
+                    self.replace,
+                    MessageDirection::ToWidget,
+                    selected.is_some(),
+                ));
+            }
+        } else if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.replace || message.destination() == self.cancel {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+
+                if message.destination() == self.replace {
+                    return self
+                        .selected
+                        .and_then(|index| self.ragdolls.get(index).cloned());
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Lists the bones, that will be skipped, before the ragdoll is generated.
+pub struct RagdollPreflightWindow {
+    window: Handle<UiNode>,
+    text: Handle<UiNode>,
+    generate: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+}
+
+impl RagdollPreflightWindow {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let text;
+        let generate;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(250.0))
+            .open(false)
+            .with_title(WindowTitle::text("Generate Ragdoll"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            ScrollViewerBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .with_margin(Thickness::uniform(2.0)),
+                            )
+                            .with_content({
+                                text = TextBuilder::new(WidgetBuilder::new())
+                                    .with_wrap(WrapMode::Word)
+                                    .build(ctx);
+                                text
+                            })
+                            .build(ctx),
+                        )
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        generate = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(100.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Generate")
+                                        .build(ctx);
+                                        generate
+                                    })
+                                    .with_child({
+                                        cancel = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(100.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Cancel")
+                                        .build(ctx);
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::stretch())
+                .add_row(Row::strict(24.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .with_cancel_button(cancel)
+            .build(ctx);
+
+        Self {
+            window,
+            text,
+            generate,
+            cancel,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface, check: &RagdollBoneCheck, mode: RagdollOutputMode) {
+        let mut text = String::new();
+        if !check.missing.is_empty() {
+            text += &format!(
+                "The ragdoll can't be generated, {} must be assigned.\n\n",
+                bone_list(&check.missing)
+            );
+        }
+        if !check.skipped.is_empty() {
+            text += &format!(
+                "{} have no bodies and will be skipped.",
+                bone_list(&check.skipped)
+            );
+            if mode == RagdollOutputMode::Ragdoll {
+                text += " The joints of their children are attached to the closest bodies up the \
+                    skeleton.";
+            }
+        }
+
+        ui.send_message(TextMessage::text(
+            self.text,
+            MessageDirection::ToWidget,
+            text.trim_end().to_owned(),
+        ));
+        ui.send_message(WidgetMessage::enabled(
+            self.generate,
+            MessageDirection::ToWidget,
+            check.missing.is_empty(),
+        ));
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    /// Returns true if the generation was confirmed.
+    pub fn handle_ui_message(&self, message: &UiMessage, ui: &UserInterface) -> bool {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.generate || message.destination() == self.cancel {
+                ui.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+                return message.destination() == self.generate;
+            }
+        }
+        false
+    }
+}
+
+pub fn make_file_selector(
+    ctx: &mut BuildContext,
+    title: &str,
+    mode: FileBrowserMode,
+    extension: &'static str,
+) -> Handle<UiNode> {
+    FileSelectorBuilder::new(
+        WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(400.0))
+            .with_title(WindowTitle::text(title))
+            .open(false),
+    )
+    .with_mode(mode)
+    .with_path("./")
+    .with_filter(Filter::new(move |p: &Path| {
+        if let Some(ext) = p.extension() {
+            ext.to_string_lossy().eq_ignore_ascii_case(extension)
+        } else {
+            p.is_dir()
+        }
+    }))
+    .build(ctx)
+}
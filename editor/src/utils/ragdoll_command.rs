@@ -0,0 +1,43 @@
+//! Undoable edits of the ragdoll preset. The preset belongs to the wizard, not to a scene, so the
+//! wizard has its own command stack, that is driven by the universal property commands.
+
+use crate::{
+    define_command_stack, define_universal_commands, utils::ragdoll::preset::RagdollPreset,
+};
+use fyrox::core::reflect::prelude::*;
+use std::fmt::Debug;
+
+pub struct RagdollPresetContext<'a> {
+    pub preset: &'a mut RagdollPreset,
+}
+
+define_command_stack!(
+    RagdollPresetCommand,
+    RagdollPresetCommandStack,
+    RagdollPresetContext
+);
+
+#[derive(Debug)]
+pub struct RagdollCommand(pub Box<dyn RagdollPresetCommand>);
+
+impl RagdollCommand {
+    pub fn new<C: RagdollPresetCommand>(cmd: C) -> Self {
+        Self(Box::new(cmd))
+    }
+
+    pub fn into_inner(self) -> Box<dyn RagdollPresetCommand> {
+        self.0
+    }
+}
+
+define_universal_commands!(
+    make_set_ragdoll_preset_property_command,
+    RagdollPresetCommand,
+    RagdollCommand,
+    RagdollPresetContext,
+    (),
+    ctx,
+    handle,
+    self,
+    { ctx.preset as &mut dyn Reflect },
+);
@@ -0,0 +1,257 @@
+//! Test simulation of a ragdoll. The bodies and the joints of the ragdoll are copied to a separate
+//! graph and simulated there, so the edited scene is never changed. Poses of the colliders are
+//! recorded in every step, the result is played back and scrubbed in the viewport.
+
+use fyrox::{
+    core::{
+        algebra::{Matrix4, UnitQuaternion, Vector2, Vector3},
+        color::Color,
+        math::{aabb::AxisAlignedBoundingBox, Matrix4Ext},
+        pool::Handle,
+    },
+    fxhash::FxHashSet,
+    scene::{
+        base::BaseBuilder,
+        collider::{Collider, ColliderBuilder, ColliderShape},
+        debug::SceneDrawingContext,
+        graph::{Graph, GraphUpdateSwitches},
+        joint::Joint,
+        node::Node,
+        rigidbody::{RigidBody, RigidBodyBuilder, RigidBodyType},
+        transform::TransformBuilder,
+    },
+};
+
+/// Half of the thickness of the ground, that the ragdoll falls on.
+const GROUND_HALF_HEIGHT: f32 = 0.1;
+
+/// Returns the lowest point of the collider shape in global coordinates, shapes other than balls,
+/// capsules and cuboids are treated as points.
+pub fn lowest_point(shape: &ColliderShape, transform: &Matrix4<f32>) -> f32 {
+    let position = transform.position();
+    match shape {
+        ColliderShape::Ball(ball) => position.y - ball.radius,
+        ColliderShape::Capsule(capsule) => {
+            let begin = transform.transform_point(&capsule.begin.into());
+            let end = transform.transform_point(&capsule.end.into());
+            begin.y.min(end.y) - capsule.radius
+        }
+        ColliderShape::Cuboid(cuboid) => {
+            let basis = transform.basis();
+            position.y
+                - (0..3)
+                    .map(|axis| basis[(1, axis)].abs() * cuboid.half_extents[axis])
+                    .sum::<f32>()
+        }
+        _ => position.y,
+    }
+}
+
+/// Draws the collider shape with the given global transform.
+pub fn draw_collider(
+    ctx: &mut SceneDrawingContext,
+    shape: &ColliderShape,
+    transform: Matrix4<f32>,
+    color: Color,
+) {
+    match shape {
+        ColliderShape::Ball(ball) => {
+            ctx.draw_wire_sphere(transform.position(), ball.radius, 16, color)
+        }
+        ColliderShape::Capsule(capsule) => ctx.draw_segment_capsule(
+            capsule.begin,
+            capsule.end,
+            capsule.radius,
+            10,
+            10,
+            transform,
+            color,
+        ),
+        ColliderShape::Cuboid(cuboid) => ctx.draw_oob(
+            &AxisAlignedBoundingBox::from_min_max(-cuboid.half_extents, cuboid.half_extents),
+            transform,
+            color,
+        ),
+        _ => (),
+    }
+}
+
+fn set_global_pose(graph: &mut Graph, node: Handle<Node>, global_transform: &Matrix4<f32>) {
+    let rotation = UnitQuaternion::from_matrix_eps(
+        &global_transform.basis(),
+        f32::EPSILON,
+        16,
+        Default::default(),
+    );
+    graph[node]
+        .local_transform_mut()
+        .set_position(global_transform.position())
+        .set_rotation(rotation);
+}
+
+/// Recorded test simulation of a ragdoll.
+pub struct RagdollSimulation {
+    /// Shapes of the colliders of the bodies.
+    colliders: Vec<ColliderShape>,
+    /// Global transforms of the colliders in every step, the first frame is the initial pose.
+    frames: Vec<Vec<Matrix4<f32>>>,
+}
+
+impl RagdollSimulation {
+    /// Simulates the bodies of the ragdoll for the given time with the fixed time step. The bodies
+    /// are dynamic during the simulation and fall on a static ground, that is put under the lowest
+    /// collider. The graph is not changed.
+    pub fn run(
+        graph: &Graph,
+        ragdoll: Handle<Node>,
+        duration: f32,
+        dt: f32,
+    ) -> Result<Self, String> {
+        let mut simulation_graph = Graph::new();
+
+        let mut bodies = Vec::new();
+        for handle in graph.traverse_handle_iter(ragdoll) {
+            if graph.try_get_of_type::<RigidBody>(handle).is_some() {
+                // Nested bodies and joints are copied separately, only colliders of the body and
+                // the rest of its children are copied with it.
+                let (copy, _) = graph.copy_node(handle, &mut simulation_graph, &mut |_, node| {
+                    node.query_component_ref::<RigidBody>().is_none()
+                        && node.query_component_ref::<Joint>().is_none()
+                });
+                // The ragdoll itself is not copied, so the bodies must keep their global poses.
+                set_global_pose(
+                    &mut simulation_graph,
+                    copy,
+                    &graph[handle].global_transform(),
+                );
+                let body = simulation_graph[copy].as_rigid_body_mut();
+                body.set_body_type(RigidBodyType::Dynamic);
+                bodies.push((handle, copy));
+            }
+        }
+        if bodies.is_empty() {
+            return Err("the ragdoll has no bodies.".to_owned());
+        }
+
+        let copy_of = |body: Handle<Node>| {
+            bodies
+                .iter()
+                .find(|(original, _)| *original == body)
+                .map(|(_, copy)| *copy)
+                .unwrap_or_default()
+        };
+        for handle in graph.traverse_handle_iter(ragdoll) {
+            if let Some(joint) = graph.try_get_of_type::<Joint>(handle) {
+                let (body1, body2) = (copy_of(joint.body1()), copy_of(joint.body2()));
+                let mut copy = graph.copy_single_node(handle);
+                let joint = copy.as_joint_mut();
+                joint.set_body1(body1);
+                joint.set_body2(body2);
+                let copy = simulation_graph.add_node(copy);
+                set_global_pose(
+                    &mut simulation_graph,
+                    copy,
+                    &graph[handle].global_transform(),
+                );
+            }
+        }
+
+        simulation_graph.update_hierarchical_data();
+        let colliders = bodies
+            .iter()
+            .flat_map(|(_, body)| simulation_graph[*body].children().to_vec())
+            .filter(|&child| {
+                simulation_graph
+                    .try_get_of_type::<Collider>(child)
+                    .is_some()
+            })
+            .collect::<Vec<_>>();
+        if colliders.is_empty() {
+            return Err("the bodies of the ragdoll have no colliders.".to_owned());
+        }
+        let lowest = colliders
+            .iter()
+            .map(|&collider| {
+                let collider_ref = &simulation_graph[collider];
+                lowest_point(
+                    collider_ref.as_collider().shape(),
+                    &collider_ref.global_transform(),
+                )
+            })
+            .fold(f32::MAX, f32::min);
+
+        // The first body is the hips, the ground is centered under them.
+        let hips_position = simulation_graph[bodies[0].1].global_position();
+        let ground_collider = ColliderBuilder::new(BaseBuilder::new())
+            .with_shape(ColliderShape::cuboid(100.0, GROUND_HALF_HEIGHT, 100.0))
+            .build(&mut simulation_graph);
+        RigidBodyBuilder::new(
+            BaseBuilder::new()
+                .with_local_transform(
+                    TransformBuilder::new()
+                        .with_local_position(Vector3::new(
+                            hips_position.x,
+                            lowest - GROUND_HALF_HEIGHT,
+                            hips_position.z,
+                        ))
+                        .build(),
+                )
+                .with_children(&[ground_collider]),
+        )
+        .with_body_type(RigidBodyType::Static)
+        .build(&mut simulation_graph);
+
+        // Only the bodies take their poses from the physics, nothing else must be updated.
+        let switches = GraphUpdateSwitches {
+            physics2d: false,
+            node_overrides: Some(
+                bodies
+                    .iter()
+                    .map(|(_, copy)| *copy)
+                    .collect::<FxHashSet<_>>(),
+            ),
+            delete_dead_nodes: false,
+            ..Default::default()
+        };
+
+        let record = |graph: &Graph| {
+            colliders
+                .iter()
+                .map(|&collider| graph[collider].global_transform())
+                .collect::<Vec<_>>()
+        };
+        simulation_graph.update_hierarchical_data();
+        let mut frames = vec![record(&simulation_graph)];
+        let steps = (duration / dt).ceil().max(0.0) as usize;
+        for _ in 0..steps {
+            simulation_graph.update(Vector2::new(1.0, 1.0), dt, switches.clone());
+            simulation_graph.update_hierarchical_data();
+            frames.push(record(&simulation_graph));
+        }
+
+        Ok(Self {
+            colliders: colliders
+                .iter()
+                .map(|&collider| simulation_graph[collider].as_collider().shape().clone())
+                .collect(),
+            frames,
+        })
+    }
+
+    /// Returns the number of recorded frames, including the initial pose.
+    pub fn frame_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Returns the global transforms of the colliders in the frame.
+    pub fn frame(&self, index: usize) -> &[Matrix4<f32>] {
+        &self.frames[index.min(self.frames.len() - 1)]
+    }
+
+    /// Draws the colliders in their poses in the frame.
+    pub fn draw(&self, index: usize, ctx: &mut SceneDrawingContext, color: Color) {
+        for (shape, transform) in self.colliders.iter().zip(self.frame(index)) {
+            draw_collider(ctx, shape, *transform, color);
+        }
+    }
+}
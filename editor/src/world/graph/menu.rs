@@ -5,8 +5,7 @@ use crate::{
     scene::{
         commands::{
             graph::{AddNodeCommand, ReplaceNodeCommand, SetGraphRootCommand},
-            make_delete_selection_command, CommandGroup, RevertSceneNodePropertyCommand,
-            SceneCommand,
+            make_delete_selection_command, CommandGroup, RevertPropertyCommand, SceneCommand,
         },
         EditorScene, Selection,
     },
@@ -236,15 +235,20 @@ impl ItemContextMenu {
                     let scene = &engine.scenes[editor_scene.scene];
                     let mut commands = Vec::new();
                     for node_handle in graph_selection.nodes.iter() {
-                        if let Some(node) = scene.graph.try_get(*node_handle) {
+                        // Only instances of some resource have parent values to revert to.
+                        if let Some(node) = scene
+                            .graph
+                            .try_get(*node_handle)
+                            .filter(|node| node.resource().is_some())
+                        {
                             (node as &dyn Reflect).enumerate_fields_recursively(
                                 &mut |path, _, val| {
                                     val.as_inheritable_variable(&mut |inheritable| {
                                         if inheritable.is_some() {
                                             commands.push(SceneCommand::new(
-                                                RevertSceneNodePropertyCommand::new(
-                                                    path.to_string(),
+                                                RevertPropertyCommand::new(
                                                     *node_handle,
+                                                    path.to_string(),
                                                 ),
                                             ));
                                         }
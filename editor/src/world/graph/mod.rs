@@ -1,3 +1,4 @@
 pub mod item;
 pub mod menu;
+pub mod query;
 pub mod selection;
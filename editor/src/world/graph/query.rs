@@ -0,0 +1,388 @@
+use crate::{
+    gui::make_dropdown_list_option,
+    message::MessageSender,
+    scene::{commands::ChangeSelectionCommand, EditorScene, Selection},
+    world::graph::selection::GraphSelection,
+};
+use fyrox::{
+    core::pool::Handle,
+    engine::Engine,
+    gui::{
+        button::{ButtonBuilder, ButtonMessage},
+        dropdown_list::{DropdownListBuilder, DropdownListMessage},
+        grid::{Column, GridBuilder, Row},
+        message::{MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
+        text::{TextBuilder, TextMessage},
+        text_box::TextBoxBuilder,
+        widget::WidgetBuilder,
+        window::{WindowBuilder, WindowMessage, WindowTitle},
+        BuildContext, HorizontalAlignment, Orientation, Thickness, UiNode, UserInterface,
+        VerticalAlignment,
+    },
+    scene::{
+        collider::Collider,
+        dim2::{
+            collider::Collider as Collider2D, joint::Joint as Joint2D,
+            rigidbody::RigidBody as RigidBody2D,
+        },
+        graph::Graph,
+        joint::Joint,
+        mesh::Mesh,
+        node::Node,
+        rigidbody::RigidBody,
+    },
+};
+
+/// Kind of scene nodes, that will pass the query.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum NodeKindFilter {
+    Any,
+    Collider,
+    Collider2D,
+    RigidBody,
+    RigidBody2D,
+    Joint,
+    Joint2D,
+    Mesh,
+}
+
+impl NodeKindFilter {
+    const ALL: [NodeKindFilter; 8] = [
+        NodeKindFilter::Any,
+        NodeKindFilter::Collider,
+        NodeKindFilter::Collider2D,
+        NodeKindFilter::RigidBody,
+        NodeKindFilter::RigidBody2D,
+        NodeKindFilter::Joint,
+        NodeKindFilter::Joint2D,
+        NodeKindFilter::Mesh,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            NodeKindFilter::Any => "Any",
+            NodeKindFilter::Collider => "Collider",
+            NodeKindFilter::Collider2D => "Collider 2D",
+            NodeKindFilter::RigidBody => "Rigid Body",
+            NodeKindFilter::RigidBody2D => "Rigid Body 2D",
+            NodeKindFilter::Joint => "Joint",
+            NodeKindFilter::Joint2D => "Joint 2D",
+            NodeKindFilter::Mesh => "Mesh",
+        }
+    }
+
+    pub fn matches(self, node: &Node) -> bool {
+        match self {
+            NodeKindFilter::Any => true,
+            NodeKindFilter::Collider => node.query_component_ref::<Collider>().is_some(),
+            NodeKindFilter::Collider2D => node.query_component_ref::<Collider2D>().is_some(),
+            NodeKindFilter::RigidBody => node.query_component_ref::<RigidBody>().is_some(),
+            NodeKindFilter::RigidBody2D => node.query_component_ref::<RigidBody2D>().is_some(),
+            NodeKindFilter::Joint => node.query_component_ref::<Joint>().is_some(),
+            NodeKindFilter::Joint2D => node.query_component_ref::<Joint2D>().is_some(),
+            NodeKindFilter::Mesh => node.query_component_ref::<Mesh>().is_some(),
+        }
+    }
+}
+
+/// Case-insensitive name matching, where `*` matches any sequence of characters (including empty
+/// one) and `?` matches exactly one character.
+pub fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern = pattern.to_lowercase().chars().collect::<Vec<_>>();
+    let text = text.to_lowercase().chars().collect::<Vec<_>>();
+
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            // Let the last star consume one more character and try again.
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Returns every scene node (editor objects are ignored), that matches the given name pattern and
+/// node kind.
+pub fn query_nodes(
+    graph: &Graph,
+    root: Handle<Node>,
+    pattern: &str,
+    kind: NodeKindFilter,
+) -> Vec<Handle<Node>> {
+    graph
+        .traverse_handle_iter(root)
+        .filter(|h| {
+            let node = &graph[*h];
+            kind.matches(node) && wildcard_match(pattern, node.name())
+        })
+        .collect()
+}
+
+/// A dialog, that allows you to select scene nodes by their name pattern and type. For example,
+/// `*Foot*` + `Collider` selects every foot collider of every ragdoll in the scene, and then all
+/// of them could be edited at once in the inspector.
+pub struct NodeQueryDialog {
+    pub window: Handle<UiNode>,
+    pattern_box: Handle<UiNode>,
+    kind_list: Handle<UiNode>,
+    select: Handle<UiNode>,
+    cancel: Handle<UiNode>,
+    pattern: String,
+    kind: NodeKindFilter,
+}
+
+impl NodeQueryDialog {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let pattern_box;
+        let kind_list;
+        let select;
+        let cancel;
+        let window = WindowBuilder::new(WidgetBuilder::new().with_width(300.0).with_height(110.0))
+            .open(false)
+            .with_title(WindowTitle::text("Select By Query"))
+            .with_content(
+                GridBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .on_column(0)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .with_text("Name Pattern")
+                            .build(ctx),
+                        )
+                        .with_child({
+                            pattern_box = TextBoxBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(0)
+                                    .on_column(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_text("*")
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .build(ctx);
+                            pattern_box
+                        })
+                        .with_child(
+                            TextBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .on_column(0)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_vertical_text_alignment(VerticalAlignment::Center)
+                            .with_text("Type")
+                            .build(ctx),
+                        )
+                        .with_child({
+                            kind_list = DropdownListBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(1)
+                                    .on_column(1)
+                                    .with_margin(Thickness::uniform(1.0)),
+                            )
+                            .with_items(
+                                NodeKindFilter::ALL
+                                    .iter()
+                                    .map(|kind| make_dropdown_list_option(ctx, kind.name()))
+                                    .collect(),
+                            )
+                            .with_selected(0)
+                            .build(ctx);
+                            kind_list
+                        })
+                        .with_child(
+                            StackPanelBuilder::new(
+                                WidgetBuilder::new()
+                                    .on_row(2)
+                                    .on_column(1)
+                                    .with_horizontal_alignment(HorizontalAlignment::Right)
+                                    .with_child({
+                                        select = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Select")
+                                        .build(ctx);
+                                        select
+                                    })
+                                    .with_child({
+                                        cancel = ButtonBuilder::new(
+                                            WidgetBuilder::new()
+                                                .with_width(80.0)
+                                                .with_margin(Thickness::uniform(1.0)),
+                                        )
+                                        .with_text("Cancel")
+                                        .build(ctx);
+                                        cancel
+                                    }),
+                            )
+                            .with_orientation(Orientation::Horizontal)
+                            .build(ctx),
+                        ),
+                )
+                .add_row(Row::strict(24.0))
+                .add_row(Row::strict(24.0))
+                .add_row(Row::strict(24.0))
+                .add_column(Column::strict(90.0))
+                .add_column(Column::stretch())
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            window,
+            pattern_box,
+            kind_list,
+            select,
+            cancel,
+            pattern: "*".to_string(),
+            kind: NodeKindFilter::Any,
+        }
+    }
+
+    pub fn open(&self, ui: &UserInterface) {
+        ui.send_message(WindowMessage::open_modal(
+            self.window,
+            MessageDirection::ToWidget,
+            true,
+        ));
+    }
+
+    pub fn handle_ui_message(
+        &mut self,
+        message: &UiMessage,
+        editor_scene: &EditorScene,
+        engine: &Engine,
+        sender: &MessageSender,
+    ) {
+        if let Some(ButtonMessage::Click) = message.data() {
+            if message.destination() == self.select {
+                let graph = &engine.scenes[editor_scene.scene].graph;
+                let nodes = query_nodes(
+                    graph,
+                    editor_scene.scene_content_root,
+                    &self.pattern,
+                    self.kind,
+                );
+
+                let new_selection = Selection::Graph(GraphSelection::from_list(nodes));
+                if new_selection != editor_scene.selection {
+                    sender.do_scene_command(ChangeSelectionCommand::new(
+                        new_selection,
+                        editor_scene.selection.clone(),
+                    ));
+                }
+
+                engine.user_interface.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            } else if message.destination() == self.cancel {
+                engine.user_interface.send_message(WindowMessage::close(
+                    self.window,
+                    MessageDirection::ToWidget,
+                ));
+            }
+        } else if let Some(TextMessage::Text(text)) = message.data() {
+            if message.destination() == self.pattern_box
+                && message.direction() == MessageDirection::FromWidget
+            {
+                self.pattern = text.clone();
+            }
+        } else if let Some(DropdownListMessage::SelectionChanged(Some(index))) = message.data() {
+            if message.destination() == self.kind_list
+                && message.direction() == MessageDirection::FromWidget
+            {
+                if let Some(kind) = NodeKindFilter::ALL.get(*index) {
+                    self.kind = *kind;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use fyrox::scene::{
+        base::BaseBuilder, collider::ColliderBuilder, mesh::MeshBuilder, pivot::PivotBuilder,
+        rigidbody::RigidBodyBuilder,
+    };
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*", ""));
+        assert!(wildcard_match("*", "LeftFoot"));
+        assert!(wildcard_match("*Foot*", "LeftFoot"));
+        assert!(wildcard_match("*Foot*", "FootCollider"));
+        assert!(wildcard_match("Left*Foot", "LeftFoot"));
+        assert!(wildcard_match("Left*Foot", "LeftBigFoot"));
+        assert!(!wildcard_match("Left*Foot", "LeftFootCollider"));
+        assert!(wildcard_match("*a*b", "aXbYab"));
+
+        assert!(wildcard_match("Bone?", "Bone1"));
+        assert!(!wildcard_match("Bone?", "Bone"));
+        assert!(!wildcard_match("Bone?", "Bone12"));
+        assert!(wildcard_match("?one*", "Bone12"));
+
+        assert!(wildcard_match("", ""));
+        assert!(!wildcard_match("", "Bone"));
+        assert!(!wildcard_match("Bone", ""));
+
+        assert!(wildcard_match("leftfoot", "LeftFoot"));
+        assert!(wildcard_match("*FOOT", "LeftFoot"));
+    }
+
+    #[test]
+    fn test_query_nodes() {
+        let mut graph = Graph::new();
+        let root = PivotBuilder::new(BaseBuilder::new().with_name("Root")).build(&mut graph);
+        let body =
+            RigidBodyBuilder::new(BaseBuilder::new().with_name("LeftFoot")).build(&mut graph);
+        let collider = ColliderBuilder::new(BaseBuilder::new().with_name("LeftFootCollider"))
+            .build(&mut graph);
+        let mesh = MeshBuilder::new(BaseBuilder::new().with_name("RightFoot")).build(&mut graph);
+        graph.link_nodes(body, root);
+        graph.link_nodes(collider, body);
+        graph.link_nodes(mesh, root);
+        // Outside of the root, must be ignored.
+        PivotBuilder::new(BaseBuilder::new().with_name("OtherFoot")).build(&mut graph);
+
+        // Nodes are returned in the order of traversal, the last child goes first.
+        let query = |pattern: &str, kind: NodeKindFilter| query_nodes(&graph, root, pattern, kind);
+
+        assert_eq!(
+            query("*", NodeKindFilter::Any),
+            vec![root, mesh, body, collider]
+        );
+        assert_eq!(
+            query("*foot*", NodeKindFilter::Any),
+            vec![mesh, body, collider]
+        );
+        assert_eq!(query("*Foot*", NodeKindFilter::Collider), vec![collider]);
+        assert_eq!(query("*Foot*", NodeKindFilter::RigidBody), vec![body]);
+        assert_eq!(query("*Foot*", NodeKindFilter::Mesh), vec![mesh]);
+        assert!(query("*", NodeKindFilter::Joint).is_empty());
+        assert!(query("*", NodeKindFilter::Collider2D).is_empty());
+        assert!(query("", NodeKindFilter::Any).is_empty());
+    }
+}
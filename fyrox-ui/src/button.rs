@@ -4,15 +4,17 @@
 
 use crate::{
     border::BorderBuilder,
+    brush::Brush,
     core::pool::Handle,
     decorator::DecoratorBuilder,
     define_constructor,
-    message::{MessageDirection, UiMessage},
+    message::{KeyCode, MessageDirection, UiMessage},
     text::TextBuilder,
     ttf::SharedFont,
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, NodeHandleMapping, Thickness, UiNode,
-    UserInterface, VerticalAlignment, BRUSH_DARKER, BRUSH_LIGHT, BRUSH_LIGHTER, BRUSH_LIGHTEST,
+    UserInterface, VerticalAlignment, BRUSH_BRIGHT_BLUE, BRUSH_DARKER, BRUSH_LIGHT, BRUSH_LIGHTER,
+    BRUSH_LIGHTEST,
 };
 use std::{
     any::{Any, TypeId},
@@ -82,6 +84,8 @@ pub struct Button {
     pub decorator: Handle<UiNode>,
     /// Current content of the button. It is attached to the content holder.
     pub content: Handle<UiNode>,
+    /// Foreground brush of the content holder, that was replaced by focus highlight.
+    unfocused_foreground: Option<Brush>,
 }
 
 crate::define_widget_deref!(Button);
@@ -120,6 +124,43 @@ impl Control for Button {
                         ui.capture_mouse(message.destination());
                         message.set_handled(true);
                     }
+                    WidgetMessage::KeyDown(
+                        KeyCode::Enter | KeyCode::NumpadEnter | KeyCode::Space,
+                    ) if !message.handled() => {
+                        ui.send_message(ButtonMessage::click(
+                            self.handle(),
+                            MessageDirection::FromWidget,
+                        ));
+                        message.set_handled(true);
+                    }
+                    _ => (),
+                }
+            }
+
+            // Highlight the button when it has keyboard focus.
+            if message.destination() == self.handle()
+                && message.direction() == MessageDirection::FromWidget
+            {
+                match msg {
+                    WidgetMessage::Focus => {
+                        if let Some(decorator) = ui.try_get_node(self.decorator) {
+                            self.unfocused_foreground = Some(decorator.foreground());
+                            ui.send_message(WidgetMessage::foreground(
+                                self.decorator,
+                                MessageDirection::ToWidget,
+                                BRUSH_BRIGHT_BLUE,
+                            ));
+                        }
+                    }
+                    WidgetMessage::Unfocus => {
+                        if let Some(foreground) = self.unfocused_foreground.take() {
+                            ui.send_message(WidgetMessage::foreground(
+                                self.decorator,
+                                MessageDirection::ToWidget,
+                                foreground,
+                            ));
+                        }
+                    }
                     _ => (),
                 }
             }
@@ -267,9 +308,14 @@ impl ButtonBuilder {
         }
 
         let button = Button {
-            widget: self.widget_builder.with_child(back).build(),
+            widget: self
+                .widget_builder
+                .with_default_tab_stop()
+                .with_child(back)
+                .build(),
             decorator: back,
             content,
+            unfocused_foreground: None,
         };
         ctx.add_node(UiNode::new(button))
     }
@@ -10,7 +10,7 @@ use crate::{
     core::{algebra::Vector2, color::Color, pool::Handle},
     define_constructor,
     grid::{Column, GridBuilder, Row},
-    message::{MessageDirection, UiMessage},
+    message::{KeyCode, MessageDirection, UiMessage},
     vector_image::{Primitive, VectorImageBuilder},
     widget::{Widget, WidgetBuilder, WidgetMessage},
     BuildContext, Control, HorizontalAlignment, MouseButton, NodeHandleMapping, Thickness, UiNode,
@@ -196,6 +196,16 @@ impl Control for CheckBox {
                         }
                     }
                 }
+                WidgetMessage::KeyDown(KeyCode::Space)
+                    if message.destination() == self.handle() && !message.handled() =>
+                {
+                    ui.send_message(CheckBoxMessage::checked(
+                        self.handle(),
+                        MessageDirection::ToWidget,
+                        Some(!self.checked.unwrap_or(false)),
+                    ));
+                    message.set_handled(true);
+                }
                 _ => (),
             }
         } else if let Some(&CheckBoxMessage::Check(value)) = message.data::<CheckBoxMessage>() {
@@ -414,7 +424,11 @@ impl CheckBoxBuilder {
         .build(ctx);
 
         let cb = CheckBox {
-            widget: self.widget_builder.with_child(grid).build(),
+            widget: self
+                .widget_builder
+                .with_default_tab_stop()
+                .with_child(grid)
+                .build(),
             checked: self.checked,
             check_mark,
             uncheck_mark,
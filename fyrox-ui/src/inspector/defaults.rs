@@ -0,0 +1,75 @@
+//! Default values of common property types, that are used to reset properties to the default
+//! values of their types.
+
+use crate::core::{
+    algebra::{UnitQuaternion, Vector2, Vector3, Vector4},
+    color::Color,
+    reflect::Reflect,
+};
+use fxhash::FxHashMap;
+use std::any::TypeId;
+
+type DefaultValueConstructor = fn() -> Box<dyn Reflect>;
+
+macro_rules! reg_default_value {
+    ($constructors:ident, $($ty:ty),*) => {
+        $(
+            $constructors.insert(
+                TypeId::of::<$ty>(),
+                (|| Box::new(<$ty>::default())) as DefaultValueConstructor,
+            );
+        )*
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref CONSTRUCTORS: FxHashMap<TypeId, DefaultValueConstructor> = {
+        let mut constructors = FxHashMap::default();
+
+        reg_default_value! { constructors, bool, String, Color }
+        reg_default_value! { constructors, f64, f32, i64, u64, i32, u32, i16, u16, i8, u8, usize, isize }
+        reg_default_value! { constructors,
+            Vector2<f64>, Vector2<f32>, Vector2<i64>, Vector2<u64>, Vector2<i32>, Vector2<u32>,
+            Vector2<i16>, Vector2<u16>, Vector2<i8>, Vector2<u8>, Vector2<usize>, Vector2<isize>
+        }
+        reg_default_value! { constructors,
+            Vector3<f64>, Vector3<f32>, Vector3<i64>, Vector3<u64>, Vector3<i32>, Vector3<u32>,
+            Vector3<i16>, Vector3<u16>, Vector3<i8>, Vector3<u8>, Vector3<usize>, Vector3<isize>
+        }
+        reg_default_value! { constructors,
+            Vector4<f64>, Vector4<f32>, Vector4<i64>, Vector4<u64>, Vector4<i32>, Vector4<u32>,
+            Vector4<i16>, Vector4<u16>, Vector4<i8>, Vector4<u8>, Vector4<usize>, Vector4<isize>
+        }
+
+        // Identity rotation is the default one.
+        constructors.insert(
+            TypeId::of::<UnitQuaternion<f32>>(),
+            (|| Box::new(UnitQuaternion::<f32>::identity())) as DefaultValueConstructor,
+        );
+        constructors.insert(
+            TypeId::of::<UnitQuaternion<f64>>(),
+            (|| Box::new(UnitQuaternion::<f64>::identity())) as DefaultValueConstructor,
+        );
+
+        constructors
+    };
+}
+
+/// Checks whether there's a default value of the given type.
+pub fn has_default_value(type_id: TypeId) -> bool {
+    CONSTRUCTORS.contains_key(&type_id)
+}
+
+/// Makes the default value of the given type, returns `None` if there's no default value of the
+/// type.
+pub fn make_default_value(type_id: TypeId) -> Option<Box<dyn Reflect>> {
+    CONSTRUCTORS.get(&type_id).map(|constructor| constructor())
+}
+
+/// Returns the type of the value of the property, inheritable variables are transparent, so the
+/// type of their inner values is returned.
+pub fn property_value_type_id(property: &dyn Reflect) -> TypeId {
+    let mut type_id = TypeId::of::<()>();
+    property.as_any(&mut |any| type_id = any.type_id());
+    type_id
+}
@@ -1,11 +1,13 @@
 use crate::{
     bit::{BitContainer, BitFieldBuilder, BitFieldMessage},
+    core::reflect::FieldInfo,
     inspector::{
         editors::{
-            PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+            clone_property_value, PropertyEditorBuildContext, PropertyEditorDefinition,
+            PropertyEditorDefinitionContainer, PropertyEditorInstance,
             PropertyEditorMessageContext, PropertyEditorTranslationContext,
         },
-        FieldKind, InspectorError, PropertyChanged,
+        FieldKind, InspectorError, ObjectValue, PropertyChanged,
     },
     MessageDirection, Thickness, UiMessage, WidgetBuilder,
 };
@@ -76,4 +78,12 @@ where
         }
         None
     }
+
+    fn clone_value(
+        &self,
+        property_info: &FieldInfo,
+        _: &PropertyEditorDefinitionContainer,
+    ) -> Option<ObjectValue> {
+        clone_property_value::<T>(property_info)
+    }
 }
@@ -1,12 +1,14 @@
 use crate::inspector::editors::PropertyEditorTranslationContext;
 use crate::{
     check_box::{CheckBoxBuilder, CheckBoxMessage},
+    core::reflect::FieldInfo,
     inspector::{
         editors::{
-            PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+            clone_property_value, PropertyEditorBuildContext, PropertyEditorDefinition,
+            PropertyEditorDefinitionContainer, PropertyEditorInstance,
             PropertyEditorMessageContext,
         },
-        FieldKind, InspectorError, PropertyChanged,
+        FieldKind, InspectorError, ObjectValue, PropertyChanged,
     },
     message::{MessageDirection, UiMessage},
     widget::WidgetBuilder,
@@ -63,4 +65,12 @@ impl PropertyEditorDefinition for BoolPropertyEditorDefinition {
         }
         None
     }
+
+    fn clone_value(
+        &self,
+        property_info: &FieldInfo,
+        _: &PropertyEditorDefinitionContainer,
+    ) -> Option<ObjectValue> {
+        clone_property_value::<bool>(property_info)
+    }
 }
@@ -8,17 +8,20 @@ use crate::{
     grid::{Column, GridBuilder, Row},
     inspector::{
         editors::{
-            PropertyEditorBuildContext, PropertyEditorDefinition,
+            clone_property_value, PropertyEditorBuildContext, PropertyEditorDefinition,
             PropertyEditorDefinitionContainer, PropertyEditorInstance,
             PropertyEditorMessageContext, PropertyEditorTranslationContext,
         },
         make_expander_container, make_property_margin, CollectionChanged, FieldKind,
         InspectorEnvironment, InspectorError, ObjectValue, PropertyChanged, PropertyFilter,
     },
+    menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
     message::{MessageDirection, UiMessage},
+    popup::{Popup, PopupBuilder},
     stack_panel::StackPanelBuilder,
+    utils::{make_arrow, ArrowDirection},
     widget::{Widget, WidgetBuilder, WidgetMessage},
-    BuildContext, Control, HorizontalAlignment, Thickness, UiNode, UserInterface,
+    BuildContext, Control, HorizontalAlignment, RcUiNodeHandle, Thickness, UiNode, UserInterface,
     VerticalAlignment,
 };
 use std::{
@@ -32,9 +35,63 @@ use std::{
 #[derive(Clone, Debug, PartialEq)]
 pub struct Item {
     editor_instance: PropertyEditorInstance,
+    move_up: Handle<UiNode>,
+    move_down: Handle<UiNode>,
     remove: Handle<UiNode>,
 }
 
+/// Context menu of the items of a collection, it allows to insert new items next to the item,
+/// that the menu was opened for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CollectionItemMenu {
+    pub menu: RcUiNodeHandle,
+    pub insert_above: Handle<UiNode>,
+    pub insert_below: Handle<UiNode>,
+}
+
+impl CollectionItemMenu {
+    pub fn new(ctx: &mut BuildContext) -> Self {
+        let insert_above;
+        let insert_below;
+        let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
+            .with_content(
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            insert_above = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Insert Above"))
+                                .build(ctx);
+                            insert_above
+                        })
+                        .with_child({
+                            insert_below = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Insert Below"))
+                                .build(ctx);
+                            insert_below
+                        }),
+                )
+                .build(ctx),
+            )
+            .build(ctx);
+
+        Self {
+            menu: RcUiNodeHandle::new(menu, ctx.sender()),
+            insert_above,
+            insert_below,
+        }
+    }
+
+    /// Returns the index of the item, that the menu was opened for. Every view of an item keeps
+    /// the index of the item as user data.
+    fn target_index(&self, ui: &UserInterface) -> Option<usize> {
+        ui.try_get_node(*self.menu)
+            .and_then(|menu| menu.cast::<Popup>())
+            .and_then(|popup| ui.try_get_node(popup.placement.target()))
+            .and_then(|view| view.user_data_ref::<usize>())
+            .cloned()
+    }
+}
+
 pub trait CollectionItem: Clone + Reflect + Debug + Default + 'static {}
 
 impl<T: Clone + Reflect + Debug + Default + 'static> CollectionItem for T {}
@@ -46,6 +103,7 @@ pub struct CollectionEditor<T: CollectionItem> {
     pub items: Vec<Item>,
     pub panel: Handle<UiNode>,
     pub layer_index: usize,
+    pub item_menu: Option<CollectionItemMenu>,
     pub phantom: PhantomData<T>,
 }
 
@@ -57,6 +115,7 @@ impl<T: CollectionItem> Clone for CollectionEditor<T> {
             items: self.items.clone(),
             panel: self.panel,
             layer_index: self.layer_index,
+            item_menu: self.item_menu.clone(),
             phantom: PhantomData,
         }
     }
@@ -110,11 +169,40 @@ impl<T: CollectionItem> Control for CollectionEditor<T> {
                     MessageDirection::FromWidget,
                     index,
                 ));
+            } else if let Some(index) = self
+                .items
+                .iter()
+                .position(|i| i.move_up == message.destination())
+            {
+                // The first item can't be moved up.
+                if index > 0 {
+                    ui.send_message(CollectionChanged::move_item(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        index,
+                        index - 1,
+                    ));
+                }
+            } else if let Some(index) = self
+                .items
+                .iter()
+                .position(|i| i.move_down == message.destination())
+            {
+                // The last item can't be moved down.
+                if index + 1 < self.items.len() {
+                    ui.send_message(CollectionChanged::move_item(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        index,
+                        index + 1,
+                    ));
+                }
             }
         } else if let Some(msg) = message.data::<CollectionEditorMessage>() {
             if message.destination == self.handle {
                 if let CollectionEditorMessage::Items(items) = msg {
-                    let views = create_item_views(items, &mut ui.build_ctx());
+                    let views =
+                        create_item_views(items, self.item_menu.as_ref(), &mut ui.build_ctx());
 
                     for old_item in ui.node(self.panel).children() {
                         ui.send_message(WidgetMessage::remove(
@@ -159,6 +247,27 @@ impl<T: CollectionItem> Control for CollectionEditor<T> {
                     },
                 ))
             }
+        } else if let Some(MenuItemMessage::Click) = message.data() {
+            if let Some(item_menu) = self.item_menu.as_ref() {
+                let offset = if message.destination() == item_menu.insert_above {
+                    Some(0)
+                } else if message.destination() == item_menu.insert_below {
+                    Some(1)
+                } else {
+                    None
+                };
+
+                if let (Some(offset), Some(index)) = (offset, item_menu.target_index(ui)) {
+                    ui.send_message(CollectionChanged::insert(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        index + offset,
+                        ObjectValue {
+                            value: Box::<T>::default(),
+                        },
+                    ))
+                }
+            }
         }
     }
 }
@@ -179,21 +288,35 @@ where
     immutable_collection: bool,
 }
 
-fn create_item_views(items: &[Item], ctx: &mut BuildContext) -> Vec<Handle<UiNode>> {
+fn create_item_views(
+    items: &[Item],
+    item_menu: Option<&CollectionItemMenu>,
+    ctx: &mut BuildContext,
+) -> Vec<Handle<UiNode>> {
     items
         .iter()
-        .map(|item| {
+        .enumerate()
+        .map(|(index, item)| {
+            let mut widget_builder = WidgetBuilder::new().with_user_data(Rc::new(index));
+            if let Some(item_menu) = item_menu {
+                widget_builder = widget_builder.with_context_menu(item_menu.menu.clone());
+            }
+
             GridBuilder::new(
-                WidgetBuilder::new()
+                widget_builder
                     .with_child(match item.editor_instance {
                         PropertyEditorInstance::Simple { editor } => editor,
                         PropertyEditorInstance::Custom { container, .. } => container,
                     })
+                    .with_child(item.move_up)
+                    .with_child(item.move_down)
                     .with_child(item.remove),
             )
             .add_row(Row::stretch())
             .add_column(Column::stretch())
             .add_column(Column::auto())
+            .add_column(Column::auto())
+            .add_column(Column::auto())
             .build(ctx)
         })
         .collect::<Vec<_>>()
@@ -265,21 +388,31 @@ where
                 ctx[editor].set_margin(make_property_margin(layer_index + 1));
             }
 
-            let remove = ButtonBuilder::new(
-                WidgetBuilder::new()
-                    .with_visibility(!immutable_collection)
-                    .with_margin(Thickness::uniform(1.0))
-                    .with_vertical_alignment(VerticalAlignment::Top)
-                    .with_horizontal_alignment(HorizontalAlignment::Right)
-                    .on_column(1)
-                    .with_width(16.0)
-                    .with_height(16.0),
-            )
-            .with_text("-")
-            .build(ctx);
+            let make_button = |column: usize| {
+                ButtonBuilder::new(
+                    WidgetBuilder::new()
+                        .with_visibility(!immutable_collection)
+                        .with_margin(Thickness::uniform(1.0))
+                        .with_vertical_alignment(VerticalAlignment::Top)
+                        .with_horizontal_alignment(HorizontalAlignment::Right)
+                        .on_column(column)
+                        .with_width(16.0)
+                        .with_height(16.0),
+                )
+            };
+
+            let move_up = make_button(1)
+                .with_content(make_arrow(ctx, ArrowDirection::Top, 8.0))
+                .build(ctx);
+            let move_down = make_button(2)
+                .with_content(make_arrow(ctx, ArrowDirection::Bottom, 8.0))
+                .build(ctx);
+            let remove = make_button(3).with_text("-").build(ctx);
 
             items.push(Item {
                 editor_instance: editor,
+                move_up,
+                move_down,
                 remove,
             });
         } else {
@@ -386,9 +519,18 @@ where
             Vec::new()
         };
 
-        let panel = StackPanelBuilder::new(
-            WidgetBuilder::new().with_children(create_item_views(&items, ctx)),
-        )
+        // Items of immutable collections can't be inserted.
+        let item_menu = if self.immutable_collection {
+            None
+        } else {
+            Some(CollectionItemMenu::new(ctx))
+        };
+
+        let panel = StackPanelBuilder::new(WidgetBuilder::new().with_children(create_item_views(
+            &items,
+            item_menu.as_ref(),
+            ctx,
+        )))
         .build(ctx);
 
         let ce = CollectionEditor::<T> {
@@ -401,6 +543,7 @@ where
             items,
             panel,
             layer_index: self.layer_index,
+            item_menu,
             phantom: PhantomData,
         };
 
@@ -612,4 +755,12 @@ where
 
         None
     }
+
+    fn clone_value(
+        &self,
+        property_info: &FieldInfo,
+        _: &PropertyEditorDefinitionContainer,
+    ) -> Option<ObjectValue> {
+        clone_property_value::<Vec<T>>(property_info)
+    }
 }
@@ -3,13 +3,14 @@ use crate::{
         gradient::{ColorGradientEditorBuilder, ColorGradientEditorMessage},
         ColorFieldBuilder, ColorFieldMessage,
     },
-    core::{algebra::Vector2, color::Color, color_gradient::ColorGradient},
+    core::{algebra::Vector2, color::Color, color_gradient::ColorGradient, reflect::FieldInfo},
     inspector::{
         editors::{
-            PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+            clone_property_value, PropertyEditorBuildContext, PropertyEditorDefinition,
+            PropertyEditorDefinitionContainer, PropertyEditorInstance,
             PropertyEditorMessageContext, PropertyEditorTranslationContext,
         },
-        FieldKind, InspectorError, PropertyChanged,
+        FieldKind, InspectorError, ObjectValue, PropertyChanged,
     },
     message::{MessageDirection, UiMessage},
     widget::WidgetBuilder,
@@ -65,6 +66,14 @@ impl PropertyEditorDefinition for ColorPropertyEditorDefinition {
         }
         None
     }
+
+    fn clone_value(
+        &self,
+        property_info: &FieldInfo,
+        _: &PropertyEditorDefinitionContainer,
+    ) -> Option<ObjectValue> {
+        clone_property_value::<Color>(property_info)
+    }
 }
 
 #[derive(Debug)]
@@ -1,18 +1,18 @@
 use crate::inspector::PropertyFilter;
 use crate::{
     border::BorderBuilder,
-    core::pool::Handle,
+    core::{pool::Handle, reflect::FieldInfo},
     decorator::DecoratorBuilder,
     define_constructor,
     dropdown_list::{DropdownList, DropdownListBuilder, DropdownListMessage},
     inspector::{
         editors::{
-            PropertyEditorBuildContext, PropertyEditorDefinition,
+            clone_property_value, PropertyEditorBuildContext, PropertyEditorDefinition,
             PropertyEditorDefinitionContainer, PropertyEditorInstance,
             PropertyEditorMessageContext, PropertyEditorTranslationContext,
         },
         make_expander_container, FieldKind, Inspector, InspectorBuilder, InspectorContext,
-        InspectorEnvironment, InspectorError, InspectorMessage, PropertyChanged,
+        InspectorEnvironment, InspectorError, InspectorMessage, ObjectValue, PropertyChanged,
     },
     message::{MessageDirection, UiMessage},
     text::TextBuilder,
@@ -501,4 +501,12 @@ where
 
         None
     }
+
+    fn clone_value(
+        &self,
+        property_info: &FieldInfo,
+        _: &PropertyEditorDefinitionContainer,
+    ) -> Option<ObjectValue> {
+        clone_property_value::<T>(property_info)
+    }
 }
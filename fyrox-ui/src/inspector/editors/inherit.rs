@@ -8,10 +8,11 @@ use crate::{
     grid::{Column, GridBuilder, Row},
     inspector::{
         editors::{
-            PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+            PropertyEditorBuildContext, PropertyEditorDefinition,
+            PropertyEditorDefinitionContainer, PropertyEditorInstance,
             PropertyEditorMessageContext, PropertyEditorTranslationContext,
         },
-        InspectorError, PropertyChanged,
+        InspectorError, ObjectValue, PropertyChanged,
     },
     inspector::{FieldKind, InheritableAction},
     message::UiMessage,
@@ -338,4 +339,18 @@ where
 
         None
     }
+
+    fn clone_value(
+        &self,
+        property_info: &FieldInfo,
+        definition_container: &PropertyEditorDefinitionContainer,
+    ) -> Option<ObjectValue> {
+        // Changes of the property carry inner values (see `translate_message`), so the inner
+        // value is cloned by the inner property editor.
+        let definition = definition_container
+            .definitions()
+            .get(&TypeId::of::<T>())
+            .cloned()?;
+        definition.clone_value(&make_proxy::<T>(property_info).ok()?, definition_container)
+    }
 }
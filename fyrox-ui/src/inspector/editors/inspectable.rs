@@ -2,11 +2,12 @@ use crate::{
     core::reflect::prelude::*,
     inspector::{
         editors::{
-            PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+            clone_property_value, PropertyEditorBuildContext, PropertyEditorDefinition,
+            PropertyEditorDefinitionContainer, PropertyEditorInstance,
             PropertyEditorMessageContext, PropertyEditorTranslationContext,
         },
         make_expander_container, FieldKind, Inspector, InspectorBuilder, InspectorContext,
-        InspectorError, InspectorMessage, PropertyChanged,
+        InspectorError, InspectorMessage, ObjectValue, PropertyChanged,
     },
     message::{MessageDirection, UiMessage},
     widget::WidgetBuilder,
@@ -23,6 +24,7 @@ where
     T: Reflect + 'static,
 {
     phantom: PhantomData<T>,
+    clone_value: Option<fn(&FieldInfo) -> Option<ObjectValue>>,
 }
 
 impl<T> InspectablePropertyEditorDefinition<T>
@@ -32,6 +34,18 @@ where
     pub fn new() -> Self {
         Self {
             phantom: PhantomData,
+            clone_value: None,
+        }
+    }
+
+    /// Creates a definition, that also allows to copy values of the properties.
+    pub fn new_cloneable() -> Self
+    where
+        T: Clone + Debug,
+    {
+        Self {
+            phantom: PhantomData,
+            clone_value: Some(clone_property_value::<T>),
         }
     }
 }
@@ -134,4 +148,13 @@ where
 
         None
     }
+
+    fn clone_value(
+        &self,
+        property_info: &FieldInfo,
+        _: &PropertyEditorDefinitionContainer,
+    ) -> Option<ObjectValue> {
+        self.clone_value
+            .and_then(|clone_value| clone_value(property_info))
+    }
 }
@@ -1,10 +1,12 @@
 use crate::{
+    core::reflect::FieldInfo,
     inspector::{
         editors::{
-            PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+            clone_property_value, PropertyEditorBuildContext, PropertyEditorDefinition,
+            PropertyEditorDefinitionContainer, PropertyEditorInstance,
             PropertyEditorMessageContext, PropertyEditorTranslationContext,
         },
-        FieldKind, InspectorError, PropertyChanged,
+        FieldKind, InspectorError, ObjectValue, PropertyChanged,
     },
     key::{
         HotKey, HotKeyEditorBuilder, HotKeyEditorMessage, KeyBinding, KeyBindingEditorBuilder,
@@ -62,6 +64,14 @@ impl PropertyEditorDefinition for HotKeyPropertyEditorDefinition {
         }
         None
     }
+
+    fn clone_value(
+        &self,
+        property_info: &FieldInfo,
+        _: &PropertyEditorDefinitionContainer,
+    ) -> Option<ObjectValue> {
+        clone_property_value::<HotKey>(property_info)
+    }
 }
 
 #[derive(Debug)]
@@ -110,4 +120,12 @@ impl PropertyEditorDefinition for KeyBindingPropertyEditorDefinition {
         }
         None
     }
+
+    fn clone_value(
+        &self,
+        property_info: &FieldInfo,
+        _: &PropertyEditorDefinitionContainer,
+    ) -> Option<ObjectValue> {
+        clone_property_value::<KeyBinding>(property_info)
+    }
 }
@@ -29,7 +29,7 @@ use crate::{
                 Vec4PropertyEditorDefinition,
             },
         },
-        InspectorEnvironment, InspectorError, PropertyChanged,
+        InspectorEnvironment, InspectorError, ObjectValue, PropertyChanged,
     },
     message::UiMessage,
     BuildContext, UiNode, UserInterface,
@@ -132,6 +132,29 @@ pub trait PropertyEditorDefinition: Debug {
     ) -> Result<Option<UiMessage>, InspectorError>;
 
     fn translate_message(&self, ctx: PropertyEditorTranslationContext) -> Option<PropertyChanged>;
+
+    /// Clones the value of the property, so it could be pasted to another property of the same
+    /// type. Returns `None` if values of the property can't be copied.
+    fn clone_value(
+        &self,
+        _property_info: &FieldInfo,
+        _definition_container: &PropertyEditorDefinitionContainer,
+    ) -> Option<ObjectValue> {
+        None
+    }
+}
+
+/// Clones the value of the property of the given type, could be used to implement
+/// [`PropertyEditorDefinition::clone_value`].
+pub fn clone_property_value<T: Reflect + Clone + Debug>(
+    property_info: &FieldInfo,
+) -> Option<ObjectValue> {
+    property_info
+        .cast_value::<T>()
+        .ok()
+        .map(|value| ObjectValue {
+            value: Box::new(value.clone()),
+        })
 }
 
 #[derive(Clone, Default)]
@@ -239,7 +262,7 @@ impl PropertyEditorDefinitionContainer {
         reg_array_property_editor! { container, isize, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16 }
 
         // SmoothAngle
-        container.register_inheritable_inspectable::<SmoothAngle>();
+        container.register_inheritable_cloneable_inspectable::<SmoothAngle>();
 
         // Uuid + InheritableVariable<Uuid>
         container.insert(UuidPropertyEditorDefinition);
@@ -285,6 +308,20 @@ impl PropertyEditorDefinitionContainer {
             .is_none());
     }
 
+    /// Same as [`Self::register_inheritable_inspectable`], but also allows to copy values of the
+    /// properties of the type.
+    pub fn register_inheritable_cloneable_inspectable<T>(&self)
+    where
+        T: Reflect + FieldValue + Clone + Debug,
+    {
+        assert!(self
+            .insert(InspectablePropertyEditorDefinition::<T>::new_cloneable())
+            .is_none());
+        assert!(self
+            .insert(InheritablePropertyEditorDefinition::<T>::new())
+            .is_none());
+    }
+
     pub fn register_inheritable_enum<T, E: Debug>(&self)
     where
         T: InspectableEnum + FieldValue + VariantNames + AsRef<str> + FromStr<Err = E> + Debug,
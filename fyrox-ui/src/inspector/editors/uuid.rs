@@ -1,11 +1,12 @@
 use crate::{
-    core::uuid::Uuid,
+    core::{reflect::FieldInfo, uuid::Uuid},
     inspector::{
         editors::{
-            PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+            clone_property_value, PropertyEditorBuildContext, PropertyEditorDefinition,
+            PropertyEditorDefinitionContainer, PropertyEditorInstance,
             PropertyEditorMessageContext, PropertyEditorTranslationContext,
         },
-        FieldKind, InspectorError, PropertyChanged,
+        FieldKind, InspectorError, ObjectValue, PropertyChanged,
     },
     message::{MessageDirection, UiMessage},
     uuid::{UuidEditorBuilder, UuidEditorMessage},
@@ -62,4 +63,12 @@ impl PropertyEditorDefinition for UuidPropertyEditorDefinition {
         }
         None
     }
+
+    fn clone_value(
+        &self,
+        property_info: &FieldInfo,
+        _: &PropertyEditorDefinitionContainer,
+    ) -> Option<ObjectValue> {
+        clone_property_value::<Uuid>(property_info)
+    }
 }
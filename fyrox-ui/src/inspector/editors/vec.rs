@@ -1,11 +1,12 @@
 use crate::{
-    core::{algebra::SVector, num_traits::NumCast},
+    core::{algebra::SVector, num_traits::NumCast, reflect::FieldInfo},
     inspector::{
         editors::{
-            PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorInstance,
+            clone_property_value, PropertyEditorBuildContext, PropertyEditorDefinition,
+            PropertyEditorDefinitionContainer, PropertyEditorInstance,
             PropertyEditorMessageContext, PropertyEditorTranslationContext,
         },
-        FieldKind, InspectorError, PropertyChanged,
+        FieldKind, InspectorError, ObjectValue, PropertyChanged,
     },
     message::{MessageDirection, UiMessage},
     numeric::NumericType,
@@ -93,6 +94,14 @@ impl<T: NumericType, const D: usize> PropertyEditorDefinition
         }
         None
     }
+
+    fn clone_value(
+        &self,
+        property_info: &FieldInfo,
+        _: &PropertyEditorDefinitionContainer,
+    ) -> Option<ObjectValue> {
+        clone_property_value::<SVector<T, D>>(property_info)
+    }
 }
 
 pub type Vec2PropertyEditorDefinition<T> = VecPropertyEditorDefinition<T, 2>;
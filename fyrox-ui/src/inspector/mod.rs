@@ -4,19 +4,23 @@ use crate::{
     core::{
         algebra::Vector2,
         pool::Handle,
-        reflect::{CastError, Reflect, ResolvePath},
+        reflect::{CastError, FieldInfo, Reflect, ResolvePath},
     },
     define_constructor,
     expander::ExpanderBuilder,
     formatted_text::WrapMode,
     grid::{Column, GridBuilder, Row},
-    inspector::editors::{
-        PropertyEditorBuildContext, PropertyEditorDefinition, PropertyEditorDefinitionContainer,
-        PropertyEditorInstance, PropertyEditorMessageContext, PropertyEditorTranslationContext,
+    inspector::{
+        defaults::{has_default_value, make_default_value, property_value_type_id},
+        editors::{
+            PropertyEditorBuildContext, PropertyEditorDefinition,
+            PropertyEditorDefinitionContainer, PropertyEditorInstance,
+            PropertyEditorMessageContext, PropertyEditorTranslationContext,
+        },
     },
     menu::{MenuItemBuilder, MenuItemContent, MenuItemMessage},
-    message::{MessageDirection, UiMessage},
-    popup::PopupBuilder,
+    message::{KeyCode, MessageDirection, UiMessage},
+    popup::{PopupBuilder, PopupMessage},
     stack_panel::StackPanelBuilder,
     text::TextBuilder,
     utils::{make_arrow, make_simple_tooltip, ArrowDirection},
@@ -33,6 +37,7 @@ use std::{
     rc::Rc,
 };
 
+pub mod defaults;
 pub mod editors;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -41,6 +46,20 @@ pub enum CollectionChanged {
     Add(ObjectValue),
     /// An item in the collection should be removed.
     Remove(usize),
+    /// An item should be inserted in the collection at the given index. Out-of-range index means
+    /// the end of the collection.
+    Insert {
+        /// Index of the new item in the collection.
+        index: usize,
+        value: ObjectValue,
+    },
+    /// An item in the collection should be moved to another index.
+    Move {
+        /// Index of the item in the collection.
+        from: usize,
+        /// New index of the item in the collection.
+        to: usize,
+    },
     /// An item in the collection has changed one of its properties.
     ItemChanged {
         /// Index of an item in the collection.
@@ -52,6 +71,8 @@ pub enum CollectionChanged {
 impl CollectionChanged {
     define_constructor!(CollectionChanged:Add => fn add(ObjectValue), layout: false);
     define_constructor!(CollectionChanged:Remove => fn remove(usize), layout: false);
+    define_constructor!(CollectionChanged:Insert => fn insert(index: usize, value: ObjectValue), layout: false);
+    define_constructor!(CollectionChanged:Move => fn move_item(from: usize, to: usize), layout: false);
     define_constructor!(CollectionChanged:ItemChanged => fn item_changed(index: usize, property: FieldKind), layout: false);
 }
 
@@ -66,6 +87,9 @@ pub enum FieldKind {
     Inspectable(Box<PropertyChanged>),
     Object(ObjectValue),
     Inheritable(InheritableAction),
+    /// The property should be reset to the default value of its type (see
+    /// [`defaults::make_default_value`]).
+    ResetToDefault,
 }
 
 /// An action for some property.
@@ -86,8 +110,26 @@ pub enum PropertyAction {
         /// Index of an item.
         index: usize,
     },
+    /// An item needs to be inserted to a collection property at the given index. Out-of-range
+    /// index is clamped to the length of the collection.
+    InsertItem {
+        /// Index of the new item.
+        index: usize,
+        /// New collection item.
+        value: Box<dyn Reflect>,
+    },
+    /// An item of a collection property needs to be moved to another index. Out-of-range index
+    /// is clamped to the last index of the collection.
+    MoveItem {
+        /// Index of an item.
+        from: usize,
+        /// New index of the item.
+        to: usize,
+    },
     /// Revert value to parent.
     Revert,
+    /// Reset value to the default value of its type.
+    ResetToDefault,
 }
 
 impl PropertyAction {
@@ -104,12 +146,18 @@ impl PropertyAction {
                     value: value.clone().into_box_reflect(),
                 },
                 CollectionChanged::Remove(index) => Self::RemoveItem { index },
+                CollectionChanged::Insert { index, ref value } => Self::InsertItem {
+                    index,
+                    value: value.clone().into_box_reflect(),
+                },
+                CollectionChanged::Move { from, to } => Self::MoveItem { from, to },
                 CollectionChanged::ItemChanged { ref property, .. } => {
                     Self::from_field_kind(property)
                 }
             },
             FieldKind::Inspectable(ref inspectable) => Self::from_field_kind(&inspectable.value),
             FieldKind::Inheritable { .. } => Self::Revert,
+            FieldKind::ResetToDefault => Self::ResetToDefault,
         }
     }
 
@@ -179,10 +227,76 @@ impl PropertyAction {
                     result_callback(Err(Self::RemoveItem { index }))
                 }
             }),
+            PropertyAction::InsertItem { index, value } => {
+                let mut value = Some(value);
+                target.resolve_path_mut(path, &mut |result| {
+                    if let Ok(field) = result {
+                        field.as_list_mut(&mut |result| {
+                            if let Some(list) = result {
+                                let index = index.min(list.reflect_len());
+                                if let Err(value) =
+                                    list.reflect_insert(index, value.take().unwrap())
+                                {
+                                    result_callback(Err(Self::InsertItem { index, value }))
+                                } else {
+                                    result_callback(Ok(None))
+                                }
+                            } else {
+                                result_callback(Err(Self::InsertItem {
+                                    index,
+                                    value: value.take().unwrap(),
+                                }))
+                            }
+                        })
+                    } else {
+                        result_callback(Err(Self::InsertItem {
+                            index,
+                            value: value.take().unwrap(),
+                        }))
+                    }
+                })
+            }
+            PropertyAction::MoveItem { from, to } => target.resolve_path_mut(path, &mut |result| {
+                if let Ok(field) = result {
+                    field.as_list_mut(&mut |result| {
+                        if let Some(list) = result {
+                            if let Some(value) = list.reflect_remove(from) {
+                                let to = to.min(list.reflect_len());
+                                if list.reflect_insert(to, value).is_ok() {
+                                    result_callback(Ok(None))
+                                } else {
+                                    result_callback(Err(Self::MoveItem { from, to }))
+                                }
+                            } else {
+                                result_callback(Err(Self::MoveItem { from, to }))
+                            }
+                        } else {
+                            result_callback(Err(Self::MoveItem { from, to }))
+                        }
+                    })
+                } else {
+                    result_callback(Err(Self::MoveItem { from, to }))
+                }
+            }),
             PropertyAction::Revert => {
                 // Unsupported due to lack of context (a reference to parent entity).
                 result_callback(Err(Self::Revert))
             }
+            PropertyAction::ResetToDefault => target.resolve_path_mut(path, &mut |result| {
+                if let Ok(field) = result {
+                    if let Some(value) = make_default_value(property_value_type_id(field)) {
+                        if field.set(value).is_ok() {
+                            result_callback(Ok(None))
+                        } else {
+                            result_callback(Err(Self::ResetToDefault))
+                        }
+                    } else {
+                        result_callback(Err(Self::ResetToDefault))
+                    }
+                } else {
+                    result_callback(Err(Self::ResetToDefault))
+                }
+            }),
         }
     }
 }
@@ -298,7 +412,7 @@ impl PropertyChanged {
             FieldKind::Inspectable(ref inspectable) => {
                 path += format!(".{}", inspectable.path()).as_ref();
             }
-            FieldKind::Object(_) | FieldKind::Inheritable { .. } => {}
+            FieldKind::Object(_) | FieldKind::Inheritable { .. } | FieldKind::ResetToDefault => {}
         }
         path
     }
@@ -308,6 +422,8 @@ impl PropertyChanged {
             FieldKind::Collection(ref collection_changed) => match **collection_changed {
                 CollectionChanged::Add(_) => false,
                 CollectionChanged::Remove(_) => false,
+                CollectionChanged::Insert { .. } => false,
+                CollectionChanged::Move { .. } => false,
                 CollectionChanged::ItemChanged { ref property, .. } => match property {
                     FieldKind::Inspectable(inspectable) => inspectable.is_inheritable(),
                     FieldKind::Inheritable(_) => true,
@@ -315,7 +431,7 @@ impl PropertyChanged {
                 },
             },
             FieldKind::Inspectable(ref inspectable) => inspectable.is_inheritable(),
-            FieldKind::Object(_) => false,
+            FieldKind::Object(_) | FieldKind::ResetToDefault => false,
             FieldKind::Inheritable(_) => true,
         }
     }
@@ -325,11 +441,101 @@ impl PropertyChanged {
 pub enum InspectorMessage {
     Context(InspectorContext),
     PropertyChanged(PropertyChanged),
+    /// The value of the property at the given path should be copied (see [`CopiedValue`]). The
+    /// inspector does not own inspected objects, so the message is sent (with
+    /// [`MessageDirection::FromWidget`]) to the owner of the object.
+    CopyValue(String),
+    /// A copied value should be pasted to the property at the given path. Sent with
+    /// [`MessageDirection::FromWidget`], just like [`InspectorMessage::CopyValue`].
+    PasteValue(String),
 }
 
 impl InspectorMessage {
     define_constructor!(InspectorMessage:Context => fn context(InspectorContext), layout: false);
     define_constructor!(InspectorMessage:PropertyChanged => fn property_changed(PropertyChanged), layout: false);
+    define_constructor!(InspectorMessage:CopyValue => fn copy_value(String), layout: false);
+    define_constructor!(InspectorMessage:PasteValue => fn paste_value(String), layout: false);
+}
+
+/// A copy of the value of a property, that could be pasted to other properties of the same type.
+#[derive(Clone, Debug)]
+pub struct CopiedValue {
+    /// Type of the copied property.
+    pub type_id: TypeId,
+    pub value: ObjectValue,
+}
+
+impl CopiedValue {
+    /// Copies the value of the property at the given path of the object. The path could lead to a
+    /// property of an inspectable property of the object (e.g. `base.local_transform`). Returns
+    /// `None` if there's no such property or its values can't be copied (see
+    /// [`PropertyEditorDefinition::clone_value`]).
+    pub fn copy(
+        object: &dyn Reflect,
+        path: &str,
+        definition_container: &PropertyEditorDefinitionContainer,
+    ) -> Option<Self> {
+        let mut copied = None;
+        with_field_info(object, path, &mut |info| {
+            if let Some(definition) = definition_container
+                .definitions()
+                .get(&info.value.type_id())
+            {
+                copied = definition
+                    .clone_value(info, definition_container)
+                    .map(|value| Self {
+                        type_id: info.value.type_id(),
+                        value,
+                    });
+            }
+        });
+        copied
+    }
+
+    /// Makes a change, that sets the value to the property at the given path of the object.
+    /// Returns `None` if there's no such property or it has another type.
+    pub fn paste(&self, object: &dyn Reflect, path: &str) -> Option<PropertyChanged> {
+        let (name, rest) = split_property_path(path);
+        let mut property_changed = None;
+        object.fields_info(&mut |fields_info| {
+            if let Some(info) = fields_info.iter().find(|info| info.name == name) {
+                let value = match rest {
+                    Some(rest) => self
+                        .paste(info.reflect_value, rest)
+                        .map(|inner| FieldKind::Inspectable(Box::new(inner))),
+                    None => (info.value.type_id() == self.type_id)
+                        .then(|| FieldKind::Object(self.value.clone())),
+                };
+                property_changed = value.map(|value| PropertyChanged {
+                    name: name.to_owned(),
+                    owner_type_id: info.owner_type_id,
+                    value,
+                });
+            }
+        });
+        property_changed
+    }
+}
+
+/// Splits the path to a property into the name of the first property and the rest of the path.
+fn split_property_path(path: &str) -> (&str, Option<&str>) {
+    match path.split_once('.') {
+        Some((name, rest)) => (name, Some(rest)),
+        None => (path, None),
+    }
+}
+
+/// Calls the function with the info of the property at the given path of the object.
+fn with_field_info(object: &dyn Reflect, path: &str, func: &mut dyn FnMut(&FieldInfo)) {
+    let (name, rest) = split_property_path(path);
+    object.fields_info(&mut |fields_info| {
+        if let Some(info) = fields_info.iter().find(|info| info.name == name) {
+            match rest {
+                Some(rest) => with_field_info(info.reflect_value, rest, func),
+                None => func(info),
+            }
+        }
+    });
 }
 
 pub trait InspectorEnvironment: Any {
@@ -348,6 +554,34 @@ impl Inspector {
     pub fn context(&self) -> &InspectorContext {
         &self.context
     }
+
+    /// Finds the entry of the property, that the context menu was opened for.
+    fn find_menu_target(&self, ui: &UserInterface) -> Option<&ContextEntry> {
+        let menu_handle = self.context.menu.menu.as_ref().map(|h| **h)?;
+        let position = ui.node(menu_handle).screen_position();
+
+        self.find_entry_at(ui, position - Vector2::new(1.0, 1.0))
+    }
+
+    /// Finds the entry of the property under the given screen-space position.
+    fn find_entry_at(&self, ui: &UserInterface, position: Vector2<f32>) -> Option<&ContextEntry> {
+        let mut parent_handle = ui.hit_test_unrestricted(position);
+
+        while let Some(parent) = ui.try_get_node(parent_handle) {
+            if let Some(entry) = self
+                .context
+                .entries
+                .iter()
+                .find(|entry| entry.property_container == parent_handle)
+            {
+                return Some(entry);
+            }
+
+            parent_handle = parent.parent;
+        }
+
+        None
+    }
 }
 
 pub const NAME_COLUMN_WIDTH: f32 = 150.0;
@@ -380,6 +614,8 @@ pub struct ContextEntry {
     pub property_editor: Handle<UiNode>,
     pub property_debug_output: String,
     pub property_container: Handle<UiNode>,
+    /// Type of the value of the property, see [`defaults::property_value_type_id`].
+    pub property_value_type_id: TypeId,
 }
 
 impl PartialEq for ContextEntry {
@@ -398,6 +634,9 @@ impl PartialEq for ContextEntry {
 #[derive(Default, Clone)]
 pub struct Menu {
     pub copy_value_as_string: Handle<UiNode>,
+    pub copy_value: Handle<UiNode>,
+    pub paste_value: Handle<UiNode>,
+    pub reset_value: Handle<UiNode>,
     pub menu: Option<RcUiNodeHandle>,
     pub target: Cell<Handle<UiNode>>,
 }
@@ -635,6 +874,7 @@ impl InspectorContext {
                                 property_owner_type_id: info.owner_type_id,
                                 property_debug_output: field_text.clone(),
                                 property_container: container,
+                                property_value_type_id: property_value_type_id(info.reflect_value),
                             });
 
                             if info.read_only {
@@ -679,14 +919,38 @@ impl InspectorContext {
         });
 
         let copy_value_as_string;
+        let copy_value;
+        let paste_value;
+        let reset_value;
         let menu = PopupBuilder::new(WidgetBuilder::new().with_visibility(false))
             .with_content(
-                StackPanelBuilder::new(WidgetBuilder::new().with_child({
-                    copy_value_as_string = MenuItemBuilder::new(WidgetBuilder::new())
-                        .with_content(MenuItemContent::text("Copy Value as String"))
-                        .build(ctx);
-                    copy_value_as_string
-                }))
+                StackPanelBuilder::new(
+                    WidgetBuilder::new()
+                        .with_child({
+                            copy_value_as_string = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Copy Value as String"))
+                                .build(ctx);
+                            copy_value_as_string
+                        })
+                        .with_child({
+                            copy_value = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Copy Value"))
+                                .build(ctx);
+                            copy_value
+                        })
+                        .with_child({
+                            paste_value = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Paste Value"))
+                                .build(ctx);
+                            paste_value
+                        })
+                        .with_child({
+                            reset_value = MenuItemBuilder::new(WidgetBuilder::new())
+                                .with_content(MenuItemContent::text("Reset to Default"))
+                                .build(ctx);
+                            reset_value
+                        }),
+                )
                 .build(ctx),
             )
             .build(ctx);
@@ -703,6 +967,9 @@ impl InspectorContext {
             stack_panel,
             menu: Menu {
                 copy_value_as_string,
+                copy_value,
+                paste_value,
+                reset_value,
                 menu: Some(menu),
                 target: Default::default(),
             },
@@ -814,6 +1081,56 @@ impl Control for Inspector {
             }
         }
 
+        // Vertical arrows move keyboard focus between property editors.
+        if let Some(WidgetMessage::KeyDown(key)) = message.data::<WidgetMessage>() {
+            if !message.handled() && message.direction() == MessageDirection::FromWidget {
+                let backward = match key {
+                    KeyCode::ArrowUp => Some(true),
+                    KeyCode::ArrowDown => Some(false),
+                    _ => None,
+                };
+                if let Some(backward) = backward {
+                    let tab_stops = self
+                        .children()
+                        .iter()
+                        .flat_map(|child| ui.tab_stops(*child))
+                        .collect::<Vec<_>>();
+                    if ui.move_focus_among(&tab_stops, backward, false) {
+                        message.set_handled(true);
+                    }
+                }
+            }
+        }
+
+        // Properties of inspectable properties are shown by nested inspectors, the paths of the
+        // properties to copy or paste are extended, so they start from the inspected object.
+        if message.direction() == MessageDirection::FromWidget {
+            if let Some(entry) = self
+                .context
+                .entries
+                .iter()
+                .find(|entry| entry.property_editor == message.destination())
+            {
+                match message.data::<InspectorMessage>() {
+                    Some(InspectorMessage::CopyValue(path)) => {
+                        ui.send_message(InspectorMessage::copy_value(
+                            self.handle,
+                            MessageDirection::FromWidget,
+                            format!("{}.{}", entry.property_name, path),
+                        ));
+                    }
+                    Some(InspectorMessage::PasteValue(path)) => {
+                        ui.send_message(InspectorMessage::paste_value(
+                            self.handle,
+                            MessageDirection::FromWidget,
+                            format!("{}.{}", entry.property_name, path),
+                        ));
+                    }
+                    _ => (),
+                }
+            }
+        }
+
         // Check each message from descendant widget and try to translate it to
         // PropertyChanged message.
         if message.flags != self.context.sync_flag {
@@ -841,29 +1158,60 @@ impl Control for Inspector {
     }
 
     fn preview_message(&self, ui: &UserInterface, message: &mut UiMessage) {
-        if message.destination() == self.context.menu.copy_value_as_string {
-            if let Some(MenuItemMessage::Click) = message.data() {
-                if let Some(menu_handle) = self.context.menu.menu.as_ref().map(|h| **h) {
-                    let position = ui.node(menu_handle).screen_position();
-
-                    let mut parent_handle =
-                        ui.hit_test_unrestricted(position - Vector2::new(1.0, 1.0));
-
-                    while let Some(parent) = ui.try_get_node(parent_handle) {
-                        for entry in self.context.entries.iter() {
-                            if entry.property_container == parent_handle {
-                                let _ = ui
-                                    .clipboard_mut()
-                                    .unwrap()
-                                    .set_contents(entry.property_debug_output.clone());
-                                break;
-                            }
-                        }
-
-                        parent_handle = parent.parent;
-                    }
+        if let Some(MenuItemMessage::Click) = message.data() {
+            let menu = &self.context.menu;
+            if message.destination() == menu.copy_value_as_string {
+                if let Some(entry) = self.find_menu_target(ui) {
+                    let _ = ui
+                        .clipboard_mut()
+                        .unwrap()
+                        .set_contents(entry.property_debug_output.clone());
+                }
+            } else if message.destination() == menu.copy_value {
+                if let Some(entry) = self.find_menu_target(ui) {
+                    ui.send_message(InspectorMessage::copy_value(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        entry.property_name.clone(),
+                    ));
+                }
+            } else if message.destination() == menu.paste_value {
+                if let Some(entry) = self.find_menu_target(ui) {
+                    ui.send_message(InspectorMessage::paste_value(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        entry.property_name.clone(),
+                    ));
+                }
+            } else if message.destination() == menu.reset_value {
+                if let Some(entry) = self.find_menu_target(ui) {
+                    ui.send_message(InspectorMessage::property_changed(
+                        self.handle,
+                        MessageDirection::FromWidget,
+                        PropertyChanged {
+                            name: entry.property_name.clone(),
+                            owner_type_id: entry.property_owner_type_id,
+                            value: FieldKind::ResetToDefault,
+                        },
+                    ));
                 }
             }
+        } else if let Some(PopupMessage::Open) = message.data() {
+            let menu = &self.context.menu;
+            if menu.menu.as_ref().map(|h| **h) == Some(message.destination()) {
+                // Properties could be reset only if there's a default value of their type. The menu
+                // is opened at the cursor, so the property under the cursor is the target.
+                let can_reset = self
+                    .find_entry_at(ui, ui.cursor_position())
+                    .map_or(false, |entry| {
+                        has_default_value(entry.property_value_type_id)
+                    });
+                ui.send_message(WidgetMessage::visibility(
+                    menu.reset_value,
+                    MessageDirection::ToWidget,
+                    can_reset,
+                ));
+            }
         }
     }
 }
@@ -905,3 +1253,81 @@ impl InspectorBuilder {
         ctx.add_node(UiNode::new(canvas))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::{reflect::prelude::*, variable::InheritableVariable},
+        inspector::{
+            editors::PropertyEditorDefinitionContainer, CopiedValue, FieldKind, PropertyAction,
+        },
+    };
+
+    #[derive(Reflect, Debug)]
+    struct Transform {
+        scale: InheritableVariable<f32>,
+    }
+
+    #[derive(Reflect, Debug)]
+    struct Node {
+        transform: Transform,
+        mass: f32,
+    }
+
+    #[test]
+    fn test_copy_paste_value() {
+        let definitions = PropertyEditorDefinitionContainer::new();
+        let node = Node {
+            transform: Transform { scale: 2.0.into() },
+            mass: 3.0,
+        };
+
+        let copied = CopiedValue::copy(&node, "transform.scale", &definitions).unwrap();
+
+        let property_changed = copied.paste(&node, "transform.scale").unwrap();
+        assert_eq!(property_changed.path(), "transform.scale");
+        assert_eq!(
+            property_changed.owner_type_id,
+            std::any::TypeId::of::<Node>()
+        );
+        match property_changed.value {
+            FieldKind::Inspectable(ref inner) => {
+                let mut value = None;
+                if let FieldKind::Object(ref object) = inner.value {
+                    object.cast_clone::<f32>(&mut |v| value = v);
+                }
+                assert_eq!(value, Some(2.0));
+            }
+            _ => panic!("Value of a nested property must be inspectable!"),
+        }
+
+        // Properties of another type can't take the value.
+        assert!(copied.paste(&node, "mass").is_none());
+        assert!(copied.paste(&node, "transform.size").is_none());
+        assert!(CopiedValue::copy(&node, "transform", &definitions).is_none());
+    }
+
+    #[test]
+    fn test_reset_to_default() {
+        let mut node = Node {
+            transform: Transform { scale: 2.0.into() },
+            mass: 3.0,
+        };
+
+        for path in ["transform.scale", "mass"] {
+            PropertyAction::from_field_kind(&FieldKind::ResetToDefault).apply(
+                path,
+                &mut node,
+                &mut |result| assert!(result.is_ok()),
+            );
+        }
+        assert_eq!(*node.transform.scale, 0.0);
+        assert!(node.transform.scale.is_modified());
+        assert_eq!(node.mass, 0.0);
+
+        // There's no default value of the type of the property.
+        PropertyAction::ResetToDefault.apply("transform", &mut node, &mut |result| {
+            assert!(result.is_err())
+        });
+    }
+}
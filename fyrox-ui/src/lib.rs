@@ -244,8 +244,8 @@ use crate::{
     },
     draw::{CommandTexture, Draw, DrawingContext},
     message::{
-        ButtonState, CursorIcon, KeyboardModifiers, MessageDirection, MouseButton, OsEvent,
-        UiMessage,
+        ButtonState, CursorIcon, KeyCode, KeyboardModifiers, MessageDirection, MouseButton,
+        OsEvent, UiMessage,
     },
     popup::{Placement, PopupMessage},
     ttf::{Font, FontBuilder, SharedFont},
@@ -1513,6 +1513,13 @@ impl UserInterface {
                                 self.request_focus(message.destination());
                             }
                         }
+                        WidgetMessage::KeyDown(KeyCode::Tab) => {
+                            if !message.handled()
+                                && message.direction() == MessageDirection::FromWidget
+                            {
+                                self.move_focus(self.keyboard_modifiers.shift);
+                            }
+                        }
                         WidgetMessage::Unfocus => {
                             if self.nodes.is_valid_handle(message.destination())
                                 && message.direction() == MessageDirection::ToWidget
@@ -1767,6 +1774,114 @@ impl UserInterface {
         self.captured_node
     }
 
+    /// Returns a handle of the widget, that currently has keyboard focus.
+    pub fn keyboard_focus_node(&self) -> Handle<UiNode> {
+        self.keyboard_focus_node
+    }
+
+    /// Collects every tab stop (see [`Widget::tab_stop`]) of the sub-tree with the given root in
+    /// depth-first order. Hidden or disabled widgets are skipped along with their descendants.
+    pub fn tab_stops(&self, root: Handle<UiNode>) -> Vec<Handle<UiNode>> {
+        let mut tab_stops = Vec::new();
+        let mut stack = vec![root];
+        while let Some(handle) = stack.pop() {
+            if let Some(node) = self.nodes.try_borrow(handle) {
+                // Local visibility is enough here, since ancestors are checked first.
+                if !node.visibility() || !node.enabled() {
+                    continue;
+                }
+
+                if node.is_tab_stop() {
+                    tab_stops.push(handle);
+                }
+
+                stack.extend(node.children().iter().rev());
+            }
+        }
+        tab_stops
+    }
+
+    /// Returns the top-level widget (a direct child of the root canvas, usually a window), that
+    /// contains the given widget. Tab key never moves focus out of such widget.
+    pub fn focus_scope(&self, node: Handle<UiNode>) -> Handle<UiNode> {
+        let mut scope = node;
+        while let Some(node_ref) = self.nodes.try_borrow(scope) {
+            let parent = node_ref.parent();
+            if parent.is_none() || parent == self.root_canvas {
+                break;
+            }
+            scope = parent;
+        }
+        scope
+    }
+
+    /// Moves keyboard focus to the next (or previous, if `backward` is `true`) tab stop in the
+    /// given scope. If `wrap` is `false` and the focused widget is the last (or first) tab stop in
+    /// the scope, the focus remains unchanged. Returns `true` if the focus was moved.
+    pub fn move_focus_in(&mut self, scope: Handle<UiNode>, backward: bool, wrap: bool) -> bool {
+        let tab_stops = self.tab_stops(scope);
+        self.move_focus_among(&tab_stops, backward, wrap)
+    }
+
+    /// Same as [`Self::move_focus_in`], but uses the given list of tab stops. It is useful for
+    /// widgets, that want to move focus among their descendants while handling a routed message
+    /// (the widget itself is not accessible via the user interface at that moment).
+    pub fn move_focus_among(
+        &mut self,
+        tab_stops: &[Handle<UiNode>],
+        backward: bool,
+        wrap: bool,
+    ) -> bool {
+        if tab_stops.is_empty() {
+            return false;
+        }
+
+        // Focused widget could be a part of a tab stop (a text of a button, for example).
+        let current = self.find_by_criteria_up(self.keyboard_focus_node, |node| {
+            tab_stops.contains(&node.handle())
+        });
+
+        let next = match tab_stops.iter().position(|h| *h == current) {
+            Some(index) => {
+                if backward {
+                    if index > 0 {
+                        Some(index - 1)
+                    } else if wrap {
+                        Some(tab_stops.len() - 1)
+                    } else {
+                        None
+                    }
+                } else if index + 1 < tab_stops.len() {
+                    Some(index + 1)
+                } else if wrap {
+                    Some(0)
+                } else {
+                    None
+                }
+            }
+            None if backward => Some(tab_stops.len() - 1),
+            None => Some(0),
+        };
+
+        if let Some(next) = next {
+            self.send_message(WidgetMessage::focus(
+                tab_stops[next],
+                MessageDirection::ToWidget,
+            ));
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves keyboard focus to the next (or previous, if `backward` is `true`) tab stop in the
+    /// scope of the currently focused widget. This method is called automatically when Tab key
+    /// is pressed and no widget has handled it.
+    pub fn move_focus(&mut self, backward: bool) -> bool {
+        let scope = self.focus_scope(self.keyboard_focus_node);
+        self.move_focus_in(scope, backward, true)
+    }
+
     // Tries to set new picked node (a node under the cursor) and returns `true` if the node was
     // changed.
     fn try_set_picked_node(&mut self, node: Handle<UiNode>) -> bool {
@@ -2502,12 +2617,19 @@ mod test {
     use crate::message::{ButtonState, KeyCode};
     use crate::{
         border::BorderBuilder,
-        core::algebra::{Rotation2, UnitComplex, Vector2},
-        message::MessageDirection,
+        button::{ButtonBuilder, ButtonMessage},
+        check_box::{CheckBoxBuilder, CheckBoxMessage},
+        core::{
+            algebra::{Rotation2, UnitComplex, Vector2},
+            pool::Handle,
+        },
+        message::{KeyboardModifiers, MessageDirection, UiMessage},
+        stack_panel::StackPanelBuilder,
         text_box::TextBoxBuilder,
         transform_size,
         widget::{WidgetBuilder, WidgetMessage},
-        OsEvent, UserInterface,
+        window::WindowBuilder,
+        OsEvent, UiNode, UserInterface,
     };
 
     #[test]
@@ -2594,4 +2716,179 @@ mod test {
 
         assert!(ui.poll_message().is_none());
     }
+
+    fn press_key(ui: &mut UserInterface, key: KeyCode) -> Vec<UiMessage> {
+        ui.process_os_event(&OsEvent::KeyboardInput {
+            button: key,
+            state: ButtonState::Pressed,
+            text: Default::default(),
+        });
+        let mut messages = Vec::new();
+        while let Some(message) = ui.poll_message() {
+            messages.push(message);
+        }
+        messages
+    }
+
+    fn set_shift(ui: &mut UserInterface, shift: bool) {
+        ui.process_os_event(&OsEvent::KeyboardModifiers(KeyboardModifiers {
+            shift,
+            ..Default::default()
+        }));
+    }
+
+    fn focus(ui: &mut UserInterface, node: Handle<UiNode>) {
+        ui.send_message(WidgetMessage::focus(node, MessageDirection::ToWidget));
+        while ui.poll_message().is_some() {}
+    }
+
+    #[test]
+    fn test_tab_navigation() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let ctx = &mut ui.build_ctx();
+        let first = ButtonBuilder::new(WidgetBuilder::new()).build(ctx);
+        let disabled = ButtonBuilder::new(WidgetBuilder::new().with_enabled(false)).build(ctx);
+        let hidden = ButtonBuilder::new(WidgetBuilder::new().with_visibility(false)).build(ctx);
+        let not_a_tab_stop =
+            ButtonBuilder::new(WidgetBuilder::new().with_tab_stop(false)).build(ctx);
+        let text_box = TextBoxBuilder::new(WidgetBuilder::new()).build(ctx);
+        let last = CheckBoxBuilder::new(WidgetBuilder::new()).build(ctx);
+        StackPanelBuilder::new(WidgetBuilder::new().with_children([
+            first,
+            disabled,
+            hidden,
+            not_a_tab_stop,
+            text_box,
+            last,
+        ]))
+        .build(ctx);
+
+        ui.update(screen_size, 0.0);
+        focus(&mut ui, first);
+
+        press_key(&mut ui, KeyCode::Tab);
+        assert_eq!(ui.keyboard_focus_node(), text_box);
+        press_key(&mut ui, KeyCode::Tab);
+        assert_eq!(ui.keyboard_focus_node(), last);
+        // Wrap around.
+        press_key(&mut ui, KeyCode::Tab);
+        assert_eq!(ui.keyboard_focus_node(), first);
+
+        set_shift(&mut ui, true);
+        press_key(&mut ui, KeyCode::Tab);
+        assert_eq!(ui.keyboard_focus_node(), last);
+        press_key(&mut ui, KeyCode::Tab);
+        assert_eq!(ui.keyboard_focus_node(), text_box);
+        set_shift(&mut ui, false);
+    }
+
+    #[test]
+    fn test_tab_navigation_stays_in_window() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let ctx = &mut ui.build_ctx();
+        let outside = ButtonBuilder::new(WidgetBuilder::new()).build(ctx);
+        let first = ButtonBuilder::new(WidgetBuilder::new()).build(ctx);
+        let second = ButtonBuilder::new(WidgetBuilder::new()).build(ctx);
+        WindowBuilder::new(WidgetBuilder::new())
+            .with_content(
+                StackPanelBuilder::new(WidgetBuilder::new().with_children([first, second]))
+                    .build(ctx),
+            )
+            .build(ctx);
+
+        ui.update(screen_size, 0.0);
+        focus(&mut ui, second);
+
+        // Header buttons of the window and the button outside of the window are skipped.
+        press_key(&mut ui, KeyCode::Tab);
+        assert_eq!(ui.keyboard_focus_node(), first);
+        press_key(&mut ui, KeyCode::Tab);
+        assert_eq!(ui.keyboard_focus_node(), second);
+
+        focus(&mut ui, outside);
+        press_key(&mut ui, KeyCode::Tab);
+        assert_eq!(ui.keyboard_focus_node(), outside);
+    }
+
+    #[test]
+    fn test_window_default_and_cancel_buttons() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let ctx = &mut ui.build_ctx();
+        let check_box = CheckBoxBuilder::new(WidgetBuilder::new()).build(ctx);
+        let ok = ButtonBuilder::new(WidgetBuilder::new()).build(ctx);
+        let cancel = ButtonBuilder::new(WidgetBuilder::new()).build(ctx);
+        WindowBuilder::new(WidgetBuilder::new())
+            .with_content(
+                StackPanelBuilder::new(WidgetBuilder::new().with_children([check_box, ok, cancel]))
+                    .build(ctx),
+            )
+            .with_default_button(ok)
+            .with_cancel_button(cancel)
+            .build(ctx);
+
+        ui.update(screen_size, 0.0);
+        focus(&mut ui, check_box);
+
+        let ok_click = ButtonMessage::click(ok, MessageDirection::FromWidget);
+        let cancel_click = ButtonMessage::click(cancel, MessageDirection::FromWidget);
+
+        let messages = press_key(&mut ui, KeyCode::Enter);
+        assert!(messages.contains(&ok_click));
+        assert!(!messages.contains(&cancel_click));
+
+        let messages = press_key(&mut ui, KeyCode::Escape);
+        assert!(messages.contains(&cancel_click));
+        assert!(!messages.contains(&ok_click));
+
+        // Disabled default button must not be clicked.
+        ui.send_message(WidgetMessage::enabled(
+            ok,
+            MessageDirection::ToWidget,
+            false,
+        ));
+        while ui.poll_message().is_some() {}
+        let messages = press_key(&mut ui, KeyCode::Enter);
+        assert!(!messages.contains(&ok_click));
+    }
+
+    #[test]
+    fn test_keyboard_activation() {
+        let screen_size = Vector2::new(1000.0, 1000.0);
+        let mut ui = UserInterface::new(screen_size);
+
+        let ctx = &mut ui.build_ctx();
+        let button = ButtonBuilder::new(WidgetBuilder::new()).build(ctx);
+        let check_box = CheckBoxBuilder::new(WidgetBuilder::new())
+            .checked(Some(false))
+            .build(ctx);
+
+        ui.update(screen_size, 0.0);
+
+        let click = ButtonMessage::click(button, MessageDirection::FromWidget);
+        focus(&mut ui, button);
+        assert!(press_key(&mut ui, KeyCode::Enter).contains(&click));
+        assert!(press_key(&mut ui, KeyCode::Space).contains(&click));
+
+        focus(&mut ui, check_box);
+        assert!(
+            press_key(&mut ui, KeyCode::Space).contains(&CheckBoxMessage::checked(
+                check_box,
+                MessageDirection::FromWidget,
+                Some(true)
+            ))
+        );
+        assert!(
+            press_key(&mut ui, KeyCode::Space).contains(&CheckBoxMessage::checked(
+                check_box,
+                MessageDirection::FromWidget,
+                Some(false)
+            ))
+        );
+    }
 }
@@ -1352,8 +1352,17 @@ impl Control for TextBox {
                         }
 
                         // TextBox "eats" all input by default, some of the keys are used for input control while
-                        // others are used directly to enter text.
-                        message.set_handled(true);
+                        // others are used directly to enter text. Keys, that are used for keyboard navigation
+                        // are passed further (vertical arrows are used for navigation by single-line text
+                        // boxes only).
+                        let navigation = match code {
+                            KeyCode::Tab | KeyCode::Escape => true,
+                            KeyCode::ArrowUp | KeyCode::ArrowDown => !self.multiline,
+                            _ => false,
+                        };
+                        if !navigation {
+                            message.set_handled(true);
+                        }
                     }
                     WidgetMessage::Focus => {
                         if message.direction() == MessageDirection::FromWidget {
@@ -1727,6 +1736,9 @@ impl TextBoxBuilder {
         if self.widget_builder.cursor.is_none() {
             self.widget_builder.cursor = Some(CursorIcon::Text);
         }
+        if self.widget_builder.tab_stop.is_none() {
+            self.widget_builder.tab_stop = Some(true);
+        }
 
         let text_box = TextBox {
             widget: self.widget_builder.build(),
@@ -625,6 +625,9 @@ pub struct Widget {
     pub context_menu: Option<RcUiNodeHandle>,
     /// A flag, that defines whether the widget should be clipped by the parent bounds or not.
     pub clip_to_bounds: bool,
+    /// A flag, that defines whether the widget can receive keyboard focus by pressing Tab key or
+    /// not. See [`crate::UserInterface::move_focus`] for more info.
+    pub tab_stop: bool,
     /// Current render transform of the node. It modifies layout information of the widget, as well as it affects visual transform
     /// of the widget.
     pub layout_transform: Matrix3<f32>,
@@ -1341,6 +1344,12 @@ impl Widget {
         self.enabled
     }
 
+    /// Returns `true` if the widget can be focused by Tab key.
+    #[inline]
+    pub fn is_tab_stop(&self) -> bool {
+        self.tab_stop
+    }
+
     /// Sets new cursor of the widget.
     #[inline]
     pub fn set_cursor(&mut self, cursor: Option<CursorIcon>) {
@@ -1505,6 +1514,9 @@ pub struct WidgetBuilder {
     pub render_transform: Matrix3<f32>,
     /// Whether the widget bounds should be clipped by its parent or not.
     pub clip_to_bounds: bool,
+    /// Whether the widget can be focused by Tab key or not. [`None`] means that the widget itself
+    /// decides it (for example, buttons and text boxes are tab stops by default).
+    pub tab_stop: Option<bool>,
     /// Unique id of the widget.
     pub id: Uuid,
 }
@@ -1551,6 +1563,7 @@ impl WidgetBuilder {
             layout_transform: Matrix3::identity(),
             render_transform: Matrix3::identity(),
             clip_to_bounds: true,
+            tab_stop: None,
             id: Uuid::new_v4(),
         }
     }
@@ -1587,6 +1600,19 @@ impl WidgetBuilder {
         self
     }
 
+    /// Defines whether the widget can be focused by Tab key or not.
+    pub fn with_tab_stop(mut self, tab_stop: bool) -> Self {
+        self.tab_stop = Some(tab_stop);
+        self
+    }
+
+    /// Makes the widget a tab stop, unless it was explicitly defined by [`Self::with_tab_stop`].
+    /// This method is meant to be used by builders of interactive widgets.
+    pub fn with_default_tab_stop(mut self) -> Self {
+        self.tab_stop.get_or_insert(true);
+        self
+    }
+
     /// Enables or disables the widget.
     pub fn with_enabled(mut self, enabled: bool) -> Self {
         self.enabled = enabled;
@@ -1836,6 +1862,7 @@ impl WidgetBuilder {
             render_transform: self.render_transform,
             visual_transform: Matrix3::identity(),
             clip_to_bounds: self.clip_to_bounds,
+            tab_stop: self.tab_stop.unwrap_or_default(),
             id: self.id,
         }
     }
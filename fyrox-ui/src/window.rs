@@ -9,7 +9,7 @@ use crate::{
     decorator::DecoratorBuilder,
     define_constructor,
     grid::{Column, GridBuilder, Row},
-    message::{CursorIcon, MessageDirection, UiMessage},
+    message::{CursorIcon, KeyCode, MessageDirection, UiMessage},
     text::{Text, TextBuilder, TextMessage},
     vector_image::{Primitive, VectorImageBuilder},
     widget::{Widget, WidgetBuilder, WidgetMessage},
@@ -238,6 +238,10 @@ pub struct Window {
     pub maximize_button: Handle<UiNode>,
     /// Handle of a close button.
     pub close_button: Handle<UiNode>,
+    /// Handle of a button, that will be clicked when Enter key is pressed inside the window.
+    pub default_button: Handle<UiNode>,
+    /// Handle of a button, that will be clicked when Escape key is pressed inside the window.
+    pub cancel_button: Handle<UiNode>,
     /// A distance per each axis when the dragging starts.
     pub drag_delta: Vector2<f32>,
     /// Handle of a current content.
@@ -319,6 +323,8 @@ impl Control for Window {
         node_map.resolve(&mut self.minimize_button);
         node_map.resolve(&mut self.maximize_button);
         node_map.resolve(&mut self.close_button);
+        node_map.resolve(&mut self.default_button);
+        node_map.resolve(&mut self.cancel_button);
         node_map.resolve(&mut self.title);
         node_map.resolve(&mut self.title_grid);
         node_map.resolve(&mut self.content);
@@ -511,6 +517,22 @@ impl Control for Window {
                     self.initial_position = self.screen_position();
                 }
             }
+            if let WidgetMessage::KeyDown(key) = msg {
+                if !message.handled() {
+                    let button = match key {
+                        KeyCode::Enter | KeyCode::NumpadEnter => self.default_button,
+                        KeyCode::Escape => self.cancel_button,
+                        _ => Handle::NONE,
+                    };
+                    if ui
+                        .try_get_node(button)
+                        .map_or(false, |button| button.enabled())
+                    {
+                        ui.send_message(ButtonMessage::click(button, MessageDirection::FromWidget));
+                        message.set_handled(true);
+                    }
+                }
+            }
         } else if let Some(ButtonMessage::Click) = message.data::<ButtonMessage>() {
             if message.destination() == self.minimize_button {
                 ui.send_message(WindowMessage::minimize(
@@ -796,6 +818,10 @@ pub struct WindowBuilder {
     pub open: bool,
     /// Optional custom closing button, if not specified, then a default button will be created.
     pub close_button: Option<Handle<UiNode>>,
+    /// Button, that will be clicked when Enter key is pressed inside the window.
+    pub default_button: Handle<UiNode>,
+    /// Button, that will be clicked when Escape key is pressed inside the window.
+    pub cancel_button: Handle<UiNode>,
     /// Optional custom minimization button, if not specified, then a default button will be created.
     pub minimize_button: Option<Handle<UiNode>>,
     /// Optional custom maximization button, if not specified, then a default button will be created.
@@ -924,19 +950,22 @@ fn make_mark(ctx: &mut BuildContext, button: HeaderButton) -> Handle<UiNode> {
 }
 
 fn make_header_button(ctx: &mut BuildContext, button: HeaderButton) -> Handle<UiNode> {
-    ButtonBuilder::new(WidgetBuilder::new().with_margin(Thickness::uniform(2.0)))
-        .with_back(
-            DecoratorBuilder::new(
-                BorderBuilder::new(WidgetBuilder::new())
-                    .with_stroke_thickness(Thickness::uniform(0.0)),
-            )
-            .with_normal_brush(Brush::Solid(Color::TRANSPARENT))
-            .with_hover_brush(BRUSH_LIGHT)
-            .with_pressed_brush(BRUSH_LIGHTEST)
-            .build(ctx),
+    ButtonBuilder::new(
+        WidgetBuilder::new()
+            .with_margin(Thickness::uniform(2.0))
+            .with_tab_stop(false),
+    )
+    .with_back(
+        DecoratorBuilder::new(
+            BorderBuilder::new(WidgetBuilder::new()).with_stroke_thickness(Thickness::uniform(0.0)),
         )
-        .with_content(make_mark(ctx, button))
-        .build(ctx)
+        .with_normal_brush(Brush::Solid(Color::TRANSPARENT))
+        .with_hover_brush(BRUSH_LIGHT)
+        .with_pressed_brush(BRUSH_LIGHTEST)
+        .build(ctx),
+    )
+    .with_content(make_mark(ctx, button))
+    .build(ctx)
 }
 
 impl WindowBuilder {
@@ -951,6 +980,8 @@ impl WindowBuilder {
             can_maximize: true,
             open: true,
             close_button: None,
+            default_button: Handle::NONE,
+            cancel_button: Handle::NONE,
             minimize_button: None,
             maximize_button: None,
             modal: false,
@@ -989,6 +1020,20 @@ impl WindowBuilder {
         self
     }
 
+    /// Sets a button, that will be clicked when Enter key is pressed while keyboard focus is
+    /// inside the window (usually it is "OK" button of a dialog).
+    pub fn with_default_button(mut self, button: Handle<UiNode>) -> Self {
+        self.default_button = button;
+        self
+    }
+
+    /// Sets a button, that will be clicked when Escape key is pressed while keyboard focus is
+    /// inside the window (usually it is "Cancel" button of a dialog).
+    pub fn with_cancel_button(mut self, button: Handle<UiNode>) -> Self {
+        self.cancel_button = button;
+        self
+    }
+
     /// Sets whether the window can be closed or not.
     pub fn can_close(mut self, can_close: bool) -> Self {
         self.can_close = can_close;
@@ -1144,6 +1189,8 @@ impl WindowBuilder {
             minimize_button,
             maximize_button,
             close_button,
+            default_button: self.default_button,
+            cancel_button: self.cancel_button,
             drag_delta: Default::default(),
             content: self.content,
             safe_border_size: self.safe_border_size,
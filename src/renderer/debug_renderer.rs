@@ -98,7 +98,11 @@ impl DebugRenderer {
         self.line_indices.clear();
 
         let mut i = 0;
-        for line in drawing_context.lines.iter() {
+        for line in drawing_context
+            .lines
+            .iter()
+            .chain(drawing_context.overlay_lines.iter())
+        {
             let color = line.color.into();
             self.vertices.push(Vertex {
                 position: line.begin,
@@ -114,28 +118,42 @@ impl DebugRenderer {
         self.geometry.set_buffer_data(state, 0, &self.vertices);
         self.geometry.bind(state).set_lines(&self.line_indices);
 
-        statistics += framebuffer.draw(
-            &self.geometry,
-            state,
-            viewport,
-            &self.shader.program,
-            &DrawParameters {
-                cull_face: None,
-                color_write: Default::default(),
-                depth_write: false,
-                stencil_test: None,
-                depth_test: true,
-                blend: None,
-                stencil_op: Default::default(),
-            },
-            ElementRange::Full,
-            |mut program_binding| {
-                program_binding
-                    .set_matrix4(&self.shader.wvp_matrix, &camera.view_projection_matrix());
-            },
-        )?;
+        // Regular lines are drawn first with depth test, then overlay lines are drawn on top.
+        for (depth_test, offset, count) in [
+            (true, 0, drawing_context.lines.len()),
+            (
+                false,
+                drawing_context.lines.len(),
+                drawing_context.overlay_lines.len(),
+            ),
+        ] {
+            if count == 0 {
+                continue;
+            }
 
-        statistics.draw_calls += 1;
+            statistics += framebuffer.draw(
+                &self.geometry,
+                state,
+                viewport,
+                &self.shader.program,
+                &DrawParameters {
+                    cull_face: None,
+                    color_write: Default::default(),
+                    depth_write: false,
+                    stencil_test: None,
+                    depth_test,
+                    blend: None,
+                    stencil_op: Default::default(),
+                },
+                ElementRange::Specific { offset, count },
+                |mut program_binding| {
+                    program_binding
+                        .set_matrix4(&self.shader.wvp_matrix, &camera.view_projection_matrix());
+                },
+            )?;
+
+            statistics.draw_calls += 1;
+        }
 
         Ok(statistics)
     }
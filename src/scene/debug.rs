@@ -68,6 +68,8 @@ pub struct Line {
 pub struct SceneDrawingContext {
     /// List of lines to draw.
     pub lines: Vec<Line>,
+    /// List of lines, that will be drawn on top of everything else (without depth test).
+    pub overlay_lines: Vec<Line>,
 }
 
 impl rapier2d::pipeline::DebugRenderBackend for SceneDrawingContext {
@@ -1068,9 +1070,15 @@ impl SceneDrawingContext {
         self.lines.push(line);
     }
 
-    /// Removes all lines from internal buffer. For dynamic drawing you should call it
-    /// every update tick of your application.
+    /// Adds single line into internal buffer. The line will be visible through any other geometry.
+    pub fn add_overlay_line(&mut self, line: Line) {
+        self.overlay_lines.push(line);
+    }
+
+    /// Removes all lines (including overlay lines) from internal buffer. For dynamic drawing you
+    /// should call it every update tick of your application.
     pub fn clear_lines(&mut self) {
-        self.lines.clear()
+        self.lines.clear();
+        self.overlay_lines.clear();
     }
 }
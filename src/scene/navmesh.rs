@@ -3,6 +3,7 @@
 
 use crate::{
     core::{
+        algebra::Vector3,
         color::Color,
         math::aabb::AxisAlignedBoundingBox,
         pool::Handle,
@@ -22,6 +23,19 @@ use crate::{
 };
 use std::ops::{Deref, DerefMut};
 
+/// Off-mesh link is a connection between two points of a navigational mesh, that can't be expressed
+/// with triangles - a jump over a gap, a drop off a ledge, a teleport and so on. Links are not used
+/// by the path finder, it is up to game code to decide when and how an agent should traverse them.
+#[derive(Debug, Clone, PartialEq, Default, Visit, Reflect)]
+pub struct NavmeshLink {
+    /// Start point of the link, in the same coordinate system as the vertices of the navmesh.
+    pub begin: Vector3<f32>,
+    /// End point of the link, in the same coordinate system as the vertices of the navmesh.
+    pub end: Vector3<f32>,
+    /// Whether the link could be traversed from the end to the start as well.
+    pub bidirectional: bool,
+}
+
 /// Navigational mesh (navmesh for short) is a surface which can be used for path finding. Unlike [A* Pathfinder](crate::utils::astar),
 /// it can build arbitrary paths on a surface of large polygons, making a path from point A to point B linear (standard pathfinder builds
 /// path only from vertex to vertex). Navmeshes should be used when you have an arbitrary "walkable" surface, for example, a game level
@@ -108,11 +122,20 @@ use std::ops::{Deref, DerefMut};
 ///     scene.graph[handle].as_navigational_mesh_mut()
 /// }
 /// ```
+///
+/// ## Off-mesh links
+///
+/// Navigational mesh could also store a set of [`NavmeshLink`]s, which connect points of the mesh, that
+/// can't be connected with triangles. Use [`NavigationalMesh::links`] to decide whether an agent should
+/// jump (or teleport, climb, etc.) somewhere instead of walking.
 #[derive(Debug, Clone, Visit, Reflect, Default)]
 pub struct NavigationalMesh {
     base: Base,
     #[reflect(read_only)]
     navmesh: InheritableVariable<Navmesh>,
+    #[reflect(read_only)]
+    #[visit(optional)]
+    links: InheritableVariable<Vec<NavmeshLink>>,
 }
 
 impl TypeUuidProvider for NavigationalMesh {
@@ -164,6 +187,14 @@ impl NodeTrait for NavigationalMesh {
                 });
             }
         }
+
+        for link in self.links.iter() {
+            ctx.add_line(Line {
+                begin: link.begin,
+                end: link.end,
+                color: Color::opaque(0, 200, 255),
+            });
+        }
     }
 }
 
@@ -177,12 +208,23 @@ impl NavigationalMesh {
     pub fn navmesh_mut(&mut self) -> &mut Navmesh {
         &mut self.navmesh
     }
+
+    /// Returns off-mesh links of the navigational mesh.
+    pub fn links(&self) -> &[NavmeshLink] {
+        &self.links
+    }
+
+    /// Returns a reference to the off-mesh links of the navigational mesh.
+    pub fn links_mut(&mut self) -> &mut Vec<NavmeshLink> {
+        &mut self.links
+    }
 }
 
 /// Creates navigational meshes and adds them to a scene graph.
 pub struct NavigationalMeshBuilder {
     base_builder: BaseBuilder,
     navmesh: Navmesh,
+    links: Vec<NavmeshLink>,
 }
 
 impl NavigationalMeshBuilder {
@@ -191,6 +233,7 @@ impl NavigationalMeshBuilder {
         Self {
             base_builder,
             navmesh: Default::default(),
+            links: Default::default(),
         }
     }
 
@@ -200,10 +243,17 @@ impl NavigationalMeshBuilder {
         self
     }
 
+    /// Sets off-mesh links of the navigational mesh.
+    pub fn with_links(mut self, links: Vec<NavmeshLink>) -> Self {
+        self.links = links;
+        self
+    }
+
     fn build_navigational_mesh(self) -> NavigationalMesh {
         NavigationalMesh {
             base: self.base_builder.build_base(),
             navmesh: self.navmesh.into(),
+            links: self.links.into(),
         }
     }
 
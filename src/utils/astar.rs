@@ -11,6 +11,7 @@ use crate::core::{
     math::{self, PositionProvider},
     visitor::prelude::*,
 };
+use fxhash::FxHashSet;
 use std::fmt::{Display, Formatter};
 
 #[derive(Copy, Clone, PartialEq, Eq, Debug)]
@@ -92,6 +93,8 @@ impl PathVertex {
 #[derive(Clone, Debug, Visit, PartialEq)]
 pub struct PathFinder {
     vertices: Vec<PathVertex>,
+    #[visit(skip)]
+    blocked_links: FxHashSet<(u32, u32)>,
 }
 
 /// Shows path status.
@@ -160,6 +163,7 @@ impl PathFinder {
     pub fn new() -> Self {
         Self {
             vertices: Default::default(),
+            blocked_links: Default::default(),
         }
     }
 
@@ -197,6 +201,27 @@ impl PathFinder {
         }
     }
 
+    /// Blocks or unblocks the link from vertex `a` to vertex `b`. Blocked links are kept in the graph,
+    /// but they're ignored by the path search. This allows you to temporarily close some parts of the
+    /// graph without rebuilding it.
+    pub fn set_link_blocked(&mut self, a: usize, b: usize, blocked: bool) {
+        if blocked {
+            self.blocked_links.insert((a as u32, b as u32));
+        } else {
+            self.blocked_links.remove(&(a as u32, b as u32));
+        }
+    }
+
+    /// Returns `true` if the link from vertex `a` to vertex `b` is blocked.
+    pub fn is_link_blocked(&self, a: usize, b: usize) -> bool {
+        self.blocked_links.contains(&(a as u32, b as u32))
+    }
+
+    /// Unblocks every link in the graph.
+    pub fn unblock_all_links(&mut self) {
+        self.blocked_links.clear();
+    }
+
     /// Returns shared reference to a path vertex at the given index.
     pub fn vertex(&self, index: usize) -> Option<&PathVertex> {
         self.vertices.get(index)
@@ -259,7 +284,20 @@ impl PathFinder {
             }
         }
 
-        self.vertices.remove(index)
+        let index = index as u32;
+        self.blocked_links = self
+            .blocked_links
+            .iter()
+            .filter(|(a, b)| *a != index && *b != index)
+            .map(|&(a, b)| {
+                (
+                    if a > index { a - 1 } else { a },
+                    if b > index { b - 1 } else { b },
+                )
+            })
+            .collect();
+
+        self.vertices.remove(index as usize)
     }
 
     /// Inserts the vertex at the given index. Automatically shifts neighbour indices of every other vertex
@@ -275,6 +313,17 @@ impl PathFinder {
                 }
             }
         }
+
+        self.blocked_links = self
+            .blocked_links
+            .iter()
+            .map(|&(a, b)| {
+                (
+                    if a >= index { a + 1 } else { a },
+                    if b >= index { b + 1 } else { b },
+                )
+            })
+            .collect();
     }
 
     /// Tries to build path from begin point to end point. Returns path kind:
@@ -375,6 +424,13 @@ impl PathFinder {
                     return Err(PathError::CyclicReferenceFound(current_index));
                 }
 
+                if self
+                    .blocked_links
+                    .contains(&(current_index as u32, *neighbour_index))
+                {
+                    continue;
+                }
+
                 // Safely get mutable reference to neighbour
                 let neighbour = unsafe_vertices
                     .get_mut(*neighbour_index as usize)
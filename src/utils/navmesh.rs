@@ -32,13 +32,18 @@ use fxhash::FxHashSet;
 pub struct Navmesh {
     octree: Octree,
     triangles: Vec<TriangleDefinition>,
+    disabled_triangles: Vec<bool>,
+    triangle_areas: Vec<u32>,
     pathfinder: PathFinder,
     query_buffer: Vec<u32>,
 }
 
 impl PartialEq for Navmesh {
     fn eq(&self, other: &Self) -> bool {
-        self.triangles == other.triangles && self.pathfinder == other.pathfinder
+        self.triangles == other.triangles
+            && self.disabled_triangles == other.disabled_triangles
+            && self.triangle_areas == other.triangle_areas
+            && self.pathfinder == other.pathfinder
     }
 }
 
@@ -48,6 +53,11 @@ impl Visit for Navmesh {
 
         self.pathfinder.visit("PathFinder", &mut region)?;
         self.triangles.visit("Triangles", &mut region)?;
+        // Backward compatibility.
+        let _ = self
+            .disabled_triangles
+            .visit("DisabledTriangles", &mut region);
+        let _ = self.triangle_areas.visit("TriangleAreas", &mut region);
 
         drop(region);
 
@@ -67,6 +77,10 @@ impl Visit for Navmesh {
                 .collect::<Vec<[Vector3<f32>; 3]>>();
 
             self.octree = Octree::new(&raw_triangles, 32);
+
+            self.disabled_triangles.resize(self.triangles.len(), false);
+            self.triangle_areas.resize(self.triangles.len(), 0);
+            self.update_blocked_links();
         }
 
         Ok(())
@@ -107,6 +121,8 @@ impl Navmesh {
 
         Self {
             triangles: triangles.to_vec(),
+            disabled_triangles: vec![false; triangles.len()],
+            triangle_areas: vec![0; triangles.len()],
             octree: Octree::new(&raw_triangles, 32),
             pathfinder,
             query_buffer: Default::default(),
@@ -220,13 +236,31 @@ impl Navmesh {
                 .link_bidirect(edge.a as usize, edge.b as usize);
         }
         self.triangles.push(triangle);
+        self.disabled_triangles.push(false);
+        self.triangle_areas.push(0);
+        self.update_blocked_links();
         index as u32
     }
 
+    /// Inserts the triangle at the given index, triangles after it are shifted. Vertex indices in the triangle
+    /// must be valid!
+    pub fn insert_triangle(&mut self, index: usize, triangle: TriangleDefinition) {
+        for edge in triangle.edges() {
+            self.pathfinder
+                .link_bidirect(edge.a as usize, edge.b as usize);
+        }
+        self.triangles.insert(index, triangle);
+        self.disabled_triangles.insert(index, false);
+        self.triangle_areas.insert(index, 0);
+        self.update_blocked_links();
+    }
+
     /// Removes a triangle at the given index from the navigational mesh. Automatically fixes vertex links in the
     /// internal navigational graph.
     pub fn remove_triangle(&mut self, index: usize) -> TriangleDefinition {
         let triangle = self.triangles.remove(index);
+        self.disabled_triangles.remove(index);
+        self.triangle_areas.remove(index);
         for &vertex_index in triangle.indices() {
             let mut isolated = true;
             for other_triangle in self.triangles.iter() {
@@ -257,9 +291,103 @@ impl Navmesh {
                 }
             }
         }
+        self.update_blocked_links();
         triangle
     }
 
+    /// Enables or disables a triangle at the given index. Disabled triangles stay in the navigational
+    /// mesh, but paths will never go through them. It could be used to close some areas of the mesh
+    /// at runtime (for example, when a bridge collapses) without rebuilding the whole navmesh.
+    ///
+    /// # Notes
+    ///
+    /// An edge is closed only if every triangle, that shares it, is disabled. This means that
+    /// boundary edges between enabled and disabled areas are still passable.
+    pub fn set_triangle_enabled(&mut self, index: usize, enabled: bool) {
+        if let Some(disabled) = self.disabled_triangles.get_mut(index) {
+            if *disabled == enabled {
+                *disabled = !enabled;
+                self.update_blocked_links();
+            }
+        }
+    }
+
+    /// Returns `true` if the triangle at the given index is enabled, `false` - if it is disabled or
+    /// the index is out of bounds.
+    pub fn is_triangle_enabled(&self, index: usize) -> bool {
+        self.disabled_triangles
+            .get(index)
+            .map_or(false, |disabled| !*disabled)
+    }
+
+    /// Sets the area tag of the triangle at the given index. Tags group triangles into areas, which
+    /// could be enabled or disabled at once with [`Self::set_area_enabled`]. Every triangle belongs
+    /// to the area `0` by default.
+    pub fn set_triangle_area(&mut self, index: usize, area: u32) {
+        if let Some(triangle_area) = self.triangle_areas.get_mut(index) {
+            *triangle_area = area;
+        }
+    }
+
+    /// Returns the area tag of the triangle at the given index, `None` if the index is out of bounds.
+    pub fn triangle_area(&self, index: usize) -> Option<u32> {
+        self.triangle_areas.get(index).cloned()
+    }
+
+    /// Enables or disables every triangle, that belongs to the area with the given tag. See
+    /// [`Self::set_triangle_enabled`] for details.
+    pub fn set_area_enabled(&mut self, area: u32, enabled: bool) {
+        let mut changed = false;
+        for (disabled, _) in self
+            .disabled_triangles
+            .iter_mut()
+            .zip(self.triangle_areas.iter())
+            .filter(|(_, triangle_area)| **triangle_area == area)
+        {
+            if *disabled == enabled {
+                *disabled = !enabled;
+                changed = true;
+            }
+        }
+        if changed {
+            self.update_blocked_links();
+        }
+    }
+
+    fn update_blocked_links(&mut self) {
+        self.pathfinder.unblock_all_links();
+
+        if !self.disabled_triangles.contains(&true) {
+            return;
+        }
+
+        let mut open_edges = FxHashSet::default();
+        for (triangle, _) in self
+            .triangles
+            .iter()
+            .zip(self.disabled_triangles.iter())
+            .filter(|(_, disabled)| !**disabled)
+        {
+            open_edges.extend(triangle.edges());
+        }
+
+        for (triangle, _) in self
+            .triangles
+            .iter()
+            .zip(self.disabled_triangles.iter())
+            .filter(|(_, disabled)| **disabled)
+        {
+            for edge in triangle.edges() {
+                if !open_edges.contains(&edge) {
+                    self.pathfinder
+                        .set_link_blocked(edge.a as usize, edge.b as usize, true);
+                    self.pathfinder
+                        .set_link_blocked(edge.b as usize, edge.a as usize, true);
+                }
+            }
+        }
+    }
+
     /// Removes last triangle from the navigational mesh. Automatically fixes vertex links in the internal
     /// navigational graph.
     pub fn pop_triangle(&mut self) -> Option<TriangleDefinition> {
@@ -315,6 +443,25 @@ impl Navmesh {
         self.pathfinder.vertices_mut()
     }
 
+    /// Returns approximate amount of memory (in bytes) allocated for the triangles, the vertices
+    /// and their links. The octree is not included.
+    pub fn allocated_size(&self) -> usize {
+        fn vec_size<T>(vec: &Vec<T>) -> usize {
+            vec.capacity() * std::mem::size_of::<T>()
+        }
+
+        let vertices = self.pathfinder.vertices();
+        vec_size(&self.triangles)
+            + vec_size(&self.disabled_triangles)
+            + vec_size(&self.triangle_areas)
+            + vec_size(&self.query_buffer)
+            + vertices.len() * std::mem::size_of::<PathVertex>()
+            + vertices
+                .iter()
+                .map(|v| vec_size(&v.neighbours))
+                .sum::<usize>()
+    }
+
     /// Adds the vertex to the navigational mesh. The vertex will **not** be connected with any other vertex.
     pub fn add_vertex(&mut self, vertex: PathVertex) -> u32 {
         self.pathfinder.add_vertex(vertex)
@@ -528,7 +675,7 @@ impl NavmeshAgent {
         };
 
         if let (Some(from_triangle), Some(to_triangle)) = (from_triangle, to_triangle) {
-            if from_triangle == to_triangle {
+            if from_triangle == to_triangle && navmesh.is_triangle_enabled(from_triangle) {
                 self.path.push(from);
                 self.path.push(to);
 
@@ -767,7 +914,7 @@ impl NavmeshAgentBuilder {
 mod test {
     use crate::{
         core::{algebra::Vector3, math::TriangleDefinition},
-        utils::navmesh::Navmesh,
+        utils::{astar::PathKind, navmesh::Navmesh},
     };
 
     fn make_navmesh() -> Navmesh {
@@ -889,4 +1036,58 @@ mod test {
         assert_eq!(navmesh.triangles().len(), 0);
         assert_eq!(navmesh.vertices().len(), 0);
     }
+
+    #[test]
+    fn test_disabled_triangles() {
+        let mut navmesh = make_navmesh();
+        let mut path = Vec::new();
+
+        assert_eq!(navmesh.build_path(1, 4, &mut path).unwrap(), PathKind::Full);
+
+        // Edges 0-1 and 1-2 belong to A only, so vertex 1 becomes unreachable.
+        navmesh.set_triangle_enabled(0, false);
+        assert!(!navmesh.is_triangle_enabled(0));
+        assert_ne!(navmesh.build_path(1, 4, &mut path).unwrap(), PathKind::Full);
+        // Edge 0-2 is shared with B, so it is still passable.
+        assert_eq!(navmesh.build_path(0, 2, &mut path).unwrap(), PathKind::Full);
+
+        navmesh.set_triangle_enabled(0, true);
+        assert_eq!(navmesh.build_path(1, 4, &mut path).unwrap(), PathKind::Full);
+
+        // The mask must follow triangles when other triangles are removed.
+        navmesh.set_triangle_enabled(1, false);
+        navmesh.remove_triangle(0);
+        assert!(!navmesh.is_triangle_enabled(0));
+        assert!(navmesh.is_triangle_enabled(1));
+        assert!(navmesh.is_triangle_enabled(2));
+    }
+
+    #[test]
+    fn test_disabled_areas() {
+        let mut navmesh = make_navmesh();
+        let mut path = Vec::new();
+
+        // A and B form the area 1, C and D stay in the default area.
+        navmesh.set_triangle_area(0, 1);
+        navmesh.set_triangle_area(1, 1);
+        assert_eq!(navmesh.triangle_area(1), Some(1));
+        assert_eq!(navmesh.triangle_area(2), Some(0));
+        assert_eq!(navmesh.triangle_area(4), None);
+
+        navmesh.set_area_enabled(1, false);
+        assert!(!navmesh.is_triangle_enabled(0));
+        assert!(!navmesh.is_triangle_enabled(1));
+        assert!(navmesh.is_triangle_enabled(2));
+        assert_ne!(navmesh.build_path(1, 4, &mut path).unwrap(), PathKind::Full);
+        // Edge 0-2 is shared by A and B only, edges 0-3 and 2-3 are still open.
+        assert_eq!(navmesh.build_path(0, 2, &mut path).unwrap(), PathKind::Full);
+
+        navmesh.set_area_enabled(1, true);
+        assert_eq!(navmesh.build_path(1, 4, &mut path).unwrap(), PathKind::Full);
+
+        // Tags must follow triangles when other triangles are removed.
+        navmesh.remove_triangle(0);
+        assert_eq!(navmesh.triangle_area(0), Some(1));
+        assert_eq!(navmesh.triangle_area(1), Some(0));
+    }
 }